@@ -155,6 +155,26 @@ pub fn main() -> anyhow::Result<()> {
         libinput::LIBINPUT_CONFIG_SCROLL_BUTTON_LOCK_STATE,
         "libinput_config_scroll_button_lock_state",
     )?;
+    write_ty(
+        &mut f,
+        libinput::LIBINPUT_CONFIG_DWT_STATE,
+        "libinput_config_dwt_state",
+    )?;
+    write_ty(
+        &mut f,
+        libinput::LIBINPUT_CONFIG_DWTP_STATE,
+        "libinput_config_dwtp_state",
+    )?;
+    write_ty(
+        &mut f,
+        libinput::LIBINPUT_CONFIG_TAP_BUTTON_MAP,
+        "libinput_config_tap_button_map",
+    )?;
+    write_ty(
+        &mut f,
+        libinput::LIBINPUT_ACCEL_TYPE,
+        "libinput_config_accel_type",
+    )?;
 
     let mut f = open("pango_tys.rs")?;
     write_ty(&mut f, pango::CAIRO_FORMATS, "cairo_format_t")?;
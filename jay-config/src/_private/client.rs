@@ -33,18 +33,31 @@ use crate::client::ClientCriterion;
 use crate::client::ClientMatcher;
 use crate::client::MatchedClient;
 use crate::exec::Command;
+use crate::input::AccessibilityFeedback;
 use crate::input::FallbackOutputMode;
 use crate::input::FocusFollowsMouseMode;
 use crate::input::InputDevice;
 use crate::input::InputEventCode;
 use crate::input::LayerDirection;
+use crate::input::LayoutMemoryMode;
+use crate::input::ModifiedPointerTrigger;
 use crate::input::Seat;
 use crate::input::SwitchEvent;
 use crate::input::Timeline;
 use crate::input::acceleration::AccelProfile;
 use crate::input::capability::Capability;
 use crate::input::clickmethod::ClickMethod;
+use crate::input::gesture::Gesture;
+use crate::input::gesture::TouchGesture;
+use crate::input::key_remap::KeyRemap;
+use crate::input::mouse_keys::MouseKeys;
 use crate::input::scrollmethod::ScrollMethod;
+use crate::input::sendevents::SendEventsMode;
+use crate::input::tablet::EraserMode;
+use crate::input::tablet::PressureCurve;
+use crate::input::tablet::TabletPadEvent;
+use crate::input::tablet::TabletToolType;
+use crate::input::tapbuttonmap::TapButtonMap;
 use crate::keyboard::Group;
 use crate::keyboard::Keymap;
 use crate::keyboard::KeymapBuilder;
@@ -54,15 +67,18 @@ use crate::keyboard::syms::KeySym;
 use crate::logging::LogLevel;
 use crate::tasks::JoinHandle;
 use crate::tasks::JoinSlot;
+use crate::theme::BarElement;
 use crate::theme::BarPosition;
 use crate::theme::Color;
 use crate::theme::ContainerBorders;
 use crate::theme::colors::Colorable;
 use crate::theme::sized::Resizable;
 use crate::timer::Timer;
+use crate::video::AutoScale;
 use crate::video::BlendSpace;
 use crate::video::ColorSpace;
 use crate::video::Connector;
+use crate::video::ConnectorConfiguration;
 use crate::video::DrmDevice;
 use crate::video::Eotf;
 use crate::video::Format;
@@ -76,6 +92,7 @@ use crate::video::connector_type::CON_UNKNOWN;
 use crate::video::connector_type::ConnectorType;
 use crate::window::ContentType;
 use crate::window::MatchedWindow;
+use crate::window::ShortcutsInhibit;
 use crate::window::TileState;
 use crate::window::Window;
 use crate::window::WindowCriterion;
@@ -145,6 +162,9 @@ pub(crate) struct ConfigClient {
     srv_unref: unsafe extern "C" fn(data: *const u8),
     srv_handler: unsafe extern "C" fn(data: *const u8, msg: *const u8, size: usize),
     key_handlers: RefCell<HashMap<(Seat, ModifiedKeySym), KeyHandler>>,
+    pointer_handlers: RefCell<HashMap<(Seat, ModifiedPointerTrigger), Callback>>,
+    gesture_handlers: RefCell<HashMap<(Seat, Gesture), Callback>>,
+    touch_gesture_handlers: RefCell<HashMap<(Seat, TouchGesture), Callback>>,
     timer_handlers: RefCell<HashMap<Timer, Callback>>,
     response: RefCell<Vec<Response>>,
     on_new_seat: RefCell<Option<Callback<Seat>>>,
@@ -152,6 +172,7 @@ pub(crate) struct ConfigClient {
     on_input_device_removed: RefCell<Option<Callback<InputDevice>>>,
     on_connector_connected: RefCell<Option<Callback<Connector>>>,
     on_connector_disconnected: RefCell<Option<Callback<Connector>>>,
+    on_apply_output_profile: RefCell<Option<Callback<String>>>,
     on_graphics_initialized: Cell<Option<Box<dyn FnOnce()>>>,
     on_devices_enumerated: Cell<Option<Box<dyn FnOnce()>>>,
     on_new_connector: RefCell<Option<Callback<Connector>>>,
@@ -159,6 +180,8 @@ pub(crate) struct ConfigClient {
     on_del_drm_device: RefCell<Option<Callback<DrmDevice>>>,
     on_idle: RefCell<Option<Callback>>,
     on_switch_event: RefCell<HashMap<InputDevice, Callback<SwitchEvent>>>,
+    on_tablet_pad_event: RefCell<HashMap<InputDevice, Callback<TabletPadEvent>>>,
+    on_keyboard_accessibility_feedback: RefCell<HashMap<Seat, Callback<AccessibilityFeedback>>>,
     on_unload: Cell<Option<OnDrop<Box<dyn FnOnce()>>>>,
     on_locked: RefCell<Option<Callback<bool>>>,
     bufs: RefCell<Vec<Vec<u8>>>,
@@ -297,6 +320,9 @@ pub unsafe extern "C" fn init(
         srv_unref,
         srv_handler,
         key_handlers: Default::default(),
+        pointer_handlers: Default::default(),
+        gesture_handlers: Default::default(),
+        touch_gesture_handlers: Default::default(),
         timer_handlers: Default::default(),
         response: Default::default(),
         on_new_seat: Default::default(),
@@ -304,6 +330,7 @@ pub unsafe extern "C" fn init(
         on_input_device_removed: Default::default(),
         on_connector_connected: Default::default(),
         on_connector_disconnected: Default::default(),
+        on_apply_output_profile: Default::default(),
         on_graphics_initialized: Default::default(),
         on_devices_enumerated: Default::default(),
         on_new_connector: Default::default(),
@@ -311,6 +338,8 @@ pub unsafe extern "C" fn init(
         on_del_drm_device: Default::default(),
         on_idle: Default::default(),
         on_switch_event: Default::default(),
+        on_tablet_pad_event: Default::default(),
+        on_keyboard_accessibility_feedback: Default::default(),
         on_unload: Default::default(),
         on_locked: Default::default(),
         bufs: Default::default(),
@@ -861,6 +890,18 @@ impl ConfigClient {
         self.send(&ClientMessage::SetCursorSize { seat, size })
     }
 
+    pub fn seat_set_cursor_theme(&self, seat: Seat, theme: &str) {
+        self.send(&ClientMessage::SeatSetCursorTheme { seat, theme });
+    }
+
+    pub fn seat_set_hide_cursor_while_typing(&self, seat: Seat, hide: bool) {
+        self.send(&ClientMessage::SeatSetHideCursorWhileTyping { seat, hide });
+    }
+
+    pub fn seat_set_cursor_hide_timeout(&self, seat: Seat, usec: u64) {
+        self.send(&ClientMessage::SeatSetCursorHideTimeout { seat, usec });
+    }
+
     pub fn set_use_hardware_cursor(&self, seat: Seat, use_hardware_cursor: bool) {
         self.send(&ClientMessage::SetUseHardwareCursor {
             seat,
@@ -994,6 +1035,16 @@ impl ConfigClient {
             .insert(input_device, cb(f));
     }
 
+    pub fn on_tablet_pad_event<F: FnMut(TabletPadEvent) + 'static>(
+        &self,
+        input_device: InputDevice,
+        f: F,
+    ) {
+        self.on_tablet_pad_event
+            .borrow_mut()
+            .insert(input_device, cb(f));
+    }
+
     pub fn set_double_click_interval(&self, usec: u64) {
         self.send(&ClientMessage::SetDoubleClickIntervalUsec { usec });
     }
@@ -1020,6 +1071,29 @@ impl ConfigClient {
         self.send(&ClientMessage::ConnectorSetEnabled { connector, enabled });
     }
 
+    pub fn connector_set_dpms(&self, connector: Connector, on: bool) {
+        self.send(&ClientMessage::ConnectorSetDpms { connector, on });
+    }
+
+    pub fn connector_get_dpms(&self, connector: Connector) -> bool {
+        let res = self.send_with_response(&ClientMessage::ConnectorGetDpms { connector });
+        get_response!(res, true, ConnectorGetDpms { on });
+        on
+    }
+
+    pub fn configure_connectors(&self, configurations: &[(Connector, ConnectorConfiguration)]) {
+        self.send(&ClientMessage::ConfigureConnectors {
+            configurations: configurations.to_vec(),
+        });
+    }
+
+    pub fn set_output_profiles(&self, profiles: &[&str], active: Option<&str>) {
+        self.send(&ClientMessage::SetOutputProfiles {
+            profiles: profiles.to_vec(),
+            active,
+        });
+    }
+
     pub fn connector_set_transform(&self, connector: Connector, transform: Transform) {
         self.send(&ClientMessage::ConnectorSetTransform {
             connector,
@@ -1195,6 +1269,23 @@ impl ConfigClient {
         position
     }
 
+    pub fn set_bar_elements(&self, elements: &[BarElement]) {
+        self.send(&ClientMessage::SetBarElements {
+            elements: elements.to_vec(),
+        });
+    }
+
+    pub fn set_bar_element_separator(&self, separator: &str) {
+        self.send(&ClientMessage::SetBarElementSeparator { separator });
+    }
+
+    pub fn seat_set_input_mode_stack(&self, seat: Seat, modes: &[&str]) {
+        self.send(&ClientMessage::SeatSetInputModeStack {
+            seat,
+            modes: modes.to_vec(),
+        });
+    }
+
     pub fn set_egui_fonts(&self, proportional: Option<Vec<&str>>, monospace: Option<Vec<&str>>) {
         self.send(&ClientMessage::SetEguiFonts {
             proportional,
@@ -1240,6 +1331,115 @@ impl ConfigClient {
         self.send(&ClientMessage::SeatReloadSimpleIm { seat });
     }
 
+    pub fn seat_set_sticky_keys_enabled(&self, seat: Seat, enabled: bool) {
+        self.send(&ClientMessage::SeatSetStickyKeysEnabled { seat, enabled });
+    }
+
+    pub fn seat_get_sticky_keys_enabled(&self, seat: Seat) -> bool {
+        let res = self.send_with_response(&ClientMessage::SeatGetStickyKeysEnabled { seat });
+        get_response!(res, false, SeatGetStickyKeysEnabled { enabled });
+        enabled
+    }
+
+    pub fn seat_set_slow_keys_enabled(&self, seat: Seat, enabled: bool) {
+        self.send(&ClientMessage::SeatSetSlowKeysEnabled { seat, enabled });
+    }
+
+    pub fn seat_get_slow_keys_enabled(&self, seat: Seat) -> bool {
+        let res = self.send_with_response(&ClientMessage::SeatGetSlowKeysEnabled { seat });
+        get_response!(res, false, SeatGetSlowKeysEnabled { enabled });
+        enabled
+    }
+
+    pub fn seat_set_slow_keys_delay(&self, seat: Seat, ms: u32) {
+        self.send(&ClientMessage::SeatSetSlowKeysDelay { seat, ms });
+    }
+
+    pub fn seat_set_bounce_keys_enabled(&self, seat: Seat, enabled: bool) {
+        self.send(&ClientMessage::SeatSetBounceKeysEnabled { seat, enabled });
+    }
+
+    pub fn seat_get_bounce_keys_enabled(&self, seat: Seat) -> bool {
+        let res = self.send_with_response(&ClientMessage::SeatGetBounceKeysEnabled { seat });
+        get_response!(res, false, SeatGetBounceKeysEnabled { enabled });
+        enabled
+    }
+
+    pub fn seat_set_bounce_keys_delay(&self, seat: Seat, ms: u32) {
+        self.send(&ClientMessage::SeatSetBounceKeysDelay { seat, ms });
+    }
+
+    pub fn seat_set_mouse_keys_enabled(&self, seat: Seat, enabled: bool) {
+        self.send(&ClientMessage::SeatSetMouseKeysEnabled { seat, enabled });
+    }
+
+    pub fn seat_get_mouse_keys_enabled(&self, seat: Seat) -> bool {
+        let res = self.send_with_response(&ClientMessage::SeatGetMouseKeysEnabled { seat });
+        get_response!(res, false, SeatGetMouseKeysEnabled { enabled });
+        enabled
+    }
+
+    pub fn seat_set_mouse_keys(&self, seat: Seat, mouse_keys: &MouseKeys) {
+        self.send(&ClientMessage::SeatSetMouseKeys {
+            seat,
+            mouse_keys: mouse_keys.clone(),
+        });
+    }
+
+    pub fn seat_set_which_key_enabled(&self, seat: Seat, enabled: bool) {
+        self.send(&ClientMessage::SeatSetWhichKeyEnabled { seat, enabled });
+    }
+
+    pub fn seat_get_which_key_enabled(&self, seat: Seat) -> bool {
+        let res = self.send_with_response(&ClientMessage::SeatGetWhichKeyEnabled { seat });
+        get_response!(res, false, SeatGetWhichKeyEnabled { enabled });
+        enabled
+    }
+
+    pub fn seat_set_which_key_delay(&self, seat: Seat, ms: u32) {
+        self.send(&ClientMessage::SeatSetWhichKeyDelay { seat, ms });
+    }
+
+    pub fn seat_set_which_key(&self, seat: Seat, title: &str, entries: &[(&str, &str)]) {
+        self.send(&ClientMessage::SeatSetWhichKey {
+            seat,
+            title: title.to_string(),
+            entries: entries
+                .iter()
+                .map(|(k, d)| (k.to_string(), d.to_string()))
+                .collect(),
+        });
+    }
+
+    pub fn seat_start_macro_recording(&self, seat: Seat, name: &str, pointer: bool) {
+        self.send(&ClientMessage::SeatStartMacroRecording {
+            seat,
+            name: name.to_string(),
+            pointer,
+        });
+    }
+
+    pub fn seat_stop_macro_recording(&self, seat: Seat) {
+        self.send(&ClientMessage::SeatStopMacroRecording { seat });
+    }
+
+    pub fn seat_replay_macro(&self, seat: Seat, name: &str) {
+        self.send(&ClientMessage::SeatReplayMacro {
+            seat,
+            name: name.to_string(),
+        });
+    }
+
+    pub fn on_keyboard_accessibility_feedback<F: FnMut(AccessibilityFeedback) + 'static>(
+        &self,
+        seat: Seat,
+        f: F,
+    ) {
+        self.on_keyboard_accessibility_feedback
+            .borrow_mut()
+            .insert(seat, cb(f));
+    }
+
     pub fn seat_enable_unicode_input(&self, seat: Seat) {
         self.send(&ClientMessage::SeatEnableUnicodeInput { seat });
     }
@@ -1321,6 +1521,78 @@ impl ConfigClient {
         });
     }
 
+    pub fn connector_set_icc_profile(&self, connector: Connector, path: Option<&str>) {
+        self.send(&ClientMessage::ConnectorSetIccProfile { connector, path });
+    }
+
+    pub fn connector_set_cvt_mode(
+        &self,
+        connector: Connector,
+        width: i32,
+        height: i32,
+        refresh_millihz: u32,
+        reduced_blanking: bool,
+    ) {
+        self.send(&ClientMessage::ConnectorSetCvtMode {
+            connector,
+            width,
+            height,
+            refresh_millihz,
+            reduced_blanking,
+        });
+    }
+
+    pub fn connector_set_modeline(&self, connector: Connector, modeline: &str) {
+        self.send(&ClientMessage::ConnectorSetModeline {
+            connector,
+            modeline,
+        });
+    }
+
+    pub fn connector_set_edid_override(&self, connector: Connector, path: Option<&str>) {
+        self.send(&ClientMessage::ConnectorSetEdidOverride { connector, path });
+    }
+
+    pub fn set_auto_scale(&self, auto_scale: Option<AutoScale>) {
+        self.send(&ClientMessage::SetAutoScale { auto_scale });
+    }
+
+    pub fn connector_set_vnc_server(&self, connector: Connector, address: Option<&str>) {
+        self.send(&ClientMessage::ConnectorSetVncServer { connector, address });
+    }
+
+    pub fn connector_get_backlight(&self, connector: Connector) -> Option<f64> {
+        let res = self.send_with_response(&ClientMessage::ConnectorGetBacklight { connector });
+        get_response!(res, None, ConnectorGetBacklight { percent });
+        percent
+    }
+
+    pub fn connector_set_backlight(&self, connector: Connector, value: f64, relative: bool) {
+        self.send(&ClientMessage::ConnectorSetBacklight {
+            connector,
+            value,
+            relative,
+        });
+    }
+
+    pub fn set_backlight_indicator_enabled(&self, enabled: bool) {
+        self.send(&ClientMessage::SetBacklightIndicatorEnabled { enabled });
+    }
+
+    pub fn set_auto_rotation_enabled(&self, enabled: bool) {
+        self.send(&ClientMessage::SetAutoRotationEnabled { enabled });
+    }
+
+    pub fn set_rotation_locked(&self, locked: bool) {
+        self.send(&ClientMessage::SetRotationLocked { locked });
+    }
+
+    pub fn get_rotation_locked(&self) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetRotationLocked);
+        get_response!(res, false, GetRotationLocked { locked });
+        locked
+    }
+
     pub fn connector_set_scaling_filter(
         &self,
         connector: Connector,
@@ -1449,6 +1721,10 @@ impl ConfigClient {
         *self.on_connector_disconnected.borrow_mut() = Some(cb(f));
     }
 
+    pub fn on_apply_output_profile<F: FnMut(String) + 'static>(&self, f: F) {
+        *self.on_apply_output_profile.borrow_mut() = Some(cb(f));
+    }
+
     pub fn on_graphics_initialized<F: FnOnce() + 'static>(&self, f: F) {
         self.on_graphics_initialized.set(Some(Box::new(f)));
     }
@@ -1560,6 +1836,34 @@ impl ConfigClient {
         self.send(&ClientMessage::SetScrollButtonLock { device, enabled })
     }
 
+    pub fn set_input_dwt(&self, device: InputDevice, enabled: bool) {
+        self.send(&ClientMessage::SetInputDwt { device, enabled })
+    }
+
+    pub fn set_input_dwtp(&self, device: InputDevice, enabled: bool) {
+        self.send(&ClientMessage::SetInputDwtp { device, enabled })
+    }
+
+    pub fn set_input_tap_button_map(&self, device: InputDevice, map: TapButtonMap) {
+        self.send(&ClientMessage::SetInputTapButtonMap { device, map })
+    }
+
+    pub fn set_input_send_events_mode(&self, device: InputDevice, mode: SendEventsMode) {
+        self.send(&ClientMessage::SetInputSendEventsMode { device, mode })
+    }
+
+    pub fn set_input_rotation(&self, device: InputDevice, degrees: u32) {
+        self.send(&ClientMessage::SetInputRotation { device, degrees })
+    }
+
+    pub fn set_input_custom_accel(&self, device: InputDevice, step: f64, points: &[f64]) {
+        self.send(&ClientMessage::SetInputCustomAccel {
+            device,
+            step,
+            points: points.to_vec(),
+        })
+    }
+
     pub fn device_name(&self, device: InputDevice) -> String {
         let res = self.send_with_response(&ClientMessage::GetDeviceName { device });
         get_response!(res, String::new(), GetDeviceName { name });
@@ -1614,6 +1918,27 @@ impl ConfigClient {
         self.send(&ClientMessage::SetFallbackOutputMode { seat, mode })
     }
 
+    pub fn set_input_key_remap(&self, device: InputDevice, remap: &KeyRemap) {
+        self.send(&ClientMessage::SetInputKeyRemap {
+            device,
+            remap: remap.clone(),
+        })
+    }
+
+    pub fn set_layout_memory_mode(&self, seat: Seat, mode: LayoutMemoryMode) {
+        self.send(&ClientMessage::SetLayoutMemoryMode { seat, mode })
+    }
+
+    pub fn seat_get_keyboard_group(&self, seat: Seat) -> u32 {
+        let res = self.send_with_response(&ClientMessage::SeatGetKeyboardGroup { seat });
+        get_response!(res, 0, GetKeyboardGroup { group });
+        group
+    }
+
+    pub fn seat_set_keyboard_group(&self, seat: Seat, group: u32) {
+        self.send(&ClientMessage::SeatSetKeyboardGroup { seat, group })
+    }
+
     pub fn set_window_management_enabled(&self, seat: Seat, enabled: bool) {
         self.send(&ClientMessage::SetWindowManagementEnabled { seat, enabled })
     }
@@ -1629,6 +1954,72 @@ impl ConfigClient {
         self.send(&ClientMessage::RemoveInputMapping { input_device })
     }
 
+    pub fn map_input_device_to_window(&self, input_device: InputDevice, window: Window) {
+        self.send(&ClientMessage::MapInputDeviceToWindow {
+            input_device,
+            window,
+        })
+    }
+
+    pub fn unmap_input_device_from_window(&self, input_device: InputDevice) {
+        self.send(&ClientMessage::UnmapInputDeviceFromWindow { input_device })
+    }
+
+    pub fn set_input_tablet_region(
+        &self,
+        input_device: InputDevice,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    ) {
+        self.send(&ClientMessage::SetInputTabletRegion {
+            input_device,
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    pub fn set_input_tablet_area(
+        &self,
+        input_device: InputDevice,
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+    ) {
+        self.send(&ClientMessage::SetInputTabletArea {
+            input_device,
+            x1,
+            y1,
+            x2,
+            y2,
+        })
+    }
+
+    pub fn set_input_tablet_keep_aspect_ratio(&self, input_device: InputDevice, keep: bool) {
+        self.send(&ClientMessage::SetInputTabletKeepAspectRatio { input_device, keep })
+    }
+
+    pub fn set_input_tablet_pressure_curve(
+        &self,
+        input_device: InputDevice,
+        tool: TabletToolType,
+        curve: PressureCurve,
+    ) {
+        self.send(&ClientMessage::SetInputTabletPressureCurve {
+            input_device,
+            tool,
+            curve,
+        })
+    }
+
+    pub fn set_input_tablet_eraser_mode(&self, input_device: InputDevice, mode: EraserMode) {
+        self.send(&ClientMessage::SetInputTabletEraserMode { input_device, mode })
+    }
+
     pub fn parse_keymap(&self, keymap: &str) -> Keymap {
         let res = self.send_with_response(&ClientMessage::ParseKeymap { keymap });
         get_response!(res, Keymap(0), ParseKeymap { keymap });
@@ -1811,6 +2202,93 @@ impl ConfigClient {
         }
     }
 
+    pub fn bind_pointer<F: FnMut() + 'static>(
+        &self,
+        seat: Seat,
+        trigger: ModifiedPointerTrigger,
+        mut f: F,
+    ) {
+        let prev = self
+            .pointer_handlers
+            .borrow_mut()
+            .insert((seat, trigger), cb(move |_| f()));
+        if prev.is_none() {
+            self.send(&ClientMessage::AddPointerShortcut { seat, trigger });
+        }
+    }
+
+    pub fn unbind_pointer(&self, seat: Seat, trigger: ModifiedPointerTrigger) {
+        if self
+            .pointer_handlers
+            .borrow_mut()
+            .remove(&(seat, trigger))
+            .is_some()
+        {
+            self.send(&ClientMessage::RemovePointerShortcut { seat, trigger });
+        }
+    }
+
+    pub fn bind_gesture<F: FnMut() + 'static>(&self, seat: Seat, gesture: Gesture, mut f: F) {
+        self.gesture_handlers
+            .borrow_mut()
+            .insert((seat, gesture), cb(move |_| f()));
+        self.send(&ClientMessage::AddGestureBinding { seat, gesture });
+    }
+
+    pub fn unbind_gesture(&self, seat: Seat, gesture: Gesture) {
+        if self
+            .gesture_handlers
+            .borrow_mut()
+            .remove(&(seat, gesture))
+            .is_some()
+        {
+            self.send(&ClientMessage::RemoveGestureBinding { seat, gesture });
+        }
+    }
+
+    pub fn set_gesture_continuous(&self, seat: Seat, gesture: Gesture, continuous: bool) {
+        self.send(&ClientMessage::SetGestureContinuous {
+            seat,
+            gesture,
+            continuous,
+        });
+    }
+
+    pub fn set_gesture_swipe_distance(&self, seat: Seat, distance: f64) {
+        self.send(&ClientMessage::SetGestureSwipeDistance { seat, distance });
+    }
+
+    pub fn bind_touch_gesture<F: FnMut() + 'static>(
+        &self,
+        seat: Seat,
+        gesture: TouchGesture,
+        mut f: F,
+    ) {
+        self.touch_gesture_handlers
+            .borrow_mut()
+            .insert((seat, gesture), cb(move |_| f()));
+        self.send(&ClientMessage::AddTouchGestureBinding { seat, gesture });
+    }
+
+    pub fn unbind_touch_gesture(&self, seat: Seat, gesture: TouchGesture) {
+        if self
+            .touch_gesture_handlers
+            .borrow_mut()
+            .remove(&(seat, gesture))
+            .is_some()
+        {
+            self.send(&ClientMessage::RemoveTouchGestureBinding { seat, gesture });
+        }
+    }
+
+    pub fn set_touch_long_press_drag(&self, seat: Seat, enabled: bool) {
+        self.send(&ClientMessage::SetTouchLongPressDrag { seat, enabled });
+    }
+
+    pub fn connector_set_touch_edge_width(&self, connector: Connector, width: i32) {
+        self.send(&ClientMessage::ConnectorSetTouchEdgeWidth { connector, width });
+    }
+
     pub fn set_repeat_bind(&self, seat: Seat, mod_sym: ModifiedKeySym, repeat: bool) {
         self.send(&ClientMessage::SetRepeatShortcut {
             seat,
@@ -2207,6 +2685,14 @@ impl ConfigClient {
         });
     }
 
+    pub fn set_window_matcher_shortcuts_inhibit(
+        &self,
+        matcher: WindowMatcher,
+        inhibit: ShortcutsInhibit,
+    ) {
+        self.send(&ClientMessage::SetWindowMatcherShortcutsInhibit { matcher, inhibit });
+    }
+
     pub fn set_window_matcher_latch_handler(
         &self,
         matcher: WindowMatcher,
@@ -2228,6 +2714,14 @@ impl ConfigClient {
         self.send(&ClientMessage::SetPointerRevertKey { seat, key });
     }
 
+    pub fn set_shortcuts_inhibit_escape(&self, seat: Seat, key: ModifiedKeySym) {
+        self.send(&ClientMessage::SetShortcutsInhibitEscape { seat, key });
+    }
+
+    pub fn set_input_capture_release(&self, seat: Seat, key: ModifiedKeySym) {
+        self.send(&ClientMessage::SetInputCaptureRelease { seat, key });
+    }
+
     pub fn get_overlay(&self, name: &str) -> Workspace {
         let res = self.send_with_response(&ClientMessage::GetOverlay { name });
         get_response!(res, Workspace(0), GetWorkspace { workspace });
@@ -2487,6 +2981,7 @@ impl ConfigClient {
             }
             ServerMessage::DelInputDevice { device } => {
                 self.on_switch_event.borrow_mut().remove(&device);
+                self.on_tablet_pad_event.borrow_mut().remove(&device);
                 let handler = self.on_input_device_removed.borrow_mut().clone();
                 if let Some(handler) = handler {
                     run_cb("input device removed", &handler, device);
@@ -2644,6 +3139,67 @@ impl ConfigClient {
                     run_cb("locked", &handler, locked);
                 }
             }
+            ServerMessage::InvokePointerShortcut { seat, trigger } => {
+                let handler = self
+                    .pointer_handlers
+                    .borrow()
+                    .get(&(seat, trigger))
+                    .cloned();
+                if let Some(handler) = handler {
+                    run_cb("pointer shortcut", &handler, ());
+                }
+            }
+            ServerMessage::InvokeGesture { seat, gesture } => {
+                let handler = self
+                    .gesture_handlers
+                    .borrow()
+                    .get(&(seat, gesture))
+                    .cloned();
+                if let Some(handler) = handler {
+                    run_cb("gesture", &handler, ());
+                }
+            }
+            ServerMessage::InvokeTouchGesture { seat, gesture } => {
+                let handler = self
+                    .touch_gesture_handlers
+                    .borrow()
+                    .get(&(seat, gesture))
+                    .cloned();
+                if let Some(handler) = handler {
+                    run_cb("touch gesture", &handler, ());
+                }
+            }
+            ServerMessage::KeyboardAccessibilityFeedback { seat, feedback } => {
+                let cb = self
+                    .on_keyboard_accessibility_feedback
+                    .borrow()
+                    .get(&seat)
+                    .cloned();
+                if let Some(cb) = cb {
+                    run_cb("keyboard accessibility feedback", &cb, feedback);
+                }
+            }
+            ServerMessage::TabletPadEvent {
+                seat,
+                input_device,
+                event,
+            } => {
+                let _ = seat;
+                let cb = self
+                    .on_tablet_pad_event
+                    .borrow()
+                    .get(&input_device)
+                    .cloned();
+                if let Some(cb) = cb {
+                    run_cb("tablet pad event", &cb, event);
+                }
+            }
+            ServerMessage::ApplyOutputProfile { name } => {
+                let handler = self.on_apply_output_profile.borrow_mut().clone();
+                if let Some(handler) = handler {
+                    run_cb("apply output profile", &handler, name);
+                }
+            }
         }
     }
 
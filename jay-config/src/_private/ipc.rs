@@ -13,32 +13,49 @@ use crate::WorkspaceKind;
 use crate::client::Client;
 use crate::client::ClientCapabilities;
 use crate::client::ClientMatcher;
+use crate::input::AccessibilityFeedback;
 use crate::input::FallbackOutputMode;
 use crate::input::FocusFollowsMouseMode;
 use crate::input::InputDevice;
 use crate::input::InputEventCode;
 use crate::input::LayerDirection;
+use crate::input::LayoutMemoryMode;
+use crate::input::ModifiedPointerTrigger;
 use crate::input::Seat;
 use crate::input::SwitchEvent;
 use crate::input::Timeline;
 use crate::input::acceleration::AccelProfile;
 use crate::input::capability::Capability;
 use crate::input::clickmethod::ClickMethod;
+use crate::input::gesture::Gesture;
+use crate::input::gesture::TouchGesture;
+use crate::input::key_remap::KeyRemap;
+use crate::input::mouse_keys::MouseKeys;
 use crate::input::scrollmethod::ScrollMethod;
+use crate::input::sendevents::SendEventsMode;
+use crate::input::tablet::EraserMode;
+use crate::input::tablet::PressureCurve;
+use crate::input::tablet::TabletPadEvent;
+use crate::input::tablet::TabletToolType;
+use crate::input::tapbuttonmap::TapButtonMap;
 use crate::keyboard::Group;
 use crate::keyboard::Keymap;
+use crate::keyboard::ModifiedKeySym;
 use crate::keyboard::mods::Modifiers;
 use crate::keyboard::syms::KeySym;
 use crate::logging::LogLevel;
+use crate::theme::BarElement;
 use crate::theme::BarPosition;
 use crate::theme::Color;
 use crate::theme::ContainerBorders;
 use crate::theme::colors::Colorable;
 use crate::theme::sized::Resizable;
 use crate::timer::Timer;
+use crate::video::AutoScale;
 use crate::video::BlendSpace;
 use crate::video::ColorSpace;
 use crate::video::Connector;
+use crate::video::ConnectorConfiguration;
 use crate::video::DrmDevice;
 use crate::video::Eotf;
 use crate::video::Format;
@@ -49,6 +66,7 @@ use crate::video::Transform;
 use crate::video::VrrMode;
 use crate::video::connector_type::ConnectorType;
 use crate::window::ContentType;
+use crate::window::ShortcutsInhibit;
 use crate::window::TileState;
 use crate::window::Window;
 use crate::window::WindowMatcher;
@@ -156,6 +174,30 @@ pub enum ServerMessage {
     Locked {
         locked: bool,
     },
+    InvokePointerShortcut {
+        seat: Seat,
+        trigger: ModifiedPointerTrigger,
+    },
+    InvokeGesture {
+        seat: Seat,
+        gesture: Gesture,
+    },
+    InvokeTouchGesture {
+        seat: Seat,
+        gesture: TouchGesture,
+    },
+    KeyboardAccessibilityFeedback {
+        seat: Seat,
+        feedback: AccessibilityFeedback,
+    },
+    TabletPadEvent {
+        seat: Seat,
+        input_device: InputDevice,
+        event: TabletPadEvent,
+    },
+    ApplyOutputProfile {
+        name: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -1018,6 +1060,281 @@ pub enum ClientMessage<'a> {
         reuse: bool,
     },
     GetSplitReusesContainer,
+    SetBarElements {
+        elements: Vec<BarElement>,
+    },
+    SetBarElementSeparator {
+        separator: &'a str,
+    },
+    SeatSetInputModeStack {
+        seat: Seat,
+        modes: Vec<&'a str>,
+    },
+    AddPointerShortcut {
+        seat: Seat,
+        trigger: ModifiedPointerTrigger,
+    },
+    RemovePointerShortcut {
+        seat: Seat,
+        trigger: ModifiedPointerTrigger,
+    },
+    AddGestureBinding {
+        seat: Seat,
+        gesture: Gesture,
+    },
+    RemoveGestureBinding {
+        seat: Seat,
+        gesture: Gesture,
+    },
+    SetGestureContinuous {
+        seat: Seat,
+        gesture: Gesture,
+        continuous: bool,
+    },
+    SetGestureSwipeDistance {
+        seat: Seat,
+        distance: f64,
+    },
+    AddTouchGestureBinding {
+        seat: Seat,
+        gesture: TouchGesture,
+    },
+    RemoveTouchGestureBinding {
+        seat: Seat,
+        gesture: TouchGesture,
+    },
+    SetTouchLongPressDrag {
+        seat: Seat,
+        enabled: bool,
+    },
+    ConnectorSetTouchEdgeWidth {
+        connector: Connector,
+        width: i32,
+    },
+    SetInputDwt {
+        device: InputDevice,
+        enabled: bool,
+    },
+    SetInputDwtp {
+        device: InputDevice,
+        enabled: bool,
+    },
+    SetInputTapButtonMap {
+        device: InputDevice,
+        map: TapButtonMap,
+    },
+    SetInputSendEventsMode {
+        device: InputDevice,
+        mode: SendEventsMode,
+    },
+    SetInputRotation {
+        device: InputDevice,
+        degrees: u32,
+    },
+    SetInputCustomAccel {
+        device: InputDevice,
+        step: f64,
+        points: Vec<f64>,
+    },
+    SetLayoutMemoryMode {
+        seat: Seat,
+        mode: LayoutMemoryMode,
+    },
+    SeatGetKeyboardGroup {
+        seat: Seat,
+    },
+    SeatSetKeyboardGroup {
+        seat: Seat,
+        group: u32,
+    },
+    SetWindowMatcherShortcutsInhibit {
+        matcher: WindowMatcher,
+        inhibit: ShortcutsInhibit,
+    },
+    SetShortcutsInhibitEscape {
+        seat: Seat,
+        key: ModifiedKeySym,
+    },
+    SetInputKeyRemap {
+        device: InputDevice,
+        remap: KeyRemap,
+    },
+    SeatSetStickyKeysEnabled {
+        seat: Seat,
+        enabled: bool,
+    },
+    SeatGetStickyKeysEnabled {
+        seat: Seat,
+    },
+    SeatSetSlowKeysEnabled {
+        seat: Seat,
+        enabled: bool,
+    },
+    SeatGetSlowKeysEnabled {
+        seat: Seat,
+    },
+    SeatSetSlowKeysDelay {
+        seat: Seat,
+        ms: u32,
+    },
+    SeatSetBounceKeysEnabled {
+        seat: Seat,
+        enabled: bool,
+    },
+    SeatGetBounceKeysEnabled {
+        seat: Seat,
+    },
+    SeatSetBounceKeysDelay {
+        seat: Seat,
+        ms: u32,
+    },
+    SeatSetMouseKeysEnabled {
+        seat: Seat,
+        enabled: bool,
+    },
+    SeatGetMouseKeysEnabled {
+        seat: Seat,
+    },
+    SeatSetMouseKeys {
+        seat: Seat,
+        mouse_keys: MouseKeys,
+    },
+    SeatSetWhichKeyEnabled {
+        seat: Seat,
+        enabled: bool,
+    },
+    SeatGetWhichKeyEnabled {
+        seat: Seat,
+    },
+    SeatSetWhichKeyDelay {
+        seat: Seat,
+        ms: u32,
+    },
+    SeatSetWhichKey {
+        seat: Seat,
+        title: String,
+        entries: Vec<(String, String)>,
+    },
+    SeatStartMacroRecording {
+        seat: Seat,
+        name: String,
+        pointer: bool,
+    },
+    SeatStopMacroRecording {
+        seat: Seat,
+    },
+    SeatReplayMacro {
+        seat: Seat,
+        name: String,
+    },
+    SetInputTabletArea {
+        input_device: InputDevice,
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+    },
+    SetInputTabletKeepAspectRatio {
+        input_device: InputDevice,
+        keep: bool,
+    },
+    MapInputDeviceToWindow {
+        input_device: InputDevice,
+        window: Window,
+    },
+    SetInputTabletRegion {
+        input_device: InputDevice,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    },
+    SetInputTabletPressureCurve {
+        input_device: InputDevice,
+        tool: TabletToolType,
+        curve: PressureCurve,
+    },
+    SetInputTabletEraserMode {
+        input_device: InputDevice,
+        mode: EraserMode,
+    },
+    UnmapInputDeviceFromWindow {
+        input_device: InputDevice,
+    },
+    SeatSetHideCursorWhileTyping {
+        seat: Seat,
+        hide: bool,
+    },
+    SeatSetCursorHideTimeout {
+        seat: Seat,
+        usec: u64,
+    },
+    SeatSetCursorTheme {
+        seat: Seat,
+        theme: &'a str,
+    },
+    ConfigureConnectors {
+        configurations: Vec<(Connector, ConnectorConfiguration)>,
+    },
+    SetOutputProfiles {
+        profiles: Vec<&'a str>,
+        active: Option<&'a str>,
+    },
+    ConnectorSetDpms {
+        connector: Connector,
+        on: bool,
+    },
+    ConnectorGetDpms {
+        connector: Connector,
+    },
+    ConnectorSetIccProfile {
+        connector: Connector,
+        path: Option<&'a str>,
+    },
+    ConnectorSetCvtMode {
+        connector: Connector,
+        width: i32,
+        height: i32,
+        refresh_millihz: u32,
+        reduced_blanking: bool,
+    },
+    ConnectorSetModeline {
+        connector: Connector,
+        modeline: &'a str,
+    },
+    ConnectorSetEdidOverride {
+        connector: Connector,
+        path: Option<&'a str>,
+    },
+    SetAutoScale {
+        auto_scale: Option<AutoScale>,
+    },
+    ConnectorSetVncServer {
+        connector: Connector,
+        address: Option<&'a str>,
+    },
+    ConnectorGetBacklight {
+        connector: Connector,
+    },
+    ConnectorSetBacklight {
+        connector: Connector,
+        value: f64,
+        relative: bool,
+    },
+    SetBacklightIndicatorEnabled {
+        enabled: bool,
+    },
+    SetAutoRotationEnabled {
+        enabled: bool,
+    },
+    SetRotationLocked {
+        locked: bool,
+    },
+    GetRotationLocked,
+    SetInputCaptureRelease {
+        seat: Seat,
+        key: ModifiedKeySym,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -1307,6 +1624,33 @@ pub enum Response {
     GetSplitReusesContainer {
         reuse: bool,
     },
+    GetKeyboardGroup {
+        group: u32,
+    },
+    SeatGetStickyKeysEnabled {
+        enabled: bool,
+    },
+    SeatGetSlowKeysEnabled {
+        enabled: bool,
+    },
+    SeatGetBounceKeysEnabled {
+        enabled: bool,
+    },
+    SeatGetMouseKeysEnabled {
+        enabled: bool,
+    },
+    SeatGetWhichKeyEnabled {
+        enabled: bool,
+    },
+    ConnectorGetDpms {
+        on: bool,
+    },
+    ConnectorGetBacklight {
+        percent: Option<f64>,
+    },
+    GetRotationLocked {
+        locked: bool,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
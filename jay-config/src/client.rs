@@ -231,5 +231,7 @@ bitflags! {
         pub const CC_GAMMA_CONTROL_MANAGER    = 1 << 14,
         /// Grants access to the `zwlr_virtual_pointer_manager_v1` global.
         pub const CC_VIRTUAL_POINTER          = 1 << 15,
+        /// Grants access to the `zwlr_output_power_manager_v1` global.
+        pub const CC_OUTPUT_POWER_MANAGER     = 1 << 16,
     }
 }
@@ -3,8 +3,14 @@
 pub mod acceleration;
 pub mod capability;
 pub mod clickmethod;
+pub mod gesture;
 pub mod input_event_codes;
+pub mod key_remap;
+pub mod mouse_keys;
 pub mod scrollmethod;
+pub mod sendevents;
+pub mod tablet;
+pub mod tapbuttonmap;
 
 use crate::_private::DEFAULT_SEAT_NAME;
 use crate::_private::ipc::WorkspaceSource;
@@ -15,7 +21,17 @@ use crate::Workspace;
 use crate::input::acceleration::AccelProfile;
 use crate::input::capability::Capability;
 use crate::input::clickmethod::ClickMethod;
+use crate::input::gesture::Gesture;
+use crate::input::gesture::TouchGesture;
+use crate::input::key_remap::KeyRemap;
+use crate::input::mouse_keys::MouseKeys;
 use crate::input::scrollmethod::ScrollMethod;
+use crate::input::sendevents::SendEventsMode;
+use crate::input::tablet::EraserMode;
+use crate::input::tablet::PressureCurve;
+use crate::input::tablet::TabletPadEvent;
+use crate::input::tablet::TabletToolType;
+use crate::input::tapbuttonmap::TapButtonMap;
 use crate::keyboard::Keymap;
 use crate::keyboard::mods::Modifiers;
 use crate::keyboard::syms::KeySym;
@@ -23,6 +39,7 @@ use crate::video::Connector;
 use crate::window::Window;
 use serde::Deserialize;
 use serde::Serialize;
+use std::ops::BitOr;
 use std::time::Duration;
 
 /// An input device.
@@ -186,6 +203,64 @@ impl InputDevice {
         get!().set_input_scroll_button_lock(self, enabled);
     }
 
+    /// Sets whether the device is disabled while typing.
+    ///
+    /// See <https://wayland.freedesktop.org/libinput/doc/latest/palm_detection.html#disable-while-typing>
+    pub fn set_disable_while_typing(self, enabled: bool) {
+        get!().set_input_dwt(self, enabled);
+    }
+
+    /// Sets whether the device is disabled while a trackpoint is in use.
+    ///
+    /// See <https://wayland.freedesktop.org/libinput/doc/latest/palm_detection.html#disable-while-trackpointing>
+    pub fn set_disable_while_trackpointing(self, enabled: bool) {
+        get!().set_input_dwtp(self, enabled);
+    }
+
+    /// Sets the button mapping of 1, 2, and 3-finger taps.
+    ///
+    /// See <https://wayland.freedesktop.org/libinput/doc/latest/tapping.html>
+    pub fn set_tap_button_map(self, map: TapButtonMap) {
+        get!().set_input_tap_button_map(self, map);
+    }
+
+    /// Sets whether the device sends events.
+    ///
+    /// See <https://wayland.freedesktop.org/libinput/doc/latest/configuration.html#send-events-mode>
+    pub fn set_send_events_mode(self, mode: SendEventsMode) {
+        get!().set_input_send_events_mode(self, mode);
+    }
+
+    /// Sets the clockwise rotation of the device in degrees.
+    ///
+    /// The value must be less than 360. Most devices only support multiples of 90.
+    ///
+    /// See <https://wayland.freedesktop.org/libinput/doc/latest/configuration.html#rotation>
+    pub fn set_rotation(self, degrees: u32) {
+        get!().set_input_rotation(self, degrees);
+    }
+
+    /// Sets the custom acceleration curve of the device.
+    ///
+    /// The curve only takes effect while the acceleration profile is
+    /// [`ACCEL_PROFILE_CUSTOM`](acceleration::ACCEL_PROFILE_CUSTOM). `points` are the
+    /// accelerated speeds at input speeds `0`, `step`, `2 * step`, etc. There must be
+    /// between 2 and 64 points.
+    ///
+    /// See <https://wayland.freedesktop.org/libinput/doc/latest/pointer-acceleration.html#the-custom-acceleration-profile>
+    pub fn set_custom_accel(self, step: f64, points: &[f64]) {
+        get!().set_input_custom_accel(self, step, points);
+    }
+
+    /// Sets the key remapping layer of the device.
+    ///
+    /// Keys that are pressed while the remapping changes are released.
+    ///
+    /// Setting an empty remapping disables remapping.
+    pub fn set_key_remap(self, remap: &KeyRemap) {
+        get!().set_input_key_remap(self, remap);
+    }
+
     /// Returns the syspath of this device.
     ///
     /// E.g. `/sys/devices/pci0000:00/0000:00:08.1/0000:14:00.4/usb5/5-1/5-1.1/5-1.1.3/5-1.1.3:1.0`.
@@ -214,10 +289,65 @@ impl InputDevice {
         get!().set_input_device_connector(self, connector);
     }
 
-    /// Removes the mapping of this device to a connector.
+    /// Removes the mapping of this device to a connector, window, or region.
     pub fn remove_mapping(self) {
         get!().remove_input_mapping(self);
     }
+
+    /// Maps this graphics tablet to a window.
+    ///
+    /// While the window is visible, the tablet area is mapped to the window. Otherwise
+    /// the region or connector mapping is used.
+    pub fn map_to_window(self, window: Window) {
+        get!().map_input_device_to_window(self, window);
+    }
+
+    /// Removes the mapping of this graphics tablet to a window.
+    pub fn unmap_from_window(self) {
+        get!().unmap_input_device_from_window(self);
+    }
+
+    /// Maps this graphics tablet to a region of the screen.
+    ///
+    /// The region is specified in compositor coordinates. It takes precedence over the
+    /// connector mapping.
+    pub fn set_tablet_region(self, x: i32, y: i32, width: i32, height: i32) {
+        get!().set_input_tablet_region(self, x, y, width, height);
+    }
+
+    /// Sets the part of the graphics tablet that is used.
+    ///
+    /// The coordinates are relative to the size of the tablet and must satisfy
+    /// `0 <= x1 < x2 <= 1` and `0 <= y1 < y2 <= 1`. The default is `(0, 0, 1, 1)`.
+    pub fn set_tablet_area(self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        get!().set_input_tablet_area(self, x1, y1, x2, y2);
+    }
+
+    /// Sets whether the aspect ratio of the mapped region is preserved.
+    ///
+    /// If this is enabled, the tablet area is cropped so that it has the same aspect
+    /// ratio as the region it is mapped to. The default is `false`.
+    pub fn set_tablet_keep_aspect_ratio(self, keep: bool) {
+        get!().set_input_tablet_keep_aspect_ratio(self, keep);
+    }
+
+    /// Sets the pressure curve of a tool type of this graphics tablet.
+    pub fn set_tablet_pressure_curve(self, tool: TabletToolType, curve: PressureCurve) {
+        get!().set_input_tablet_pressure_curve(self, tool, curve);
+    }
+
+    /// Sets how the eraser end of pens of this graphics tablet is reported.
+    ///
+    /// This only affects tools that come into proximity after this call.
+    pub fn set_tablet_eraser_mode(self, mode: EraserMode) {
+        get!().set_input_tablet_eraser_mode(self, mode);
+    }
+
+    /// Sets a callback that will be run if this tablet pad generates an event that is
+    /// not handled by an application.
+    pub fn on_tablet_pad_event<F: FnMut(TabletPadEvent) + 'static>(self, f: F) {
+        get!().on_tablet_pad_event(self, f)
+    }
 }
 
 /// A direction in a timeline.
@@ -273,6 +403,41 @@ impl Seat {
         get!().set_cursor_size(self, size)
     }
 
+    /// Sets the cursor theme of this seat.
+    ///
+    /// The theme is loaded from the XCursor search path. Both XCursor themes and SVG
+    /// themes in the `cursors_scalable` format are supported. An empty string resets the
+    /// theme to the value of the `XCURSOR_THEME` environment variable at startup.
+    ///
+    /// The theme and size of the seat that uses the hardware cursor are exported to
+    /// spawned applications and Xwayland via `XCURSOR_THEME`, `XCURSOR_SIZE`, and the
+    /// X resources.
+    pub fn set_cursor_theme(self, theme: &str) {
+        get!().seat_set_cursor_theme(self, theme);
+    }
+
+    /// Sets whether this seat's cursor is hidden while typing.
+    ///
+    /// The cursor is hidden when a key is pressed that is forwarded to an application and
+    /// is shown again on the next pointer event. Modifier keys and keys pressed with
+    /// modifiers other than shift don't hide the cursor. The pointer focus is not changed.
+    ///
+    /// The default is `false`.
+    pub fn set_hide_cursor_while_typing(self, hide: bool) {
+        get!().seat_set_hide_cursor_while_typing(self, hide);
+    }
+
+    /// Sets the duration without pointer events after which this seat's cursor is hidden.
+    ///
+    /// The cursor is shown again on the next pointer event. The pointer focus is not
+    /// changed. A duration of zero disables this.
+    ///
+    /// The default is zero.
+    pub fn set_cursor_hide_timeout(self, timeout: Duration) {
+        let usec = timeout.as_micros().min(u64::MAX as u128);
+        get!().seat_set_cursor_hide_timeout(self, usec as u64);
+    }
+
     /// Creates a compositor-wide hotkey.
     ///
     /// The closure is invoked when the user presses the last key of the modified keysym.
@@ -343,6 +508,107 @@ impl Seat {
         get!().unbind(self, mod_sym.into())
     }
 
+    /// Creates a compositor-wide pointer binding.
+    ///
+    /// The callback is invoked when the pointer button is pressed or the scroll wheel is
+    /// scrolled while exactly the given modifiers are pressed. If the trigger is a button
+    /// and contains the `RELEASE` modifier, the callback is instead invoked when the
+    /// button is released.
+    ///
+    /// Before the callback is invoked, the window under the pointer receives the keyboard
+    /// focus so that window-related actions apply to it. The event is not forwarded to
+    /// the application.
+    pub fn bind_pointer<T: Into<ModifiedPointerTrigger>, F: FnMut() + 'static>(
+        self,
+        trigger: T,
+        f: F,
+    ) {
+        get!().bind_pointer(self, trigger.into(), f)
+    }
+
+    /// Removes a pointer binding.
+    pub fn unbind_pointer<T: Into<ModifiedPointerTrigger>>(self, trigger: T) {
+        get!().unbind_pointer(self, trigger.into())
+    }
+
+    /// Binds a touchpad gesture.
+    ///
+    /// Once a gesture with the given number of fingers starts, the compositor checks
+    /// whether any gesture of the same type (swipe, pinch, or hold) is bound for this
+    /// number of fingers. If so, the whole gesture is handled by the compositor and not
+    /// forwarded to applications. Swipes are still forwarded if the direction in which
+    /// the fingers start moving is not bound.
+    ///
+    /// By default, the callback is invoked once when the gesture ends. See
+    /// [`Seat::set_gesture_continuous`] to invoke it while the gesture is in progress.
+    pub fn bind_gesture<F: FnMut() + 'static>(self, gesture: Gesture, f: F) {
+        get!().bind_gesture(self, gesture, f)
+    }
+
+    /// Unbinds a touchpad gesture.
+    pub fn unbind_gesture(self, gesture: Gesture) {
+        get!().unbind_gesture(self, gesture)
+    }
+
+    /// Configures whether a gesture binding is continuous.
+    ///
+    /// A continuous swipe binding is invoked every time the fingers have moved by the
+    /// swipe distance in the direction of the gesture. A continuous pinch binding is
+    /// invoked every time the distance between the fingers has changed by 25%. This
+    /// allows, for example, moving through several windows with a single swipe.
+    ///
+    /// This has no effect on hold gestures.
+    ///
+    /// The default is `false`.
+    ///
+    /// Binding resets the value to the default.
+    pub fn set_gesture_continuous(self, gesture: Gesture, continuous: bool) {
+        get!().set_gesture_continuous(self, gesture, continuous)
+    }
+
+    /// Sets the distance, in logical pixels, that fingers have to move for a swipe
+    /// gesture to be recognized.
+    ///
+    /// The default is `100`.
+    pub fn set_gesture_swipe_distance(self, distance: f64) {
+        get!().set_gesture_swipe_distance(self, distance)
+    }
+
+    /// Binds a touchscreen gesture.
+    ///
+    /// Edge gestures are recognized when the first finger touches an output within
+    /// the edge width of that edge. See [`Connector::set_touch_edge_width`]. Such
+    /// touch sequences are never forwarded to applications.
+    ///
+    /// Multi-finger swipe and pinch gestures are recognized once as many fingers as
+    /// bound for any of these gestures are on the screen. At that point, the touch
+    /// sequence is cancelled for the application that received it.
+    ///
+    /// Swipes use the distance set with [`Seat::set_gesture_swipe_distance`].
+    ///
+    /// The callback is invoked once per touch sequence.
+    ///
+    /// [`Connector::set_touch_edge_width`]: crate::video::Connector::set_touch_edge_width
+    pub fn bind_touch_gesture<F: FnMut() + 'static>(self, gesture: TouchGesture, f: F) {
+        get!().bind_touch_gesture(self, gesture, f)
+    }
+
+    /// Unbinds a touchscreen gesture.
+    pub fn unbind_touch_gesture(self, gesture: TouchGesture) {
+        get!().unbind_touch_gesture(self, gesture)
+    }
+
+    /// Configures whether a long press on a tiled window starts moving the window.
+    ///
+    /// If this is enabled, touching a tiled window for half a second without moving
+    /// the finger starts dragging the window as if its title had been dragged with
+    /// the mouse.
+    ///
+    /// The default is `false`.
+    pub fn set_touch_long_press_drag(self, enabled: bool) {
+        get!().set_touch_long_press_drag(self, enabled)
+    }
+
     /// Moves the focus in the focus history.
     pub fn focus_history(self, timeline: Timeline) {
         get!().seat_focus_history(self, timeline)
@@ -574,6 +840,26 @@ impl Seat {
         get!().set_fallback_output_mode(self, mode);
     }
 
+    /// Sets how the active keyboard layout is remembered when the keyboard focus changes.
+    ///
+    /// The default is `Global`.
+    pub fn set_layout_memory_mode(self, mode: LayoutMemoryMode) {
+        get!().set_layout_memory_mode(self, mode);
+    }
+
+    /// Returns the 0-based index of the active keyboard layout (group) of the seat.
+    pub fn keyboard_group(self) -> u32 {
+        get!(0).seat_get_keyboard_group(self)
+    }
+
+    /// Sets the active keyboard layout (group) of the seat.
+    ///
+    /// The index is 0-based and wraps around if it is larger than the number of layouts
+    /// in the keymap.
+    pub fn set_keyboard_group(self, group: u32) {
+        get!().seat_set_keyboard_group(self, group);
+    }
+
     /// Enables or disable window management mode.
     ///
     /// In window management mode, floating windows can be moved by pressing the left
@@ -647,6 +933,30 @@ impl Seat {
         get!().set_pointer_revert_key(self, sym);
     }
 
+    /// Sets the shortcut that deactivates a keyboard shortcuts inhibitor.
+    ///
+    /// Applications such as virtual machine viewers can ask the compositor to forward
+    /// all key combinations to them. While such an inhibitor is active, this shortcut
+    /// is the only one handled by the compositor. Pressing it deactivates the inhibitor
+    /// until the window loses and regains the keyboard focus.
+    ///
+    /// The default is `LOGO | SYM_Escape`.
+    pub fn set_shortcuts_inhibit_escape<T: Into<ModifiedKeySym>>(self, key: T) {
+        get!().set_shortcuts_inhibit_escape(self, key.into());
+    }
+
+    /// Sets the shortcut that releases an input capture.
+    ///
+    /// While an application such as Deskflow captures the input of this seat, all
+    /// keyboard and pointer input is sent to that application. This shortcut is
+    /// handled by the compositor before the input is diverted and always ends the
+    /// capture.
+    ///
+    /// The default is `LOGO | SHIFT | SYM_Escape`.
+    pub fn set_input_capture_release<T: Into<ModifiedKeySym>>(self, key: T) {
+        get!().set_input_capture_release(self, key.into());
+    }
+
     /// Creates a mark for the currently focused window.
     ///
     /// `kc` should be an evdev keycode. If `kc` is none, then the keycode will be
@@ -703,6 +1013,196 @@ impl Seat {
         get!().seat_reload_simple_im(self);
     }
 
+    /// Sets whether sticky keys are enabled.
+    ///
+    /// While sticky keys are enabled, pressing and releasing a modifier latches it until
+    /// the next other key has been released. Pressing and releasing a latched modifier
+    /// again locks it until it is pressed a third time.
+    ///
+    /// The default is `false`.
+    pub fn set_sticky_keys_enabled(self, enabled: bool) {
+        get!().seat_set_sticky_keys_enabled(self, enabled);
+    }
+
+    /// Returns whether sticky keys are enabled.
+    pub fn sticky_keys_enabled(self) -> bool {
+        get!(false).seat_get_sticky_keys_enabled(self)
+    }
+
+    /// Toggles whether sticky keys are enabled.
+    pub fn toggle_sticky_keys_enabled(self) {
+        let get = get!();
+        get.seat_set_sticky_keys_enabled(self, !get.seat_get_sticky_keys_enabled(self));
+    }
+
+    /// Sets whether slow keys are enabled.
+    ///
+    /// While slow keys are enabled, key presses are only accepted after the key has
+    /// been held for the slow keys delay.
+    ///
+    /// The default is `false`.
+    pub fn set_slow_keys_enabled(self, enabled: bool) {
+        get!().seat_set_slow_keys_enabled(self, enabled);
+    }
+
+    /// Returns whether slow keys are enabled.
+    pub fn slow_keys_enabled(self) -> bool {
+        get!(false).seat_get_slow_keys_enabled(self)
+    }
+
+    /// Toggles whether slow keys are enabled.
+    pub fn toggle_slow_keys_enabled(self) {
+        let get = get!();
+        get.seat_set_slow_keys_enabled(self, !get.seat_get_slow_keys_enabled(self));
+    }
+
+    /// Sets the number of milliseconds a key has to be held before it is accepted by
+    /// slow keys.
+    ///
+    /// The default is `300`.
+    pub fn set_slow_keys_delay(self, ms: u32) {
+        get!().seat_set_slow_keys_delay(self, ms);
+    }
+
+    /// Sets whether bounce keys are enabled.
+    ///
+    /// While bounce keys are enabled, a key press is ignored if the same key was
+    /// released less than the bounce keys delay ago.
+    ///
+    /// The default is `false`.
+    pub fn set_bounce_keys_enabled(self, enabled: bool) {
+        get!().seat_set_bounce_keys_enabled(self, enabled);
+    }
+
+    /// Returns whether bounce keys are enabled.
+    pub fn bounce_keys_enabled(self) -> bool {
+        get!(false).seat_get_bounce_keys_enabled(self)
+    }
+
+    /// Toggles whether bounce keys are enabled.
+    pub fn toggle_bounce_keys_enabled(self) {
+        let get = get!();
+        get.seat_set_bounce_keys_enabled(self, !get.seat_get_bounce_keys_enabled(self));
+    }
+
+    /// Sets the number of milliseconds during which repeated presses of the same key
+    /// are ignored by bounce keys.
+    ///
+    /// The default is `300`.
+    pub fn set_bounce_keys_delay(self, ms: u32) {
+        get!().seat_set_bounce_keys_delay(self, ms);
+    }
+
+    /// Sets a callback to run when sticky, slow, or bounce keys change state.
+    ///
+    /// This can be used to provide audible feedback, for example by playing a sound.
+    pub fn on_keyboard_accessibility_feedback<F: FnMut(AccessibilityFeedback) + 'static>(
+        self,
+        f: F,
+    ) {
+        get!().on_keyboard_accessibility_feedback(self, f)
+    }
+
+    /// Sets whether mouse keys are enabled.
+    ///
+    /// While mouse keys are enabled, the keys configured with [`Self::set_mouse_keys`]
+    /// move the pointer, press buttons, and scroll instead of being sent to
+    /// applications.
+    ///
+    /// The default is `false`.
+    pub fn set_mouse_keys_enabled(self, enabled: bool) {
+        get!().seat_set_mouse_keys_enabled(self, enabled);
+    }
+
+    /// Returns whether mouse keys are enabled.
+    pub fn mouse_keys_enabled(self) -> bool {
+        get!(false).seat_get_mouse_keys_enabled(self)
+    }
+
+    /// Toggles whether mouse keys are enabled.
+    pub fn toggle_mouse_keys_enabled(self) {
+        let get = get!();
+        get.seat_set_mouse_keys_enabled(self, !get.seat_get_mouse_keys_enabled(self));
+    }
+
+    /// Sets the keys and tuning parameters of mouse keys.
+    ///
+    /// The default is [`MouseKeys::default`].
+    pub fn set_mouse_keys(self, mouse_keys: &MouseKeys) {
+        get!().seat_set_mouse_keys(self, mouse_keys);
+    }
+
+    /// Sets whether the which-key overlay is enabled.
+    ///
+    /// The overlay lists the entries set with [`Self::set_which_key`] after the
+    /// which-key delay.
+    ///
+    /// The default is `false`.
+    pub fn set_which_key_enabled(self, enabled: bool) {
+        get!().seat_set_which_key_enabled(self, enabled);
+    }
+
+    /// Returns whether the which-key overlay is enabled.
+    pub fn which_key_enabled(self) -> bool {
+        get!(false).seat_get_which_key_enabled(self)
+    }
+
+    /// Toggles whether the which-key overlay is enabled.
+    pub fn toggle_which_key_enabled(self) {
+        let get = get!();
+        get.seat_set_which_key_enabled(self, !get.seat_get_which_key_enabled(self));
+    }
+
+    /// Sets the number of milliseconds after which the which-key overlay appears.
+    ///
+    /// The default is `500`.
+    pub fn set_which_key_delay(self, ms: u32) {
+        get!().seat_set_which_key_delay(self, ms);
+    }
+
+    /// Sets the entries of the which-key overlay.
+    ///
+    /// Each entry is a pair of a key name and a description. This is usually called
+    /// whenever an input mode is entered, with the shortcuts of that mode. Setting an
+    /// empty list hides the overlay.
+    ///
+    /// The overlay is hidden immediately and shown again after the which-key delay.
+    pub fn set_which_key(self, title: &str, entries: &[(&str, &str)]) {
+        get!().seat_set_which_key(self, title, entries);
+    }
+
+    /// Starts recording a keyboard macro with the given name.
+    ///
+    /// While recording, all key events that are delivered to clients are recorded. If
+    /// `pointer` is `true`, pointer button events are recorded as well, together with
+    /// the position of the pointer.
+    ///
+    /// If a recording is already in progress, it is discarded.
+    ///
+    /// The name must not be empty and must not contain `/`.
+    pub fn start_macro_recording(self, name: &str, pointer: bool) {
+        get!().seat_start_macro_recording(self, name, pointer);
+    }
+
+    /// Stops recording the current keyboard macro.
+    ///
+    /// The macro is saved in `$XDG_DATA_HOME/jay/macros` and replaces any previous
+    /// macro with the same name.
+    pub fn stop_macro_recording(self) {
+        get!().seat_stop_macro_recording(self);
+    }
+
+    /// Replays the keyboard macro with the given name into the focused window.
+    ///
+    /// If the macro was not recorded since the compositor was started, it is loaded
+    /// from `$XDG_DATA_HOME/jay/macros`. The recorded key events are interpreted with
+    /// the keymap of the seat.
+    ///
+    /// This has no effect while a macro is being recorded or replayed.
+    pub fn replay_macro(self, name: &str) {
+        get!().seat_replay_macro(self, name);
+    }
+
     /// Enables Unicode input in the simple, XCompose based input method.
     ///
     /// This has no effect if the simple IM is not currently active.
@@ -742,6 +1242,15 @@ impl Seat {
     pub fn get_keyboard_connector(self) -> Connector {
         get!(Connector(0)).get_seat_keyboard_connector(self)
     }
+
+    /// Informs the compositor about the active input modes of this seat.
+    ///
+    /// Input modes are implemented by the configuration. The compositor uses this
+    /// information only to display the modes, for example in the bar. The last element
+    /// is the innermost mode.
+    pub fn set_input_mode_stack(self, modes: &[&str]) {
+        get!().seat_set_input_mode_stack(self, modes)
+    }
 }
 
 /// A focus-follows-mouse mode.
@@ -767,6 +1276,22 @@ pub enum FallbackOutputMode {
     Focus,
 }
 
+/// Defines how the active keyboard layout is remembered when the keyboard focus changes.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum LayoutMemoryMode {
+    /// The active layout is shared by all windows.
+    Global,
+    /// Each window remembers its own layout.
+    ///
+    /// Windows that have not been focused before start with the first layout.
+    Window,
+    /// Each app ID remembers its own layout.
+    ///
+    /// App IDs that have not been focused before start with the first layout.
+    AppId,
+}
+
 /// Returns all seats.
 pub fn get_seats() -> Vec<Seat> {
     get!().seats()
@@ -868,6 +1393,26 @@ pub enum SwitchEvent {
     ConvertedToTablet,
 }
 
+/// Feedback generated by sticky, slow, and bounce keys.
+#[non_exhaustive]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum AccessibilityFeedback {
+    /// A modifier has been latched by sticky keys.
+    ModifierLatched,
+    /// A modifier has been locked by sticky keys.
+    ModifierLocked,
+    /// Latched or locked modifiers have been released.
+    ModifierReleased,
+    /// A key has been pressed and is waiting to be accepted by slow keys.
+    SlowKeyPressed,
+    /// A key has been held long enough to be accepted by slow keys.
+    SlowKeyAccepted,
+    /// A key has been released before it was accepted by slow keys.
+    SlowKeyRejected,
+    /// A key press has been ignored by bounce keys.
+    BounceKeyRejected,
+}
+
 /// Enables or disables the unauthenticated libei socket.
 ///
 /// Even if the socket is disabled, application can still request access via the portal.
@@ -883,3 +1428,58 @@ pub struct InputEventCode(pub u32);
 impl InputEventCode {
     pub const NONE: Self = Self(0);
 }
+
+/// A pointer button or scroll direction that can be bound with [`Seat::bind_pointer`].
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum PointerTrigger {
+    /// A pointer button, e.g. [`BTN_SIDE`](input_event_codes::BTN_SIDE).
+    ///
+    /// Combine with the `RELEASE` modifier to react to the button being released.
+    Button(InputEventCode),
+    /// The scroll wheel was scrolled up.
+    ScrollUp,
+    /// The scroll wheel was scrolled down.
+    ScrollDown,
+    /// The scroll wheel was scrolled left.
+    ScrollLeft,
+    /// The scroll wheel was scrolled right.
+    ScrollRight,
+}
+
+/// A pointer trigger with zero or more modifiers
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct ModifiedPointerTrigger {
+    pub mods: Modifiers,
+    pub trigger: PointerTrigger,
+}
+
+impl From<PointerTrigger> for ModifiedPointerTrigger {
+    fn from(trigger: PointerTrigger) -> Self {
+        Self {
+            mods: Modifiers(0),
+            trigger,
+        }
+    }
+}
+
+impl BitOr<Modifiers> for ModifiedPointerTrigger {
+    type Output = ModifiedPointerTrigger;
+
+    fn bitor(self, rhs: Modifiers) -> Self::Output {
+        ModifiedPointerTrigger {
+            mods: self.mods | rhs,
+            trigger: self.trigger,
+        }
+    }
+}
+
+impl BitOr<PointerTrigger> for Modifiers {
+    type Output = ModifiedPointerTrigger;
+
+    fn bitor(self, rhs: PointerTrigger) -> Self::Output {
+        ModifiedPointerTrigger {
+            mods: self,
+            trigger: rhs,
+        }
+    }
+}
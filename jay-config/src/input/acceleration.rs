@@ -13,3 +13,7 @@ pub struct AccelProfile(pub u32);
 pub const ACCEL_PROFILE_FLAT: AccelProfile = AccelProfile(1 << 0);
 /// An adaptive acceleration profile.
 pub const ACCEL_PROFILE_ADAPTIVE: AccelProfile = AccelProfile(1 << 1);
+/// A user-defined acceleration curve.
+///
+/// The curve is set with [`InputDevice::set_custom_accel`](crate::input::InputDevice::set_custom_accel).
+pub const ACCEL_PROFILE_CUSTOM: AccelProfile = AccelProfile(1 << 2);
@@ -0,0 +1,76 @@
+//! Touchpad and touchscreen gestures that can be bound to callbacks.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The kind of a touchpad gesture.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum GestureKind {
+    /// The fingers move to the left.
+    SwipeLeft,
+    /// The fingers move to the right.
+    SwipeRight,
+    /// The fingers move up.
+    SwipeUp,
+    /// The fingers move down.
+    SwipeDown,
+    /// The fingers move towards each other.
+    PinchIn,
+    /// The fingers move away from each other.
+    PinchOut,
+    /// The fingers rest on the touchpad without moving.
+    Hold,
+}
+
+/// A touchpad gesture performed with a number of fingers.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct Gesture {
+    pub kind: GestureKind,
+    pub fingers: u32,
+}
+
+impl Gesture {
+    /// Creates a new gesture.
+    pub fn new(kind: GestureKind, fingers: u32) -> Self {
+        Self { kind, fingers }
+    }
+}
+
+/// The kind of a touchscreen gesture.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum TouchGestureKind {
+    /// The fingers start at the left edge of an output and move to the right.
+    FromLeftEdge,
+    /// The fingers start at the right edge of an output and move to the left.
+    FromRightEdge,
+    /// The fingers start at the top edge of an output and move down.
+    FromTopEdge,
+    /// The fingers start at the bottom edge of an output and move up.
+    FromBottomEdge,
+    /// The fingers move to the left.
+    SwipeLeft,
+    /// The fingers move to the right.
+    SwipeRight,
+    /// The fingers move up.
+    SwipeUp,
+    /// The fingers move down.
+    SwipeDown,
+    /// The fingers move towards each other.
+    PinchIn,
+    /// The fingers move away from each other.
+    PinchOut,
+}
+
+/// A touchscreen gesture performed with a number of fingers.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct TouchGesture {
+    pub kind: TouchGestureKind,
+    pub fingers: u32,
+}
+
+impl TouchGesture {
+    /// Creates a new touchscreen gesture.
+    pub fn new(kind: TouchGestureKind, fingers: u32) -> Self {
+        Self { kind, fingers }
+    }
+}
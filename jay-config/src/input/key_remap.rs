@@ -0,0 +1,60 @@
+//! Tools for remapping the keys of keyboard devices.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The default number of milliseconds after which a tap-hold key acts as held.
+pub const DEFAULT_TAP_HOLD_TIMEOUT_MS: u32 = 200;
+
+/// A key remapping layer.
+///
+/// The layer operates on evdev keycodes before they are translated by the keymap. It
+/// therefore affects all applications as well as the compositor's own shortcuts.
+///
+/// Keys that are not mentioned are forwarded unchanged.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct KeyRemap {
+    /// The actions of keys when no layer is active.
+    pub keys: Vec<(u32, RemapAction)>,
+    /// Additional layers that can be activated with [`RemapAction::Layer`] or
+    /// [`HoldAction::Layer`].
+    ///
+    /// While a layer is active, its actions take precedence over the actions of
+    /// layers activated before it and over `keys`. Keys that are not mentioned in a
+    /// layer fall through to the layers below.
+    pub layers: Vec<Vec<(u32, RemapAction)>>,
+}
+
+/// The action performed by a remapped key.
+#[non_exhaustive]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub enum RemapAction {
+    /// Emits a different key.
+    Key(u32),
+    /// Activates the layer with this index while the key is held.
+    Layer(u32),
+    /// Emits this key and keeps it pressed until the next other key is released.
+    ///
+    /// This is usually used for modifiers. If other keys are pressed while the key is
+    /// held, it acts like a normal key.
+    OneShot(u32),
+    /// Acts as `tap` if the key is released quickly and as `hold` otherwise.
+    ///
+    /// The key acts as held if it is held for longer than `timeout_ms` milliseconds or
+    /// if another key is pressed and released while it is held.
+    TapHold {
+        tap: u32,
+        hold: HoldAction,
+        timeout_ms: u32,
+    },
+}
+
+/// The action performed by a tap-hold key when it is held.
+#[non_exhaustive]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub enum HoldAction {
+    /// Emits this key.
+    Key(u32),
+    /// Activates the layer with this index.
+    Layer(u32),
+}
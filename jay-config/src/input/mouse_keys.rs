@@ -0,0 +1,136 @@
+//! Tools for controlling the pointer with the keyboard.
+
+use crate::input::input_event_codes::KEY_KP0;
+use crate::input::input_event_codes::KEY_KP1;
+use crate::input::input_event_codes::KEY_KP2;
+use crate::input::input_event_codes::KEY_KP3;
+use crate::input::input_event_codes::KEY_KP4;
+use crate::input::input_event_codes::KEY_KP5;
+use crate::input::input_event_codes::KEY_KP6;
+use crate::input::input_event_codes::KEY_KP7;
+use crate::input::input_event_codes::KEY_KP8;
+use crate::input::input_event_codes::KEY_KP9;
+use crate::input::input_event_codes::KEY_KPASTERISK;
+use crate::input::input_event_codes::KEY_KPDOT;
+use crate::input::input_event_codes::KEY_KPMINUS;
+use crate::input::input_event_codes::KEY_KPPLUS;
+use crate::input::input_event_codes::KEY_KPSLASH;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The default initial speed of the pointer in pixels per second.
+pub const DEFAULT_MOUSE_KEYS_SPEED: f64 = 100.0;
+/// The default maximum speed of the pointer in pixels per second.
+pub const DEFAULT_MOUSE_KEYS_MAX_SPEED: f64 = 1200.0;
+/// The default number of milliseconds until the pointer reaches its maximum speed.
+pub const DEFAULT_MOUSE_KEYS_ACCELERATION_TIME_MS: u32 = 1000;
+/// The default number of milliseconds between two scroll steps while a scroll key is
+/// held.
+pub const DEFAULT_MOUSE_KEYS_SCROLL_INTERVAL_MS: u32 = 100;
+
+/// The settings of the mouse-keys mode.
+///
+/// While mouse keys are enabled, the keys mentioned in `keys` no longer reach
+/// applications or shortcuts. Instead they move the pointer, press buttons, and
+/// scroll.
+///
+/// The default uses the numpad in the same way as the mouse keys of X11:
+///
+/// | Key             | Action                |
+/// |-----------------|-----------------------|
+/// | `1`-`4`,`6`-`9` | move in the direction |
+/// | `5`             | click                 |
+/// | `+`             | double-click          |
+/// | `0`             | press (drag-lock)     |
+/// | `.`             | release               |
+/// | `/`             | select left button    |
+/// | `*`             | select middle button  |
+/// | `-`             | select right button   |
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MouseKeys {
+    /// The actions of the keys.
+    pub keys: Vec<(u32, MouseKeysAction)>,
+    /// The initial speed of the pointer in pixels per second.
+    pub speed: f64,
+    /// The maximum speed of the pointer in pixels per second.
+    pub max_speed: f64,
+    /// The number of milliseconds until the pointer reaches its maximum speed.
+    pub acceleration_time_ms: u32,
+    /// The number of milliseconds between two scroll steps while a scroll key is held.
+    pub scroll_interval_ms: u32,
+}
+
+impl Default for MouseKeys {
+    fn default() -> Self {
+        use MouseKeysAction::*;
+        let keys = [
+            (KEY_KP1, MoveDownLeft),
+            (KEY_KP2, MoveDown),
+            (KEY_KP3, MoveDownRight),
+            (KEY_KP4, MoveLeft),
+            (KEY_KP6, MoveRight),
+            (KEY_KP7, MoveUpLeft),
+            (KEY_KP8, MoveUp),
+            (KEY_KP9, MoveUpRight),
+            (KEY_KP5, Click),
+            (KEY_KPPLUS, DoubleClick),
+            (KEY_KP0, Press),
+            (KEY_KPDOT, Release),
+            (KEY_KPSLASH, SelectLeftButton),
+            (KEY_KPASTERISK, SelectMiddleButton),
+            (KEY_KPMINUS, SelectRightButton),
+        ];
+        Self {
+            keys: keys.iter().map(|(k, a)| (k.0, *a)).collect(),
+            speed: DEFAULT_MOUSE_KEYS_SPEED,
+            max_speed: DEFAULT_MOUSE_KEYS_MAX_SPEED,
+            acceleration_time_ms: DEFAULT_MOUSE_KEYS_ACCELERATION_TIME_MS,
+            scroll_interval_ms: DEFAULT_MOUSE_KEYS_SCROLL_INTERVAL_MS,
+        }
+    }
+}
+
+/// The action performed by a key while mouse keys are enabled.
+#[non_exhaustive]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MouseKeysAction {
+    /// Moves the pointer left while the key is held.
+    MoveLeft,
+    /// Moves the pointer right while the key is held.
+    MoveRight,
+    /// Moves the pointer up while the key is held.
+    MoveUp,
+    /// Moves the pointer down while the key is held.
+    MoveDown,
+    /// Moves the pointer up and left while the key is held.
+    MoveUpLeft,
+    /// Moves the pointer up and right while the key is held.
+    MoveUpRight,
+    /// Moves the pointer down and left while the key is held.
+    MoveDownLeft,
+    /// Moves the pointer down and right while the key is held.
+    MoveDownRight,
+    /// Clicks the selected button.
+    Click,
+    /// Clicks the selected button twice.
+    DoubleClick,
+    /// Presses the selected button and keeps it pressed until [`Self::Release`] is
+    /// used.
+    Press,
+    /// Releases the buttons pressed with [`Self::Press`].
+    Release,
+    /// Selects the left button for subsequent clicks.
+    SelectLeftButton,
+    /// Selects the middle button for subsequent clicks.
+    SelectMiddleButton,
+    /// Selects the right button for subsequent clicks.
+    SelectRightButton,
+    /// Scrolls up while the key is held.
+    ScrollUp,
+    /// Scrolls down while the key is held.
+    ScrollDown,
+    /// Scrolls left while the key is held.
+    ScrollLeft,
+    /// Scrolls right while the key is held.
+    ScrollRight,
+}
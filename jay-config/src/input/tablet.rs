@@ -0,0 +1,80 @@
+//! Settings and events of graphics tablets.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The type of a tablet tool.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct TabletToolType(pub u32);
+
+/// A pen.
+pub const TABLET_TOOL_TYPE_PEN: TabletToolType = TabletToolType(0);
+/// The eraser end of a pen.
+pub const TABLET_TOOL_TYPE_ERASER: TabletToolType = TabletToolType(1);
+/// A brush.
+pub const TABLET_TOOL_TYPE_BRUSH: TabletToolType = TabletToolType(2);
+/// A pencil.
+pub const TABLET_TOOL_TYPE_PENCIL: TabletToolType = TabletToolType(3);
+/// An airbrush.
+pub const TABLET_TOOL_TYPE_AIRBRUSH: TabletToolType = TabletToolType(4);
+/// A mouse bound to the tablet.
+pub const TABLET_TOOL_TYPE_MOUSE: TabletToolType = TabletToolType(5);
+/// A mouse tool with a lens.
+pub const TABLET_TOOL_TYPE_LENS: TabletToolType = TabletToolType(6);
+
+/// How the eraser end of a pen is reported to applications.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct EraserMode(pub u32);
+
+/// The eraser is reported as a separate eraser tool.
+///
+/// This is the default.
+pub const ERASER_MODE_ERASER: EraserMode = EraserMode(0);
+/// The eraser is reported as a pen.
+///
+/// This is useful for applications that do not support erasers.
+pub const ERASER_MODE_PEN: EraserMode = EraserMode(1);
+
+/// A pressure curve.
+///
+/// The curve is a cubic bézier curve from `(0, 0)` to `(1, 1)` with the control points
+/// `(x1, y1)` and `(x2, y2)`. The x axis is the pressure reported by the hardware and
+/// the y axis is the pressure reported to applications. All values should be in the
+/// range `[0, 1]`.
+///
+/// The default is the linear curve `(0, 0)`, `(1, 1)`.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub struct PressureCurve {
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+}
+
+impl Default for PressureCurve {
+    fn default() -> Self {
+        Self {
+            x1: 0.0,
+            y1: 0.0,
+            x2: 1.0,
+            y2: 1.0,
+        }
+    }
+}
+
+/// An event of a tablet pad that was not handled by an application.
+///
+/// These events are only generated if the focused application does not use the tablet
+/// protocol.
+#[non_exhaustive]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum TabletPadEvent {
+    /// A button was pressed or released.
+    Button { button: u32, pressed: bool },
+    /// A ring was rotated by one step.
+    Ring { ring: u32, clockwise: bool },
+    /// A finger moved along a strip by one step.
+    Strip { strip: u32, up: bool },
+    /// A dial was rotated by one step.
+    Dial { dial: u32, clockwise: bool },
+}
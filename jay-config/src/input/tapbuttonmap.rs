@@ -0,0 +1,16 @@
+//! Constants determining the button mapping of multi-finger taps.
+//!
+//! See the libinput documentation for details.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The button mapping of 1, 2, and 3-finger taps.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct TapButtonMap(pub u32);
+
+/// 1, 2, 3 finger taps map to left, right, middle buttons.
+pub const TAP_BUTTON_MAP_LEFT_RIGHT_MIDDLE: TapButtonMap = TapButtonMap(0);
+
+/// 1, 2, 3 finger taps map to left, middle, right buttons.
+pub const TAP_BUTTON_MAP_LEFT_MIDDLE_RIGHT: TapButtonMap = TapButtonMap(1);
@@ -198,6 +198,74 @@ pub fn get_bar_position() -> BarPosition {
     get!(BarPosition::Top).get_bar_position()
 }
 
+/// The kind of a built-in bar element.
+#[non_exhaustive]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BarElementKind {
+    /// The title of the window that has the keyboard focus.
+    FocusedWindowTitle,
+    /// The app-id of the window that has the keyboard focus.
+    FocusedWindowAppId,
+    /// The stack of active input modes.
+    InputModes,
+    /// The name of the active keyboard layout.
+    KeyboardLayout,
+    /// The state of the caps lock and num lock LEDs.
+    LockIndicators,
+    /// An indicator that is shown while the screen is being shared.
+    ScreenShare,
+    /// The modifiers that are latched or locked by sticky keys.
+    StickyKeys,
+}
+
+/// A built-in bar element.
+///
+/// Built-in elements are displayed to the right of the workspaces.
+#[non_exhaustive]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct BarElement {
+    /// The kind of the element.
+    pub kind: BarElementKind,
+    /// The text color of the element.
+    ///
+    /// If this is not set, the bar text color is used.
+    pub color: Option<Color>,
+}
+
+impl BarElement {
+    /// Creates a new bar element with the default color.
+    pub fn new(kind: BarElementKind) -> Self {
+        Self { kind, color: None }
+    }
+
+    /// Sets the text color of the element.
+    pub fn with_color(self, color: Color) -> Self {
+        Self {
+            color: Some(color),
+            ..self
+        }
+    }
+}
+
+/// Sets the built-in elements displayed in the bar.
+///
+/// The elements are displayed in the order given. Elements without content, for
+/// example the screen share indicator while nothing is being shared, are omitted.
+///
+/// By default, no built-in elements are displayed.
+pub fn set_bar_elements(elements: &[BarElement]) {
+    get!().set_bar_elements(elements);
+}
+
+/// Sets the separator between built-in bar elements.
+///
+/// The separator should be specified in [pango][pango] markup language.
+///
+/// [pango]: https://docs.gtk.org/Pango/pango_markup.html
+pub fn set_bar_element_separator(separator: &str) {
+    get!().set_bar_element_separator(separator);
+}
+
 #[non_exhaustive]
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
 pub enum ContainerBorders {
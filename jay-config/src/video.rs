@@ -64,6 +64,15 @@ impl Mode {
         self.refresh_millihz
     }
 
+    /// Creates a new mode.
+    pub fn new(width: i32, height: i32, refresh_millihz: u32) -> Self {
+        Self {
+            width,
+            height,
+            refresh_millihz,
+        }
+    }
+
     pub(crate) fn zeroed() -> Self {
         Self {
             width: 0,
@@ -111,6 +120,8 @@ impl Connector {
     }
 
     /// Sets the scale to use for the currently connected monitor.
+    ///
+    /// This overrides the scale computed via [set_auto_scale].
     pub fn set_scale(self, scale: f64) {
         if !self.exists() {
             return;
@@ -171,6 +182,140 @@ impl Connector {
         )
     }
 
+    /// Sets a mode that is not necessarily advertised by the connector.
+    ///
+    /// The timings of the mode are generated with the VESA Coordinated Video Timings
+    /// (CVT) formula. If `reduced_blanking` is set, the CVT reduced blanking timings
+    /// are used instead. These require a lower pixel clock and are usually preferable
+    /// for digital displays.
+    ///
+    /// The actual refresh rate of the mode can deviate slightly from the requested
+    /// refresh rate. The mode is tested before it is applied. If the display or the
+    /// graphics card does not support it, the current mode is kept.
+    ///
+    /// This can be used with monitors that have a broken EDID or to run displays at
+    /// refresh rates that they do not advertise. Only connectors of the DRM backend
+    /// support custom timings.
+    pub fn set_cvt_mode(
+        self,
+        width: i32,
+        height: i32,
+        refresh_millihz: u32,
+        reduced_blanking: bool,
+    ) {
+        if !self.exists() {
+            log::warn!("set_cvt_mode called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_cvt_mode(self, width, height, refresh_millihz, reduced_blanking);
+    }
+
+    /// Sets a mode from an X11-style modeline.
+    ///
+    /// The modeline has the format
+    ///
+    /// ```text
+    /// [Modeline ["name"]] clock hdisp hsyncstart hsyncend htotal vdisp vsyncstart vsyncend vtotal [flags...]
+    /// ```
+    ///
+    /// where the pixel clock is specified in MHz and the flags are `+hsync`, `-hsync`,
+    /// `+vsync`, `-vsync`, `interlace`, or `doublescan`. This is the format printed by
+    /// tools such as `cvt` and `gtf`.
+    ///
+    /// See [`Connector::set_cvt_mode`] for how the mode is applied.
+    pub fn set_modeline(self, modeline: &str) {
+        if !self.exists() {
+            log::warn!("set_modeline called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_modeline(self, modeline);
+    }
+
+    /// Replaces the EDID of the display connected to this connector.
+    ///
+    /// The path must point to a binary EDID blob, for example one that was dumped with
+    /// `jay randr output <output> edid dump` and then edited. The blob is used instead
+    /// of the EDID reported by the display for the name, manufacturer, and serial
+    /// number of the display, its physical size, its color and HDR capabilities, and
+    /// its modes. The modes are taken from the detailed timing descriptors and the CTA
+    /// video data blocks of the blob. If the blob does not contain any usable timings,
+    /// the modes of the display are used.
+    ///
+    /// The display is reconnected when the override changes. Since the override can
+    /// change the serial number, output settings that match on it are evaluated again.
+    ///
+    /// If the path is `None`, the override is removed. Only connectors of the DRM
+    /// backend support EDID overrides.
+    pub fn set_edid_override(self, path: Option<&str>) {
+        if !self.exists() {
+            log::warn!("set_edid_override called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_edid_override(self, path);
+    }
+
+    /// Serves the contents of the output connected to this connector via VNC.
+    ///
+    /// The address is either the name of a unix socket that is created in
+    /// `$XDG_RUNTIME_DIR`, a port such as `5900` that is bound to `127.0.0.1`, or a
+    /// loopback TCP address such as `[::1]:5900`. Since the server does not support
+    /// authentication, unix sockets are only accessible to the current user and TCP
+    /// addresses must be loopback addresses.
+    ///
+    /// Keyboard and pointer events of VNC clients are delivered via a dedicated seat
+    /// named `vnc-<connector>`.
+    ///
+    /// If the address is `None`, the server is stopped. The server keeps running while
+    /// the connector is disconnected.
+    pub fn set_vnc_server(self, address: Option<&str>) {
+        if !self.exists() {
+            log::warn!("set_vnc_server called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_vnc_server(self, address);
+    }
+
+    /// Returns the brightness of the display attached to this connector in percent.
+    ///
+    /// Returns `None` if the brightness of the display cannot be controlled.
+    ///
+    /// See [Connector::set_backlight].
+    pub fn backlight(self) -> Option<f64> {
+        if !self.exists() {
+            return None;
+        }
+        get!(None).connector_get_backlight(self)
+    }
+
+    /// Sets the brightness of the display attached to this connector in percent.
+    ///
+    /// For internal panels, this changes the brightness of the backlight via logind.
+    /// For outputs that use the PQ transfer function, this instead changes the
+    /// luminance of SDR content. See [Connector::set_brightness].
+    ///
+    /// If enabled, an on-screen indicator shows the new brightness. See
+    /// [set_backlight_indicator_enabled].
+    pub fn set_backlight(self, percent: f64) {
+        if !self.exists() {
+            log::warn!("set_backlight called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_backlight(self, percent, false);
+    }
+
+    /// Changes the brightness of the display attached to this connector by the given
+    /// number of percentage points.
+    ///
+    /// Unlike [Connector::set_backlight], this never turns the backlight off
+    /// completely.
+    pub fn adjust_backlight(self, delta: f64) {
+        if !self.exists() {
+            log::warn!("adjust_backlight called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_backlight(self, delta, true);
+    }
+
     /// Returns the available modes of the connector.
     pub fn modes(self) -> Vec<Mode> {
         if !self.exists() {
@@ -252,6 +397,32 @@ impl Connector {
         get!().connector_set_enabled(self, enabled);
     }
 
+    /// Turns the display attached to this connector on or off.
+    ///
+    /// Unlike [Connector::set_enabled], this does not change the layout. The output keeps
+    /// its workspaces and windows but nothing is displayed. The output stays off until
+    /// this function is called again with `true`, even if the compositor wakes up from
+    /// idle in the meantime.
+    ///
+    /// By default, all displays are on.
+    pub fn set_dpms(self, on: bool) {
+        if !self.exists() {
+            log::warn!("set_dpms called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_dpms(self, on);
+    }
+
+    /// Returns whether the display attached to this connector has been turned on.
+    ///
+    /// See [Connector::set_dpms].
+    pub fn dpms(self) -> bool {
+        if !self.exists() {
+            return true;
+        }
+        get!(true).connector_get_dpms(self)
+    }
+
     /// Sets the transformation to apply to the content of this connector.
     pub fn set_transform(self, transform: Transform) {
         if !self.exists() {
@@ -399,6 +570,38 @@ impl Connector {
         get!().connector_set_use_native_gamut(self, use_native_gamut);
     }
 
+    /// Sets the ICC profile of the display.
+    ///
+    /// The path must point to an ICC profile of the display, for example one created
+    /// with a colorimeter. Only matrix/TRC profiles are supported. Profiles that only
+    /// contain lookup tables are rejected. The primaries and tone curves of the
+    /// profile are used instead of the primaries and transfer function that Jay would
+    /// otherwise assume.
+    ///
+    /// The profile is loaded asynchronously. Errors are logged by the compositor.
+    ///
+    /// Tone curves that cannot be represented exactly by a supported transfer function,
+    /// including per-channel curves, are corrected with the gamma lookup table of the
+    /// display if it has one. The profile takes precedence over
+    /// [`Connector::set_use_native_gamut`]. It has no effect when the display is
+    /// explicitly operating in a wide color space or with a non-default transfer
+    /// function.
+    ///
+    /// If the path is `None`, the profile is removed.
+    pub fn set_icc_profile(self, path: Option<&str>) {
+        get!().connector_set_icc_profile(self, path);
+    }
+
+    /// Sets the width, in logical pixels, of the zones at the edges of this output
+    /// in which touchscreen edge gestures start.
+    ///
+    /// See [`Seat::bind_touch_gesture`](crate::input::Seat::bind_touch_gesture).
+    ///
+    /// The default is `20`.
+    pub fn set_touch_edge_width(self, width: i32) {
+        get!().connector_set_touch_edge_width(self, width);
+    }
+
     /// Sets the scaling filter of the output.
     ///
     /// The default is [`ScalingFilter::LINEAR`]
@@ -437,6 +640,48 @@ pub fn on_connector_disconnected<F: FnMut(Connector) + 'static>(f: F) {
     get!().on_connector_disconnected(f)
 }
 
+/// A set of changes to apply to a connector with [configure_connectors].
+///
+/// Fields that are `None` are left unchanged.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq)]
+pub struct ConnectorConfiguration {
+    /// Whether the connector is enabled.
+    pub enabled: Option<bool>,
+    /// The mode of the connector.
+    pub mode: Option<Mode>,
+    /// The position of the output in compositor space.
+    pub position: Option<(i32, i32)>,
+    /// The scale of the output.
+    pub scale: Option<f64>,
+    /// The transform of the output.
+    pub transform: Option<Transform>,
+}
+
+/// Applies changes to multiple connectors at once.
+///
+/// Changes to the enabled state and the mode of the connectors are committed in a
+/// single transaction. If any of them cannot be applied, none of the changes are
+/// applied.
+pub fn configure_connectors(configurations: &[(Connector, ConnectorConfiguration)]) {
+    get!().configure_connectors(configurations)
+}
+
+/// Sets the list of output profiles known to the configuration.
+///
+/// The compositor does not interpret these names. They are shown by `jay randr profile
+/// list` and can be applied with `jay randr profile apply`, which invokes the callback
+/// set with [on_apply_output_profile].
+pub fn set_output_profiles(profiles: &[&str], active: Option<&str>) {
+    get!().set_output_profiles(profiles, active)
+}
+
+/// Sets the callback to be called when an output profile should be applied.
+///
+/// This is invoked by `jay randr profile apply`.
+pub fn on_apply_output_profile<F: FnMut(String) + 'static>(f: F) {
+    get!().on_apply_output_profile(f)
+}
+
 /// Sets the callback to be called when the graphics of the compositor have been initialized.
 ///
 /// This callback is only invoked once during the lifetime of the compositor. This is a good place
@@ -788,6 +1033,83 @@ pub fn set_tearing_mode(mode: TearingMode) {
     get!().set_tearing_mode(None, mode)
 }
 
+/// Parameters for automatically computing the scale of outputs.
+///
+/// See [set_auto_scale].
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub struct AutoScale {
+    /// The target DPI of the logical output.
+    ///
+    /// The default is 96.
+    pub dpi: f64,
+    /// The granularity of the computed scale.
+    ///
+    /// The computed scale is rounded to a multiple of this value. The default is 0.25.
+    pub step: f64,
+}
+
+impl Default for AutoScale {
+    fn default() -> Self {
+        Self {
+            dpi: 96.0,
+            step: 0.25,
+        }
+    }
+}
+
+/// Enables or disables the automatic computation of output scales.
+///
+/// If this is enabled, the scale of outputs that are connected for the first time is
+/// computed from the physical size reported by the EDID and the resolution of the
+/// current mode. The scale is never less than 1. Outputs that do not report a
+/// physical size use a scale of 1.
+///
+/// Scales set explicitly via [Connector::set_scale] take precedence.
+///
+/// By default, this is disabled.
+pub fn set_auto_scale(auto_scale: Option<AutoScale>) {
+    get!().set_auto_scale(auto_scale)
+}
+
+/// Enables or disables the on-screen indicator shown when the brightness changes.
+///
+/// See [Connector::set_backlight].
+///
+/// By default, this is enabled.
+pub fn set_backlight_indicator_enabled(enabled: bool) {
+    get!().set_backlight_indicator_enabled(enabled)
+}
+
+/// Enables or disables the automatic rotation of built-in panels.
+///
+/// If this is enabled, the transform of built-in panels follows the orientation
+/// reported by the accelerometer. The orientation is retrieved from iio-sensor-proxy
+/// via D-Bus.
+///
+/// Touchscreens and tablets that are mapped to a rotated panel via
+/// [`InputDevice::set_connector`](crate::input::InputDevice::set_connector) are rotated
+/// together with the panel.
+///
+/// By default, this is disabled.
+pub fn set_auto_rotation_enabled(enabled: bool) {
+    get!().set_auto_rotation_enabled(enabled)
+}
+
+/// Locks or unlocks the rotation of built-in panels.
+///
+/// While the rotation is locked, orientation changes are ignored. When the rotation is
+/// unlocked, the current orientation is applied.
+///
+/// See [set_auto_rotation_enabled].
+pub fn set_rotation_locked(locked: bool) {
+    get!().set_rotation_locked(locked)
+}
+
+/// Returns whether the rotation of built-in panels is locked.
+pub fn rotation_locked() -> bool {
+    get!(false).get_rotation_locked()
+}
+
 /// Creates a virtual output with the given name.
 ///
 /// This is a no-op if a virtual output with that name already exists.
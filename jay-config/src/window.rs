@@ -66,6 +66,18 @@ pub enum TileState {
     Floating,
 }
 
+/// Whether a window may inhibit the compositor's keyboard shortcuts.
+#[non_exhaustive]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum ShortcutsInhibit {
+    /// Requests to inhibit shortcuts are granted.
+    Allow,
+    /// Requests to inhibit shortcuts are ignored.
+    Deny,
+    /// The user is asked whether to grant the request.
+    Ask,
+}
+
 /// A window created by a client.
 ///
 /// This is the same as `XDG_TOPLEVEL | X_WINDOW`.
@@ -376,6 +388,18 @@ impl WindowCriterion<'_> {
     pub fn set_initial_tile_state(self, tile_state: TileState) {
         self.to_matcher().set_initial_tile_state(tile_state);
     }
+
+    /// Sets whether windows that match this criterion may inhibit the compositor's
+    /// keyboard shortcuts.
+    ///
+    /// If no matcher matches a window, the user is asked.
+    ///
+    /// If multiple such window matchers match a window, the used value is unspecified.
+    ///
+    /// This leaks the matcher.
+    pub fn set_shortcuts_inhibit(self, inhibit: ShortcutsInhibit) {
+        self.to_matcher().set_shortcuts_inhibit(inhibit);
+    }
 }
 
 impl WindowMatcher {
@@ -409,6 +433,16 @@ impl WindowMatcher {
     pub fn set_initial_tile_state(self, tile_state: TileState) {
         get!().set_window_matcher_initial_tile_state(self, tile_state);
     }
+
+    /// Sets whether windows that match this matcher may inhibit the compositor's
+    /// keyboard shortcuts.
+    ///
+    /// If no matcher matches a window, the user is asked.
+    ///
+    /// If multiple such window matchers match a window, the used value is unspecified.
+    pub fn set_shortcuts_inhibit(self, inhibit: ShortcutsInhibit) {
+        get!().set_window_matcher_shortcuts_inhibit(self, inhibit);
+    }
 }
 
 impl MatchedWindow {
@@ -0,0 +1,326 @@
+#[cfg(test)]
+mod tests;
+
+use crate::async_engine::SpawnedFuture;
+use crate::dbus::BUS_DEST;
+use crate::dbus::BUS_PATH;
+use crate::dbus::DbusSocket;
+use crate::dbus::FALSE;
+use crate::dbus::SignalHandler;
+use crate::env::JAY_SENSOR_PROXY_SESSION_BUS;
+use crate::state::State;
+use crate::tree::OutputNode;
+use crate::tree::Transform;
+use crate::utils::clonecell::CloneCell;
+use crate::utils::errorfmt::ErrorFmt;
+use crate::wire_dbus::net;
+use crate::wire_dbus::org::freedesktop::dbus::NameOwnerChanged;
+use crate::wire_dbus::org::freedesktop::dbus::properties::PropertiesChanged;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::rc::Weak;
+
+const SENSOR_PROXY_NAME: &str = "net.hadess.SensorProxy";
+const SENSOR_PROXY_PATH: &str = "/net/hadess/SensorProxy";
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Orientation {
+    Normal,
+    BottomUp,
+    LeftUp,
+    RightUp,
+}
+
+impl Orientation {
+    fn parse(s: &str) -> Option<Self> {
+        let o = match s {
+            "normal" => Self::Normal,
+            "bottom-up" => Self::BottomUp,
+            "left-up" => Self::LeftUp,
+            "right-up" => Self::RightUp,
+            _ => return None,
+        };
+        Some(o)
+    }
+
+    pub fn transform(self) -> Transform {
+        match self {
+            Self::Normal => Transform::None,
+            Self::BottomUp => Transform::Rotate180,
+            Self::LeftUp => Transform::Rotate90,
+            Self::RightUp => Transform::Rotate270,
+        }
+    }
+}
+
+/// Maps normalized coordinates of a device that is attached to a panel with the given
+/// transform to normalized coordinates of the output.
+pub fn transform_normalized(transform: Transform, x: f64, y: f64) -> (f64, f64) {
+    match transform {
+        Transform::None => (x, y),
+        Transform::Rotate90 => (1.0 - y, x),
+        Transform::Rotate180 => (1.0 - x, 1.0 - y),
+        Transform::Rotate270 => (y, 1.0 - x),
+        Transform::Flip => (1.0 - x, y),
+        Transform::FlipRotate90 => (y, x),
+        Transform::FlipRotate180 => (x, 1.0 - y),
+        Transform::FlipRotate270 => (1.0 - y, 1.0 - x),
+    }
+}
+
+#[derive(Default)]
+pub struct AutoRotation {
+    enabled: Cell<bool>,
+    locked: Cell<bool>,
+    orientation: Cell<Option<Orientation>>,
+    task: Cell<Option<SpawnedFuture<()>>>,
+    socket: CloneCell<Option<Rc<DbusSocket>>>,
+    signal_handler: Cell<Option<SignalHandler>>,
+    owner_handler: Cell<Option<SignalHandler>>,
+}
+
+impl AutoRotation {
+    pub fn locked(&self) -> bool {
+        self.locked.get()
+    }
+
+    pub fn set_enabled(&self, state: &Rc<State>, enabled: bool) {
+        if self.enabled.replace(enabled) == enabled {
+            return;
+        }
+        if enabled {
+            let future = state
+                .eng
+                .spawn("auto rotation", connect(Rc::downgrade(state)));
+            self.task.set(Some(future));
+        } else {
+            self.disconnect();
+            for output in state.root.outputs.lock().values() {
+                output.set_sensor_rotation(Transform::None);
+            }
+        }
+    }
+
+    pub fn set_locked(&self, state: &State, locked: bool) {
+        if self.locked.replace(locked) != locked && !locked {
+            self.apply(state);
+        }
+    }
+
+    pub fn output_connected(&self, output: &Rc<OutputNode>) {
+        if self.enabled.get()
+            && !self.locked.get()
+            && let Some(orientation) = self.orientation.get()
+            && is_rotated_output(output)
+        {
+            output.set_sensor_rotation(orientation.transform());
+        }
+    }
+
+    fn apply(&self, state: &State) {
+        if !self.enabled.get() || self.locked.get() {
+            return;
+        }
+        let Some(orientation) = self.orientation.get() else {
+            return;
+        };
+        for output in state.root.outputs.lock().values() {
+            if is_rotated_output(output) {
+                output.set_sensor_rotation(orientation.transform());
+            }
+        }
+    }
+
+    fn set_orientation(&self, state: &State, orientation: &str) {
+        let orientation = Orientation::parse(orientation);
+        log::debug!("Accelerometer orientation: {:?}", orientation);
+        if orientation.is_none() || self.orientation.replace(orientation) == orientation {
+            return;
+        }
+        self.apply(state);
+    }
+
+    fn update_orientation(&self, state: &Rc<State>) {
+        let Some(socket) = self.socket.get() else {
+            return;
+        };
+        let state = Rc::downgrade(state);
+        socket.get::<net::hadess::sensor_proxy::AccelerometerOrientation, _>(
+            SENSOR_PROXY_NAME,
+            SENSOR_PROXY_PATH,
+            move |res| match res {
+                Ok(o) => {
+                    if let Some(state) = state.upgrade() {
+                        state.auto_rotation.set_orientation(&state, o);
+                    }
+                }
+                Err(e) => {
+                    log::error!("Could not retrieve the orientation: {}", ErrorFmt(e));
+                }
+            },
+        );
+    }
+
+    fn disconnect(&self) {
+        self.task.take();
+        self.signal_handler.take();
+        self.owner_handler.take();
+        if let Some(socket) = self.socket.take() {
+            socket.call_noreply(
+                SENSOR_PROXY_NAME,
+                SENSOR_PROXY_PATH,
+                net::hadess::sensor_proxy::ReleaseAccelerometer,
+            );
+        }
+        self.orientation.take();
+    }
+
+    /// Called if the connection to the bus could not be established so that enabling
+    /// auto-rotation again retries.
+    fn connect_failed(&self) {
+        self.enabled.set(false);
+        self.signal_handler.take();
+        self.owner_handler.take();
+        self.socket.take();
+    }
+
+    fn sensor_proxy_owner_changed(&self, state: &Rc<State>, new_owner: &str) {
+        let Some(socket) = self.socket.get() else {
+            return;
+        };
+        if new_owner.is_empty() {
+            log::warn!("iio-sensor-proxy has disappeared");
+            self.task.take();
+            return;
+        }
+        log::info!("iio-sensor-proxy has appeared");
+        let future = state
+            .eng
+            .spawn("auto rotation claim", claim(Rc::downgrade(state), socket));
+        self.task.set(Some(future));
+    }
+
+    pub fn clear(&self) {
+        self.disconnect();
+    }
+}
+
+fn is_rotated_output(output: &OutputNode) -> bool {
+    output
+        .global
+        .connector
+        .connector
+        .kernel_id()
+        .ty
+        .is_internal()
+}
+
+async fn connect(state: Weak<State>) {
+    let Some(slf) = state.upgrade() else {
+        return;
+    };
+    let socket = match *JAY_SENSOR_PROXY_SESSION_BUS {
+        true => slf.dbus.session().await,
+        false => slf.dbus.system().await,
+    };
+    let socket = match socket {
+        Ok(s) => s,
+        Err(e) => {
+            log::error!("Could not connect to dbus: {}", ErrorFmt(e));
+            slf.auto_rotation.connect_failed();
+            return;
+        }
+    };
+    let owner_handler =
+        socket.handle_signal::<NameOwnerChanged, _>(Some(BUS_DEST), Some(BUS_PATH), {
+            let state = state.clone();
+            move |signal| {
+                if signal.name != SENSOR_PROXY_NAME {
+                    return;
+                }
+                if let Some(state) = state.upgrade() {
+                    state
+                        .auto_rotation
+                        .sensor_proxy_owner_changed(&state, &signal.new_owner);
+                }
+            }
+        });
+    let owner_handler = match owner_handler {
+        Ok(h) => h,
+        Err(e) => {
+            log::error!(
+                "Could not listen for iio-sensor-proxy restarts: {}",
+                ErrorFmt(e)
+            );
+            slf.auto_rotation.connect_failed();
+            return;
+        }
+    };
+    let handler = socket.handle_signal::<PropertiesChanged, _>(
+        Some(SENSOR_PROXY_NAME),
+        Some(SENSOR_PROXY_PATH),
+        {
+            let state = state.clone();
+            move |signal| {
+                if signal.interface_name != SENSOR_PROXY_NAME {
+                    return;
+                }
+                if let Some(state) = state.upgrade() {
+                    state.auto_rotation.update_orientation(&state);
+                }
+            }
+        },
+    );
+    let handler = match handler {
+        Ok(h) => h,
+        Err(e) => {
+            log::error!("Could not listen for orientation changes: {}", ErrorFmt(e));
+            slf.auto_rotation.connect_failed();
+            return;
+        }
+    };
+    let ar = &slf.auto_rotation;
+    ar.signal_handler.set(Some(handler));
+    ar.owner_handler.set(Some(owner_handler));
+    ar.socket.set(Some(socket.clone()));
+    drop(slf);
+    claim(state, socket).await;
+}
+
+/// Claims the accelerometer. If this fails because iio-sensor-proxy is not running,
+/// the claim is retried when the service appears on the bus.
+async fn claim(state: Weak<State>, socket: Rc<DbusSocket>) {
+    let res = socket
+        .call_async(
+            SENSOR_PROXY_NAME,
+            SENSOR_PROXY_PATH,
+            net::hadess::sensor_proxy::ClaimAccelerometer,
+        )
+        .await;
+    if let Err(e) = res {
+        log::error!("Could not claim the accelerometer: {}", ErrorFmt(e));
+        log::info!("Will retry when iio-sensor-proxy appears on the bus");
+        return;
+    }
+    let has_accelerometer = socket
+        .get_async::<net::hadess::sensor_proxy::HasAccelerometer>(
+            SENSOR_PROXY_NAME,
+            SENSOR_PROXY_PATH,
+        )
+        .await;
+    match has_accelerometer {
+        Ok(v) if *v.get() == FALSE => {
+            log::warn!("The system does not have an accelerometer");
+        }
+        Ok(_) => {}
+        Err(e) => {
+            log::error!(
+                "Could not determine if the system has an accelerometer: {}",
+                ErrorFmt(e),
+            );
+        }
+    }
+    if let Some(state) = state.upgrade() {
+        state.auto_rotation.update_orientation(&state);
+    }
+}
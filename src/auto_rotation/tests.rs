@@ -0,0 +1,80 @@
+use crate::auto_rotation::Orientation;
+use crate::auto_rotation::transform_normalized;
+use crate::tree::Transform;
+
+#[test]
+fn orientation() {
+    assert_eq!(Orientation::parse("normal"), Some(Orientation::Normal));
+    assert_eq!(Orientation::parse("left-up"), Some(Orientation::LeftUp));
+    assert_eq!(Orientation::parse("undefined"), None);
+    assert_eq!(Orientation::BottomUp.transform(), Transform::Rotate180);
+    assert_eq!(Orientation::LeftUp.transform(), Transform::Rotate90);
+    assert_eq!(Orientation::RightUp.transform(), Transform::Rotate270);
+}
+
+#[test]
+fn normalized() {
+    let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+    let map = |t: Transform| corners.map(|(x, y)| transform_normalized(t, x, y));
+    assert_eq!(map(Transform::None), corners);
+    // The top-left corner of the panel is the top-right corner of the rotated output.
+    assert_eq!(
+        map(Transform::Rotate90),
+        [(1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)],
+    );
+    assert_eq!(
+        map(Transform::Rotate180),
+        [(1.0, 1.0), (0.0, 1.0), (0.0, 0.0), (1.0, 0.0)],
+    );
+    assert_eq!(
+        map(Transform::Rotate270),
+        [(0.0, 1.0), (0.0, 0.0), (1.0, 0.0), (1.0, 1.0)],
+    );
+    for t in [
+        Transform::Flip,
+        Transform::FlipRotate90,
+        Transform::FlipRotate180,
+        Transform::FlipRotate270,
+    ] {
+        let mut mapped = map(t);
+        mapped.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mut expected = corners;
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(mapped, expected);
+    }
+}
+
+#[test]
+fn composition() {
+    use Transform::*;
+    let all = [
+        None,
+        Rotate90,
+        Rotate180,
+        Rotate270,
+        Flip,
+        FlipRotate90,
+        FlipRotate180,
+        FlipRotate270,
+    ];
+    for t in all {
+        assert_eq!(None.then(t), t);
+        assert_eq!(t.then(None), t);
+        assert_eq!(t.then(t.inverse()), None, "{t:?}");
+    }
+    // Rotations add up and flipping reverses the direction of earlier rotations.
+    assert_eq!(Rotate90.then(Rotate90), Rotate180);
+    assert_eq!(Rotate270.then(Rotate180), Rotate90);
+    assert_eq!(Flip.then(Rotate90), FlipRotate90);
+    assert_eq!(Rotate90.then(Flip), FlipRotate270);
+    // Composition agrees with mapping points one transform after another.
+    let (w, h) = (4, 4);
+    for a in all {
+        for b in all {
+            for p in [(0, 0), (1, 0), (0, 2)] {
+                let expected = b.apply_point(w, h, a.apply_point(w, h, p));
+                assert_eq!(a.then(b).apply_point(w, h, p), expected, "{a:?} {b:?}");
+            }
+        }
+    }
+}
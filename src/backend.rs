@@ -3,6 +3,7 @@ use crate::backend::transaction::BackendConnectorTransaction;
 use crate::backend::transaction::BackendConnectorTransactionError;
 use crate::backend::transaction::BackendConnectorTransactionType;
 use crate::backend::transaction::BackendConnectorTransactionTypeDyn;
+use crate::backlight::Backlight;
 use crate::cmm::cmm_primaries::Primaries;
 use crate::evdev::input_event_codes::InputEventCode;
 use crate::fixed::Fixed;
@@ -27,11 +28,18 @@ use crate::ifs::wl_seat::wl_pointer::HORIZONTAL_SCROLL;
 use crate::ifs::wl_seat::wl_pointer::VERTICAL_SCROLL;
 use crate::ifs::wl_seat::wl_pointer::WHEEL;
 use crate::libinput::consts::ConfigScrollMethod;
+use crate::libinput::consts::ConfigSendEventsMode;
+use crate::libinput::consts::ConfigTapButtonMap;
 use crate::libinput::consts::DeviceCapability;
 use crate::libinput::consts::LIBINPUT_CONFIG_SCROLL_2FG;
 use crate::libinput::consts::LIBINPUT_CONFIG_SCROLL_EDGE;
 use crate::libinput::consts::LIBINPUT_CONFIG_SCROLL_NO_SCROLL;
 use crate::libinput::consts::LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN;
+use crate::libinput::consts::LIBINPUT_CONFIG_SEND_EVENTS_DISABLED;
+use crate::libinput::consts::LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE;
+use crate::libinput::consts::LIBINPUT_CONFIG_SEND_EVENTS_ENABLED;
+use crate::libinput::consts::LIBINPUT_CONFIG_TAP_MAP_LMR;
+use crate::libinput::consts::LIBINPUT_CONFIG_TAP_MAP_LRM;
 use crate::utils::obj_and_id::ObjWithId;
 use crate::utils::static_text::StaticText;
 use crate::utils::str_fmt::StrCtx;
@@ -42,6 +50,7 @@ use crate::video::drm::DRM_MODE_COLORIMETRY_BT2020_RGB;
 use crate::video::drm::DRM_MODE_COLORIMETRY_DEFAULT;
 use crate::video::drm::DrmConnector;
 use crate::video::drm::DrmError;
+use crate::video::drm::DrmModeInfo;
 use crate::video::drm::DrmVersion;
 use crate::video::drm::HDMI_EOTF_SMPTE_ST2084;
 use crate::video::drm::HDMI_EOTF_TRADITIONAL_GAMMA_SDR;
@@ -77,6 +86,11 @@ pub trait Backend: Any {
         let _ = vtnr;
     }
 
+    fn set_backlight(&self, backlight: &Rc<Backlight>, brightness: u32) {
+        let _ = backlight;
+        let _ = brightness;
+    }
+
     fn import_environment(&self) -> bool {
         false
     }
@@ -176,6 +190,12 @@ pub trait Connector: Any {
     fn before_non_desktop_override_update(&self, overrd: Option<bool>) {
         let _ = overrd;
     }
+    fn set_edid_override(&self, edid: Option<Rc<Vec<u8>>>) {
+        let _ = edid;
+    }
+    fn edid(&self) -> Option<Rc<Vec<u8>>> {
+        None
+    }
     fn transaction_type(&self) -> Box<dyn BackendConnectorTransactionTypeDyn> {
         #[derive(Hash, Eq, PartialEq)]
         struct UnimplementedConnectorTransactionType;
@@ -314,6 +334,33 @@ pub trait InputDevice {
         None
     }
     fn set_scroll_button_lock(&self, enabled: bool);
+    fn dwt_enabled(&self) -> Option<bool> {
+        None
+    }
+    fn set_dwt_enabled(&self, enabled: bool);
+    fn dwtp_enabled(&self) -> Option<bool> {
+        None
+    }
+    fn set_dwtp_enabled(&self, enabled: bool);
+    fn tap_button_map(&self) -> Option<InputDeviceTapButtonMap> {
+        None
+    }
+    fn set_tap_button_map(&self, map: InputDeviceTapButtonMap);
+    fn send_events_modes(&self) -> StaticCopyMap<InputDeviceSendEventsMode, bool> {
+        Default::default()
+    }
+    fn send_events_mode(&self) -> Option<InputDeviceSendEventsMode> {
+        None
+    }
+    fn set_send_events_mode(&self, mode: InputDeviceSendEventsMode);
+    fn rotation(&self) -> Option<u32> {
+        None
+    }
+    fn set_rotation(&self, degrees_cw: u32);
+    fn custom_accel(&self) -> Option<Rc<InputDeviceCustomAccel>> {
+        None
+    }
+    fn set_custom_accel(&self, accel: Rc<InputDeviceCustomAccel>);
 }
 
 #[jay_hash]
@@ -361,6 +408,7 @@ impl InputDeviceCapability {
 pub enum InputDeviceAccelProfile {
     Flat,
     Adaptive,
+    Custom,
 }
 
 impl StaticText for InputDeviceAccelProfile {
@@ -368,10 +416,17 @@ impl StaticText for InputDeviceAccelProfile {
         match self {
             InputDeviceAccelProfile::Flat => "Flat",
             InputDeviceAccelProfile::Adaptive => "Adaptive",
+            InputDeviceAccelProfile::Custom => "Custom",
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputDeviceCustomAccel {
+    pub step: f64,
+    pub points: Vec<f64>,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Linearize)]
 pub enum InputDeviceClickMethod {
     None,
@@ -430,6 +485,80 @@ impl InputDeviceScrollMethod {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Linearize)]
+pub enum InputDeviceTapButtonMap {
+    LeftRightMiddle,
+    LeftMiddleRight,
+}
+
+impl StaticText for InputDeviceTapButtonMap {
+    fn text(&self) -> &'static str {
+        match self {
+            InputDeviceTapButtonMap::LeftRightMiddle => "Left/Right/Middle",
+            InputDeviceTapButtonMap::LeftMiddleRight => "Left/Middle/Right",
+        }
+    }
+}
+
+impl InputDeviceTapButtonMap {
+    pub fn to_libinput(self) -> ConfigTapButtonMap {
+        match self {
+            InputDeviceTapButtonMap::LeftRightMiddle => LIBINPUT_CONFIG_TAP_MAP_LRM,
+            InputDeviceTapButtonMap::LeftMiddleRight => LIBINPUT_CONFIG_TAP_MAP_LMR,
+        }
+    }
+
+    pub fn from_libinput(v: ConfigTapButtonMap) -> Option<Self> {
+        let m = match v {
+            LIBINPUT_CONFIG_TAP_MAP_LRM => InputDeviceTapButtonMap::LeftRightMiddle,
+            LIBINPUT_CONFIG_TAP_MAP_LMR => InputDeviceTapButtonMap::LeftMiddleRight,
+            _ => return None,
+        };
+        Some(m)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Linearize)]
+pub enum InputDeviceSendEventsMode {
+    Enabled,
+    Disabled,
+    DisabledOnExternalMouse,
+}
+
+impl StaticText for InputDeviceSendEventsMode {
+    fn text(&self) -> &'static str {
+        match self {
+            InputDeviceSendEventsMode::Enabled => "Enabled",
+            InputDeviceSendEventsMode::Disabled => "Disabled",
+            InputDeviceSendEventsMode::DisabledOnExternalMouse => "Disabled On External Mouse",
+        }
+    }
+}
+
+impl InputDeviceSendEventsMode {
+    pub fn to_libinput(self) -> ConfigSendEventsMode {
+        match self {
+            InputDeviceSendEventsMode::Enabled => LIBINPUT_CONFIG_SEND_EVENTS_ENABLED,
+            InputDeviceSendEventsMode::Disabled => LIBINPUT_CONFIG_SEND_EVENTS_DISABLED,
+            InputDeviceSendEventsMode::DisabledOnExternalMouse => {
+                LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE
+            }
+        }
+    }
+
+    pub fn from_libinput(v: ConfigSendEventsMode) -> Option<Self> {
+        let m = match v {
+            LIBINPUT_CONFIG_SEND_EVENTS_ENABLED => InputDeviceSendEventsMode::Enabled,
+            LIBINPUT_CONFIG_SEND_EVENTS_DISABLED => InputDeviceSendEventsMode::Disabled,
+            LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE => {
+                InputDeviceSendEventsMode::DisabledOnExternalMouse
+            }
+            _ => return None,
+        };
+        Some(m)
+    }
+}
+
 pub enum BackendEvent {
     NewDrmDevice(Rc<dyn BackendDrmDevice>),
     NewConnector(Rc<dyn Connector>),
@@ -800,6 +929,7 @@ pub struct BackendConnectorState {
     pub enabled: bool,
     pub active: bool,
     pub mode: Mode,
+    pub custom_mode: Option<Rc<DrmModeInfo>>,
     pub non_desktop_override: Option<bool>,
     pub vrr: bool,
     pub tearing: bool,
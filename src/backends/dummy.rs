@@ -68,6 +68,7 @@ impl Connector for DummyOutput {
             enabled: true,
             active: false,
             mode,
+            custom_mode: None,
             non_desktop_override: None,
             vrr: false,
             tearing: false,
@@ -12,9 +12,12 @@ use crate::backend::InputDevice;
 use crate::backend::InputDeviceAccelProfile;
 use crate::backend::InputDeviceCapability;
 use crate::backend::InputDeviceClickMethod;
+use crate::backend::InputDeviceCustomAccel;
 use crate::backend::InputDeviceGroupId;
 use crate::backend::InputDeviceId;
 use crate::backend::InputDeviceScrollMethod;
+use crate::backend::InputDeviceSendEventsMode;
+use crate::backend::InputDeviceTapButtonMap;
 use crate::backend::InputEvent;
 use crate::backend::KeyState;
 use crate::backend::Leds;
@@ -28,6 +31,7 @@ use crate::backends::metal::video::MetalLeaseData;
 use crate::backends::metal::video::MetalRenderContext;
 use crate::backends::metal::video::PendingDrmDevice;
 use crate::backends::metal::video::PersistentDisplayData;
+use crate::backlight::Backlight;
 use crate::dbus::DbusError;
 use crate::dbus::SignalHandler;
 use crate::evdev::eviocgbit_key;
@@ -46,7 +50,10 @@ use crate::libinput::LibInputError;
 use crate::libinput::consts::AccelProfile;
 use crate::libinput::consts::ConfigClickMethod;
 use crate::libinput::consts::ConfigScrollMethod;
+use crate::libinput::consts::ConfigSendEventsMode;
+use crate::libinput::consts::ConfigTapButtonMap;
 use crate::libinput::consts::LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE;
+use crate::libinput::consts::LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM;
 use crate::libinput::consts::LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT;
 use crate::libinput::consts::LIBINPUT_CONFIG_CLICK_METHOD_BUTTON_AREAS;
 use crate::libinput::consts::LIBINPUT_CONFIG_CLICK_METHOD_CLICKFINGER;
@@ -256,6 +263,24 @@ impl Backend for MetalBackend {
         })
     }
 
+    fn set_backlight(&self, backlight: &Rc<Backlight>, brightness: u32) {
+        backlight.requested.set(Some(brightness));
+        let bl = backlight.clone();
+        self.session
+            .set_brightness("backlight", &backlight.name, brightness, move |res| {
+                if bl.requested.get() == Some(brightness) {
+                    bl.requested.take();
+                }
+                if let Err(e) = res {
+                    log::error!(
+                        "Could not set the brightness of {}: {}",
+                        bl.name,
+                        ErrorFmt(e)
+                    );
+                }
+            });
+    }
+
     fn import_environment(&self) -> bool {
         true
     }
@@ -390,6 +415,12 @@ struct InputDeviceProperties {
     scroll_method: Cell<Option<ConfigScrollMethod>>,
     scroll_button: Cell<Option<Option<InputEventCode>>>,
     scroll_button_lock: Cell<Option<bool>>,
+    dwt_enabled: Cell<Option<bool>>,
+    dwtp_enabled: Cell<Option<bool>>,
+    tap_button_map: Cell<Option<ConfigTapButtonMap>>,
+    send_events_mode: Cell<Option<ConfigSendEventsMode>>,
+    rotation: Cell<Option<u32>>,
+    custom_accel: CloneCell<Option<Rc<InputDeviceCustomAccel>>>,
 }
 
 #[jay_clone]
@@ -465,6 +496,21 @@ impl MetalInputDevice {
         if let Some(enabled) = self.desired.scroll_button_lock.get() {
             self.set_scroll_button_lock(enabled);
         }
+        if let Some(enabled) = self.desired.dwt_enabled.get() {
+            self.set_dwt_enabled(enabled);
+        }
+        if let Some(enabled) = self.desired.dwtp_enabled.get() {
+            self.set_dwtp_enabled(enabled);
+        }
+        if let Some(map) = self.desired.tap_button_map.get() {
+            self.set_tap_button_map_(map);
+        }
+        if let Some(mode) = self.desired.send_events_mode.get() {
+            self.set_send_events_mode_(mode);
+        }
+        if let Some(degrees) = self.desired.rotation.get() {
+            self.set_rotation(degrees);
+        }
         self.fetch_effective();
     }
 
@@ -518,6 +564,25 @@ impl MetalInputDevice {
         self.effective
             .scroll_button_lock
             .set(Some(device.scroll_button_lock_enabled()));
+        if device.dwt_available() {
+            self.effective.dwt_enabled.set(Some(device.dwt_enabled()));
+        }
+        if device.dwtp_available() {
+            self.effective.dwtp_enabled.set(Some(device.dwtp_enabled()));
+        }
+        if device.tap_available() {
+            self.effective
+                .tap_button_map
+                .set(Some(device.tap_button_map()));
+        }
+        if device.send_events_modes() != 0 {
+            self.effective
+                .send_events_mode
+                .set(Some(device.send_events_mode()));
+        }
+        if device.rotation_available() {
+            self.effective.rotation.set(Some(device.rotation()));
+        }
     }
 
     fn pre_pause(&self) {
@@ -547,6 +612,53 @@ impl MetalInputDevice {
             self.effective
                 .accel_profile
                 .set(Some(dev.device().accel_profile()));
+            self.apply_custom_accel();
+        }
+    }
+
+    fn apply_custom_accel(&self) {
+        self.effective.custom_accel.take();
+        let Some(dev) = self.inputdev.get() else {
+            return;
+        };
+        let Some(accel) = self.desired.custom_accel.get() else {
+            return;
+        };
+        if self.effective.accel_profile.get() != Some(LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM) {
+            return;
+        }
+        if !dev.device().custom_accel_available() {
+            log::warn!("libinput does not support custom acceleration curves");
+            return;
+        }
+        if dev.device().set_custom_accel(accel.step, &accel.points) {
+            self.effective.custom_accel.set(Some(accel));
+        } else {
+            log::warn!("Could not apply the custom acceleration curve");
+        }
+    }
+
+    fn set_tap_button_map_(&self, map: ConfigTapButtonMap) {
+        self.desired.tap_button_map.set(Some(map));
+        if let Some(dev) = self.inputdev.get()
+            && dev.device().tap_available()
+        {
+            dev.device().set_tap_button_map(map);
+            self.effective
+                .tap_button_map
+                .set(Some(dev.device().tap_button_map()));
+        }
+    }
+
+    fn set_send_events_mode_(&self, mode: ConfigSendEventsMode) {
+        self.desired.send_events_mode.set(Some(mode));
+        if let Some(dev) = self.inputdev.get()
+            && dev.device().send_events_modes() != 0
+        {
+            dev.device().set_send_events_mode(mode);
+            self.effective
+                .send_events_mode
+                .set(Some(dev.device().send_events_mode()));
         }
     }
 
@@ -643,6 +755,7 @@ impl InputDevice for MetalInputDevice {
         let p = match p {
             LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT => InputDeviceAccelProfile::Flat,
             LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE => InputDeviceAccelProfile::Adaptive,
+            LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM => InputDeviceAccelProfile::Custom,
             _ => return None,
         };
         Some(p)
@@ -652,6 +765,7 @@ impl InputDevice for MetalInputDevice {
         let profile = match profile {
             InputDeviceAccelProfile::Flat => LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT,
             InputDeviceAccelProfile::Adaptive => LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE,
+            InputDeviceAccelProfile::Custom => LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM,
         };
         self.set_accel_profile_(profile);
     }
@@ -828,6 +942,7 @@ impl InputDevice for MetalInputDevice {
             vid: dev.vendor(),
             bustype: dev.bustype(),
             path: self.syspath.as_bytes().as_bstr().to_string(),
+            size_mm: dev.size(),
         }))
     }
 
@@ -937,6 +1052,97 @@ impl InputDevice for MetalInputDevice {
                 .set(Some(dev.device().scroll_button_lock_enabled()));
         }
     }
+
+    fn dwt_enabled(&self) -> Option<bool> {
+        self.effective.dwt_enabled.get()
+    }
+
+    fn set_dwt_enabled(&self, enabled: bool) {
+        self.desired.dwt_enabled.set(Some(enabled));
+        if let Some(dev) = self.inputdev.get()
+            && dev.device().dwt_available()
+        {
+            dev.device().set_dwt_enabled(enabled);
+            self.effective
+                .dwt_enabled
+                .set(Some(dev.device().dwt_enabled()));
+        }
+    }
+
+    fn dwtp_enabled(&self) -> Option<bool> {
+        self.effective.dwtp_enabled.get()
+    }
+
+    fn set_dwtp_enabled(&self, enabled: bool) {
+        self.desired.dwtp_enabled.set(Some(enabled));
+        if let Some(dev) = self.inputdev.get()
+            && dev.device().dwtp_available()
+        {
+            dev.device().set_dwtp_enabled(enabled);
+            self.effective
+                .dwtp_enabled
+                .set(Some(dev.device().dwtp_enabled()));
+        }
+    }
+
+    fn tap_button_map(&self) -> Option<InputDeviceTapButtonMap> {
+        self.effective
+            .tap_button_map
+            .get()
+            .and_then(InputDeviceTapButtonMap::from_libinput)
+    }
+
+    fn set_tap_button_map(&self, map: InputDeviceTapButtonMap) {
+        self.set_tap_button_map_(map.to_libinput());
+    }
+
+    fn send_events_modes(&self) -> StaticCopyMap<InputDeviceSendEventsMode, bool> {
+        let mut res = StaticCopyMap::default();
+        let Some(dev) = self.inputdev.get() else {
+            return res;
+        };
+        let modes = dev.device().send_events_modes();
+        if modes != 0 {
+            for mode in InputDeviceSendEventsMode::variants() {
+                res[mode] = modes.contains(mode.to_libinput().0 as u32);
+            }
+        }
+        res
+    }
+
+    fn send_events_mode(&self) -> Option<InputDeviceSendEventsMode> {
+        self.effective
+            .send_events_mode
+            .get()
+            .and_then(InputDeviceSendEventsMode::from_libinput)
+    }
+
+    fn set_send_events_mode(&self, mode: InputDeviceSendEventsMode) {
+        self.set_send_events_mode_(mode.to_libinput());
+    }
+
+    fn rotation(&self) -> Option<u32> {
+        self.effective.rotation.get()
+    }
+
+    fn set_rotation(&self, degrees_cw: u32) {
+        self.desired.rotation.set(Some(degrees_cw));
+        if let Some(dev) = self.inputdev.get()
+            && dev.device().rotation_available()
+        {
+            dev.device().set_rotation(degrees_cw);
+            self.effective.rotation.set(Some(dev.device().rotation()));
+        }
+    }
+
+    fn custom_accel(&self) -> Option<Rc<InputDeviceCustomAccel>> {
+        self.effective.custom_accel.get()
+    }
+
+    fn set_custom_accel(&self, accel: Rc<InputDeviceCustomAccel>) {
+        self.desired.custom_accel.set(Some(accel));
+        self.apply_custom_accel();
+    }
 }
 
 impl MetalInputDevice {
@@ -478,7 +478,13 @@ impl MetalDeviceTransaction {
                 );
             }
             let mode = 'mode: {
-                let Some(mode) = dd.modes.iter().find(|m| m.to_backend() == state.mode) else {
+                let custom_mode = state.custom_mode.as_deref();
+                let Some(mode) = dd
+                    .modes
+                    .iter()
+                    .chain(custom_mode)
+                    .find(|m| m.to_backend() == state.mode)
+                else {
                     return Err(BackendConnectorTransactionError::UnsupportedMode(
                         connector.obj.kernel_id(),
                         state.mode,
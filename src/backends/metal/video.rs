@@ -52,6 +52,7 @@ use crate::copy_device::CopyDeviceRegistry;
 use crate::edid::CtaDataBlock;
 use crate::edid::Descriptor;
 use crate::edid::EdidExtension;
+use crate::edid::EdidFile;
 use crate::format::Format;
 use crate::format::XRGB8888;
 use crate::gfx_api::FdSync;
@@ -86,6 +87,7 @@ use crate::utils::ordered_float::F64;
 use crate::utils::oserror::OsError;
 use crate::video::INVALID_MODIFIER;
 use crate::video::Modifier;
+use crate::video::cvt;
 use crate::video::dmabuf::DmaBufId;
 use crate::video::drm::ConnectorStatus;
 use crate::video::drm::ConnectorType;
@@ -94,6 +96,7 @@ use crate::video::drm::DRM_CLIENT_CAP_PLANE_COLOR_PIPELINE;
 use crate::video::drm::DrmBlob;
 use crate::video::drm::DrmCardResources;
 use crate::video::drm::DrmConnector;
+use crate::video::drm::DrmConnectorInfo;
 use crate::video::drm::DrmCrtc;
 use crate::video::drm::DrmEncoder;
 use crate::video::drm::DrmError;
@@ -465,6 +468,8 @@ pub struct ConnectorDisplayData {
     pub primaries: Primaries,
     pub luminance: Option<BackendLuminance>,
 
+    pub edid: Option<Rc<Vec<u8>>>,
+
     pub drm_state: DrmConnectorState,
 }
 
@@ -650,6 +655,9 @@ pub struct MetalConnector {
     pub cm: MetalCmConnector,
 
     pub last_direct_scanout_error: Cell<Option<MetalDirectScanoutError>>,
+
+    pub edid_override: CloneCell<Option<Rc<Vec<u8>>>>,
+    pub edid_override_changed: Cell<bool>,
 }
 
 impl Debug for MetalConnector {
@@ -961,6 +969,23 @@ impl Connector for MetalConnector {
         }
     }
 
+    fn set_edid_override(&self, edid: Option<Rc<Vec<u8>>>) {
+        if self.edid_override.set(edid.clone()) == edid {
+            return;
+        }
+        self.edid_override_changed.set(true);
+        let Some(dev) = self.backend.device_holder.drm_devices.get(&self.dev.devnum) else {
+            return;
+        };
+        if let Err(e) = self.backend.handle_drm_change_(&dev) {
+            log::error!("Could not apply EDID override: {}", ErrorFmt(e));
+        }
+    }
+
+    fn edid(&self) -> Option<Rc<Vec<u8>>> {
+        self.display.borrow().edid.clone()
+    }
+
     fn transaction_type(&self) -> Box<dyn BackendConnectorTransactionTypeDyn> {
         #[derive(Eq, PartialEq, Hash)]
         struct TT(dev_t);
@@ -1159,7 +1184,7 @@ fn create_connector(
     connector: DrmConnector,
     dev: &Rc<MetalDrmDevice>,
 ) -> Result<(Rc<MetalConnector>, ConnectorFutures), DrmError> {
-    let display = create_connector_display_data(connector, dev)?;
+    let display = create_connector_display_data(connector, dev, None)?;
     log::info!(
         "Creating connector {} for device {}",
         display.connector_id,
@@ -1216,6 +1241,8 @@ fn create_connector(
         gamma_lut: Default::default(),
         cm: MetalCmConnector::new(&dev.cm),
         last_direct_scanout_error: Default::default(),
+        edid_override: Default::default(),
+        edid_override_changed: Cell::new(false),
     });
     let futures = ConnectorFutures {
         _present: backend.state.eng.spawn2(
@@ -1227,13 +1254,73 @@ fn create_connector(
     Ok((slf, futures))
 }
 
+fn apply_edid_override(
+    connector_id: ConnectorKernelId,
+    edid: &EdidFile,
+    info: &mut DrmConnectorInfo,
+) {
+    let mut modes = vec![];
+    let mut add = |mode: Option<DrmModeInfo>| {
+        if let Some(mode) = mode
+            && !modes.contains(&mode)
+        {
+            modes.push(mode);
+        }
+    };
+    let mut dtds = vec![];
+    for descriptor in edid.base_block.descriptors.iter().flatten() {
+        if let Descriptor::DetailedTimingDescriptor(dtd) = descriptor {
+            dtds.push(dtd);
+        }
+    }
+    for ext in &edid.extension_blocks {
+        if let EdidExtension::CtaV3(cta) = ext {
+            dtds.extend(&cta.detailed_timing_descriptors);
+        }
+    }
+    if let Some(dtd) = dtds.first()
+        && dtd.horizontal_addressable_mm != 0
+    {
+        info.mm_width = dtd.horizontal_addressable_mm as _;
+        info.mm_height = dtd.vertical_addressable_mm as _;
+    }
+    for dtd in dtds {
+        add(cvt::from_detailed_timing(dtd));
+    }
+    for ext in &edid.extension_blocks {
+        if let EdidExtension::CtaV3(cta) = ext {
+            for data_block in &cta.data_blocks {
+                if let CtaDataBlock::Video(vics) = data_block {
+                    for &vic in vics {
+                        add(cvt::from_vic(vic));
+                    }
+                }
+            }
+        }
+    }
+    if modes.is_empty() {
+        log::warn!(
+            "The EDID override of connector {} does not contain any usable timings",
+            connector_id,
+        );
+        let dims = &edid.base_block.screen_dimensions;
+        if let (Some(w), Some(h)) = (dims.horizontal_screen_size_cm, dims.vertical_screen_size_cm) {
+            info.mm_width = w as u32 * 10;
+            info.mm_height = h as u32 * 10;
+        }
+        return;
+    }
+    info.modes = modes;
+}
+
 fn create_connector_display_data(
     connector: DrmConnector,
     dev: &Rc<MetalDrmDevice>,
+    edid_override: Option<Rc<Vec<u8>>>,
 ) -> Result<ConnectorDisplayData, DrmError> {
-    let info = dev.master.get_connector_info(connector, true)?;
+    let mut info = dev.master.get_connector_info(connector, true)?;
     let mut crtcs = BinarySearchMap::new();
-    for encoder in info.encoders {
+    for &encoder in &info.encoders {
         if let Some(encoder) = dev.encoders.get(&encoder) {
             for (_, crtc) in &encoder.crtcs {
                 crtcs.insert(crtc.id, crtc.clone());
@@ -1254,31 +1341,38 @@ fn create_connector_display_data(
     let mut supports_pq = false;
     let mut luminance = None;
     let mut primaries = Primaries::SRGB;
+    let mut edid_blob = None;
     'fetch_edid: {
         if connection != ConnectorStatus::Connected {
             break 'fetch_edid;
         }
-        let edid = match props.get("EDID") {
-            Ok(e) => e,
-            _ => {
-                log::warn!(
-                    "Connector {} is connected but has no EDID blob",
-                    connector_id,
-                );
-                break 'fetch_edid;
-            }
-        };
-        let blob = match dev.master.getblob_vec::<u8>(DrmBlob(edid.value as _)) {
-            Ok(b) => b,
-            Err(e) => {
-                log::error!(
-                    "Could not fetch edid property of connector {}: {}",
-                    connector_id,
-                    ErrorFmt(e)
-                );
-                break 'fetch_edid;
+        let blob = match &edid_override {
+            Some(blob) => blob.clone(),
+            None => {
+                let edid = match props.get("EDID") {
+                    Ok(e) => e,
+                    _ => {
+                        log::warn!(
+                            "Connector {} is connected but has no EDID blob",
+                            connector_id,
+                        );
+                        break 'fetch_edid;
+                    }
+                };
+                match dev.master.getblob_vec::<u8>(DrmBlob(edid.value as _)) {
+                    Ok(b) => Rc::new(b),
+                    Err(e) => {
+                        log::error!(
+                            "Could not fetch edid property of connector {}: {}",
+                            connector_id,
+                            ErrorFmt(e)
+                        );
+                        break 'fetch_edid;
+                    }
+                }
             }
         };
+        edid_blob = Some(blob.clone());
         let edid = match crate::edid::parse(&blob) {
             Ok(e) => e,
             Err(e) => {
@@ -1290,6 +1384,9 @@ fn create_connector_display_data(
                 break 'fetch_edid;
             }
         };
+        if edid_override.is_some() {
+            apply_edid_override(connector_id, &edid, &mut info);
+        }
         manufacturer = edid.base_block.id_manufacturer_name.to_string();
         for descriptor in edid.base_block.descriptors.iter().flatten() {
             match descriptor {
@@ -1393,6 +1490,7 @@ fn create_connector_display_data(
                     enabled: true,
                     active: true,
                     mode: first_mode,
+                    custom_mode: None,
                     non_desktop_override: None,
                     vrr: false,
                     tearing: false,
@@ -1414,10 +1512,12 @@ fn create_connector_display_data(
     } else if info
         .modes
         .iter()
+        .chain(desired_state.custom_mode.as_deref())
         .all(|m| m.to_backend() != desired_state.mode)
     {
         log::warn!("Discarding previously desired mode");
         desired_state.mode = first_mode;
+        desired_state.custom_mode = None;
     }
     let non_desktop = props.get("non-desktop")?.value != 0;
     let vrr_capable = match props.get("vrr_capable") {
@@ -1524,6 +1624,7 @@ fn create_connector_display_data(
         supports_pq,
         primaries,
         luminance,
+        edid: edid_blob,
         connector_id,
         output_id,
         drm_state,
@@ -1886,7 +1987,7 @@ impl MetalBackend {
             return Err(MetalError::UpdateProperties(e));
         }
         for c in dev.connectors.lock().values() {
-            let dd = create_connector_display_data(c.id, &dev.dev);
+            let dd = create_connector_display_data(c.id, &dev.dev, c.edid_override.get());
             let mut dd = match dd {
                 Ok(d) => d,
                 Err(e) => {
@@ -1907,6 +2008,9 @@ impl MetalBackend {
                     let mut disconnect = false;
                     // Disconnect if the connector has been disabled.
                     disconnect |= !old.persistent.state.borrow().enabled;
+                    // Disconnect if the EDID override changed so that the display is
+                    // announced again with the new information.
+                    disconnect |= c.edid_override_changed.take();
                     // If the connector is connected and switched between being a non-desktop
                     // and desktop device, break leases and disconnect.
                     disconnect |= old.connection == ConnectorStatus::Connected
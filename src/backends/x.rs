@@ -18,8 +18,11 @@ use crate::backend::InputDevice;
 use crate::backend::InputDeviceAccelProfile;
 use crate::backend::InputDeviceCapability;
 use crate::backend::InputDeviceClickMethod;
+use crate::backend::InputDeviceCustomAccel;
 use crate::backend::InputDeviceId;
 use crate::backend::InputDeviceScrollMethod;
+use crate::backend::InputDeviceSendEventsMode;
+use crate::backend::InputDeviceTapButtonMap;
 use crate::backend::InputEvent;
 use crate::backend::KeyState;
 use crate::backend::Mode;
@@ -554,6 +557,7 @@ impl XBackend {
                 height: HEIGHT,
                 refresh_rate_millihz: 60_000, // TODO
             },
+            custom_mode: None,
             non_desktop_override: None,
             vrr: false,
             tearing: false,
@@ -1402,6 +1406,30 @@ impl InputDevice for XSeatKeyboard {
     fn set_scroll_button_lock(&self, enabled: bool) {
         let _ = enabled;
     }
+
+    fn set_dwt_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_dwtp_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_tap_button_map(&self, map: InputDeviceTapButtonMap) {
+        let _ = map;
+    }
+
+    fn set_send_events_mode(&self, mode: InputDeviceSendEventsMode) {
+        let _ = mode;
+    }
+
+    fn set_rotation(&self, degrees_cw: u32) {
+        let _ = degrees_cw;
+    }
+
+    fn set_custom_accel(&self, accel: Rc<InputDeviceCustomAccel>) {
+        let _ = accel;
+    }
 }
 
 impl InputDevice for XSeatMouse {
@@ -1491,4 +1519,28 @@ impl InputDevice for XSeatMouse {
     fn set_scroll_button_lock(&self, enabled: bool) {
         let _ = enabled;
     }
+
+    fn set_dwt_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_dwtp_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_tap_button_map(&self, map: InputDeviceTapButtonMap) {
+        let _ = map;
+    }
+
+    fn set_send_events_mode(&self, mode: InputDeviceSendEventsMode) {
+        let _ = mode;
+    }
+
+    fn set_rotation(&self, degrees_cw: u32) {
+        let _ = degrees_cw;
+    }
+
+    fn set_custom_accel(&self, accel: Rc<InputDeviceCustomAccel>) {
+        let _ = accel;
+    }
 }
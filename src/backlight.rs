@@ -0,0 +1,278 @@
+#[cfg(test)]
+mod tests;
+
+use crate::backend::BackendEotfs;
+use crate::backend::ConnectorKernelId;
+use crate::cmm::cmm_luminance::Luminance;
+use crate::output_overlay::OutputOverlay;
+use crate::output_overlay::OverlayKind;
+use crate::state::State;
+use crate::tree::OutputNode;
+use crate::tree::TreeTimeline::LiveTL;
+use crate::utils::errorfmt::ErrorFmt;
+use std::cell::Cell;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::rc::Rc;
+use thiserror::Error;
+
+const BACKLIGHT_CLASS: &str = "/sys/class/backlight";
+const INDICATOR_TIMEOUT_MS: u64 = 1500;
+const INDICATOR_CELLS: usize = 20;
+
+#[derive(Debug, Error)]
+pub enum BacklightError {
+    #[error("Connector {0} has no backlight")]
+    NotSupported(String),
+    #[error("Could not read {0}")]
+    Read(String, #[source] io::Error),
+    #[error("{0} does not contain a number")]
+    Parse(String),
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+enum BacklightType {
+    Raw,
+    Platform,
+    Firmware,
+}
+
+impl BacklightType {
+    fn parse(s: &str) -> Option<Self> {
+        let ty = match s.trim() {
+            "raw" => Self::Raw,
+            "platform" => Self::Platform,
+            "firmware" => Self::Firmware,
+            _ => return None,
+        };
+        Some(ty)
+    }
+}
+
+pub struct Backlight {
+    pub name: String,
+    pub max_brightness: u32,
+    /// The last brightness sent to logind whose reply has not yet been received.
+    pub requested: Cell<Option<u32>>,
+}
+
+impl Backlight {
+    /// Finds the backlight device of an internal panel.
+    ///
+    /// Devices that are children of the DRM connector in sysfs are preferred. Otherwise
+    /// the device of `/sys/class/backlight` with the highest-priority type is used.
+    pub fn find(kernel_id: ConnectorKernelId, drm_syspath: Option<&str>) -> Option<Rc<Self>> {
+        if !kernel_id.ty.is_internal() {
+            return None;
+        }
+        let name = drm_syspath
+            .and_then(|syspath| find_connector_child(syspath, kernel_id))
+            .or_else(find_by_type)?;
+        let path = format!("{BACKLIGHT_CLASS}/{name}/max_brightness");
+        let max_brightness = match read_u32(&path) {
+            Ok(m) if m > 0 => m,
+            Ok(_) => return None,
+            Err(e) => {
+                log::warn!("Could not read the maximum brightness: {}", ErrorFmt(e));
+                return None;
+            }
+        };
+        log::info!("Using backlight {} for connector {}", name, kernel_id);
+        Some(Rc::new(Self {
+            name,
+            max_brightness,
+            requested: Default::default(),
+        }))
+    }
+
+    /// Returns the current raw brightness.
+    pub fn brightness(&self) -> Result<u32, BacklightError> {
+        if let Some(requested) = self.requested.get() {
+            return Ok(requested);
+        }
+        read_u32(&format!("{BACKLIGHT_CLASS}/{}/brightness", self.name))
+    }
+
+    pub fn to_percent(&self, brightness: u32) -> f64 {
+        to_percent(self.max_brightness, brightness)
+    }
+
+    pub fn compute(&self, current: u32, value: f64, relative: bool) -> u32 {
+        compute_brightness(self.max_brightness, current, value, relative)
+    }
+}
+
+fn find_connector_child(syspath: &str, kernel_id: ConnectorKernelId) -> Option<String> {
+    let card = Path::new(syspath).file_name()?.to_str()?;
+    let dir = format!("{syspath}/{card}-{kernel_id}");
+    for entry in fs::read_dir(dir).ok()? {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let Some(name) = entry.file_name().to_str().map(|s| s.to_string()) else {
+            continue;
+        };
+        if Path::new(BACKLIGHT_CLASS).join(&name).exists() {
+            return Some(name);
+        }
+    }
+    None
+}
+
+fn find_by_type() -> Option<String> {
+    let mut candidates = vec![];
+    for entry in fs::read_dir(BACKLIGHT_CLASS).ok()? {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let Some(name) = entry.file_name().to_str().map(|s| s.to_string()) else {
+            continue;
+        };
+        let Ok(ty) = fs::read_to_string(entry.path().join("type")) else {
+            continue;
+        };
+        if let Some(ty) = BacklightType::parse(&ty) {
+            candidates.push((name, ty));
+        }
+    }
+    select_by_type(candidates)
+}
+
+fn select_by_type(mut candidates: Vec<(String, BacklightType)>) -> Option<String> {
+    candidates.sort_by(|(n1, t1), (n2, t2)| t2.cmp(t1).then_with(|| n1.cmp(n2)));
+    candidates.into_iter().next().map(|(name, _)| name)
+}
+
+fn read_u32(path: &str) -> Result<u32, BacklightError> {
+    let s = match fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) => return Err(BacklightError::Read(path.to_string(), e)),
+    };
+    s.trim()
+        .parse()
+        .map_err(|_| BacklightError::Parse(path.to_string()))
+}
+
+fn to_percent(max: u32, brightness: u32) -> f64 {
+    brightness.min(max) as f64 * 100.0 / max as f64
+}
+
+/// Computes the new raw brightness.
+///
+/// If `relative` is set, `value` is a delta in percent. Relative changes always change
+/// the brightness by at least one step and never turn the backlight off completely.
+fn compute_brightness(max: u32, current: u32, value: f64, relative: bool) -> u32 {
+    let current = current.min(max);
+    let percent = match relative {
+        true => to_percent(max, current) + value,
+        false => value,
+    };
+    let mut new = (percent.clamp(0.0, 100.0) * max as f64 / 100.0).round() as u32;
+    if relative {
+        if value > 0.0 && new == current {
+            new = current.saturating_add(1).min(max);
+        } else if value < 0.0 {
+            if new == current {
+                new = current.saturating_sub(1);
+            }
+            new = new.max(current.min(1));
+        }
+    }
+    new
+}
+
+/// Returns the luminance range of the SDR white point if the output uses the PQ transfer
+/// function.
+///
+/// Such outputs are usually driven at a fixed backlight level. Brightness changes are
+/// instead applied to the luminance of SDR content.
+pub fn hdr_white_range(output: &OutputNode) -> Option<f64> {
+    if output.node_state[LiveTL].btf.get() != BackendEotfs::Pq {
+        return None;
+    }
+    let max = output
+        .global
+        .luminance
+        .map(|l| if l.max_fall > 0.0 { l.max_fall } else { l.max })
+        .filter(|&max| max > 0.0)
+        .unwrap_or(1000.0);
+    Some(max)
+}
+
+pub fn hdr_white_to_percent(range: f64, white: Option<f64>) -> f64 {
+    let white = white.unwrap_or(Luminance::ST2084_PQ.white.0);
+    (white * 100.0 / range).clamp(0.0, 100.0)
+}
+
+pub fn hdr_percent_to_white(range: f64, percent: f64) -> f64 {
+    (percent.clamp(0.0, 100.0) * range / 100.0).max(1.0)
+}
+
+fn indicator_markup(percent: f64) -> String {
+    let percent = percent.clamp(0.0, 100.0);
+    let filled = (percent * INDICATOR_CELLS as f64 / 100.0).round() as usize;
+    let mut s = String::new();
+    s.push_str("<b>Brightness</b>\n<tt>");
+    for idx in 0..INDICATOR_CELLS {
+        s.push(if idx < filled { '█' } else { '░' });
+    }
+    s.push_str(&format!("</tt>  {:3.0}%", percent));
+    s
+}
+
+pub struct BacklightIndicator {
+    pub enabled: Cell<bool>,
+    overlay: OutputOverlay,
+}
+
+impl Default for BacklightIndicator {
+    fn default() -> Self {
+        Self {
+            enabled: Cell::new(true),
+            overlay: OutputOverlay::new(OverlayKind::BacklightIndicator),
+        }
+    }
+}
+
+impl BacklightIndicator {
+    pub fn show(&self, state: &Rc<State>, output: &Rc<OutputNode>, percent: f64) {
+        if !self.enabled.get() {
+            return;
+        }
+        let slf = Rc::downgrade(state);
+        let output = output.clone();
+        let future = state.eng.spawn("backlight indicator", async move {
+            let Some(state) = slf.upgrade() else {
+                return;
+            };
+            let overlay = &state.backlight_indicator.overlay;
+            if !overlay
+                .show(&state, &output, &indicator_markup(percent))
+                .await
+            {
+                return;
+            }
+            let wheel = state.wheel.clone();
+            drop(state);
+            if wheel.timeout(INDICATOR_TIMEOUT_MS).await.is_err() {
+                return;
+            }
+            if let Some(state) = slf.upgrade() {
+                state.backlight_indicator.overlay.remove();
+            }
+        });
+        self.overlay.set_pending(future);
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+        if !enabled {
+            self.clear();
+        }
+    }
+
+    pub fn clear(&self) {
+        self.overlay.clear();
+    }
+}
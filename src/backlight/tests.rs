@@ -0,0 +1,57 @@
+use crate::backlight::BacklightType;
+use crate::backlight::compute_brightness;
+use crate::backlight::indicator_markup;
+use crate::backlight::select_by_type;
+
+#[test]
+fn select() {
+    let candidates = vec![
+        ("acpi_video0".to_string(), BacklightType::Firmware),
+        ("intel_backlight".to_string(), BacklightType::Raw),
+        ("dell_backlight".to_string(), BacklightType::Platform),
+    ];
+    assert_eq!(select_by_type(candidates).as_deref(), Some("acpi_video0"));
+    let candidates = vec![
+        ("intel_backlight".to_string(), BacklightType::Raw),
+        ("amdgpu_bl1".to_string(), BacklightType::Raw),
+    ];
+    assert_eq!(select_by_type(candidates).as_deref(), Some("amdgpu_bl1"));
+    assert_eq!(select_by_type(vec![]), None);
+    assert_eq!(BacklightType::parse("raw\n"), Some(BacklightType::Raw));
+    assert_eq!(BacklightType::parse("unknown"), None);
+}
+
+#[test]
+fn absolute() {
+    assert_eq!(compute_brightness(255, 10, 50.0, false), 128);
+    assert_eq!(compute_brightness(255, 10, 0.0, false), 0);
+    assert_eq!(compute_brightness(255, 10, 150.0, false), 255);
+    assert_eq!(compute_brightness(96000, 0, 100.0, false), 96000);
+}
+
+#[test]
+fn relative() {
+    assert_eq!(compute_brightness(100, 50, 5.0, true), 55);
+    assert_eq!(compute_brightness(100, 50, -5.0, true), 45);
+    assert_eq!(compute_brightness(100, 98, 5.0, true), 100);
+    assert_eq!(compute_brightness(100, 100, 5.0, true), 100);
+    // Small ranges still make progress.
+    assert_eq!(compute_brightness(7, 3, 5.0, true), 4);
+    assert_eq!(compute_brightness(7, 3, -5.0, true), 2);
+    // Relative changes do not turn the backlight off.
+    assert_eq!(compute_brightness(100, 3, -5.0, true), 1);
+    assert_eq!(compute_brightness(100, 1, -5.0, true), 1);
+    assert_eq!(compute_brightness(100, 0, -5.0, true), 0);
+}
+
+#[test]
+fn markup() {
+    assert_eq!(
+        indicator_markup(50.0),
+        "<b>Brightness</b>\n<tt>██████████░░░░░░░░░░</tt>   50%",
+    );
+    assert_eq!(
+        indicator_markup(120.0),
+        "<b>Brightness</b>\n<tt>████████████████████</tt>  100%",
+    );
+}
@@ -0,0 +1,211 @@
+#[cfg(test)]
+mod tests;
+
+use crate::backend::LED_CAPS_LOCK;
+use crate::backend::LED_NUM_LOCK;
+use crate::backend::Leds;
+use crate::state::State;
+use jay_config::theme::BarElement;
+use jay_config::theme::BarElementKind;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::fmt::Write;
+
+pub const BE_FOCUS: u32 = 1 << 0;
+pub const BE_INPUT_MODES: u32 = 1 << 1;
+pub const BE_KEYBOARD_LAYOUT: u32 = 1 << 2;
+pub const BE_LOCK_INDICATORS: u32 = 1 << 3;
+pub const BE_SCREEN_SHARE: u32 = 1 << 4;
+pub const BE_STICKY_KEYS: u32 = 1 << 5;
+
+const DEFAULT_SEPARATOR: &str = r##" <span color="#333333">|</span> "##;
+
+#[derive(Default)]
+pub struct BarElements {
+    elements: RefCell<Vec<BarElement>>,
+    separator: RefCell<Option<String>>,
+    dependencies: Cell<u32>,
+}
+
+impl BarElements {
+    pub fn is_empty(&self) -> bool {
+        self.elements.borrow().is_empty()
+    }
+
+    fn format(&self, mut element_text: impl FnMut(BarElementKind, &mut String)) -> String {
+        let mut res = String::new();
+        let separator = self.separator.borrow();
+        let separator = separator.as_deref().unwrap_or(DEFAULT_SEPARATOR);
+        let mut text = String::new();
+        for element in &*self.elements.borrow() {
+            text.clear();
+            element_text(element.kind, &mut text);
+            if text.is_empty() {
+                continue;
+            }
+            if !res.is_empty() {
+                res.push_str(separator);
+            }
+            if let Some(color) = element.color {
+                let [r, g, b, a] = color.to_u8_straight();
+                let _ = write!(res, r##"<span color="#{r:02x}{g:02x}{b:02x}{a:02x}">"##);
+            }
+            escape_pango(&text, &mut res);
+            if element.color.is_some() {
+                res.push_str("</span>");
+            }
+        }
+        res
+    }
+}
+
+fn element_text(state: &State, kind: BarElementKind, dst: &mut String) {
+    if kind == BarElementKind::ScreenShare {
+        if is_screen_shared(state) {
+            dst.push_str("Screen shared");
+        }
+        return;
+    }
+    let Some(seat) = state.seat_queue.last() else {
+        return;
+    };
+    match kind {
+        BarElementKind::FocusedWindowTitle | BarElementKind::FocusedWindowAppId => {
+            let Some(tl) = seat.get_keyboard_node().node_toplevel() else {
+                return;
+            };
+            let data = tl.tl_data();
+            match kind {
+                BarElementKind::FocusedWindowTitle => dst.push_str(&data.title.borrow()),
+                _ => dst.push_str(&data.app_id.borrow()),
+            }
+        }
+        BarElementKind::InputModes => input_modes_text(&seat.input_modes(), dst),
+        BarElementKind::KeyboardLayout => {
+            let kb_state = seat.latest_kb_state();
+            let kb_state = kb_state.borrow();
+            if let Some(name) = kb_state.map.group_name(kb_state.mods.group) {
+                dst.push_str(name);
+            }
+        }
+        BarElementKind::LockIndicators => {
+            lock_indicators_text(seat.latest_kb_state().borrow().leds, dst);
+        }
+        BarElementKind::StickyKeys => {
+            let mut modifiers = vec![];
+            seat.sticky_modifiers(&mut modifiers);
+            sticky_keys_text(&modifiers, dst);
+        }
+        _ => {}
+    }
+}
+
+fn input_modes_text(modes: &[String], dst: &mut String) {
+    for (idx, mode) in modes.iter().enumerate() {
+        if idx > 0 {
+            dst.push_str(" > ");
+        }
+        dst.push_str(mode);
+    }
+}
+
+fn lock_indicators_text(leds: Leds, dst: &mut String) {
+    if leds.contains(LED_CAPS_LOCK) {
+        dst.push_str("Caps");
+    }
+    if leds.contains(LED_NUM_LOCK) {
+        if !dst.is_empty() {
+            dst.push(' ');
+        }
+        dst.push_str("Num");
+    }
+}
+
+fn sticky_keys_text(modifiers: &[(&str, bool)], dst: &mut String) {
+    for (idx, &(name, locked)) in modifiers.iter().enumerate() {
+        if idx > 0 {
+            dst.push(' ');
+        }
+        dst.push_str(name);
+        if locked {
+            dst.push_str(" (locked)");
+        }
+    }
+}
+
+fn is_screen_shared(state: &State) -> bool {
+    for server in state.vnc_servers.lock().values() {
+        if server.has_clients() {
+            return true;
+        }
+    }
+    for output in state.root.outputs.lock().values() {
+        if output.screencasts.is_not_empty() || output.ext_copy_sessions.is_not_empty() {
+            return true;
+        }
+    }
+    for tl in state.toplevels.lock().values() {
+        if let Some(tl) = tl.upgrade() {
+            let data = tl.tl_data();
+            if data.jay_screencasts.is_not_empty() || data.ext_copy_sessions.is_not_empty() {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+pub fn escape_pango(src: &str, dst: &mut String) {
+    for c in src.chars() {
+        match c {
+            '&' => dst.push_str("&amp;"),
+            '<' => dst.push_str("&lt;"),
+            '>' => dst.push_str("&gt;"),
+            '\'' => dst.push_str("&apos;"),
+            '"' => dst.push_str("&quot;"),
+            _ => dst.push(c),
+        }
+    }
+}
+
+impl State {
+    pub fn set_bar_elements(&self, elements: Vec<BarElement>) {
+        let mut dependencies = 0;
+        for element in &elements {
+            dependencies |= match element.kind {
+                BarElementKind::FocusedWindowTitle | BarElementKind::FocusedWindowAppId => BE_FOCUS,
+                BarElementKind::InputModes => BE_INPUT_MODES,
+                BarElementKind::KeyboardLayout => BE_KEYBOARD_LAYOUT,
+                BarElementKind::LockIndicators => BE_LOCK_INDICATORS,
+                BarElementKind::ScreenShare => BE_SCREEN_SHARE,
+                BarElementKind::StickyKeys => BE_STICKY_KEYS,
+                _ => 0,
+            };
+        }
+        *self.bar_elements.elements.borrow_mut() = elements;
+        self.bar_elements.dependencies.set(dependencies);
+        self.update_bar_elements();
+    }
+
+    pub fn set_bar_element_separator(&self, separator: &str) {
+        *self.bar_elements.separator.borrow_mut() = Some(separator.to_string());
+        self.update_bar_elements();
+    }
+
+    pub fn bar_elements_changed(&self, changed: u32) {
+        if self.bar_elements.dependencies.get() & changed != 0 {
+            self.update_bar_elements();
+        }
+    }
+
+    fn update_bar_elements(&self) {
+        for output in self.root.outputs.lock().values() {
+            output.schedule_update_render_data();
+        }
+    }
+
+    pub fn format_bar_elements(&self) -> String {
+        self.bar_elements
+            .format(|kind, dst| element_text(self, kind, dst))
+    }
+}
@@ -0,0 +1,104 @@
+use crate::backend::LED_CAPS_LOCK;
+use crate::backend::LED_NUM_LOCK;
+use crate::backend::LED_SCROLL_LOCK;
+use crate::backend::Leds;
+use crate::bar_elements::BarElements;
+use crate::bar_elements::escape_pango;
+use crate::bar_elements::input_modes_text;
+use crate::bar_elements::lock_indicators_text;
+use crate::bar_elements::sticky_keys_text;
+use jay_config::theme::BarElement;
+use jay_config::theme::BarElementKind;
+use jay_config::theme::BarElementKind::*;
+use jay_config::theme::Color;
+
+fn elements(elements: &[BarElement]) -> BarElements {
+    let be = BarElements::default();
+    *be.elements.borrow_mut() = elements.to_vec();
+    be
+}
+
+fn text(kind: BarElementKind, dst: &mut String) {
+    match kind {
+        FocusedWindowTitle => dst.push_str("title"),
+        InputModes => dst.push_str("resize"),
+        KeyboardLayout => dst.push_str("<us>"),
+        _ => {}
+    }
+}
+
+#[test]
+fn separator() {
+    let be = elements(&[
+        BarElement::new(FocusedWindowTitle),
+        BarElement::new(InputModes),
+    ]);
+    assert_eq!(
+        be.format(text),
+        r##"title <span color="#333333">|</span> resize"##,
+    );
+    *be.separator.borrow_mut() = Some(" / ".to_string());
+    assert_eq!(be.format(text), "title / resize");
+}
+
+#[test]
+fn empty_elements_are_skipped() {
+    let be = elements(&[
+        BarElement::new(ScreenShare),
+        BarElement::new(FocusedWindowTitle),
+        BarElement::new(LockIndicators),
+        BarElement::new(InputModes),
+        BarElement::new(StickyKeys),
+    ]);
+    *be.separator.borrow_mut() = Some("|".to_string());
+    assert_eq!(be.format(text), "title|resize");
+    assert_eq!(elements(&[BarElement::new(ScreenShare)]).format(text), "");
+}
+
+#[test]
+fn color_and_escaping() {
+    let be = elements(&[
+        BarElement::new(KeyboardLayout).with_color(Color::new_straight(0xff, 0x80, 0x00, 0xff))
+    ]);
+    assert_eq!(
+        be.format(text),
+        r##"<span color="#ff8000ff">&lt;us&gt;</span>"##,
+    );
+}
+
+#[test]
+fn pango() {
+    let mut dst = String::new();
+    escape_pango(r#"a & <b> 'c' "d""#, &mut dst);
+    assert_eq!(dst, "a &amp; &lt;b&gt; &apos;c&apos; &quot;d&quot;");
+}
+
+#[test]
+fn input_modes() {
+    let mut dst = String::new();
+    input_modes_text(&[], &mut dst);
+    assert_eq!(dst, "");
+    input_modes_text(&["normal".to_string(), "resize".to_string()], &mut dst);
+    assert_eq!(dst, "normal > resize");
+}
+
+#[test]
+fn lock_indicators() {
+    let text = |leds: Leds| {
+        let mut dst = String::new();
+        lock_indicators_text(leds, &mut dst);
+        dst
+    };
+    assert_eq!(text(Leds::none()), "");
+    assert_eq!(text(LED_SCROLL_LOCK), "");
+    assert_eq!(text(LED_CAPS_LOCK), "Caps");
+    assert_eq!(text(LED_NUM_LOCK), "Num");
+    assert_eq!(text(LED_CAPS_LOCK | LED_NUM_LOCK), "Caps Num");
+}
+
+#[test]
+fn sticky_keys() {
+    let mut dst = String::new();
+    sticky_keys_text(&[("Shift", false), ("Ctrl", true)], &mut dst);
+    assert_eq!(dst, "Shift Ctrl (locked)");
+}
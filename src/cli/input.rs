@@ -1,7 +1,10 @@
 use crate::backend::InputDeviceAccelProfile;
 use crate::backend::InputDeviceCapability;
 use crate::backend::InputDeviceClickMethod;
+use crate::backend::InputDeviceCustomAccel;
 use crate::backend::InputDeviceScrollMethod;
+use crate::backend::InputDeviceSendEventsMode;
+use crate::backend::InputDeviceTapButtonMap;
 use crate::cli::GlobalArgs;
 use crate::cli::json::JsonInputData;
 use crate::cli::json::JsonInputDevice;
@@ -9,9 +12,15 @@ use crate::cli::json::JsonSeat;
 use crate::cli::json::jsonl;
 use crate::clientmem::ClientMem;
 use crate::evdev::input_event_codes::InputEventCode;
+use crate::ifs::jay_input::LAYOUT_MEMORY_APP_ID;
+use crate::ifs::jay_input::LAYOUT_MEMORY_GLOBAL;
+use crate::ifs::jay_input::LAYOUT_MEMORY_WINDOW;
 use crate::libinput::consts::ConfigClickMethod;
 use crate::libinput::consts::ConfigScrollMethod;
+use crate::libinput::consts::ConfigSendEventsMode;
+use crate::libinput::consts::ConfigTapButtonMap;
 use crate::libinput::consts::LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE;
+use crate::libinput::consts::LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM;
 use crate::libinput::consts::LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT;
 use crate::libinput::consts::LIBINPUT_CONFIG_CLICK_METHOD_BUTTON_AREAS;
 use crate::libinput::consts::LIBINPUT_CONFIG_CLICK_METHOD_CLICKFINGER;
@@ -20,6 +29,11 @@ use crate::libinput::consts::LIBINPUT_CONFIG_SCROLL_2FG;
 use crate::libinput::consts::LIBINPUT_CONFIG_SCROLL_EDGE;
 use crate::libinput::consts::LIBINPUT_CONFIG_SCROLL_NO_SCROLL;
 use crate::libinput::consts::LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN;
+use crate::libinput::consts::LIBINPUT_CONFIG_SEND_EVENTS_DISABLED;
+use crate::libinput::consts::LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE;
+use crate::libinput::consts::LIBINPUT_CONFIG_SEND_EVENTS_ENABLED;
+use crate::libinput::consts::LIBINPUT_CONFIG_TAP_MAP_LMR;
+use crate::libinput::consts::LIBINPUT_CONFIG_TAP_MAP_LRM;
 use crate::object::Version;
 use crate::tools::tool_client::Handle;
 use crate::tools::tool_client::ToolClient;
@@ -114,8 +128,36 @@ pub enum SeatCommand {
     UseHardwareCursor(UseHardwareCursorArgs),
     /// Set the size of the cursor.
     SetCursorSize(SetCursorSizeArgs),
+    /// Set the cursor theme.
+    SetCursorTheme(SetCursorThemeArgs),
     /// Configure the simple, XCompose based input method.
     SimpleIm(SimpleImArgs),
+    /// Set how the active keyboard layout is remembered when the focus changes.
+    SetLayoutMemory(SetLayoutMemoryArgs),
+    /// Set the active keyboard layout.
+    SetKeyboardGroup(SetKeyboardGroupArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetLayoutMemoryArgs {
+    /// The mode.
+    pub mode: LayoutMemory,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+pub enum LayoutMemory {
+    /// All windows share the same layout.
+    Global,
+    /// Each window remembers its own layout.
+    Window,
+    /// Each app ID remembers its own layout.
+    AppId,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetKeyboardGroupArgs {
+    /// The 0-based index of the layout.
+    pub group: u32,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -196,12 +238,25 @@ pub enum DeviceCommand {
     SetScrollButton(SetScrollButtonArgs),
     /// Set the scroll button locking.
     SetScrollButtonLock(SetScrollButtonLockArgs),
+    /// Set whether the device is disabled while typing.
+    SetDisableWhileTyping(SetDisableWhileTypingArgs),
+    /// Set whether the device is disabled while using a trackpoint.
+    SetDisableWhileTrackpointing(SetDisableWhileTrackpointingArgs),
+    /// Set the button mapping of 1/2/3-finger taps.
+    SetTapButtonMap(SetTapButtonMapArgs),
+    /// Set whether the device sends events.
+    SetSendEventsMode(SetSendEventsModeArgs),
+    /// Set the clockwise rotation of the device.
+    SetRotation(SetRotationArgs),
+    /// Set the points of the custom acceleration curve.
+    SetCustomAccel(SetCustomAccelArgs),
 }
 
 #[derive(ValueEnum, Debug, Clone)]
 pub enum AccelProfile {
     Flat,
     Adaptive,
+    Custom,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -322,6 +377,62 @@ pub struct SetScrollButtonLockArgs {
     pub enabled: bool,
 }
 
+#[derive(Args, Debug, Clone)]
+pub struct SetDisableWhileTypingArgs {
+    /// Whether disable-while-typing is enabled.
+    #[arg(action = clap::ArgAction::Set)]
+    pub enabled: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetDisableWhileTrackpointingArgs {
+    /// Whether disable-while-trackpointing is enabled.
+    #[arg(action = clap::ArgAction::Set)]
+    pub enabled: bool,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+pub enum TapButtonMap {
+    /// 1, 2, 3 fingers map to left, right, middle.
+    Lrm,
+    /// 1, 2, 3 fingers map to left, middle, right.
+    Lmr,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetTapButtonMapArgs {
+    /// The mapping.
+    pub map: TapButtonMap,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+pub enum SendEventsMode {
+    Enabled,
+    Disabled,
+    DisabledOnExternalMouse,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetSendEventsModeArgs {
+    /// The mode.
+    pub mode: SendEventsMode,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetRotationArgs {
+    /// The clockwise rotation in degrees. Must be in the range \[0, 360).
+    pub degrees: u32,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetCustomAccelArgs {
+    /// The distance between the points, in device units per millisecond.
+    pub step: f64,
+    /// The accelerated speed at each step, starting at speed 0.
+    #[clap(required = true, num_args = 2..=64)]
+    pub points: Vec<f64>,
+}
+
 #[derive(Debug, Error)]
 #[error("Value is neither `none` nor the name of a known button")]
 struct ParseButtonError;
@@ -366,6 +477,12 @@ pub struct SetCursorSizeArgs {
     pub size: u32,
 }
 
+#[derive(Args, Debug, Clone)]
+pub struct SetCursorThemeArgs {
+    /// The name of the cursor theme. An empty string restores the default theme.
+    pub theme: String,
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct SetKeymapArgs {
     /// The keymap group to use for shortcuts.
@@ -422,6 +539,8 @@ struct Seat {
     pub repeat_rate: i32,
     pub repeat_delay: i32,
     pub hardware_cursor: bool,
+    pub keyboard_group: Option<(u32, String)>,
+    pub layout_memory: Option<u32>,
 }
 
 #[derive(Clone, Debug)]
@@ -449,6 +568,13 @@ struct InputDevice {
     pub scroll_method: Option<InputDeviceScrollMethod>,
     pub scroll_button: Option<Option<InputEventCode>>,
     pub scroll_button_lock: Option<bool>,
+    pub dwt_enabled: Option<bool>,
+    pub dwtp_enabled: Option<bool>,
+    pub tap_button_map: Option<InputDeviceTapButtonMap>,
+    pub send_events_modes: Option<u32>,
+    pub send_events_mode: Option<InputDeviceSendEventsMode>,
+    pub rotation: Option<u32>,
+    pub custom_accel: Option<InputDeviceCustomAccel>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -652,6 +778,16 @@ impl Input {
                     size: a.size,
                 });
             }
+            SeatCommand::SetCursorTheme(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set cursor theme: {}", e);
+                });
+                tc.send(jay_input::SetCursorTheme {
+                    self_id: input,
+                    seat: &args.seat,
+                    theme: &a.theme,
+                });
+            }
             SeatCommand::SimpleIm(a) => match a.command {
                 SimpleImCommand::Enable | SimpleImCommand::Disable => {
                     self.handle_error(input, |e| {
@@ -673,6 +809,31 @@ impl Input {
                     });
                 }
             },
+            SeatCommand::SetLayoutMemory(a) => {
+                let mode = match a.mode {
+                    LayoutMemory::Global => LAYOUT_MEMORY_GLOBAL,
+                    LayoutMemory::Window => LAYOUT_MEMORY_WINDOW,
+                    LayoutMemory::AppId => LAYOUT_MEMORY_APP_ID,
+                };
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set the layout memory mode: {}", e);
+                });
+                tc.send(jay_input::SetLayoutMemory {
+                    self_id: input,
+                    seat: &args.seat,
+                    mode,
+                });
+            }
+            SeatCommand::SetKeyboardGroup(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set the keyboard group: {}", e);
+                });
+                tc.send(jay_input::SetKeyboardGroup {
+                    self_id: input,
+                    seat: &args.seat,
+                    group: a.group,
+                });
+            }
             SeatCommand::SetKeymapFromNames(a) => {
                 self.handle_error(input, |e| {
                     eprintln!("Could not set keymap: {}", e);
@@ -723,6 +884,7 @@ impl Input {
                 let profile = match a.profile {
                     AccelProfile::Flat => LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT.0,
                     AccelProfile::Adaptive => LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE.0,
+                    AccelProfile::Custom => LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM.0,
                 };
                 self.handle_error(input, |e| {
                     eprintln!("Could not set the acceleration profile: {}", e);
@@ -997,6 +1159,78 @@ impl Input {
                     enabled: a.enabled,
                 });
             }
+            DeviceCommand::SetDisableWhileTyping(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set disable-while-typing: {}", e);
+                });
+                tc.send(jay_input::SetDwt {
+                    self_id: input,
+                    id: args.device,
+                    enabled: a.enabled,
+                });
+            }
+            DeviceCommand::SetDisableWhileTrackpointing(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set disable-while-trackpointing: {}", e);
+                });
+                tc.send(jay_input::SetDwtp {
+                    self_id: input,
+                    id: args.device,
+                    enabled: a.enabled,
+                });
+            }
+            DeviceCommand::SetTapButtonMap(a) => {
+                let map = match a.map {
+                    TapButtonMap::Lrm => LIBINPUT_CONFIG_TAP_MAP_LRM,
+                    TapButtonMap::Lmr => LIBINPUT_CONFIG_TAP_MAP_LMR,
+                };
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set the tap button map: {}", e);
+                });
+                tc.send(jay_input::SetTapButtonMap {
+                    self_id: input,
+                    id: args.device,
+                    map: map.0,
+                });
+            }
+            DeviceCommand::SetSendEventsMode(a) => {
+                let mode = match a.mode {
+                    SendEventsMode::Enabled => LIBINPUT_CONFIG_SEND_EVENTS_ENABLED,
+                    SendEventsMode::Disabled => LIBINPUT_CONFIG_SEND_EVENTS_DISABLED,
+                    SendEventsMode::DisabledOnExternalMouse => {
+                        LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE
+                    }
+                };
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set the send-events mode: {}", e);
+                });
+                tc.send(jay_input::SetSendEventsMode {
+                    self_id: input,
+                    id: args.device,
+                    mode: mode.0 as u32,
+                });
+            }
+            DeviceCommand::SetRotation(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set the rotation: {}", e);
+                });
+                tc.send(jay_input::SetRotation {
+                    self_id: input,
+                    id: args.device,
+                    degrees_cw: a.degrees,
+                });
+            }
+            DeviceCommand::SetCustomAccel(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set the custom acceleration curve: {}", e);
+                });
+                tc.send(jay_input::SetCustomAccel {
+                    self_id: input,
+                    id: args.device,
+                    step: a.step,
+                    points: uapi::as_bytes(&a.points[..]),
+                });
+            }
         }
         tc.round_trip().await;
     }
@@ -1062,6 +1296,16 @@ impl Input {
         if !seat.hardware_cursor {
             println!("  hardware cursor disabled");
         }
+        if let Some((group, name)) = &seat.keyboard_group {
+            if name.is_empty() {
+                println!("  keyboard group: {}", group);
+            } else {
+                println!("  keyboard group: {} ({})", group, name);
+            }
+        }
+        if let Some(mode) = seat.layout_memory {
+            println!("  layout memory: {}", layout_memory_name(mode));
+        }
     }
 
     fn print_device(&self, prefix: &str, print_seat: bool, device: &InputDevice) {
@@ -1091,6 +1335,7 @@ impl Input {
             let name = match v {
                 InputDeviceAccelProfile::Flat => "flat",
                 InputDeviceAccelProfile::Adaptive => "adaptive",
+                InputDeviceAccelProfile::Custom => "custom",
             };
             println!("{prefix}  accel profile: {}", name);
         }
@@ -1150,6 +1395,31 @@ impl Input {
         if let Some(v) = &device.scroll_button_lock {
             println!("{prefix}  scroll button lock: {v}");
         }
+        if let Some(v) = &device.dwt_enabled {
+            println!("{prefix}  disable while typing: {v}");
+        }
+        if let Some(v) = &device.dwtp_enabled {
+            println!("{prefix}  disable while trackpointing: {v}");
+        }
+        if let Some(v) = device.tap_button_map {
+            println!("{prefix}  tap button map: {}", tap_button_map_name(v));
+        }
+        if let Some(v) = device.send_events_mode {
+            println!("{prefix}  send events mode: {}", send_events_mode_name(v));
+        }
+        if let Some(v) = device.send_events_modes {
+            println!(
+                "{prefix}  send events modes: {}",
+                supported_send_events_mode_names(v).join(","),
+            );
+        }
+        if let Some(v) = &device.rotation {
+            println!("{prefix}  rotation: {v}");
+        }
+        if let Some(v) = &device.custom_accel {
+            println!("{prefix}  custom accel step: {}", v.step);
+            println!("{prefix}  custom accel points: {:?}", v.points);
+        }
     }
 
     fn print_data_json(&self, mut data: Data) {
@@ -1165,6 +1435,13 @@ impl Input {
                     repeat_rate: Some(seat.repeat_rate),
                     repeat_delay: Some(seat.repeat_delay),
                     hardware_cursor: seat.hardware_cursor,
+                    keyboard_group: seat.keyboard_group.as_ref().map(|g| g.0),
+                    keyboard_group_name: seat
+                        .keyboard_group
+                        .as_ref()
+                        .map(|g| &*g.1)
+                        .filter(|n| !n.is_empty()),
+                    layout_memory: seat.layout_memory.map(layout_memory_name),
                     devices: Default::default(),
                 },
             );
@@ -1177,6 +1454,9 @@ impl Input {
                     repeat_rate: Default::default(),
                     repeat_delay: Default::default(),
                     hardware_cursor: Default::default(),
+                    keyboard_group: Default::default(),
+                    keyboard_group_name: Default::default(),
+                    layout_memory: Default::default(),
                     devices: Default::default(),
                 });
                 seat.devices.push(device);
@@ -1206,8 +1486,22 @@ impl Input {
                 repeat_rate: msg.repeat_rate,
                 repeat_delay: msg.repeat_delay,
                 hardware_cursor: msg.hardware_cursor,
+                keyboard_group: None,
+                layout_memory: None,
             });
         });
+        jay_input::KeyboardGroup::handle(tc, input, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.seats.last_mut() {
+                last.keyboard_group = Some((msg.group, msg.name.to_string()));
+            }
+        });
+        jay_input::LayoutMemory::handle(tc, input, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.seats.last_mut() {
+                last.layout_memory = Some(msg.mode);
+            }
+        });
         jay_input::InputDevice::handle(tc, input, data.clone(), |data, msg| {
             use crate::backend::InputDeviceCapability::*;
             use crate::libinput::consts::*;
@@ -1236,6 +1530,7 @@ impl Input {
             let mut accel_profile = match AccelProfile(msg.accel_profile) {
                 LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT => Some(InputDeviceAccelProfile::Flat),
                 LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE => Some(InputDeviceAccelProfile::Adaptive),
+                LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM => Some(InputDeviceAccelProfile::Custom),
                 _ => None,
             };
             if !accel_available {
@@ -1267,6 +1562,13 @@ impl Input {
                 scroll_method: None,
                 scroll_button: None,
                 scroll_button_lock: None,
+                dwt_enabled: None,
+                dwtp_enabled: None,
+                tap_button_map: None,
+                send_events_modes: None,
+                send_events_mode: None,
+                rotation: None,
+                custom_accel: None,
             });
         });
         jay_input::InputDeviceOutput::handle(tc, input, data.clone(), |data, msg| {
@@ -1331,6 +1633,59 @@ impl Input {
                 last.scroll_button_lock = Some(msg.enabled);
             }
         });
+        jay_input::Dwt::handle(tc, input, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.input_device.last_mut() {
+                last.dwt_enabled = Some(msg.enabled);
+            }
+        });
+        jay_input::Dwtp::handle(tc, input, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.input_device.last_mut() {
+                last.dwtp_enabled = Some(msg.enabled);
+            }
+        });
+        jay_input::TapButtonMap::handle(tc, input, data.clone(), |data, msg| {
+            let map = InputDeviceTapButtonMap::from_libinput(ConfigTapButtonMap(msg.map));
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.input_device.last_mut() {
+                last.tap_button_map = map;
+            }
+        });
+        jay_input::SendEventsModes::handle(tc, input, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.input_device.last_mut() {
+                last.send_events_modes = Some(msg.modes);
+            }
+        });
+        jay_input::SendEventsMode::handle(tc, input, data.clone(), |data, msg| {
+            let mode =
+                InputDeviceSendEventsMode::from_libinput(ConfigSendEventsMode(msg.mode as i32));
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.input_device.last_mut() {
+                last.send_events_mode = mode;
+            }
+        });
+        jay_input::Rotation::handle(tc, input, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.input_device.last_mut() {
+                last.rotation = Some(msg.degrees_cw);
+            }
+        });
+        jay_input::CustomAccel::handle(tc, input, data.clone(), |data, msg| {
+            let points = msg
+                .points
+                .chunks_exact(size_of::<f64>())
+                .map(|c| f64::from_ne_bytes(c.try_into().unwrap()))
+                .collect();
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.input_device.last_mut() {
+                last.custom_accel = Some(InputDeviceCustomAccel {
+                    step: msg.step,
+                    points,
+                });
+            }
+        });
         tc.round_trip().await;
         data.borrow_mut().clone()
     }
@@ -1363,6 +1718,46 @@ fn scroll_button_name(button: Option<InputEventCode>) -> &'static str {
     button.map(|v| v.text()).unwrap_or("none")
 }
 
+fn layout_memory_name(mode: u32) -> &'static str {
+    match mode {
+        LAYOUT_MEMORY_GLOBAL => "global",
+        LAYOUT_MEMORY_WINDOW => "window",
+        LAYOUT_MEMORY_APP_ID => "app-id",
+        _ => "unknown",
+    }
+}
+
+fn tap_button_map_name(map: InputDeviceTapButtonMap) -> &'static str {
+    match map {
+        InputDeviceTapButtonMap::LeftRightMiddle => "lrm",
+        InputDeviceTapButtonMap::LeftMiddleRight => "lmr",
+    }
+}
+
+fn send_events_mode_name(mode: InputDeviceSendEventsMode) -> &'static str {
+    match mode {
+        InputDeviceSendEventsMode::Enabled => "enabled",
+        InputDeviceSendEventsMode::Disabled => "disabled",
+        InputDeviceSendEventsMode::DisabledOnExternalMouse => "disabled-on-external-mouse",
+    }
+}
+
+fn supported_send_events_mode_names(modes: u32) -> Vec<&'static str> {
+    let mut names = vec!["enabled"];
+    for (name, const_) in [
+        ("disabled", LIBINPUT_CONFIG_SEND_EVENTS_DISABLED),
+        (
+            "disabled-on-external-mouse",
+            LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE,
+        ),
+    ] {
+        if modes.contains(const_.0 as u32) {
+            names.push(name);
+        }
+    }
+    names
+}
+
 fn make_json_device(device: &InputDevice) -> JsonInputDevice<'_> {
     JsonInputDevice {
         input_device_id: device.id,
@@ -1388,5 +1783,15 @@ fn make_json_device(device: &InputDevice) -> JsonInputDevice<'_> {
         supported_scroll_methods: device.scroll_methods.map(supported_scroll_method_names),
         scroll_button: device.scroll_button.map(scroll_button_name),
         scroll_button_lock: device.scroll_button_lock,
+        disable_while_typing: device.dwt_enabled,
+        disable_while_trackpointing: device.dwtp_enabled,
+        tap_button_map: device.tap_button_map.map(tap_button_map_name),
+        send_events_mode: device.send_events_mode.map(send_events_mode_name),
+        supported_send_events_modes: device
+            .send_events_modes
+            .map(supported_send_events_mode_names),
+        rotation: device.rotation,
+        custom_accel_step: device.custom_accel.as_ref().map(|a| a.step),
+        custom_accel_points: device.custom_accel.as_ref().map(|a| &a.points[..]),
     }
 }
@@ -183,7 +183,17 @@ pub struct JsonOutput<'a> {
     pub non_desktop: bool,
     pub scale: f64,
     #[serde(skip_serializing_if = "is_none")]
+    pub auto_scale: Option<f64>,
+    #[serde(skip_serializing_if = "is_none")]
     pub scaling_filter: Option<&'a str>,
+    #[serde(skip_serializing_if = "is_none")]
+    pub power: Option<&'static str>,
+    #[serde(skip_serializing_if = "is_none")]
+    pub icc_profile: Option<&'a str>,
+    #[serde(skip_serializing_if = "is_none")]
+    pub vnc_server: Option<&'a str>,
+    #[serde(skip_serializing_if = "is_none")]
+    pub backlight: Option<f64>,
     pub x: i32,
     pub y: i32,
     pub width: i32,
@@ -269,6 +279,12 @@ pub struct JsonSeat<'a> {
     pub repeat_delay: Option<i32>,
     #[serde(skip_serializing_if = "is_false")]
     pub hardware_cursor: bool,
+    #[serde(skip_serializing_if = "is_none")]
+    pub keyboard_group: Option<u32>,
+    #[serde(skip_serializing_if = "is_none")]
+    pub keyboard_group_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "is_none")]
+    pub layout_memory: Option<&'static str>,
     #[serde(skip_serializing_if = "is_empty")]
     pub devices: Vec<JsonInputDevice<'a>>,
 }
@@ -319,6 +335,22 @@ pub struct JsonInputDevice<'a> {
     pub scroll_button: Option<&'a str>,
     #[serde(skip_serializing_if = "is_none")]
     pub scroll_button_lock: Option<bool>,
+    #[serde(skip_serializing_if = "is_none")]
+    pub disable_while_typing: Option<bool>,
+    #[serde(skip_serializing_if = "is_none")]
+    pub disable_while_trackpointing: Option<bool>,
+    #[serde(skip_serializing_if = "is_none")]
+    pub tap_button_map: Option<&'a str>,
+    #[serde(skip_serializing_if = "is_none")]
+    pub send_events_mode: Option<&'a str>,
+    #[serde(skip_serializing_if = "is_none")]
+    pub supported_send_events_modes: Option<Vec<&'a str>>,
+    #[serde(skip_serializing_if = "is_none")]
+    pub rotation: Option<u32>,
+    #[serde(skip_serializing_if = "is_none")]
+    pub custom_accel_step: Option<f64>,
+    #[serde(skip_serializing_if = "is_none")]
+    pub custom_accel_points: Option<&'a [f64]>,
 }
 
 pub struct JsonTreeNodeType(pub u32);
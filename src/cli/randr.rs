@@ -15,6 +15,7 @@ use crate::format::Format;
 use crate::format::XRGB8888;
 use crate::gfx_api::ScalingFilter;
 use crate::ifs::wl_output::BlendSpace;
+use crate::object::Version;
 use crate::scale::Scale;
 use crate::tools::tool_client::Handle;
 use crate::tools::tool_client::ToolClient;
@@ -41,6 +42,7 @@ use std::cell::RefCell;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::{self};
+use std::io::Write;
 use std::rc::Rc;
 use std::str::FromStr;
 use std::time::Duration;
@@ -64,6 +66,8 @@ pub enum RandrCmd {
     Output(OutputArgs),
     /// Modify virtual outputs.
     VirtualOutput(VirtualOutputArgs),
+    /// Manage output profiles.
+    Profile(ProfileArgs),
 }
 
 #[derive(Args, Debug, Default)]
@@ -205,6 +209,140 @@ pub enum OutputCommand {
     BlendSpace(BlendSpaceArgs),
     /// Change whether the display primaries are used.
     UseNativeGamut(UseNativeGamutArgs),
+    /// Turn the display on or off without changing the layout.
+    Dpms(DpmsArgs),
+    /// Set the ICC profile of the display.
+    IccProfile(IccProfileArgs),
+    /// Set a mode that is not advertised by the display.
+    CustomMode(CustomModeArgs),
+    /// Dump or override the EDID of the display.
+    Edid(EdidArgs),
+    /// Serve the contents of the output via VNC.
+    Vnc(VncArgs),
+    /// Change the brightness of the backlight.
+    Backlight(BacklightArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct BacklightArgs {
+    /// The brightness in percent.
+    ///
+    /// If the value starts with `+` or `-`, the brightness is changed relative to the
+    /// current brightness.
+    ///
+    /// For outputs that use the PQ transfer function, this changes the brightness of
+    /// standard white instead.
+    #[clap(value_parser = parse_backlight, allow_hyphen_values = true)]
+    pub value: BacklightValue,
+}
+
+#[derive(Debug, Clone)]
+pub struct BacklightValue {
+    value: f64,
+    relative: bool,
+}
+
+#[derive(Debug, Error)]
+#[error("Value is not a floating point value")]
+struct ParseBacklightError;
+
+fn parse_backlight(s: &str) -> Result<BacklightValue, ParseBacklightError> {
+    let relative = s.starts_with(['+', '-']);
+    match f64::from_str(s) {
+        Ok(value) if value.is_finite() => Ok(BacklightValue { value, relative }),
+        _ => Err(ParseBacklightError),
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct VncArgs {
+    /// The name of a unix socket in `$XDG_RUNTIME_DIR`, such as `jay-vnc`, a loopback
+    /// TCP port or address, such as `5900`, or `none` to stop the server.
+    pub address: String,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct EdidArgs {
+    #[clap(subcommand)]
+    pub cmd: EdidCmd,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum EdidCmd {
+    /// Write the current EDID of the display to a file.
+    Dump(EdidDumpArgs),
+    /// Replace the EDID of the display with the contents of a file.
+    Override(EdidOverrideArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct EdidDumpArgs {
+    /// The file to write the EDID to or `-` to write it to stdout.
+    pub path: String,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct EdidOverrideArgs {
+    /// The path of a binary EDID blob or `none` to remove the override.
+    pub path: String,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct CustomModeArgs {
+    #[clap(subcommand)]
+    pub cmd: CustomModeCmd,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum CustomModeCmd {
+    /// Generate the timings with the VESA CVT formula.
+    Cvt(CvtModeArgs),
+    /// Use the timings of an X11-style modeline.
+    ///
+    /// The modeline has the format printed by `cvt` and `gtf`, e.g.
+    /// `173.00 1920 2048 2248 2576 1080 1083 1088 1120 -hsync +vsync`.
+    Modeline(ModelineArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct CvtModeArgs {
+    /// The width.
+    pub width: i32,
+    /// The height.
+    pub height: i32,
+    /// The refresh rate.
+    pub refresh_rate: f64,
+    /// Use reduced blanking timings. These require a lower pixel clock and are usually
+    /// preferable for digital displays.
+    #[arg(long)]
+    pub reduced_blanking: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ModelineArgs {
+    /// The modeline.
+    #[arg(required = true, num_args = 1.., allow_hyphen_values = true, trailing_var_arg = true)]
+    pub modeline: Vec<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct IccProfileArgs {
+    /// The path of a matrix/TRC ICC profile or `none` to remove the profile.
+    pub path: String,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct DpmsArgs {
+    /// The new power state of the display.
+    pub state: DpmsState,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+pub enum DpmsState {
+    /// Turn the display on.
+    On,
+    /// Turn the display off.
+    Off,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -539,6 +677,44 @@ pub struct RemoveVirtualOutputArgs {
     pub name: String,
 }
 
+#[derive(Args, Debug)]
+pub struct ProfileArgs {
+    #[clap(subcommand)]
+    pub command: ProfileCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ProfileCommand {
+    /// List the output profiles defined in the configuration.
+    List,
+    /// Apply an output profile.
+    Apply(ApplyProfileArgs),
+    /// Print the current output configuration as an output profile.
+    ///
+    /// The output can be appended to the config.toml.
+    SaveCurrent(SaveCurrentProfileArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ApplyProfileArgs {
+    /// The name of the profile.
+    pub name: String,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SaveCurrentProfileArgs {
+    /// The name of the profile.
+    pub name: String,
+}
+
+const OUTPUT_PROFILES_SINCE: Version = Version(48);
+const DPMS_SINCE: Version = Version(49);
+const ICC_PROFILE_SINCE: Version = Version(50);
+const CUSTOM_MODE_SINCE: Version = Version(51);
+const EDID_SINCE: Version = Version(52);
+const VNC_SINCE: Version = Version(54);
+const BACKLIGHT_SINCE: Version = Version(55);
+
 pub fn main(global: GlobalArgs, args: RandrArgs) {
     with_tool_client(|tc| async move {
         let idle = Rc::new(Randr { tc: tc.clone() });
@@ -605,6 +781,11 @@ struct Output {
     pub use_native_gamut: bool,
     pub arbitrary_modes: bool,
     pub scaling_filter: Option<ScalingFilter>,
+    pub dpms: Option<bool>,
+    pub icc_profile: Option<String>,
+    pub auto_scale: Option<f64>,
+    pub vnc_server: Option<String>,
+    pub backlight: Option<f64>,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -638,6 +819,7 @@ struct Data {
     default_api: String,
     drm_devices: Vec<Device>,
     connectors: Vec<Connector>,
+    profiles: Vec<(String, bool)>,
 }
 
 struct Randr {
@@ -658,6 +840,7 @@ impl Randr {
             RandrCmd::Card(args) => self.card(randr, args).await,
             RandrCmd::Output(args) => self.output(randr, args).await,
             RandrCmd::VirtualOutput(args) => self.virtual_output(randr, args).await,
+            RandrCmd::Profile(args) => self.profile(randr, args).await,
         }
     }
 
@@ -935,6 +1118,149 @@ impl Randr {
                     },
                 });
             }
+            OutputCommand::Dpms(a) => {
+                if tc.jay_compositor_version().await < DPMS_SINCE {
+                    fatal!("The compositor does not support changing the power state of outputs");
+                }
+                self.handle_error(randr, move |msg| {
+                    eprintln!("Could not change the power state: {}", msg);
+                });
+                tc.send(jay_randr::SetDpms {
+                    self_id: randr,
+                    output: &args.output,
+                    on: matches!(a.state, DpmsState::On) as _,
+                });
+            }
+            OutputCommand::IccProfile(a) => {
+                if tc.jay_compositor_version().await < ICC_PROFILE_SINCE {
+                    fatal!("The compositor does not support ICC profiles");
+                }
+                let path = match a.path.as_str() {
+                    "none" => String::new(),
+                    _ => match std::path::absolute(&a.path) {
+                        Ok(p) => p.display().to_string(),
+                        Err(e) => fatal!("Could not resolve {}: {}", a.path, ErrorFmt(e)),
+                    },
+                };
+                self.handle_error(randr, move |msg| {
+                    eprintln!("Could not change the ICC profile: {}", msg);
+                });
+                tc.send(jay_randr::SetIccProfile {
+                    self_id: randr,
+                    output: &args.output,
+                    path: &path,
+                });
+            }
+            OutputCommand::CustomMode(a) => {
+                if tc.jay_compositor_version().await < CUSTOM_MODE_SINCE {
+                    fatal!("The compositor does not support custom modes");
+                }
+                self.handle_error(randr, |msg| {
+                    eprintln!("Could not modify the mode: {}", msg);
+                });
+                match a.cmd {
+                    CustomModeCmd::Cvt(c) => {
+                        tc.send(jay_randr::SetCvtMode {
+                            self_id: randr,
+                            output: &args.output,
+                            width: c.width,
+                            height: c.height,
+                            refresh_rate_millihz: (c.refresh_rate * 1_000.0).round() as u32,
+                            reduced_blanking: c.reduced_blanking,
+                        });
+                    }
+                    CustomModeCmd::Modeline(m) => {
+                        tc.send(jay_randr::SetModeline {
+                            self_id: randr,
+                            output: &args.output,
+                            modeline: &m.modeline.join(" "),
+                        });
+                    }
+                }
+            }
+            OutputCommand::Edid(a) => {
+                if tc.jay_compositor_version().await < EDID_SINCE {
+                    fatal!("The compositor does not support EDID overrides");
+                }
+                match a.cmd {
+                    EdidCmd::Dump(d) => {
+                        self.handle_error(randr, |msg| {
+                            eprintln!("Could not retrieve the EDID: {}", msg);
+                        });
+                        let edid = Rc::new(RefCell::new(None));
+                        jay_randr::Edid::handle(tc, randr, edid.clone(), |edid, msg| {
+                            *edid.borrow_mut() = Some(msg.data.to_vec());
+                        });
+                        tc.send(jay_randr::GetEdid {
+                            self_id: randr,
+                            output: &args.output,
+                        });
+                        tc.round_trip().await;
+                        let Some(edid) = edid.take() else {
+                            fatal!("The compositor did not send an EDID");
+                        };
+                        let res = match d.path.as_str() {
+                            "-" => std::io::stdout().write_all(&edid),
+                            _ => std::fs::write(&d.path, &edid),
+                        };
+                        if let Err(e) = res {
+                            fatal!("Could not write the EDID: {}", ErrorFmt(e));
+                        }
+                    }
+                    EdidCmd::Override(o) => {
+                        let path = match o.path.as_str() {
+                            "none" => String::new(),
+                            _ => match std::path::absolute(&o.path) {
+                                Ok(p) => p.display().to_string(),
+                                Err(e) => fatal!("Could not resolve {}: {}", o.path, ErrorFmt(e)),
+                            },
+                        };
+                        self.handle_error(randr, |msg| {
+                            eprintln!("Could not override the EDID: {}", msg);
+                        });
+                        tc.send(jay_randr::SetEdidOverride {
+                            self_id: randr,
+                            output: &args.output,
+                            path: &path,
+                        });
+                    }
+                }
+            }
+            OutputCommand::Vnc(a) => {
+                if tc.jay_compositor_version().await < VNC_SINCE {
+                    fatal!("The compositor does not support VNC");
+                }
+                let address = match a.address.as_str() {
+                    "none" => String::new(),
+                    _ if a.address.contains(':') => a.address,
+                    _ => match std::path::absolute(&a.address) {
+                        Ok(p) => p.display().to_string(),
+                        Err(e) => fatal!("Could not resolve {}: {}", a.address, ErrorFmt(e)),
+                    },
+                };
+                self.handle_error(randr, |msg| {
+                    eprintln!("Could not change the VNC server: {}", msg);
+                });
+                tc.send(jay_randr::SetVncServer {
+                    self_id: randr,
+                    output: &args.output,
+                    address: &address,
+                });
+            }
+            OutputCommand::Backlight(a) => {
+                if tc.jay_compositor_version().await < BACKLIGHT_SINCE {
+                    fatal!("The compositor does not support backlight control");
+                }
+                self.handle_error(randr, |msg| {
+                    eprintln!("Could not change the backlight: {}", msg);
+                });
+                tc.send(jay_randr::SetBacklight {
+                    self_id: randr,
+                    output: &args.output,
+                    value: a.value.value,
+                    relative: a.value.relative,
+                });
+            }
         }
         tc.round_trip().await;
     }
@@ -964,6 +1290,103 @@ impl Randr {
         tc.round_trip().await;
     }
 
+    async fn profile(self: &Rc<Self>, randr: JayRandrId, args: ProfileArgs) {
+        let tc = &self.tc;
+        if !matches!(args.command, ProfileCommand::SaveCurrent(_))
+            && tc.jay_compositor_version().await < OUTPUT_PROFILES_SINCE
+        {
+            fatal!("The compositor does not support output profiles");
+        }
+        match args.command {
+            ProfileCommand::List => {
+                let data = self.get(randr).await;
+                for (name, active) in &data.profiles {
+                    if *active {
+                        println!("{} (active)", name);
+                    } else {
+                        println!("{}", name);
+                    }
+                }
+            }
+            ProfileCommand::Apply(a) => {
+                self.handle_error(randr, |msg| {
+                    eprintln!("Could not apply the output profile: {}", msg);
+                });
+                tc.send(jay_randr::ApplyOutputProfile {
+                    self_id: randr,
+                    name: &a.name,
+                });
+                tc.round_trip().await;
+            }
+            ProfileCommand::SaveCurrent(a) => {
+                let data = self.get(randr).await;
+                self.print_profile(&data, &a.name);
+            }
+        }
+    }
+
+    fn print_profile(&self, data: &Data, name: &str) {
+        println!("[[output-profiles]]");
+        println!("name = {}", toml_string(name));
+        for connector in &data.connectors {
+            let o = connector.output.as_ref();
+            if !connector.enabled {
+                println!();
+                println!("[[output-profiles.outputs]]");
+                print_profile_match(connector, o);
+                println!("enabled = false");
+                continue;
+            }
+            let Some(o) = o else {
+                continue;
+            };
+            if o.non_desktop {
+                continue;
+            }
+            println!();
+            println!("[[output-profiles.outputs]]");
+            print_profile_match(connector, Some(o));
+            println!("x = {}", o.x);
+            println!("y = {}", o.y);
+            println!("scale = {}", o.scale);
+            let transform = match o.transform {
+                Transform::None => "none",
+                Transform::Rotate90 => "rotate-90",
+                Transform::Rotate180 => "rotate-180",
+                Transform::Rotate270 => "rotate-270",
+                Transform::Flip => "flip",
+                Transform::FlipRotate90 => "flip-rotate-90",
+                Transform::FlipRotate180 => "flip-rotate-180",
+                Transform::FlipRotate270 => "flip-rotate-270",
+            };
+            println!("transform = \"{}\"", transform);
+            if let Some(mode) = &o.current_mode {
+                println!(
+                    "mode = {{ width = {}, height = {}, refresh-rate = {} }}",
+                    mode.width,
+                    mode.height,
+                    mode.refresh_rate(),
+                );
+            }
+            if let Some(mode) = vrr_mode_name(o.vrr_mode) {
+                let mut vrr = format!("mode = \"{mode}\"");
+                if let Some(hz) = o.vrr_cursor_hz {
+                    match hz.is_finite() {
+                        true => vrr.push_str(&format!(", cursor-hz = {hz}")),
+                        false => vrr.push_str(", cursor-hz = \"none\""),
+                    }
+                }
+                println!("vrr = {{ {vrr} }}");
+            }
+            if let Some(mode) = tearing_mode_name(o.tearing_mode) {
+                println!("tearing = {{ mode = \"{mode}\" }}");
+            }
+            if let Some(format) = &o.format {
+                println!("format = {}", toml_string(format));
+            }
+        }
+    }
+
     async fn card(self: &Rc<Self>, randr: JayRandrId, args: CardArgs) {
         let tc = &self.tc;
         match args.command {
@@ -1164,12 +1587,8 @@ impl Randr {
         if o.vrr_capable {
             println!("        VRR enabled: {}", o.vrr_enabled);
             let mode_str;
-            let mode = match o.vrr_mode {
-                VrrMode::NEVER => "never",
-                VrrMode::ALWAYS => "always",
-                VrrMode::VARIANT_1 => "variant1",
-                VrrMode::VARIANT_2 => "variant2",
-                VrrMode::VARIANT_3 => "variant3",
+            let mode = match vrr_mode_name(o.vrr_mode) {
+                Some(mode) => mode,
                 _ => {
                     mode_str = format!("unknown ({})", o.vrr_mode.0);
                     &mode_str
@@ -1182,12 +1601,8 @@ impl Randr {
         }
         {
             let mode_str;
-            let mode = match o.tearing_mode {
-                TearingMode::NEVER => "never",
-                TearingMode::ALWAYS => "always",
-                TearingMode::VARIANT_1 => "variant1",
-                TearingMode::VARIANT_2 => "variant2",
-                TearingMode::VARIANT_3 => "variant3",
+            let mode = match tearing_mode_name(o.tearing_mode) {
+                Some(mode) => mode,
                 _ => {
                     mode_str = format!("unknown ({})", o.tearing_mode.0);
                     &mode_str
@@ -1209,9 +1624,24 @@ impl Randr {
         if o.scale != 1.0 {
             println!("        scale: {}", o.scale);
         }
+        if let Some(scale) = o.auto_scale {
+            println!("        auto scale: {}", scale);
+        }
         if let Some(v) = &o.scaling_filter {
             println!("        scaling filter: {}", v.text());
         }
+        if o.dpms == Some(false) {
+            println!("        power: off");
+        }
+        if let Some(path) = &o.icc_profile {
+            println!("        ICC profile: {path}");
+        }
+        if let Some(address) = &o.vnc_server {
+            println!("        VNC server: {address}");
+        }
+        if let Some(percent) = o.backlight {
+            println!("        backlight: {percent:.0}%");
+        }
         if o.transform != Transform::None {
             println!("        transform: {}", o.transform.text());
         }
@@ -1498,11 +1928,97 @@ impl Randr {
             output.scaling_filter =
                 ScalingFilter::from_config(ConfigScalingFilter(msg.scaling_filter));
         });
+        jay_randr::Dpms::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            let c = data.connectors.last_mut().unwrap();
+            let output = c.output.as_mut().unwrap();
+            output.dpms = Some(msg.on);
+        });
+        jay_randr::IccProfile::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            let c = data.connectors.last_mut().unwrap();
+            let output = c.output.as_mut().unwrap();
+            output.icc_profile = Some(msg.path.to_string());
+        });
+        jay_randr::AutoScale::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            let c = data.connectors.last_mut().unwrap();
+            let output = c.output.as_mut().unwrap();
+            output.auto_scale = Some(Scale::from_wl(msg.scale).to_f64());
+        });
+        jay_randr::VncServer::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            let c = data.connectors.last_mut().unwrap();
+            let output = c.output.as_mut().unwrap();
+            output.vnc_server = Some(msg.address.to_string());
+        });
+        jay_randr::Backlight::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            let c = data.connectors.last_mut().unwrap();
+            let output = c.output.as_mut().unwrap();
+            output.backlight = Some(msg.percent);
+        });
+        jay_randr::OutputProfile::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            data.profiles.push((msg.name.to_string(), msg.active));
+        });
         tc.round_trip().await;
         data.borrow_mut().clone()
     }
 }
 
+fn print_profile_match(connector: &Connector, o: Option<&Output>) {
+    let mut matchers = vec![];
+    match o {
+        Some(o) if !o.serial_number.is_empty() => {
+            matchers.push(format!("manufacturer = {}", toml_string(&o.manufacturer)));
+            matchers.push(format!("model = {}", toml_string(&o.product)));
+            matchers.push(format!("serial-number = {}", toml_string(&o.serial_number)));
+        }
+        _ => matchers.push(format!("connector = {}", toml_string(&connector.name))),
+    }
+    println!("match = {{ {} }}", matchers.join(", "));
+}
+
+fn vrr_mode_name(mode: VrrMode) -> Option<&'static str> {
+    let name = match mode {
+        VrrMode::NEVER => "never",
+        VrrMode::ALWAYS => "always",
+        VrrMode::VARIANT_1 => "variant1",
+        VrrMode::VARIANT_2 => "variant2",
+        VrrMode::VARIANT_3 => "variant3",
+        _ => return None,
+    };
+    Some(name)
+}
+
+fn tearing_mode_name(mode: TearingMode) -> Option<&'static str> {
+    let name = match mode {
+        TearingMode::NEVER => "never",
+        TearingMode::ALWAYS => "always",
+        TearingMode::VARIANT_1 => "variant1",
+        TearingMode::VARIANT_2 => "variant2",
+        TearingMode::VARIANT_3 => "variant3",
+        _ => return None,
+    };
+    Some(name)
+}
+
+fn toml_string(s: &str) -> String {
+    let mut res = String::with_capacity(s.len() + 2);
+    res.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            c if c.is_control() => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
 fn make_json_connector(c: &Connector) -> JsonConnector<'_> {
     let output = c.output.as_ref().map(|o| {
         let modes = o
@@ -1524,7 +2040,15 @@ fn make_json_connector(c: &Connector) -> JsonConnector<'_> {
             height_mm: o.height_mm,
             non_desktop: o.non_desktop,
             scale: o.scale,
+            auto_scale: o.auto_scale,
             scaling_filter: o.scaling_filter.map(|f| f.text()),
+            power: o.dpms.map(|on| match on {
+                true => "on",
+                false => "off",
+            }),
+            icc_profile: o.icc_profile.as_deref(),
+            vnc_server: o.vnc_server.as_deref(),
+            backlight: o.backlight,
             x: o.x,
             y: o.y,
             width: o.width,
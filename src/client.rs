@@ -80,6 +80,7 @@ bitflags! {
         CAP_HEAD_MANAGER             = 1 << 13,
         CAP_GAMMA_CONTROL_MANAGER    = 1 << 14,
         CAP_VIRTUAL_POINTER_MANAGER  = 1 << 15,
+        CAP_OUTPUT_POWER_MANAGER     = 1 << 16,
 }
 
 impl StaticText for ClientCapsEnum {
@@ -101,6 +102,7 @@ impl StaticText for ClientCapsEnum {
             ClientCapsEnum::CAP_HEAD_MANAGER => "head-manager",
             ClientCapsEnum::CAP_GAMMA_CONTROL_MANAGER => "gamma-control-manager",
             ClientCapsEnum::CAP_VIRTUAL_POINTER_MANAGER => "virtual-pointer",
+            ClientCapsEnum::CAP_OUTPUT_POWER_MANAGER => "output-power-manager",
         }
     }
 }
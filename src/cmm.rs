@@ -1,5 +1,6 @@
 pub mod cmm_description;
 pub mod cmm_eotf;
+pub mod cmm_icc;
 pub mod cmm_luminance;
 pub mod cmm_manager;
 pub mod cmm_primaries;
@@ -0,0 +1,533 @@
+use crate::cmm::cmm_eotf::Eotf;
+use crate::cmm::cmm_eotf::EotfPow;
+use crate::cmm::cmm_primaries::Primaries;
+use crate::cmm::cmm_transform::ColorMatrix;
+use crate::cmm::cmm_transform::Xyz;
+use crate::cmm::cmm_transform::bradford_adjustment;
+use crate::cpu_worker::AsyncCpuWork;
+use crate::cpu_worker::CpuJob;
+use crate::cpu_worker::CpuWork;
+use crate::cpu_worker::CpuWorker;
+use crate::utils::ordered_float::F64;
+use jay_algorithms::tf::eotfs;
+use std::io;
+use thiserror::Error;
+
+const HEADER_SIZE: usize = 128;
+const MAX_PROFILE_SIZE: usize = 16 * 1024 * 1024;
+const NUM_SAMPLES: usize = 256;
+/// Corrections below this threshold are not visible even on 10-bit outputs.
+const GAMMA_LUT_TOLERANCE: f64 = 1.0 / 2048.0;
+
+const D50: [f64; 3] = [0.9642, 1.0, 0.8249];
+
+#[derive(Debug, Error)]
+pub enum IccError {
+    #[error("Could not read {0}")]
+    Read(String, #[source] io::Error),
+    #[error("The profile is larger than 16 MiB")]
+    TooLarge,
+    #[error("The profile is truncated")]
+    Truncated,
+    #[error("The file is not an ICC profile")]
+    NotAProfile,
+    #[error("Only RGB profiles are supported")]
+    NotRgb,
+    #[error("The profile does not contain the tag `{0}`")]
+    MissingTag(&'static str),
+    #[error("Tag `{0}` has an unsupported type `{1}`")]
+    UnsupportedTagType(&'static str, String),
+    #[error("Tag `{0}` is malformed")]
+    MalformedTag(&'static str),
+    #[error("Only matrix/TRC profiles are supported but the profile only contains LUTs")]
+    LutOnly,
+    #[error("The profile describes degenerate primaries")]
+    DegeneratePrimaries,
+}
+
+#[derive(Debug)]
+pub struct IccProfile {
+    pub path: String,
+    pub primaries: Primaries,
+    /// The transfer function used by the renderer. This is the supported transfer
+    /// function that best approximates the tone curves of the profile.
+    pub eotf: Eotf,
+    trc: [Curve; 3],
+}
+
+/// A per-channel lookup table that maps values encoded with the transfer function of
+/// the profile to the values that produce the same luminance with the tone curves
+/// of the profile.
+#[derive(Debug)]
+pub struct IccGammaLut {
+    table: Vec<[f64; 3]>,
+}
+
+struct LoadIccProfileJob {
+    done: Option<Box<dyn FnOnce(Result<IccProfile, IccError>)>>,
+    work: LoadIccProfileWork,
+}
+
+struct LoadIccProfileWork {
+    path: String,
+    result: Option<Result<IccProfile, IccError>>,
+}
+
+impl CpuJob for LoadIccProfileJob {
+    fn work(&mut self) -> &mut dyn CpuWork {
+        &mut self.work
+    }
+
+    fn completed(mut self: Box<Self>) {
+        let done = self.done.take().unwrap();
+        done(self.work.result.take().unwrap());
+    }
+}
+
+impl CpuWork for LoadIccProfileWork {
+    fn run(&mut self) -> Option<Box<dyn AsyncCpuWork>> {
+        zone!("LoadIccProfileWork");
+        self.result = Some(IccProfile::load(&self.path));
+        None
+    }
+}
+
+impl IccProfile {
+    pub fn load(path: &str) -> Result<Self, IccError> {
+        let data = std::fs::read(path).map_err(|e| IccError::Read(path.to_string(), e))?;
+        parse_icc_profile(path, &data)
+    }
+
+    /// Loads the profile on the CPU worker and invokes `done` on the main thread.
+    pub fn load_async(
+        cpu_worker: &CpuWorker,
+        path: &str,
+        done: impl FnOnce(Result<Self, IccError>) + 'static,
+    ) {
+        let job = LoadIccProfileJob {
+            done: Some(Box::new(done)),
+            work: LoadIccProfileWork {
+                path: path.to_string(),
+                result: None,
+            },
+        };
+        cpu_worker.submit(Box::new(job)).detach();
+    }
+
+    /// Computes the lookup table that corrects the difference between `self.eotf`
+    /// and the per-channel tone curves.
+    ///
+    /// Returns `None` if the tone curves are already represented accurately.
+    pub fn gamma_lut(&self, size: usize) -> Option<IccGammaLut> {
+        if size < 2 {
+            return None;
+        }
+        let mut table = Vec::with_capacity(size);
+        let mut identity = true;
+        for i in 0..size {
+            let x = i as f64 / (size - 1) as f64;
+            let y = eval_eotf(self.eotf, x);
+            let v = self.trc.each_ref().map(|c| c.invert(y));
+            identity &= v.iter().all(|v| (v - x).abs() < GAMMA_LUT_TOLERANCE);
+            table.push(v);
+        }
+        if identity {
+            return None;
+        }
+        Some(IccGammaLut { table })
+    }
+}
+
+impl IccGammaLut {
+    pub fn size(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn apply(&self, channel: usize, x: f64) -> f64 {
+        let t = &self.table;
+        let pos = x.clamp(0.0, 1.0) * (t.len() - 1) as f64;
+        let lo = pos.floor() as usize;
+        let hi = (lo + 1).min(t.len() - 1);
+        let f = pos - lo as f64;
+        t[lo][channel] * (1.0 - f) + t[hi][channel] * f
+    }
+}
+
+/// Parses a matrix/TRC profile.
+///
+/// Tabulated and parametric curves that do not correspond to a transfer function
+/// supported by the renderer are approximated by the closest supported function.
+/// The remaining error and differences between the channels can be corrected with
+/// [`IccProfile::gamma_lut`].
+pub fn parse_icc_profile(path: &str, data: &[u8]) -> Result<IccProfile, IccError> {
+    if data.len() > MAX_PROFILE_SIZE {
+        return Err(IccError::TooLarge);
+    }
+    let icc = Icc { data };
+    if data.len() < HEADER_SIZE + 4 {
+        return Err(IccError::Truncated);
+    }
+    if &data[36..40] != b"acsp" {
+        return Err(IccError::NotAProfile);
+    }
+    if &data[16..20] != b"RGB " {
+        return Err(IccError::NotRgb);
+    }
+    let has_matrix = icc.find_tag(*b"rXYZ")?.is_some();
+    if !has_matrix && icc.find_tag(*b"A2B0")?.is_some() {
+        return Err(IccError::LutOnly);
+    }
+    let r = icc.xyz_tag(*b"rXYZ", "rXYZ")?;
+    let g = icc.xyz_tag(*b"gXYZ", "gXYZ")?;
+    let b = icc.xyz_tag(*b"bXYZ", "bXYZ")?;
+    // The colorants are stored relative to the D50 PCS illuminant. Undo the chromatic
+    // adaptation to get the native primaries of the display.
+    let from_pcs: ColorMatrix<Xyz, Xyz> = match icc.chad_tag()? {
+        Some(chad) => invert(&chad).ok_or(IccError::MalformedTag("chad"))?,
+        None => match icc.find_tag(*b"wtpt")? {
+            Some(_) => {
+                let wtpt = icc.xyz_tag(*b"wtpt", "wtpt")?;
+                let d50 = chromaticity(D50).ok_or(IccError::MalformedTag("wtpt"))?;
+                let wp = chromaticity(wtpt).ok_or(IccError::MalformedTag("wtpt"))?;
+                bradford_adjustment(d50, wp)
+            }
+            None => ColorMatrix::IDENTITY,
+        },
+    };
+    let native = |xyz: [f64; 3]| chromaticity(from_pcs * xyz);
+    let white = [r[0] + g[0] + b[0], r[1] + g[1] + b[1], r[2] + g[2] + b[2]];
+    let primaries = Primaries {
+        r: native(r).ok_or(IccError::DegeneratePrimaries)?,
+        g: native(g).ok_or(IccError::DegeneratePrimaries)?,
+        b: native(b).ok_or(IccError::DegeneratePrimaries)?,
+        wp: native(white).ok_or(IccError::DegeneratePrimaries)?,
+    };
+    let r = icc.curve_tag(*b"rTRC", "rTRC")?;
+    let g = icc.curve_tag(*b"gTRC", "gTRC")?;
+    let b = icc.curve_tag(*b"bTRC", "bTRC")?;
+    let eotf = if r == g && r == b {
+        r.to_eotf()
+    } else {
+        let (rs, gs, bs) = (r.sample(), g.sample(), b.sample());
+        let mean: Vec<_> = (0..NUM_SAMPLES)
+            .map(|i| (rs[i] + gs[i] + bs[i]) / 3.0)
+            .collect();
+        fit_eotf(&mean)
+    };
+    Ok(IccProfile {
+        path: path.to_string(),
+        primaries,
+        eotf,
+        trc: [r, g, b],
+    })
+}
+
+struct Icc<'a> {
+    data: &'a [u8],
+}
+
+impl Icc<'_> {
+    fn u16(&self, offset: usize) -> Result<u16, IccError> {
+        self.data
+            .get(offset..offset + 2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]))
+            .ok_or(IccError::Truncated)
+    }
+
+    fn u32(&self, offset: usize) -> Result<u32, IccError> {
+        self.data
+            .get(offset..offset + 4)
+            .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+            .ok_or(IccError::Truncated)
+    }
+
+    fn s15f16(&self, offset: usize) -> Result<f64, IccError> {
+        Ok(self.u32(offset)? as i32 as f64 / 65536.0)
+    }
+
+    fn find_tag(&self, sig: [u8; 4]) -> Result<Option<&[u8]>, IccError> {
+        let count = self.u32(HEADER_SIZE)? as usize;
+        for i in 0..count {
+            let entry = HEADER_SIZE + 4 + i * 12;
+            if self.u32(entry)? != u32::from_be_bytes(sig) {
+                continue;
+            }
+            let offset = self.u32(entry + 4)? as usize;
+            let size = self.u32(entry + 8)? as usize;
+            let tag = self
+                .data
+                .get(offset..offset.saturating_add(size))
+                .ok_or(IccError::Truncated)?;
+            return Ok(Some(tag));
+        }
+        Ok(None)
+    }
+
+    fn tag(
+        &self,
+        sig: [u8; 4],
+        name: &'static str,
+        ty: &[u8; 4],
+    ) -> Result<Option<Icc<'_>>, IccError> {
+        let Some(data) = self.find_tag(sig)? else {
+            return Ok(None);
+        };
+        if data.len() < 8 {
+            return Err(IccError::MalformedTag(name));
+        }
+        if &data[..4] != ty {
+            let ty = String::from_utf8_lossy(&data[..4]).into_owned();
+            return Err(IccError::UnsupportedTagType(name, ty));
+        }
+        Ok(Some(Icc { data }))
+    }
+
+    fn xyz_tag(&self, sig: [u8; 4], name: &'static str) -> Result<[f64; 3], IccError> {
+        let tag = self
+            .tag(sig, name, b"XYZ ")?
+            .ok_or(IccError::MissingTag(name))?;
+        if tag.data.len() < 20 {
+            return Err(IccError::MalformedTag(name));
+        }
+        Ok([tag.s15f16(8)?, tag.s15f16(12)?, tag.s15f16(16)?])
+    }
+
+    fn chad_tag(&self) -> Result<Option<ColorMatrix<Xyz, Xyz>>, IccError> {
+        let Some(tag) = self.tag(*b"chad", "chad", b"sf32")? else {
+            return Ok(None);
+        };
+        if tag.data.len() < 44 {
+            return Err(IccError::MalformedTag("chad"));
+        }
+        let mut m = [[0.0; 4]; 3];
+        for (row, m) in m.iter_mut().enumerate() {
+            for (col, v) in m[..3].iter_mut().enumerate() {
+                *v = tag.s15f16(8 + (row * 3 + col) * 4)?;
+            }
+        }
+        Ok(Some(ColorMatrix::new(m)))
+    }
+
+    fn curve_tag(&self, sig: [u8; 4], name: &'static str) -> Result<Curve, IccError> {
+        let data = self.find_tag(sig)?.ok_or(IccError::MissingTag(name))?;
+        if data.len() < 12 {
+            return Err(IccError::MalformedTag(name));
+        }
+        let tag = Icc { data };
+        let res = match &data[..4] {
+            b"curv" => {
+                let count = tag.u32(8)? as usize;
+                match count {
+                    0 => Curve::Gamma(1.0),
+                    1 => Curve::Gamma(tag.u16(12)? as f64 / 256.0),
+                    _ => {
+                        let mut table = Vec::with_capacity(count);
+                        for i in 0..count {
+                            table.push(tag.u16(12 + i * 2)? as f64 / 65535.0);
+                        }
+                        Curve::Table(table)
+                    }
+                }
+            }
+            b"para" => {
+                let num_params = match tag.u16(8)? {
+                    0 => 1,
+                    1 => 3,
+                    2 => 4,
+                    3 => 5,
+                    4 => 7,
+                    _ => return Err(IccError::MalformedTag(name)),
+                };
+                let mut params = [0.0; 7];
+                for (i, p) in params[..num_params].iter_mut().enumerate() {
+                    *p = tag.s15f16(12 + i * 4)?;
+                }
+                Curve::Parametric(tag.u16(8)?, params)
+            }
+            ty => {
+                let ty = String::from_utf8_lossy(ty).into_owned();
+                return Err(IccError::UnsupportedTagType(name, ty));
+            }
+        };
+        Ok(res)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Curve {
+    Gamma(f64),
+    Table(Vec<f64>),
+    Parametric(u16, [f64; 7]),
+}
+
+impl Curve {
+    fn eval(&self, x: f64) -> f64 {
+        match self {
+            Curve::Gamma(g) => x.powf(*g),
+            Curve::Table(t) => {
+                let pos = x.clamp(0.0, 1.0) * (t.len() - 1) as f64;
+                let lo = pos.floor() as usize;
+                let hi = (lo + 1).min(t.len() - 1);
+                let f = pos - lo as f64;
+                t[lo] * (1.0 - f) + t[hi] * f
+            }
+            &Curve::Parametric(ty, [g, a, b, c, d, e, f]) => {
+                // For types 1 and 2, the curve is constant for `a * x + b < 0`. Checking
+                // the base instead of `x >= -b / a` also handles `a == 0`.
+                let pow = |x: f64| {
+                    let base = a * x + b;
+                    if base > 0.0 { base.powf(g) } else { 0.0 }
+                };
+                match ty {
+                    0 => x.max(0.0).powf(g),
+                    1 => pow(x),
+                    2 => pow(x) + c,
+                    3 if x >= d => pow(x),
+                    3 => c * x,
+                    4 if x >= d => pow(x) + e,
+                    _ => c * x + f,
+                }
+            }
+        }
+    }
+
+    /// Returns the input that produces `y`, assuming that the curve is
+    /// non-decreasing.
+    fn invert(&self, y: f64) -> f64 {
+        let (mut lo, mut hi) = (0.0, 1.0);
+        for _ in 0..32 {
+            let mid = (lo + hi) / 2.0;
+            if self.eval(mid) < y {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        (lo + hi) / 2.0
+    }
+
+    fn sample(&self) -> Vec<f64> {
+        (0..NUM_SAMPLES)
+            .map(|i| self.eval(i as f64 / (NUM_SAMPLES - 1) as f64))
+            .collect()
+    }
+
+    fn to_eotf(&self) -> Eotf {
+        if let Curve::Gamma(g) = *self {
+            return eotf_from_gamma(g);
+        }
+        fit_eotf(&self.sample())
+    }
+}
+
+fn fit_eotf(samples: &[f64]) -> Eotf {
+    let error = |f: &dyn Fn(f64) -> f64| {
+        samples
+            .iter()
+            .enumerate()
+            .map(|(i, y)| {
+                let x = i as f64 / (NUM_SAMPLES - 1) as f64;
+                (f(x) - y).powi(2)
+            })
+            .sum::<f64>()
+    };
+    let gamma_error = |g: f64| error(&|x| x.powf(g));
+    // Golden-section search for the exponent that best approximates the curve.
+    let (min, max) = (
+        EotfPow::MIN.eotf_f32() as f64,
+        EotfPow::MAX.eotf_f32() as f64,
+    );
+    let phi = (5.0f64.sqrt() - 1.0) / 2.0;
+    let (mut lo, mut hi) = (min, max);
+    while hi - lo > 0.0001 {
+        let m1 = hi - phi * (hi - lo);
+        let m2 = lo + phi * (hi - lo);
+        if gamma_error(m1) < gamma_error(m2) {
+            hi = m2;
+        } else {
+            lo = m1;
+        }
+    }
+    let gamma = (lo + hi) / 2.0;
+    let srgb_error = error(&|x| eotfs::compound_power_2_4::<()>(x as f32) as f64);
+    if srgb_error <= gamma_error(gamma) {
+        Eotf::CompoundPower24
+    } else {
+        eotf_from_gamma(gamma)
+    }
+}
+
+fn eotf_from_gamma(gamma: f64) -> Eotf {
+    // Gamma values in `curv` tags are stored with a precision of 1/256.
+    const EPSILON: f64 = 0.005;
+    for (g, eotf) in [
+        (1.0, Eotf::Linear),
+        (2.2, Eotf::Gamma22),
+        (2.4, Eotf::Gamma24),
+        (2.8, Eotf::Gamma28),
+    ] {
+        if (gamma - g).abs() < EPSILON {
+            return eotf;
+        }
+    }
+    let pow = EotfPow(((gamma * 10_000.0).round() as u32).clamp(EotfPow::MIN.0, EotfPow::MAX.0));
+    Eotf::Pow(pow)
+}
+
+fn eval_eotf(eotf: Eotf, x: f64) -> f64 {
+    let x = x as f32;
+    let y = match eotf {
+        Eotf::Linear => eotfs::linear::<()>(x),
+        Eotf::St2084Pq => eotfs::st2084_pq::<()>(x),
+        Eotf::Bt1886(c) => eotfs::bt1886::<()>(c.0)(x),
+        Eotf::Gamma22 => eotfs::gamma22::<()>(x),
+        Eotf::Gamma24 => eotfs::gamma24::<()>(x),
+        Eotf::Gamma28 => eotfs::gamma28::<()>(x),
+        Eotf::St240 => eotfs::st240::<()>(x),
+        Eotf::Log100 => eotfs::log100::<()>(x),
+        Eotf::Log316 => eotfs::log316::<()>(x),
+        Eotf::St428 => eotfs::st428::<()>(x),
+        Eotf::Pow(p) => eotfs::pow::<()>(p.eotf_f32())(x),
+        Eotf::CompoundPower24 => eotfs::compound_power_2_4::<()>(x),
+    };
+    y as f64
+}
+
+fn chromaticity(xyz: [f64; 3]) -> Option<(F64, F64)> {
+    let sum = xyz[0] + xyz[1] + xyz[2];
+    if sum.abs() < 1e-9 {
+        return None;
+    }
+    Some((F64(xyz[0] / sum), F64(xyz[1] / sum)))
+}
+
+fn invert(m: &ColorMatrix<Xyz, Xyz>) -> Option<ColorMatrix<Xyz, Xyz>> {
+    let m = m.0.map(|r| r.map(|v| v.0));
+    let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+    if det.abs() < 1e-9 {
+        return None;
+    }
+    let inv = 1.0 / det;
+    Some(ColorMatrix::new([
+        [
+            (m[1][1] * m[2][2] - m[1][2] * m[2][1]) * inv,
+            (m[0][2] * m[2][1] - m[0][1] * m[2][2]) * inv,
+            (m[0][1] * m[1][2] - m[0][2] * m[1][1]) * inv,
+            0.0,
+        ],
+        [
+            (m[1][2] * m[2][0] - m[1][0] * m[2][2]) * inv,
+            (m[0][0] * m[2][2] - m[0][2] * m[2][0]) * inv,
+            (m[0][2] * m[1][0] - m[0][0] * m[1][2]) * inv,
+            0.0,
+        ],
+        [
+            (m[1][0] * m[2][1] - m[1][1] * m[2][0]) * inv,
+            (m[0][1] * m[2][0] - m[0][0] * m[2][1]) * inv,
+            (m[0][0] * m[1][1] - m[0][1] * m[1][0]) * inv,
+            0.0,
+        ],
+    ]))
+}
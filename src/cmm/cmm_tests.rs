@@ -349,3 +349,188 @@ fn target_contained() {
         assert!(!desc.linear.target_contained_in_primary());
     }
 }
+
+mod icc {
+    use crate::cmm::cmm_eotf::Eotf;
+    use crate::cmm::cmm_icc::IccError;
+    use crate::cmm::cmm_icc::parse_icc_profile;
+    use crate::cmm::cmm_primaries::Primaries;
+
+    fn s15f16(v: f64) -> [u8; 4] {
+        ((v * 65536.0).round() as i32).to_be_bytes()
+    }
+
+    fn xyz(v: [f64; 3]) -> Vec<u8> {
+        let mut res = b"XYZ \0\0\0\0".to_vec();
+        for v in v {
+            res.extend(s15f16(v));
+        }
+        res
+    }
+
+    fn para(ty: u16, params: &[f64]) -> Vec<u8> {
+        let mut res = b"para\0\0\0\0".to_vec();
+        res.extend(ty.to_be_bytes());
+        res.extend([0, 0]);
+        for &v in params {
+            res.extend(s15f16(v));
+        }
+        res
+    }
+
+    fn para_srgb() -> Vec<u8> {
+        para(3, &[2.4, 1.0 / 1.055, 0.055 / 1.055, 1.0 / 12.92, 0.04045])
+    }
+
+    fn curv_gamma(gamma: f64) -> Vec<u8> {
+        let mut res = b"curv\0\0\0\0\0\0\0\x01".to_vec();
+        res.extend(((gamma * 256.0).round() as u16).to_be_bytes());
+        res
+    }
+
+    fn curv_table(gamma: f64) -> Vec<u8> {
+        let mut res = b"curv\0\0\0\0".to_vec();
+        res.extend(1024u32.to_be_bytes());
+        for i in 0..1024 {
+            let v = (i as f64 / 1023.0).powf(gamma);
+            res.extend(((v * 65535.0).round() as u16).to_be_bytes());
+        }
+        res
+    }
+
+    fn profile(tags: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+        let mut header = vec![0; 128];
+        header[16..20].copy_from_slice(b"RGB ");
+        header[20..24].copy_from_slice(b"XYZ ");
+        header[36..40].copy_from_slice(b"acsp");
+        let mut table = (tags.len() as u32).to_be_bytes().to_vec();
+        let mut data = vec![];
+        let mut offset = 128 + 4 + 12 * tags.len();
+        for (sig, tag) in tags {
+            table.extend(*sig);
+            table.extend((offset as u32).to_be_bytes());
+            table.extend((tag.len() as u32).to_be_bytes());
+            data.extend(tag);
+            while data.len() % 4 != 0 {
+                data.push(0);
+            }
+            offset = 128 + 4 + 12 * tags.len() + data.len();
+        }
+        let mut res = header;
+        res.extend(table);
+        res.extend(data);
+        res
+    }
+
+    fn srgb_tags() -> Vec<(&'static [u8; 4], Vec<u8>)> {
+        let mut chad = b"sf32\0\0\0\0".to_vec();
+        for v in [
+            1.0478112, 0.0228866, -0.0501270, 0.0295424, 0.9904844, -0.0170491, -0.0092345,
+            0.0150436, 0.7521316,
+        ] {
+            chad.extend(s15f16(v));
+        }
+        vec![
+            (b"wtpt", xyz([0.9642, 1.0, 0.8249])),
+            (b"rXYZ", xyz([0.4360747, 0.2225045, 0.0139322])),
+            (b"gXYZ", xyz([0.3850649, 0.7168786, 0.0971045])),
+            (b"bXYZ", xyz([0.1430804, 0.0606169, 0.7141733])),
+            (b"chad", chad),
+        ]
+    }
+
+    #[test]
+    fn srgb_parametric() {
+        let mut tags = srgb_tags();
+        tags.push((b"rTRC", para_srgb()));
+        tags.push((b"gTRC", para_srgb()));
+        tags.push((b"bTRC", para_srgb()));
+        let icc = parse_icc_profile("", &profile(&tags)).unwrap();
+        assert!(
+            icc.primaries.about_equal(&Primaries::SRGB),
+            "{:?}",
+            icc.primaries
+        );
+        assert_eq!(icc.eotf, Eotf::CompoundPower24);
+        assert!(icc.gamma_lut(256).is_none());
+    }
+
+    #[test]
+    fn gamma() {
+        let mut tags = srgb_tags();
+        tags.push((b"rTRC", curv_gamma(2.2)));
+        tags.push((b"gTRC", curv_gamma(2.2)));
+        tags.push((b"bTRC", curv_gamma(2.2)));
+        let icc = parse_icc_profile("", &profile(&tags)).unwrap();
+        assert_eq!(icc.eotf, Eotf::Gamma22);
+        assert!(icc.gamma_lut(256).is_none());
+    }
+
+    #[test]
+    fn table() {
+        let mut tags = srgb_tags();
+        tags.push((b"rTRC", curv_table(2.4)));
+        tags.push((b"gTRC", curv_table(2.4)));
+        tags.push((b"bTRC", curv_table(2.4)));
+        let icc = parse_icc_profile("", &profile(&tags)).unwrap();
+        assert_eq!(icc.eotf, Eotf::Gamma24);
+    }
+
+    #[test]
+    fn mismatched_curves() {
+        let mut tags = srgb_tags();
+        tags.push((b"rTRC", curv_gamma(2.2)));
+        tags.push((b"gTRC", curv_gamma(1.8)));
+        tags.push((b"bTRC", curv_gamma(2.2)));
+        let icc = parse_icc_profile("", &profile(&tags)).unwrap();
+        let lut = icc.gamma_lut(1024).unwrap();
+        assert_eq!(lut.size(), 1024);
+        // All channels must produce the same luminance after the correction.
+        let (r, g) = (563.0 / 256.0, 461.0 / 256.0);
+        for i in 0..=100 {
+            let x = i as f64 / 100.0;
+            let lr = lut.apply(0, x).powf(r);
+            let lg = lut.apply(1, x).powf(g);
+            let lb = lut.apply(2, x).powf(r);
+            assert!((lr - lg).abs() < 0.001, "{x}: {lr} != {lg}");
+            assert!((lr - lb).abs() < 0.001, "{x}: {lr} != {lb}");
+        }
+        assert_eq!(lut.apply(0, 0.0), lut.apply(2, 0.0));
+        assert!(lut.apply(0, 1.0) > 0.999);
+        assert!(lut.apply(1, 1.0) > 0.999);
+    }
+
+    #[test]
+    fn degenerate_parametric() {
+        let curves = [
+            // a == 0
+            para(1, &[2.2, 0.0, 0.5]),
+            para(2, &[2.2, 0.0, 0.0, 0.1]),
+            // negative base
+            para(3, &[2.2, 1.0, -0.5, 0.0, 0.0]),
+            para(4, &[2.2, 1.0, -0.5, 0.0, 0.0, 0.1, 0.0]),
+        ];
+        for curve in curves {
+            let mut tags = srgb_tags();
+            tags.push((b"rTRC", curve.clone()));
+            tags.push((b"gTRC", curve.clone()));
+            tags.push((b"bTRC", curve));
+            let icc = parse_icc_profile("", &profile(&tags)).unwrap();
+            if let Some(lut) = icc.gamma_lut(256) {
+                for i in 0..256 {
+                    let x = i as f64 / 255.0;
+                    for c in 0..3 {
+                        assert!(lut.apply(c, x).is_finite());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lut_only() {
+        let tags = [(b"A2B0", b"mAB \0\0\0\0".to_vec())];
+        let res = parse_icc_profile("", &profile(&tags));
+        assert!(matches!(res, Err(IccError::LutOnly)));
+    }
+}
@@ -306,6 +306,8 @@ fn start_compositor2(
         render_ctx_version: NumCell::new(1),
         render_ctx_ever_initialized: Cell::new(false),
         cursors: Default::default(),
+        themed_cursors: Default::default(),
+        cursor_env: Default::default(),
         wheel,
         clients: Clients::new(),
         globals: Globals::new(),
@@ -345,6 +347,7 @@ fn start_compositor2(
         drm_devs: Default::default(),
         drm_devs_by_dev_t: Default::default(),
         status: Default::default(),
+        bar_elements: Default::default(),
         idle: IdleState {
             input: Default::default(),
             change: Default::default(),
@@ -387,6 +390,7 @@ fn start_compositor2(
         },
         scales,
         cursor_sizes: Default::default(),
+        cursor_themes: Default::default(),
         hardware_tick_cursor: Default::default(),
         testers: Default::default(),
         render_ctx_watchers: Default::default(),
@@ -421,11 +425,16 @@ fn start_compositor2(
         default_vrr_mode: Cell::new(*VrrMode::NEVER),
         default_vrr_cursor_hz: Cell::new(None),
         default_tearing_mode: Cell::new(*TearingMode::VARIANT_3),
+        auto_scale: Cell::new(None),
+        vnc_servers: Default::default(),
+        backlight_indicator: Default::default(),
+        auto_rotation: Default::default(),
         ei_acceptor: Default::default(),
         ei_acceptor_future: Default::default(),
         enable_ei_acceptor: Default::default(),
         ei_clients: EiClients::new(),
         slow_ei_clients: Default::default(),
+        input_captures: Default::default(),
         cpu_worker,
         ui_drag_enabled: Cell::new(true),
         ui_drag_threshold_squared: Cell::new(10),
@@ -850,6 +859,9 @@ fn create_dummy_output(state: &Rc<State>) {
         state: RefCell::new(backend_state),
         head_manager: HeadManager::new(head_name, head_state),
         wlr_output_heads: Default::default(),
+        dpms_off: Cell::new(false),
+        wlr_output_powers: Default::default(),
+        backlight: None,
     });
     let schedule = Rc::new(OutputSchedule::new(
         state,
@@ -8,6 +8,7 @@ use crate::client::ClientCaps;
 use crate::config::handler::ConfigProxyHandler;
 use crate::env::JAY_NO_REALTIME;
 use crate::ifs::wl_seat::SeatId;
+use crate::ifs::wl_surface::zwp_keyboard_shortcuts_inhibitor_v1::ShortcutsInhibitPolicy;
 #[cfg(feature = "it")]
 use crate::it::test_config::TEST_CONFIG_ENTRY;
 use crate::state::State;
@@ -29,9 +30,14 @@ use jay_config::_private::ipc::InitMessage;
 use jay_config::_private::ipc::ServerFeature;
 use jay_config::_private::ipc::ServerMessage;
 use jay_config::_private::ipc::V1InitMessage;
+use jay_config::input::AccessibilityFeedback;
 use jay_config::input::InputDevice;
+use jay_config::input::ModifiedPointerTrigger;
 use jay_config::input::Seat;
 use jay_config::input::SwitchEvent;
+use jay_config::input::gesture::Gesture;
+use jay_config::input::gesture::TouchGesture;
+use jay_config::input::tablet::TabletPadEvent;
 use jay_config::keyboard::mods::Modifiers;
 use jay_config::keyboard::syms::KeySym;
 use jay_config::video::Connector;
@@ -112,6 +118,34 @@ impl ConfigProxy {
         self.send(&msg);
     }
 
+    pub fn invoke_pointer_shortcut(&self, seat: SeatId, trigger: ModifiedPointerTrigger) {
+        self.send(&ServerMessage::InvokePointerShortcut {
+            seat: Seat(seat.raw() as _),
+            trigger,
+        });
+    }
+
+    pub fn invoke_gesture(&self, seat: SeatId, gesture: Gesture) {
+        self.send(&ServerMessage::InvokeGesture {
+            seat: Seat(seat.raw() as _),
+            gesture,
+        });
+    }
+
+    pub fn invoke_touch_gesture(&self, seat: SeatId, gesture: TouchGesture) {
+        self.send(&ServerMessage::InvokeTouchGesture {
+            seat: Seat(seat.raw() as _),
+            gesture,
+        });
+    }
+
+    pub fn keyboard_accessibility_feedback(&self, seat: SeatId, feedback: AccessibilityFeedback) {
+        self.send(&ServerMessage::KeyboardAccessibilityFeedback {
+            seat: Seat(seat.raw() as _),
+            feedback,
+        });
+    }
+
     pub fn new_drm_dev(&self, dev: DrmDeviceId) {
         self.send(&ServerMessage::NewDrmDev {
             device: DrmDevice(dev.raw() as _),
@@ -188,6 +222,19 @@ impl ConfigProxy {
         });
     }
 
+    pub fn tablet_pad_event(
+        &self,
+        seat: SeatId,
+        input_device: InputDeviceId,
+        event: TabletPadEvent,
+    ) {
+        self.send(&ServerMessage::TabletPadEvent {
+            seat: Seat(seat.raw() as _),
+            input_device: InputDevice(input_device.raw() as _),
+            event,
+        });
+    }
+
     pub fn toplevel_removed(&self, id: ToplevelIdentifier) {
         let Some(handler) = self.handler.get() else {
             return;
@@ -208,10 +255,30 @@ impl ConfigProxy {
         self.handler.get()?.initial_tile_state(data)
     }
 
+    pub fn shortcuts_inhibit_policy(&self, data: &ToplevelData) -> Option<ShortcutsInhibitPolicy> {
+        self.handler.get()?.shortcuts_inhibit_policy(data)
+    }
+
     pub fn initial_output_for_workspace(&self, name: &str) -> Option<Option<Rc<OutputNode>>> {
         self.handler.get()?.initial_output_for_workspace(name)
     }
 
+    pub fn output_profiles(&self) -> (Vec<String>, Option<String>) {
+        let Some(handler) = self.handler.get() else {
+            return Default::default();
+        };
+        let profiles = handler.output_profiles.borrow().clone();
+        let active = handler.active_output_profile.borrow().clone();
+        (profiles, active)
+    }
+
+    pub fn apply_output_profile(&self, name: &str) -> bool {
+        let Some(handler) = self.handler.get() else {
+            return false;
+        };
+        handler.apply_output_profile(name)
+    }
+
     pub fn update_capabilities(
         &self,
         data: &Rc<Client>,
@@ -288,6 +355,9 @@ impl ConfigProxy {
             window_matcher_std_kinds: state.tl_matcher_manager.kind(window::CLIENT_WINDOW),
             window_matcher_no_auto_focus: Default::default(),
             window_matcher_initial_tile_state: Default::default(),
+            window_matcher_shortcuts_inhibit: Default::default(),
+            output_profiles: Default::default(),
+            active_output_profile: Default::default(),
         });
         let init_msg = bincode_ops()
             .serialize(&InitMessage::V1(V1InitMessage {}))
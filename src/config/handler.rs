@@ -6,11 +6,16 @@ use crate::backend::DrmDeviceId;
 use crate::backend::InputDeviceAccelProfile;
 use crate::backend::InputDeviceCapability;
 use crate::backend::InputDeviceClickMethod;
+use crate::backend::InputDeviceCustomAccel;
 use crate::backend::InputDeviceId;
 use crate::backend::InputDeviceScrollMethod;
+use crate::backend::InputDeviceSendEventsMode;
+use crate::backend::InputDeviceTapButtonMap;
 use crate::backend::MonitorInfo;
 use crate::backend::transaction::BackendConnectorTransactionError;
+use crate::backend::transaction::ConnectorTransaction;
 use crate::backend::{self};
+use crate::backlight::BacklightError;
 use crate::client::CAP_JAY_COMPOSITOR;
 use crate::client::Client;
 use crate::client::ClientCaps;
@@ -24,6 +29,8 @@ use crate::criteria::CritUpstreamNode;
 use crate::criteria::clm::ClmLeafMatcher;
 use crate::criteria::tlm::TlmLeafMatcher;
 use crate::criteria::tlm::TlmUpstreamNode;
+use crate::edid;
+use crate::edid::EdidLoadError;
 use crate::env::WAYLAND_DISPLAY;
 use crate::evdev::input_event_codes::InputEventCode;
 use crate::format::config_formats;
@@ -32,11 +39,18 @@ use crate::ifs::wl_output::BlendSpace;
 use crate::ifs::wl_output::PersistentOutputState;
 use crate::ifs::wl_seat::SeatId;
 use crate::ifs::wl_seat::WlSeatGlobal;
+use crate::ifs::wl_seat::tablet::TabletArea;
+use crate::ifs::wl_seat::tablet::TabletToolType;
+use crate::ifs::wl_surface::zwp_keyboard_shortcuts_inhibitor_v1::ShortcutsInhibitPolicy;
 use crate::ifs::wp_content_type_v1::ContentTypeExt;
 use crate::io_uring::TaskResultExt;
 use crate::kbvm::KbvmError;
 use crate::kbvm::KbvmMap;
+use crate::key_remap::KeyRemapError;
+use crate::key_remap::KeyRemapper;
 use crate::output_schedule::map_cursor_hz;
+use crate::rect::Rect;
+use crate::scale::AutoScale;
 use crate::scale::Scale;
 use crate::state::ConnectorData;
 use crate::state::DeviceHandlerData;
@@ -74,6 +88,10 @@ use crate::utils::oserror::OsErrorExt;
 use crate::utils::stack::Stack;
 use crate::utils::timer::TimerError;
 use crate::utils::timer::TimerFd;
+use crate::video::cvt;
+use crate::video::cvt::CvtError;
+use crate::video::cvt::ModelineError;
+use crate::vnc::VncError;
 use az::SaturatingCast;
 use jay_config::_private::ClientCriterionIpc;
 use jay_config::_private::ClientCriterionStringField;
@@ -104,9 +122,12 @@ use jay_config::input::FocusFollowsMouseMode;
 use jay_config::input::InputDevice;
 use jay_config::input::InputEventCode as ConfigInputEventCode;
 use jay_config::input::LayerDirection;
+use jay_config::input::LayoutMemoryMode;
+use jay_config::input::ModifiedPointerTrigger;
 use jay_config::input::Seat;
 use jay_config::input::Timeline;
 use jay_config::input::acceleration::ACCEL_PROFILE_ADAPTIVE;
+use jay_config::input::acceleration::ACCEL_PROFILE_CUSTOM;
 use jay_config::input::acceleration::ACCEL_PROFILE_FLAT;
 use jay_config::input::acceleration::AccelProfile;
 use jay_config::input::capability::CAP_GESTURE;
@@ -121,24 +142,51 @@ use jay_config::input::clickmethod::CLICK_METHOD_BUTTON_AREAS;
 use jay_config::input::clickmethod::CLICK_METHOD_CLICKFINGER;
 use jay_config::input::clickmethod::CLICK_METHOD_NONE;
 use jay_config::input::clickmethod::ClickMethod;
+use jay_config::input::gesture::Gesture;
+use jay_config::input::gesture::TouchGesture;
+use jay_config::input::key_remap::KeyRemap;
+use jay_config::input::mouse_keys::MouseKeys;
 use jay_config::input::scrollmethod::SCROLL_METHOD_EDGE;
 use jay_config::input::scrollmethod::SCROLL_METHOD_NO_SCROLL;
 use jay_config::input::scrollmethod::SCROLL_METHOD_ON_BUTTON_DOWN;
 use jay_config::input::scrollmethod::SCROLL_METHOD_TWO_FINGERS;
 use jay_config::input::scrollmethod::ScrollMethod;
+use jay_config::input::sendevents::SEND_EVENTS_DISABLED;
+use jay_config::input::sendevents::SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE;
+use jay_config::input::sendevents::SEND_EVENTS_ENABLED;
+use jay_config::input::sendevents::SendEventsMode;
+use jay_config::input::tablet::ERASER_MODE_ERASER;
+use jay_config::input::tablet::ERASER_MODE_PEN;
+use jay_config::input::tablet::EraserMode;
+use jay_config::input::tablet::PressureCurve;
+use jay_config::input::tablet::TABLET_TOOL_TYPE_AIRBRUSH;
+use jay_config::input::tablet::TABLET_TOOL_TYPE_BRUSH;
+use jay_config::input::tablet::TABLET_TOOL_TYPE_ERASER;
+use jay_config::input::tablet::TABLET_TOOL_TYPE_LENS;
+use jay_config::input::tablet::TABLET_TOOL_TYPE_MOUSE;
+use jay_config::input::tablet::TABLET_TOOL_TYPE_PEN;
+use jay_config::input::tablet::TABLET_TOOL_TYPE_PENCIL;
+use jay_config::input::tablet::TabletToolType as ConfigTabletToolType;
+use jay_config::input::tapbuttonmap::TAP_BUTTON_MAP_LEFT_MIDDLE_RIGHT;
+use jay_config::input::tapbuttonmap::TAP_BUTTON_MAP_LEFT_RIGHT_MIDDLE;
+use jay_config::input::tapbuttonmap::TapButtonMap;
 use jay_config::keyboard::Group;
 use jay_config::keyboard::Keymap;
+use jay_config::keyboard::ModifiedKeySym;
 use jay_config::keyboard::mods::Modifiers;
 use jay_config::keyboard::syms::KeySym;
 use jay_config::logging::LogLevel as ConfigLogLevel;
+use jay_config::theme::BarElement;
 use jay_config::theme::BarPosition;
 use jay_config::theme::ContainerBorders;
 use jay_config::theme::colors::Colorable;
 use jay_config::theme::sized::Resizable;
 use jay_config::timer::Timer as JayTimer;
+use jay_config::video::AutoScale as ConfigAutoScale;
 use jay_config::video::BlendSpace as ConfigBlendSpace;
 use jay_config::video::ColorSpace;
 use jay_config::video::Connector;
+use jay_config::video::ConnectorConfiguration;
 use jay_config::video::DrmDevice;
 use jay_config::video::Eotf as ConfigEotf;
 use jay_config::video::Format as ConfigFormat;
@@ -147,6 +195,7 @@ use jay_config::video::ScalingFilter as ConfigScalingFilter;
 use jay_config::video::TearingMode as ConfigTearingMode;
 use jay_config::video::Transform;
 use jay_config::video::VrrMode as ConfigVrrMode;
+use jay_config::window::ShortcutsInhibit as ConfigShortcutsInhibit;
 use jay_config::window::TileState as ConfigTileState;
 use jay_config::window::Window;
 use jay_config::window::WindowMatcher;
@@ -159,6 +208,7 @@ use log::Level;
 use regex::Regex;
 use smallvec::SmallVec;
 use std::cell::Cell;
+use std::cell::RefCell;
 use std::fmt;
 use std::ops::Deref;
 use std::rc::Rc;
@@ -232,6 +282,15 @@ pub(super) struct ConfigProxyHandler {
             TileState,
         ),
     >,
+    pub window_matcher_shortcuts_inhibit: CopyHashMap<
+        WindowMatcher,
+        (
+            Rc<CachedCriterion<WindowCriterionIpc, ToplevelData>>,
+            ShortcutsInhibitPolicy,
+        ),
+    >,
+    pub output_profiles: RefCell<Vec<String>>,
+    pub active_output_profile: RefCell<Option<String>>,
 }
 
 pub struct ConfigWorkspace {
@@ -600,6 +659,33 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_layout_memory_mode(
+        &self,
+        seat: Seat,
+        mode: LayoutMemoryMode,
+    ) -> Result<(), CphError> {
+        let Ok(mode) = mode.try_into() else {
+            return Err(CphError::UnknownLayoutMemoryMode(mode));
+        };
+        let seat = self.get_seat(seat)?;
+        seat.set_layout_memory_mode(mode);
+        Ok(())
+    }
+
+    fn handle_seat_get_keyboard_group(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.respond(Response::GetKeyboardGroup {
+            group: seat.keyboard_group().0,
+        });
+        Ok(())
+    }
+
+    fn handle_seat_set_keyboard_group(&self, seat: Seat, group: u32) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_keyboard_group(group);
+        Ok(())
+    }
+
     fn handle_set_window_management_enabled(
         &self,
         seat: Seat,
@@ -624,6 +710,110 @@ impl ConfigProxyHandler {
     fn handle_remove_input_mapping(&self, input_device: InputDevice) -> Result<(), CphError> {
         let dev = self.get_device_handler_data(input_device)?;
         dev.set_output(&self.state, None);
+        dev.set_tablet_window(&self.state, None);
+        dev.set_tablet_region(&self.state, None);
+        Ok(())
+    }
+
+    fn handle_map_input_device_to_window(
+        &self,
+        input_device: InputDevice,
+        window: Window,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(input_device)?;
+        let window = self.get_window(window)?;
+        let id = window.tl_data().identifier.get();
+        dev.set_tablet_window(&self.state, Some(id));
+        Ok(())
+    }
+
+    fn handle_unmap_input_device_from_window(
+        &self,
+        input_device: InputDevice,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(input_device)?;
+        dev.set_tablet_window(&self.state, None);
+        Ok(())
+    }
+
+    fn handle_set_input_tablet_region(
+        &self,
+        input_device: InputDevice,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(input_device)?;
+        let Some(rect) = Rect::new_sized(x, y, width, height).filter(|r| !r.is_empty()) else {
+            return Err(CphError::InvalidTabletRegion(x, y, width, height));
+        };
+        dev.set_tablet_region(&self.state, Some(rect));
+        Ok(())
+    }
+
+    fn handle_set_input_tablet_area(
+        &self,
+        input_device: InputDevice,
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(input_device)?;
+        let valid = |lo: f64, hi: f64| 0.0 <= lo && lo < hi && hi <= 1.0;
+        if !valid(x1, x2) || !valid(y1, y2) {
+            return Err(CphError::InvalidTabletArea(x1, y1, x2, y2));
+        }
+        dev.set_tablet_area(&self.state, TabletArea { x1, y1, x2, y2 });
+        Ok(())
+    }
+
+    fn handle_set_input_tablet_keep_aspect_ratio(
+        &self,
+        input_device: InputDevice,
+        keep: bool,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(input_device)?;
+        dev.set_tablet_keep_aspect_ratio(&self.state, keep);
+        Ok(())
+    }
+
+    fn handle_set_input_tablet_pressure_curve(
+        &self,
+        input_device: InputDevice,
+        tool: ConfigTabletToolType,
+        curve: PressureCurve,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(input_device)?;
+        let tool = match tool {
+            TABLET_TOOL_TYPE_PEN => TabletToolType::Pen,
+            TABLET_TOOL_TYPE_ERASER => TabletToolType::Eraser,
+            TABLET_TOOL_TYPE_BRUSH => TabletToolType::Brush,
+            TABLET_TOOL_TYPE_PENCIL => TabletToolType::Pencil,
+            TABLET_TOOL_TYPE_AIRBRUSH => TabletToolType::Airbrush,
+            TABLET_TOOL_TYPE_MOUSE => TabletToolType::Mouse,
+            TABLET_TOOL_TYPE_LENS => TabletToolType::Lens,
+            _ => return Err(CphError::UnknownTabletToolType(tool)),
+        };
+        let values = [curve.x1, curve.y1, curve.x2, curve.y2];
+        if values.iter().any(|v| !(0.0..=1.0).contains(v)) {
+            return Err(CphError::InvalidPressureCurve(curve));
+        }
+        dev.set_tablet_pressure_curve(&self.state, tool, curve);
+        Ok(())
+    }
+
+    fn handle_set_input_tablet_eraser_mode(
+        &self,
+        input_device: InputDevice,
+        mode: EraserMode,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(input_device)?;
+        if !matches!(mode, ERASER_MODE_ERASER | ERASER_MODE_PEN) {
+            return Err(CphError::UnknownEraserMode(mode));
+        }
+        dev.set_tablet_eraser_mode(&self.state, mode);
         Ok(())
     }
 
@@ -928,6 +1118,7 @@ impl ConfigProxyHandler {
         let profile = match accel_profile {
             ACCEL_PROFILE_FLAT => InputDeviceAccelProfile::Flat,
             ACCEL_PROFILE_ADAPTIVE => InputDeviceAccelProfile::Adaptive,
+            ACCEL_PROFILE_CUSTOM => InputDeviceAccelProfile::Custom,
             _ => return Err(CphError::UnknownAccelProfile(accel_profile)),
         };
         dev.set_accel_profile(&self.state, profile);
@@ -1068,6 +1259,92 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_input_dwt(&self, device: InputDevice, enabled: bool) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        dev.set_dwt_enabled(&self.state, enabled);
+        Ok(())
+    }
+
+    fn handle_set_input_dwtp(&self, device: InputDevice, enabled: bool) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        dev.set_dwtp_enabled(&self.state, enabled);
+        Ok(())
+    }
+
+    fn handle_set_input_tap_button_map(
+        &self,
+        device: InputDevice,
+        map: TapButtonMap,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let m = match map {
+            TAP_BUTTON_MAP_LEFT_RIGHT_MIDDLE => InputDeviceTapButtonMap::LeftRightMiddle,
+            TAP_BUTTON_MAP_LEFT_MIDDLE_RIGHT => InputDeviceTapButtonMap::LeftMiddleRight,
+            _ => return Err(CphError::UnknownTapButtonMap(map)),
+        };
+        dev.set_tap_button_map(&self.state, m);
+        Ok(())
+    }
+
+    fn handle_set_input_send_events_mode(
+        &self,
+        device: InputDevice,
+        mode: SendEventsMode,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let m = match mode {
+            SEND_EVENTS_ENABLED => InputDeviceSendEventsMode::Enabled,
+            SEND_EVENTS_DISABLED => InputDeviceSendEventsMode::Disabled,
+            SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE => {
+                InputDeviceSendEventsMode::DisabledOnExternalMouse
+            }
+            _ => return Err(CphError::UnknownSendEventsMode(mode)),
+        };
+        dev.set_send_events_mode(&self.state, m);
+        Ok(())
+    }
+
+    fn handle_set_input_rotation(&self, device: InputDevice, degrees: u32) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        if degrees >= 360 {
+            return Err(CphError::InvalidRotation(degrees));
+        }
+        dev.set_rotation(&self.state, degrees);
+        Ok(())
+    }
+
+    fn handle_set_input_custom_accel(
+        &self,
+        device: InputDevice,
+        step: f64,
+        points: Vec<f64>,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        if !(2..=64).contains(&points.len()) {
+            return Err(CphError::InvalidCustomAccelPoints(points.len()));
+        }
+        let accel = InputDeviceCustomAccel { step, points };
+        dev.set_custom_accel(&self.state, Rc::new(accel));
+        Ok(())
+    }
+
+    fn handle_set_input_key_remap(
+        &self,
+        device: InputDevice,
+        remap: KeyRemap,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let remapper = if remap == KeyRemap::default() {
+            None
+        } else {
+            let remapper =
+                KeyRemapper::new(&self.state, &dev, &remap).map_err(CphError::KeyRemap)?;
+            Some(Rc::new(remapper))
+        };
+        dev.set_key_remap(&self.state, remapper);
+        Ok(())
+    }
+
     fn handle_set_middle_button_emulation_enabled(
         &self,
         device: InputDevice,
@@ -1601,6 +1878,130 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_connector_set_cvt_mode(
+        &self,
+        connector: Connector,
+        width: i32,
+        height: i32,
+        refresh_millihz: u32,
+        reduced_blanking: bool,
+    ) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        let mode = cvt::generate(
+            width.max(0) as u32,
+            height.max(0) as u32,
+            refresh_millihz,
+            reduced_blanking,
+        )
+        .map_err(CphError::GenerateCvtMode)?;
+        connector
+            .set_custom_mode(&self.state, mode)
+            .map_err(CphError::ModifyConnectorState)?;
+        Ok(())
+    }
+
+    fn handle_connector_set_modeline(
+        &self,
+        connector: Connector,
+        modeline: &str,
+    ) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        let mode = cvt::parse_modeline(modeline).map_err(CphError::ParseModeline)?;
+        connector
+            .set_custom_mode(&self.state, mode)
+            .map_err(CphError::ModifyConnectorState)?;
+        Ok(())
+    }
+
+    fn handle_connector_set_edid_override(
+        &self,
+        connector: Connector,
+        path: Option<&str>,
+    ) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        let edid = match path {
+            Some(path) => Some(Rc::new(edid::load(path).map_err(CphError::LoadEdid)?)),
+            None => None,
+        };
+        connector.connector.set_edid_override(edid);
+        Ok(())
+    }
+
+    fn handle_set_auto_scale(&self, auto_scale: Option<ConfigAutoScale>) -> Result<(), CphError> {
+        let auto_scale = match auto_scale {
+            Some(a) => {
+                let valid = |v: f64| v.is_finite() && v > 0.0;
+                if !valid(a.dpi) || !valid(a.step) {
+                    return Err(CphError::InvalidAutoScale(a.dpi, a.step));
+                }
+                Some(AutoScale {
+                    dpi: a.dpi,
+                    step: a.step,
+                })
+            }
+            None => None,
+        };
+        self.state.auto_scale.set(auto_scale);
+        Ok(())
+    }
+
+    fn handle_connector_set_vnc_server(
+        &self,
+        connector: Connector,
+        address: Option<&str>,
+    ) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        self.state
+            .set_vnc_server(&connector.name, address)
+            .map_err(CphError::SetVncServer)?;
+        Ok(())
+    }
+
+    fn handle_connector_get_backlight(&self, connector: Connector) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        let percent = match self.state.get_backlight(&connector) {
+            Ok(p) => Some(p),
+            Err(BacklightError::NotSupported(_)) => None,
+            Err(e) => return Err(CphError::GetBacklight(e)),
+        };
+        self.respond(Response::ConnectorGetBacklight { percent });
+        Ok(())
+    }
+
+    fn handle_connector_set_backlight(
+        &self,
+        connector: Connector,
+        value: f64,
+        relative: bool,
+    ) -> Result<(), CphError> {
+        if !value.is_finite() {
+            return Err(CphError::InvalidBacklight(value));
+        }
+        let connector = self.get_connector(connector)?;
+        self.state
+            .set_backlight(&connector, value, relative)
+            .map_err(CphError::SetBacklight)?;
+        Ok(())
+    }
+
+    fn handle_set_backlight_indicator_enabled(&self, enabled: bool) {
+        self.state.backlight_indicator.set_enabled(enabled);
+    }
+
+    fn handle_set_auto_rotation_enabled(&self, enabled: bool) {
+        self.state.auto_rotation.set_enabled(&self.state, enabled);
+    }
+
+    fn handle_set_rotation_locked(&self, locked: bool) {
+        self.state.auto_rotation.set_locked(&self.state, locked);
+    }
+
+    fn handle_get_rotation_locked(&self) {
+        self.respond(Response::GetRotationLocked {
+            locked: self.state.auto_rotation.locked(),
+        });
+    }
+
     fn handle_connector_modes(&self, connector: Connector) -> Result<(), CphError> {
         let info = self.get_monitor_info(connector)?;
         self.respond(Response::ConnectorModes {
@@ -1670,6 +2071,29 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_seat_set_cursor_theme(&self, seat: Seat, theme: &str) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let theme = (!theme.is_empty()).then(|| Rc::new(theme.to_string()));
+        seat.cursor_group().set_cursor_theme(theme);
+        Ok(())
+    }
+
+    fn handle_seat_set_hide_cursor_while_typing(
+        &self,
+        seat: Seat,
+        hide: bool,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.cursor_group().set_hide_while_typing(hide);
+        Ok(())
+    }
+
+    fn handle_seat_set_cursor_hide_timeout(&self, seat: Seat, usec: u64) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.cursor_group().set_hide_timeout_usec(usec);
+        Ok(())
+    }
+
     fn handle_disable_pointer_constraint(&self, seat: Seat) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         seat.disable_pointer_constraint();
@@ -1797,6 +2221,16 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_connector_set_touch_edge_width(
+        &self,
+        connector: Connector,
+        width: i32,
+    ) -> Result<(), CphError> {
+        let connector = self.get_output_node_or_persistent(connector)?;
+        connector.set_touch_edge_width(width.max(0));
+        Ok(())
+    }
+
     fn handle_connector_set_use_native_gamut(
         &self,
         connector: Connector,
@@ -1807,6 +2241,24 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_connector_set_icc_profile(
+        &self,
+        connector: Connector,
+        path: Option<&str>,
+    ) -> Result<(), CphError> {
+        let connector = self.get_output_node_or_persistent(connector)?;
+        match path {
+            Some(path) => {
+                let p = path.to_string();
+                connector.load_icc_profile(&self.state, path, move |e| {
+                    log::error!("Could not load ICC profile {}: {}", p, ErrorFmt(e));
+                });
+            }
+            None => connector.set_icc_profile(None),
+        }
+        Ok(())
+    }
+
     fn handle_set_float_above_fullscreen(&self, above: bool) {
         self.state.set_float_above_fullscreen(above);
     }
@@ -1855,6 +2307,24 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_bar_elements(&self, elements: Vec<BarElement>) {
+        self.state.set_bar_elements(elements);
+    }
+
+    fn handle_set_bar_element_separator(&self, separator: &str) {
+        self.state.set_bar_element_separator(separator);
+    }
+
+    fn handle_seat_set_input_mode_stack(
+        &self,
+        seat: Seat,
+        modes: Vec<&str>,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_input_mode_stack(modes.into_iter().map(|m| m.to_string()).collect());
+        Ok(())
+    }
+
     fn handle_get_bar_position(&self) {
         self.respond(Response::GetBarPosition {
             position: self.state.theme.bar_position[LiveTL].get().into(),
@@ -1996,6 +2466,98 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_connector_set_dpms(&self, connector: Connector, on: bool) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        connector
+            .set_dpms(&self.state, on)
+            .map_err(CphError::ModifyConnectorState)?;
+        Ok(())
+    }
+
+    fn handle_connector_get_dpms(&self, connector: Connector) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        self.respond(Response::ConnectorGetDpms {
+            on: !connector.dpms_off.get(),
+        });
+        Ok(())
+    }
+
+    fn handle_configure_connectors(
+        &self,
+        configurations: &[(Connector, ConnectorConfiguration)],
+    ) -> Result<(), CphError> {
+        let mut tran = ConnectorTransaction::new(&self.state);
+        for (connector, config) in configurations {
+            if let Some((x, y)) = config.position
+                && (x < 0 || y < 0 || x > MAX_EXTENTS || y > MAX_EXTENTS)
+            {
+                return Err(CphError::InvalidConnectorPosition(x, y));
+            }
+            if let Some(scale) = config.scale {
+                if scale < 0.1 {
+                    return Err(CphError::ScaleTooSmall(scale));
+                }
+                if scale > 1000.0 {
+                    return Err(CphError::ScaleTooLarge(scale));
+                }
+            }
+            let connector = self.get_connector(*connector)?;
+            let old = connector.state.borrow().clone();
+            let mut new = old.clone();
+            if let Some(enabled) = config.enabled {
+                new.enabled = enabled;
+            }
+            if let Some(mode) = config.mode {
+                new.mode = backend::Mode {
+                    width: mode.width(),
+                    height: mode.height(),
+                    refresh_rate_millihz: mode.refresh_rate(),
+                };
+            }
+            if old != new {
+                tran.add(&connector.connector, new)
+                    .map_err(CphError::ModifyConnectorState)?;
+            }
+        }
+        tran.prepare()
+            .and_then(|t| t.apply())
+            .map_err(CphError::ModifyConnectorState)?
+            .commit();
+        for (connector, config) in configurations {
+            if config.position.is_none() && config.scale.is_none() && config.transform.is_none() {
+                continue;
+            }
+            let Ok(output) = self.get_output_node_or_persistent(*connector) else {
+                continue;
+            };
+            if let Some((x, y)) = config.position {
+                output.set_position(x, y);
+            }
+            if let Some(scale) = config.scale {
+                output.set_preferred_scale(Scale::from_f64(scale));
+            }
+            if let Some(transform) = config.transform {
+                output.update_transform(transform.into());
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_set_output_profiles(&self, profiles: Vec<&str>, active: Option<&str>) {
+        *self.output_profiles.borrow_mut() = profiles.into_iter().map(|p| p.to_string()).collect();
+        *self.active_output_profile.borrow_mut() = active.map(|a| a.to_string());
+    }
+
+    pub fn apply_output_profile(&self, name: &str) -> bool {
+        if !self.output_profiles.borrow().iter().any(|p| p == name) {
+            return false;
+        }
+        self.send(&ServerMessage::ApplyOutputProfile {
+            name: name.to_string(),
+        });
+        true
+    }
+
     fn handle_get_connector(
         &self,
         ty: jay_config::video::connector_type::ConnectorType,
@@ -2202,6 +2764,81 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_add_pointer_shortcut(
+        &self,
+        seat: Seat,
+        trigger: ModifiedPointerTrigger,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.add_pointer_shortcut(trigger);
+        Ok(())
+    }
+
+    fn handle_remove_pointer_shortcut(
+        &self,
+        seat: Seat,
+        trigger: ModifiedPointerTrigger,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.remove_pointer_shortcut(trigger);
+        Ok(())
+    }
+
+    fn handle_add_gesture_binding(&self, seat: Seat, gesture: Gesture) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.add_gesture_binding(gesture);
+        Ok(())
+    }
+
+    fn handle_remove_gesture_binding(&self, seat: Seat, gesture: Gesture) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.remove_gesture_binding(gesture);
+        Ok(())
+    }
+
+    fn handle_set_gesture_continuous(
+        &self,
+        seat: Seat,
+        gesture: Gesture,
+        continuous: bool,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_gesture_continuous(gesture, continuous);
+        Ok(())
+    }
+
+    fn handle_set_gesture_swipe_distance(&self, seat: Seat, distance: f64) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_gesture_swipe_distance(distance);
+        Ok(())
+    }
+
+    fn handle_add_touch_gesture_binding(
+        &self,
+        seat: Seat,
+        gesture: TouchGesture,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.add_touch_gesture_binding(gesture);
+        Ok(())
+    }
+
+    fn handle_remove_touch_gesture_binding(
+        &self,
+        seat: Seat,
+        gesture: TouchGesture,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.remove_touch_gesture_binding(gesture);
+        Ok(())
+    }
+
+    fn handle_set_touch_long_press_drag(&self, seat: Seat, enabled: bool) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_touch_long_press_drag(enabled);
+        Ok(())
+    }
+
     fn handle_set_shortcut_repeat(
         &self,
         seat: Seat,
@@ -2687,6 +3324,7 @@ impl ConfigProxyHandler {
         self.window_matcher_leafs.remove(&matcher);
         self.window_matcher_no_auto_focus.remove(&matcher);
         self.window_matcher_initial_tile_state.remove(&matcher);
+        self.window_matcher_shortcuts_inhibit.remove(&matcher);
     }
 
     fn handle_enable_window_matcher_events(
@@ -2744,11 +3382,43 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_window_matcher_shortcuts_inhibit(
+        &self,
+        matcher: WindowMatcher,
+        inhibit: ConfigShortcutsInhibit,
+    ) -> Result<(), CphError> {
+        let Ok(policy) = inhibit.try_into() else {
+            return Err(CphError::UnknownShortcutsInhibit(inhibit));
+        };
+        let m = self.get_window_matcher(matcher)?;
+        self.window_matcher_shortcuts_inhibit
+            .set(matcher, (m, policy));
+        Ok(())
+    }
+
     fn handle_set_pointer_revert_key(&self, seat: Seat, key: KeySym) -> Result<(), CphError> {
         self.get_seat(seat)?.set_pointer_revert_key(key);
         Ok(())
     }
 
+    fn handle_set_shortcuts_inhibit_escape(
+        &self,
+        seat: Seat,
+        key: ModifiedKeySym,
+    ) -> Result<(), CphError> {
+        self.get_seat(seat)?.set_shortcuts_inhibit_escape(key);
+        Ok(())
+    }
+
+    fn handle_set_input_capture_release(
+        &self,
+        seat: Seat,
+        key: ModifiedKeySym,
+    ) -> Result<(), CphError> {
+        self.get_seat(seat)?.set_input_capture_release(key);
+        Ok(())
+    }
+
     fn handle_seat_focus_history(&self, seat: Seat, timeline: Timeline) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         match timeline {
@@ -2841,6 +3511,150 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_seat_set_sticky_keys_enabled(
+        &self,
+        seat: Seat,
+        enabled: bool,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_sticky_keys_enabled(enabled);
+        Ok(())
+    }
+
+    fn handle_seat_get_sticky_keys_enabled(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.respond(Response::SeatGetStickyKeysEnabled {
+            enabled: seat.sticky_keys_enabled(),
+        });
+        Ok(())
+    }
+
+    fn handle_seat_set_slow_keys_enabled(&self, seat: Seat, enabled: bool) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_slow_keys_enabled(enabled);
+        Ok(())
+    }
+
+    fn handle_seat_get_slow_keys_enabled(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.respond(Response::SeatGetSlowKeysEnabled {
+            enabled: seat.slow_keys_enabled(),
+        });
+        Ok(())
+    }
+
+    fn handle_seat_set_slow_keys_delay(&self, seat: Seat, ms: u32) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_slow_keys_delay(ms);
+        Ok(())
+    }
+
+    fn handle_seat_set_bounce_keys_enabled(
+        &self,
+        seat: Seat,
+        enabled: bool,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_bounce_keys_enabled(enabled);
+        Ok(())
+    }
+
+    fn handle_seat_get_bounce_keys_enabled(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.respond(Response::SeatGetBounceKeysEnabled {
+            enabled: seat.bounce_keys_enabled(),
+        });
+        Ok(())
+    }
+
+    fn handle_seat_set_bounce_keys_delay(&self, seat: Seat, ms: u32) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_bounce_keys_delay(ms);
+        Ok(())
+    }
+
+    fn handle_seat_set_mouse_keys_enabled(
+        &self,
+        seat: Seat,
+        enabled: bool,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_mouse_keys_enabled(enabled);
+        Ok(())
+    }
+
+    fn handle_seat_get_mouse_keys_enabled(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.respond(Response::SeatGetMouseKeysEnabled {
+            enabled: seat.mouse_keys_enabled(),
+        });
+        Ok(())
+    }
+
+    fn handle_seat_set_mouse_keys(
+        &self,
+        seat: Seat,
+        mouse_keys: MouseKeys,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_mouse_keys_settings(mouse_keys);
+        Ok(())
+    }
+
+    fn handle_seat_set_which_key_enabled(&self, seat: Seat, enabled: bool) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_which_key_enabled(enabled);
+        Ok(())
+    }
+
+    fn handle_seat_get_which_key_enabled(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.respond(Response::SeatGetWhichKeyEnabled {
+            enabled: seat.which_key_enabled(),
+        });
+        Ok(())
+    }
+
+    fn handle_seat_set_which_key_delay(&self, seat: Seat, ms: u32) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_which_key_delay(ms);
+        Ok(())
+    }
+
+    fn handle_seat_set_which_key(
+        &self,
+        seat: Seat,
+        title: String,
+        entries: Vec<(String, String)>,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_which_key(title, entries);
+        Ok(())
+    }
+
+    fn handle_seat_start_macro_recording(
+        &self,
+        seat: Seat,
+        name: String,
+        pointer: bool,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.start_macro_recording(name, pointer);
+        Ok(())
+    }
+
+    fn handle_seat_stop_macro_recording(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.stop_macro_recording();
+        Ok(())
+    }
+
+    fn handle_seat_replay_macro(&self, seat: Seat, name: String) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.replay_macro(&name);
+        Ok(())
+    }
+
     fn handle_seat_reload_simple_im(&self, seat: Seat) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         seat.reload_simple_im();
@@ -3875,6 +4689,264 @@ impl ConfigProxyHandler {
                 self.handle_set_split_reuses_container(reuse)
             }
             ClientMessage::GetSplitReusesContainer => self.handle_get_split_reuses_container(),
+            ClientMessage::SetBarElements { elements } => self.handle_set_bar_elements(elements),
+            ClientMessage::SetBarElementSeparator { separator } => {
+                self.handle_set_bar_element_separator(separator)
+            }
+            ClientMessage::SeatSetInputModeStack { seat, modes } => self
+                .handle_seat_set_input_mode_stack(seat, modes)
+                .wrn("seat_set_input_mode_stack")?,
+            ClientMessage::AddPointerShortcut { seat, trigger } => self
+                .handle_add_pointer_shortcut(seat, trigger)
+                .wrn("add_pointer_shortcut")?,
+            ClientMessage::RemovePointerShortcut { seat, trigger } => self
+                .handle_remove_pointer_shortcut(seat, trigger)
+                .wrn("remove_pointer_shortcut")?,
+            ClientMessage::AddGestureBinding { seat, gesture } => self
+                .handle_add_gesture_binding(seat, gesture)
+                .wrn("add_gesture_binding")?,
+            ClientMessage::RemoveGestureBinding { seat, gesture } => self
+                .handle_remove_gesture_binding(seat, gesture)
+                .wrn("remove_gesture_binding")?,
+            ClientMessage::SetGestureContinuous {
+                seat,
+                gesture,
+                continuous,
+            } => self
+                .handle_set_gesture_continuous(seat, gesture, continuous)
+                .wrn("set_gesture_continuous")?,
+            ClientMessage::SetGestureSwipeDistance { seat, distance } => self
+                .handle_set_gesture_swipe_distance(seat, distance)
+                .wrn("set_gesture_swipe_distance")?,
+            ClientMessage::AddTouchGestureBinding { seat, gesture } => self
+                .handle_add_touch_gesture_binding(seat, gesture)
+                .wrn("add_touch_gesture_binding")?,
+            ClientMessage::RemoveTouchGestureBinding { seat, gesture } => self
+                .handle_remove_touch_gesture_binding(seat, gesture)
+                .wrn("remove_touch_gesture_binding")?,
+            ClientMessage::SetTouchLongPressDrag { seat, enabled } => self
+                .handle_set_touch_long_press_drag(seat, enabled)
+                .wrn("set_touch_long_press_drag")?,
+            ClientMessage::ConnectorSetTouchEdgeWidth { connector, width } => self
+                .handle_connector_set_touch_edge_width(connector, width)
+                .wrn("connector_set_touch_edge_width")?,
+            ClientMessage::SetInputDwt { device, enabled } => self
+                .handle_set_input_dwt(device, enabled)
+                .wrn("set_input_dwt")?,
+            ClientMessage::SetInputDwtp { device, enabled } => self
+                .handle_set_input_dwtp(device, enabled)
+                .wrn("set_input_dwtp")?,
+            ClientMessage::SetInputTapButtonMap { device, map } => self
+                .handle_set_input_tap_button_map(device, map)
+                .wrn("set_input_tap_button_map")?,
+            ClientMessage::SetInputSendEventsMode { device, mode } => self
+                .handle_set_input_send_events_mode(device, mode)
+                .wrn("set_input_send_events_mode")?,
+            ClientMessage::SetInputRotation { device, degrees } => self
+                .handle_set_input_rotation(device, degrees)
+                .wrn("set_input_rotation")?,
+            ClientMessage::SetInputCustomAccel {
+                device,
+                step,
+                points,
+            } => self
+                .handle_set_input_custom_accel(device, step, points)
+                .wrn("set_input_custom_accel")?,
+            ClientMessage::SetLayoutMemoryMode { seat, mode } => self
+                .handle_set_layout_memory_mode(seat, mode)
+                .wrn("set_layout_memory_mode")?,
+            ClientMessage::SeatGetKeyboardGroup { seat } => self
+                .handle_seat_get_keyboard_group(seat)
+                .wrn("seat_get_keyboard_group")?,
+            ClientMessage::SeatSetKeyboardGroup { seat, group } => self
+                .handle_seat_set_keyboard_group(seat, group)
+                .wrn("seat_set_keyboard_group")?,
+            ClientMessage::SetWindowMatcherShortcutsInhibit { matcher, inhibit } => self
+                .handle_set_window_matcher_shortcuts_inhibit(matcher, inhibit)
+                .wrn("set_window_matcher_shortcuts_inhibit")?,
+            ClientMessage::SetShortcutsInhibitEscape { seat, key } => self
+                .handle_set_shortcuts_inhibit_escape(seat, key)
+                .wrn("set_shortcuts_inhibit_escape")?,
+            ClientMessage::SetInputCaptureRelease { seat, key } => self
+                .handle_set_input_capture_release(seat, key)
+                .wrn("set_input_capture_release")?,
+            ClientMessage::SetInputKeyRemap { device, remap } => self
+                .handle_set_input_key_remap(device, remap)
+                .wrn("set_input_key_remap")?,
+            ClientMessage::SeatSetStickyKeysEnabled { seat, enabled } => self
+                .handle_seat_set_sticky_keys_enabled(seat, enabled)
+                .wrn("seat_set_sticky_keys_enabled")?,
+            ClientMessage::SeatGetStickyKeysEnabled { seat } => self
+                .handle_seat_get_sticky_keys_enabled(seat)
+                .wrn("seat_get_sticky_keys_enabled")?,
+            ClientMessage::SeatSetSlowKeysEnabled { seat, enabled } => self
+                .handle_seat_set_slow_keys_enabled(seat, enabled)
+                .wrn("seat_set_slow_keys_enabled")?,
+            ClientMessage::SeatGetSlowKeysEnabled { seat } => self
+                .handle_seat_get_slow_keys_enabled(seat)
+                .wrn("seat_get_slow_keys_enabled")?,
+            ClientMessage::SeatSetSlowKeysDelay { seat, ms } => self
+                .handle_seat_set_slow_keys_delay(seat, ms)
+                .wrn("seat_set_slow_keys_delay")?,
+            ClientMessage::SeatSetBounceKeysEnabled { seat, enabled } => self
+                .handle_seat_set_bounce_keys_enabled(seat, enabled)
+                .wrn("seat_set_bounce_keys_enabled")?,
+            ClientMessage::SeatGetBounceKeysEnabled { seat } => self
+                .handle_seat_get_bounce_keys_enabled(seat)
+                .wrn("seat_get_bounce_keys_enabled")?,
+            ClientMessage::SeatSetBounceKeysDelay { seat, ms } => self
+                .handle_seat_set_bounce_keys_delay(seat, ms)
+                .wrn("seat_set_bounce_keys_delay")?,
+            ClientMessage::SeatSetMouseKeysEnabled { seat, enabled } => self
+                .handle_seat_set_mouse_keys_enabled(seat, enabled)
+                .wrn("seat_set_mouse_keys_enabled")?,
+            ClientMessage::SeatGetMouseKeysEnabled { seat } => self
+                .handle_seat_get_mouse_keys_enabled(seat)
+                .wrn("seat_get_mouse_keys_enabled")?,
+            ClientMessage::SeatSetMouseKeys { seat, mouse_keys } => self
+                .handle_seat_set_mouse_keys(seat, mouse_keys)
+                .wrn("seat_set_mouse_keys")?,
+            ClientMessage::SeatSetWhichKeyEnabled { seat, enabled } => self
+                .handle_seat_set_which_key_enabled(seat, enabled)
+                .wrn("seat_set_which_key_enabled")?,
+            ClientMessage::SeatGetWhichKeyEnabled { seat } => self
+                .handle_seat_get_which_key_enabled(seat)
+                .wrn("seat_get_which_key_enabled")?,
+            ClientMessage::SeatSetWhichKeyDelay { seat, ms } => self
+                .handle_seat_set_which_key_delay(seat, ms)
+                .wrn("seat_set_which_key_delay")?,
+            ClientMessage::SeatSetWhichKey {
+                seat,
+                title,
+                entries,
+            } => self
+                .handle_seat_set_which_key(seat, title, entries)
+                .wrn("seat_set_which_key")?,
+            ClientMessage::SeatStartMacroRecording {
+                seat,
+                name,
+                pointer,
+            } => self
+                .handle_seat_start_macro_recording(seat, name, pointer)
+                .wrn("seat_start_macro_recording")?,
+            ClientMessage::SeatStopMacroRecording { seat } => self
+                .handle_seat_stop_macro_recording(seat)
+                .wrn("seat_stop_macro_recording")?,
+            ClientMessage::SeatReplayMacro { seat, name } => self
+                .handle_seat_replay_macro(seat, name)
+                .wrn("seat_replay_macro")?,
+            ClientMessage::SetInputTabletArea {
+                input_device,
+                x1,
+                y1,
+                x2,
+                y2,
+            } => self
+                .handle_set_input_tablet_area(input_device, x1, y1, x2, y2)
+                .wrn("set_input_tablet_area")?,
+            ClientMessage::SetInputTabletKeepAspectRatio { input_device, keep } => self
+                .handle_set_input_tablet_keep_aspect_ratio(input_device, keep)
+                .wrn("set_input_tablet_keep_aspect_ratio")?,
+            ClientMessage::MapInputDeviceToWindow {
+                input_device,
+                window,
+            } => self
+                .handle_map_input_device_to_window(input_device, window)
+                .wrn("map_input_device_to_window")?,
+            ClientMessage::SetInputTabletRegion {
+                input_device,
+                x,
+                y,
+                width,
+                height,
+            } => self
+                .handle_set_input_tablet_region(input_device, x, y, width, height)
+                .wrn("set_input_tablet_region")?,
+            ClientMessage::SetInputTabletPressureCurve {
+                input_device,
+                tool,
+                curve,
+            } => self
+                .handle_set_input_tablet_pressure_curve(input_device, tool, curve)
+                .wrn("set_input_tablet_pressure_curve")?,
+            ClientMessage::SetInputTabletEraserMode { input_device, mode } => self
+                .handle_set_input_tablet_eraser_mode(input_device, mode)
+                .wrn("set_input_tablet_eraser_mode")?,
+            ClientMessage::UnmapInputDeviceFromWindow { input_device } => self
+                .handle_unmap_input_device_from_window(input_device)
+                .wrn("unmap_input_device_from_window")?,
+            ClientMessage::SeatSetHideCursorWhileTyping { seat, hide } => self
+                .handle_seat_set_hide_cursor_while_typing(seat, hide)
+                .wrn("seat_set_hide_cursor_while_typing")?,
+            ClientMessage::SeatSetCursorHideTimeout { seat, usec } => self
+                .handle_seat_set_cursor_hide_timeout(seat, usec)
+                .wrn("seat_set_cursor_hide_timeout")?,
+            ClientMessage::SeatSetCursorTheme { seat, theme } => self
+                .handle_seat_set_cursor_theme(seat, theme)
+                .wrn("seat_set_cursor_theme")?,
+            ClientMessage::ConfigureConnectors { configurations } => self
+                .handle_configure_connectors(&configurations)
+                .wrn("configure_connectors")?,
+            ClientMessage::SetOutputProfiles { profiles, active } => {
+                self.handle_set_output_profiles(profiles, active)
+            }
+            ClientMessage::ConnectorSetDpms { connector, on } => self
+                .handle_connector_set_dpms(connector, on)
+                .wrn("connector_set_dpms")?,
+            ClientMessage::ConnectorGetDpms { connector } => self
+                .handle_connector_get_dpms(connector)
+                .wrn("connector_get_dpms")?,
+            ClientMessage::ConnectorSetIccProfile { connector, path } => self
+                .handle_connector_set_icc_profile(connector, path)
+                .wrn("connector_set_icc_profile")?,
+            ClientMessage::ConnectorSetCvtMode {
+                connector,
+                width,
+                height,
+                refresh_millihz,
+                reduced_blanking,
+            } => self
+                .handle_connector_set_cvt_mode(
+                    connector,
+                    width,
+                    height,
+                    refresh_millihz,
+                    reduced_blanking,
+                )
+                .wrn("connector_set_cvt_mode")?,
+            ClientMessage::ConnectorSetModeline {
+                connector,
+                modeline,
+            } => self
+                .handle_connector_set_modeline(connector, modeline)
+                .wrn("connector_set_modeline")?,
+            ClientMessage::ConnectorSetEdidOverride { connector, path } => self
+                .handle_connector_set_edid_override(connector, path)
+                .wrn("connector_set_edid_override")?,
+            ClientMessage::SetAutoScale { auto_scale } => self
+                .handle_set_auto_scale(auto_scale)
+                .wrn("set_auto_scale")?,
+            ClientMessage::ConnectorSetVncServer { connector, address } => self
+                .handle_connector_set_vnc_server(connector, address)
+                .wrn("connector_set_vnc_server")?,
+            ClientMessage::ConnectorGetBacklight { connector } => self
+                .handle_connector_get_backlight(connector)
+                .wrn("connector_get_backlight")?,
+            ClientMessage::ConnectorSetBacklight {
+                connector,
+                value,
+                relative,
+            } => self
+                .handle_connector_set_backlight(connector, value, relative)
+                .wrn("connector_set_backlight")?,
+            ClientMessage::SetBacklightIndicatorEnabled { enabled } => {
+                self.handle_set_backlight_indicator_enabled(enabled)
+            }
+            ClientMessage::SetAutoRotationEnabled { enabled } => {
+                self.handle_set_auto_rotation_enabled(enabled)
+            }
+            ClientMessage::SetRotationLocked { locked } => self.handle_set_rotation_locked(locked),
+            ClientMessage::GetRotationLocked => self.handle_get_rotation_locked(),
             ClientMessage::SetShowBar { show } => self.handle_set_show_bar(show),
             ClientMessage::GetShowBar => self.handle_get_show_bar(),
             ClientMessage::SetShowTitles { show } => self.handle_set_show_titles(show),
@@ -4114,6 +5186,15 @@ impl ConfigProxyHandler {
         None
     }
 
+    pub fn shortcuts_inhibit_policy(&self, data: &ToplevelData) -> Option<ShortcutsInhibitPolicy> {
+        for (matcher, policy) in self.window_matcher_shortcuts_inhibit.lock().values() {
+            if matcher.node.pull(data) {
+                return Some(*policy);
+            }
+        }
+        None
+    }
+
     pub fn initial_output_for_workspace(&self, name: &str) -> Option<Option<Rc<OutputNode>>> {
         let ws = self.workspaces_by_name.get(name)?;
         let connector = ws.initial_connector.get()?;
@@ -4265,6 +5346,46 @@ enum CphError {
     UnknownScrollButton(ConfigInputEventCode),
     #[error("Tried to set an unknown scaling filter: {}", (.0).0)]
     UnknownScalingFilter(ConfigScalingFilter),
+    #[error("Tried to set an unknown tap button map: {}", (.0).0)]
+    UnknownTapButtonMap(TapButtonMap),
+    #[error("Tried to set an unknown send-events mode: {}", (.0).0)]
+    UnknownSendEventsMode(SendEventsMode),
+    #[error("Rotation must be less than 360 degrees but is {0}")]
+    InvalidRotation(u32),
+    #[error("Custom acceleration curves must have between 2 and 64 points but have {0}")]
+    InvalidCustomAccelPoints(usize),
+    #[error("Unknown layout memory mode {0:?}")]
+    UnknownLayoutMemoryMode(LayoutMemoryMode),
+    #[error("Unknown shortcuts inhibit policy {0:?}")]
+    UnknownShortcutsInhibit(ConfigShortcutsInhibit),
+    #[error("Invalid key remapping")]
+    KeyRemap(#[source] KeyRemapError),
+    #[error("Tablet region {2}x{3}+{0}+{1} is invalid")]
+    InvalidTabletRegion(i32, i32, i32, i32),
+    #[error("Tablet area ({0}, {1}, {2}, {3}) is invalid")]
+    InvalidTabletArea(f64, f64, f64, f64),
+    #[error("Unknown tablet tool type {}", (.0).0)]
+    UnknownTabletToolType(ConfigTabletToolType),
+    #[error("Pressure curve {0:?} has control points outside of [0, 1]")]
+    InvalidPressureCurve(PressureCurve),
+    #[error("Unknown eraser mode {}", (.0).0)]
+    UnknownEraserMode(EraserMode),
+    #[error("Could not generate the CVT mode")]
+    GenerateCvtMode(#[source] CvtError),
+    #[error("Could not parse the modeline")]
+    ParseModeline(#[source] ModelineError),
+    #[error("Could not load the EDID override")]
+    LoadEdid(#[source] EdidLoadError),
+    #[error("Auto scale parameters (dpi = {0}, step = {1}) are invalid")]
+    InvalidAutoScale(f64, f64),
+    #[error("Could not set the VNC server")]
+    SetVncServer(#[source] VncError),
+    #[error("Could not retrieve the brightness")]
+    GetBacklight(#[source] BacklightError),
+    #[error("Brightness {0} is invalid")]
+    InvalidBacklight(f64),
+    #[error("Could not set the brightness")]
+    SetBacklight(#[source] BacklightError),
 }
 
 trait WithRequestName {
@@ -1,4 +1,5 @@
 use crate::backend::InputDeviceCapability;
+use crate::backend::InputDeviceCustomAccel;
 use crate::backend::InputDeviceId;
 use crate::backend::InputDeviceScrollMethod;
 use crate::control_center::ControlCenterInner;
@@ -16,6 +17,7 @@ use crate::control_center::grid_label;
 use crate::control_center::grid_label_ui;
 use crate::control_center::label;
 use crate::control_center::row;
+use crate::control_center::row_ui;
 use crate::control_center::text_edit;
 use crate::control_center::tip;
 use crate::egui_adapter::egui_platform::icons::ICON_PENDING;
@@ -23,6 +25,8 @@ use crate::evdev::input_event_codes::InputEventCode;
 use crate::ifs::wl_output::WlOutputGlobal;
 use crate::ifs::wl_seat::SeatId;
 use crate::ifs::wl_seat::WlSeatGlobal;
+use crate::ifs::wl_seat::tablet::TabletArea;
+use crate::ifs::wl_seat::tablet::TabletToolType;
 use crate::kbvm::KbvmMap;
 use crate::state::DeviceHandlerData;
 use crate::state::State;
@@ -44,6 +48,11 @@ use egui::Widget;
 use egui::emath::Numeric;
 use egui::text::LayoutJob;
 use isnt::std_1::string::IsntStringExt;
+use jay_config::input::mouse_keys::MouseKeys;
+use jay_config::input::tablet::ERASER_MODE_ERASER;
+use jay_config::input::tablet::ERASER_MODE_PEN;
+use jay_config::input::tablet::EraserMode;
+use jay_config::input::tablet::PressureCurve;
 use jay_config::keyboard::syms::KeySym;
 use kbvm::GroupIndex;
 use kbvm::Keysym;
@@ -78,6 +87,8 @@ struct KeymapState {
     pointer_revert_key_str: Option<String>,
     unknown_pointer_revert_key: bool,
     shortcuts_group: Option<GroupIndex>,
+    cursor_theme: String,
+    cursor_theme_source: Option<Rc<String>>,
 }
 
 impl Default for KeymapState {
@@ -96,6 +107,8 @@ impl Default for KeymapState {
             pointer_revert_key_str: None,
             unknown_pointer_revert_key: false,
             shortcuts_group: None,
+            cursor_theme: Default::default(),
+            cursor_theme_source: None,
         }
     }
 }
@@ -186,6 +199,28 @@ impl InputPane {
                         1.0,
                         |v| seat.cursor_group().set_cursor_size(v),
                     );
+                    {
+                        let ui = &mut *ui.row();
+                        let theme = seat.cursor_group().cursor_theme();
+                        if ks.cursor_theme_source != theme {
+                            ks.cursor_theme = theme.as_deref().cloned().unwrap_or_default();
+                            ks.cursor_theme_source = theme;
+                        }
+                        grid_label_ui(ui, |ui| {
+                            ui.label("Cursor Theme");
+                            tip(ui, |ui| {
+                                ui.label("Leave this empty to use the default theme.");
+                            });
+                        });
+                        ui.horizontal(|ui| {
+                            text_edit(ui, &mut ks.cursor_theme);
+                            if ui.button("Apply").clicked() {
+                                let theme = (!ks.cursor_theme.is_empty())
+                                    .then(|| Rc::new(ks.cursor_theme.clone()));
+                                seat.cursor_group().set_cursor_theme(theme);
+                            }
+                        });
+                    }
                     bool_ui(
                         ui,
                         "Simple IM",
@@ -220,6 +255,25 @@ impl InputPane {
                         seat.cursor_group().hardware_cursor(),
                         |b| seat.cursor_group().set_hardware_cursor(b),
                     );
+                    bool(
+                        ui,
+                        "Hide Cursor While Typing",
+                        seat.cursor_group().hide_while_typing(),
+                        |b| seat.cursor_group().set_hide_while_typing(b),
+                    );
+                    drag_value_ui(
+                        ui,
+                        "Hide Cursor After (ms)",
+                        |ui| {
+                            tip(ui, |ui| {
+                                ui.label("0 means that the cursor is never hidden.");
+                            });
+                        },
+                        seat.cursor_group().hide_timeout_usec() / 1000,
+                        0..=u64::MAX / 1000,
+                        100.0,
+                        |v| seat.cursor_group().set_hide_timeout_usec(v * 1000),
+                    );
                     {
                         let ui = &mut *ui.row();
                         let v = seat.pointer_revert_key();
@@ -284,6 +338,155 @@ impl InputPane {
                         seat.fallback_output_mode(),
                         |v| seat.set_fallback_output_mode(v),
                     );
+                    combo_box_ui(
+                        ui,
+                        "Layout Memory",
+                        |ui| {
+                            tip(ui, |ui| {
+                                ui.label(concat!(
+                                    "This determines how the active keyboard layout is ",
+                                    "remembered when the keyboard focus changes.",
+                                ));
+                                ui.label("`Global` uses the same layout for all windows.");
+                                ui.label("`Window` remembers the layout of each window.");
+                                ui.label("`App ID` remembers the layout of each app ID.");
+                            });
+                        },
+                        seat.layout_memory_mode(),
+                        |v| seat.set_layout_memory_mode(v),
+                    );
+                    bool_ui(
+                        ui,
+                        "Sticky Keys",
+                        |ui| {
+                            tip(ui, |ui| {
+                                ui.label(concat!(
+                                    "Pressing and releasing a modifier latches it until the ",
+                                    "next other key has been released.",
+                                ));
+                                ui.label(concat!(
+                                    "Pressing and releasing a latched modifier again locks it ",
+                                    "until it is pressed a third time.",
+                                ));
+                            });
+                        },
+                        seat.sticky_keys_enabled(),
+                        |b| seat.set_sticky_keys_enabled(b),
+                    );
+                    bool_ui(
+                        ui,
+                        "Slow Keys",
+                        |ui| {
+                            tip(ui, |ui| {
+                                ui.label(concat!(
+                                    "Key presses are only accepted after the key has been ",
+                                    "held for the slow keys delay.",
+                                ));
+                            });
+                        },
+                        seat.slow_keys_enabled(),
+                        |b| seat.set_slow_keys_enabled(b),
+                    );
+                    drag_value(
+                        ui,
+                        "Slow Keys Delay (ms)",
+                        seat.slow_keys_delay(),
+                        0..=u32::MAX,
+                        10.0,
+                        |v| seat.set_slow_keys_delay(v),
+                    );
+                    bool_ui(
+                        ui,
+                        "Bounce Keys",
+                        |ui| {
+                            tip(ui, |ui| {
+                                ui.label(concat!(
+                                    "Key presses are ignored if the same key was released ",
+                                    "less than the bounce keys delay ago.",
+                                ));
+                            });
+                        },
+                        seat.bounce_keys_enabled(),
+                        |b| seat.set_bounce_keys_enabled(b),
+                    );
+                    drag_value(
+                        ui,
+                        "Bounce Keys Delay (ms)",
+                        seat.bounce_keys_delay(),
+                        0..=u32::MAX,
+                        10.0,
+                        |v| seat.set_bounce_keys_delay(v),
+                    );
+                    bool_ui(
+                        ui,
+                        "Mouse Keys",
+                        |ui| {
+                            tip(ui, |ui| {
+                                ui.label("The numpad moves the pointer, clicks, and scrolls.");
+                            });
+                        },
+                        seat.mouse_keys_enabled(),
+                        |b| seat.set_mouse_keys_enabled(b),
+                    );
+                    let mouse_keys = seat.mouse_keys_settings();
+                    drag_value(
+                        ui,
+                        "Mouse Keys Speed (px/s)",
+                        mouse_keys.speed,
+                        0.0..=f64::INFINITY,
+                        10.0,
+                        |v| {
+                            seat.set_mouse_keys_settings(MouseKeys {
+                                speed: v,
+                                ..mouse_keys.clone()
+                            })
+                        },
+                    );
+                    drag_value(
+                        ui,
+                        "Mouse Keys Max Speed (px/s)",
+                        mouse_keys.max_speed,
+                        0.0..=f64::INFINITY,
+                        10.0,
+                        |v| {
+                            seat.set_mouse_keys_settings(MouseKeys {
+                                max_speed: v,
+                                ..mouse_keys.clone()
+                            })
+                        },
+                    );
+                    drag_value(
+                        ui,
+                        "Mouse Keys Acceleration Time (ms)",
+                        mouse_keys.acceleration_time_ms,
+                        0..=u32::MAX,
+                        10.0,
+                        |v| {
+                            seat.set_mouse_keys_settings(MouseKeys {
+                                acceleration_time_ms: v,
+                                ..mouse_keys.clone()
+                            })
+                        },
+                    );
+                    bool_ui(
+                        ui,
+                        "Which-Key Overlay",
+                        |ui| {
+                            tip(ui, |ui| {
+                                ui.label("Shows the shortcuts of the current input mode.");
+                            });
+                        },
+                        seat.which_key_enabled(),
+                        |b| seat.set_which_key_enabled(b),
+                    );
+                    drag_value(
+                        ui,
+                        "Which-Key Delay (ms)",
+                        seat.which_key_delay(),
+                        0..=u32::MAX,
+                        10.0,
+                        |v| seat.set_which_key_delay(v),
+                    );
                 });
                 ui.label("Focus History");
                 ui.indent("focus-history", |ui| {
@@ -494,6 +697,48 @@ impl InputPane {
                             });
                         }
                     }
+                    if let Some(old) = dev.device.dwt_enabled() {
+                        bool(ui, "Disable While Typing", old, |v| {
+                            dev.set_dwt_enabled(&self.state, v)
+                        });
+                    }
+                    if let Some(old) = dev.device.dwtp_enabled() {
+                        bool(ui, "Disable While Trackpointing", old, |v| {
+                            dev.set_dwtp_enabled(&self.state, v)
+                        });
+                    }
+                    if let Some(old) = dev.device.tap_button_map() {
+                        combo_box(ui, "Tap Button Map", old, |v| {
+                            dev.set_tap_button_map(&self.state, v)
+                        });
+                    }
+                    if let Some(old) = dev.device.send_events_mode() {
+                        combo_box_filtered(ui, "Send Events", dev.send_events_modes, old, |v| {
+                            dev.set_send_events_mode(&self.state, v)
+                        });
+                    }
+                    if let Some(old) = dev.device.rotation() {
+                        drag_value(ui, "Rotation (degrees)", old, 0..=359, 1.0, |v| {
+                            dev.set_rotation(&self.state, v)
+                        });
+                    }
+                    if let Some(old) = dev.device.custom_accel() {
+                        drag_value(
+                            ui,
+                            "Custom Accel Step",
+                            old.step,
+                            0.0..=f64::INFINITY,
+                            0.01,
+                            |v| {
+                                let accel = InputDeviceCustomAccel {
+                                    step: v,
+                                    points: old.points.clone(),
+                                };
+                                dev.set_custom_accel(&self.state, Rc::new(accel))
+                            },
+                        );
+                        label(ui, "Custom Accel Points", format!("{:?}", old.points));
+                    }
                     {
                         let ui = &mut *ui.row();
                         grid_label_ui(ui, |ui| {
@@ -527,6 +772,9 @@ impl InputPane {
                             }
                         });
                     }
+                    if dev.tablet_init.is_some() {
+                        self.show_tablet_settings(ui, dev);
+                    }
                     matrix_ui(
                         ui,
                         "Transform Matrix",
@@ -578,6 +826,135 @@ impl InputPane {
     }
 }
 
+impl InputPane {
+    fn show_tablet_settings(&self, ui: &mut Ui, dev: &Rc<DeviceHandlerData>) {
+        let config = &dev.tablet_config;
+        {
+            let window = config
+                .window
+                .get()
+                .and_then(|id| self.state.toplevels.get(&id))
+                .and_then(|tl| tl.upgrade());
+            let ui = &mut *ui.row();
+            grid_label_ui(ui, |ui| {
+                ui.label("Tablet Window");
+                tip(ui, |ui| {
+                    ui.label("While this window is visible, the tablet is mapped to it.");
+                });
+            });
+            ui.horizontal(|ui| {
+                match &window {
+                    Some(tl) => ui.label(&*tl.tl_data().title.borrow()),
+                    None => ui.label("None"),
+                };
+                if ui.button("Unmap").clicked() {
+                    dev.set_tablet_window(&self.state, None);
+                }
+            });
+        }
+        {
+            let region = config.region.get();
+            let ui = &mut *ui.row();
+            grid_label(ui, "Tablet Region");
+            ui.horizontal(|ui| {
+                match region {
+                    Some(r) => ui.label(format!(
+                        "{}x{}+{}+{}",
+                        r.width(),
+                        r.height(),
+                        r.x1(),
+                        r.y1()
+                    )),
+                    None => ui.label("None"),
+                };
+                if ui.button("Clear").clicked() {
+                    dev.set_tablet_region(&self.state, None);
+                }
+            });
+        }
+        {
+            let old = config.area.get();
+            let mut v = [old.x1, old.y1, old.x2, old.y2];
+            row_ui(
+                ui,
+                "Tablet Area",
+                |ui| {
+                    tip(ui, |ui| {
+                        ui.label("The part of the tablet that is used: x1, y1, x2, y2.");
+                    });
+                },
+                |ui| {
+                    ui.horizontal(|ui| {
+                        for cell in &mut v {
+                            DragValue::new(cell).speed(0.01).range(0.0..=1.0).ui(ui);
+                        }
+                    });
+                },
+            );
+            let [x1, y1, x2, y2] = v;
+            if v != [old.x1, old.y1, old.x2, old.y2] && x1 < x2 && y1 < y2 {
+                dev.set_tablet_area(&self.state, TabletArea { x1, y1, x2, y2 });
+            }
+        }
+        bool(
+            ui,
+            "Keep Aspect Ratio",
+            config.keep_aspect_ratio.get(),
+            |v| dev.set_tablet_keep_aspect_ratio(&self.state, v),
+        );
+        {
+            let old = config.eraser_mode.get();
+            let text = |m: EraserMode| match m {
+                ERASER_MODE_PEN => "Pen",
+                _ => "Eraser",
+            };
+            let mut v = old;
+            row(ui, "Eraser Mode", |ui| {
+                ComboBox::from_id_salt("eraser-mode")
+                    .selected_text(text(old))
+                    .show_ui(ui, |ui| {
+                        for m in [ERASER_MODE_ERASER, ERASER_MODE_PEN] {
+                            ui.selectable_value(&mut v, m, text(m));
+                        }
+                    });
+            });
+            if v != old {
+                dev.set_tablet_eraser_mode(&self.state, v);
+            }
+        }
+        {
+            let old = config
+                .pressure_curves
+                .borrow()
+                .get(&TabletToolType::Pen)
+                .copied()
+                .unwrap_or_default();
+            let mut v = [old.x1, old.y1, old.x2, old.y2];
+            row_ui(
+                ui,
+                "Pen Pressure Curve",
+                |ui| {
+                    tip(ui, |ui| {
+                        ui.label("The control points of a bézier curve: x1, y1, x2, y2.");
+                    });
+                },
+                |ui| {
+                    ui.horizontal(|ui| {
+                        for cell in &mut v {
+                            DragValue::new(cell).speed(0.01).range(0.0..=1.0).ui(ui);
+                        }
+                    });
+                },
+            );
+            let [x1, y1, x2, y2] = v;
+            let new = PressureCurve { x1, y1, x2, y2 };
+            if new != old {
+                dev.set_tablet_pressure_curve(&self.state, TabletToolType::Pen, new);
+            }
+        }
+    }
+}
+
 impl KeymapState {
     fn backup(&mut self, map: Option<&Rc<KbvmMap>>) {
         if self.backup.is_none()
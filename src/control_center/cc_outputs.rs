@@ -1004,6 +1004,7 @@ fn show_connector(state: &State, settings: &Settings, head: &mut CompleteHead, u
                 let mut diff = false;
                 show_serial_number(ui, m);
                 diff |= show_enablement(state, ui, m, t);
+                show_power(state, ui, m, head.id);
                 diff |= show_position(ui, m, t);
                 diff |= show_scale(ui, m, t);
                 diff |= show_scaling_filter(ui, m, t);
@@ -1020,6 +1021,7 @@ fn show_connector(state: &State, settings: &Settings, head: &mut CompleteHead, u
                 diff |= show_blend_space(ui, m, t);
                 diff |= show_use_native_gamut(ui, m, t);
                 show_native_gamut(ui, m);
+                show_icc_profile(state, ui, head.id);
                 diff |= show_cursor_hz(ui, m, t);
                 show_flip_margin(state, ui, m, t, head.id);
                 if diff {
@@ -1060,6 +1062,23 @@ fn show_enablement(state: &State, ui: &mut Ui, m: &HeadState, t: &mut Option<Hea
     diff
 }
 
+fn show_power(state: &State, ui: &mut Ui, m: &HeadState, connector_id: ConnectorId) {
+    if !m.connected || !m.connector_enabled {
+        return;
+    }
+    let Some(connector) = state.connectors.get(&connector_id) else {
+        return;
+    };
+    let ui = &mut *ui.row();
+    grid_label(ui, "Powered On");
+    let mut v = !connector.dpms_off.get();
+    if Checkbox::without_text(&mut v).ui(ui).changed()
+        && let Err(e) = connector.set_dpms(state, v)
+    {
+        log::error!("Could not change the power state: {}", ErrorFmt(e));
+    }
+}
+
 fn show_position(ui: &mut Ui, m: &HeadState, t: &mut Option<HeadState>) -> bool {
     if !effective!(m, t).in_compositor_space {
         return false;
@@ -1723,6 +1742,16 @@ fn show_native_gamut(ui: &mut Ui, m: &HeadState) {
     });
 }
 
+fn show_icc_profile(state: &State, ui: &mut Ui, connector_id: ConnectorId) {
+    let Some(node) = state.root.outputs.get(&connector_id) else {
+        return;
+    };
+    let Some(icc) = node.global.persistent.icc_profile.get() else {
+        return;
+    };
+    label(ui, "ICC Profile", icc.path.as_str());
+}
+
 fn show_cursor_hz(ui: &mut Ui, m: &HeadState, t: &mut Option<HeadState>) -> bool {
     if !effective!(m, t).in_compositor_space {
         return false;
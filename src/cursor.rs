@@ -8,6 +8,7 @@ use crate::format::ARGB8888;
 use crate::gfx_api::GfxContext;
 use crate::gfx_api::GfxError;
 use crate::gfx_api::GfxTexture;
+use crate::pango::PangoError;
 use crate::rect::Rect;
 use crate::renderer::Renderer;
 use crate::renderer::renderer_base::RenderTexture;
@@ -45,6 +46,8 @@ use std::sync::LazyLock;
 use std::time::Duration;
 use thiserror::Error;
 
+mod svg;
+
 const XCURSOR_MAGIC: u32 = 0x72756358;
 const XCURSOR_IMAGE_TYPE: u32 = 0xfffd0002;
 const XCURSOR_PATH_DEFAULT: &str =
@@ -167,7 +170,11 @@ pub enum KnownCursor {
 }
 
 impl ServerCursors {
-    pub fn load(ctx: &Rc<dyn GfxContext>, state: &State) -> Result<Option<Self>, CursorError> {
+    pub fn load(
+        ctx: &Rc<dyn GfxContext>,
+        state: &State,
+        theme: Option<&str>,
+    ) -> Result<Option<Self>, CursorError> {
         let paths = find_cursor_paths();
         log::debug!("Trying to load cursors from paths {:?}", paths);
         let sizes = state.cursor_sizes.to_vec();
@@ -175,7 +182,9 @@ impl ServerCursors {
         if sizes.is_empty() || scales.is_empty() {
             return Ok(None);
         }
-        let theme = XCURSOR_THEME.map(|theme| BStr::new(theme.as_bytes()));
+        let theme = theme
+            .or(*XCURSOR_THEME)
+            .map(|theme| BStr::new(theme.as_bytes()));
 
         let load =
             |names: &[&str]| ServerCursorTemplate::load(names, theme, &scales, &sizes, &paths, ctx);
@@ -518,6 +527,11 @@ impl Cursor for AnimatedCursor {
     }
 }
 
+enum CursorFile {
+    Xcursor(File),
+    Svg(BString),
+}
+
 struct OpenCursorResult {
     images: Vec<BHashMap<(Scale, u32), Rc<XCursorImage>>>,
 }
@@ -549,12 +563,14 @@ fn open_cursor(
             }
         }
     }
-    let file = match file {
-        Some(f) => f,
-        _ => return Err(CursorError::NotFound),
-    };
-    let mut file = BufReader::new(file);
-    parser_cursor_file(&mut file, scales, sizes)
+    match file {
+        Some(CursorFile::Xcursor(file)) => {
+            let mut file = BufReader::new(file);
+            parser_cursor_file(&mut file, scales, sizes)
+        }
+        Some(CursorFile::Svg(dir)) => svg::load_svg_cursor(&dir, scales, sizes),
+        _ => Err(CursorError::NotFound),
+    }
 }
 
 fn open_cursor_file<'a>(
@@ -562,7 +578,7 @@ fn open_cursor_file<'a>(
     paths: &[BString],
     theme: &BStr,
     name: &'a BStr,
-) -> Option<File> {
+) -> Option<CursorFile> {
     if !pairs_tested.insert((theme.to_owned(), name)) {
         return None;
     }
@@ -574,11 +590,17 @@ fn open_cursor_file<'a>(
         let mut theme_dir = cursor_path.to_vec();
         theme_dir.push(b'/');
         theme_dir.extend_from_slice(theme.as_bytes());
+        let mut svg_dir = theme_dir.clone();
+        svg_dir.extend_from_slice(b"/cursors_scalable/");
+        svg_dir.extend_from_slice(name.as_bytes());
+        if svg::is_svg_cursor(&svg_dir) {
+            return Some(CursorFile::Svg(svg_dir.into()));
+        }
         let mut cursor_file = theme_dir.clone();
         cursor_file.extend_from_slice(b"/cursors/");
         cursor_file.extend_from_slice(name.as_bytes());
         if let Ok(f) = File::open(cursor_file.to_os_str().unwrap()) {
-            return Some(f);
+            return Some(CursorFile::Xcursor(f));
         }
         if parents.is_none() {
             let mut index_file = theme_dir.clone();
@@ -670,6 +692,18 @@ pub enum CursorError {
     NotFound,
     #[error("Could not import the cursor as a texture")]
     ImportError(#[from] GfxError),
+    #[error("Could not parse the SVG cursor metadata")]
+    SvgMetadata(#[source] serde_json::Error),
+    #[error("The SVG cursor metadata is empty")]
+    EmptySvgCursor,
+    #[error("The SVG cursor has an invalid nominal size")]
+    InvalidSvgNominalSize,
+    #[error("Could not parse the SVG file: {0}")]
+    ParseSvg(String),
+    #[error("Could not render the SVG file: {0}")]
+    RenderSvg(String),
+    #[error("Could not create a cairo surface")]
+    Cairo(#[from] PangoError),
 }
 
 #[derive(Default, Clone)]
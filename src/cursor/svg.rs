@@ -0,0 +1,212 @@
+#![allow(non_camel_case_types)]
+
+#[cfg(test)]
+mod tests;
+
+use crate::cursor::CursorError;
+use crate::cursor::OpenCursorResult;
+use crate::cursor::XCursorImage;
+use crate::pango::CairoImageSurface;
+use crate::pango::consts::CAIRO_FORMAT_ARGB32;
+use crate::scale::Scale;
+use crate::utils::bhash::BHashMap;
+use bstr::ByteSlice;
+use serde::Deserialize;
+use std::cell::Cell;
+use std::ffi::CStr;
+use std::ffi::c_char;
+use std::ffi::c_int;
+use std::ffi::c_void;
+use std::fs;
+use std::path::Path;
+use std::ptr;
+use std::rc::Rc;
+
+pub struct RsvgHandle(#[expect(unused)] u8);
+
+#[repr(C)]
+pub struct GError {
+    domain: u32,
+    code: c_int,
+    message: *mut c_char,
+}
+
+#[repr(C)]
+pub struct RsvgRectangle {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+dynload! {
+    RSVG: Rsvg from "librsvg-2.so.2" for "svg-cursors" ("required for SVG cursor themes") {
+        rsvg_handle_new_from_data: unsafe extern "C" fn(
+            data: *const u8,
+            data_len: usize,
+            error: *mut *mut GError,
+        ) -> *mut RsvgHandle,
+        rsvg_handle_render_document: unsafe extern "C" fn(
+            handle: *mut RsvgHandle,
+            cr: *mut c_void,
+            viewport: *const RsvgRectangle,
+            error: *mut *mut GError,
+        ) -> c_int,
+        g_object_unref: unsafe extern "C" fn(object: *mut c_void),
+        g_error_free: unsafe extern "C" fn(error: *mut GError),
+    }
+}
+
+#[derive(Deserialize)]
+struct SvgCursorFrame {
+    filename: String,
+    hotspot_x: f64,
+    hotspot_y: f64,
+    nominal_size: f64,
+    #[serde(default)]
+    delay: u32,
+}
+
+/// Returns whether `dir` contains a cursor in the KDE `cursors_scalable` format.
+///
+/// This returns `false` if librsvg is not available so that XCursor files can be used
+/// as a fallback.
+pub(super) fn is_svg_cursor(dir: &[u8]) -> bool {
+    let Ok(dir) = dir.to_path() else {
+        return false;
+    };
+    dir.join("metadata.json").is_file() && RSVG.is_some()
+}
+
+fn parse_metadata(metadata: &[u8]) -> Result<Vec<SvgCursorFrame>, CursorError> {
+    let frames: Vec<SvgCursorFrame> =
+        serde_json::from_slice(metadata).map_err(CursorError::SvgMetadata)?;
+    if frames.is_empty() {
+        return Err(CursorError::EmptySvgCursor);
+    }
+    if frames.iter().any(|f| f.nominal_size <= 0.0) {
+        return Err(CursorError::InvalidSvgNominalSize);
+    }
+    Ok(frames)
+}
+
+fn effective_size(size: u32, scale: Scale) -> i32 {
+    (size as f64 * scale.to_f64()).round().max(1.0) as i32
+}
+
+impl SvgCursorFrame {
+    fn hotspot(&self, effective_size: i32) -> (i32, i32) {
+        let factor = effective_size as f64 / self.nominal_size;
+        (
+            (self.hotspot_x * factor).round() as i32,
+            (self.hotspot_y * factor).round() as i32,
+        )
+    }
+}
+
+pub(super) fn load_svg_cursor(
+    dir: &[u8],
+    scales: &[Scale],
+    sizes: &[u32],
+) -> Result<OpenCursorResult, CursorError> {
+    let Some(rsvg) = &*RSVG else {
+        return Err(CursorError::NotFound);
+    };
+    let dir = dir.to_path().map_err(|_| CursorError::NotFound)?;
+    let metadata = fs::read(dir.join("metadata.json"))?;
+    let frames = parse_metadata(&metadata)?;
+    let mut images = vec![];
+    for frame in &frames {
+        let handle = SvgHandle::new(rsvg, &dir.join(&frame.filename))?;
+        let mut frame_images = BHashMap::default();
+        for scale in scales {
+            for size in sizes {
+                let effective_size = effective_size(*size, *scale);
+                let mut image = handle.render(effective_size)?;
+                (image.xhot, image.yhot) = frame.hotspot(effective_size);
+                image.delay = frame.delay;
+                frame_images.insert((*scale, *size), Rc::new(image));
+            }
+        }
+        images.push(frame_images);
+    }
+    Ok(OpenCursorResult { images })
+}
+
+struct SvgHandle<'a> {
+    rsvg: &'a Rsvg,
+    handle: *mut RsvgHandle,
+}
+
+impl<'a> SvgHandle<'a> {
+    fn new(rsvg: &'a Rsvg, path: &Path) -> Result<Self, CursorError> {
+        let data = fs::read(path)?;
+        let mut error = ptr::null_mut();
+        let handle =
+            unsafe { (rsvg.rsvg_handle_new_from_data)(data.as_ptr(), data.len(), &mut error) };
+        if handle.is_null() {
+            return Err(CursorError::ParseSvg(take_error(rsvg, error)));
+        }
+        Ok(Self { rsvg, handle })
+    }
+
+    fn render(&self, size: i32) -> Result<XCursorImage, CursorError> {
+        let surface = CairoImageSurface::new_image_surface(CAIRO_FORMAT_ARGB32, size, size)?;
+        let cctx = surface.create_context()?;
+        let viewport = RsvgRectangle {
+            x: 0.0,
+            y: 0.0,
+            width: size as f64,
+            height: size as f64,
+        };
+        let mut error = ptr::null_mut();
+        let res = unsafe {
+            (self.rsvg.rsvg_handle_render_document)(self.handle, cctx.raw(), &viewport, &mut error)
+        };
+        if res == 0 {
+            return Err(CursorError::RenderSvg(take_error(self.rsvg, error)));
+        }
+        drop(cctx);
+        surface.flush();
+        let data = surface.data()?;
+        let stride = surface.stride() as usize;
+        let row_len = size as usize * 4;
+        let mut pixels = Vec::with_capacity(row_len * size as usize);
+        for row in data.chunks(stride).take(size as usize) {
+            pixels.extend(row[..row_len].iter().map(|b| Cell::new(b.get())));
+        }
+        Ok(XCursorImage {
+            width: size,
+            height: size,
+            xhot: 0,
+            yhot: 0,
+            delay: 0,
+            pixels,
+        })
+    }
+}
+
+impl Drop for SvgHandle<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            (self.rsvg.g_object_unref)(self.handle.cast());
+        }
+    }
+}
+
+fn take_error(rsvg: &Rsvg, error: *mut GError) -> String {
+    if error.is_null() {
+        return "unknown error".to_string();
+    }
+    unsafe {
+        let msg = if (*error).message.is_null() {
+            "unknown error".to_string()
+        } else {
+            CStr::from_ptr((*error).message)
+                .to_string_lossy()
+                .into_owned()
+        };
+        (rsvg.g_error_free)(error);
+        msg
+    }
+}
@@ -0,0 +1,83 @@
+use crate::cursor::CursorError;
+use crate::cursor::svg::effective_size;
+use crate::cursor::svg::is_svg_cursor;
+use crate::cursor::svg::parse_metadata;
+use crate::scale::Scale;
+
+const METADATA: &str = r#"[
+    {
+        "filename": "wait-01.svg",
+        "hotspot_x": 12,
+        "hotspot_y": 6.5,
+        "nominal_size": 24,
+        "delay": 30
+    },
+    {
+        "filename": "wait-02.svg",
+        "hotspot_x": 12,
+        "hotspot_y": 6.5,
+        "nominal_size": 24,
+        "delay": 30
+    }
+]"#;
+
+#[test]
+fn metadata() {
+    let frames = parse_metadata(METADATA.as_bytes()).unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].filename, "wait-01.svg");
+    assert_eq!(frames[1].filename, "wait-02.svg");
+    assert_eq!(frames[0].delay, 30);
+}
+
+#[test]
+fn metadata_without_delay() {
+    let frames = parse_metadata(
+        br#"[{ "filename": "default.svg", "hotspot_x": 4, "hotspot_y": 4, "nominal_size": 32 }]"#,
+    )
+    .unwrap();
+    assert_eq!(frames[0].delay, 0);
+}
+
+#[test]
+fn invalid_metadata() {
+    assert!(matches!(
+        parse_metadata(b"[]"),
+        Err(CursorError::EmptySvgCursor),
+    ));
+    assert!(matches!(
+        parse_metadata(b"{}"),
+        Err(CursorError::SvgMetadata(_)),
+    ));
+    assert!(matches!(
+        parse_metadata(br#"[{ "filename": "default.svg" }]"#),
+        Err(CursorError::SvgMetadata(_)),
+    ));
+    assert!(matches!(
+        parse_metadata(
+            br#"[{ "filename": "a.svg", "hotspot_x": 0, "hotspot_y": 0, "nominal_size": 0 }]"#
+        ),
+        Err(CursorError::InvalidSvgNominalSize),
+    ));
+}
+
+#[test]
+fn hotspot() {
+    let frames = parse_metadata(METADATA.as_bytes()).unwrap();
+    assert_eq!(frames[0].hotspot(24), (12, 7));
+    assert_eq!(frames[0].hotspot(48), (24, 13));
+    assert_eq!(frames[0].hotspot(36), (18, 10));
+}
+
+#[test]
+fn sizes() {
+    assert_eq!(effective_size(24, Scale::from_int(1)), 24);
+    assert_eq!(effective_size(24, Scale::from_int(2)), 48);
+    assert_eq!(effective_size(24, Scale::from_f64(1.25)), 30);
+    assert_eq!(effective_size(0, Scale::from_int(1)), 1);
+}
+
+#[test]
+fn not_an_svg_cursor() {
+    assert!(!is_svg_cursor(b"/nonexistent/cursors_scalable/default"));
+}
@@ -1,3 +1,4 @@
+use crate::async_engine::SpawnedFuture;
 use crate::backend::HardwareCursorUpdate;
 use crate::control_center::CCI_INPUT;
 use crate::cursor::Cursor;
@@ -21,6 +22,7 @@ use crate::utils::rc_eq::rc_eq;
 use std::cell::Cell;
 use std::ops::Deref;
 use std::rc::Rc;
+use std::rc::Weak;
 
 linear_ids!(CursorUserGroupIds, CursorUserGroupId, u64);
 linear_ids!(CursorUserIds, CursorUserId, u64);
@@ -37,7 +39,13 @@ pub struct CursorUserGroup {
     users: CopyHashMap<CursorUserId, Rc<CursorUser>>,
     hardware_cursor: Cell<bool>,
     size: Cell<u32>,
+    theme: CloneCell<Option<Rc<String>>>,
     latest_output: CloneCell<Rc<OutputNode>>,
+    hide_while_typing: Cell<bool>,
+    hide_timeout_usec: Cell<u64>,
+    last_pointer_activity_usec: Cell<u64>,
+    hide_timer_running: Cell<bool>,
+    hide_timer: Cell<Option<SpawnedFuture<()>>>,
 }
 
 pub struct CursorUser {
@@ -78,7 +86,13 @@ impl CursorUserGroup {
             users: Default::default(),
             hardware_cursor: Cell::new(hardware_cursor),
             size: Cell::new(*DEFAULT_CURSOR_SIZE),
+            theme: Default::default(),
             latest_output: CloneCell::new(output),
+            hide_while_typing: Cell::new(false),
+            hide_timeout_usec: Cell::new(0),
+            last_pointer_activity_usec: Cell::new(0),
+            hide_timer_running: Cell::new(false),
+            hide_timer: Default::default(),
         });
         state.add_cursor_size(*DEFAULT_CURSOR_SIZE);
         state.cursor_user_groups.set(group.id, group.clone());
@@ -86,6 +100,7 @@ impl CursorUserGroup {
             state
                 .cursor_user_group_hardware_cursor
                 .set(Some(group.clone()));
+            state.update_cursor_env();
         }
         group
     }
@@ -121,10 +136,14 @@ impl CursorUserGroup {
     }
 
     pub fn detach(&self) {
+        self.stop_hide_timer();
         self.deactivate();
         self.latest_output
             .set(self.state.dummy_output.get().unwrap());
         self.state.remove_cursor_size(self.size.get());
+        if let Some(theme) = self.theme.take() {
+            self.state.remove_cursor_theme(&theme);
+        }
         self.state.cursor_user_groups.remove(&self.id);
         for user in self.users.lock().drain_values() {
             user.detach();
@@ -192,6 +211,7 @@ impl CursorUserGroup {
                 None => self.remove_hardware_cursor(),
                 Some(a) => a.update_hardware_cursor(),
             }
+            self.state.update_cursor_env();
         } else {
             self.remove_hardware_cursor();
             self.state.cursor_user_group_hardware_cursor.take();
@@ -209,6 +229,7 @@ impl CursorUserGroup {
             self.state.remove_cursor_size(old);
             self.state.add_cursor_size(size);
             self.reload_known_cursor();
+            self.state.update_cursor_env();
             self.state.trigger_cci(CCI_INPUT);
         }
     }
@@ -217,6 +238,77 @@ impl CursorUserGroup {
         self.size.get()
     }
 
+    pub fn set_cursor_theme(&self, theme: Option<Rc<String>>) {
+        if self.theme.get() == theme {
+            return;
+        }
+        if let Some(theme) = &theme {
+            self.state.add_cursor_theme(theme);
+        }
+        if let Some(old) = self.theme.set(theme) {
+            self.state.remove_cursor_theme(&old);
+        }
+        self.reload_known_cursor();
+        self.state.update_cursor_env();
+        self.state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn cursor_theme(&self) -> Option<Rc<String>> {
+        self.theme.get()
+    }
+
+    pub fn set_hide_while_typing(&self, hide: bool) {
+        if self.hide_while_typing.replace(hide) != hide {
+            self.state.trigger_cci(CCI_INPUT);
+        }
+    }
+
+    pub fn hide_while_typing(&self) -> bool {
+        self.hide_while_typing.get()
+    }
+
+    pub fn set_hide_timeout_usec(self: &Rc<Self>, usec: u64) {
+        if self.hide_timeout_usec.replace(usec) == usec {
+            return;
+        }
+        self.stop_hide_timer();
+        if self.active.id().is_some() {
+            self.pointer_activity();
+        }
+        self.state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn hide_timeout_usec(&self) -> u64 {
+        self.hide_timeout_usec.get()
+    }
+
+    pub fn key_pressed(&self) {
+        if self.hide_while_typing.get() && self.active.id().is_some() {
+            self.deactivate();
+        }
+    }
+
+    fn stop_hide_timer(&self) {
+        self.hide_timer.take();
+        self.hide_timer_running.set(false);
+    }
+
+    fn pointer_activity(self: &Rc<Self>) {
+        if self.hide_timeout_usec.get() == 0 {
+            return;
+        }
+        self.last_pointer_activity_usec.set(self.state.now_usec());
+        if self.hide_timer_running.replace(true) {
+            return;
+        }
+        let slf = Rc::downgrade(self);
+        let future = self
+            .state
+            .eng
+            .spawn("hide cursor", hide_cursor_after_timeout(slf));
+        self.hide_timer.set(Some(future));
+    }
+
     fn output_center(&self, output: &Rc<OutputNode>) -> (Fixed, Fixed) {
         let pos = output.node_state[LiveTL].pos.get();
         let x = Fixed::from_int((pos.x1() + pos.x2()) / 2);
@@ -289,6 +381,7 @@ impl CursorUser {
     }
 
     pub fn activate(self: &Rc<Self>) {
+        self.group.pointer_activity();
         if self.group.active.id() == Some(self.id) {
             return;
         }
@@ -310,7 +403,11 @@ impl CursorUser {
 
     pub fn set_known(&self, cursor: KnownCursor) {
         self.desired_known_cursor.set(Some(cursor));
-        let cursors = match self.group.state.cursors.get() {
+        let cursors = match self
+            .group
+            .state
+            .server_cursors(self.group.theme.get().as_ref())
+        {
             Some(c) => c,
             None => {
                 self.set_cursor2(None);
@@ -396,7 +493,7 @@ impl CursorUser {
         self.desired_known_cursor.set(None);
     }
 
-    fn is_active(&self) -> bool {
+    pub fn is_active(&self) -> bool {
         self.group.active.id() == Some(self.id)
     }
 
@@ -566,3 +663,27 @@ impl CursorUser {
         }
     }
 }
+
+async fn hide_cursor_after_timeout(group: Weak<CursorUserGroup>) {
+    loop {
+        let Some(slf) = group.upgrade() else {
+            return;
+        };
+        let deadline = slf.last_pointer_activity_usec.get() + slf.hide_timeout_usec.get();
+        let now = slf.state.now_usec();
+        if now >= deadline {
+            slf.hide_timer_running.set(false);
+            slf.deactivate();
+            return;
+        }
+        let wheel = slf.state.wheel.clone();
+        drop(slf);
+        if wheel
+            .timeout((deadline - now).div_ceil(1000))
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
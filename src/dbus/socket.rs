@@ -133,7 +133,6 @@ impl DbusSocket {
         }
     }
 
-    #[expect(unused)]
     pub fn get<T, F>(&self, destination: &str, path: &str, f: F)
     where
         T: Property,
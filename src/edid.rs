@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 use crate::utils::bitflags::BitflagsExt;
 use crate::utils::ptr_ext::PtrExt;
 use crate::utils::stack::Stack;
@@ -718,7 +721,7 @@ impl<'a> EdidParser<'a> {
     fn parse_detailed_timing_descriptor(&self, b: &[u8; 18]) -> DetailedTimingDescriptor {
         let l = b[17];
         DetailedTimingDescriptor {
-            pixel_clock_khz: u16::from_le_bytes([b[0], b[1]]) as u32 * 10_000,
+            pixel_clock_khz: u16::from_le_bytes([b[0], b[1]]) as u32 * 10,
             horizontal_addressable_pixels: u16::from_le_bytes([b[2], b[4] >> 4]),
             horizontal_blanking_pixels: u16::from_le_bytes([b[3], b[4] & 0b1111]),
             vertical_addressable_lines: u16::from_le_bytes([b[5], b[7] >> 4]),
@@ -1081,8 +1084,23 @@ impl<'a> EdidParser<'a> {
         }
     }
 
+    fn parse_cta_video_data_block(&mut self) -> Result<CtaDataBlock, EdidError> {
+        let mut vics = vec![];
+        while !self.is_empty() {
+            let svd = self.read_u8()?;
+            // SVDs 129..=192 denote native modes with VIC 1..=64.
+            let vic = match svd {
+                129..=192 => svd & 0x7f,
+                _ => svd,
+            };
+            vics.push(vic);
+        }
+        Ok(CtaDataBlock::Video(vics))
+    }
+
     fn parse_cta_data_block(&mut self, tag: u8) -> Result<CtaDataBlock, EdidError> {
         match tag {
+            0x2 => self.parse_cta_video_data_block(),
             0x3 => self.parse_cta_vendor_data_block(),
             0x7 => self.parse_cta_extended_data_block(),
             _ => Ok(CtaDataBlock::Unknown),
@@ -1105,7 +1123,22 @@ impl<'a> EdidParser<'a> {
                 }
             }
         }
-        Ok(EdidExtension::CtaV3(CtaExtensionV3 { data_blocks }))
+        let mut detailed_timing_descriptors = vec![];
+        if detailed_timing_descriptors_offset >= 4 {
+            self.pos = self.pos.max(detailed_timing_descriptors_offset);
+            // The last byte of the block is the checksum.
+            while self.data.len() - self.pos > 18 {
+                let b = self.read_n::<18>()?;
+                if b[0] == 0 && b[1] == 0 {
+                    break;
+                }
+                detailed_timing_descriptors.push(self.parse_detailed_timing_descriptor(b));
+            }
+        }
+        Ok(EdidExtension::CtaV3(CtaExtensionV3 {
+            data_blocks,
+            detailed_timing_descriptors,
+        }))
     }
 
     fn parse_cta_extension(&mut self) -> Result<EdidExtension, EdidError> {
@@ -1214,11 +1247,13 @@ pub enum EdidExtension {
 #[derive(Debug)]
 pub struct CtaExtensionV3 {
     pub data_blocks: Vec<CtaDataBlock>,
+    pub detailed_timing_descriptors: Vec<DetailedTimingDescriptor>,
 }
 
 #[derive(Debug)]
 pub enum CtaDataBlock {
     Unknown,
+    Video(Vec<u8>),
     VendorAmd(CtaAmdVendorDataBlock),
     Colorimetry(CtaColorimetryDataBlock),
     StaticHdrMetadata(CtaStaticHdrMetadataDataBlock),
@@ -1271,6 +1306,23 @@ pub enum EdidError {
     InvalidMagic(BString),
 }
 
+#[derive(Debug, Error)]
+pub enum EdidLoadError {
+    #[error("Could not read {0}")]
+    Read(String, #[source] std::io::Error),
+    #[error("Could not parse {0}")]
+    Parse(String, #[source] EdidError),
+}
+
+/// Reads an EDID blob from a file and checks that it can be parsed.
+pub fn load(path: &str) -> Result<Vec<u8>, EdidLoadError> {
+    let data = std::fs::read(path).map_err(|e| EdidLoadError::Read(path.to_string(), e))?;
+    if let Err(e) = parse(&data) {
+        return Err(EdidLoadError::Parse(path.to_string(), e));
+    }
+    Ok(data)
+}
+
 pub fn parse(data: &[u8]) -> Result<EdidFile, EdidError> {
     let mut parser = EdidParser {
         data,
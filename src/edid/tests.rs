@@ -0,0 +1,46 @@
+use crate::edid::CtaDataBlock;
+use crate::edid::EdidExtension;
+use crate::edid::parse;
+
+const DTD_1920X1080_60: [u8; 18] = [
+    0x02, 0x3a, 0x80, 0x18, 0x71, 0x38, 0x2d, 0x40, 0x58, 0x2c, 0x45, 0x00, 0x0f, 0x28, 0x21, 0x00,
+    0x00, 0x1e,
+];
+
+fn edid_with_cta_extension() -> Vec<u8> {
+    let mut edid = vec![0; 256];
+    edid[..8].copy_from_slice(&[0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]);
+    edid[18] = 1;
+    edid[19] = 4;
+    edid[126] = 1;
+    let ext = &mut edid[128..];
+    // CTA extension revision 3 whose detailed timings start at byte 7.
+    ext[..4].copy_from_slice(&[0x02, 0x03, 0x07, 0x00]);
+    // A video data block with the native VIC 16 and VIC 4.
+    ext[4..7].copy_from_slice(&[0x2 << 5 | 2, 0x80 | 16, 4]);
+    ext[7..25].copy_from_slice(&DTD_1920X1080_60);
+    edid
+}
+
+#[test]
+fn cta_extension_timings() {
+    let edid = parse(&edid_with_cta_extension()).unwrap();
+    let [EdidExtension::CtaV3(cta)] = &edid.extension_blocks[..] else {
+        panic!("{:?}", edid.extension_blocks);
+    };
+    let vics: Vec<_> = cta
+        .data_blocks
+        .iter()
+        .filter_map(|b| match b {
+            CtaDataBlock::Video(vics) => Some(vics.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(vics, [vec![16, 4]]);
+    let [dtd] = &cta.detailed_timing_descriptors[..] else {
+        panic!("{:?}", cta.detailed_timing_descriptors);
+    };
+    assert_eq!(dtd.pixel_clock_khz, 148500);
+    assert_eq!(dtd.horizontal_addressable_pixels, 1920);
+    assert_eq!(dtd.vertical_addressable_lines, 1080);
+}
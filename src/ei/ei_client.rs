@@ -114,6 +114,7 @@ impl EiClients {
             name: Default::default(),
             app_id,
             last_serial: Default::default(),
+            input_capture: Cell::new(false),
         });
         track!(data, data);
         let handshake = Rc::new(EiHandshake::new(&data));
@@ -210,6 +211,7 @@ pub struct EiClient {
     pub name: RefCell<Option<String>>,
     pub app_id: Option<String>,
     pub last_serial: NumCell<u64>,
+    pub input_capture: Cell<bool>,
 }
 
 impl EiClient {
@@ -130,6 +130,9 @@ impl EiHandshakeRequestHandler for EiHandshake {
             2 => EiContext::Sender,
             _ => return Err(EiHandshakeError::UnknownContextType(req.context_type)),
         };
+        if ty == EiContext::Sender && self.client.input_capture.get() {
+            return Err(EiHandshakeError::InputCaptureSender);
+        }
         self.client.context.set(ty);
         self.have_context_type.set(true);
         Ok(())
@@ -187,5 +190,7 @@ pub enum EiHandshakeError {
     NoContextType,
     #[error("Client did not set name")]
     NoName,
+    #[error("Input capture clients must use the receiver context")]
+    InputCaptureSender,
 }
 efrom!(EiHandshakeError, EiClientError);
@@ -398,6 +398,20 @@ impl EiSeat {
         Ok(())
     }
 
+    /// Destroys this object after the compositor seat has been removed.
+    pub fn seat_removed(&self) {
+        self.send_destroyed();
+        if let Some(device) = self.device.take()
+            && let Err(e) = device.destroy()
+        {
+            self.client.error(e);
+            return;
+        }
+        if let Err(e) = self.client.remove_obj(self) {
+            self.client.error(e);
+        }
+    }
+
     pub fn is_touch_input(&self) -> bool {
         self.capabilities.get().contains(EI_CAP_TOUCHSCREEN) && self.context() == EiContext::Sender
     }
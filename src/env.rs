@@ -233,6 +233,7 @@ declare_bool!(JAY_MCM_NVIDIA_USE_LUTS);
 declare_bool!(JAY_NO_CLIENT_PRIME);
 declare_bool!(JAY_NO_DESCRIPTOR_HEAP);
 declare_bool!(JAY_NO_REALTIME);
+declare_bool!(JAY_SENSOR_PROXY_SESSION_BUS);
 declare_bool!(JAY_VULKAN_VALIDATION);
 declare_str!(DBUS_SESSION_BUS_ADDRESS);
 declare_str!(DISPLAY);
@@ -58,9 +58,11 @@ use crate::ifs::xdg_wm_dialog_v1::XdgWmDialogV1Global;
 use crate::ifs::zwlr_foreign_toplevel_manager_v1::ZwlrForeignToplevelManagerV1Global;
 use crate::ifs::zwlr_gamma_control_manager_v1::ZwlrGammaControlManagerV1Global;
 use crate::ifs::zwlr_layer_shell_v1::ZwlrLayerShellV1Global;
+use crate::ifs::zwlr_output_power_manager_v1::ZwlrOutputPowerManagerV1Global;
 use crate::ifs::zwlr_screencopy_manager_v1::ZwlrScreencopyManagerV1Global;
 use crate::ifs::zwlr_virtual_pointer_manager_v1::ZwlrVirtualPointerManagerV1Global;
 use crate::ifs::zwp_idle_inhibit_manager_v1::ZwpIdleInhibitManagerV1Global;
+use crate::ifs::zwp_keyboard_shortcuts_inhibit_manager_v1::ZwpKeyboardShortcutsInhibitManagerV1Global;
 use crate::ifs::zwp_linux_dmabuf_v1::ZwpLinuxDmabufV1Global;
 use crate::ifs::zxdg_decoration_manager_v1::ZxdgDecorationManagerV1Global;
 use crate::ifs::zxdg_output_manager_v1::ZxdgOutputManagerV1Global;
@@ -242,6 +244,8 @@ singletons! {
     ZwlrVirtualPointerManagerV1,
     XdgSessionManagerV1,
     XdgToplevelIconManagerV1,
+    ZwpKeyboardShortcutsInhibitManagerV1,
+    ZwlrOutputPowerManagerV1,
 }
 
 pub struct Globals {
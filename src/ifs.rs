@@ -102,11 +102,14 @@ pub mod zwlr_foreign_toplevel_manager_v1;
 pub mod zwlr_gamma_control_manager_v1;
 pub mod zwlr_gamma_control_v1;
 pub mod zwlr_layer_shell_v1;
+pub mod zwlr_output_power_manager_v1;
+pub mod zwlr_output_power_v1;
 pub mod zwlr_screencopy_frame_v1;
 pub mod zwlr_screencopy_manager_v1;
 pub mod zwlr_virtual_pointer_manager_v1;
 pub mod zwlr_virtual_pointer_v1;
 pub mod zwp_idle_inhibit_manager_v1;
+pub mod zwp_keyboard_shortcuts_inhibit_manager_v1;
 pub mod zwp_linux_buffer_params_v1;
 pub mod zwp_linux_dmabuf_feedback_v1;
 pub mod zwp_linux_dmabuf_v1;
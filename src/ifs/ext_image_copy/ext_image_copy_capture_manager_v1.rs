@@ -1,3 +1,4 @@
+use crate::bar_elements::BE_SCREEN_SHARE;
 use crate::client::CAP_SCREENCOPY_MANAGER;
 use crate::client::Client;
 use crate::client::ClientCaps;
@@ -88,6 +89,7 @@ impl ExtImageCopyCaptureManagerV1RequestHandler for ExtImageCopyCaptureManagerV1
                     }
                 }
             }
+            self.client.state.bar_elements_changed(BE_SCREEN_SHARE);
             let Some(ctx) = self.client.state.render_ctx.get() else {
                 obj.send_stopped();
                 break 'send_constraints;
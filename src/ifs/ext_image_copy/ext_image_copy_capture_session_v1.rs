@@ -1,3 +1,4 @@
+use crate::bar_elements::BE_SCREEN_SHARE;
 use crate::client::Client;
 use crate::client::ClientError;
 use crate::cmm::cmm_description::ColorDescription;
@@ -183,6 +184,7 @@ impl ExtImageCopyCaptureSessionV1 {
                 }
             }
         }
+        self.client.state.bar_elements_changed(BE_SCREEN_SHARE);
         self.frame.take();
         self.shm_bridge.take();
         self.shm_staging.take();
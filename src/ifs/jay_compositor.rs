@@ -62,6 +62,7 @@ pub const CREATE_EI_SESSION_SINCE: Version = Version(5);
 pub const SCREENSHOT_SPLITUP_SINCE: Version = Version(6);
 pub const GET_TOPLEVEL_SINCE: Version = Version(12);
 pub const SCREENSHOT_DMABUF3_SINCE: Version = Version(40);
+pub const INPUT_CAPTURE_SINCE: Version = Version(46);
 
 pub struct JayCompositorGlobal {
     name: GlobalName,
@@ -95,7 +96,7 @@ global_base!(JayCompositorGlobal, JayCompositor, JayCompositorError);
 
 impl Global for JayCompositorGlobal {
     fn version(&self) -> u32 {
-        43
+        55
     }
 
     fn required_caps(&self) -> ClientCaps {
@@ -438,6 +439,7 @@ impl JayCompositorRequestHandler for JayCompositor {
             tracker: Default::default(),
             version: self.version,
             app_id: Default::default(),
+            input_capture: Default::default(),
         });
         track!(self.client, obj);
         self.client.add_client_obj(&obj)?;
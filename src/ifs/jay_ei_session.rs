@@ -1,15 +1,28 @@
 use crate::client::Client;
 use crate::client::ClientError;
 use crate::client::ClientId;
+use crate::fixed::Fixed;
+use crate::ifs::wl_seat::input_capture::InputCapture;
 use crate::leaks::Tracker;
 use crate::object::Object;
 use crate::object::Version;
+use crate::rect::Rect;
 use crate::wire::JayEiSessionId;
+use crate::wire::jay_ei_session::AddBarrier;
+use crate::wire::jay_ei_session::CaptureActivated;
+use crate::wire::jay_ei_session::CaptureDeactivated;
+use crate::wire::jay_ei_session::ClearBarriers;
 use crate::wire::jay_ei_session::Created;
 use crate::wire::jay_ei_session::Destroyed;
+use crate::wire::jay_ei_session::DisableCapture;
+use crate::wire::jay_ei_session::EnableCapture;
 use crate::wire::jay_ei_session::Failed;
 use crate::wire::jay_ei_session::JayEiSessionRequestHandler;
 use crate::wire::jay_ei_session::Release;
+use crate::wire::jay_ei_session::ReleaseCapture;
+use crate::wire::jay_ei_session::ReleaseCaptureAt;
+use crate::wire::jay_ei_session::Zone;
+use crate::wire::jay_ei_session::ZonesDone;
 use std::rc::Rc;
 use thiserror::Error;
 use uapi::OwnedFd;
@@ -20,6 +33,7 @@ pub struct JayEiSession {
     pub ei_client_id: Option<ClientId>,
     pub tracker: Tracker<Self>,
     pub version: Version,
+    pub input_capture: Option<Rc<InputCapture>>,
 }
 
 impl JayEiSession {
@@ -37,11 +51,47 @@ impl JayEiSession {
         });
     }
 
+    pub fn send_zone(&self, rect: Rect) {
+        self.client.event(Zone {
+            self_id: self.id,
+            x: rect.x1(),
+            y: rect.y1(),
+            width: rect.width(),
+            height: rect.height(),
+        });
+    }
+
+    pub fn send_zones_done(&self) {
+        self.client.event(ZonesDone { self_id: self.id });
+    }
+
+    pub fn send_capture_activated(&self, activation_id: u32, barrier_id: u32, x: Fixed, y: Fixed) {
+        self.client.event(CaptureActivated {
+            self_id: self.id,
+            activation_id,
+            barrier_id,
+            x,
+            y,
+        });
+    }
+
+    pub fn send_capture_deactivated(&self, activation_id: u32, x: Fixed, y: Fixed) {
+        self.client.event(CaptureDeactivated {
+            self_id: self.id,
+            activation_id,
+            x,
+            y,
+        });
+    }
+
     fn send_destroyed(&self) {
         self.client.event(Destroyed { self_id: self.id });
     }
 
     fn kill(&self, send_destroyed: bool) {
+        if let Some(capture) = &self.input_capture {
+            capture.kill();
+        }
         if let Some(id) = self.ei_client_id {
             self.client.state.ei_clients.shutdown(id);
         }
@@ -59,6 +109,52 @@ impl JayEiSessionRequestHandler for JayEiSession {
         self.client.remove_obj(self)?;
         Ok(())
     }
+
+    fn clear_barriers(&self, _req: ClearBarriers, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.input_capture()?.clear_barriers();
+        Ok(())
+    }
+
+    fn add_barrier(&self, req: AddBarrier, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let capture = self.input_capture()?;
+        if !capture.add_barrier(req.id, req.x1, req.y1, req.x2, req.y2) {
+            return Err(JayEiSessionError::DiagonalBarrier);
+        }
+        Ok(())
+    }
+
+    fn enable_capture(&self, _req: EnableCapture, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.input_capture()?.set_enabled(true);
+        Ok(())
+    }
+
+    fn disable_capture(&self, _req: DisableCapture, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.input_capture()?.set_enabled(false);
+        Ok(())
+    }
+
+    fn release_capture(&self, req: ReleaseCapture, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.input_capture()?.release(req.activation_id, None);
+        Ok(())
+    }
+
+    fn release_capture_at(
+        &self,
+        req: ReleaseCaptureAt,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.input_capture()?
+            .release(req.activation_id, Some((req.x, req.y)));
+        Ok(())
+    }
+}
+
+impl JayEiSession {
+    fn input_capture(&self) -> Result<&Rc<InputCapture>, JayEiSessionError> {
+        self.input_capture
+            .as_ref()
+            .ok_or(JayEiSessionError::NotInputCapture)
+    }
 }
 
 object_base! {
@@ -78,5 +174,9 @@ simple_add_obj!(JayEiSession);
 pub enum JayEiSessionError {
     #[error(transparent)]
     ClientError(Box<ClientError>),
+    #[error("The session is not an input capture session")]
+    NotInputCapture,
+    #[error("Pointer barriers must be horizontal or vertical")]
+    DiagonalBarrier,
 }
 efrom!(JayEiSessionError, ClientError);
@@ -2,6 +2,7 @@ use crate::client::Client;
 use crate::client::ClientError;
 use crate::ei::ei_client::EiClientError;
 use crate::ifs::jay_ei_session::JayEiSession;
+use crate::ifs::wl_seat::input_capture::InputCapture;
 use crate::leaks::Tracker;
 use crate::object::Object;
 use crate::object::Version;
@@ -12,6 +13,8 @@ use crate::wire::JayEiSessionBuilderId;
 use crate::wire::jay_ei_session_builder::Commit;
 use crate::wire::jay_ei_session_builder::JayEiSessionBuilderRequestHandler;
 use crate::wire::jay_ei_session_builder::SetAppId;
+use crate::wire::jay_ei_session_builder::SetInputCapture;
+use std::cell::Cell;
 use std::cell::RefCell;
 use std::rc::Rc;
 use thiserror::Error;
@@ -23,6 +26,7 @@ pub struct JayEiSessionBuilder {
     pub tracker: Tracker<Self>,
     pub version: Version,
     pub app_id: RefCell<Option<String>>,
+    pub input_capture: Cell<bool>,
 }
 
 impl JayEiSessionBuilderRequestHandler for JayEiSessionBuilder {
@@ -34,30 +38,41 @@ impl JayEiSessionBuilderRequestHandler for JayEiSessionBuilder {
         if app_id.is_none() {
             return Err(JayEiSessionBuilderError::NoAppId);
         }
+        let input_capture = self.input_capture.get();
         let res: Result<_, JayEiSessionBuilderError> = (move || {
             let (server, client) =
                 uapi::socketpair(c::AF_UNIX, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0)
                     .map_os_err(JayEiSessionBuilderError::SocketPair)?;
-            let ei_client_id = self
+            let ei_client = self
                 .client
                 .state
                 .ei_clients
                 .spawn2(&self.client.state, Rc::new(server), None, app_id)
-                .map_err(JayEiSessionBuilderError::SpawnClient)?
-                .id;
-            Ok((ei_client_id, Rc::new(client)))
+                .map_err(JayEiSessionBuilderError::SpawnClient)?;
+            ei_client.input_capture.set(input_capture);
+            Ok((ei_client.id, Rc::new(client)))
         })();
+        let ei_client_id = res.as_ref().ok().map(|v| v.0);
         let obj = Rc::new(JayEiSession {
             id: req.id,
             client: self.client.clone(),
-            ei_client_id: res.as_ref().ok().map(|v| v.0),
+            ei_client_id,
             tracker: Default::default(),
             version: self.version,
+            input_capture: ei_client_id
+                .filter(|_| input_capture)
+                .map(|id| InputCapture::new(&self.client.state, id)),
         });
         track!(self.client, obj);
         self.client.add_client_obj(&obj)?;
         match res {
-            Ok((_, fd)) => obj.send_created(&fd),
+            Ok((_, fd)) => {
+                obj.send_created(&fd);
+                if let Some(capture) = &obj.input_capture {
+                    capture.session.set(Some(obj.clone()));
+                    capture.send_zones();
+                }
+            }
             Err(e) => {
                 let e = format!("Could not spawn client: {}", ErrorFmt(e));
                 log::error!("{}", e);
@@ -71,6 +86,11 @@ impl JayEiSessionBuilderRequestHandler for JayEiSessionBuilder {
         *self.app_id.borrow_mut() = Some(req.app_id.to_string());
         Ok(())
     }
+
+    fn set_input_capture(&self, _req: SetInputCapture, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.input_capture.set(true);
+        Ok(())
+    }
 }
 
 object_base! {
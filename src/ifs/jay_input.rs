@@ -1,14 +1,18 @@
 use crate::backend::InputDeviceAccelProfile;
 use crate::backend::InputDeviceCapability;
 use crate::backend::InputDeviceClickMethod;
+use crate::backend::InputDeviceCustomAccel;
 use crate::backend::InputDeviceId;
 use crate::backend::InputDeviceScrollMethod;
+use crate::backend::InputDeviceSendEventsMode;
+use crate::backend::InputDeviceTapButtonMap;
 use crate::client::Client;
 use crate::client::ClientError;
 use crate::clientmem::ClientMem;
 use crate::clientmem::ClientMemError;
 use crate::evdev::input_event_codes::InputEventCode;
 use crate::ifs::jay_keymap_builder::MapKind;
+use crate::ifs::wl_seat::LayoutMemoryMode;
 use crate::ifs::wl_seat::WlSeatGlobal;
 use crate::kbvm::KbvmError;
 use crate::kbvm::KbvmMap;
@@ -16,7 +20,10 @@ use crate::leaks::Tracker;
 use crate::libinput::consts::AccelProfile;
 use crate::libinput::consts::ConfigClickMethod;
 use crate::libinput::consts::ConfigScrollMethod;
+use crate::libinput::consts::ConfigSendEventsMode;
+use crate::libinput::consts::ConfigTapButtonMap;
 use crate::libinput::consts::LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE;
+use crate::libinput::consts::LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM;
 use crate::libinput::consts::LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT;
 use crate::libinput::consts::LIBINPUT_CONFIG_CLICK_METHOD_BUTTON_AREAS;
 use crate::libinput::consts::LIBINPUT_CONFIG_CLICK_METHOD_CLICKFINGER;
@@ -52,6 +59,12 @@ const MIDDLE_BUTTON_EMULATION_SINCE: Version = Version(19);
 const SCROLL_METHOD_SINCE: Version = Version(34);
 const SCROLL_BUTTON_SINCE: Version = Version(35);
 const SCROLL_BUTTON_LOCK_SINCE: Version = Version(36);
+const MORE_LIBINPUT_SETTINGS_SINCE: Version = Version(44);
+const LAYOUT_MEMORY_SINCE: Version = Version(45);
+
+pub const LAYOUT_MEMORY_GLOBAL: u32 = 0;
+pub const LAYOUT_MEMORY_WINDOW: u32 = 1;
+pub const LAYOUT_MEMORY_APP_ID: u32 = 2;
 
 impl JayInput {
     pub fn new(id: JayInputId, client: &Rc<Client>, version: Version) -> Self {
@@ -88,6 +101,24 @@ impl JayInput {
             repeat_delay: data.get_rate().1,
             hardware_cursor: data.cursor_group().hardware_cursor() as _,
         });
+        if self.version >= LAYOUT_MEMORY_SINCE {
+            let group = data.keyboard_group();
+            let keymap = data.keymap();
+            self.client.event(KeyboardGroup {
+                self_id: self.id,
+                group: group.0,
+                name: keymap.group_name(group).unwrap_or_default(),
+            });
+            let mode = match data.layout_memory_mode() {
+                LayoutMemoryMode::Global => LAYOUT_MEMORY_GLOBAL,
+                LayoutMemoryMode::Window => LAYOUT_MEMORY_WINDOW,
+                LayoutMemoryMode::AppId => LAYOUT_MEMORY_APP_ID,
+            };
+            self.client.event(LayoutMemory {
+                self_id: self.id,
+                mode,
+            });
+        }
     }
 
     fn send_error(&self, error: &str) {
@@ -138,6 +169,7 @@ impl JayInput {
                 Some(p) => match p {
                     InputDeviceAccelProfile::Flat => LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT.0,
                     InputDeviceAccelProfile::Adaptive => LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE.0,
+                    InputDeviceAccelProfile::Custom => LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM.0,
                 },
             },
             accel_speed: dev.accel_speed().unwrap_or_default(),
@@ -237,6 +269,56 @@ impl JayInput {
                 }
             }
         }
+        if self.version >= MORE_LIBINPUT_SETTINGS_SINCE {
+            if let Some(v) = dev.dwt_enabled() {
+                self.client.event(Dwt {
+                    self_id: self.id,
+                    enabled: v,
+                });
+            }
+            if let Some(v) = dev.dwtp_enabled() {
+                self.client.event(Dwtp {
+                    self_id: self.id,
+                    enabled: v,
+                });
+            }
+            if let Some(v) = dev.tap_button_map() {
+                self.client.event(TapButtonMap {
+                    self_id: self.id,
+                    map: v.to_libinput().0,
+                });
+            }
+            let modes = dev
+                .send_events_modes()
+                .map(|m, v| v.then_some(m.to_libinput().0 as u32).unwrap_or_default())
+                .into_values()
+                .fold(0, |acc, m| acc | m);
+            if modes != 0 {
+                self.client.event(SendEventsModes {
+                    self_id: self.id,
+                    modes,
+                });
+            }
+            if let Some(v) = dev.send_events_mode() {
+                self.client.event(SendEventsMode {
+                    self_id: self.id,
+                    mode: v.to_libinput().0 as u32,
+                });
+            }
+            if let Some(v) = dev.rotation() {
+                self.client.event(Rotation {
+                    self_id: self.id,
+                    degrees_cw: v,
+                });
+            }
+            if let Some(v) = dev.custom_accel() {
+                self.client.event(CustomAccel {
+                    self_id: self.id,
+                    step: v.step,
+                    points: uapi::as_bytes(&v.points[..]),
+                });
+            }
+        }
     }
 
     fn device(&self, id: u32) -> Result<Rc<DeviceHandlerData>, JayInputError> {
@@ -411,6 +493,7 @@ impl JayInputRequestHandler for JayInput {
             let profile = match AccelProfile(req.profile) {
                 LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT => InputDeviceAccelProfile::Flat,
                 LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE => InputDeviceAccelProfile::Adaptive,
+                LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM => InputDeviceAccelProfile::Custom,
                 _ => return Err(JayInputError::UnknownAccelerationProfile(req.profile)),
             };
             dev.set_accel_profile(&self.state, profile);
@@ -784,6 +867,117 @@ impl JayInputRequestHandler for JayInput {
             Ok(())
         })
     }
+
+    fn set_dwt(&self, req: SetDwt, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            dev.set_dwt_enabled(&self.state, req.enabled);
+            Ok(())
+        })
+    }
+
+    fn set_dwtp(&self, req: SetDwtp, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            dev.set_dwtp_enabled(&self.state, req.enabled);
+            Ok(())
+        })
+    }
+
+    fn set_tap_button_map(&self, req: SetTapButtonMap, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            let Some(map) = InputDeviceTapButtonMap::from_libinput(ConfigTapButtonMap(req.map))
+            else {
+                return Err(JayInputError::UnknownTapButtonMap(req.map));
+            };
+            dev.set_tap_button_map(&self.state, map);
+            Ok(())
+        })
+    }
+
+    fn set_send_events_mode(
+        &self,
+        req: SetSendEventsMode,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            let Some(mode) =
+                InputDeviceSendEventsMode::from_libinput(ConfigSendEventsMode(req.mode as i32))
+            else {
+                return Err(JayInputError::UnknownSendEventsMode(req.mode));
+            };
+            dev.set_send_events_mode(&self.state, mode);
+            Ok(())
+        })
+    }
+
+    fn set_rotation(&self, req: SetRotation, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            if req.degrees_cw >= 360 {
+                return Err(JayInputError::InvalidRotation(req.degrees_cw));
+            }
+            dev.set_rotation(&self.state, req.degrees_cw);
+            Ok(())
+        })
+    }
+
+    fn set_custom_accel(&self, req: SetCustomAccel, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            if req.points.len() % size_of::<f64>() != 0 {
+                return Err(JayInputError::InvalidCustomAccelPoints);
+            }
+            let points = req
+                .points
+                .chunks_exact(size_of::<f64>())
+                .map(|c| f64::from_ne_bytes(c.try_into().unwrap()))
+                .collect();
+            let accel = InputDeviceCustomAccel {
+                step: req.step,
+                points,
+            };
+            dev.set_custom_accel(&self.state, Rc::new(accel));
+            Ok(())
+        })
+    }
+
+    fn set_layout_memory(&self, req: SetLayoutMemory, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let seat = self.seat(req.seat)?;
+            let mode = match req.mode {
+                LAYOUT_MEMORY_GLOBAL => LayoutMemoryMode::Global,
+                LAYOUT_MEMORY_WINDOW => LayoutMemoryMode::Window,
+                LAYOUT_MEMORY_APP_ID => LayoutMemoryMode::AppId,
+                _ => return Err(JayInputError::UnknownLayoutMemoryMode(req.mode)),
+            };
+            seat.set_layout_memory_mode(mode);
+            Ok(())
+        })
+    }
+
+    fn set_keyboard_group(
+        &self,
+        req: SetKeyboardGroup,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let seat = self.seat(req.seat)?;
+            seat.set_keyboard_group(req.group);
+            Ok(())
+        })
+    }
+
+    fn set_cursor_theme(&self, req: SetCursorTheme, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let seat = self.seat(req.seat)?;
+            let theme = (!req.theme.is_empty()).then(|| Rc::new(req.theme.to_string()));
+            seat.cursor_group().set_cursor_theme(theme);
+            Ok(())
+        })
+    }
 }
 
 object_base! {
@@ -823,5 +1017,15 @@ pub enum JayInputError {
     UnknownScrollMethod(i32),
     #[error("There is no input event code with id {0}")]
     UnknownInputEventCode(u32),
+    #[error("There is no tap button map with id {0}")]
+    UnknownTapButtonMap(i32),
+    #[error("There is no send-events mode with id {0}")]
+    UnknownSendEventsMode(u32),
+    #[error("Rotation must be less than 360 degrees but is {0}")]
+    InvalidRotation(u32),
+    #[error("The custom acceleration points are not a whole number of f64 values")]
+    InvalidCustomAccelPoints,
+    #[error("There is no layout memory mode with id {0}")]
+    UnknownLayoutMemoryMode(u32),
 }
 efrom!(JayInputError, ClientError);
@@ -4,6 +4,7 @@ use crate::backend::{self};
 use crate::client::Client;
 use crate::client::ClientError;
 use crate::compositor::MAX_EXTENTS;
+use crate::edid;
 use crate::format::named_formats;
 use crate::gfx_api::GfxApi;
 use crate::gfx_api::{self};
@@ -17,11 +18,14 @@ use crate::state::DrmDevData;
 use crate::state::OutputData;
 use crate::state::State;
 use crate::tree::OutputNode;
+use crate::tree::OutputNodeOrPersistent;
 use crate::tree::TearingMode;
 use crate::tree::Transform;
 use crate::tree::TreeTimeline::LiveTL;
 use crate::tree::VrrMode;
 use crate::utils::errorfmt::ErrorFmt;
+use crate::video::cvt;
+use crate::video::drm::DrmModeInfo;
 use crate::wire::JayRandrId;
 use crate::wire::jay_randr::*;
 use jay_config::video::ScalingFilter as ConfigScalingFilter;
@@ -51,6 +55,12 @@ const NATIVE_GAMUT_SINCE: Version = Version(23);
 const ARBITRARY_MODES_SINCE: Version = Version(29);
 const SCALING_FILTER_SINCE: Version = Version(37);
 const USE_PLANE_COLOR_PIPELINES_SINCE: Version = Version(38);
+const OUTPUT_PROFILES_SINCE: Version = Version(48);
+const DPMS_SINCE: Version = Version(49);
+const ICC_PROFILE_SINCE: Version = Version(50);
+const AUTO_SCALE_SINCE: Version = Version(53);
+const VNC_SINCE: Version = Version(54);
+const BACKLIGHT_SINCE: Version = Version(55);
 
 impl JayRandr {
     pub fn new(id: JayRandrId, client: &Rc<Client>, version: Version) -> Self {
@@ -268,6 +278,62 @@ impl JayRandr {
                 scaling_filter: global.persistent.scaling_filter.get().to_config().0,
             });
         }
+        if self.version >= DPMS_SINCE {
+            self.client.event(Dpms {
+                self_id: self.id,
+                on: !data.dpms_off.get(),
+            });
+        }
+        if self.version >= ICC_PROFILE_SINCE
+            && let Some(icc) = global.persistent.icc_profile.get()
+        {
+            self.client.event(IccProfile {
+                self_id: self.id,
+                path: &icc.path,
+            });
+        }
+        if self.version >= AUTO_SCALE_SINCE
+            && let Some(scale) = self.client.state.compute_auto_scale(
+                &global.mode.get(),
+                global.width_mm,
+                global.height_mm,
+            )
+        {
+            self.client.event(AutoScale {
+                self_id: self.id,
+                scale: scale.to_wl(),
+            });
+        }
+        if self.version >= VNC_SINCE
+            && let Some(vnc) = self.client.state.vnc_servers.get(&**data.name)
+        {
+            self.client.event(VncServer {
+                self_id: self.id,
+                address: &vnc.address().to_string(),
+            });
+        }
+        if self.version >= BACKLIGHT_SINCE
+            && let Ok(percent) = self.client.state.get_backlight(data)
+        {
+            self.client.event(Backlight {
+                self_id: self.id,
+                percent,
+            });
+        }
+    }
+
+    fn send_output_profiles(&self) {
+        let Some(config) = self.client.state.config.get() else {
+            return;
+        };
+        let (profiles, active) = config.output_profiles();
+        for name in &profiles {
+            self.client.event(OutputProfile {
+                self_id: self.id,
+                name,
+                active: active.as_ref() == Some(name),
+            });
+        }
     }
 
     fn send_error(&self, msg: &str) {
@@ -342,6 +408,12 @@ impl JayRandr {
         }
         None
     }
+
+    fn set_custom_mode(&self, c: &ConnectorData, mode: DrmModeInfo) {
+        if let Err(e) = c.set_custom_mode(&self.state, mode) {
+            self.send_error(&format!("Could not modify connector mode: {}", ErrorFmt(e)));
+        }
+    }
 }
 
 impl JayRandrRequestHandler for JayRandr {
@@ -361,6 +433,9 @@ impl JayRandrRequestHandler for JayRandr {
         for connector in state.connectors.lock().values() {
             self.send_connector(connector);
         }
+        if self.version >= OUTPUT_PROFILES_SINCE {
+            self.send_output_profiles();
+        }
         Ok(())
     }
 
@@ -678,6 +753,143 @@ impl JayRandrRequestHandler for JayRandr {
         dev.set_use_plane_color_pipelines(&self.state, req.enabled);
         Ok(())
     }
+
+    fn apply_output_profile(
+        &self,
+        req: ApplyOutputProfile<'_>,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let applied = match self.state.config.get() {
+            Some(config) => config.apply_output_profile(req.name),
+            None => false,
+        };
+        if !applied {
+            self.send_error(&format!("Unknown output profile `{}`", req.name));
+        }
+        Ok(())
+    }
+
+    fn set_icc_profile(&self, req: SetIccProfile<'_>, slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(c) = self.get_output_node(req.output) else {
+            return Ok(());
+        };
+        let c = OutputNodeOrPersistent::Node(c);
+        if req.path.is_empty() {
+            c.set_icc_profile(None);
+            return Ok(());
+        }
+        let slf = slf.clone();
+        c.load_icc_profile(&self.state, req.path, move |e| {
+            slf.send_error(&format!("Could not load the ICC profile: {}", ErrorFmt(e)));
+        });
+        Ok(())
+    }
+
+    fn set_dpms(&self, req: SetDpms<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(c) = self.get_connector(req.output) else {
+            return Ok(());
+        };
+        if let Err(e) = c.set_dpms(&self.state, req.on) {
+            self.send_error(&format!(
+                "Could not change the power state of the connector: {}",
+                ErrorFmt(e)
+            ));
+        }
+        Ok(())
+    }
+
+    fn set_cvt_mode(&self, req: SetCvtMode<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(c) = self.get_connector(req.output) else {
+            return Ok(());
+        };
+        let mode = match cvt::generate(
+            req.width.max(0) as u32,
+            req.height.max(0) as u32,
+            req.refresh_rate_millihz,
+            req.reduced_blanking,
+        ) {
+            Ok(m) => m,
+            Err(e) => {
+                self.send_error(&format!("Could not generate the mode: {}", ErrorFmt(e)));
+                return Ok(());
+            }
+        };
+        self.set_custom_mode(&c, mode);
+        Ok(())
+    }
+
+    fn set_modeline(&self, req: SetModeline<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(c) = self.get_connector(req.output) else {
+            return Ok(());
+        };
+        let mode = match cvt::parse_modeline(req.modeline) {
+            Ok(m) => m,
+            Err(e) => {
+                self.send_error(&format!("Could not parse the modeline: {}", ErrorFmt(e)));
+                return Ok(());
+            }
+        };
+        self.set_custom_mode(&c, mode);
+        Ok(())
+    }
+
+    fn set_edid_override(
+        &self,
+        req: SetEdidOverride<'_>,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let Some(c) = self.get_connector(req.output) else {
+            return Ok(());
+        };
+        if req.path.is_empty() {
+            c.connector.set_edid_override(None);
+            return Ok(());
+        }
+        match edid::load(req.path) {
+            Ok(e) => c.connector.set_edid_override(Some(Rc::new(e))),
+            Err(e) => self.send_error(&format!("Could not load the EDID: {}", ErrorFmt(e))),
+        }
+        Ok(())
+    }
+
+    fn get_edid(&self, req: GetEdid<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(c) = self.get_connector(req.output) else {
+            return Ok(());
+        };
+        match c.connector.edid() {
+            Some(edid) => self.client.event(Edid {
+                self_id: self.id,
+                data: &edid,
+            }),
+            None => self.send_error(&format!("Connector {} has no EDID", c.name)),
+        }
+        Ok(())
+    }
+
+    fn set_vnc_server(&self, req: SetVncServer<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(c) = self.get_connector(req.output) else {
+            return Ok(());
+        };
+        let address = Some(req.address).filter(|a| !a.is_empty());
+        if let Err(e) = self.client.state.set_vnc_server(&c.name, address) {
+            self.send_error(&format!("Could not set the VNC server: {}", ErrorFmt(e)));
+        }
+        Ok(())
+    }
+
+    fn set_backlight(&self, req: SetBacklight<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(c) = self.get_connector(req.output) else {
+            return Ok(());
+        };
+        if !req.value.is_finite() {
+            self.send_error(&format!("Brightness {} is invalid", req.value));
+            return Ok(());
+        }
+        if let Err(e) = self.client.state.set_backlight(&c, req.value, req.relative) {
+            self.send_error(&format!("Could not set the brightness: {}", ErrorFmt(e)));
+        }
+        Ok(())
+    }
 }
 
 object_base! {
@@ -2,6 +2,7 @@ use crate::allocator::AllocatorError;
 use crate::allocator::BO_USE_LINEAR;
 use crate::allocator::BO_USE_RENDERING;
 use crate::allocator::BufferObject;
+use crate::bar_elements::BE_SCREEN_SHARE;
 use crate::client::Client;
 use crate::client::ClientError;
 use crate::cmm::cmm_description::ColorDescription;
@@ -405,6 +406,7 @@ impl JayScreencast {
                     data.jay_screencasts.remove(&(self.client.id, self.id));
                 }
             }
+            self.client.state.bar_elements_changed(BE_SCREEN_SHARE);
         }
     }
 
@@ -633,6 +635,7 @@ impl JayScreencastRequestHandler for JayScreencast {
                         let data = t.tl_data();
                         data.jay_screencasts
                             .set((self.client.id, self.id), slf.clone());
+                        self.client.state.bar_elements_changed(BE_SCREEN_SHARE);
                         if data.visible[LiveTL].get() {
                             self.latch_listener.attach(&data.output(LiveTL).latch_event);
                         }
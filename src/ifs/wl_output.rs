@@ -7,6 +7,7 @@ use crate::backend::{self};
 use crate::client::Client;
 use crate::client::ClientError;
 use crate::client::ClientId;
+use crate::cmm::cmm_icc::IccProfile;
 use crate::cmm::cmm_primaries::Primaries;
 use crate::format::Format;
 use crate::format::XRGB8888;
@@ -31,6 +32,7 @@ use crate::utils::cell_ext::CellExt;
 use crate::utils::clonecell::CloneCell;
 use crate::utils::copyhashmap::CopyHashMap;
 use crate::utils::markers::JayHash;
+use crate::utils::numcell::NumCell;
 use crate::utils::rc_eq::rc_eq;
 use crate::wire::WlOutputId;
 use crate::wire::ZxdgOutputV1Id;
@@ -134,6 +136,8 @@ impl BlendSpace {
     }
 }
 
+pub const DEFAULT_TOUCH_EDGE_WIDTH: i32 = 20;
+
 #[derive(Derivative)]
 #[derivative(Default)]
 pub struct PersistentOutputState {
@@ -150,6 +154,10 @@ pub struct PersistentOutputState {
     #[derivative(Default(value = "Cell::new(BlendSpace::Srgb)"))]
     pub blend_space: Cell<BlendSpace>,
     pub use_native_gamut: Cell<bool>,
+    pub icc_profile: CloneCell<Option<Rc<IccProfile>>>,
+    pub icc_profile_serial: NumCell<u64>,
+    #[derivative(Default(value = "Cell::new(DEFAULT_TOUCH_EDGE_WIDTH)"))]
+    pub touch_edge_width: Cell<i32>,
 }
 
 #[derive(Eq, Debug)]
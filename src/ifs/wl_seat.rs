@@ -2,11 +2,17 @@ mod event_handling;
 pub mod ext_transient_seat_manager_v1;
 pub mod ext_transient_seat_v1;
 mod gesture_owner;
+pub mod input_capture;
 mod kb_owner;
+mod key_macro;
+mod keyboard_accessibility;
+mod mouse_keys;
 mod pointer_owner;
+mod pointer_shortcuts;
 pub mod tablet;
 pub mod text_input;
 mod touch_owner;
+mod which_key;
 pub mod wl_keyboard;
 pub mod wl_pointer;
 pub mod wl_touch;
@@ -22,12 +28,17 @@ pub mod zwp_virtual_keyboard_manager_v1;
 pub mod zwp_virtual_keyboard_v1;
 
 use crate::async_engine::SpawnedFuture;
+use crate::auto_rotation::transform_normalized;
 use crate::backend::ButtonState;
 use crate::backend::InputDeviceAccelProfile;
 use crate::backend::InputDeviceClickMethod;
+use crate::backend::InputDeviceCustomAccel;
 use crate::backend::InputDeviceScrollMethod;
+use crate::backend::InputDeviceSendEventsMode;
+use crate::backend::InputDeviceTapButtonMap;
 use crate::backend::Leds;
 use crate::backend::TransformMatrix;
+use crate::bar_elements::BE_INPUT_MODES;
 use crate::client::Client;
 use crate::client::ClientError;
 use crate::client::ClientId;
@@ -40,6 +51,7 @@ use crate::evdev::input_event_codes::InputEventCode;
 use crate::fixed::Fixed;
 use crate::globals::Global;
 use crate::globals::GlobalName;
+use crate::globals::RemovableWaylandGlobal;
 use crate::ifs::ext_idle_notification_v1::ExtIdleNotificationV1;
 use crate::ifs::ipc::DynDataSource;
 use crate::ifs::ipc::IpcError;
@@ -61,14 +73,22 @@ use crate::ifs::ipc::{self};
 use crate::ifs::wl_output::WlOutputGlobal;
 use crate::ifs::wl_seat::event_handling::FocusHistoryData;
 use crate::ifs::wl_seat::gesture_owner::GestureOwnerHolder;
+use crate::ifs::wl_seat::input_capture::InputCapture;
 use crate::ifs::wl_seat::kb_owner::KbOwnerHolder;
+use crate::ifs::wl_seat::key_macro::KeyMacros;
+use crate::ifs::wl_seat::keyboard_accessibility::KeyboardAccessibility;
+use crate::ifs::wl_seat::mouse_keys::MouseKeys;
 use crate::ifs::wl_seat::pointer_owner::PointerOwnerHolder;
+use crate::ifs::wl_seat::pointer_shortcuts::PointerShortcuts;
+use crate::ifs::wl_seat::tablet::TabletArea;
 use crate::ifs::wl_seat::tablet::TabletSeatData;
+use crate::ifs::wl_seat::tablet::TabletToolType;
 use crate::ifs::wl_seat::text_input::InputMethod;
 use crate::ifs::wl_seat::text_input::InputMethodKeyboardGrab;
 use crate::ifs::wl_seat::text_input::simple_im::SimpleIm;
 use crate::ifs::wl_seat::text_input::zwp_text_input_v3::ZwpTextInputV3;
 use crate::ifs::wl_seat::touch_owner::TouchOwnerHolder;
+use crate::ifs::wl_seat::which_key::WhichKey;
 use crate::ifs::wl_seat::wl_keyboard::REPEAT_INFO_SINCE;
 use crate::ifs::wl_seat::wl_keyboard::WlKeyboard;
 use crate::ifs::wl_seat::wl_keyboard::WlKeyboardError;
@@ -87,11 +107,13 @@ use crate::ifs::wl_surface::tray::TrayItemId;
 use crate::ifs::wl_surface::xdg_surface::xdg_popup::XdgPopup;
 use crate::ifs::wl_surface::xdg_surface::xdg_toplevel::ResizeEdges;
 use crate::ifs::wl_surface::zwlr_layer_surface_v1::LayerSurfaceLink;
+use crate::ifs::wl_surface::zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1;
 use crate::ifs::xdg_toplevel_drag_v1::XdgToplevelDragV1;
 use crate::kbvm::KbvmMap;
 use crate::kbvm::KbvmMapId;
 use crate::kbvm::KbvmState;
 use crate::kbvm::PhysicalKeyboardState;
+use crate::key_remap::KeyRemapper;
 use crate::keyboard::DynKeyboardState;
 use crate::keyboard::KeyboardState;
 use crate::keyboard::KeyboardStateId;
@@ -115,7 +137,9 @@ use crate::tree::NodeLocation;
 use crate::tree::NodesStack;
 use crate::tree::OutputNode;
 use crate::tree::StackedNode;
+use crate::tree::ToplevelIdentifier;
 use crate::tree::ToplevelNode;
+use crate::tree::Transform;
 use crate::tree::TreeTimeline::LiveTL;
 use crate::tree::WorkspaceChangeReason;
 use crate::tree::WorkspaceNode;
@@ -129,6 +153,7 @@ use crate::utils::bhash::BHashMap;
 use crate::utils::bindings::PerClientBindings;
 use crate::utils::clonecell::CloneCell;
 use crate::utils::copyhashmap::CopyHashMap;
+use crate::utils::errorfmt::ErrorFmt;
 use crate::utils::event_listener::EventListener;
 use crate::utils::event_listener::EventSource;
 use crate::utils::linkedlist::LinkedList;
@@ -156,8 +181,17 @@ use CursorPositionType::Warp;
 pub use event_handling::NodeSeatState;
 use hashbrown::hash_map::Entry;
 use jay_config::input::FallbackOutputMode as ConfigFallbackOutputMode;
+use jay_config::input::LayoutMemoryMode as ConfigLayoutMemoryMode;
+use jay_config::input::gesture::Gesture;
+use jay_config::input::gesture::TouchGesture;
+use jay_config::input::tablet::EraserMode;
+use jay_config::input::tablet::PressureCurve;
+use jay_config::keyboard::ModifiedKeySym;
+use jay_config::keyboard::mods::LOGO;
+use jay_config::keyboard::mods::SHIFT;
 use jay_config::keyboard::syms::KeySym;
 use jay_config::keyboard::syms::SYM_Escape;
+use kbvm::GroupIndex;
 use kbvm::Keycode;
 use linearize::Linearize;
 pub use pointer_owner::ToplevelSelector;
@@ -165,10 +199,12 @@ pub use pointer_owner::WorkspaceSelector;
 use run_on_drop::on_drop;
 use smallvec::SmallVec;
 use std::cell::Cell;
+use std::cell::Ref;
 use std::cell::RefCell;
 use std::mem;
 use std::ops::Deref;
 use std::ops::DerefMut;
+use std::ptr;
 use std::rc::Rc;
 use std::rc::Weak;
 use thiserror::Error;
@@ -188,6 +224,8 @@ pub const BTN_EXTRA: u32 = 0x114;
 
 pub const SEAT_NAME_SINCE: Version = Version(2);
 
+const DEFAULT_GESTURE_SWIPE_DISTANCE: f64 = 100.0;
+
 pub const PX_PER_SCROLL: f64 = 15.0;
 
 #[derive(Clone)]
@@ -268,6 +306,11 @@ pub struct WlSeatGlobal {
     touch_owner: TouchOwnerHolder,
     dropped_dnd: RefCell<Option<DroppedDnd>>,
     shortcuts: RefCell<BHashMap<u32, SmallMapMut<u32, Shortcut, 2>>>,
+    pointer_shortcuts: PointerShortcuts,
+    gesture_bindings: CopyHashMap<Gesture, bool>,
+    gesture_swipe_distance: Cell<f64>,
+    touch_gesture_bindings: CopyHashMap<TouchGesture, ()>,
+    touch_long_press_drag: Cell<bool>,
     queue_link: RefCell<Option<LinkedNode<Rc<Self>>>>,
     tree_changed_handler: Cell<Option<SpawnedFuture<()>>>,
     changes: NumCell<u32>,
@@ -281,6 +324,8 @@ pub struct WlSeatGlobal {
     forward: Cell<bool>,
     focus_follows_mouse: Cell<bool>,
     fallback_output_mode: Cell<FallbackOutputMode>,
+    layout_memory_mode: Cell<LayoutMemoryMode>,
+    app_id_kb_groups: CopyHashMap<String, u32>,
     swipe_bindings: PerClientBindings<ZwpPointerGestureSwipeV1>,
     pinch_bindings: PerClientBindings<ZwpPointerGesturePinchV1>,
     hold_bindings: PerClientBindings<ZwpPointerGestureHoldV1>,
@@ -290,6 +335,9 @@ pub struct WlSeatGlobal {
     keyboard_node_serial: Cell<u64>,
     tray_popups: CopyHashMap<(TrayItemId, XdgPopupId), Rc<dyn DynTrayItem>>,
     revert_key: Cell<KeySym>,
+    shortcuts_inhibitor: CloneCell<Option<Rc<ZwpKeyboardShortcutsInhibitorV1>>>,
+    shortcuts_inhibit_escape: Cell<ModifiedKeySym>,
+    input_capture_release: Cell<ModifiedKeySym>,
     last_focus_location: Cell<Option<NodeLocation>>,
     focus_history: LinkedList<FocusHistoryData>,
     focus_history_rotate: NumCell<u64>,
@@ -303,6 +351,13 @@ pub struct WlSeatGlobal {
     simple_im_enabled: Cell<bool>,
     warp_mouse_to_focus_scheduled: Cell<bool>,
     mouse_follows_focus: Cell<bool>,
+    input_modes: RefCell<Vec<String>>,
+    bar_kb_state: Cell<(GroupIndex, Leds)>,
+    keyboard_accessibility: KeyboardAccessibility,
+    mouse_keys: MouseKeys,
+    which_key: WhichKey,
+    key_macros: KeyMacros,
+    input_capture: CloneCell<Option<Rc<InputCapture>>>,
 }
 
 impl PartialEq for WlSeatGlobal {
@@ -359,6 +414,37 @@ impl Into<ConfigFallbackOutputMode> for FallbackOutputMode {
     }
 }
 
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Linearize)]
+pub enum LayoutMemoryMode {
+    Global,
+    Window,
+    AppId,
+}
+
+impl StaticText for LayoutMemoryMode {
+    fn text(&self) -> &'static str {
+        match self {
+            LayoutMemoryMode::Global => "Global",
+            LayoutMemoryMode::Window => "Window",
+            LayoutMemoryMode::AppId => "App ID",
+        }
+    }
+}
+
+impl TryFrom<ConfigLayoutMemoryMode> for LayoutMemoryMode {
+    type Error = ();
+
+    fn try_from(value: ConfigLayoutMemoryMode) -> Result<Self, Self::Error> {
+        let v = match value {
+            ConfigLayoutMemoryMode::Global => LayoutMemoryMode::Global,
+            ConfigLayoutMemoryMode::Window => LayoutMemoryMode::Window,
+            ConfigLayoutMemoryMode::AppId => LayoutMemoryMode::AppId,
+            _ => return Err(()),
+        };
+        Ok(v)
+    }
+}
+
 const CHANGE_CURSOR_MOVED: u32 = 1 << 0;
 const CHANGE_TREE: u32 = 1 << 1;
 
@@ -418,6 +504,11 @@ impl WlSeatGlobal {
             touch_owner: Default::default(),
             dropped_dnd: RefCell::new(None),
             shortcuts: Default::default(),
+            pointer_shortcuts: Default::default(),
+            gesture_bindings: Default::default(),
+            gesture_swipe_distance: Cell::new(DEFAULT_GESTURE_SWIPE_DISTANCE),
+            touch_gesture_bindings: Default::default(),
+            touch_long_press_drag: Cell::new(false),
             queue_link: Default::default(),
             tree_changed_handler: Cell::new(None),
             changes: NumCell::new(CHANGE_CURSOR_MOVED | CHANGE_TREE),
@@ -432,6 +523,8 @@ impl WlSeatGlobal {
             forward: Cell::new(false),
             focus_follows_mouse: Cell::new(true),
             fallback_output_mode: Cell::new(FallbackOutputMode::Cursor),
+            layout_memory_mode: Cell::new(LayoutMemoryMode::Global),
+            app_id_kb_groups: Default::default(),
             swipe_bindings: Default::default(),
             pinch_bindings: Default::default(),
             hold_bindings: Default::default(),
@@ -440,6 +533,9 @@ impl WlSeatGlobal {
             ui_drag_highlight: Default::default(),
             tray_popups: Default::default(),
             revert_key: Cell::new(SYM_Escape),
+            shortcuts_inhibitor: Default::default(),
+            shortcuts_inhibit_escape: Cell::new(LOGO | SYM_Escape),
+            input_capture_release: Cell::new(LOGO | SHIFT | SYM_Escape),
             last_focus_location: Default::default(),
             focus_history: Default::default(),
             focus_history_rotate: Default::default(),
@@ -453,6 +549,13 @@ impl WlSeatGlobal {
             simple_im_enabled: Cell::new(true),
             warp_mouse_to_focus_scheduled: Cell::new(false),
             mouse_follows_focus: Cell::new(false),
+            input_modes: Default::default(),
+            bar_kb_state: Default::default(),
+            keyboard_accessibility: Default::default(),
+            mouse_keys: Default::default(),
+            which_key: Default::default(),
+            key_macros: Default::default(),
+            input_capture: Default::default(),
         });
         slf.pointer_cursor.set_owner(slf.clone());
         slf.modifiers_listener
@@ -1532,8 +1635,35 @@ impl WlSeatGlobal {
         self.tablet_clear();
         self.ei_seats.clear();
         self.marks.clear();
+        self.shortcuts_inhibitor.take();
         self.key_repeater.take();
         self.repeat_key_state.take();
+        self.mouse_keys.clear();
+        self.which_key.clear();
+        self.key_macros.clear();
+        self.input_capture.take();
+    }
+
+    /// Removes the seat global and detaches all input devices from the seat.
+    pub fn destroy(self: &Rc<Self>) {
+        if let Err(e) = self.state.remove_global(self) {
+            log::error!(
+                "Could not remove the global of seat {}: {}",
+                self.seat_name,
+                ErrorFmt(e),
+            );
+        }
+        for dev in self.state.input_device_handlers.borrow().values() {
+            if let Some(seat) = dev.data.seat.get()
+                && seat.id == self.id
+            {
+                dev.data.set_seat(&self.state, None);
+            }
+        }
+        for ei_seat in self.ei_seats.clear().into_values() {
+            ei_seat.seat_removed();
+        }
+        self.clear();
     }
 
     pub fn id(&self) -> SeatId {
@@ -1638,6 +1768,15 @@ impl WlSeatGlobal {
         self.mouse_follows_focus.get()
     }
 
+    pub fn set_input_mode_stack(&self, modes: Vec<String>) {
+        *self.input_modes.borrow_mut() = modes;
+        self.state.bar_elements_changed(BE_INPUT_MODES);
+    }
+
+    pub fn input_modes(&self) -> Ref<'_, Vec<String>> {
+        self.input_modes.borrow()
+    }
+
     pub fn set_fallback_output_mode(&self, fallback_output_mode: FallbackOutputMode) {
         self.fallback_output_mode.set(fallback_output_mode);
         self.state.trigger_cci(CCI_INPUT);
@@ -1647,6 +1786,15 @@ impl WlSeatGlobal {
         self.fallback_output_mode.get()
     }
 
+    pub fn set_layout_memory_mode(&self, mode: LayoutMemoryMode) {
+        self.layout_memory_mode.set(mode);
+        self.state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn layout_memory_mode(&self) -> LayoutMemoryMode {
+        self.layout_memory_mode.get()
+    }
+
     pub fn set_window_management_enabled(self: &Rc<Self>, enabled: bool) {
         self.pointer_owner
             .set_window_management_enabled(self, enabled);
@@ -1659,6 +1807,7 @@ impl WlSeatGlobal {
 
     pub fn remove_ei_seat(self: &Rc<Self>, ei: &EiSeat) {
         self.ei_seats.remove(&(ei.client.id, ei.id));
+        self.input_capture_removed_ei_seat(ei);
         self.destroy_physical_keyboard(ei.keyboard_id);
         self.update_capabilities();
     }
@@ -1743,6 +1892,7 @@ impl WlSeatGlobal {
         let Some(kb) = self.kb_devices.remove(&id) else {
             return;
         };
+        self.keyboard_accessibility.remove_keyboard(&kb);
         kb.phy_state.destroy(self.state.now_usec(), self);
     }
 
@@ -1768,6 +1918,50 @@ impl WlSeatGlobal {
     pub fn pointer_revert_key(&self) -> KeySym {
         self.revert_key.get()
     }
+
+    pub fn set_shortcuts_inhibit_escape(&self, key: ModifiedKeySym) {
+        self.shortcuts_inhibit_escape.set(key);
+    }
+
+    pub fn set_input_capture_release(&self, key: ModifiedKeySym) {
+        self.input_capture_release.set(key);
+    }
+
+    pub fn update_shortcuts_inhibitor(self: &Rc<Self>) {
+        let new = self
+            .keyboard_node
+            .get()
+            .node_into_surface()
+            .and_then(|s| s.shortcuts_inhibitors.get(&self.id));
+        if let Some(old) = self.shortcuts_inhibitor.get() {
+            if let Some(new) = &new
+                && rc_eq(&old, new)
+            {
+                return;
+            }
+            old.deactivate();
+            self.shortcuts_inhibitor.take();
+        }
+        if let Some(new) = new
+            && new.request_activation()
+        {
+            self.shortcuts_inhibitor.set(Some(new));
+        }
+    }
+
+    pub fn remove_shortcuts_inhibitor(&self, inhibitor: &ZwpKeyboardShortcutsInhibitorV1) {
+        if let Some(active) = self.shortcuts_inhibitor.get()
+            && ptr::eq(&*active, inhibitor)
+        {
+            self.shortcuts_inhibitor.take();
+        }
+    }
+
+    fn escape_shortcuts_inhibitor(&self) {
+        if let Some(inhibitor) = self.shortcuts_inhibitor.take() {
+            inhibitor.deactivate();
+        }
+    }
 }
 
 impl CursorUserOwner for WlSeatGlobal {
@@ -1799,6 +1993,12 @@ impl Global for WlSeatGlobal {
 
 dedicated_add_global!(WlSeatGlobal, seats);
 
+impl RemovableWaylandGlobal for WlSeatGlobal {
+    fn create_replacement(self: Rc<Self>) -> Rc<dyn Global> {
+        self
+    }
+}
+
 pub struct WlSeat {
     pub global: Rc<WlSeatGlobal>,
     pub id: WlSeatId,
@@ -2008,6 +2208,9 @@ impl DeviceHandlerData {
     }
 
     fn destroy_physical_keyboard_state(&self) {
+        if let Some(remap) = self.key_remap.get() {
+            remap.reset();
+        }
         self.mods_listener.detach();
         if let Some(seat) = self.seat.get() {
             seat.destroy_physical_keyboard(self.keyboard_id);
@@ -2033,6 +2236,13 @@ impl DeviceHandlerData {
         state.trigger_cci(CCI_INPUT);
     }
 
+    pub fn set_key_remap(&self, state: &State, remap: Option<Rc<KeyRemapper>>) {
+        self.destroy_physical_keyboard_state();
+        self.key_remap.set(remap);
+        self.attach_event_listeners();
+        state.trigger_cci(CCI_INPUT);
+    }
+
     pub fn set_output(&self, state: &State, output: Option<&WlOutputGlobal>) {
         match output {
             None => {
@@ -2047,6 +2257,27 @@ impl DeviceHandlerData {
         state.trigger_cci(CCI_INPUT);
     }
 
+    /// Returns the rotation that auto-rotation has applied to the output this device is
+    /// mapped to. The configured transform of the output is not included since it is
+    /// handled by the calibration matrix of the device.
+    pub fn get_input_transform(&self) -> Transform {
+        if let Some(output) = self.output.get()
+            && let Some(output) = output.node()
+        {
+            return output.sensor_rotation.get();
+        }
+        Transform::None
+    }
+
+    pub fn map_normalized(&self, x: Fixed, y: Fixed) -> (Fixed, Fixed) {
+        let transform = self.get_input_transform();
+        if transform == Transform::None {
+            return (x, y);
+        }
+        let (x, y) = transform_normalized(transform, x.to_f64(), y.to_f64());
+        (Fixed::from_f64(x), Fixed::from_f64(y))
+    }
+
     pub fn get_rect(&self, state: &State) -> Rect {
         if let Some(output) = self.output.get()
             && let Some(output) = output.node()
@@ -2056,6 +2287,51 @@ impl DeviceHandlerData {
         state.root.node_state[LiveTL].extents.get()
     }
 
+    pub fn set_tablet_window(&self, state: &State, window: Option<ToplevelIdentifier>) {
+        self.tablet_config.window.set(window);
+        state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn set_tablet_region(&self, state: &State, region: Option<Rect>) {
+        self.tablet_config.region.set(region);
+        state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn set_tablet_area(&self, state: &State, area: TabletArea) {
+        self.tablet_config.area.set(area);
+        state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn set_tablet_keep_aspect_ratio(&self, state: &State, v: bool) {
+        self.tablet_config.keep_aspect_ratio.set(v);
+        state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn set_tablet_pressure_curve(
+        &self,
+        state: &State,
+        tool: TabletToolType,
+        curve: PressureCurve,
+    ) {
+        if curve == PressureCurve::default() {
+            self.tablet_config
+                .pressure_curves
+                .borrow_mut()
+                .remove(&tool);
+        } else {
+            self.tablet_config
+                .pressure_curves
+                .borrow_mut()
+                .insert(tool, curve);
+        }
+        state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn set_tablet_eraser_mode(&self, state: &State, mode: EraserMode) {
+        self.tablet_config.eraser_mode.set(mode);
+        state.trigger_cci(CCI_INPUT);
+    }
+
     pub fn set_accel_profile(&self, state: &State, v: InputDeviceAccelProfile) {
         self.device.set_accel_profile(v);
         state.trigger_cci(CCI_INPUT);
@@ -2135,6 +2411,36 @@ impl DeviceHandlerData {
         self.device.set_scroll_button_lock(v);
         state.trigger_cci(CCI_INPUT);
     }
+
+    pub fn set_dwt_enabled(&self, state: &State, v: bool) {
+        self.device.set_dwt_enabled(v);
+        state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn set_dwtp_enabled(&self, state: &State, v: bool) {
+        self.device.set_dwtp_enabled(v);
+        state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn set_tap_button_map(&self, state: &State, v: InputDeviceTapButtonMap) {
+        self.device.set_tap_button_map(v);
+        state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn set_send_events_mode(&self, state: &State, v: InputDeviceSendEventsMode) {
+        self.device.set_send_events_mode(v);
+        state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn set_rotation(&self, state: &State, v: u32) {
+        self.device.set_rotation(v);
+        state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn set_custom_accel(&self, state: &State, v: Rc<InputDeviceCustomAccel>) {
+        self.device.set_custom_accel(v);
+        state.trigger_cci(CCI_INPUT);
+    }
 }
 
 impl LedsListener for DeviceHandlerData {
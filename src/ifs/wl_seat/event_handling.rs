@@ -6,6 +6,8 @@ use crate::backend::InputDeviceId;
 use crate::backend::InputEvent;
 use crate::backend::KeyState;
 use crate::backend::ScrollAxis;
+use crate::bar_elements::BE_KEYBOARD_LAYOUT;
+use crate::bar_elements::BE_LOCK_INDICATORS;
 use crate::client::ClientId;
 use crate::config::InvokedShortcut;
 use crate::ei::ei_ifs::ei_seat::EiSeat;
@@ -21,6 +23,7 @@ use crate::ifs::wl_seat::CHANGE_CURSOR_MOVED;
 use crate::ifs::wl_seat::CHANGE_TREE;
 use crate::ifs::wl_seat::CursorPositionType;
 use crate::ifs::wl_seat::Dnd;
+use crate::ifs::wl_seat::LayoutMemoryMode;
 use crate::ifs::wl_seat::MarkMode;
 use crate::ifs::wl_seat::SeatId;
 use crate::ifs::wl_seat::Shortcut;
@@ -74,12 +77,19 @@ use crate::wire::WlDataOfferId;
 use CursorPositionType::Motion;
 use hashbrown::hash_map::Entry;
 use isnt::std_1::primitive::IsntSliceExt;
+use jay_config::input::ModifiedPointerTrigger;
 use jay_config::input::SwitchEvent;
+use jay_config::input::gesture::Gesture;
+use jay_config::input::gesture::GestureKind;
+use jay_config::input::gesture::TouchGesture;
+use jay_config::input::gesture::TouchGestureKind;
 use jay_config::keyboard::mods::CAPS;
 use jay_config::keyboard::mods::Modifiers;
 use jay_config::keyboard::mods::NUM;
 use jay_config::keyboard::mods::RELEASE;
+use jay_config::keyboard::mods::SHIFT;
 use jay_config::keyboard::syms::KeySym;
+use kbvm::GroupIndex;
 use kbvm::Keycode;
 use kbvm::ModifierMask;
 use kbvm::evdev;
@@ -420,16 +430,18 @@ impl WlSeatGlobal {
             InputEvent::TouchCancel { .. } => {}
             InputEvent::TouchFrame { .. } => {}
         }
+        if self.input_capture_event(dev, &event) {
+            return;
+        }
         match event {
             InputEvent::Key {
                 time_usec,
                 key,
                 state,
-            } => {
-                self.get_physical_keyboard(dev.keyboard_id, dev.keymap.get().as_ref())
-                    .phy_state
-                    .update(time_usec, self, key, state);
-            }
+            } => match dev.key_remap.get() {
+                Some(remap) => remap.handle_key(time_usec, key, state),
+                None => self.physical_key_event(dev, time_usec, key, state),
+            },
             InputEvent::ConnectorPosition {
                 time_usec,
                 connector,
@@ -528,13 +540,13 @@ impl WlSeatGlobal {
                 self.switch_event(dev.device.id(), time_usec, event)
             }
             InputEvent::TabletToolAdded { time_usec, init } => {
-                self.tablet_handle_new_tool(time_usec, &init)
+                self.tablet_handle_new_tool(dev, time_usec, &init)
             }
             InputEvent::TabletToolChanged {
                 time_usec,
                 id,
-                changes: change,
-            } => self.tablet_event_tool_changes(id, time_usec, dev.get_rect(&self.state), &change),
+                mut changes,
+            } => self.tablet_event_tool_changes(dev, id, time_usec, &mut changes),
             InputEvent::TabletToolButton {
                 time_usec,
                 id,
@@ -581,14 +593,20 @@ impl WlSeatGlobal {
                 id,
                 x_normed,
                 y_normed,
-            } => self.touch_down(time_usec, id, dev.get_rect(&self.state), x_normed, y_normed),
+            } => {
+                let (x_normed, y_normed) = dev.map_normalized(x_normed, y_normed);
+                self.touch_down(time_usec, id, dev.get_rect(&self.state), x_normed, y_normed)
+            }
             InputEvent::TouchUp { time_usec, id } => self.touch_up(time_usec, id),
             InputEvent::TouchMotion {
                 time_usec,
                 id,
                 x_normed,
                 y_normed,
-            } => self.touch_motion(time_usec, id, dev.get_rect(&self.state), x_normed, y_normed),
+            } => {
+                let (x_normed, y_normed) = dev.map_normalized(x_normed, y_normed);
+                self.touch_motion(time_usec, id, dev.get_rect(&self.state), x_normed, y_normed)
+            }
             InputEvent::TouchCancel { time_usec, id } => self.touch_cancel(time_usec, id),
             InputEvent::TouchFrame { time_usec } => self.touch_frame(time_usec),
         }
@@ -677,6 +695,9 @@ impl WlSeatGlobal {
         };
         let (mut x, mut y) = self.pointer_cursor.position();
         if !locked {
+            if self.cross_input_capture_barriers((x, y), (x + dx, y + dy)) {
+                return;
+            }
             x += dx;
             y += dy;
             if let Some(c) = &constraint {
@@ -725,6 +746,10 @@ impl WlSeatGlobal {
         self.state.for_each_seat_tester(|t| {
             t.send_button(self.id, time_usec, button, state);
         });
+        if self.handle_pointer_button_shortcut(button, state) {
+            return;
+        }
+        self.record_macro_button(time_usec, button, state);
         self.pointer_owner.button(self, time_usec, button, state);
     }
 
@@ -745,6 +770,16 @@ impl WlSeatGlobal {
     }
 
     pub fn axis_frame(self: &Rc<Self>, px_per_scroll_wheel: f64, time_usec: u64) {
+        let consumed = self.handle_pointer_scroll_shortcuts();
+        for axis in ScrollAxis::variants() {
+            if consumed[axis as usize] {
+                self.pointer_owner.discard_pending_scroll_axis(axis);
+            }
+        }
+        if consumed.contains(&true) && !self.pointer_owner.has_pending_scroll() {
+            self.pointer_owner.discard_pending_scroll();
+            return;
+        }
         self.pointer_owner
             .frame(px_per_scroll_wheel, self, time_usec);
     }
@@ -924,6 +959,20 @@ impl WlSeatGlobal {
         self.touch_owner.frame(self);
     }
 
+    pub fn physical_key_event(
+        self: &Rc<Self>,
+        dev: &DeviceHandlerData,
+        time_usec: u64,
+        key: u32,
+        state: KeyState,
+    ) {
+        if self.mouse_keys_event(key, state) {
+            return;
+        }
+        let phy = self.get_physical_keyboard(dev.keyboard_id, dev.keymap.get().as_ref());
+        self.accessible_key_event(&phy, time_usec, key, state);
+    }
+
     pub fn key_events(
         self: &Rc<Self>,
         time_usec: u64,
@@ -937,9 +986,11 @@ impl WlSeatGlobal {
         let mut components_changed = false;
         let mut shortcuts_only = false;
         while let Some(event) = events.pop() {
+            let mut modifier_key = false;
             let (key_state, kc) = match event {
                 EventOrRepeat::Event(event) => {
-                    components_changed |= kbvm_state.kb_state.apply_event(event);
+                    modifier_key = kbvm_state.kb_state.apply_event(event);
+                    components_changed |= modifier_key;
                     match event {
                         Event::KeyDown(kc) => (KeyState::Pressed, kc),
                         Event::KeyUp(kc) => (KeyState::Released, kc),
@@ -982,6 +1033,7 @@ impl WlSeatGlobal {
             }
             shortcuts.clear();
             let repeats;
+            let mut escape_inhibitor = false;
             {
                 let mut mods = kbvm_state.kb_state.mods.mods.0 & !(CAPS.0 | NUM.0);
                 if key_state == KeyState::Released {
@@ -995,11 +1047,22 @@ impl WlSeatGlobal {
                 let keysyms = map.lookup_table.lookup(group, ModifierMask::default(), kc);
                 repeats = keysyms.repeats();
                 let mut revert_pointer_to_default = false;
+                let inhibited = self.shortcuts_inhibitor.is_some();
+                let escape = self.shortcuts_inhibit_escape.get();
                 for props in keysyms {
                     let sym = props.keysym().0;
                     if sym == self.revert_key.get().0 && mods == 0 {
                         revert_pointer_to_default = true;
                     }
+                    if inhibited {
+                        if key_state == KeyState::Pressed
+                            && sym == escape.sym.0
+                            && mods == escape.mods.0
+                        {
+                            escape_inhibitor = true;
+                        }
+                        continue;
+                    }
                     if (!self.state.lock.locked[LiveTL].get()
                         || self.state.config_locked_shortcuts.get())
                         && let Some(key_mods) = scs.get(&sym)
@@ -1027,6 +1090,10 @@ impl WlSeatGlobal {
                 t.send_key(self.id, time_usec, kc.to_evdev(), key_state);
             });
             self.forward.set(true);
+            if escape_inhibitor {
+                self.forward.set(false);
+                self.escape_shortcuts_inhibitor();
+            }
             if shortcuts.is_not_empty() {
                 self.forward.set(key_state == KeyState::Released);
                 if let Some(config) = self.state.config.get() {
@@ -1060,7 +1127,14 @@ impl WlSeatGlobal {
                 update_pressed_keys(&mut kbvm_state);
                 continue;
             }
+            if key_state == KeyState::Pressed
+                && !modifier_key
+                && kbvm_state.kb_state.mods.mods.0 & !(CAPS.0 | NUM.0 | SHIFT.0) == 0
+            {
+                self.cursor_user_group.key_pressed();
+            }
             self.send_components(&mut components_changed, &kbvm_state);
+            self.record_macro_key(time_usec, kc.to_evdev(), key_state, &kbvm_state.kb_state);
             self.send_key(time_usec, kc, key_state, &kbvm_state.kb_state);
             self.for_each_ei_seat(|ei_seat| {
                 ei_seat.handle_key(time_usec, kc.to_evdev(), key_state, &kbvm_state.kb_state);
@@ -1146,25 +1220,121 @@ impl WlSeatGlobal {
             return;
         }
         let kb_state = &kbvm_state.kb_state;
+        self.send_components_to_observers(kb_state);
+        let mut forward_to_node = true;
+        if let Some(g) = self.input_method_grab.get() {
+            forward_to_node = g.on_modifiers(kb_state);
+        }
+        if forward_to_node {
+            self.keyboard_node.get().node_on_mods(self, kb_state)
+        }
+    }
+
+    fn send_components_to_observers(&self, kb_state: &KeyboardState) {
+        let bar_kb_state = (kb_state.mods.group, kb_state.leds);
+        if self.bar_kb_state.replace(bar_kb_state) != bar_kb_state {
+            self.state
+                .bar_elements_changed(BE_KEYBOARD_LAYOUT | BE_LOCK_INDICATORS);
+        }
         self.for_each_ei_seat(|ei_seat| {
             ei_seat.handle_modifiers_changed(kb_state);
         });
         self.state.for_each_seat_tester(|t| {
             t.send_modifiers(self.id, &kb_state.mods);
         });
-        let mut forward_to_node = true;
-        if let Some(g) = self.input_method_grab.get() {
-            forward_to_node = g.on_modifiers(kb_state);
+    }
+
+    pub fn keyboard_group(&self) -> GroupIndex {
+        self.seat_kb_state.get().borrow().kb_state.mods.group
+    }
+
+    pub fn set_keyboard_group(&self, group: u32) {
+        let kbvm_state = self.seat_kb_state.get();
+        let kbvm_state = &mut *kbvm_state.borrow_mut();
+        let mut changed = kbvm_state.kb_state.set_locked_group(GroupIndex(group));
+        self.send_components(&mut changed, kbvm_state);
+    }
+
+    pub(super) fn save_keyboard_group(&self, node: &Rc<dyn Node>) {
+        let mode = self.layout_memory_mode.get();
+        if mode == LayoutMemoryMode::Global {
+            return;
         }
-        if forward_to_node {
-            self.keyboard_node.get().node_on_mods(self, kb_state)
+        let Some(tl) = node.clone().node_toplevel() else {
+            return;
+        };
+        let data = tl.tl_data();
+        let group = self
+            .latest_seat_kb_state()
+            .borrow()
+            .kb_state
+            .mods
+            .group_locked
+            .0;
+        match mode {
+            LayoutMemoryMode::Global => {}
+            LayoutMemoryMode::Window => {
+                data.kb_groups.set(self.id, group);
+            }
+            LayoutMemoryMode::AppId => {
+                let app_id = data.app_id.borrow().clone();
+                self.app_id_kb_groups.set(app_id, group);
+            }
+        }
+    }
+
+    pub(super) fn restore_keyboard_group(&self, node: &Rc<dyn Node>) {
+        let mode = self.layout_memory_mode.get();
+        if mode == LayoutMemoryMode::Global {
+            return;
+        }
+        let Some(tl) = node.clone().node_toplevel() else {
+            return;
+        };
+        let data = tl.tl_data();
+        let group = match mode {
+            LayoutMemoryMode::Global => None,
+            LayoutMemoryMode::Window => data.kb_groups.get(&self.id),
+            LayoutMemoryMode::AppId => self.app_id_kb_groups.get(&*data.app_id.borrow()),
+        };
+        let group = GroupIndex(group.unwrap_or_default());
+        let latest = self.latest_kb_state_id.get();
+        // Keyboards with a custom keymap have their own state. All of them switch to
+        // the group of the window so that it does not depend on the device used next.
+        let states: SmallVec<[_; 2]> = self
+            .kb_states
+            .lock()
+            .values()
+            .filter_map(|s| s.upgrade())
+            .collect();
+        for kbvm_state in states {
+            let kb_state = &mut kbvm_state.borrow_mut().kb_state;
+            if kb_state.set_locked_group(group) && kb_state.id == latest {
+                // The node has not been sent an enter event yet. It will receive the new
+                // group as part of that event.
+                self.send_components_to_observers(kb_state);
+            }
         }
     }
 
+    fn latest_seat_kb_state(&self) -> Rc<RefCell<KbvmState>> {
+        let latest = self.latest_kb_state_id.get();
+        for kbvm_state in self.kb_states.lock().values() {
+            if let Some(kbvm_state) = kbvm_state.upgrade()
+                && kbvm_state.borrow().kb_state.id == latest
+            {
+                return kbvm_state;
+            }
+        }
+        self.seat_kb_state.get()
+    }
+
     pub(super) fn for_each_ei_seat(&self, mut f: impl FnMut(&Rc<EiSeat>)) {
         if self.ei_seats.is_not_empty() {
             for ei_seat in self.ei_seats.lock().values() {
-                f(ei_seat);
+                if !ei_seat.client.input_capture.get() {
+                    f(ei_seat);
+                }
             }
         }
     }
@@ -1357,6 +1527,9 @@ impl WlSeatGlobal {
 
     pub fn clear_shortcuts(&self) {
         self.shortcuts.borrow_mut().clear();
+        self.pointer_shortcuts.clear();
+        self.gesture_bindings.clear();
+        self.touch_gesture_bindings.clear();
     }
 
     pub fn add_shortcut(&self, mod_mask: Modifiers, mods: Modifiers, keysym: KeySym) {
@@ -1382,6 +1555,150 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn add_pointer_shortcut(&self, trigger: ModifiedPointerTrigger) {
+        self.pointer_shortcuts.add(trigger);
+    }
+
+    pub fn remove_pointer_shortcut(&self, trigger: ModifiedPointerTrigger) {
+        self.pointer_shortcuts.remove(trigger);
+    }
+
+    fn pointer_shortcut_mods(&self) -> Option<Modifiers> {
+        if self.pointer_shortcuts.is_empty() || self.state.lock.locked[LiveTL].get() {
+            return None;
+        }
+        let mods = self.latest_kb_state().borrow().mods.mods.0 & !(CAPS.0 | NUM.0);
+        Some(Modifiers(mods))
+    }
+
+    fn invoke_pointer_shortcuts(self: &Rc<Self>, triggers: &[ModifiedPointerTrigger]) {
+        let Some(config) = self.state.config.get() else {
+            return;
+        };
+        let tl = self
+            .pointer_stack
+            .borrow()
+            .iter()
+            .rev()
+            .find_map(|n| n.clone().node_toplevel());
+        if let Some(tl) = tl {
+            self.focus_toplevel(tl);
+        }
+        for &trigger in triggers {
+            config.invoke_pointer_shortcut(self.id, trigger);
+        }
+    }
+
+    fn handle_pointer_button_shortcut(self: &Rc<Self>, button: u32, state: ButtonState) -> bool {
+        let mods = match state {
+            ButtonState::Pressed => self.pointer_shortcut_mods(),
+            ButtonState::Released => None,
+        };
+        let (consumed, trigger) = self.pointer_shortcuts.button(mods, button, state);
+        if let Some(trigger) = trigger {
+            self.invoke_pointer_shortcuts(&[trigger]);
+        }
+        consumed
+    }
+
+    fn handle_pointer_scroll_shortcuts(self: &Rc<Self>) -> [bool; 2] {
+        let Some(mods) = self.pointer_shortcut_mods() else {
+            return [false; 2];
+        };
+        let mut v120 = [None; 2];
+        for axis in ScrollAxis::variants() {
+            v120[axis as usize] = self.pointer_owner.pending_v120(axis);
+        }
+        let (consumed, triggers) = self.pointer_shortcuts.scroll(mods, v120);
+        if triggers.is_not_empty() {
+            self.invoke_pointer_shortcuts(&triggers);
+        }
+        consumed
+    }
+
+    pub fn add_gesture_binding(&self, gesture: Gesture) {
+        self.gesture_bindings.set(gesture, false);
+    }
+
+    pub fn remove_gesture_binding(&self, gesture: Gesture) {
+        self.gesture_bindings.remove(&gesture);
+    }
+
+    pub fn set_gesture_continuous(&self, gesture: Gesture, continuous: bool) {
+        if self.gesture_bindings.contains(&gesture) {
+            self.gesture_bindings.set(gesture, continuous);
+        }
+    }
+
+    pub fn set_gesture_swipe_distance(&self, distance: f64) {
+        if distance > 0.0 && distance.is_finite() {
+            self.gesture_swipe_distance.set(distance);
+        }
+    }
+
+    pub(super) fn has_gesture_binding(&self, fingers: u32, kinds: &[GestureKind]) -> bool {
+        if self.gesture_bindings.is_empty() || self.state.lock.locked[LiveTL].get() {
+            return false;
+        }
+        kinds
+            .iter()
+            .any(|&kind| self.gesture_bindings.contains(&Gesture::new(kind, fingers)))
+    }
+
+    pub(super) fn invoke_gesture(&self, gesture: Gesture) {
+        if let Some(config) = self.state.config.get() {
+            config.invoke_gesture(self.id, gesture);
+        }
+    }
+
+    pub fn add_touch_gesture_binding(&self, gesture: TouchGesture) {
+        self.touch_gesture_bindings.set(gesture, ());
+    }
+
+    pub fn remove_touch_gesture_binding(&self, gesture: TouchGesture) {
+        self.touch_gesture_bindings.remove(&gesture);
+    }
+
+    pub fn set_touch_long_press_drag(&self, enabled: bool) {
+        self.touch_long_press_drag.set(enabled);
+    }
+
+    fn touch_gestures_enabled(&self) -> bool {
+        self.touch_gesture_bindings.is_not_empty() && !self.state.lock.locked[LiveTL].get()
+    }
+
+    pub(super) fn has_touch_edge_gesture_binding(&self, kind: TouchGestureKind) -> bool {
+        self.touch_gestures_enabled()
+            && self
+                .touch_gesture_bindings
+                .lock()
+                .keys()
+                .any(|g| g.kind == kind)
+    }
+
+    pub(super) fn has_touch_gesture_binding(&self, fingers: u32) -> bool {
+        self.touch_gestures_enabled()
+            && self.touch_gesture_bindings.lock().keys().any(|g| {
+                g.fingers == fingers
+                    && !matches!(
+                        g.kind,
+                        TouchGestureKind::FromLeftEdge
+                            | TouchGestureKind::FromRightEdge
+                            | TouchGestureKind::FromTopEdge
+                            | TouchGestureKind::FromBottomEdge
+                    )
+            })
+    }
+
+    pub(super) fn invoke_touch_gesture(&self, gesture: TouchGesture) {
+        if !self.touch_gesture_bindings.contains(&gesture) {
+            return;
+        }
+        if let Some(config) = self.state.config.get() {
+            config.invoke_touch_gesture(self.id, gesture);
+        }
+    }
+
     pub fn set_shortcut_repeat(&self, mods: Modifiers, keysym: KeySym, repeat: bool) {
         if let Some(scs) = self.shortcuts.borrow_mut().get_mut(&keysym.0)
             && let Some(sc) = scs.get_mut(&mods.0)
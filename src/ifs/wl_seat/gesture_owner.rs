@@ -1,9 +1,19 @@
+#[cfg(test)]
+mod tests;
+
 use crate::fixed::Fixed;
 use crate::ifs::wl_seat::WlSeatGlobal;
 use crate::tree::Node;
 use crate::utils::clonecell::CloneCell;
+use crate::utils::copyhashmap::CopyHashMap;
+use jay_config::input::gesture::Gesture;
+use jay_config::input::gesture::GestureKind;
+use std::cell::Cell;
 use std::rc::Rc;
 
+const PINCH_FACTOR: f64 = 1.25;
+const SWIPE_DIRECTION_DISTANCE: f64 = 16.0;
+
 pub struct GestureOwnerHolder {
     default: Rc<NoGesture>,
     owner: CloneCell<Rc<dyn GestureOwner>>,
@@ -144,6 +154,18 @@ impl GestureOwner for NoGesture {
     }
 
     fn swipe_begin(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, finger_count: u32) {
+        let kinds = [
+            GestureKind::SwipeLeft,
+            GestureKind::SwipeRight,
+            GestureKind::SwipeUp,
+            GestureKind::SwipeDown,
+        ];
+        if seat.has_gesture_binding(finger_count, &kinds) {
+            seat.gesture_owner
+                .owner
+                .set(Rc::new(BoundSwipeGesture::new(finger_count, time_usec)));
+            return;
+        }
         let Some(node) = seat.pointer_node() else {
             return;
         };
@@ -153,6 +175,13 @@ impl GestureOwner for NoGesture {
     }
 
     fn pinch_begin(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, finger_count: u32) {
+        let kinds = [GestureKind::PinchIn, GestureKind::PinchOut];
+        if seat.has_gesture_binding(finger_count, &kinds) {
+            seat.gesture_owner
+                .owner
+                .set(Rc::new(BoundPinchGesture::new(finger_count)));
+            return;
+        }
         let Some(node) = seat.pointer_node() else {
             return;
         };
@@ -162,6 +191,12 @@ impl GestureOwner for NoGesture {
     }
 
     fn hold_begin(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, finger_count: u32) {
+        if seat.has_gesture_binding(finger_count, &[GestureKind::Hold]) {
+            seat.gesture_owner.owner.set(Rc::new(BoundHoldGesture {
+                fingers: finger_count,
+            }));
+            return;
+        }
         let Some(node) = seat.pointer_node() else {
             return;
         };
@@ -235,3 +270,291 @@ impl GestureOwner for HoldGesture {
         seat.gesture_owner.set_default_owner();
     }
 }
+
+struct BoundSwipeGesture {
+    fingers: u32,
+    begin_usec: u64,
+    total_dx: Cell<f64>,
+    total_dy: Cell<f64>,
+    bound: Cell<bool>,
+    dx: Cell<f64>,
+    dy: Cell<f64>,
+    fired: Cell<bool>,
+}
+
+impl BoundSwipeGesture {
+    fn new(fingers: u32, begin_usec: u64) -> Self {
+        Self {
+            fingers,
+            begin_usec,
+            total_dx: Cell::new(0.0),
+            total_dy: Cell::new(0.0),
+            bound: Cell::new(false),
+            dx: Cell::new(0.0),
+            dy: Cell::new(0.0),
+            fired: Cell::new(false),
+        }
+    }
+
+    /// Returns whether the direction of the swipe is bound once the fingers have moved
+    /// far enough to determine the direction.
+    fn is_bound(
+        &self,
+        bindings: &CopyHashMap<Gesture, bool>,
+        distance: f64,
+        dx: f64,
+        dy: f64,
+    ) -> Option<bool> {
+        let dx = self.total_dx.get() + dx;
+        let dy = self.total_dy.get() + dy;
+        self.total_dx.set(dx);
+        self.total_dy.set(dy);
+        let (v, negative, positive) = if dx.abs() >= dy.abs() {
+            (dx, GestureKind::SwipeLeft, GestureKind::SwipeRight)
+        } else {
+            (dy, GestureKind::SwipeUp, GestureKind::SwipeDown)
+        };
+        if v.abs() < SWIPE_DIRECTION_DISTANCE.min(distance) {
+            return None;
+        }
+        let kind = if v > 0.0 { positive } else { negative };
+        Some(bindings.contains(&Gesture::new(kind, self.fingers)))
+    }
+
+    /// Forwards the swipe to the node under the pointer since its direction is not
+    /// bound.
+    fn forward(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64) {
+        seat.gesture_owner.set_default_owner();
+        let Some(node) = seat.pointer_node() else {
+            return;
+        };
+        node.node_seat_state().gesture_begin(seat);
+        node.node_on_swipe_begin(seat, self.begin_usec, self.fingers);
+        node.node_on_swipe_update(
+            seat,
+            time_usec,
+            Fixed::from_f64(self.total_dx.get()),
+            Fixed::from_f64(self.total_dy.get()),
+        );
+        seat.gesture_owner.owner.set(Rc::new(SwipeGesture { node }));
+    }
+
+    /// Invokes the continuous bindings every time the fingers have moved `distance`
+    /// along an axis.
+    fn update(
+        &self,
+        bindings: &CopyHashMap<Gesture, bool>,
+        distance: f64,
+        dx: f64,
+        dy: f64,
+        mut invoke: impl FnMut(Gesture),
+    ) {
+        let axes = [
+            (
+                &self.dx,
+                dx,
+                GestureKind::SwipeLeft,
+                GestureKind::SwipeRight,
+            ),
+            (&self.dy, dy, GestureKind::SwipeUp, GestureKind::SwipeDown),
+        ];
+        for (acc, delta, negative, positive) in axes {
+            let mut v = acc.get() + delta;
+            loop {
+                let kind = if v >= distance {
+                    positive
+                } else if v <= -distance {
+                    negative
+                } else {
+                    break;
+                };
+                let gesture = Gesture::new(kind, self.fingers);
+                if bindings.get(&gesture) != Some(true) {
+                    break;
+                }
+                self.fired.set(true);
+                invoke(gesture);
+                v -= distance.copysign(v);
+            }
+            acc.set(v);
+        }
+    }
+
+    /// Returns the gesture that should be invoked when the fingers are lifted.
+    fn end(
+        &self,
+        bindings: &CopyHashMap<Gesture, bool>,
+        distance: f64,
+        cancelled: bool,
+    ) -> Option<Gesture> {
+        if cancelled || self.fired.get() {
+            return None;
+        }
+        let dx = self.dx.get();
+        let dy = self.dy.get();
+        let (v, negative, positive) = if dx.abs() >= dy.abs() {
+            (dx, GestureKind::SwipeLeft, GestureKind::SwipeRight)
+        } else {
+            (dy, GestureKind::SwipeUp, GestureKind::SwipeDown)
+        };
+        if v.abs() < distance {
+            return None;
+        }
+        let kind = if v > 0.0 { positive } else { negative };
+        let gesture = Gesture::new(kind, self.fingers);
+        bindings.contains(&gesture).then_some(gesture)
+    }
+}
+
+impl GestureOwner for BoundSwipeGesture {
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.gesture_owner.set_default_owner();
+    }
+
+    fn swipe_update(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, dx: Fixed, dy: Fixed) {
+        let distance = seat.gesture_swipe_distance.get();
+        if !self.bound.get() {
+            match self.is_bound(&seat.gesture_bindings, distance, dx.to_f64(), dy.to_f64()) {
+                Some(true) => self.bound.set(true),
+                Some(false) => {
+                    self.forward(seat, time_usec);
+                    return;
+                }
+                None => {}
+            }
+        }
+        self.update(
+            &seat.gesture_bindings,
+            distance,
+            dx.to_f64(),
+            dy.to_f64(),
+            |g| seat.invoke_gesture(g),
+        );
+    }
+
+    fn swipe_end(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, cancelled: bool) {
+        seat.gesture_owner.set_default_owner();
+        let distance = seat.gesture_swipe_distance.get();
+        if let Some(gesture) = self.end(&seat.gesture_bindings, distance, cancelled) {
+            seat.invoke_gesture(gesture);
+        }
+    }
+}
+
+struct BoundPinchGesture {
+    fingers: u32,
+    base: Cell<f64>,
+    scale: Cell<f64>,
+    fired: Cell<bool>,
+}
+
+impl BoundPinchGesture {
+    fn new(fingers: u32) -> Self {
+        Self {
+            fingers,
+            base: Cell::new(1.0),
+            scale: Cell::new(1.0),
+            fired: Cell::new(false),
+        }
+    }
+
+    /// Invokes the continuous bindings every time the scale has changed by
+    /// [`PINCH_FACTOR`].
+    fn update(
+        &self,
+        bindings: &CopyHashMap<Gesture, bool>,
+        scale: f64,
+        mut invoke: impl FnMut(Gesture),
+    ) {
+        self.scale.set(scale);
+        loop {
+            let base = self.base.get();
+            let (kind, next) = if scale >= base * PINCH_FACTOR {
+                (GestureKind::PinchOut, base * PINCH_FACTOR)
+            } else if scale <= base / PINCH_FACTOR {
+                (GestureKind::PinchIn, base / PINCH_FACTOR)
+            } else {
+                break;
+            };
+            let gesture = Gesture::new(kind, self.fingers);
+            if bindings.get(&gesture) != Some(true) {
+                break;
+            }
+            self.fired.set(true);
+            invoke(gesture);
+            self.base.set(next);
+        }
+    }
+
+    /// Returns the gesture that should be invoked when the fingers are lifted.
+    fn end(&self, bindings: &CopyHashMap<Gesture, bool>, cancelled: bool) -> Option<Gesture> {
+        if cancelled || self.fired.get() {
+            return None;
+        }
+        let scale = self.scale.get();
+        let kind = if scale >= PINCH_FACTOR {
+            GestureKind::PinchOut
+        } else if scale <= 1.0 / PINCH_FACTOR {
+            GestureKind::PinchIn
+        } else {
+            return None;
+        };
+        let gesture = Gesture::new(kind, self.fingers);
+        bindings.contains(&gesture).then_some(gesture)
+    }
+}
+
+impl GestureOwner for BoundPinchGesture {
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.gesture_owner.set_default_owner();
+    }
+
+    fn pinch_update(
+        &self,
+        seat: &Rc<WlSeatGlobal>,
+        _time_usec: u64,
+        _dx: Fixed,
+        _dy: Fixed,
+        scale: Fixed,
+        _rotation: Fixed,
+    ) {
+        self.update(&seat.gesture_bindings, scale.to_f64(), |g| {
+            seat.invoke_gesture(g)
+        });
+    }
+
+    fn pinch_end(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, cancelled: bool) {
+        seat.gesture_owner.set_default_owner();
+        if let Some(gesture) = self.end(&seat.gesture_bindings, cancelled) {
+            seat.invoke_gesture(gesture);
+        }
+    }
+}
+
+struct BoundHoldGesture {
+    fingers: u32,
+}
+
+impl GestureOwner for BoundHoldGesture {
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.gesture_owner.set_default_owner();
+    }
+
+    fn hold_end(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, cancelled: bool) {
+        seat.gesture_owner.set_default_owner();
+        if let Some(gesture) = self.end(&seat.gesture_bindings, cancelled) {
+            seat.invoke_gesture(gesture);
+        }
+    }
+}
+
+impl BoundHoldGesture {
+    /// Returns the gesture that should be invoked when the fingers are lifted.
+    fn end(&self, bindings: &CopyHashMap<Gesture, bool>, cancelled: bool) -> Option<Gesture> {
+        if cancelled {
+            return None;
+        }
+        let gesture = Gesture::new(GestureKind::Hold, self.fingers);
+        bindings.contains(&gesture).then_some(gesture)
+    }
+}
@@ -0,0 +1,149 @@
+use crate::ifs::wl_seat::gesture_owner::BoundHoldGesture;
+use crate::ifs::wl_seat::gesture_owner::BoundPinchGesture;
+use crate::ifs::wl_seat::gesture_owner::BoundSwipeGesture;
+use crate::utils::copyhashmap::CopyHashMap;
+use jay_config::input::gesture::Gesture;
+use jay_config::input::gesture::GestureKind;
+use jay_config::input::gesture::GestureKind::*;
+
+const DISTANCE: f64 = 100.0;
+
+fn bindings(gestures: &[(GestureKind, u32, bool)]) -> CopyHashMap<Gesture, bool> {
+    let bindings = CopyHashMap::new();
+    for &(kind, fingers, continuous) in gestures {
+        bindings.set(Gesture::new(kind, fingers), continuous);
+    }
+    bindings
+}
+
+fn swipe(
+    g: &BoundSwipeGesture,
+    bindings: &CopyHashMap<Gesture, bool>,
+    dx: f64,
+    dy: f64,
+) -> Vec<GestureKind> {
+    let mut res = vec![];
+    g.update(bindings, DISTANCE, dx, dy, |g| res.push(g.kind));
+    res
+}
+
+fn pinch(
+    g: &BoundPinchGesture,
+    bindings: &CopyHashMap<Gesture, bool>,
+    scale: f64,
+) -> Vec<GestureKind> {
+    let mut res = vec![];
+    g.update(bindings, scale, |g| res.push(g.kind));
+    res
+}
+
+#[test]
+fn swipe_on_end() {
+    let b = bindings(&[(SwipeLeft, 3, false), (SwipeUp, 3, false)]);
+    let g = BoundSwipeGesture::new(3, 0);
+    assert_eq!(swipe(&g, &b, -60.0, 10.0), []);
+    assert_eq!(swipe(&g, &b, -60.0, -30.0), []);
+    assert_eq!(g.end(&b, DISTANCE, false), Some(Gesture::new(SwipeLeft, 3)));
+}
+
+#[test]
+fn swipe_dominant_axis() {
+    let b = bindings(&[(SwipeLeft, 3, false), (SwipeDown, 3, false)]);
+    let g = BoundSwipeGesture::new(3, 0);
+    swipe(&g, &b, -110.0, 150.0);
+    assert_eq!(g.end(&b, DISTANCE, false), Some(Gesture::new(SwipeDown, 3)));
+}
+
+#[test]
+fn swipe_too_short_or_cancelled() {
+    let b = bindings(&[(SwipeRight, 3, false)]);
+    let g = BoundSwipeGesture::new(3, 0);
+    swipe(&g, &b, 99.0, 0.0);
+    assert_eq!(g.end(&b, DISTANCE, false), None);
+    let g = BoundSwipeGesture::new(3, 0);
+    swipe(&g, &b, 200.0, 0.0);
+    assert_eq!(g.end(&b, DISTANCE, true), None);
+}
+
+#[test]
+fn swipe_unbound() {
+    let b = bindings(&[(SwipeRight, 4, false)]);
+    let g = BoundSwipeGesture::new(3, 0);
+    swipe(&g, &b, 200.0, 0.0);
+    assert_eq!(g.end(&b, DISTANCE, false), None);
+}
+
+#[test]
+fn swipe_direction() {
+    let b = bindings(&[(SwipeLeft, 3, true), (SwipeRight, 3, true)]);
+    let g = BoundSwipeGesture::new(3, 0);
+    assert_eq!(g.is_bound(&b, DISTANCE, -5.0, 3.0), None);
+    assert_eq!(g.is_bound(&b, DISTANCE, -15.0, 5.0), Some(true));
+    // Vertical swipes are not bound and are forwarded to the application.
+    let g = BoundSwipeGesture::new(3, 0);
+    assert_eq!(g.is_bound(&b, DISTANCE, 2.0, 10.0), None);
+    assert_eq!(g.is_bound(&b, DISTANCE, 2.0, 10.0), Some(false));
+    // The direction is determined early even with a large swipe distance.
+    let g = BoundSwipeGesture::new(3, 0);
+    assert_eq!(g.is_bound(&b, 1000.0, 20.0, 0.0), Some(true));
+}
+
+#[test]
+fn swipe_continuous() {
+    let b = bindings(&[(SwipeRight, 3, true), (SwipeLeft, 3, true)]);
+    let g = BoundSwipeGesture::new(3, 0);
+    assert_eq!(swipe(&g, &b, 50.0, 0.0), []);
+    assert_eq!(swipe(&g, &b, 60.0, 0.0), [SwipeRight]);
+    assert_eq!(swipe(&g, &b, 250.0, 0.0), [SwipeRight, SwipeRight]);
+    // The remainder of 60 is consumed before the fingers move far enough to the left.
+    assert_eq!(swipe(&g, &b, -150.0, 0.0), []);
+    assert_eq!(swipe(&g, &b, -20.0, 0.0), [SwipeLeft]);
+    // Continuous bindings are not invoked again when the fingers are lifted.
+    assert_eq!(g.end(&b, DISTANCE, false), None);
+}
+
+#[test]
+fn swipe_continuous_and_discrete() {
+    let b = bindings(&[(SwipeRight, 3, true), (SwipeUp, 3, false)]);
+    let g = BoundSwipeGesture::new(3, 0);
+    assert_eq!(swipe(&g, &b, 0.0, -250.0), []);
+    assert_eq!(g.end(&b, DISTANCE, false), Some(Gesture::new(SwipeUp, 3)));
+}
+
+#[test]
+fn pinch_on_end() {
+    let b = bindings(&[(PinchIn, 2, false), (PinchOut, 2, false)]);
+    let g = BoundPinchGesture::new(2);
+    assert_eq!(pinch(&g, &b, 1.5), []);
+    assert_eq!(pinch(&g, &b, 1.1), []);
+    assert_eq!(g.end(&b, false), None);
+    let g = BoundPinchGesture::new(2);
+    pinch(&g, &b, 0.7);
+    assert_eq!(g.end(&b, false), Some(Gesture::new(PinchIn, 2)));
+    let g = BoundPinchGesture::new(2);
+    pinch(&g, &b, 1.3);
+    assert_eq!(g.end(&b, false), Some(Gesture::new(PinchOut, 2)));
+    assert_eq!(g.end(&b, true), None);
+}
+
+#[test]
+fn pinch_continuous() {
+    let b = bindings(&[(PinchIn, 2, true), (PinchOut, 2, true)]);
+    let g = BoundPinchGesture::new(2);
+    assert_eq!(pinch(&g, &b, 1.2), []);
+    assert_eq!(pinch(&g, &b, 1.25), [PinchOut]);
+    // 1.25^3 ≈ 1.95
+    assert_eq!(pinch(&g, &b, 2.0), [PinchOut, PinchOut]);
+    assert_eq!(pinch(&g, &b, 1.5), [PinchIn]);
+    assert_eq!(g.end(&b, false), None);
+}
+
+#[test]
+fn hold() {
+    let b = bindings(&[(Hold, 3, false)]);
+    let g = BoundHoldGesture { fingers: 3 };
+    assert_eq!(g.end(&b, false), Some(Gesture::new(Hold, 3)));
+    assert_eq!(g.end(&b, true), None);
+    let g = BoundHoldGesture { fingers: 4 };
+    assert_eq!(g.end(&b, false), None);
+}
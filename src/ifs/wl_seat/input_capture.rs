@@ -0,0 +1,443 @@
+use crate::backend::AXIS_120;
+use crate::backend::ButtonState;
+use crate::backend::InputEvent;
+use crate::backend::KeyState;
+use crate::backend::ScrollAxis;
+use crate::client::ClientId;
+use crate::ei::ei_ifs::ei_seat::EiSeat;
+use crate::fixed::Fixed;
+use crate::ifs::jay_ei_session::JayEiSession;
+use crate::ifs::wl_seat::CursorPositionType;
+use crate::ifs::wl_seat::WlSeatGlobal;
+use crate::ifs::wl_seat::wl_pointer::PendingScroll;
+use crate::kbvm::KbvmState;
+use crate::keyboard::KeyboardState;
+use crate::state::DeviceHandlerData;
+use crate::state::State;
+use crate::tree::NodeBase;
+use crate::tree::TreeTimeline::LiveTL;
+use crate::utils::clonecell::CloneCell;
+use crate::utils::numcell::NumCell;
+use ahash::AHashSet;
+use jay_config::keyboard::mods::CAPS;
+use jay_config::keyboard::mods::NUM;
+use kbvm::Keycode;
+use kbvm::ModifierMask;
+use kbvm::state_machine::Direction;
+use kbvm::state_machine::Event;
+use linearize::LinearizeExt;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub struct InputCapture {
+    pub ei_client_id: ClientId,
+    pub session: CloneCell<Option<Rc<JayEiSession>>>,
+    state: Rc<State>,
+    barriers: RefCell<Vec<PointerBarrier>>,
+    enabled: Cell<bool>,
+    seat: CloneCell<Option<Rc<WlSeatGlobal>>>,
+    activation_id: NumCell<u32>,
+    pressed_keys: RefCell<AHashSet<u32>>,
+    pressed_buttons: RefCell<AHashSet<u32>>,
+    /// The keyboard state of the captured keys. This starts out without any pressed
+    /// modifiers since keys that were pressed before the activation are not captured.
+    kb_state: RefCell<Option<KbvmState>>,
+    scroll: PendingScroll,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct PointerBarrier {
+    id: u32,
+    x1: i32,
+    y1: i32,
+    x2: i32,
+    y2: i32,
+}
+
+impl PointerBarrier {
+    fn crossed_by(&self, (x0, y0): (f64, f64), (x1, y1): (f64, f64)) -> bool {
+        let (b, b1, b2, p0, p1, q0, q1) = match self.x1 == self.x2 {
+            true => (self.x1, self.y1, self.y2, x0, x1, y0, y1),
+            false => (self.y1, self.x1, self.x2, y0, y1, x0, x1),
+        };
+        let b = b as f64;
+        if (p0 >= b) == (p1 >= b) {
+            return false;
+        }
+        let t = (b - p0) / (p1 - p0);
+        let q = q0 + t * (q1 - q0);
+        q >= b1 as f64 && q <= b2 as f64
+    }
+}
+
+impl InputCapture {
+    pub fn new(state: &Rc<State>, ei_client_id: ClientId) -> Rc<Self> {
+        let capture = Rc::new(Self {
+            ei_client_id,
+            session: Default::default(),
+            state: state.clone(),
+            barriers: Default::default(),
+            enabled: Cell::new(false),
+            seat: Default::default(),
+            activation_id: Default::default(),
+            pressed_keys: Default::default(),
+            pressed_buttons: Default::default(),
+            kb_state: Default::default(),
+            scroll: Default::default(),
+        });
+        state.input_captures.set(ei_client_id, capture.clone());
+        capture
+    }
+
+    pub fn kill(&self) {
+        self.deactivate(None);
+        self.state.input_captures.remove(&self.ei_client_id);
+        self.barriers.borrow_mut().clear();
+        self.session.take();
+    }
+
+    pub fn send_zones(&self) {
+        let Some(session) = self.session.get() else {
+            return;
+        };
+        for output in self.state.root.outputs.lock().values() {
+            session.send_zone(output.node_absolute_position(LiveTL));
+        }
+        session.send_zones_done();
+    }
+
+    pub fn zones_changed(&self) {
+        self.deactivate(None);
+        self.barriers.borrow_mut().clear();
+        self.send_zones();
+    }
+
+    pub fn clear_barriers(&self) {
+        self.barriers.borrow_mut().clear();
+    }
+
+    pub fn add_barrier(&self, id: u32, x1: i32, y1: i32, x2: i32, y2: i32) -> bool {
+        if x1 != x2 && y1 != y2 {
+            return false;
+        }
+        self.barriers.borrow_mut().push(PointerBarrier {
+            id,
+            x1: x1.min(x2),
+            y1: y1.min(y2),
+            x2: x1.max(x2),
+            y2: y1.max(y2),
+        });
+        true
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+        if !enabled {
+            self.deactivate(None);
+        }
+    }
+
+    pub fn release(&self, activation_id: u32, position: Option<(Fixed, Fixed)>) {
+        if self.seat.is_some() && self.activation_id.get() == activation_id {
+            self.deactivate(position);
+        }
+    }
+
+    fn for_each_ei_seat(&self, seat: &WlSeatGlobal, mut f: impl FnMut(&Rc<EiSeat>)) {
+        for ei_seat in seat.ei_seats.lock().values() {
+            if ei_seat.client.id == self.ei_client_id {
+                f(ei_seat);
+            }
+        }
+    }
+
+    fn has_ei_seat(&self, seat: &WlSeatGlobal) -> bool {
+        let mut found = false;
+        self.for_each_ei_seat(seat, |_| found = true);
+        found
+    }
+
+    fn activate(self: &Rc<Self>, seat: &Rc<WlSeatGlobal>, barrier_id: u32) {
+        let id = self.activation_id.fetch_add(1) + 1;
+        self.seat.set(Some(seat.clone()));
+        seat.input_capture.set(Some(self.clone()));
+        {
+            let kb_state = seat.latest_kb_state();
+            let kb_state = &*kb_state.borrow();
+            *self.kb_state.borrow_mut() = Some(kb_state.map.state(kb_state.id));
+        }
+        let (x, y) = seat.pointer_cursor.position();
+        log::info!(
+            "Seat {} is now captured by ei client {}",
+            seat.seat_name,
+            self.ei_client_id,
+        );
+        if let Some(session) = self.session.get() {
+            session.send_capture_activated(id, barrier_id, x, y);
+        }
+    }
+
+    fn deactivate(&self, position: Option<(Fixed, Fixed)>) {
+        let Some(seat) = self.seat.take() else {
+            return;
+        };
+        seat.input_capture.take();
+        let now = self.state.now_usec();
+        let pressed_keys: Vec<_> = self.pressed_keys.borrow_mut().drain().collect();
+        for key in pressed_keys {
+            self.forward_key(&seat, now, key, KeyState::Released);
+        }
+        if self.kb_state.take().is_some() {
+            let kb_state = seat.latest_kb_state();
+            self.send_modifiers(&seat, &kb_state.borrow());
+        }
+        for button in self.pressed_buttons.borrow_mut().drain() {
+            self.for_each_ei_seat(&seat, |ei_seat| {
+                ei_seat.handle_button(now, button, ButtonState::Released);
+            });
+        }
+        self.scroll.take();
+        if let Some((x, y)) = position {
+            seat.motion_event_abs(now, x, y, CursorPositionType::Motion);
+        }
+        let (x, y) = seat.pointer_cursor.position();
+        log::info!("Seat {} is no longer captured", seat.seat_name);
+        if let Some(session) = self.session.get() {
+            session.send_capture_deactivated(self.activation_id.get(), x, y);
+        }
+    }
+
+    fn is_release_shortcut(&self, seat: &WlSeatGlobal, key: u32) -> bool {
+        let kb_state = self.kb_state.borrow();
+        let Some(kb_state) = &*kb_state else {
+            return false;
+        };
+        let kb_state = &kb_state.kb_state;
+        let release = seat.input_capture_release.get();
+        let mods = kb_state.mods.mods.0 & !(CAPS.0 | NUM.0);
+        if mods != release.mods.0 {
+            return false;
+        }
+        let map = &kb_state.map;
+        let group = map.shortcuts_group.unwrap_or(kb_state.mods.group);
+        map.lookup_table
+            .lookup(group, ModifierMask::default(), Keycode::from_evdev(key))
+            .into_iter()
+            .any(|props| props.keysym().0 == release.sym.0)
+    }
+
+    fn forward_key(&self, seat: &WlSeatGlobal, time_usec: u64, key: u32, state: KeyState) {
+        let direction = match state {
+            KeyState::Released => Direction::Up,
+            KeyState::Pressed => Direction::Down,
+            KeyState::Repeated => return,
+        };
+        let kb_state = &mut *self.kb_state.borrow_mut();
+        let Some(kb_state) = kb_state else {
+            return;
+        };
+        let mut events = vec![];
+        kb_state.map.state_machine.handle_key(
+            &mut kb_state.state,
+            &mut events,
+            Keycode::from_evdev(key),
+            direction,
+        );
+        let kb_state = &mut kb_state.kb_state;
+        let mut mods_changed = false;
+        for event in events {
+            let (kc, state) = match event {
+                Event::KeyDown(kc) => (kc, KeyState::Pressed),
+                Event::KeyUp(kc) => (kc, KeyState::Released),
+                _ => {
+                    mods_changed |= kb_state.apply_event(event);
+                    continue;
+                }
+            };
+            if mods_changed {
+                mods_changed = false;
+                self.send_modifiers(seat, kb_state);
+            }
+            match state {
+                KeyState::Pressed => kb_state.pressed_keys.insert(kc.to_evdev()),
+                _ => kb_state.pressed_keys.remove(&kc.to_evdev()),
+            };
+            self.for_each_ei_seat(seat, |ei_seat| {
+                ei_seat.handle_key(time_usec, kc.to_evdev(), state, kb_state);
+            });
+        }
+        if mods_changed {
+            self.send_modifiers(seat, kb_state);
+        }
+    }
+
+    fn send_modifiers(&self, seat: &WlSeatGlobal, kb_state: &KeyboardState) {
+        self.for_each_ei_seat(seat, |ei_seat| {
+            ei_seat.handle_modifiers_changed(kb_state);
+        });
+    }
+}
+
+impl WlSeatGlobal {
+    pub(super) fn cross_input_capture_barriers(
+        self: &Rc<Self>,
+        from: (Fixed, Fixed),
+        to: (Fixed, Fixed),
+    ) -> bool {
+        if self.state.input_captures.is_empty() || self.input_capture.is_some() {
+            return false;
+        }
+        let from = (from.0.to_f64(), from.1.to_f64());
+        let to = (to.0.to_f64(), to.1.to_f64());
+        for capture in self.state.input_captures.lock().values() {
+            if !capture.enabled.get() || capture.seat.is_some() {
+                continue;
+            }
+            let barrier = capture
+                .barriers
+                .borrow()
+                .iter()
+                .find(|b| b.crossed_by(from, to))
+                .copied();
+            if let Some(barrier) = barrier
+                && capture.has_ei_seat(self)
+            {
+                capture.activate(self, barrier.id);
+                return true;
+            }
+        }
+        false
+    }
+
+    pub(super) fn input_capture_removed_ei_seat(&self, ei: &EiSeat) {
+        if let Some(capture) = self.input_capture.get()
+            && capture.ei_client_id == ei.client.id
+            && !capture.has_ei_seat(self)
+        {
+            capture.deactivate(None);
+        }
+    }
+
+    pub(super) fn input_capture_event(&self, dev: &DeviceHandlerData, event: &InputEvent) -> bool {
+        let Some(capture) = self.input_capture.get() else {
+            return false;
+        };
+        let capture = &*capture;
+        match *event {
+            InputEvent::Key {
+                time_usec,
+                key,
+                state,
+            } => {
+                match state {
+                    KeyState::Pressed => {
+                        if capture.is_release_shortcut(self, key) {
+                            log::info!("Releasing the input capture via the release shortcut");
+                            capture.deactivate(None);
+                            return true;
+                        }
+                        capture.pressed_keys.borrow_mut().insert(key);
+                    }
+                    KeyState::Released => {
+                        if !capture.pressed_keys.borrow_mut().remove(&key) {
+                            // The key was pressed before the capture was activated.
+                            return false;
+                        }
+                    }
+                    KeyState::Repeated => return true,
+                }
+                capture.forward_key(self, time_usec, key, state);
+            }
+            InputEvent::Motion {
+                time_usec, dx, dy, ..
+            } => {
+                capture.for_each_ei_seat(self, |ei_seat| {
+                    ei_seat.handle_motion(time_usec, dx, dy);
+                });
+            }
+            InputEvent::Button {
+                time_usec,
+                button,
+                state,
+            } => {
+                let pressed_buttons = &mut *capture.pressed_buttons.borrow_mut();
+                match state {
+                    ButtonState::Pressed => {
+                        pressed_buttons.insert(button);
+                    }
+                    ButtonState::Released => {
+                        if !pressed_buttons.remove(&button) {
+                            return false;
+                        }
+                    }
+                }
+                capture.for_each_ei_seat(self, |ei_seat| {
+                    ei_seat.handle_button(time_usec, button, state);
+                });
+            }
+            InputEvent::AxisSource { .. } => {}
+            InputEvent::Axis120 {
+                dist,
+                axis,
+                inverted,
+            } => {
+                capture.scroll.v120[axis as usize].set(Some(dist));
+                capture.scroll.inverted[axis as usize].set(inverted);
+            }
+            InputEvent::AxisPx {
+                mut dist,
+                axis,
+                inverted,
+            } => {
+                if let Some(mul) = dev.px_scroll_multiplier.get() {
+                    dist *= mul;
+                }
+                capture.scroll.px[axis as usize].set(Some(Fixed::from_f64(dist)));
+                capture.scroll.inverted[axis as usize].set(inverted);
+            }
+            InputEvent::AxisStop { axis } => {
+                capture.scroll.stop[axis as usize].set(true);
+            }
+            InputEvent::AxisFrame { time_usec } => {
+                let pending = capture.scroll.take();
+                for axis in ScrollAxis::variants() {
+                    let axis = axis as usize;
+                    if let Some(dist) = pending.v120[axis].get() {
+                        let px = (dist as f64 / AXIS_120 as f64) * dev.px_per_scroll_wheel.get();
+                        pending.px[axis].set(Some(Fixed::from_f64(px)));
+                    }
+                }
+                capture.for_each_ei_seat(self, |ei_seat| {
+                    ei_seat.handle_pending_scroll(time_usec, &pending);
+                });
+            }
+            InputEvent::ConnectorPosition { .. }
+            | InputEvent::MotionAbsolute { .. }
+            | InputEvent::SwipeBegin { .. }
+            | InputEvent::SwipeUpdate { .. }
+            | InputEvent::SwipeEnd { .. }
+            | InputEvent::PinchBegin { .. }
+            | InputEvent::PinchUpdate { .. }
+            | InputEvent::PinchEnd { .. }
+            | InputEvent::HoldBegin { .. }
+            | InputEvent::HoldEnd { .. } => {}
+            InputEvent::SwitchEvent { .. }
+            | InputEvent::TabletToolAdded { .. }
+            | InputEvent::TabletToolChanged { .. }
+            | InputEvent::TabletToolButton { .. }
+            | InputEvent::TabletToolRemoved { .. }
+            | InputEvent::TabletPadButton { .. }
+            | InputEvent::TabletPadModeSwitch { .. }
+            | InputEvent::TabletPadRing { .. }
+            | InputEvent::TabletPadStrip { .. }
+            | InputEvent::TabletPadDial { .. }
+            | InputEvent::TouchDown { .. }
+            | InputEvent::TouchUp { .. }
+            | InputEvent::TouchMotion { .. }
+            | InputEvent::TouchCancel { .. }
+            | InputEvent::TouchFrame { .. } => return false,
+        }
+        true
+    }
+}
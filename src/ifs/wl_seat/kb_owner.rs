@@ -1,3 +1,4 @@
+use crate::bar_elements::BE_FOCUS;
 use crate::criteria::tlm::TL_CHANGED_SEAT_FOCI;
 use crate::ifs::wl_seat::WlSeatGlobal;
 use crate::tree::Node;
@@ -80,6 +81,7 @@ impl KbOwner for DefaultKbOwner {
         if old.node_is_xwayland_surface() && !node.node_is_xwayland_surface() {
             seat.state.xwayland.queue.push(XWaylandEvent::ActivateRoot);
         }
+        seat.save_keyboard_group(&old);
         old.node_on_unfocus(seat);
         notify_matcher!(old, data, data.seat_foci.remove(&seat.id));
         if old.node_seat_state().unfocus(seat) {
@@ -89,12 +91,15 @@ impl KbOwner for DefaultKbOwner {
         if node.node_seat_state().focus(&node, seat) {
             node.node_active_changed(true);
         }
+        seat.restore_keyboard_group(&node);
         // log::info!("focus {}", node.node_id());
         node.clone().node_on_focus(seat);
         notify_matcher!(node, data, data.seat_foci.set(seat.id, ()));
         seat.keyboard_node_serial.set(serial);
         seat.keyboard_node.set(node.clone());
+        seat.update_shortcuts_inhibitor();
         seat.tablet_on_keyboard_node_change();
+        seat.state.bar_elements_changed(BE_FOCUS);
     }
 }
 
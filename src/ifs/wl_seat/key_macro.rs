@@ -0,0 +1,418 @@
+#[cfg(test)]
+mod tests;
+
+use crate::async_engine::SpawnedFuture;
+use crate::backend::ButtonState;
+use crate::backend::KeyState;
+use crate::cpu_worker::AsyncCpuWork;
+use crate::cpu_worker::CpuJob;
+use crate::cpu_worker::CpuWork;
+use crate::fixed::Fixed;
+use crate::ifs::wl_seat::CursorPositionType;
+use crate::ifs::wl_seat::WlSeatGlobal;
+use crate::keyboard::KeyboardState;
+use crate::utils::data_dir::data_dir;
+use crate::utils::errorfmt::ErrorFmt;
+use crate::utils::numcell::NumCell;
+use ahash::AHashMap;
+use kbvm::Components;
+use serde::Deserialize;
+use serde::Serialize;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::rc::Weak;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+enum KeyMacroError {
+    #[error("Macro names must not be empty, start with `.`, or contain `/`")]
+    InvalidName,
+    #[error("Could not create the macro directory")]
+    CreateDir(#[source] io::Error),
+    #[error("Could not write {0}")]
+    Write(String, #[source] io::Error),
+    #[error("Could not read {0}")]
+    Read(String, #[source] io::Error),
+    #[error("Could not serialize the macro")]
+    Serialize(#[source] serde_json::Error),
+    #[error("Could not deserialize {0}")]
+    Deserialize(String, #[source] serde_json::Error),
+}
+
+#[derive(Default)]
+pub struct KeyMacros {
+    recording: RefCell<Option<Recording>>,
+    macros: RefCell<AHashMap<String, Rc<KeyMacro>>>,
+    replaying: Cell<bool>,
+    replay: Cell<Option<SpawnedFuture<()>>>,
+    /// Incremented by [`KeyMacros::clear`] to ignore macros that finish loading
+    /// afterwards.
+    serial: NumCell<u64>,
+}
+
+struct Recording {
+    name: String,
+    pointer: bool,
+    last_usec: Option<u64>,
+    events: Vec<MacroEvent>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct KeyMacro {
+    events: Vec<MacroEvent>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+enum MacroEvent {
+    Key {
+        delay_ms: u32,
+        key: u32,
+        pressed: bool,
+        mods: [u32; 4],
+    },
+    Button {
+        delay_ms: u32,
+        x: f64,
+        y: f64,
+        button: u32,
+        pressed: bool,
+    },
+}
+
+fn macro_path(name: &str) -> Result<PathBuf, KeyMacroError> {
+    if name.is_empty() || name.starts_with('.') || name.contains('/') {
+        return Err(KeyMacroError::InvalidName);
+    }
+    Ok(data_dir().join("macros").join(format!("{name}.json")))
+}
+
+fn save_macro(path: &Path, data: &[u8]) -> Result<(), KeyMacroError> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(KeyMacroError::CreateDir)?;
+    }
+    std::fs::write(path, data).map_err(|e| KeyMacroError::Write(path.display().to_string(), e))
+}
+
+fn load_macro(path: &Path) -> Result<KeyMacro, KeyMacroError> {
+    let display = || path.display().to_string();
+    let data = std::fs::read(path).map_err(|e| KeyMacroError::Read(display(), e))?;
+    serde_json::from_slice(&data).map_err(|e| KeyMacroError::Deserialize(display(), e))
+}
+
+struct SaveMacroJob {
+    name: String,
+    work: SaveMacroWork,
+}
+
+struct SaveMacroWork {
+    path: PathBuf,
+    data: Vec<u8>,
+    result: Option<Result<(), KeyMacroError>>,
+}
+
+impl CpuJob for SaveMacroJob {
+    fn work(&mut self) -> &mut dyn CpuWork {
+        &mut self.work
+    }
+
+    fn completed(mut self: Box<Self>) {
+        if let Some(Err(e)) = self.work.result.take() {
+            log::error!("Could not save macro {}: {}", self.name, ErrorFmt(e));
+        }
+    }
+}
+
+impl CpuWork for SaveMacroWork {
+    fn run(&mut self) -> Option<Box<dyn AsyncCpuWork>> {
+        zone!("SaveMacroWork");
+        self.result = Some(save_macro(&self.path, &self.data));
+        None
+    }
+}
+
+struct LoadMacroJob {
+    seat: Weak<WlSeatGlobal>,
+    name: String,
+    serial: u64,
+    work: LoadMacroWork,
+}
+
+struct LoadMacroWork {
+    path: PathBuf,
+    result: Option<Result<KeyMacro, KeyMacroError>>,
+}
+
+impl CpuJob for LoadMacroJob {
+    fn work(&mut self) -> &mut dyn CpuWork {
+        &mut self.work
+    }
+
+    fn completed(mut self: Box<Self>) {
+        let Some(seat) = self.seat.upgrade() else {
+            return;
+        };
+        let km = &seat.key_macros;
+        if km.serial.get() != self.serial {
+            return;
+        }
+        match self.work.result.take().unwrap() {
+            Ok(m) => {
+                let m = Rc::new(m);
+                km.macros.borrow_mut().insert(self.name, m.clone());
+                seat.start_macro_replay(m);
+            }
+            Err(e) => {
+                log::error!("Could not load macro {}: {}", self.name, ErrorFmt(e));
+                km.replaying.set(false);
+            }
+        }
+    }
+}
+
+impl CpuWork for LoadMacroWork {
+    fn run(&mut self) -> Option<Box<dyn AsyncCpuWork>> {
+        zone!("LoadMacroWork");
+        self.result = Some(load_macro(&self.path));
+        None
+    }
+}
+
+impl Recording {
+    fn delay_ms(&mut self, time_usec: u64) -> u32 {
+        let last = self.last_usec.replace(time_usec).unwrap_or(time_usec);
+        (time_usec.saturating_sub(last) / 1000) as u32
+    }
+
+    fn key(&mut self, time_usec: u64, key: u32, state: KeyState, mods: &Components) {
+        let pressed = match state {
+            KeyState::Released => false,
+            KeyState::Pressed => true,
+            KeyState::Repeated => return,
+        };
+        let delay_ms = self.delay_ms(time_usec);
+        self.events.push(MacroEvent::Key {
+            delay_ms,
+            key,
+            pressed,
+            mods: [
+                mods.mods_pressed.0,
+                mods.mods_latched.0,
+                mods.mods_locked.0,
+                mods.group_locked.0,
+            ],
+        });
+    }
+
+    fn button(&mut self, time_usec: u64, (x, y): (f64, f64), button: u32, state: ButtonState) {
+        if !self.pointer {
+            return;
+        }
+        let delay_ms = self.delay_ms(time_usec);
+        self.events.push(MacroEvent::Button {
+            delay_ms,
+            x,
+            y,
+            button,
+            pressed: state == ButtonState::Pressed,
+        });
+    }
+}
+
+impl MacroEvent {
+    fn delay_ms(&self) -> u32 {
+        match *self {
+            MacroEvent::Key { delay_ms, .. } => delay_ms,
+            MacroEvent::Button { delay_ms, .. } => delay_ms,
+        }
+    }
+}
+
+/// Applies the modifiers recorded with a key event. Returns whether they changed.
+fn apply_mods(c: &mut Components, mods: [u32; 4]) -> bool {
+    let old = *c;
+    c.mods_pressed.0 = mods[0];
+    c.mods_latched.0 = mods[1];
+    c.mods_locked.0 = mods[2];
+    c.group_locked.0 = mods[3];
+    c.update_effective();
+    old != *c
+}
+
+impl KeyMacros {
+    pub fn clear(&self) {
+        self.recording.take();
+        self.replay.take();
+        self.replaying.set(false);
+        self.serial.fetch_add(1);
+    }
+}
+
+impl WlSeatGlobal {
+    pub(super) fn record_macro_key(
+        &self,
+        time_usec: u64,
+        key: u32,
+        state: KeyState,
+        kb_state: &KeyboardState,
+    ) {
+        if let Some(recording) = &mut *self.key_macros.recording.borrow_mut() {
+            recording.key(time_usec, key, state, &kb_state.mods);
+        }
+    }
+
+    pub(super) fn record_macro_button(&self, time_usec: u64, button: u32, state: ButtonState) {
+        if let Some(recording) = &mut *self.key_macros.recording.borrow_mut() {
+            let (x, y) = self.pointer_cursor.position();
+            recording.button(time_usec, (x.to_f64(), y.to_f64()), button, state);
+        }
+    }
+
+    pub fn start_macro_recording(&self, name: String, pointer: bool) {
+        if let Err(e) = macro_path(&name) {
+            log::error!("Cannot record macro {name}: {}", ErrorFmt(e));
+            return;
+        }
+        *self.key_macros.recording.borrow_mut() = Some(Recording {
+            name,
+            pointer,
+            last_usec: None,
+            events: vec![],
+        });
+    }
+
+    pub fn stop_macro_recording(&self) {
+        let Some(recording) = self.key_macros.recording.take() else {
+            return;
+        };
+        let m = KeyMacro {
+            events: recording.events,
+        };
+        let res = macro_path(&recording.name).and_then(|path| {
+            let data = serde_json::to_vec(&m).map_err(KeyMacroError::Serialize)?;
+            Ok((path, data))
+        });
+        match res {
+            Ok((path, data)) => {
+                let job = SaveMacroJob {
+                    name: recording.name.clone(),
+                    work: SaveMacroWork {
+                        path,
+                        data,
+                        result: None,
+                    },
+                };
+                self.state.cpu_worker.submit(Box::new(job)).detach();
+            }
+            Err(e) => {
+                log::error!("Could not save macro {}: {}", recording.name, ErrorFmt(e));
+            }
+        }
+        self.key_macros
+            .macros
+            .borrow_mut()
+            .insert(recording.name, Rc::new(m));
+    }
+
+    pub fn replay_macro(self: &Rc<Self>, name: &str) {
+        let km = &self.key_macros;
+        if km.recording.borrow().is_some() || km.replaying.get() {
+            log::warn!("Cannot replay macro {name} while a macro is being recorded or replayed");
+            return;
+        }
+        let m = km.macros.borrow().get(name).cloned();
+        km.replaying.set(true);
+        if let Some(m) = m {
+            self.start_macro_replay(m);
+            return;
+        }
+        let path = match macro_path(name) {
+            Ok(p) => p,
+            Err(e) => {
+                log::error!("Could not load macro {name}: {}", ErrorFmt(e));
+                km.replaying.set(false);
+                return;
+            }
+        };
+        let job = LoadMacroJob {
+            seat: Rc::downgrade(self),
+            name: name.to_string(),
+            serial: km.serial.get(),
+            work: LoadMacroWork { path, result: None },
+        };
+        self.state.cpu_worker.submit(Box::new(job)).detach();
+    }
+
+    fn start_macro_replay(self: &Rc<Self>, m: Rc<KeyMacro>) {
+        let km = &self.key_macros;
+        let kb_state = Rc::new(RefCell::new(KeyboardState {
+            id: self.state.keyboard_state_ids.next(),
+            map: self.keymap(),
+            pressed_keys: Default::default(),
+            mods: Default::default(),
+            leds: Default::default(),
+            leds_changed: Default::default(),
+            group_offset: 0,
+        }));
+        let slf = Rc::downgrade(self);
+        let wheel = self.state.wheel.clone();
+        let future = self.state.eng.spawn("replay macro", async move {
+            for event in &m.events {
+                let delay_ms = event.delay_ms();
+                if delay_ms > 0 && wheel.timeout(delay_ms as _).await.is_err() {
+                    break;
+                }
+                let Some(seat) = slf.upgrade() else {
+                    return;
+                };
+                let now = seat.state.now_usec();
+                match *event {
+                    MacroEvent::Key {
+                        key, pressed, mods, ..
+                    } => {
+                        let changed = apply_mods(&mut kb_state.borrow_mut().mods, mods);
+                        if changed {
+                            seat.virtual_modifiers_event(&kb_state);
+                        }
+                        let state = match pressed {
+                            true => KeyState::Pressed,
+                            false => KeyState::Released,
+                        };
+                        seat.virtual_key_event(&kb_state, (now / 1000) as u32, key, state);
+                    }
+                    MacroEvent::Button {
+                        x,
+                        y,
+                        button,
+                        pressed,
+                        ..
+                    } => {
+                        let (x, y) = (Fixed::from_f64(x), Fixed::from_f64(y));
+                        seat.motion_event_abs(now, x, y, CursorPositionType::Motion);
+                        let state = match pressed {
+                            true => ButtonState::Pressed,
+                            false => ButtonState::Released,
+                        };
+                        seat.button_event(now, button, state);
+                    }
+                }
+            }
+            let Some(seat) = slf.upgrade() else {
+                return;
+            };
+            let time = (seat.state.now_usec() / 1000) as u32;
+            let pressed: Vec<_> = kb_state.borrow().pressed_keys.iter().copied().collect();
+            for key in pressed {
+                seat.virtual_key_event(&kb_state, time, key, KeyState::Released);
+            }
+            kb_state.borrow_mut().mods = Default::default();
+            seat.virtual_modifiers_event(&kb_state);
+            seat.key_macros.replaying.set(false);
+        });
+        km.replay.set(Some(future));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::backend::ButtonState;
+use crate::backend::KeyState;
+use crate::ifs::wl_seat::key_macro::KeyMacro;
+use crate::ifs::wl_seat::key_macro::KeyMacroError;
+use crate::ifs::wl_seat::key_macro::MacroEvent;
+use crate::ifs::wl_seat::key_macro::Recording;
+use crate::ifs::wl_seat::key_macro::apply_mods;
+use crate::ifs::wl_seat::key_macro::load_macro;
+use crate::ifs::wl_seat::key_macro::macro_path;
+use crate::ifs::wl_seat::key_macro::save_macro;
+use kbvm::Components;
+
+const A: u32 = 30;
+const BTN_LEFT: u32 = 0x110;
+const SHIFT: u32 = 1;
+
+fn recording(pointer: bool) -> Recording {
+    Recording {
+        name: "test".to_string(),
+        pointer,
+        last_usec: None,
+        events: vec![],
+    }
+}
+
+fn shift() -> Components {
+    let mut c = Components::default();
+    c.mods_pressed.0 = SHIFT;
+    c.update_effective();
+    c
+}
+
+fn sample() -> KeyMacro {
+    KeyMacro {
+        events: vec![
+            MacroEvent::Key {
+                delay_ms: 0,
+                key: A,
+                pressed: true,
+                mods: [SHIFT, 0, 0, 0],
+            },
+            MacroEvent::Button {
+                delay_ms: 25,
+                x: 10.5,
+                y: -3.0,
+                button: BTN_LEFT,
+                pressed: false,
+            },
+        ],
+    }
+}
+
+#[test]
+fn record_keys() {
+    let mut r = recording(false);
+    r.key(1_000_000, A, KeyState::Pressed, &shift());
+    r.key(1_010_000, A, KeyState::Repeated, &shift());
+    r.key(1_120_500, A, KeyState::Released, &Components::default());
+    assert_eq!(
+        r.events,
+        [
+            MacroEvent::Key {
+                delay_ms: 0,
+                key: A,
+                pressed: true,
+                mods: [SHIFT, 0, 0, 0],
+            },
+            MacroEvent::Key {
+                delay_ms: 120,
+                key: A,
+                pressed: false,
+                mods: [0; 4],
+            },
+        ],
+    );
+}
+
+#[test]
+fn record_buttons() {
+    let mut r = recording(true);
+    r.key(0, A, KeyState::Pressed, &Components::default());
+    r.button(50_000, (1.0, 2.0), BTN_LEFT, ButtonState::Pressed);
+    r.button(60_000, (1.0, 2.0), BTN_LEFT, ButtonState::Released);
+    let delays: Vec<_> = r.events.iter().map(|e| e.delay_ms()).collect();
+    assert_eq!(delays, [0, 50, 10]);
+    assert_eq!(
+        r.events[1],
+        MacroEvent::Button {
+            delay_ms: 50,
+            x: 1.0,
+            y: 2.0,
+            button: BTN_LEFT,
+            pressed: true,
+        },
+    );
+}
+
+#[test]
+fn record_buttons_without_pointer() {
+    let mut r = recording(false);
+    r.button(0, (1.0, 2.0), BTN_LEFT, ButtonState::Pressed);
+    assert!(r.events.is_empty());
+    assert_eq!(r.last_usec, None);
+}
+
+#[test]
+fn serialization() {
+    let m = sample();
+    let json = serde_json::to_value(&m).unwrap();
+    assert_eq!(json["events"][0]["type"], "key");
+    assert_eq!(json["events"][1]["type"], "button");
+    let data = serde_json::to_vec(&m).unwrap();
+    assert_eq!(serde_json::from_slice::<KeyMacro>(&data).unwrap(), m);
+}
+
+#[test]
+fn save_and_load() {
+    let dir = std::env::temp_dir().join(format!("jay-key-macro-{}", std::process::id()));
+    let path = dir.join("macros").join("test.json");
+    let data = serde_json::to_vec(&sample()).unwrap();
+    save_macro(&path, &data).unwrap();
+    let res = load_macro(&path);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(res.unwrap(), sample());
+    assert!(matches!(load_macro(&path), Err(KeyMacroError::Read(..))));
+}
+
+#[test]
+fn invalid_names() {
+    for name in ["", ".hidden", "a/b", "../a"] {
+        assert!(matches!(macro_path(name), Err(KeyMacroError::InvalidName)));
+    }
+}
+
+#[test]
+fn replay_mods() {
+    let mut c = Components::default();
+    assert!(apply_mods(&mut c, [SHIFT, 0, 0, 0]));
+    assert_eq!(c, shift());
+    assert!(!apply_mods(&mut c, [SHIFT, 0, 0, 0]));
+    assert!(apply_mods(&mut c, [0; 4]));
+    assert_eq!(c, Components::default());
+}
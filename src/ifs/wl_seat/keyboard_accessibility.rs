@@ -0,0 +1,521 @@
+#[cfg(test)]
+mod tests;
+
+use crate::async_engine::SpawnedFuture;
+use crate::backend::KeyState;
+use crate::bar_elements::BE_STICKY_KEYS;
+use crate::control_center::CCI_INPUT;
+use crate::ifs::wl_seat::PhysicalKeyboard;
+use crate::ifs::wl_seat::WlSeatGlobal;
+use ahash::AHashMap;
+use ahash::AHashSet;
+use jay_config::input::AccessibilityFeedback;
+use kbvm::Keycode;
+use kbvm::evdev;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub const DEFAULT_SLOW_KEYS_DELAY_MS: u32 = 300;
+pub const DEFAULT_BOUNCE_KEYS_DELAY_MS: u32 = 300;
+
+const MODIFIERS: [(Keycode, &str); 8] = [
+    (evdev::LEFTSHIFT, "Shift"),
+    (evdev::RIGHTSHIFT, "Shift"),
+    (evdev::LEFTCTRL, "Ctrl"),
+    (evdev::RIGHTCTRL, "Ctrl"),
+    (evdev::LEFTALT, "Alt"),
+    (evdev::RIGHTALT, "AltGr"),
+    (evdev::LEFTMETA, "Logo"),
+    (evdev::RIGHTMETA, "Logo"),
+];
+
+pub struct KeyboardAccessibility {
+    sticky_keys: Cell<bool>,
+    slow_keys: Cell<bool>,
+    slow_keys_delay_ms: Cell<u32>,
+    bounce_keys: Cell<bool>,
+    bounce_keys_delay_ms: Cell<u32>,
+    inner: RefCell<Inner>,
+}
+
+struct Inner<P = Rc<PhysicalKeyboard>> {
+    last_release: AHashMap<u32, u64>,
+    /// Keys whose press was not forwarded. Their release is dropped as well.
+    suppressed: AHashSet<u32>,
+    slow_pending: AHashMap<u32, SlowKey<P>>,
+    sticky: AHashMap<u32, StickyModifier<P>>,
+    held_modifiers: AHashMap<u32, bool>,
+    held_keys: AHashSet<u32>,
+}
+
+struct SlowKey<P> {
+    phy: P,
+    timeout: Option<SpawnedFuture<()>>,
+}
+
+struct StickyModifier<P> {
+    phy: P,
+    state: StickyState,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum StickyState {
+    Latched,
+    Locking,
+    Locked,
+    Unlocking,
+}
+
+struct Output<P = Rc<PhysicalKeyboard>> {
+    events: Vec<(P, u64, u32, KeyState)>,
+    feedback: Vec<AccessibilityFeedback>,
+    sticky_changed: bool,
+}
+
+impl<P> Default for Inner<P> {
+    fn default() -> Self {
+        Self {
+            last_release: Default::default(),
+            suppressed: Default::default(),
+            slow_pending: Default::default(),
+            sticky: Default::default(),
+            held_modifiers: Default::default(),
+            held_keys: Default::default(),
+        }
+    }
+}
+
+impl<P> Default for Output<P> {
+    fn default() -> Self {
+        Self {
+            events: Default::default(),
+            feedback: Default::default(),
+            sticky_changed: false,
+        }
+    }
+}
+
+fn is_modifier(key: u32) -> bool {
+    modifier_name(key).is_some()
+}
+
+fn modifier_name(key: u32) -> Option<&'static str> {
+    let kc = Keycode::from_evdev(key);
+    MODIFIERS.iter().find(|m| m.0 == kc).map(|m| m.1)
+}
+
+impl Default for KeyboardAccessibility {
+    fn default() -> Self {
+        Self {
+            sticky_keys: Cell::new(false),
+            slow_keys: Cell::new(false),
+            slow_keys_delay_ms: Cell::new(DEFAULT_SLOW_KEYS_DELAY_MS),
+            bounce_keys: Cell::new(false),
+            bounce_keys_delay_ms: Cell::new(DEFAULT_BOUNCE_KEYS_DELAY_MS),
+            inner: Default::default(),
+        }
+    }
+}
+
+impl KeyboardAccessibility {
+    fn is_active(&self) -> bool {
+        self.sticky_keys.get() || self.slow_keys.get() || self.bounce_keys.get()
+    }
+
+    fn has_state(&self) -> bool {
+        let inner = self.inner.borrow();
+        !inner.suppressed.is_empty()
+            || !inner.slow_pending.is_empty()
+            || !inner.sticky.is_empty()
+            || !inner.held_modifiers.is_empty()
+    }
+
+    fn handle_key(
+        &self,
+        seat: &Rc<WlSeatGlobal>,
+        phy: &Rc<PhysicalKeyboard>,
+        time_usec: u64,
+        key: u32,
+        state: KeyState,
+    ) {
+        let mut out = Output::default();
+        {
+            let inner = &mut *self.inner.borrow_mut();
+            self.process(inner, &mut out, phy, time_usec, key, state);
+            if let Some(slow_key) = inner.slow_pending.get_mut(&key)
+                && slow_key.timeout.is_none()
+            {
+                slow_key.timeout = Some(self.slow_key_timeout(seat, key));
+            }
+        }
+        self.flush(seat, out);
+    }
+
+    fn process<P: Clone>(
+        &self,
+        inner: &mut Inner<P>,
+        out: &mut Output<P>,
+        phy: &P,
+        time_usec: u64,
+        key: u32,
+        state: KeyState,
+    ) {
+        if self.bounce(inner, out, time_usec, key, state) && self.slow(inner, out, phy, key, state)
+        {
+            self.sticky(inner, out, phy, time_usec, key, state);
+        }
+    }
+
+    fn bounce<P>(
+        &self,
+        inner: &mut Inner<P>,
+        out: &mut Output<P>,
+        time_usec: u64,
+        key: u32,
+        state: KeyState,
+    ) -> bool {
+        match state {
+            KeyState::Pressed => {
+                if !self.bounce_keys.get() {
+                    return true;
+                }
+                let delay_usec = self.bounce_keys_delay_ms.get() as u64 * 1000;
+                if let Some(&last) = inner.last_release.get(&key)
+                    && time_usec.saturating_sub(last) < delay_usec
+                {
+                    inner.suppressed.insert(key);
+                    out.feedback.push(AccessibilityFeedback::BounceKeyRejected);
+                    return false;
+                }
+                true
+            }
+            KeyState::Released => {
+                if self.bounce_keys.get() {
+                    inner.last_release.insert(key, time_usec);
+                }
+                !inner.suppressed.remove(&key)
+            }
+            KeyState::Repeated => !inner.suppressed.contains(&key),
+        }
+    }
+
+    fn slow<P: Clone>(
+        &self,
+        inner: &mut Inner<P>,
+        out: &mut Output<P>,
+        phy: &P,
+        key: u32,
+        state: KeyState,
+    ) -> bool {
+        match state {
+            KeyState::Pressed => {
+                if !self.slow_keys.get() {
+                    return true;
+                }
+                let slow_key = SlowKey {
+                    phy: phy.clone(),
+                    timeout: None,
+                };
+                inner.slow_pending.insert(key, slow_key);
+                out.feedback.push(AccessibilityFeedback::SlowKeyPressed);
+                false
+            }
+            KeyState::Released => {
+                if inner.slow_pending.remove(&key).is_some() {
+                    out.feedback.push(AccessibilityFeedback::SlowKeyRejected);
+                    return false;
+                }
+                true
+            }
+            KeyState::Repeated => !inner.slow_pending.contains_key(&key),
+        }
+    }
+
+    fn slow_key_timeout(&self, seat: &Rc<WlSeatGlobal>, key: u32) -> SpawnedFuture<()> {
+        let delay_ms = self.slow_keys_delay_ms.get();
+        let slf = Rc::downgrade(seat);
+        let wheel = seat.state.wheel.clone();
+        seat.state.eng.spawn("slow keys", async move {
+            if wheel.timeout(delay_ms as _).await.is_ok()
+                && let Some(seat) = slf.upgrade()
+            {
+                seat.keyboard_accessibility.accept_slow_key(&seat, key);
+            }
+        })
+    }
+
+    fn accept_slow_key(&self, seat: &Rc<WlSeatGlobal>, key: u32) {
+        let mut out = Output::default();
+        let time_usec = seat.state.now_usec();
+        self.accept_slow(&mut self.inner.borrow_mut(), &mut out, time_usec, key);
+        self.flush(seat, out);
+    }
+
+    fn accept_slow<P: Clone>(
+        &self,
+        inner: &mut Inner<P>,
+        out: &mut Output<P>,
+        time_usec: u64,
+        key: u32,
+    ) {
+        let Some(slow_key) = inner.slow_pending.remove(&key) else {
+            return;
+        };
+        out.feedback.push(AccessibilityFeedback::SlowKeyAccepted);
+        self.sticky(inner, out, &slow_key.phy, time_usec, key, KeyState::Pressed);
+    }
+
+    fn sticky<P: Clone>(
+        &self,
+        inner: &mut Inner<P>,
+        out: &mut Output<P>,
+        phy: &P,
+        time_usec: u64,
+        key: u32,
+        state: KeyState,
+    ) {
+        let forward = |out: &mut Output<P>| out.events.push((phy.clone(), time_usec, key, state));
+        if state == KeyState::Repeated {
+            return;
+        }
+        if !self.sticky_keys.get() && inner.sticky.is_empty() && inner.held_modifiers.is_empty() {
+            forward(out);
+            return;
+        }
+        if !is_modifier(key) {
+            match state {
+                KeyState::Pressed => {
+                    for used in inner.held_modifiers.values_mut() {
+                        *used = true;
+                    }
+                    inner.held_keys.insert(key);
+                    forward(out);
+                }
+                _ => {
+                    inner.held_keys.remove(&key);
+                    forward(out);
+                    if inner.held_keys.is_empty() {
+                        self.release_latched(inner, out, time_usec);
+                    }
+                }
+            }
+            return;
+        }
+        if let Some(sticky) = inner.sticky.get_mut(&key) {
+            match (state, sticky.state) {
+                (KeyState::Pressed, StickyState::Latched) => {
+                    sticky.state = StickyState::Locking;
+                }
+                (KeyState::Pressed, StickyState::Locked) => {
+                    sticky.state = StickyState::Unlocking;
+                }
+                (KeyState::Released, StickyState::Locking) => {
+                    sticky.state = StickyState::Locked;
+                    out.feedback.push(AccessibilityFeedback::ModifierLocked);
+                    out.sticky_changed = true;
+                }
+                (KeyState::Released, StickyState::Unlocking) => {
+                    let sticky = inner.sticky.remove(&key).unwrap();
+                    out.events
+                        .push((sticky.phy, time_usec, key, KeyState::Released));
+                    out.feedback.push(AccessibilityFeedback::ModifierReleased);
+                    out.sticky_changed = true;
+                }
+                _ => {}
+            }
+            return;
+        }
+        match state {
+            KeyState::Pressed => {
+                if self.sticky_keys.get() {
+                    inner.held_modifiers.insert(key, false);
+                }
+                forward(out);
+            }
+            _ => {
+                let used = inner.held_modifiers.remove(&key);
+                if used == Some(false) && self.sticky_keys.get() {
+                    inner.sticky.insert(
+                        key,
+                        StickyModifier {
+                            phy: phy.clone(),
+                            state: StickyState::Latched,
+                        },
+                    );
+                    out.feedback.push(AccessibilityFeedback::ModifierLatched);
+                    out.sticky_changed = true;
+                } else {
+                    forward(out);
+                }
+            }
+        }
+    }
+
+    fn release_latched<P: Clone>(&self, inner: &mut Inner<P>, out: &mut Output<P>, time_usec: u64) {
+        let mut released = false;
+        inner.sticky.retain(|&key, sticky| {
+            if sticky.state != StickyState::Latched {
+                return true;
+            }
+            out.events
+                .push((sticky.phy.clone(), time_usec, key, KeyState::Released));
+            released = true;
+            false
+        });
+        if released {
+            out.feedback.push(AccessibilityFeedback::ModifierReleased);
+            out.sticky_changed = true;
+        }
+    }
+
+    fn flush(&self, seat: &Rc<WlSeatGlobal>, out: Output) {
+        for (phy, time_usec, key, state) in out.events {
+            phy.phy_state.update(time_usec, seat, key, state);
+        }
+        if out.sticky_changed {
+            seat.state.bar_elements_changed(BE_STICKY_KEYS);
+        }
+        if !out.feedback.is_empty()
+            && let Some(config) = seat.state.config.get()
+        {
+            for feedback in out.feedback {
+                config.keyboard_accessibility_feedback(seat.id, feedback);
+            }
+        }
+    }
+
+    fn reset(&self, seat: &Rc<WlSeatGlobal>, sticky: bool, slow: bool) {
+        let mut out = Output::default();
+        let time_usec = seat.state.now_usec();
+        self.reset_(
+            &mut self.inner.borrow_mut(),
+            &mut out,
+            time_usec,
+            sticky,
+            slow,
+        );
+        self.flush(seat, out);
+    }
+
+    fn reset_<P>(
+        &self,
+        inner: &mut Inner<P>,
+        out: &mut Output<P>,
+        time_usec: u64,
+        sticky: bool,
+        slow: bool,
+    ) {
+        if slow {
+            // The presses of pending keys were never forwarded. Drop their releases
+            // as well.
+            for (key, _) in inner.slow_pending.drain() {
+                inner.suppressed.insert(key);
+            }
+        }
+        if sticky {
+            inner.held_modifiers.clear();
+            for (key, sticky) in inner.sticky.drain() {
+                out.events
+                    .push((sticky.phy, time_usec, key, KeyState::Released));
+                out.sticky_changed = true;
+            }
+        }
+    }
+
+    pub fn remove_keyboard(&self, phy: &Rc<PhysicalKeyboard>) {
+        let inner = &mut *self.inner.borrow_mut();
+        inner.slow_pending.retain(|_, k| !Rc::ptr_eq(&k.phy, phy));
+        inner.sticky.retain(|_, k| !Rc::ptr_eq(&k.phy, phy));
+    }
+
+    pub fn sticky_modifiers(&self, dst: &mut Vec<(&'static str, bool)>) {
+        let inner = self.inner.borrow();
+        for (&key, sticky) in &inner.sticky {
+            let Some(name) = modifier_name(key) else {
+                continue;
+            };
+            let locked = matches!(sticky.state, StickyState::Locked | StickyState::Unlocking);
+            match dst.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, l)) => *l |= locked,
+                None => dst.push((name, locked)),
+            }
+        }
+        dst.sort_by_key(|(n, _)| MODIFIERS.iter().position(|m| m.1 == *n));
+    }
+}
+
+impl WlSeatGlobal {
+    pub(super) fn accessible_key_event(
+        self: &Rc<Self>,
+        phy: &Rc<PhysicalKeyboard>,
+        time_usec: u64,
+        key: u32,
+        state: KeyState,
+    ) {
+        let ka = &self.keyboard_accessibility;
+        if ka.is_active() || ka.has_state() {
+            ka.handle_key(self, phy, time_usec, key, state);
+        } else {
+            phy.phy_state.update(time_usec, self, key, state);
+        }
+    }
+
+    pub fn sticky_keys_enabled(&self) -> bool {
+        self.keyboard_accessibility.sticky_keys.get()
+    }
+
+    pub fn set_sticky_keys_enabled(self: &Rc<Self>, enabled: bool) {
+        let ka = &self.keyboard_accessibility;
+        if ka.sticky_keys.replace(enabled) && !enabled {
+            ka.reset(self, true, false);
+        }
+        self.state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn slow_keys_enabled(&self) -> bool {
+        self.keyboard_accessibility.slow_keys.get()
+    }
+
+    pub fn set_slow_keys_enabled(self: &Rc<Self>, enabled: bool) {
+        let ka = &self.keyboard_accessibility;
+        if ka.slow_keys.replace(enabled) && !enabled {
+            ka.reset(self, false, true);
+        }
+        self.state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn slow_keys_delay(&self) -> u32 {
+        self.keyboard_accessibility.slow_keys_delay_ms.get()
+    }
+
+    pub fn set_slow_keys_delay(&self, ms: u32) {
+        self.keyboard_accessibility.slow_keys_delay_ms.set(ms);
+        self.state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn bounce_keys_enabled(&self) -> bool {
+        self.keyboard_accessibility.bounce_keys.get()
+    }
+
+    pub fn set_bounce_keys_enabled(&self, enabled: bool) {
+        let ka = &self.keyboard_accessibility;
+        if ka.bounce_keys.replace(enabled) && !enabled {
+            ka.inner.borrow_mut().last_release.clear();
+        }
+        self.state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn bounce_keys_delay(&self) -> u32 {
+        self.keyboard_accessibility.bounce_keys_delay_ms.get()
+    }
+
+    pub fn set_bounce_keys_delay(&self, ms: u32) {
+        self.keyboard_accessibility.bounce_keys_delay_ms.set(ms);
+        self.state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn sticky_modifiers(&self, dst: &mut Vec<(&'static str, bool)>) {
+        self.keyboard_accessibility.sticky_modifiers(dst);
+    }
+}
@@ -0,0 +1,211 @@
+use crate::backend::KeyState;
+use crate::backend::KeyState::Pressed;
+use crate::backend::KeyState::Released;
+use crate::backend::KeyState::Repeated;
+use crate::ifs::wl_seat::keyboard_accessibility::Inner;
+use crate::ifs::wl_seat::keyboard_accessibility::KeyboardAccessibility;
+use crate::ifs::wl_seat::keyboard_accessibility::Output;
+use jay_config::input::AccessibilityFeedback;
+use jay_config::input::AccessibilityFeedback::*;
+use kbvm::evdev;
+
+const KB: u32 = 1;
+const A: u32 = 30;
+const B: u32 = 48;
+
+fn shift() -> u32 {
+    evdev::LEFTSHIFT.to_evdev()
+}
+
+#[derive(Default)]
+struct Harness {
+    ka: KeyboardAccessibility,
+    inner: Inner<u32>,
+    feedback: Vec<AccessibilityFeedback>,
+}
+
+impl Harness {
+    fn run(
+        &mut self,
+        f: impl FnOnce(&KeyboardAccessibility, &mut Inner<u32>, &mut Output<u32>),
+    ) -> Vec<(u32, KeyState)> {
+        let mut out = Output::default();
+        f(&self.ka, &mut self.inner, &mut out);
+        self.feedback.extend(out.feedback);
+        out.events
+            .into_iter()
+            .map(|(phy, _, key, state)| {
+                assert_eq!(phy, KB);
+                (key, state)
+            })
+            .collect()
+    }
+
+    fn key(&mut self, time_ms: u64, key: u32, state: KeyState) -> Vec<(u32, KeyState)> {
+        self.run(|ka, inner, out| ka.process(inner, out, &KB, time_ms * 1000, key, state))
+    }
+
+    fn tap(&mut self, time_ms: u64, key: u32) -> Vec<(u32, KeyState)> {
+        let mut res = self.key(time_ms, key, Pressed);
+        res.extend(self.key(time_ms, key, Released));
+        res
+    }
+
+    fn accept_slow(&mut self, time_ms: u64, key: u32) -> Vec<(u32, KeyState)> {
+        self.run(|ka, inner, out| ka.accept_slow(inner, out, time_ms * 1000, key))
+    }
+
+    fn reset(&mut self, sticky: bool, slow: bool) -> Vec<(u32, KeyState)> {
+        self.run(|ka, inner, out| ka.reset_(inner, out, 0, sticky, slow))
+    }
+
+    fn feedback(&mut self) -> Vec<AccessibilityFeedback> {
+        std::mem::take(&mut self.feedback)
+    }
+}
+
+fn bounce() -> Harness {
+    let h = Harness::default();
+    h.ka.bounce_keys.set(true);
+    h.ka.bounce_keys_delay_ms.set(100);
+    h
+}
+
+fn slow() -> Harness {
+    let h = Harness::default();
+    h.ka.slow_keys.set(true);
+    h
+}
+
+fn sticky() -> Harness {
+    let h = Harness::default();
+    h.ka.sticky_keys.set(true);
+    h
+}
+
+#[test]
+fn disabled() {
+    let mut h = Harness::default();
+    assert_eq!(h.key(0, A, Pressed), [(A, Pressed)]);
+    assert_eq!(h.key(0, A, Repeated), []);
+    assert_eq!(h.key(0, A, Released), [(A, Released)]);
+    assert_eq!(h.feedback(), []);
+}
+
+#[test]
+fn bounce_rejects_quick_press() {
+    let mut h = bounce();
+    assert_eq!(h.tap(0, A), [(A, Pressed), (A, Released)]);
+    assert_eq!(h.key(50, A, Pressed), []);
+    assert_eq!(h.feedback(), [BounceKeyRejected]);
+    assert_eq!(h.key(60, A, Repeated), []);
+    assert_eq!(h.key(60, A, Released), []);
+    assert!(h.inner.suppressed.is_empty());
+    // The delay is measured from the last release, including rejected ones.
+    assert_eq!(h.key(150, A, Pressed), []);
+    assert_eq!(h.key(160, A, Released), []);
+    assert_eq!(h.tap(300, A), [(A, Pressed), (A, Released)]);
+}
+
+#[test]
+fn bounce_is_per_key() {
+    let mut h = bounce();
+    assert_eq!(h.tap(0, A), [(A, Pressed), (A, Released)]);
+    assert_eq!(h.tap(10, B), [(B, Pressed), (B, Released)]);
+    assert_eq!(h.feedback(), []);
+}
+
+#[test]
+fn slow_accepted() {
+    let mut h = slow();
+    assert_eq!(h.key(0, A, Pressed), []);
+    assert_eq!(h.key(10, A, Repeated), []);
+    assert_eq!(h.feedback(), [SlowKeyPressed]);
+    assert_eq!(h.accept_slow(300, A), [(A, Pressed)]);
+    assert_eq!(h.feedback(), [SlowKeyAccepted]);
+    assert_eq!(h.key(400, A, Released), [(A, Released)]);
+}
+
+#[test]
+fn slow_rejected() {
+    let mut h = slow();
+    assert_eq!(h.key(0, A, Pressed), []);
+    assert_eq!(h.key(100, A, Released), []);
+    assert_eq!(h.feedback(), [SlowKeyPressed, SlowKeyRejected]);
+    assert_eq!(h.accept_slow(300, A), []);
+    assert!(h.inner.slow_pending.is_empty());
+}
+
+#[test]
+fn slow_disabled_while_pending() {
+    let mut h = slow();
+    assert_eq!(h.key(0, A, Pressed), []);
+    h.ka.slow_keys.set(false);
+    assert_eq!(h.reset(false, true), []);
+    assert!(h.inner.slow_pending.is_empty());
+    // The press was never forwarded, so the release must not be forwarded either.
+    assert_eq!(h.key(10, A, Repeated), []);
+    assert_eq!(h.key(20, A, Released), []);
+    assert!(h.inner.suppressed.is_empty());
+    assert_eq!(h.tap(30, A), [(A, Pressed), (A, Released)]);
+}
+
+#[test]
+fn sticky_latched() {
+    let mut h = sticky();
+    assert_eq!(h.tap(0, shift()), [(shift(), Pressed)]);
+    assert_eq!(h.feedback(), [ModifierLatched]);
+    assert_eq!(
+        h.tap(10, A),
+        [(A, Pressed), (A, Released), (shift(), Released)],
+    );
+    assert_eq!(h.feedback(), [ModifierReleased]);
+    assert_eq!(h.tap(20, A), [(A, Pressed), (A, Released)]);
+}
+
+#[test]
+fn sticky_locked() {
+    let mut h = sticky();
+    assert_eq!(h.tap(0, shift()), [(shift(), Pressed)]);
+    assert_eq!(h.tap(10, shift()), []);
+    assert_eq!(h.feedback(), [ModifierLatched, ModifierLocked]);
+    assert_eq!(h.tap(20, A), [(A, Pressed), (A, Released)]);
+    assert_eq!(h.tap(30, B), [(B, Pressed), (B, Released)]);
+    assert_eq!(h.tap(40, shift()), [(shift(), Released)]);
+    assert_eq!(h.feedback(), [ModifierReleased]);
+    assert!(h.inner.sticky.is_empty());
+}
+
+#[test]
+fn sticky_used_modifier() {
+    let mut h = sticky();
+    assert_eq!(h.key(0, shift(), Pressed), [(shift(), Pressed)]);
+    assert_eq!(h.tap(10, A), [(A, Pressed), (A, Released)]);
+    assert_eq!(h.key(20, shift(), Released), [(shift(), Released)]);
+    assert_eq!(h.feedback(), []);
+    assert!(h.inner.sticky.is_empty());
+}
+
+#[test]
+fn sticky_disabled_while_latched() {
+    let mut h = sticky();
+    assert_eq!(h.tap(0, shift()), [(shift(), Pressed)]);
+    h.ka.sticky_keys.set(false);
+    assert_eq!(h.reset(true, false), [(shift(), Released)]);
+    assert_eq!(h.tap(10, A), [(A, Pressed), (A, Released)]);
+}
+
+#[test]
+fn slow_and_sticky() {
+    let mut h = slow();
+    h.ka.sticky_keys.set(true);
+    assert_eq!(h.key(0, shift(), Pressed), []);
+    assert_eq!(h.accept_slow(300, shift()), [(shift(), Pressed)]);
+    assert_eq!(h.key(400, shift(), Released), []);
+    assert_eq!(h.key(500, A, Pressed), []);
+    assert_eq!(h.accept_slow(800, A), [(A, Pressed)]);
+    assert_eq!(
+        h.key(900, A, Released),
+        [(A, Released), (shift(), Released)],
+    );
+}
@@ -0,0 +1,368 @@
+#[cfg(test)]
+mod tests;
+
+use crate::async_engine::SpawnedFuture;
+use crate::backend::AXIS_120;
+use crate::backend::AxisSource;
+use crate::backend::ButtonState;
+use crate::backend::KeyState;
+use crate::backend::ScrollAxis;
+use crate::control_center::CCI_INPUT;
+use crate::fixed::Fixed;
+use crate::ifs::wl_seat::BTN_LEFT;
+use crate::ifs::wl_seat::BTN_MIDDLE;
+use crate::ifs::wl_seat::BTN_RIGHT;
+use crate::ifs::wl_seat::PX_PER_SCROLL;
+use crate::ifs::wl_seat::WlSeatGlobal;
+use ahash::AHashMap;
+use ahash::AHashSet;
+use jay_config::input::mouse_keys::MouseKeys as MouseKeysSettings;
+use jay_config::input::mouse_keys::MouseKeysAction;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::f64::consts::FRAC_1_SQRT_2;
+use std::rc::Rc;
+use std::rc::Weak;
+
+const MOTION_INTERVAL_MS: u64 = 10;
+
+pub struct MouseKeys {
+    enabled: Cell<bool>,
+    inner: RefCell<Inner>,
+}
+
+struct Inner {
+    settings: MouseKeysSettings,
+    keys: AHashMap<u32, MouseKeysAction>,
+    consumed: AHashSet<u32>,
+    button: u32,
+    locked: Vec<u32>,
+    moving: AHashMap<u32, (i32, i32)>,
+    motion: Option<Motion>,
+    scrolling: AHashMap<u32, (ScrollAxis, i32)>,
+    scroll: Option<SpawnedFuture<()>>,
+}
+
+struct Motion {
+    start_usec: u64,
+    last_usec: u64,
+    _future: SpawnedFuture<()>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Output {
+    None,
+    Move,
+    Click(u32, usize),
+    Press(u32),
+    Release(Vec<u32>),
+    Scroll,
+}
+
+impl Default for MouseKeys {
+    fn default() -> Self {
+        let settings = MouseKeysSettings::default();
+        Self {
+            enabled: Cell::new(false),
+            inner: RefCell::new(Inner {
+                keys: settings.keys.iter().copied().collect(),
+                settings,
+                consumed: Default::default(),
+                button: BTN_LEFT,
+                locked: Default::default(),
+                moving: Default::default(),
+                motion: Default::default(),
+                scrolling: Default::default(),
+                scroll: Default::default(),
+            }),
+        }
+    }
+}
+
+fn direction(action: MouseKeysAction) -> Option<(i32, i32)> {
+    use MouseKeysAction::*;
+    let dir = match action {
+        MoveLeft => (-1, 0),
+        MoveRight => (1, 0),
+        MoveUp => (0, -1),
+        MoveDown => (0, 1),
+        MoveUpLeft => (-1, -1),
+        MoveUpRight => (1, -1),
+        MoveDownLeft => (-1, 1),
+        MoveDownRight => (1, 1),
+        _ => return None,
+    };
+    Some(dir)
+}
+
+fn scroll_direction(action: MouseKeysAction) -> Option<(ScrollAxis, i32)> {
+    use MouseKeysAction::*;
+    let dir = match action {
+        ScrollUp => (ScrollAxis::Vertical, -1),
+        ScrollDown => (ScrollAxis::Vertical, 1),
+        ScrollLeft => (ScrollAxis::Horizontal, -1),
+        ScrollRight => (ScrollAxis::Horizontal, 1),
+        _ => return None,
+    };
+    Some(dir)
+}
+
+fn motion_delta(
+    settings: &MouseKeysSettings,
+    (x, y): (i32, i32),
+    elapsed: f64,
+    dt: f64,
+) -> (f64, f64) {
+    let (x, y) = (x.clamp(-1, 1) as f64, y.clamp(-1, 1) as f64);
+    let accel_time = settings.acceleration_time_ms as f64 / 1000.0;
+    let progress = match accel_time > 0.0 {
+        true => (elapsed / accel_time).min(1.0),
+        false => 1.0,
+    };
+    let max_speed = settings.max_speed.max(settings.speed);
+    let mut distance = (settings.speed + (max_speed - settings.speed) * progress) * dt;
+    if x != 0.0 && y != 0.0 {
+        distance *= FRAC_1_SQRT_2;
+    }
+    (x * distance, y * distance)
+}
+
+fn scroll_steps(scrolling: &AHashMap<u32, (ScrollAxis, i32)>) -> (i32, i32) {
+    let (mut horizontal, mut vertical) = (0, 0);
+    for &(axis, dir) in scrolling.values() {
+        match axis {
+            ScrollAxis::Horizontal => horizontal += dir,
+            ScrollAxis::Vertical => vertical += dir,
+        }
+    }
+    (horizontal, vertical)
+}
+
+fn button_action(inner: &mut Inner, action: MouseKeysAction) -> Output {
+    match action {
+        MouseKeysAction::Click => Output::Click(inner.button, 1),
+        MouseKeysAction::DoubleClick => Output::Click(inner.button, 2),
+        MouseKeysAction::Press => {
+            if inner.locked.contains(&inner.button) {
+                return Output::None;
+            }
+            inner.locked.push(inner.button);
+            Output::Press(inner.button)
+        }
+        MouseKeysAction::Release => Output::Release(inner.locked.drain(..).collect()),
+        MouseKeysAction::SelectLeftButton => {
+            inner.button = BTN_LEFT;
+            Output::None
+        }
+        MouseKeysAction::SelectMiddleButton => {
+            inner.button = BTN_MIDDLE;
+            Output::None
+        }
+        MouseKeysAction::SelectRightButton => {
+            inner.button = BTN_RIGHT;
+            Output::None
+        }
+        _ => Output::None,
+    }
+}
+
+impl MouseKeys {
+    fn handle_key(&self, seat: &Rc<WlSeatGlobal>, key: u32, state: KeyState) -> bool {
+        let output = {
+            let inner = &mut *self.inner.borrow_mut();
+            match state {
+                KeyState::Pressed => {
+                    if !self.enabled.get() {
+                        return false;
+                    }
+                    let Some(&action) = inner.keys.get(&key) else {
+                        return false;
+                    };
+                    inner.consumed.insert(key);
+                    self.press(seat, inner, key, action)
+                }
+                KeyState::Released => {
+                    if !inner.consumed.remove(&key) {
+                        return false;
+                    }
+                    if inner.moving.remove(&key).is_some() && inner.moving.is_empty() {
+                        inner.motion = None;
+                    }
+                    if inner.scrolling.remove(&key).is_some() && inner.scrolling.is_empty() {
+                        inner.scroll = None;
+                    }
+                    Output::None
+                }
+                KeyState::Repeated => return inner.consumed.contains(&key),
+            }
+        };
+        self.flush(seat, output);
+        true
+    }
+
+    fn press(
+        &self,
+        seat: &Rc<WlSeatGlobal>,
+        inner: &mut Inner,
+        key: u32,
+        action: MouseKeysAction,
+    ) -> Output {
+        if let Some(dir) = direction(action) {
+            inner.moving.insert(key, dir);
+            if inner.motion.is_none() {
+                let now = seat.state.now_usec();
+                inner.motion = Some(Motion {
+                    start_usec: now,
+                    last_usec: now.saturating_sub(MOTION_INTERVAL_MS * 1000),
+                    _future: spawn_repeated(seat, "mouse keys motion", MOTION_INTERVAL_MS, |s| {
+                        s.mouse_keys.move_pointer(s);
+                    }),
+                });
+                return Output::Move;
+            }
+            return Output::None;
+        }
+        if let Some(dir) = scroll_direction(action) {
+            inner.scrolling.insert(key, dir);
+            if inner.scroll.is_none() {
+                let interval = inner.settings.scroll_interval_ms.max(1) as u64;
+                inner.scroll = Some(spawn_repeated(seat, "mouse keys scroll", interval, |s| {
+                    s.mouse_keys.flush(s, Output::Scroll);
+                }));
+            }
+            return Output::Scroll;
+        }
+        button_action(inner, action)
+    }
+
+    fn move_pointer(&self, seat: &Rc<WlSeatGlobal>) {
+        let (dx, dy) = {
+            let inner = &mut *self.inner.borrow_mut();
+            let Some(motion) = &mut inner.motion else {
+                return;
+            };
+            let (mut x, mut y) = (0, 0);
+            for &(dx, dy) in inner.moving.values() {
+                x += dx;
+                y += dy;
+            }
+            let now = seat.state.now_usec();
+            let elapsed = now.saturating_sub(motion.start_usec) as f64 / 1_000_000.0;
+            let dt = now.saturating_sub(motion.last_usec) as f64 / 1_000_000.0;
+            motion.last_usec = now;
+            let (dx, dy) = motion_delta(&inner.settings, (x, y), elapsed, dt);
+            (Fixed::from_f64(dx), Fixed::from_f64(dy))
+        };
+        if dx != Fixed(0) || dy != Fixed(0) {
+            seat.motion_event(seat.state.now_usec(), dx, dy, dx, dy);
+        }
+    }
+
+    fn flush(&self, seat: &Rc<WlSeatGlobal>, output: Output) {
+        let now = seat.state.now_usec();
+        match output {
+            Output::None => {}
+            Output::Move => self.move_pointer(seat),
+            Output::Click(button, count) => {
+                for _ in 0..count {
+                    seat.button_event(now, button, ButtonState::Pressed);
+                    seat.button_event(now, button, ButtonState::Released);
+                }
+            }
+            Output::Press(button) => {
+                seat.button_event(now, button, ButtonState::Pressed);
+            }
+            Output::Release(buttons) => {
+                for button in buttons {
+                    seat.button_event(now, button, ButtonState::Released);
+                }
+            }
+            Output::Scroll => {
+                let (horizontal, vertical) = scroll_steps(&self.inner.borrow().scrolling);
+                if horizontal == 0 && vertical == 0 {
+                    return;
+                }
+                seat.axis_source(AxisSource::Wheel);
+                let steps = [
+                    (ScrollAxis::Horizontal, horizontal),
+                    (ScrollAxis::Vertical, vertical),
+                ];
+                for (axis, steps) in steps {
+                    if steps != 0 {
+                        seat.axis_120(steps * AXIS_120, axis, false);
+                        seat.axis_px(Fixed::from_f64(steps as f64 * PX_PER_SCROLL), axis, false);
+                    }
+                }
+                seat.axis_frame(PX_PER_SCROLL, now);
+            }
+        }
+    }
+
+    fn reset(&self, seat: &Rc<WlSeatGlobal>) {
+        let locked = {
+            let inner = &mut *self.inner.borrow_mut();
+            inner.moving.clear();
+            inner.motion = None;
+            inner.scrolling.clear();
+            inner.scroll = None;
+            inner.locked.drain(..).collect()
+        };
+        self.flush(seat, Output::Release(locked));
+    }
+
+    pub fn clear(&self) {
+        let inner = &mut *self.inner.borrow_mut();
+        inner.motion = None;
+        inner.scroll = None;
+    }
+}
+
+fn spawn_repeated(
+    seat: &Rc<WlSeatGlobal>,
+    name: &'static str,
+    interval_ms: u64,
+    f: impl Fn(&Rc<WlSeatGlobal>) + 'static,
+) -> SpawnedFuture<()> {
+    let slf = Rc::downgrade(seat);
+    let wheel = seat.state.wheel.clone();
+    seat.state.eng.spawn(name, async move {
+        loop {
+            if wheel.timeout(interval_ms).await.is_err() {
+                return;
+            }
+            let Some(seat) = Weak::upgrade(&slf) else {
+                return;
+            };
+            f(&seat);
+        }
+    })
+}
+
+impl WlSeatGlobal {
+    pub(super) fn mouse_keys_event(self: &Rc<Self>, key: u32, state: KeyState) -> bool {
+        self.mouse_keys.handle_key(self, key, state)
+    }
+
+    pub fn mouse_keys_enabled(&self) -> bool {
+        self.mouse_keys.enabled.get()
+    }
+
+    pub fn set_mouse_keys_enabled(self: &Rc<Self>, enabled: bool) {
+        let mk = &self.mouse_keys;
+        if mk.enabled.replace(enabled) && !enabled {
+            mk.reset(self);
+        }
+        self.state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn mouse_keys_settings(&self) -> MouseKeysSettings {
+        self.mouse_keys.inner.borrow().settings.clone()
+    }
+
+    pub fn set_mouse_keys_settings(&self, settings: MouseKeysSettings) {
+        let inner = &mut *self.mouse_keys.inner.borrow_mut();
+        inner.keys = settings.keys.iter().copied().collect();
+        inner.settings = settings;
+        self.state.trigger_cci(CCI_INPUT);
+    }
+}
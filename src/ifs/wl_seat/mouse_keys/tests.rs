@@ -0,0 +1,122 @@
+use crate::backend::ScrollAxis;
+use crate::ifs::wl_seat::BTN_LEFT;
+use crate::ifs::wl_seat::BTN_MIDDLE;
+use crate::ifs::wl_seat::BTN_RIGHT;
+use crate::ifs::wl_seat::mouse_keys::MouseKeys;
+use crate::ifs::wl_seat::mouse_keys::Output;
+use crate::ifs::wl_seat::mouse_keys::button_action;
+use crate::ifs::wl_seat::mouse_keys::direction;
+use crate::ifs::wl_seat::mouse_keys::motion_delta;
+use crate::ifs::wl_seat::mouse_keys::scroll_direction;
+use crate::ifs::wl_seat::mouse_keys::scroll_steps;
+use ahash::AHashMap;
+use jay_config::input::mouse_keys::MouseKeys as MouseKeysSettings;
+use jay_config::input::mouse_keys::MouseKeysAction::*;
+use std::f64::consts::FRAC_1_SQRT_2;
+
+fn settings(speed: f64, max_speed: f64, acceleration_time_ms: u32) -> MouseKeysSettings {
+    MouseKeysSettings {
+        speed,
+        max_speed,
+        acceleration_time_ms,
+        ..Default::default()
+    }
+}
+
+fn assert_close((x, y): (f64, f64), (ex, ey): (f64, f64)) {
+    assert!(
+        (x - ex).abs() < 1e-9 && (y - ey).abs() < 1e-9,
+        "({x}, {y}) != ({ex}, {ey})",
+    );
+}
+
+#[test]
+fn acceleration() {
+    let s = settings(100.0, 500.0, 1000);
+    assert_close(motion_delta(&s, (1, 0), 0.0, 0.01), (1.0, 0.0));
+    assert_close(motion_delta(&s, (1, 0), 0.5, 0.01), (3.0, 0.0));
+    assert_close(motion_delta(&s, (1, 0), 1.0, 0.01), (5.0, 0.0));
+    // The speed does not increase past the maximum speed.
+    assert_close(motion_delta(&s, (1, 0), 10.0, 0.01), (5.0, 0.0));
+    // The distance is proportional to the time since the last update.
+    assert_close(motion_delta(&s, (1, 0), 1.0, 0.02), (10.0, 0.0));
+}
+
+#[test]
+fn no_acceleration() {
+    // Without an acceleration time, the pointer moves at the maximum speed immediately.
+    let s = settings(100.0, 500.0, 0);
+    assert_close(motion_delta(&s, (0, -1), 0.0, 0.01), (0.0, -5.0));
+    // A maximum speed below the initial speed is ignored.
+    let s = settings(100.0, 50.0, 1000);
+    assert_close(motion_delta(&s, (0, 1), 0.0, 0.01), (0.0, 1.0));
+    assert_close(motion_delta(&s, (0, 1), 1.0, 0.01), (0.0, 1.0));
+}
+
+#[test]
+fn diagonal() {
+    let s = settings(100.0, 100.0, 0);
+    let d = FRAC_1_SQRT_2;
+    assert_close(motion_delta(&s, (-1, 1), 0.0, 0.01), (-d, d));
+    // Opposite directions cancel and combined keys move at most one step per axis.
+    assert_close(motion_delta(&s, (0, 0), 0.0, 0.01), (0.0, 0.0));
+    assert_close(motion_delta(&s, (2, 1), 0.0, 0.01), (d, d));
+}
+
+#[test]
+fn directions() {
+    assert_eq!(direction(MoveUpLeft), Some((-1, -1)));
+    assert_eq!(direction(MoveDownRight), Some((1, 1)));
+    assert_eq!(direction(Click), None);
+    assert_eq!(direction(ScrollUp), None);
+    assert_eq!(
+        scroll_direction(ScrollLeft),
+        Some((ScrollAxis::Horizontal, -1)),
+    );
+    assert_eq!(
+        scroll_direction(ScrollDown),
+        Some((ScrollAxis::Vertical, 1))
+    );
+    assert_eq!(scroll_direction(MoveUp), None);
+}
+
+#[test]
+fn scrolling() {
+    let mut scrolling = AHashMap::new();
+    assert_eq!(scroll_steps(&scrolling), (0, 0));
+    scrolling.insert(1, (ScrollAxis::Vertical, -1));
+    scrolling.insert(2, (ScrollAxis::Horizontal, 1));
+    assert_eq!(scroll_steps(&scrolling), (1, -1));
+    scrolling.insert(3, (ScrollAxis::Vertical, 1));
+    assert_eq!(scroll_steps(&scrolling), (1, 0));
+}
+
+#[test]
+fn buttons() {
+    let mk = MouseKeys::default();
+    let inner = &mut *mk.inner.borrow_mut();
+    assert_eq!(button_action(inner, Click), Output::Click(BTN_LEFT, 1));
+    assert_eq!(button_action(inner, SelectRightButton), Output::None);
+    assert_eq!(
+        button_action(inner, DoubleClick),
+        Output::Click(BTN_RIGHT, 2)
+    );
+    assert_eq!(button_action(inner, SelectMiddleButton), Output::None);
+    assert_eq!(button_action(inner, Click), Output::Click(BTN_MIDDLE, 1));
+}
+
+#[test]
+fn press_and_release() {
+    let mk = MouseKeys::default();
+    let inner = &mut *mk.inner.borrow_mut();
+    assert_eq!(button_action(inner, Press), Output::Press(BTN_LEFT));
+    // A button that is already held is not pressed again.
+    assert_eq!(button_action(inner, Press), Output::None);
+    button_action(inner, SelectRightButton);
+    assert_eq!(button_action(inner, Press), Output::Press(BTN_RIGHT));
+    assert_eq!(
+        button_action(inner, Release),
+        Output::Release(vec![BTN_LEFT, BTN_RIGHT]),
+    );
+    assert_eq!(button_action(inner, Release), Output::Release(vec![]));
+}
@@ -100,6 +100,22 @@ impl PointerOwnerHolder {
         self.pending_scroll.stop[axis as usize].set(true);
     }
 
+    pub fn pending_v120(&self, axis: ScrollAxis) -> Option<i32> {
+        self.pending_scroll.v120[axis as usize].get()
+    }
+
+    pub fn discard_pending_scroll(&self) {
+        self.pending_scroll.take();
+    }
+
+    pub fn discard_pending_scroll_axis(&self, axis: ScrollAxis) {
+        self.pending_scroll.discard_axis(axis);
+    }
+
+    pub fn has_pending_scroll(&self) -> bool {
+        self.pending_scroll.has_axis_events()
+    }
+
     pub fn frame(&self, px_per_scroll_wheel: f64, seat: &Rc<WlSeatGlobal>, time_usec: u64) {
         self.pending_scroll.time_usec.set(time_usec);
         let pending = self.pending_scroll.take();
@@ -973,14 +989,7 @@ impl SimplePointerOwnerUsecase for DefaultPointerUsecase {
         seat: &Rc<WlSeatGlobal>,
         tl: &Rc<dyn ToplevelNode>,
     ) {
-        self.start_ui_drag(
-            grab,
-            seat,
-            TileDragUsecase {
-                tl: tl.clone(),
-                destination: Default::default(),
-            },
-        );
+        self.start_ui_drag(grab, seat, TileDragUsecase::new(tl));
     }
 
     fn start_workspace_drag(
@@ -1506,7 +1515,7 @@ impl WindowManagementGrabUsecase for MoveFullscreenToplevelGrabPointerOwner {
     }
 }
 
-trait UiDragUsecase: 'static {
+pub(super) trait UiDragUsecase: 'static {
     fn node_seat_state(&self) -> &NodeSeatState;
     fn left_button_up(&self, seat: &Rc<WlSeatGlobal>);
     fn apply_changes(&self, seat: &Rc<WlSeatGlobal>) -> Option<Rect>;
@@ -1555,7 +1564,7 @@ where
     }
 }
 
-fn handle_ui_drag_highlight(seat: &Rc<WlSeatGlobal>, new_highlight: Option<Rect>) {
+pub(super) fn handle_ui_drag_highlight(seat: &Rc<WlSeatGlobal>, new_highlight: Option<Rect>) {
     let prev_highlight = seat.ui_drag_highlight.replace(new_highlight);
     if prev_highlight != new_highlight {
         if let Some(rect) = prev_highlight {
@@ -1567,11 +1576,46 @@ fn handle_ui_drag_highlight(seat: &Rc<WlSeatGlobal>, new_highlight: Option<Rect>
     }
 }
 
-struct TileDragUsecase {
+pub(super) struct TileDragUsecase {
     tl: Rc<dyn ToplevelNode>,
     destination: Cell<Option<TddType>>,
 }
 
+impl TileDragUsecase {
+    pub(super) fn new(tl: &Rc<dyn ToplevelNode>) -> Self {
+        Self {
+            tl: tl.clone(),
+            destination: Default::default(),
+        }
+    }
+
+    pub(super) fn update_destination(
+        &self,
+        seat: &Rc<WlSeatGlobal>,
+        x: i32,
+        y: i32,
+    ) -> Option<Rect> {
+        if self.tl.tl_data().is_root_container[LiveTL].get() {
+            self.destination.take();
+            return None;
+        }
+        let dest = seat
+            .state
+            .root
+            .tile_drag_destination(self.tl.node_id(), x, y);
+        match dest {
+            None => {
+                self.destination.take();
+                None
+            }
+            Some(d) => {
+                self.destination.set(Some(d.ty));
+                Some(d.highlight)
+            }
+        }
+    }
+}
+
 impl UiDragUsecase for TileDragUsecase {
     fn node_seat_state(&self) -> &NodeSeatState {
         self.tl.node_seat_state()
@@ -1663,26 +1707,8 @@ impl UiDragUsecase for TileDragUsecase {
     }
 
     fn apply_changes(&self, seat: &Rc<WlSeatGlobal>) -> Option<Rect> {
-        if self.tl.tl_data().is_root_container[LiveTL].get() {
-            self.destination.take();
-            return None;
-        }
         let (x, y) = seat.pointer_cursor.position();
-        let dest = seat.state.root.tile_drag_destination(
-            self.tl.node_id(),
-            x.round_down(),
-            y.round_down(),
-        );
-        match dest {
-            None => {
-                self.destination.take();
-                None
-            }
-            Some(d) => {
-                self.destination.set(Some(d.ty));
-                Some(d.highlight)
-            }
-        }
+        self.update_destination(seat, x.round_down(), y.round_down())
     }
 }
 
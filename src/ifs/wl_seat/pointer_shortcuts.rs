@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod tests;
+
+use crate::backend::AXIS_120;
+use crate::backend::ButtonState;
+use crate::backend::ScrollAxis;
+use crate::utils::copyhashmap::CopyHashMap;
+use jay_config::input::InputEventCode;
+use jay_config::input::ModifiedPointerTrigger;
+use jay_config::input::PointerTrigger;
+use jay_config::keyboard::mods::Modifiers;
+use jay_config::keyboard::mods::RELEASE;
+use linearize::LinearizeExt;
+use smallvec::SmallVec;
+use std::cell::Cell;
+
+#[derive(Default)]
+pub struct PointerShortcuts {
+    triggers: CopyHashMap<ModifiedPointerTrigger, ()>,
+    buttons: CopyHashMap<u32, Option<ModifiedPointerTrigger>>,
+    scroll: [Cell<i32>; 2],
+}
+
+pub type ScrollTriggers = SmallVec<[ModifiedPointerTrigger; 2]>;
+
+impl PointerShortcuts {
+    pub fn add(&self, trigger: ModifiedPointerTrigger) {
+        self.triggers.set(trigger, ());
+    }
+
+    pub fn remove(&self, trigger: ModifiedPointerTrigger) {
+        self.triggers.remove(&trigger);
+    }
+
+    pub fn clear(&self) {
+        self.triggers.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triggers.is_empty()
+    }
+
+    /// Matches a button event.
+    ///
+    /// Returns whether the event was consumed and the trigger that should be invoked.
+    /// Releases of buttons whose press was consumed are always consumed.
+    pub fn button(
+        &self,
+        mods: Option<Modifiers>,
+        button: u32,
+        state: ButtonState,
+    ) -> (bool, Option<ModifiedPointerTrigger>) {
+        if state == ButtonState::Released {
+            let Some(release) = self.buttons.remove(&button) else {
+                return (false, None);
+            };
+            let release = release.filter(|r| self.triggers.contains(r));
+            return (true, release);
+        }
+        let Some(mods) = mods else {
+            return (false, None);
+        };
+        let trigger = PointerTrigger::Button(InputEventCode(button));
+        let press = mods | trigger;
+        let release = mods | RELEASE | trigger;
+        let has_press = self.triggers.contains(&press);
+        let has_release = self.triggers.contains(&release);
+        if !has_press && !has_release {
+            return (false, None);
+        }
+        self.buttons.set(button, has_release.then_some(release));
+        (true, has_press.then_some(press))
+    }
+
+    /// Matches the v120 scroll deltas of a frame, indexed by [`ScrollAxis`].
+    ///
+    /// Returns the axes that were consumed and the triggers that should be invoked.
+    /// A trigger fires once the deltas of consecutive frames in the same direction
+    /// add up to a full scroll wheel step.
+    pub fn scroll(&self, mods: Modifiers, v120: [Option<i32>; 2]) -> ([bool; 2], ScrollTriggers) {
+        let mut consumed = [false; 2];
+        let mut triggers = ScrollTriggers::new();
+        for axis in ScrollAxis::variants() {
+            let Some(delta) = v120[axis as usize] else {
+                continue;
+            };
+            let (negative, positive) = match axis {
+                ScrollAxis::Horizontal => (PointerTrigger::ScrollLeft, PointerTrigger::ScrollRight),
+                ScrollAxis::Vertical => (PointerTrigger::ScrollUp, PointerTrigger::ScrollDown),
+            };
+            let trigger = mods | if delta < 0 { negative } else { positive };
+            let acc = &self.scroll[axis as usize];
+            if self.triggers.not_contains(&trigger) {
+                acc.set(0);
+                continue;
+            }
+            consumed[axis as usize] = true;
+            let mut sum = delta;
+            if acc.get().signum() == delta.signum() {
+                sum += acc.get();
+            }
+            if sum.abs() >= AXIS_120 {
+                sum = 0;
+                triggers.push(trigger);
+            }
+            acc.set(sum);
+        }
+        (consumed, triggers)
+    }
+}
@@ -0,0 +1,175 @@
+use crate::backend::ButtonState::Pressed;
+use crate::backend::ButtonState::Released;
+use crate::ifs::wl_seat::pointer_shortcuts::PointerShortcuts;
+use jay_config::input::InputEventCode;
+use jay_config::input::PointerTrigger;
+use jay_config::input::PointerTrigger::ScrollDown;
+use jay_config::input::PointerTrigger::ScrollLeft;
+use jay_config::input::PointerTrigger::ScrollRight;
+use jay_config::input::PointerTrigger::ScrollUp;
+use jay_config::keyboard::mods::ALT;
+use jay_config::keyboard::mods::LOGO;
+use jay_config::keyboard::mods::Modifiers;
+use jay_config::keyboard::mods::RELEASE;
+
+const BTN_SIDE: u32 = 0x113;
+const BTN_EXTRA: u32 = 0x114;
+
+fn button(b: u32) -> PointerTrigger {
+    PointerTrigger::Button(InputEventCode(b))
+}
+
+fn none() -> Modifiers {
+    Modifiers(0)
+}
+
+#[test]
+fn button_press() {
+    let ps = PointerShortcuts::default();
+    ps.add(LOGO | button(BTN_SIDE));
+    assert_eq!(
+        ps.button(Some(LOGO), BTN_SIDE, Pressed),
+        (true, Some(LOGO | button(BTN_SIDE))),
+    );
+    // The release of a consumed press is consumed as well.
+    assert_eq!(ps.button(Some(none()), BTN_SIDE, Released), (true, None));
+    assert_eq!(ps.button(Some(none()), BTN_SIDE, Released), (false, None));
+}
+
+#[test]
+fn button_release() {
+    let ps = PointerShortcuts::default();
+    ps.add(LOGO | RELEASE | button(BTN_SIDE));
+    assert_eq!(ps.button(Some(LOGO), BTN_SIDE, Pressed), (true, None));
+    assert_eq!(
+        ps.button(None, BTN_SIDE, Released),
+        (true, Some(LOGO | RELEASE | button(BTN_SIDE))),
+    );
+}
+
+#[test]
+fn button_press_and_release() {
+    let ps = PointerShortcuts::default();
+    ps.add(none() | button(BTN_SIDE));
+    ps.add(RELEASE | button(BTN_SIDE));
+    assert_eq!(
+        ps.button(Some(none()), BTN_SIDE, Pressed),
+        (true, Some(none() | button(BTN_SIDE))),
+    );
+    assert_eq!(
+        ps.button(None, BTN_SIDE, Released),
+        (true, Some(RELEASE | button(BTN_SIDE))),
+    );
+}
+
+#[test]
+fn button_mismatch() {
+    let ps = PointerShortcuts::default();
+    ps.add(LOGO | button(BTN_SIDE));
+    assert_eq!(ps.button(Some(ALT), BTN_SIDE, Pressed), (false, None));
+    assert_eq!(
+        ps.button(Some(LOGO | ALT), BTN_SIDE, Pressed),
+        (false, None)
+    );
+    assert_eq!(ps.button(Some(LOGO), BTN_EXTRA, Pressed), (false, None));
+    // No modifiers are available, e.g. because the session is locked.
+    assert_eq!(ps.button(None, BTN_SIDE, Pressed), (false, None));
+    assert_eq!(ps.button(None, BTN_SIDE, Released), (false, None));
+}
+
+#[test]
+fn button_removed_while_pressed() {
+    let ps = PointerShortcuts::default();
+    ps.add(RELEASE | button(BTN_SIDE));
+    assert_eq!(ps.button(Some(none()), BTN_SIDE, Pressed), (true, None));
+    ps.clear();
+    assert_eq!(ps.button(None, BTN_SIDE, Released), (true, None));
+}
+
+// Scroll deltas and consumed axes are indexed by `ScrollAxis`, i.e. `[vertical, horizontal]`.
+
+#[test]
+fn scroll_step() {
+    let ps = PointerShortcuts::default();
+    ps.add(LOGO | ScrollDown);
+    ps.add(LOGO | ScrollUp);
+    let (consumed, triggers) = ps.scroll(LOGO, [Some(120), None]);
+    assert_eq!(consumed, [true, false]);
+    assert_eq!(&triggers[..], [LOGO | ScrollDown]);
+    let (consumed, triggers) = ps.scroll(LOGO, [Some(-240), None]);
+    assert_eq!(consumed, [true, false]);
+    assert_eq!(&triggers[..], [LOGO | ScrollUp]);
+}
+
+#[test]
+fn scroll_only_matching_axis_consumed() {
+    let ps = PointerShortcuts::default();
+    ps.add(LOGO | ScrollDown);
+    let (consumed, triggers) = ps.scroll(LOGO, [Some(120), Some(120)]);
+    assert_eq!(consumed, [true, false]);
+    assert_eq!(&triggers[..], [LOGO | ScrollDown]);
+    let (consumed, triggers) = ps.scroll(ALT, [Some(120), Some(120)]);
+    assert_eq!(consumed, [false, false]);
+    assert!(triggers.is_empty());
+}
+
+#[test]
+fn scroll_both_axes() {
+    let ps = PointerShortcuts::default();
+    ps.add(none() | ScrollLeft);
+    ps.add(none() | ScrollDown);
+    let (consumed, triggers) = ps.scroll(none(), [Some(120), Some(-120)]);
+    assert_eq!(consumed, [true, true]);
+    assert_eq!(&triggers[..], [none() | ScrollLeft, none() | ScrollDown]);
+    let (consumed, triggers) = ps.scroll(none(), [None, Some(120)]);
+    assert_eq!(consumed, [false, false]);
+    assert!(triggers.is_empty());
+    assert_eq!(ps.scroll(none(), [None, None]).0, [false, false]);
+}
+
+#[test]
+fn scroll_high_resolution() {
+    let ps = PointerShortcuts::default();
+    ps.add(none() | ScrollRight);
+    for _ in 0..2 {
+        let (consumed, triggers) = ps.scroll(none(), [None, Some(40)]);
+        assert_eq!(consumed, [false, true]);
+        assert!(triggers.is_empty());
+    }
+    let (_, triggers) = ps.scroll(none(), [None, Some(40)]);
+    assert_eq!(&triggers[..], [none() | ScrollRight]);
+    let (_, triggers) = ps.scroll(none(), [None, Some(80)]);
+    assert!(triggers.is_empty());
+}
+
+#[test]
+fn scroll_direction_change_resets() {
+    let ps = PointerShortcuts::default();
+    ps.add(none() | ScrollDown);
+    ps.add(none() | ScrollUp);
+    let (_, triggers) = ps.scroll(none(), [Some(90), None]);
+    assert!(triggers.is_empty());
+    // A full step in the opposite direction triggers immediately.
+    let (_, triggers) = ps.scroll(none(), [Some(-120), None]);
+    assert_eq!(&triggers[..], [none() | ScrollUp]);
+    let (_, triggers) = ps.scroll(none(), [Some(90), None]);
+    assert!(triggers.is_empty());
+    let (_, triggers) = ps.scroll(none(), [Some(-60), None]);
+    assert!(triggers.is_empty());
+    let (_, triggers) = ps.scroll(none(), [Some(90), None]);
+    assert!(triggers.is_empty());
+    let (_, triggers) = ps.scroll(none(), [Some(30), None]);
+    assert_eq!(&triggers[..], [none() | ScrollDown]);
+}
+
+#[test]
+fn scroll_unbound_direction_resets() {
+    let ps = PointerShortcuts::default();
+    ps.add(none() | ScrollDown);
+    let (_, triggers) = ps.scroll(none(), [Some(90), None]);
+    assert!(triggers.is_empty());
+    let (consumed, _) = ps.scroll(none(), [Some(-10), None]);
+    assert_eq!(consumed, [false, false]);
+    let (_, triggers) = ps.scroll(none(), [Some(90), None]);
+    assert!(triggers.is_empty());
+}
@@ -1,3 +1,7 @@
+#[cfg(test)]
+mod tests;
+
+use crate::auto_rotation::transform_normalized;
 use crate::backend::InputDeviceGroupId;
 use crate::backend::InputDeviceId;
 use crate::cursor_user::CursorUser;
@@ -15,12 +19,22 @@ use crate::ifs::wl_seat::tablet::zwp_tablet_tool_v2::ZwpTabletToolV2;
 use crate::ifs::wl_seat::tablet::zwp_tablet_v2::ZwpTabletV2;
 use crate::ifs::wl_surface::WlSurface;
 use crate::object::Version;
+use crate::rect::Rect;
+use crate::state::State;
 use crate::tree::FoundNode;
 use crate::tree::Node;
+use crate::tree::ToplevelIdentifier;
+use crate::tree::Transform;
+use crate::tree::TreeTimeline::LiveTL;
 use crate::utils::bindings::PerClientBindings;
 use crate::utils::clonecell::CloneCell;
 use crate::utils::copyhashmap::CopyHashMap;
 use crate::utils::hash_map_ext::HashMapExt;
+use crate::utils::smallmap::SmallMap;
+use ahash::AHashMap;
+use jay_config::input::tablet::ERASER_MODE_ERASER;
+use jay_config::input::tablet::EraserMode;
+use jay_config::input::tablet::PressureCurve;
 use std::cell::Cell;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -57,6 +71,7 @@ pub struct TabletInit {
     pub vid: u32,
     pub bustype: Option<u32>,
     pub path: String,
+    pub size_mm: Option<(f64, f64)>,
 }
 
 #[derive(Debug, Clone)]
@@ -121,7 +136,7 @@ pub struct Tablet {
     seat: Rc<WlSeatGlobal>,
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum TabletToolType {
     Pen,
     Eraser,
@@ -190,6 +205,7 @@ pub struct TabletPad {
     dials: Vec<Rc<TabletPadDial>>,
     node: CloneCell<Rc<dyn Node>>,
     pub(super) pad_owner: PadOwnerHolder,
+    config_buttons: SmallMap<u32, (), 4>,
 }
 
 pub struct TabletPadGroup {
@@ -204,14 +220,19 @@ pub struct TabletPadGroup {
 
 pub struct TabletPadStrip {
     bindings: TabletBindings<ZwpTabletPadStripV2>,
+    last_position: Cell<Option<f64>>,
+    accum: Cell<f64>,
 }
 
 pub struct TabletPadRing {
     bindings: TabletBindings<ZwpTabletPadRingV2>,
+    last_angle: Cell<Option<f64>>,
+    accum: Cell<f64>,
 }
 
 pub struct TabletPadDial {
     bindings: TabletBindings<ZwpTabletPadDialV2>,
+    accum: Cell<i32>,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -224,6 +245,155 @@ pub enum TabletStripEventSource {
     Finger,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TabletArea {
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+}
+
+impl Default for TabletArea {
+    fn default() -> Self {
+        Self {
+            x1: 0.0,
+            y1: 0.0,
+            x2: 1.0,
+            y2: 1.0,
+        }
+    }
+}
+
+pub struct TabletConfig {
+    pub window: Cell<Option<ToplevelIdentifier>>,
+    pub region: Cell<Option<Rect>>,
+    pub area: Cell<TabletArea>,
+    pub keep_aspect_ratio: Cell<bool>,
+    pub pressure_curves: RefCell<AHashMap<TabletToolType, PressureCurve>>,
+    pub eraser_mode: Cell<EraserMode>,
+}
+
+impl Default for TabletConfig {
+    fn default() -> Self {
+        Self {
+            window: Default::default(),
+            region: Default::default(),
+            area: Default::default(),
+            keep_aspect_ratio: Default::default(),
+            pressure_curves: Default::default(),
+            eraser_mode: Cell::new(ERASER_MODE_ERASER),
+        }
+    }
+}
+
+impl TabletConfig {
+    fn target_rect(&self, state: &State, output_rect: Rect) -> Rect {
+        if let Some(id) = self.window.get()
+            && let Some(tl) = state.toplevels.get(&id)
+            && let Some(tl) = tl.upgrade()
+            && tl.node_visible(LiveTL)
+        {
+            return tl.node_absolute_position(LiveTL);
+        }
+        if let Some(region) = self.region.get() {
+            return region;
+        }
+        output_rect
+    }
+
+    /// Returns the part of the tablet that is mapped to `rect`.
+    fn effective_area(&self, size_mm: Option<(f64, f64)>, rect: Rect) -> TabletArea {
+        let mut area = self.area.get();
+        if !self.keep_aspect_ratio.get() || rect.is_empty() {
+            return area;
+        }
+        let Some((width_mm, height_mm)) = size_mm else {
+            return area;
+        };
+        let area_width = (area.x2 - area.x1) * width_mm;
+        let area_height = (area.y2 - area.y1) * height_mm;
+        if area_width <= 0.0 || area_height <= 0.0 {
+            return area;
+        }
+        let target = rect.width() as f64 / rect.height() as f64;
+        if area_width / area_height > target {
+            let width = area_height * target / width_mm;
+            let center = (area.x1 + area.x2) / 2.0;
+            area.x1 = center - width / 2.0;
+            area.x2 = center + width / 2.0;
+        } else {
+            let height = area_width / target / height_mm;
+            let center = (area.y1 + area.y2) / 2.0;
+            area.y1 = center - height / 2.0;
+            area.y2 = center + height / 2.0;
+        }
+        area
+    }
+
+    pub fn map_position(
+        &self,
+        state: &State,
+        size_mm: Option<(f64, f64)>,
+        output_rect: Rect,
+        transform: Transform,
+        x: f64,
+        y: f64,
+    ) -> (f64, f64) {
+        let rect = self.target_rect(state, output_rect);
+        self.map_to_rect(size_mm, rect, transform, x, y)
+    }
+
+    fn map_to_rect(
+        &self,
+        size_mm: Option<(f64, f64)>,
+        rect: Rect,
+        transform: Transform,
+        x: f64,
+        y: f64,
+    ) -> (f64, f64) {
+        let area = self.effective_area(size_mm, rect);
+        let map = |v: f64, lo: f64, hi: f64| {
+            if hi > lo {
+                ((v - lo) / (hi - lo)).clamp(0.0, 1.0)
+            } else {
+                v
+            }
+        };
+        let x = map(x, area.x1, area.x2);
+        let y = map(y, area.y1, area.y2);
+        let (x, y) = transform_normalized(transform, x, y);
+        (
+            rect.x1() as f64 + rect.width() as f64 * x,
+            rect.y1() as f64 + rect.height() as f64 * y,
+        )
+    }
+
+    pub fn map_pressure(&self, type_: TabletToolType, pressure: f64) -> f64 {
+        match self.pressure_curves.borrow().get(&type_) {
+            Some(curve) => apply_pressure_curve(curve, pressure),
+            None => pressure,
+        }
+    }
+}
+
+fn apply_pressure_curve(curve: &PressureCurve, pressure: f64) -> f64 {
+    let bezier = |t: f64, p1: f64, p2: f64| {
+        let u = 1.0 - t;
+        3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t
+    };
+    let pressure = pressure.clamp(0.0, 1.0);
+    let (mut lo, mut hi) = (0.0, 1.0);
+    for _ in 0..32 {
+        let mid = (lo + hi) / 2.0;
+        if bezier(mid, curve.x1, curve.x2) < pressure {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    bezier((lo + hi) / 2.0, curve.y1, curve.y2).clamp(0.0, 1.0)
+}
+
 #[derive(Debug, Default)]
 pub struct TabletToolChanges {
     pub down: Option<bool>,
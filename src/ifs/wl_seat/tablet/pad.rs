@@ -18,6 +18,7 @@ use crate::ifs::wl_surface::WlSurface;
 use crate::time::usec_to_msec;
 use crate::utils::clonecell::CloneCell;
 use crate::utils::hash_map_ext::HashMapExt;
+use jay_config::input::tablet::TabletPadEvent;
 use std::cell::Cell;
 use std::rc::Rc;
 
@@ -27,18 +28,23 @@ impl WlSeatGlobal {
         for _ in 0..init.strips {
             strips.push(Rc::new(TabletPadStrip {
                 bindings: Default::default(),
+                last_position: Default::default(),
+                accum: Default::default(),
             }));
         }
         let mut rings = Vec::new();
         for _ in 0..init.rings {
             rings.push(Rc::new(TabletPadRing {
                 bindings: Default::default(),
+                last_angle: Default::default(),
+                accum: Default::default(),
             }));
         }
         let mut dials = Vec::new();
         for _ in 0..init.dials {
             dials.push(Rc::new(TabletPadDial {
                 bindings: Default::default(),
+                accum: Default::default(),
             }));
         }
         let mut groups = Vec::new();
@@ -68,6 +74,7 @@ impl WlSeatGlobal {
             dials,
             node: CloneCell::new(self.state.root.clone()),
             pad_owner: Default::default(),
+            config_buttons: Default::default(),
         });
         self.tablet.pads.set(init.id, pad.clone());
         self.tablet_for_each_seat_obj(|s| s.announce_pad(&pad));
@@ -123,7 +130,16 @@ impl WlSeatGlobal {
             self.state.for_each_seat_tester(|t| {
                 t.send_tablet_pad_button(self.id, pad.dev, time_usec, button, state)
             });
-            if pad.tablet.is_some() {
+            let to_config = match state {
+                PadButtonState::Released => pad.config_buttons.remove(&button).is_some(),
+                PadButtonState::Pressed => {
+                    !pad.is_claimed() && pad.config_buttons.insert(button, ()).is_none()
+                }
+            };
+            if to_config {
+                let pressed = state == PadButtonState::Pressed;
+                self.tablet_pad_config_event(&pad, TabletPadEvent::Button { button, pressed });
+            } else if pad.tablet.is_some() {
                 pad.pad_owner.button(&pad, time_usec, button, state);
             }
         }
@@ -141,11 +157,19 @@ impl WlSeatGlobal {
             self.state.for_each_seat_tester(|t| {
                 t.send_tablet_pad_ring(self.id, pad.dev, time_usec, ring, source, angle)
             });
-            if pad.tablet.is_some()
-                && let Some(ring) = pad.rings.get(ring as usize)
-            {
+            let Some(ring_data) = pad.rings.get(ring as usize) else {
+                return;
+            };
+            if !pad.is_claimed() {
+                let steps = ring_data.steps(angle);
+                for _ in 0..steps.unsigned_abs() {
+                    let clockwise = steps > 0;
+                    self.tablet_pad_config_event(&pad, TabletPadEvent::Ring { ring, clockwise });
+                }
+            } else {
+                ring_data.last_angle.take();
                 let node = self.keyboard_node.get();
-                node.node_on_tablet_pad_ring(&pad, ring, source, angle, time_usec);
+                node.node_on_tablet_pad_ring(&pad, ring_data, source, angle, time_usec);
             }
         }
     }
@@ -162,11 +186,19 @@ impl WlSeatGlobal {
             self.state.for_each_seat_tester(|t| {
                 t.send_tablet_pad_strip(self.id, pad.dev, time_usec, strip, source, position)
             });
-            if pad.tablet.is_some()
-                && let Some(strip) = pad.strips.get(strip as usize)
-            {
+            let Some(strip_data) = pad.strips.get(strip as usize) else {
+                return;
+            };
+            if !pad.is_claimed() {
+                let steps = strip_data.steps(position);
+                for _ in 0..steps.unsigned_abs() {
+                    let up = steps < 0;
+                    self.tablet_pad_config_event(&pad, TabletPadEvent::Strip { strip, up });
+                }
+            } else {
+                strip_data.last_position.take();
                 let node = pad.node.get();
-                node.node_on_tablet_pad_strip(&pad, strip, source, position, time_usec);
+                node.node_on_tablet_pad_strip(&pad, strip_data, source, position, time_usec);
             }
         }
     }
@@ -182,17 +214,99 @@ impl WlSeatGlobal {
             self.state.for_each_seat_tester(|t| {
                 t.send_tablet_pad_dial(self.id, pad.dev, time_usec, value120, dial)
             });
-            if pad.tablet.is_some()
-                && let Some(dial) = pad.dials.get(dial as usize)
-            {
+            let Some(dial_data) = pad.dials.get(dial as usize) else {
+                return;
+            };
+            if !pad.is_claimed() {
+                let steps = dial_data.steps(value120);
+                for _ in 0..steps.unsigned_abs() {
+                    let clockwise = steps > 0;
+                    self.tablet_pad_config_event(&pad, TabletPadEvent::Dial { dial, clockwise });
+                }
+            } else {
+                dial_data.accum.set(0);
                 let node = self.keyboard_node.get();
-                node.node_on_tablet_pad_dial(&pad, dial, value120, time_usec);
+                node.node_on_tablet_pad_dial(&pad, dial_data, value120, time_usec);
             }
         }
     }
+
+    fn tablet_pad_config_event(&self, pad: &TabletPad, event: TabletPadEvent) {
+        if let Some(config) = self.state.config.get() {
+            config.tablet_pad_event(self.id, pad.dev, event);
+        }
+    }
+}
+
+const RING_STEP_DEGREES: f64 = 15.0;
+const STRIP_STEP: f64 = 0.1;
+const DIAL_STEP: i32 = 120;
+
+impl TabletPadRing {
+    /// Returns the number of clockwise steps since the last event.
+    fn steps(&self, angle: Option<f64>) -> i32 {
+        let Some(angle) = angle else {
+            self.last_angle.take();
+            self.accum.set(0.0);
+            return 0;
+        };
+        let Some(last) = self.last_angle.replace(Some(angle)) else {
+            return 0;
+        };
+        let mut delta = angle - last;
+        if delta > 180.0 {
+            delta -= 360.0;
+        } else if delta < -180.0 {
+            delta += 360.0;
+        }
+        let accum = self.accum.get() + delta;
+        let steps = (accum / RING_STEP_DEGREES).trunc();
+        self.accum.set(accum - steps * RING_STEP_DEGREES);
+        steps as i32
+    }
+}
+
+impl TabletPadStrip {
+    /// Returns the number of downward steps since the last event.
+    fn steps(&self, position: Option<f64>) -> i32 {
+        let Some(position) = position else {
+            self.last_position.take();
+            self.accum.set(0.0);
+            return 0;
+        };
+        let Some(last) = self.last_position.replace(Some(position)) else {
+            return 0;
+        };
+        let accum = self.accum.get() + position - last;
+        let steps = (accum / STRIP_STEP).trunc();
+        self.accum.set(accum - steps * STRIP_STEP);
+        steps as i32
+    }
+}
+
+impl TabletPadDial {
+    /// Returns the number of clockwise steps since the last event.
+    fn steps(&self, value120: i32) -> i32 {
+        let accum = self.accum.get() + value120;
+        self.accum.set(accum % DIAL_STEP);
+        accum / DIAL_STEP
+    }
 }
 
 impl TabletPad {
+    /// Returns whether the focused client handles events of this pad.
+    fn is_claimed(&self) -> bool {
+        if self.tablet.is_none() {
+            return false;
+        }
+        let Some(surface) = self.node.get().node_into_surface() else {
+            return false;
+        };
+        let mut claimed = false;
+        self.for_each_entered(&surface, |_| claimed = true);
+        claimed
+    }
+
     fn for_each_pair(&self, n: &WlSurface, mut f: impl FnMut(&ZwpTabletV2, &ZwpTabletPadV2)) {
         let Some(tablet) = self.tablet.get() else {
             return;
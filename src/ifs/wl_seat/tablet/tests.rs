@@ -0,0 +1,132 @@
+use crate::ifs::wl_seat::tablet::TabletArea;
+use crate::ifs::wl_seat::tablet::TabletConfig;
+use crate::ifs::wl_seat::tablet::TabletToolType;
+use crate::ifs::wl_seat::tablet::apply_pressure_curve;
+use crate::rect::Rect;
+use crate::tree::Transform;
+use jay_config::input::tablet::PressureCurve;
+
+fn assert_close(v: f64, expected: f64) {
+    assert!((v - expected).abs() < 1e-6, "{v} != {expected}");
+}
+
+fn assert_close2((x, y): (f64, f64), (ex, ey): (f64, f64)) {
+    assert_close(x, ex);
+    assert_close(y, ey);
+}
+
+fn area(x1: f64, y1: f64, x2: f64, y2: f64) -> TabletArea {
+    TabletArea { x1, y1, x2, y2 }
+}
+
+#[test]
+fn full_area() {
+    let config = TabletConfig::default();
+    let rect = Rect::new_sized(100, 200, 1000, 500).unwrap();
+    let map = |x, y| config.map_to_rect(None, rect, Transform::None, x, y);
+    assert_close2(map(0.0, 0.0), (100.0, 200.0));
+    assert_close2(map(0.5, 0.5), (600.0, 450.0));
+    assert_close2(map(1.0, 1.0), (1100.0, 700.0));
+}
+
+#[test]
+fn partial_area() {
+    let config = TabletConfig::default();
+    config.area.set(area(0.25, 0.5, 0.75, 1.0));
+    let rect = Rect::new_sized(0, 0, 1000, 1000).unwrap();
+    let map = |x, y| config.map_to_rect(None, rect, Transform::None, x, y);
+    assert_close2(map(0.25, 0.5), (0.0, 0.0));
+    assert_close2(map(0.5, 0.75), (500.0, 500.0));
+    // Positions outside of the area are clamped to the edges of the rect.
+    assert_close2(map(0.0, 0.0), (0.0, 0.0));
+    assert_close2(map(1.0, 0.9), (1000.0, 800.0));
+}
+
+#[test]
+fn transform() {
+    let config = TabletConfig::default();
+    let rect = Rect::new_sized(0, 0, 1000, 500).unwrap();
+    let map = |t, x, y| config.map_to_rect(None, rect, t, x, y);
+    assert_close2(map(Transform::Rotate90, 0.0, 0.0), (1000.0, 0.0));
+    assert_close2(map(Transform::Rotate90, 0.25, 0.0), (1000.0, 125.0));
+    assert_close2(map(Transform::Rotate180, 0.25, 0.0), (750.0, 500.0));
+}
+
+#[test]
+fn keep_aspect_ratio() {
+    let config = TabletConfig::default();
+    config.keep_aspect_ratio.set(true);
+    // A 4:3 tablet mapped to a 16:9 output uses the full width and the center of the
+    // height.
+    let size = Some((160.0, 120.0));
+    let rect = Rect::new_sized(0, 0, 1600, 900).unwrap();
+    let a = config.effective_area(size, rect);
+    assert_close2((a.x1, a.x2), (0.0, 1.0));
+    assert_close2((a.y1, a.y2), (0.125, 0.875));
+    // A 16:10 tablet mapped to a 4:3 output uses the full height.
+    let size = Some((160.0, 100.0));
+    let rect = Rect::new_sized(0, 0, 1200, 900).unwrap();
+    let a = config.effective_area(size, rect);
+    assert_close2((a.x1, a.x2), (1.0 / 12.0, 11.0 / 12.0));
+    assert_close2((a.y1, a.y2), (0.0, 1.0));
+}
+
+#[test]
+fn keep_aspect_ratio_without_size() {
+    let config = TabletConfig::default();
+    config.keep_aspect_ratio.set(true);
+    let rect = Rect::new_sized(0, 0, 1600, 900).unwrap();
+    assert_eq!(config.effective_area(None, rect), TabletArea::default());
+    config.keep_aspect_ratio.set(false);
+    assert_eq!(
+        config.effective_area(Some((160.0, 120.0)), rect),
+        TabletArea::default(),
+    );
+}
+
+#[test]
+fn pressure_curve() {
+    let linear = PressureCurve {
+        x1: 0.0,
+        y1: 0.0,
+        x2: 1.0,
+        y2: 1.0,
+    };
+    for p in [0.0, 0.1, 0.5, 0.9, 1.0] {
+        assert_close(apply_pressure_curve(&linear, p), p);
+    }
+    assert_close(apply_pressure_curve(&linear, -1.0), 0.0);
+    assert_close(apply_pressure_curve(&linear, 2.0), 1.0);
+    let soft = PressureCurve {
+        x1: 0.0,
+        y1: 0.5,
+        x2: 0.5,
+        y2: 1.0,
+    };
+    let hard = PressureCurve {
+        x1: 0.5,
+        y1: 0.0,
+        x2: 1.0,
+        y2: 0.5,
+    };
+    assert!(apply_pressure_curve(&soft, 0.5) > 0.5);
+    assert!(apply_pressure_curve(&hard, 0.5) < 0.5);
+    assert_close(apply_pressure_curve(&soft, 0.0), 0.0);
+    assert_close(apply_pressure_curve(&soft, 1.0), 1.0);
+}
+
+#[test]
+fn pressure_curve_per_tool() {
+    let config = TabletConfig::default();
+    config.pressure_curves.borrow_mut().insert(
+        TabletToolType::Pen,
+        PressureCurve {
+            x1: 0.0,
+            y1: 0.5,
+            x2: 0.5,
+            y2: 1.0,
+        },
+    );
+    assert!(config.map_pressure(TabletToolType::Pen, 0.5) > 0.5);
+    assert_close(config.map_pressure(TabletToolType::Eraser, 0.5), 0.5);
+}
@@ -13,10 +13,11 @@ use crate::ifs::wl_seat::tablet::normalizeu;
 use crate::ifs::wl_seat::tablet::zwp_tablet_tool_v2::ZwpTabletToolV2;
 use crate::ifs::wl_seat::tablet::zwp_tablet_v2::ZwpTabletV2;
 use crate::ifs::wl_surface::WlSurface;
-use crate::rect::Rect;
+use crate::state::DeviceHandlerData;
 use crate::time::usec_to_msec;
 use crate::utils::clonecell::CloneCell;
 use crate::utils::hash_map_ext::HashMapExt;
+use jay_config::input::tablet::ERASER_MODE_PEN;
 use std::cell::Cell;
 use std::rc::Rc;
 
@@ -37,15 +38,25 @@ impl WlSeatGlobal {
         tool.tablet.tools.remove(&id);
     }
 
-    pub fn tablet_handle_new_tool(self: &Rc<Self>, time_usec: u64, init: &TabletToolInit) {
+    pub fn tablet_handle_new_tool(
+        self: &Rc<Self>,
+        dev: &DeviceHandlerData,
+        time_usec: u64,
+        init: &TabletToolInit,
+    ) {
         let Some(tablet) = self.tablet.tablets.get(&init.tablet_id) else {
             return;
         };
+        let mut type_ = init.type_;
+        if type_ == TabletToolType::Eraser && dev.tablet_config.eraser_mode.get() == ERASER_MODE_PEN
+        {
+            type_ = TabletToolType::Pen;
+        }
         let tool = Rc::new(TabletTool {
             id: init.id,
             opt: Default::default(),
             tablet,
-            type_: init.type_,
+            type_,
             hardware_serial: init.hardware_serial,
             hardware_id_wacom: init.hardware_id_wacom,
             capabilities: init.capabilities.clone(),
@@ -89,10 +100,10 @@ impl WlSeatGlobal {
 
     pub fn tablet_event_tool_changes(
         self: &Rc<Self>,
+        dev: &DeviceHandlerData,
         id: TabletToolId,
         time_usec: u64,
-        rect: Rect,
-        changes: &TabletToolChanges,
+        changes: &mut TabletToolChanges,
     ) {
         let Some(tool) = self.tablet.tools.get(&id) else {
             return;
@@ -100,6 +111,10 @@ impl WlSeatGlobal {
         self.state.for_each_seat_tester(|t| {
             t.send_tablet_tool_changes(self.id, tool.tablet.dev, &tool, time_usec, changes);
         });
+        let config = &dev.tablet_config;
+        if let Some(val) = &mut changes.pressure {
+            *val = config.map_pressure(tool.type_, *val);
+        }
         if let Some(val) = changes.down {
             tool.down.set(val);
         }
@@ -133,9 +148,18 @@ impl WlSeatGlobal {
                 | TabletToolType::Pencil
                 | TabletToolType::Airbrush
                 | TabletToolType::Finger => {
-                    let x = Fixed::from_f64(rect.x1() as f64 + (rect.width() as f64 * delta.x.x));
-                    let y = Fixed::from_f64(rect.y1() as f64 + (rect.height() as f64 * delta.y.x));
-                    (x, y)
+                    let size_mm = dev.tablet_init.as_ref().and_then(|t| t.size_mm);
+                    let rect = dev.get_rect(&self.state);
+                    let transform = dev.get_input_transform();
+                    let (x, y) = config.map_position(
+                        &self.state,
+                        size_mm,
+                        rect,
+                        transform,
+                        delta.x.x,
+                        delta.y.x,
+                    );
+                    (Fixed::from_f64(x), Fixed::from_f64(y))
                 }
             };
             tool.cursor.set_position(x, y);
@@ -1,11 +1,29 @@
+#[cfg(test)]
+mod tests;
+
+use crate::async_engine::SpawnedFuture;
 use crate::fixed::Fixed;
 use crate::ifs::wl_seat::WlSeatGlobal;
+use crate::ifs::wl_seat::pointer_owner::TileDragUsecase;
+use crate::ifs::wl_seat::pointer_owner::UiDragUsecase;
+use crate::ifs::wl_seat::pointer_owner::handle_ui_drag_highlight;
+use crate::rect::Rect;
 use crate::tree::Node;
+use crate::tree::ToplevelNode;
 use crate::tree::TreeTimeline::LiveTL;
 use crate::utils::clonecell::CloneCell;
 use crate::utils::smallmap::SmallMap;
+use crate::utils::smallmap::SmallMapMut;
+use jay_config::input::gesture::TouchGesture;
+use jay_config::input::gesture::TouchGestureKind;
+use std::cell::Cell;
+use std::cell::RefCell;
 use std::rc::Rc;
 
+const LONG_PRESS_MS: u64 = 500;
+const LONG_PRESS_TOLERANCE: f64 = 10.0;
+const PINCH_FACTOR: f64 = 1.25;
+
 pub struct TouchOwnerHolder {
     default: Rc<DefaultTouchOwner>,
     owner: CloneCell<Rc<dyn TouchOwner>>,
@@ -54,7 +72,27 @@ struct DefaultTouchOwner;
 
 struct GrabTouchOwner {
     node: Rc<dyn Node>,
-    down_ids: SmallMap<i32, (), 10>,
+    down_ids: SmallMap<i32, (f64, f64), 10>,
+    origin: (f64, f64),
+    long_press: Cell<Option<SpawnedFuture<()>>>,
+}
+
+struct GestureTouchOwner {
+    edge: Option<TouchGestureKind>,
+    points: RefCell<SmallMapMut<i32, TouchPoint, 10>>,
+    fingers: Cell<u32>,
+    done: Cell<bool>,
+}
+
+#[derive(Copy, Clone)]
+struct TouchPoint {
+    start: (f64, f64),
+    pos: (f64, f64),
+}
+
+struct TileDragTouchOwner {
+    id: i32,
+    usecase: TileDragUsecase,
 }
 
 trait TouchOwner {
@@ -67,15 +105,30 @@ trait TouchOwner {
 
 impl TouchOwner for DefaultTouchOwner {
     fn down(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
-        let node = seat.state.node_at(x.round_down(), y.round_down());
+        let (x_int, y_int) = (x.round_down(), y.round_down());
+        if let Some(edge) = edge_gesture_at(seat, x_int, y_int) {
+            let owner = Rc::new(GestureTouchOwner {
+                edge: Some(edge),
+                points: Default::default(),
+                fingers: Cell::new(0),
+                done: Cell::new(false),
+            });
+            seat.touch_owner.owner.set(owner.clone());
+            owner.down(seat, time_usec, id, x, y);
+            return;
+        }
+        let node = seat.state.node_at(x_int, y_int);
         node.node.node_seat_state().touch_begin(seat);
         node.node.node_restack();
         let owner = Rc::new(GrabTouchOwner {
             node: node.node,
             down_ids: Default::default(),
+            origin: (x.to_f64(), y.to_f64()),
+            long_press: Default::default(),
         });
         seat.touch_owner.owner.set(owner.clone());
         owner.down(seat, time_usec, id, x, y);
+        owner.start_long_press(seat);
     }
 
     fn up(&self, _seat: &Rc<WlSeatGlobal>, _time_usec: u64, _id: i32) {
@@ -107,16 +160,81 @@ impl GrabTouchOwner {
     }
 
     fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        self.long_press.take();
         self.node.node_seat_state().touch_end(seat);
         seat.touch_owner.set_default_owner();
     }
+
+    fn start_long_press(self: &Rc<Self>, seat: &Rc<WlSeatGlobal>) {
+        if !seat.touch_long_press_drag.get() || !seat.state.ui_drag_enabled.get() {
+            return;
+        }
+        let Some(tl) = self.node.clone().node_toplevel() else {
+            return;
+        };
+        if !is_tiled(&tl) {
+            return;
+        }
+        let slf = Rc::downgrade(self);
+        let seat2 = seat.clone();
+        let future = seat.state.eng.spawn("touch long press", async move {
+            if seat2.state.wheel.timeout(LONG_PRESS_MS).await.is_err() {
+                return;
+            }
+            if let Some(slf) = slf.upgrade() {
+                slf.start_tile_drag(&seat2, &tl);
+            }
+        });
+        self.long_press.set(Some(future));
+    }
+
+    fn start_tile_drag(&self, seat: &Rc<WlSeatGlobal>, tl: &Rc<dyn ToplevelNode>) {
+        if !is_tiled(tl) {
+            return;
+        }
+        let Some((id, (x, y))) = self.down_ids.iter().next() else {
+            return;
+        };
+        self.node.node_on_touch_cancel(seat);
+        self.revert_to_default(seat);
+        let owner = Rc::new(TileDragTouchOwner {
+            id,
+            usecase: TileDragUsecase::new(tl),
+        });
+        owner.usecase.node_seat_state().add_ui_drag(seat);
+        seat.touch_owner.owner.set(owner.clone());
+        owner.update(seat, x, y);
+    }
+
+    fn start_gesture(&self, seat: &Rc<WlSeatGlobal>) {
+        self.node.node_on_touch_cancel(seat);
+        self.revert_to_default(seat);
+        let mut points = SmallMapMut::new();
+        for (id, pos) in &self.down_ids {
+            points.insert(id, TouchPoint { start: pos, pos });
+        }
+        let owner = Rc::new(GestureTouchOwner {
+            edge: None,
+            fingers: Cell::new(points.len() as u32),
+            points: RefCell::new(points),
+            done: Cell::new(false),
+        });
+        seat.touch_owner.owner.set(owner);
+    }
 }
 
 impl TouchOwner for GrabTouchOwner {
     fn down(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
-        if self.down_ids.insert(id, ()).is_some() {
+        if self.down_ids.insert(id, (x.to_f64(), y.to_f64())).is_some() {
             return;
         }
+        if self.down_ids.len() > 1 {
+            self.long_press.take();
+            if seat.has_touch_gesture_binding(self.down_ids.len() as u32) {
+                self.start_gesture(seat);
+                return;
+            }
+        }
         let (x, y) = self.translate(x, y);
         self.node
             .clone()
@@ -127,6 +245,7 @@ impl TouchOwner for GrabTouchOwner {
         if self.down_ids.remove(&id).is_none() {
             return;
         }
+        self.long_press.take();
         self.node.clone().node_on_touch_up(seat, time_usec, id);
     }
 
@@ -134,6 +253,11 @@ impl TouchOwner for GrabTouchOwner {
         if !self.down_ids.contains(&id) {
             return;
         }
+        let pos = (x.to_f64(), y.to_f64());
+        self.down_ids.insert(id, pos);
+        if (pos.0 - self.origin.0).hypot(pos.1 - self.origin.1) > LONG_PRESS_TOLERANCE {
+            self.long_press.take();
+        }
         let (x, y) = self.translate(x, y);
         self.node
             .clone()
@@ -152,3 +276,196 @@ impl TouchOwner for GrabTouchOwner {
         self.revert_to_default(seat);
     }
 }
+
+impl GestureTouchOwner {
+    fn recognize(&self, distance: f64) -> Option<TouchGestureKind> {
+        let points = self.points.borrow();
+        if points.is_empty() {
+            return None;
+        }
+        let n = points.len() as f64;
+        let mut start = (0.0, 0.0);
+        let mut pos = (0.0, 0.0);
+        for (_, p) in &*points {
+            start.0 += p.start.0 / n;
+            start.1 += p.start.1 / n;
+            pos.0 += p.pos.0 / n;
+            pos.1 += p.pos.1 / n;
+        }
+        let dx = pos.0 - start.0;
+        let dy = pos.1 - start.1;
+        if let Some(edge) = self.edge {
+            let progress = match edge {
+                TouchGestureKind::FromLeftEdge => dx,
+                TouchGestureKind::FromRightEdge => -dx,
+                TouchGestureKind::FromTopEdge => dy,
+                _ => -dy,
+            };
+            return (progress >= distance).then_some(edge);
+        }
+        if points.len() > 1 {
+            let spread = |center: (f64, f64), get: fn(&TouchPoint) -> (f64, f64)| {
+                let sum: f64 = points
+                    .iter()
+                    .map(|(_, p)| {
+                        let (x, y) = get(p);
+                        (x - center.0).hypot(y - center.1)
+                    })
+                    .sum();
+                sum / n
+            };
+            let initial = spread(start, |p| p.start);
+            let current = spread(pos, |p| p.pos);
+            if initial > 0.0 {
+                let ratio = current / initial;
+                if ratio >= PINCH_FACTOR {
+                    return Some(TouchGestureKind::PinchOut);
+                }
+                if ratio <= 1.0 / PINCH_FACTOR {
+                    return Some(TouchGestureKind::PinchIn);
+                }
+            }
+        }
+        if dx.abs().max(dy.abs()) < distance {
+            return None;
+        }
+        let kind = if dx.abs() > dy.abs() {
+            match dx > 0.0 {
+                true => TouchGestureKind::SwipeRight,
+                false => TouchGestureKind::SwipeLeft,
+            }
+        } else {
+            match dy > 0.0 {
+                true => TouchGestureKind::SwipeDown,
+                false => TouchGestureKind::SwipeUp,
+            }
+        };
+        Some(kind)
+    }
+
+    fn detect(&self, seat: &Rc<WlSeatGlobal>) {
+        if self.done.get() {
+            return;
+        }
+        let Some(kind) = self.recognize(seat.gesture_swipe_distance.get()) else {
+            return;
+        };
+        self.done.set(true);
+        seat.invoke_touch_gesture(TouchGesture::new(kind, self.fingers.get()));
+    }
+}
+
+impl TouchOwner for GestureTouchOwner {
+    fn down(&self, _seat: &Rc<WlSeatGlobal>, _time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+        let pos = (x.to_f64(), y.to_f64());
+        let points = &mut *self.points.borrow_mut();
+        points.insert(id, TouchPoint { start: pos, pos });
+        if !self.done.get() {
+            self.fingers
+                .set(self.fingers.get().max(points.len() as u32));
+        }
+    }
+
+    fn up(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, id: i32) {
+        self.detect(seat);
+        let points = &mut *self.points.borrow_mut();
+        points.remove(&id);
+        if points.is_empty() {
+            seat.touch_owner.set_default_owner();
+        }
+    }
+
+    fn motion(&self, _seat: &Rc<WlSeatGlobal>, _time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+        if let Some(point) = self.points.borrow_mut().get_mut(&id) {
+            point.pos = (x.to_f64(), y.to_f64());
+        }
+    }
+
+    fn frame(&self, seat: &Rc<WlSeatGlobal>) {
+        self.detect(seat);
+    }
+
+    fn cancel(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.touch_owner.set_default_owner();
+    }
+}
+
+impl TileDragTouchOwner {
+    fn update(&self, seat: &Rc<WlSeatGlobal>, x: f64, y: f64) {
+        let highlight = self
+            .usecase
+            .update_destination(seat, x.floor() as i32, y.floor() as i32);
+        handle_ui_drag_highlight(seat, highlight);
+    }
+
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>, needs_layout: bool) {
+        self.usecase.node_seat_state().remove_ui_drag(seat);
+        handle_ui_drag_highlight(seat, None);
+        seat.touch_owner.set_default_owner();
+        seat.trigger_tree_changed(needs_layout);
+    }
+}
+
+impl TouchOwner for TileDragTouchOwner {
+    fn down(&self, _seat: &Rc<WlSeatGlobal>, _time_usec: u64, _id: i32, _x: Fixed, _y: Fixed) {
+        // nothing
+    }
+
+    fn up(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, id: i32) {
+        if id != self.id {
+            return;
+        }
+        self.usecase.left_button_up(seat);
+        self.revert_to_default(seat, true);
+    }
+
+    fn motion(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+        if id == self.id {
+            self.update(seat, x.to_f64(), y.to_f64());
+        }
+    }
+
+    fn frame(&self, _seat: &Rc<WlSeatGlobal>) {
+        // nothing
+    }
+
+    fn cancel(&self, seat: &Rc<WlSeatGlobal>) {
+        self.revert_to_default(seat, false);
+    }
+}
+
+fn is_tiled(tl: &Rc<dyn ToplevelNode>) -> bool {
+    let data = tl.tl_data();
+    data.parent.is_some()
+        && data.float.is_none()
+        && !data.is_root_container[LiveTL].get()
+        && !data.is_fullscreen[LiveTL].get()
+}
+
+fn edge_gesture_at(seat: &Rc<WlSeatGlobal>, x: i32, y: i32) -> Option<TouchGestureKind> {
+    let outputs = seat.state.root.outputs.lock();
+    let output = outputs
+        .values()
+        .find(|o| o.node_state[LiveTL].pos.get().contains(x, y))?;
+    let rect = output.node_state[LiveTL].pos.get();
+    let width = output.global.persistent.touch_edge_width.get();
+    let kind = edge_zone(rect, width, x, y)?;
+    seat.has_touch_edge_gesture_binding(kind).then_some(kind)
+}
+
+/// Returns the edge zone of the output containing the point. The left and right edges
+/// take precedence over the top and bottom edges in the corners.
+fn edge_zone(rect: Rect, width: i32, x: i32, y: i32) -> Option<TouchGestureKind> {
+    let kind = if x < rect.x1() + width {
+        TouchGestureKind::FromLeftEdge
+    } else if x >= rect.x2() - width {
+        TouchGestureKind::FromRightEdge
+    } else if y < rect.y1() + width {
+        TouchGestureKind::FromTopEdge
+    } else if y >= rect.y2() - width {
+        TouchGestureKind::FromBottomEdge
+    } else {
+        return None;
+    };
+    Some(kind)
+}
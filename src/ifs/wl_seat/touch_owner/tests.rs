@@ -0,0 +1,141 @@
+use crate::ifs::wl_seat::touch_owner::GestureTouchOwner;
+use crate::ifs::wl_seat::touch_owner::TouchPoint;
+use crate::ifs::wl_seat::touch_owner::edge_zone;
+use crate::rect::Rect;
+use jay_config::input::gesture::TouchGestureKind;
+use jay_config::input::gesture::TouchGestureKind::*;
+use std::cell::Cell;
+
+const DISTANCE: f64 = 100.0;
+
+#[test]
+fn edge_zones() {
+    let rect = Rect::new_sized(1000, 0, 800, 600).unwrap();
+    let zone = |x, y| edge_zone(rect, 10, x, y);
+    assert_eq!(zone(1000, 300), Some(FromLeftEdge));
+    assert_eq!(zone(1009, 300), Some(FromLeftEdge));
+    assert_eq!(zone(1010, 300), None);
+    assert_eq!(zone(1789, 300), None);
+    assert_eq!(zone(1790, 300), Some(FromRightEdge));
+    assert_eq!(zone(1799, 300), Some(FromRightEdge));
+    assert_eq!(zone(1400, 0), Some(FromTopEdge));
+    assert_eq!(zone(1400, 9), Some(FromTopEdge));
+    assert_eq!(zone(1400, 10), None);
+    assert_eq!(zone(1400, 590), Some(FromBottomEdge));
+    assert_eq!(zone(1400, 599), Some(FromBottomEdge));
+    // Corners belong to the left and right edges.
+    assert_eq!(zone(1000, 0), Some(FromLeftEdge));
+    assert_eq!(zone(1799, 599), Some(FromRightEdge));
+}
+
+#[test]
+fn edge_zones_disabled() {
+    let rect = Rect::new_sized(0, 0, 800, 600).unwrap();
+    for (x, y) in [(0, 0), (0, 300), (799, 300), (400, 0), (400, 599)] {
+        assert_eq!(edge_zone(rect, 0, x, y), None);
+    }
+}
+
+fn owner(edge: Option<TouchGestureKind>, points: &[((f64, f64), (f64, f64))]) -> GestureTouchOwner {
+    let owner = GestureTouchOwner {
+        edge,
+        points: Default::default(),
+        fingers: Cell::new(points.len() as u32),
+        done: Cell::new(false),
+    };
+    for (id, &(start, pos)) in points.iter().enumerate() {
+        owner
+            .points
+            .borrow_mut()
+            .insert(id as i32, TouchPoint { start, pos });
+    }
+    owner
+}
+
+fn recognize(
+    edge: Option<TouchGestureKind>,
+    points: &[((f64, f64), (f64, f64))],
+) -> Option<TouchGestureKind> {
+    owner(edge, points).recognize(DISTANCE)
+}
+
+#[test]
+fn edge_swipe() {
+    let from_left = |dx| recognize(Some(FromLeftEdge), &[((0.0, 300.0), (dx, 310.0))]);
+    assert_eq!(from_left(99.0), None);
+    assert_eq!(from_left(100.0), Some(FromLeftEdge));
+    assert_eq!(
+        recognize(Some(FromRightEdge), &[((799.0, 300.0), (600.0, 300.0))]),
+        Some(FromRightEdge),
+    );
+    assert_eq!(
+        recognize(Some(FromTopEdge), &[((400.0, 0.0), (400.0, 150.0))]),
+        Some(FromTopEdge),
+    );
+    assert_eq!(
+        recognize(Some(FromBottomEdge), &[((400.0, 599.0), (400.0, 450.0))]),
+        Some(FromBottomEdge),
+    );
+    // Moving away from the screen does not trigger the gesture.
+    assert_eq!(
+        recognize(Some(FromBottomEdge), &[((400.0, 599.0), (400.0, 750.0))]),
+        None,
+    );
+}
+
+#[test]
+fn swipe() {
+    let swipe = |dx, dy| {
+        recognize(
+            None,
+            &[
+                ((100.0, 100.0), (100.0 + dx, 100.0 + dy)),
+                ((200.0, 100.0), (200.0 + dx, 100.0 + dy)),
+            ],
+        )
+    };
+    assert_eq!(swipe(50.0, 50.0), None);
+    assert_eq!(swipe(120.0, 30.0), Some(SwipeRight));
+    assert_eq!(swipe(-120.0, 30.0), Some(SwipeLeft));
+    assert_eq!(swipe(30.0, 120.0), Some(SwipeDown));
+    assert_eq!(swipe(30.0, -120.0), Some(SwipeUp));
+}
+
+#[test]
+fn swipe_uses_the_centroid() {
+    // The fingers move different distances, so the centroid moves the average distance.
+    let points = [((0.0, 0.0), (120.0, 0.0)), ((0.0, 400.0), (60.0, 400.0))];
+    assert_eq!(recognize(None, &points), None);
+    let points = [((0.0, 0.0), (150.0, 0.0)), ((0.0, 400.0), (50.0, 400.0))];
+    assert_eq!(recognize(None, &points), Some(SwipeRight));
+}
+
+#[test]
+fn pinch() {
+    let pinch = |spread: f64| {
+        recognize(
+            None,
+            &[
+                ((400.0, 300.0), (400.0 - spread, 300.0)),
+                ((500.0, 300.0), (500.0 + spread, 300.0)),
+            ],
+        )
+    };
+    // The initial spread around the centroid is 50.
+    assert_eq!(pinch(12.0), None);
+    assert_eq!(pinch(12.5), Some(PinchOut));
+    assert_eq!(pinch(-9.0), None);
+    assert_eq!(pinch(-10.0), Some(PinchIn));
+}
+
+#[test]
+fn pinch_takes_precedence_over_swipe() {
+    let points = [((0.0, 0.0), (150.0, 0.0)), ((100.0, 0.0), (400.0, 0.0))];
+    assert_eq!(recognize(None, &points), Some(PinchOut));
+}
+
+#[test]
+fn single_finger_cannot_pinch() {
+    assert_eq!(recognize(None, &[((0.0, 0.0), (50.0, 0.0))]), None);
+    assert_eq!(recognize(None, &[]), None);
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests;
+
+use crate::bar_elements::escape_pango;
+use crate::control_center::CCI_INPUT;
+use crate::ifs::wl_seat::WlSeatGlobal;
+use crate::output_overlay::OutputOverlay;
+use crate::output_overlay::OverlayKind;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::rc::Rc;
+
+pub const DEFAULT_WHICH_KEY_DELAY_MS: u32 = 500;
+
+pub struct WhichKey {
+    enabled: Cell<bool>,
+    delay_ms: Cell<u32>,
+    content: RefCell<Content>,
+    overlay: OutputOverlay,
+}
+
+#[derive(Default)]
+struct Content {
+    title: String,
+    entries: Vec<(String, String)>,
+}
+
+impl Default for WhichKey {
+    fn default() -> Self {
+        Self {
+            enabled: Cell::new(false),
+            delay_ms: Cell::new(DEFAULT_WHICH_KEY_DELAY_MS),
+            content: Default::default(),
+            overlay: OutputOverlay::new(OverlayKind::WhichKey),
+        }
+    }
+}
+
+impl Content {
+    fn markup(&self) -> String {
+        let width = self
+            .entries
+            .iter()
+            .map(|(k, _)| k.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut s = String::new();
+        if !self.title.is_empty() {
+            s.push_str("<b>");
+            escape_pango(&self.title, &mut s);
+            s.push_str("</b>\n");
+        }
+        for (idx, (key, description)) in self.entries.iter().enumerate() {
+            if idx > 0 {
+                s.push('\n');
+            }
+            s.push_str("<tt>");
+            escape_pango(key, &mut s);
+            let _ = write!(s, "{:1$}</tt>  ", "", width - key.chars().count());
+            escape_pango(description, &mut s);
+        }
+        s
+    }
+}
+
+impl WhichKey {
+    fn schedule(&self, seat: &Rc<WlSeatGlobal>) {
+        self.overlay.hide();
+        if !self.enabled.get() || self.content.borrow().entries.is_empty() {
+            return;
+        }
+        let delay_ms = self.delay_ms.get();
+        let slf = Rc::downgrade(seat);
+        let wheel = seat.state.wheel.clone();
+        let future = seat.state.eng.spawn("which-key", async move {
+            if wheel.timeout(delay_ms as _).await.is_err() {
+                return;
+            }
+            if let Some(seat) = slf.upgrade() {
+                seat.which_key.show(&seat).await;
+            }
+        });
+        self.overlay.set_pending(future);
+    }
+
+    async fn show(&self, seat: &Rc<WlSeatGlobal>) {
+        let output = seat.get_fallback_output();
+        let markup = self.content.borrow().markup();
+        self.overlay.show(&seat.state, &output, &markup).await;
+    }
+
+    pub fn clear(&self) {
+        self.overlay.clear();
+    }
+}
+
+impl WlSeatGlobal {
+    pub fn which_key_enabled(&self) -> bool {
+        self.which_key.enabled.get()
+    }
+
+    pub fn set_which_key_enabled(self: &Rc<Self>, enabled: bool) {
+        if self.which_key.enabled.replace(enabled) != enabled {
+            self.which_key.schedule(self);
+        }
+        self.state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn which_key_delay(&self) -> u32 {
+        self.which_key.delay_ms.get()
+    }
+
+    pub fn set_which_key_delay(&self, ms: u32) {
+        self.which_key.delay_ms.set(ms);
+        self.state.trigger_cci(CCI_INPUT);
+    }
+
+    pub fn set_which_key(self: &Rc<Self>, title: String, entries: Vec<(String, String)>) {
+        *self.which_key.content.borrow_mut() = Content { title, entries };
+        self.which_key.schedule(self);
+    }
+}
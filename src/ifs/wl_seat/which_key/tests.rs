@@ -0,0 +1,36 @@
+use crate::ifs::wl_seat::which_key::Content;
+
+fn content(title: &str, entries: &[(&str, &str)]) -> Content {
+    Content {
+        title: title.to_string(),
+        entries: entries
+            .iter()
+            .map(|(k, d)| (k.to_string(), d.to_string()))
+            .collect(),
+    }
+}
+
+#[test]
+fn markup() {
+    let c = content("resize", &[("h", "shrink"), ("alt-l", "grow")]);
+    assert_eq!(
+        c.markup(),
+        "<b>resize</b>\n<tt>h    </tt>  shrink\n<tt>alt-l</tt>  grow",
+    );
+}
+
+#[test]
+fn no_title() {
+    let c = content("", &[("q", "close")]);
+    assert_eq!(c.markup(), "<tt>q</tt>  close");
+    assert_eq!(content("", &[]).markup(), "");
+}
+
+#[test]
+fn escaping() {
+    let c = content("<mode>", &[("ä", "a & b"), ("ctrl-<", "")]);
+    assert_eq!(
+        c.markup(),
+        "<b>&lt;mode&gt;</b>\n<tt>ä     </tt>  a &amp; b\n<tt>ctrl-&lt;</tt>  ",
+    );
+}
@@ -1,3 +1,4 @@
+use crate::backend::ScrollAxis;
 use crate::client::ClientError;
 use crate::cursor::Cursor;
 use crate::fixed::Fixed;
@@ -69,6 +70,22 @@ impl PendingScroll {
             time_usec: Cell::new(self.time_usec.take()),
         }
     }
+
+    pub fn discard_axis(&self, axis: ScrollAxis) {
+        let axis = axis as usize;
+        self.v120[axis].take();
+        self.inverted[axis].take();
+        self.px[axis].take();
+        self.stop[axis].take();
+    }
+
+    pub fn has_axis_events(&self) -> bool {
+        (0..2).any(|axis| {
+            self.v120[axis].get().is_some()
+                || self.px[axis].get().is_some()
+                || self.stop[axis].get()
+        })
+    }
 }
 
 pub struct WlPointer {
@@ -90,6 +90,7 @@ impl ZwpVirtualKeyboardManagerV1RequestHandler for ZwpVirtualKeyboardManagerV1 {
                 mods: Default::default(),
                 leds: Default::default(),
                 leds_changed: Default::default(),
+                group_offset: 0,
             })),
         });
         track!(self.client, kb);
@@ -27,19 +27,61 @@ pub struct ZwpVirtualKeyboardV1 {
     pub kb_state: Rc<RefCell<KeyboardState>>,
 }
 
-impl ZwpVirtualKeyboardV1 {
-    fn for_each_kb<F>(&self, mut f: F)
+impl WlSeatGlobal {
+    fn for_each_virtual_kb<F>(&self, mut f: F)
     where
         F: FnMut(u64, &WlSurface, &Rc<WlKeyboard>),
     {
-        let Some(surface) = self.seat.keyboard_node.get().node_into_surface() else {
+        let Some(surface) = self.keyboard_node.get().node_into_surface() else {
             return;
         };
         let serial = surface.client.next_serial();
-        self.seat.surface_kb_event(Version::ALL, &surface, |kb| {
+        self.surface_kb_event(Version::ALL, &surface, |kb| {
             f(serial, &surface, kb);
         });
     }
+
+    /// Sends a key event of a virtual keyboard to the focused surface.
+    ///
+    /// Presses of pressed keys and releases of released keys are ignored.
+    pub fn virtual_key_event(
+        &self,
+        kb_state_rc: &Rc<RefCell<KeyboardState>>,
+        time: u32,
+        key: u32,
+        state: KeyState,
+    ) {
+        let kb_state = &mut *kb_state_rc.borrow_mut();
+        let contains = kb_state.pressed_keys.contains(&key);
+        let valid = match state {
+            KeyState::Released => contains,
+            _ => !contains,
+        };
+        if !valid {
+            return;
+        }
+        self.for_each_virtual_kb(|serial, surface, kb| {
+            kb.on_key(serial, time, key, state, surface.id, kb_state);
+        });
+        match state {
+            KeyState::Released => kb_state.pressed_keys.remove(&key),
+            _ => kb_state.pressed_keys.insert(key),
+        };
+        self.latest_kb_state_id.set(kb_state.id);
+        self.latest_kb_state.set(kb_state_rc.clone());
+    }
+
+    /// Sends the modifiers of a virtual keyboard to the focused surface.
+    pub fn virtual_modifiers_event(&self, kb_state_rc: &Rc<RefCell<KeyboardState>>) {
+        let kb_state = &mut *kb_state_rc.borrow_mut();
+        kb_state.mods.update_effective();
+        kb_state.update_leds();
+        self.for_each_virtual_kb(|serial, surface, kb| {
+            kb.on_mods_changed(serial, surface.id, kb_state);
+        });
+        self.latest_kb_state_id.set(kb_state.id);
+        self.latest_kb_state.set(kb_state_rc.clone());
+    }
 }
 
 impl ZwpVirtualKeyboardV1RequestHandler for ZwpVirtualKeyboardV1 {
@@ -78,45 +120,31 @@ impl ZwpVirtualKeyboardV1RequestHandler for ZwpVirtualKeyboardV1 {
             mods: Default::default(),
             leds: Default::default(),
             leds_changed: Default::default(),
+            group_offset: 0,
         };
         Ok(())
     }
 
     fn key(&self, req: Key, _slf: &Rc<Self>) -> Result<(), Self::Error> {
-        let kb_state = &mut *self.kb_state.borrow_mut();
-        let contains = kb_state.pressed_keys.contains(&req.key);
-        let (state, valid) = match req.state {
-            wl_keyboard::RELEASED => (KeyState::Released, contains),
-            wl_keyboard::PRESSED => (KeyState::Pressed, !contains),
+        let state = match req.state {
+            wl_keyboard::RELEASED => KeyState::Released,
+            wl_keyboard::PRESSED => KeyState::Pressed,
             _ => return Err(ZwpVirtualKeyboardV1Error::UnknownState(req.state)),
         };
-        if valid {
-            self.for_each_kb(|serial, surface, kb| {
-                kb.on_key(serial, req.time, req.key, state, surface.id, kb_state);
-            });
-            match req.state {
-                wl_keyboard::RELEASED => kb_state.pressed_keys.remove(&req.key),
-                _ => kb_state.pressed_keys.insert(req.key),
-            };
-            self.seat.latest_kb_state_id.set(kb_state.id);
-            self.seat.latest_kb_state.set(self.kb_state.clone());
-        }
+        self.seat
+            .virtual_key_event(&self.kb_state, req.time, req.key, state);
         Ok(())
     }
 
     fn modifiers(&self, req: Modifiers, _slf: &Rc<Self>) -> Result<(), Self::Error> {
-        let kb_state = &mut *self.kb_state.borrow_mut();
-        kb_state.mods.mods_pressed.0 = req.mods_depressed;
-        kb_state.mods.mods_latched.0 = req.mods_latched;
-        kb_state.mods.mods_locked.0 = req.mods_locked;
-        kb_state.mods.group_locked.0 = req.group;
-        kb_state.mods.update_effective();
-        kb_state.update_leds();
-        self.for_each_kb(|serial, surface, kb| {
-            kb.on_mods_changed(serial, surface.id, &kb_state);
-        });
-        self.seat.latest_kb_state_id.set(kb_state.id);
-        self.seat.latest_kb_state.set(self.kb_state.clone());
+        {
+            let mods = &mut self.kb_state.borrow_mut().mods;
+            mods.mods_pressed.0 = req.mods_depressed;
+            mods.mods_latched.0 = req.mods_latched;
+            mods.mods_locked.0 = req.mods_locked;
+            mods.group_locked.0 = req.group;
+        }
+        self.seat.virtual_modifiers_event(&self.kb_state);
         Ok(())
     }
 
@@ -22,6 +22,7 @@ pub mod xwayland_shell_v1;
 pub mod zwlr_layer_surface_v1;
 pub mod zwp_idle_inhibitor_v1;
 pub mod zwp_input_popup_surface_v2;
+pub mod zwp_keyboard_shortcuts_inhibitor_v1;
 
 use crate::backend::ButtonState;
 use crate::backend::KeyState;
@@ -186,6 +187,7 @@ use std::rc::Weak;
 use thiserror::Error;
 use wp_color_management_surface_v1::WpColorManagementSurfaceV1;
 use zwp_idle_inhibitor_v1::ZwpIdleInhibitorV1;
+use zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1;
 
 #[expect(unused)]
 const INVALID_SCALE: u32 = 0;
@@ -383,6 +385,7 @@ pub struct WlSurface {
     location: Cell<NodeLocation>,
     fractional_scale: CloneCell<Option<Rc<WpFractionalScaleV1>>>,
     pub constraints: SmallMap<SeatId, Rc<SeatConstraint>, 1>,
+    pub shortcuts_inhibitors: SmallMap<SeatId, Rc<ZwpKeyboardShortcutsInhibitorV1>, 1>,
     xwayland_serial: Cell<Option<u64>>,
     tearing_control: CloneCell<Option<Rc<WpTearingControlV1>>>,
     pub tearing: Cell<bool>,
@@ -775,6 +778,7 @@ impl WlSurface {
             output: CloneCell::new(dummy_output),
             fractional_scale: Default::default(),
             constraints: Default::default(),
+            shortcuts_inhibitors: Default::default(),
             xwayland_serial: Default::default(),
             tearing_control: Default::default(),
             tearing: Cell::new(false),
@@ -1225,6 +1229,7 @@ impl WlSurfaceRequestHandler for WlSurface {
         self.client.remove_obj(self)?;
         self.idle_inhibitors.clear();
         self.constraints.take();
+        self.shortcuts_inhibitors.take();
         self.destroyed.set(true);
         Ok(())
     }
@@ -2112,6 +2117,7 @@ impl Object for WlSurface {
         self.fractional_scale.take();
         self.tearing_control.take();
         self.constraints.clear();
+        self.shortcuts_inhibitors.clear();
         self.commit_timeline.clear(ClearReason::BreakLoops);
         self.alpha_modifier.take();
         self.text_input_connections.clear();
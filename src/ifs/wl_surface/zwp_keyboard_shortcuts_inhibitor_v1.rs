@@ -0,0 +1,229 @@
+use crate::client::Client;
+use crate::client::ClientError;
+use crate::egui_adapter::egui_platform::EggWindow;
+use crate::egui_adapter::egui_platform::EggWindowOwner;
+use crate::ifs::wl_seat::WlSeatGlobal;
+use crate::ifs::wl_surface::WlSurface;
+use crate::leaks::Tracker;
+use crate::object::Object;
+use crate::object::Version;
+use crate::utils::clonecell::CloneCell;
+use crate::utils::errorfmt::ErrorFmt;
+use crate::wire::ZwpKeyboardShortcutsInhibitorV1Id;
+use crate::wire::zwp_keyboard_shortcuts_inhibitor_v1::*;
+use egui::CentralPanel;
+use egui::Ui;
+use jay_config::window::ShortcutsInhibit as ConfigShortcutsInhibit;
+use std::cell::Cell;
+use std::rc::Rc;
+use thiserror::Error;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ShortcutsInhibitPolicy {
+    Allow,
+    Deny,
+    Ask,
+}
+
+impl TryFrom<ConfigShortcutsInhibit> for ShortcutsInhibitPolicy {
+    type Error = ();
+
+    fn try_from(value: ConfigShortcutsInhibit) -> Result<Self, Self::Error> {
+        let v = match value {
+            ConfigShortcutsInhibit::Allow => Self::Allow,
+            ConfigShortcutsInhibit::Deny => Self::Deny,
+            ConfigShortcutsInhibit::Ask => Self::Ask,
+            _ => return Err(()),
+        };
+        Ok(v)
+    }
+}
+
+pub struct ZwpKeyboardShortcutsInhibitorV1 {
+    pub id: ZwpKeyboardShortcutsInhibitorV1Id,
+    pub client: Rc<Client>,
+    pub surface: Rc<WlSurface>,
+    pub seat: Rc<WlSeatGlobal>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub active: Cell<bool>,
+    pub permission: Cell<Option<bool>>,
+    pub prompt: CloneCell<Option<Rc<ShortcutsInhibitPrompt>>>,
+}
+
+impl ZwpKeyboardShortcutsInhibitorV1RequestHandler for ZwpKeyboardShortcutsInhibitorV1 {
+    type Error = ZwpKeyboardShortcutsInhibitorV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        self.detach();
+        Ok(())
+    }
+}
+
+impl ZwpKeyboardShortcutsInhibitorV1 {
+    pub fn install(self: &Rc<Self>) {
+        self.surface
+            .shortcuts_inhibitors
+            .insert(self.seat.id(), self.clone());
+        self.seat.update_shortcuts_inhibitor();
+    }
+
+    fn detach(&self) {
+        if let Some(prompt) = self.prompt.take() {
+            prompt.close();
+        }
+        self.surface.shortcuts_inhibitors.remove(&self.seat.id());
+        self.seat.remove_shortcuts_inhibitor(self);
+    }
+
+    fn policy(&self) -> ShortcutsInhibitPolicy {
+        match self.permission.get() {
+            Some(true) => ShortcutsInhibitPolicy::Allow,
+            Some(false) => ShortcutsInhibitPolicy::Deny,
+            None => self
+                .surface
+                .get_toplevel()
+                .and_then(|tl| self.client.state.shortcuts_inhibit_policy(tl.tl_data()))
+                .unwrap_or(ShortcutsInhibitPolicy::Ask),
+        }
+    }
+
+    pub fn request_activation(self: &Rc<Self>) -> bool {
+        match self.policy() {
+            ShortcutsInhibitPolicy::Allow => {
+                self.activate();
+                true
+            }
+            ShortcutsInhibitPolicy::Deny => false,
+            ShortcutsInhibitPolicy::Ask => {
+                self.ask();
+                false
+            }
+        }
+    }
+
+    fn activate(&self) {
+        if !self.active.replace(true) {
+            self.send_active();
+        }
+    }
+
+    pub fn deactivate(&self) {
+        if self.active.replace(false) {
+            self.send_inactive();
+        }
+    }
+
+    fn ask(self: &Rc<Self>) {
+        if self.prompt.is_some() {
+            return;
+        }
+        let ctx = match self.client.state.get_egg_context() {
+            Ok(ctx) => ctx,
+            Err(e) => {
+                log::error!(
+                    "Could not ask for permission to inhibit shortcuts: {}",
+                    ErrorFmt(e),
+                );
+                self.permission.set(Some(false));
+                return;
+            }
+        };
+        let mut app = self.client.pid_info.comm.clone();
+        if let Some(tl) = self.surface.get_toplevel() {
+            let title = tl.tl_data().title.borrow();
+            if !title.is_empty() {
+                app = title.clone();
+            }
+        }
+        let prompt = Rc::new(ShortcutsInhibitPrompt {
+            window: ctx.create_window("Keyboard Shortcuts"),
+            inhibitor: CloneCell::new(Some(self.clone())),
+            app,
+        });
+        prompt.window.set_owner(Some(prompt.clone()));
+        self.prompt.set(Some(prompt));
+    }
+
+    fn answer(self: &Rc<Self>, allow: bool) {
+        self.permission.set(Some(allow));
+        if let Some(prompt) = self.prompt.take() {
+            prompt.close();
+        }
+        self.seat.update_shortcuts_inhibitor();
+    }
+
+    fn send_active(&self) {
+        self.client.event(Active { self_id: self.id });
+    }
+
+    fn send_inactive(&self) {
+        self.client.event(Inactive { self_id: self.id });
+    }
+}
+
+pub struct ShortcutsInhibitPrompt {
+    window: Rc<EggWindow>,
+    inhibitor: CloneCell<Option<Rc<ZwpKeyboardShortcutsInhibitorV1>>>,
+    app: String,
+}
+
+impl ShortcutsInhibitPrompt {
+    fn close(&self) {
+        self.window.set_owner(None);
+        self.inhibitor.take();
+    }
+}
+
+impl EggWindowOwner for ShortcutsInhibitPrompt {
+    fn close(&self) {
+        if let Some(inhibitor) = self.inhibitor.get() {
+            inhibitor.answer(false);
+        }
+    }
+
+    fn render(self: Rc<Self>, ui: &mut Ui) {
+        CentralPanel::default().show(ui, |ui| {
+            ui.label(format!("{} wants to inhibit keyboard shortcuts.", self.app,));
+            ui.label("While inhibited, all key combinations are sent to the application.");
+            ui.horizontal(|ui| {
+                let mut answer = None;
+                if ui.button("Allow").clicked() {
+                    answer = Some(true);
+                }
+                if ui.button("Deny").clicked() {
+                    answer = Some(false);
+                }
+                if let Some(answer) = answer
+                    && let Some(inhibitor) = self.inhibitor.get()
+                {
+                    let state = inhibitor.client.state.clone();
+                    state
+                        .run_toplevel
+                        .schedule(move || inhibitor.answer(answer));
+                }
+            });
+        });
+    }
+}
+
+object_base! {
+    self = ZwpKeyboardShortcutsInhibitorV1;
+    version = self.version;
+}
+
+impl Object for ZwpKeyboardShortcutsInhibitorV1 {
+    fn break_loops(self: Rc<Self>) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwpKeyboardShortcutsInhibitorV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpKeyboardShortcutsInhibitorV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwpKeyboardShortcutsInhibitorV1Error, ClientError);
@@ -0,0 +1,105 @@
+use crate::client::CAP_OUTPUT_POWER_MANAGER;
+use crate::client::Client;
+use crate::client::ClientCaps;
+use crate::client::ClientError;
+use crate::globals::Global;
+use crate::globals::GlobalName;
+use crate::ifs::zwlr_output_power_v1::ZwlrOutputPowerV1;
+use crate::leaks::Tracker;
+use crate::object::Object;
+use crate::object::Version;
+use crate::wire::ZwlrOutputPowerManagerV1Id;
+use crate::wire::zwlr_output_power_manager_v1::*;
+use std::rc::Rc;
+use thiserror::Error;
+
+pub struct ZwlrOutputPowerManagerV1Global {
+    name: GlobalName,
+}
+
+impl ZwlrOutputPowerManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwlrOutputPowerManagerV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), ZwlrOutputPowerManagerV1Error> {
+        let obj = Rc::new(ZwlrOutputPowerManagerV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwlrOutputPowerManagerV1Global,
+    ZwlrOutputPowerManagerV1,
+    ZwlrOutputPowerManagerV1Error
+);
+
+simple_add_global!(ZwlrOutputPowerManagerV1Global);
+
+impl Global for ZwlrOutputPowerManagerV1Global {
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn required_caps(&self) -> ClientCaps {
+        CAP_OUTPUT_POWER_MANAGER
+    }
+}
+
+pub struct ZwlrOutputPowerManagerV1 {
+    pub id: ZwlrOutputPowerManagerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl ZwlrOutputPowerManagerV1RequestHandler for ZwlrOutputPowerManagerV1 {
+    type Error = ZwlrOutputPowerManagerV1Error;
+
+    fn get_output_power(&self, req: GetOutputPower, slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let output = self.client.lookup(req.output)?.global.clone();
+        let p = Rc::new(ZwlrOutputPowerV1::new(req.id, slf, output.clone()));
+        track!(self.client, p);
+        self.client.add_client_obj(&p)?;
+        let Some(global) = output.get() else {
+            p.send_failed();
+            return Ok(());
+        };
+        p.send_mode(global.connector.state.borrow().active);
+        global.connector.wlr_output_powers.set(p.id(), p.clone());
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrOutputPowerManagerV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputPowerManagerV1 {}
+
+simple_add_obj!(ZwlrOutputPowerManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputPowerManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrOutputPowerManagerV1Error, ClientError);
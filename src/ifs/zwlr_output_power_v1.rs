@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests;
+
+use crate::client::Client;
+use crate::client::ClientError;
+use crate::client::ClientId;
+use crate::ifs::wl_output::OutputGlobalOpt;
+use crate::ifs::zwlr_output_power_manager_v1::ZwlrOutputPowerManagerV1;
+use crate::leaks::Tracker;
+use crate::object::Object;
+use crate::object::Version;
+use crate::utils::errorfmt::ErrorFmt;
+use crate::wire::ZwlrOutputPowerV1Id;
+use crate::wire::zwlr_output_power_v1::*;
+use std::rc::Rc;
+use thiserror::Error;
+
+const MODE_OFF: u32 = 0;
+const MODE_ON: u32 = 1;
+
+fn power_mode(on: bool) -> u32 {
+    match on {
+        true => MODE_ON,
+        false => MODE_OFF,
+    }
+}
+
+fn parse_power_mode(mode: u32) -> Option<bool> {
+    match mode {
+        MODE_OFF => Some(false),
+        MODE_ON => Some(true),
+        _ => None,
+    }
+}
+
+pub struct ZwlrOutputPowerV1 {
+    id: ZwlrOutputPowerV1Id,
+    client: Rc<Client>,
+    version: Version,
+    output: Rc<OutputGlobalOpt>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwlrOutputPowerV1 {
+    pub fn new(
+        id: ZwlrOutputPowerV1Id,
+        manager: &Rc<ZwlrOutputPowerManagerV1>,
+        output: Rc<OutputGlobalOpt>,
+    ) -> Self {
+        Self {
+            id,
+            client: manager.client.clone(),
+            version: manager.version,
+            output,
+            tracker: Default::default(),
+        }
+    }
+
+    pub fn id(&self) -> (ClientId, ZwlrOutputPowerV1Id) {
+        (self.client.id, self.id)
+    }
+
+    pub fn send_mode(&self, on: bool) {
+        self.client.event(Mode {
+            self_id: self.id,
+            mode: power_mode(on),
+        });
+    }
+
+    pub fn send_failed(&self) {
+        self.client.event(Failed { self_id: self.id });
+    }
+
+    fn detach(&self) {
+        if let Some(global) = self.output.get() {
+            global.connector.wlr_output_powers.remove(&self.id());
+        }
+    }
+}
+
+impl ZwlrOutputPowerV1RequestHandler for ZwlrOutputPowerV1 {
+    type Error = ZwlrOutputPowerV1Error;
+
+    fn set_mode(&self, req: SetMode, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(on) = parse_power_mode(req.mode) else {
+            return Err(ZwlrOutputPowerV1Error::InvalidMode(req.mode));
+        };
+        let Some(global) = self.output.get() else {
+            return Ok(());
+        };
+        if let Err(e) = global.connector.set_dpms(&self.client.state, on) {
+            log::error!("Could not change the power mode: {}", ErrorFmt(e));
+            self.detach();
+            self.send_failed();
+        }
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrOutputPowerV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputPowerV1 {
+    fn break_loops(self: Rc<Self>) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwlrOutputPowerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputPowerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Unknown power mode {0}")]
+    InvalidMode(u32),
+}
+efrom!(ZwlrOutputPowerV1Error, ClientError);
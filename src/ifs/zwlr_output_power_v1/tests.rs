@@ -0,0 +1,19 @@
+use crate::ifs::zwlr_output_power_v1::MODE_OFF;
+use crate::ifs::zwlr_output_power_v1::MODE_ON;
+use crate::ifs::zwlr_output_power_v1::parse_power_mode;
+use crate::ifs::zwlr_output_power_v1::power_mode;
+
+#[test]
+fn modes() {
+    assert_eq!(MODE_OFF, 0);
+    assert_eq!(MODE_ON, 1);
+    for on in [false, true] {
+        assert_eq!(parse_power_mode(power_mode(on)), Some(on));
+    }
+}
+
+#[test]
+fn invalid_mode() {
+    assert_eq!(parse_power_mode(2), None);
+    assert_eq!(parse_power_mode(u32::MAX), None);
+}
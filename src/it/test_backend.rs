@@ -17,8 +17,11 @@ use crate::backend::InputDevice;
 use crate::backend::InputDeviceAccelProfile;
 use crate::backend::InputDeviceCapability;
 use crate::backend::InputDeviceClickMethod;
+use crate::backend::InputDeviceCustomAccel;
 use crate::backend::InputDeviceId;
 use crate::backend::InputDeviceScrollMethod;
+use crate::backend::InputDeviceSendEventsMode;
+use crate::backend::InputDeviceTapButtonMap;
 use crate::backend::InputEvent;
 use crate::backend::KeyState;
 use crate::backend::Mode;
@@ -118,6 +121,7 @@ impl TestBackend {
             enabled: true,
             active: true,
             mode,
+            custom_mode: None,
             non_desktop_override: None,
             vrr: false,
             tearing: false,
@@ -161,6 +165,12 @@ impl TestBackend {
             accel_speed: Cell::new(1.0),
             accel_profile: Cell::new(InputDeviceAccelProfile::Flat),
             left_handed: Cell::new(false),
+            dwt_enabled: Cell::new(false),
+            dwtp_enabled: Cell::new(false),
+            tap_button_map: Cell::new(InputDeviceTapButtonMap::LeftRightMiddle),
+            send_events_mode: Cell::new(InputDeviceSendEventsMode::Enabled),
+            rotation: Cell::new(0),
+            custom_accel: Default::default(),
         });
         let default_kb = Rc::new(TestBackendKb {
             common: TestInputDeviceCommon {
@@ -481,6 +491,12 @@ pub struct TestBackendMouse {
     pub accel_speed: Cell<f64>,
     pub accel_profile: Cell<InputDeviceAccelProfile>,
     pub left_handed: Cell<bool>,
+    pub dwt_enabled: Cell<bool>,
+    pub dwtp_enabled: Cell<bool>,
+    pub tap_button_map: Cell<InputDeviceTapButtonMap>,
+    pub send_events_mode: Cell<InputDeviceSendEventsMode>,
+    pub rotation: Cell<u32>,
+    pub custom_accel: CloneCell<Option<Rc<InputDeviceCustomAccel>>>,
 }
 
 impl TestBackendMouse {
@@ -607,6 +623,30 @@ impl TestInputDevice for TestBackendMouse {
     fn set_transform_matrix(&self, matrix: TransformMatrix) {
         self.transform_matrix.set(matrix);
     }
+
+    fn set_dwt_enabled(&self, enabled: bool) {
+        self.dwt_enabled.set(enabled);
+    }
+
+    fn set_dwtp_enabled(&self, enabled: bool) {
+        self.dwtp_enabled.set(enabled);
+    }
+
+    fn set_tap_button_map(&self, map: InputDeviceTapButtonMap) {
+        self.tap_button_map.set(map);
+    }
+
+    fn set_send_events_mode(&self, mode: InputDeviceSendEventsMode) {
+        self.send_events_mode.set(mode);
+    }
+
+    fn set_rotation(&self, degrees_cw: u32) {
+        self.rotation.set(degrees_cw);
+    }
+
+    fn set_custom_accel(&self, accel: Rc<InputDeviceCustomAccel>) {
+        self.custom_accel.set(Some(accel));
+    }
 }
 
 pub struct TestInputDeviceCommon {
@@ -682,6 +722,30 @@ trait TestInputDevice: InputDevice {
     fn set_scroll_button_lock(&self, enabled: bool) {
         let _ = enabled;
     }
+
+    fn set_dwt_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_dwtp_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_tap_button_map(&self, map: InputDeviceTapButtonMap) {
+        let _ = map;
+    }
+
+    fn set_send_events_mode(&self, mode: InputDeviceSendEventsMode) {
+        let _ = mode;
+    }
+
+    fn set_rotation(&self, degrees_cw: u32) {
+        let _ = degrees_cw;
+    }
+
+    fn set_custom_accel(&self, accel: Rc<InputDeviceCustomAccel>) {
+        let _ = accel;
+    }
 }
 
 impl<T: TestInputDevice> InputDevice for T {
@@ -768,6 +832,30 @@ impl<T: TestInputDevice> InputDevice for T {
     fn set_scroll_button_lock(&self, enabled: bool) {
         <Self as TestInputDevice>::set_scroll_button_lock(self, enabled)
     }
+
+    fn set_dwt_enabled(&self, enabled: bool) {
+        <Self as TestInputDevice>::set_dwt_enabled(self, enabled)
+    }
+
+    fn set_dwtp_enabled(&self, enabled: bool) {
+        <Self as TestInputDevice>::set_dwtp_enabled(self, enabled)
+    }
+
+    fn set_tap_button_map(&self, map: InputDeviceTapButtonMap) {
+        <Self as TestInputDevice>::set_tap_button_map(self, map)
+    }
+
+    fn set_send_events_mode(&self, mode: InputDeviceSendEventsMode) {
+        <Self as TestInputDevice>::set_send_events_mode(self, mode)
+    }
+
+    fn set_rotation(&self, degrees_cw: u32) {
+        <Self as TestInputDevice>::set_rotation(self, degrees_cw)
+    }
+
+    fn set_custom_accel(&self, accel: Rc<InputDeviceCustomAccel>) {
+        <Self as TestInputDevice>::set_custom_accel(self, accel)
+    }
 }
 
 pub struct TestDrmDevice {
@@ -10,6 +10,7 @@ use bincode::Options;
 use isnt::std_1::primitive::IsntConstPtrExt;
 use jay_config::_private::ConfigEntry;
 use jay_config::_private::VERSION;
+use jay_config::_private::WindowCriterionIpc;
 use jay_config::_private::bincode_ops;
 use jay_config::_private::ipc::ClientMessage;
 use jay_config::_private::ipc::Response;
@@ -18,6 +19,8 @@ use jay_config::Axis;
 use jay_config::Direction;
 use jay_config::input::InputDevice;
 use jay_config::input::Seat;
+use jay_config::input::sendevents::SendEventsMode;
+use jay_config::input::tapbuttonmap::TapButtonMap;
 use jay_config::keyboard::Keymap;
 use jay_config::keyboard::ModifiedKeySym;
 use jay_config::theme::BarPosition;
@@ -25,6 +28,8 @@ use jay_config::theme::sized::BAR_SEPARATOR_WIDTH;
 use jay_config::theme::sized::Resizable;
 use jay_config::video::Connector;
 use jay_config::video::Transform;
+use jay_config::window::ShortcutsInhibit;
+use jay_config::window::WindowMatcher;
 use std::cell::Cell;
 use std::ops::Deref;
 use std::ptr;
@@ -139,6 +144,12 @@ unsafe extern "C" fn handle_msg(data: *const u8, msg: *const u8, size: usize) {
         ServerMessage::WindowMatcherMatched { .. } => {}
         ServerMessage::WindowMatcherUnmatched { .. } => {}
         ServerMessage::Locked { .. } => {}
+        ServerMessage::InvokePointerShortcut { .. } => {}
+        ServerMessage::InvokeGesture { .. } => {}
+        ServerMessage::InvokeTouchGesture { .. } => {}
+        ServerMessage::KeyboardAccessibilityFeedback { .. } => {}
+        ServerMessage::TabletPadEvent { .. } => {}
+        ServerMessage::ApplyOutputProfile { .. } => {}
     }
 }
 
@@ -228,6 +239,37 @@ impl TestConfig {
         })
     }
 
+    pub fn set_device_keymap(&self, id: InputDeviceId, keymap: Keymap) -> TestResult {
+        self.send(ClientMessage::DeviceSetKeymap {
+            device: InputDevice(id.raw() as _),
+            keymap,
+        })
+    }
+
+    pub fn create_window_matcher(
+        &self,
+        criterion: WindowCriterionIpc,
+    ) -> Result<WindowMatcher, TestError> {
+        let reply = self.send_with_reply(ClientMessage::CreateWindowMatcher { criterion })?;
+        get_response!(reply, CreateWindowMatcher { matcher });
+        Ok(matcher)
+    }
+
+    pub fn set_window_matcher_shortcuts_inhibit(
+        &self,
+        matcher: WindowMatcher,
+        inhibit: ShortcutsInhibit,
+    ) -> TestResult {
+        self.send(ClientMessage::SetWindowMatcherShortcutsInhibit { matcher, inhibit })
+    }
+
+    pub fn set_shortcuts_inhibit_escape(&self, seat: SeatId, key: ModifiedKeySym) -> TestResult {
+        self.send(ClientMessage::SetShortcutsInhibitEscape {
+            seat: Seat(seat.raw() as _),
+            key,
+        })
+    }
+
     pub fn create_split(&self, seat: SeatId, axis: Axis) -> TestResult {
         self.send(ClientMessage::CreateSeatSplit {
             seat: Seat(seat.raw() as _),
@@ -262,6 +304,72 @@ impl TestConfig {
         })
     }
 
+    pub fn set_input_dwt(&self, id: InputDeviceId, enabled: bool) -> TestResult {
+        self.send(ClientMessage::SetInputDwt {
+            device: InputDevice(id.raw() as _),
+            enabled,
+        })
+    }
+
+    pub fn set_input_dwtp(&self, id: InputDeviceId, enabled: bool) -> TestResult {
+        self.send(ClientMessage::SetInputDwtp {
+            device: InputDevice(id.raw() as _),
+            enabled,
+        })
+    }
+
+    pub fn set_input_tap_button_map(&self, id: InputDeviceId, map: TapButtonMap) -> TestResult {
+        self.send(ClientMessage::SetInputTapButtonMap {
+            device: InputDevice(id.raw() as _),
+            map,
+        })
+    }
+
+    pub fn set_input_send_events_mode(
+        &self,
+        id: InputDeviceId,
+        mode: SendEventsMode,
+    ) -> TestResult {
+        self.send(ClientMessage::SetInputSendEventsMode {
+            device: InputDevice(id.raw() as _),
+            mode,
+        })
+    }
+
+    pub fn set_input_rotation(&self, id: InputDeviceId, degrees: u32) -> TestResult {
+        self.send(ClientMessage::SetInputRotation {
+            device: InputDevice(id.raw() as _),
+            degrees,
+        })
+    }
+
+    pub fn set_input_custom_accel(
+        &self,
+        id: InputDeviceId,
+        step: f64,
+        points: Vec<f64>,
+    ) -> TestResult {
+        self.send(ClientMessage::SetInputCustomAccel {
+            device: InputDevice(id.raw() as _),
+            step,
+            points,
+        })
+    }
+
+    pub fn set_hide_cursor_while_typing(&self, seat: SeatId, hide: bool) -> TestResult {
+        self.send(ClientMessage::SeatSetHideCursorWhileTyping {
+            seat: Seat(seat.raw() as _),
+            hide,
+        })
+    }
+
+    pub fn set_cursor_hide_timeout(&self, seat: SeatId, timeout: Duration) -> TestResult {
+        self.send(ClientMessage::SeatSetCursorHideTimeout {
+            seat: Seat(seat.raw() as _),
+            usec: timeout.as_micros() as u64,
+        })
+    }
+
     pub fn focus(&self, seat: SeatId, direction: Direction) -> TestResult {
         self.send(ClientMessage::SeatFocus {
             seat: Seat(seat.raw() as _),
@@ -27,6 +27,8 @@ pub mod test_input_method_manager;
 pub mod test_input_popup_surface;
 pub mod test_jay_compositor;
 pub mod test_keyboard;
+pub mod test_keyboard_shortcuts_inhibit_manager;
+pub mod test_keyboard_shortcuts_inhibitor;
 pub mod test_pointer;
 pub mod test_pointer_warp;
 pub mod test_region;
@@ -0,0 +1,51 @@
+use crate::it::test_error::TestError;
+use crate::it::test_ifs::test_keyboard_shortcuts_inhibitor::TestKeyboardShortcutsInhibitor;
+use crate::it::test_ifs::test_seat::TestSeat;
+use crate::it::test_ifs::test_surface::TestSurface;
+use crate::it::test_object::TestObject;
+use crate::it::test_transport::TestTransport;
+use crate::wire::ZwpKeyboardShortcutsInhibitManagerV1Id;
+use crate::wire::zwp_keyboard_shortcuts_inhibit_manager_v1::*;
+use std::cell::Cell;
+use std::rc::Rc;
+
+pub struct TestKeyboardShortcutsInhibitManager {
+    pub id: ZwpKeyboardShortcutsInhibitManagerV1Id,
+    pub tran: Rc<TestTransport>,
+}
+
+impl TestKeyboardShortcutsInhibitManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+        }
+    }
+
+    pub fn inhibit_shortcuts(
+        &self,
+        surface: &TestSurface,
+        seat: &TestSeat,
+    ) -> Result<Rc<TestKeyboardShortcutsInhibitor>, TestError> {
+        let obj = Rc::new(TestKeyboardShortcutsInhibitor {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            active: Cell::new(false),
+        });
+        self.tran.send(InhibitShortcuts {
+            self_id: self.id,
+            id: obj.id,
+            surface: surface.id,
+            seat: seat.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+}
+
+test_object! {
+    TestKeyboardShortcutsInhibitManager, ZwpKeyboardShortcutsInhibitManagerV1;
+}
+
+impl TestObject for TestKeyboardShortcutsInhibitManager {}
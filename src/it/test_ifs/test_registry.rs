@@ -12,6 +12,7 @@ use crate::it::test_ifs::test_ext_foreign_toplevel_list::TestExtForeignToplevelL
 use crate::it::test_ifs::test_fifo_manager::TestFifoManager;
 use crate::it::test_ifs::test_input_method_manager::TestInputMethodManager;
 use crate::it::test_ifs::test_jay_compositor::TestJayCompositor;
+use crate::it::test_ifs::test_keyboard_shortcuts_inhibit_manager::TestKeyboardShortcutsInhibitManager;
 use crate::it::test_ifs::test_pointer_warp::TestPointerWarp;
 use crate::it::test_ifs::test_session_manager::TestSessionManager;
 use crate::it::test_ifs::test_shm::TestShm;
@@ -67,6 +68,7 @@ pub struct TestRegistrySingletons {
     pub wp_fifo_manager_v1: u32,
     pub wp_pointer_warp_v1: u32,
     pub xdg_session_manager_v1: u32,
+    pub zwp_keyboard_shortcuts_inhibit_manager_v1: u32,
 }
 
 pub struct TestRegistry {
@@ -98,6 +100,7 @@ pub struct TestRegistry {
     pub fifo_manager: CloneCell<Option<Rc<TestFifoManager>>>,
     pub pointer_warp: CloneCell<Option<Rc<TestPointerWarp>>>,
     pub session_manager: CloneCell<Option<Rc<TestSessionManager>>>,
+    pub shortcuts_inhibit_manager: CloneCell<Option<Rc<TestKeyboardShortcutsInhibitManager>>>,
     pub seats: CopyHashMap<GlobalName, Rc<WlSeatGlobal>>,
 }
 
@@ -173,6 +176,7 @@ impl TestRegistry {
             wp_fifo_manager_v1,
             wp_pointer_warp_v1,
             xdg_session_manager_v1,
+            zwp_keyboard_shortcuts_inhibit_manager_v1,
         };
         self.singletons.set(Some(singletons.clone()));
         Ok(singletons)
@@ -310,6 +314,13 @@ impl TestRegistry {
         1,
         TestSessionManager
     );
+    create_singleton!(
+        get_shortcuts_inhibit_manager,
+        shortcuts_inhibit_manager,
+        zwp_keyboard_shortcuts_inhibit_manager_v1,
+        1,
+        TestKeyboardShortcutsInhibitManager
+    );
 
     pub fn bind<O: TestObject>(
         &self,
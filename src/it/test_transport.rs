@@ -76,6 +76,7 @@ impl TestTransport {
             fifo_manager: Default::default(),
             pointer_warp: Default::default(),
             session_manager: Default::default(),
+            shortcuts_inhibit_manager: Default::default(),
             seats: Default::default(),
         });
         self.send(wl_display::GetRegistry {
@@ -160,6 +160,7 @@ impl TestRun {
                     height: 600,
                     refresh_rate_millihz: 0,
                 },
+                custom_mode: None,
                 non_desktop_override: None,
                 vrr: true,
                 tearing: false,
@@ -91,6 +91,10 @@ mod t0057_sm_floating;
 mod t0058_sm_parent;
 mod t0059_sm_background_ws;
 mod t0060_overlay;
+mod t0061_layout_memory;
+mod t0062_shortcuts_inhibit;
+mod t0063_input_settings;
+mod t0064_cursor_hide;
 
 pub trait TestCase: Sync {
     fn name(&self) -> &'static str;
@@ -170,5 +174,9 @@ pub fn tests() -> Vec<&'static dyn TestCase> {
         t0058_sm_parent,
         t0059_sm_background_ws,
         t0060_overlay,
+        t0061_layout_memory,
+        t0062_shortcuts_inhibit,
+        t0063_input_settings,
+        t0064_cursor_hide,
     }
 }
@@ -34,6 +34,7 @@ async fn test(run: Rc<TestRun>) -> TestResult {
         enabled: true,
         active: true,
         mode: Default::default(),
+        custom_mode: None,
         non_desktop_override: None,
         vrr: false,
         tearing: false,
@@ -0,0 +1,72 @@
+use crate::ifs::wl_seat::LayoutMemoryMode;
+use crate::it::test_error::TestResult;
+use crate::it::testrun::TestRun;
+use crate::tree::NodeBase;
+use jay_config::Direction;
+use std::rc::Rc;
+
+testcase!();
+
+/// Test that the keyboard group is remembered per window when the focus changes
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    ds.seat.set_layout_memory_mode(LayoutMemoryMode::Window);
+
+    let keymap = run.cfg.parse_keymap(KEYMAP)?;
+    run.cfg.set_keymap(ds.seat.id(), keymap)?;
+    let keymap = run.cfg.parse_keymap(KEYMAP)?;
+    run.cfg.set_device_keymap(ds.kb.common.id, keymap)?;
+    run.sync().await;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    client.sync().await;
+    tassert!(ds.seat.get_keyboard_node().node_id() == win2.surface.server.node_id());
+
+    let device = || ds.seat.latest_kb_state().borrow().mods.group_locked.0;
+    let seat = || ds.seat.seat_kb_state().borrow().mods.group_locked.0;
+
+    ds.kb.press(1);
+    run.sync().await;
+    tassert_eq!(device(), 1);
+    tassert_eq!(seat(), 0);
+
+    run.cfg.focus(ds.seat.id(), Direction::Left)?;
+    run.sync().await;
+    tassert!(ds.seat.get_keyboard_node().node_id() == win1.surface.server.node_id());
+    tassert_eq!(device(), 0);
+
+    run.cfg.focus(ds.seat.id(), Direction::Right)?;
+    run.sync().await;
+    tassert!(ds.seat.get_keyboard_node().node_id() == win2.surface.server.node_id());
+    tassert_eq!(device(), 1);
+    tassert_eq!(seat(), 1);
+
+    Ok(())
+}
+
+const KEYMAP: &str = r#"
+xkb_keymap {
+    xkb_keycodes {
+        <1> = 9;
+        <2> = 10;
+    };
+    xkb_types {
+    };
+    xkb_compatibility {
+    };
+    xkb_symbols {
+        key <1> {
+            [ ISO_Next_Group ],
+            actions[Group1] = [ LockGroup(group = +1) ]
+        };
+        key <2> {
+            symbols[Group1] = [ a ],
+            symbols[Group2] = [ b ]
+        };
+    };
+};
+"#;
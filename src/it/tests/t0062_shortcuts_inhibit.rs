@@ -0,0 +1,84 @@
+use crate::it::test_error::TestResult;
+use crate::it::testrun::TestRun;
+use jay_config::_private::WindowCriterionIpc;
+use jay_config::_private::WindowCriterionStringField;
+use jay_config::keyboard::syms::SYM_Escape;
+use jay_config::keyboard::syms::SYM_F13;
+use jay_config::window::ShortcutsInhibit;
+use std::rc::Rc;
+
+testcase!();
+
+/// Test that shortcuts are forwarded while inhibited and that the escape key ends the
+/// inhibition
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let keymap = run.cfg.parse_keymap(KEYMAP)?;
+    run.cfg.set_keymap(ds.seat.id(), keymap)?;
+    run.cfg.add_shortcut(ds.seat.id(), SYM_F13)?;
+    run.cfg
+        .set_shortcuts_inhibit_escape(ds.seat.id(), SYM_Escape.into())?;
+    let matcher = run.cfg.create_window_matcher(WindowCriterionIpc::String {
+        string: "inhibit".to_string(),
+        field: WindowCriterionStringField::Title,
+        regex: false,
+    })?;
+    run.cfg
+        .set_window_matcher_shortcuts_inhibit(matcher, ShortcutsInhibit::Allow)?;
+    run.sync().await;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let win = client.create_window().await?;
+    win.tl.core.set_title("inhibit")?;
+    win.map2().await?;
+
+    let inhibitor = client
+        .registry
+        .get_shortcuts_inhibit_manager()
+        .await?
+        .inhibit_shortcuts(&win.surface, &seat.seat)?;
+    client.sync().await;
+    tassert!(inhibitor.active.get());
+
+    let key = seat.kb.key.expect()?;
+    ds.kb.press(1);
+    run.sync().await;
+    client.sync().await;
+    tassert!(run.cfg.invoked_shortcuts.is_empty());
+    let (_, k) = key.next()?;
+    tassert_eq!((k.key, k.state), (1, 1));
+
+    ds.kb.press(2);
+    run.sync().await;
+    client.sync().await;
+    tassert!(!inhibitor.active.get());
+
+    ds.kb.press(1);
+    run.sync().await;
+    tassert!(
+        run.cfg
+            .invoked_shortcuts
+            .contains(&(ds.seat.id(), SYM_F13.into()))
+    );
+
+    Ok(())
+}
+
+const KEYMAP: &str = r#"
+xkb_keymap {
+    xkb_keycodes {
+        <1> = 9;
+        <2> = 10;
+    };
+    xkb_types {
+    };
+    xkb_compatibility {
+    };
+    xkb_symbols {
+        key <1> { [ F13 ] };
+        key <2> { [ Escape ] };
+    };
+};
+"#;
@@ -0,0 +1,67 @@
+use crate::backend::InputDeviceCustomAccel;
+use crate::backend::InputDeviceSendEventsMode;
+use crate::backend::InputDeviceTapButtonMap;
+use crate::it::test_error::TestResult;
+use crate::it::testrun::TestRun;
+use jay_config::input::sendevents::SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE;
+use jay_config::input::sendevents::SendEventsMode;
+use jay_config::input::tapbuttonmap::TAP_BUTTON_MAP_LEFT_MIDDLE_RIGHT;
+use jay_config::input::tapbuttonmap::TapButtonMap;
+use std::rc::Rc;
+
+testcase!();
+
+/// Test that the libinput settings reach the device and that invalid values are rejected
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let id = ds.mouse.common.id;
+
+    run.cfg.set_input_dwt(id, true)?;
+    run.cfg.set_input_dwtp(id, true)?;
+    run.cfg
+        .set_input_tap_button_map(id, TAP_BUTTON_MAP_LEFT_MIDDLE_RIGHT)?;
+    run.cfg
+        .set_input_send_events_mode(id, SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE)?;
+    run.cfg.set_input_rotation(id, 90)?;
+    run.cfg
+        .set_input_custom_accel(id, 0.5, vec![0.0, 1.0, 2.5])?;
+    run.sync().await;
+
+    tassert!(ds.mouse.dwt_enabled.get());
+    tassert!(ds.mouse.dwtp_enabled.get());
+    tassert_eq!(
+        ds.mouse.tap_button_map.get(),
+        InputDeviceTapButtonMap::LeftMiddleRight
+    );
+    tassert_eq!(
+        ds.mouse.send_events_mode.get(),
+        InputDeviceSendEventsMode::DisabledOnExternalMouse
+    );
+    tassert_eq!(ds.mouse.rotation.get(), 90);
+    let accel = InputDeviceCustomAccel {
+        step: 0.5,
+        points: vec![0.0, 1.0, 2.5],
+    };
+    tassert_eq!(ds.mouse.custom_accel.get().as_deref(), Some(&accel));
+
+    run.cfg.set_input_tap_button_map(id, TapButtonMap(2))?;
+    run.cfg
+        .set_input_send_events_mode(id, SendEventsMode(1 << 2))?;
+    run.cfg.set_input_rotation(id, 360)?;
+    run.cfg.set_input_custom_accel(id, 1.0, vec![1.0])?;
+    run.cfg.set_input_custom_accel(id, 1.0, vec![1.0; 65])?;
+    run.sync().await;
+
+    tassert_eq!(
+        ds.mouse.tap_button_map.get(),
+        InputDeviceTapButtonMap::LeftMiddleRight
+    );
+    tassert_eq!(
+        ds.mouse.send_events_mode.get(),
+        InputDeviceSendEventsMode::DisabledOnExternalMouse
+    );
+    tassert_eq!(ds.mouse.rotation.get(), 90);
+    tassert_eq!(ds.mouse.custom_accel.get().as_deref(), Some(&accel));
+
+    Ok(())
+}
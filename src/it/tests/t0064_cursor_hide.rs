@@ -0,0 +1,50 @@
+use crate::it::test_error::TestResult;
+use crate::it::testrun::TestRun;
+use std::rc::Rc;
+use std::time::Duration;
+
+testcase!();
+
+const KEY_A: u32 = 30;
+const KEY_LEFTCTRL: u32 = 29;
+
+/// Test that the cursor is hidden while typing and after a timeout and that it
+/// reappears on the next pointer event
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let cursor = ds.seat.pointer_cursor();
+
+    run.cfg.set_hide_cursor_while_typing(ds.seat.id(), true)?;
+    ds.mouse.rel(1.0, 1.0);
+    run.sync().await;
+    tassert!(cursor.is_active());
+
+    {
+        // Modifiers and shortcuts do not count as typing.
+        let _ctrl = ds.kb.press(KEY_LEFTCTRL);
+        ds.kb.press(KEY_A);
+        run.sync().await;
+        tassert!(cursor.is_active());
+    }
+
+    ds.kb.press(KEY_A);
+    run.sync().await;
+    tassert!(!cursor.is_active());
+
+    ds.mouse.rel(1.0, 1.0);
+    run.sync().await;
+    tassert!(cursor.is_active());
+
+    run.cfg
+        .set_cursor_hide_timeout(ds.seat.id(), Duration::from_millis(1))?;
+    ds.mouse.rel(1.0, 1.0);
+    run.sync().await;
+    run.state.wheel.timeout(20).await?;
+    tassert!(!cursor.is_active());
+
+    ds.mouse.rel(1.0, 1.0);
+    run.sync().await;
+    tassert!(cursor.is_active());
+
+    Ok(())
+}
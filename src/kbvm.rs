@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 use crate::backend::KeyState;
 use crate::ifs::wl_seat::WlSeatGlobal;
 use crate::keyboard::DynKeyboardState;
@@ -69,6 +72,8 @@ pub struct KbvmMap {
     pub compose: Option<IndicatorMatcher>,
     pub kana: Option<IndicatorMatcher>,
     pub shortcuts_group: Option<GroupIndex>,
+    pub group_names: Vec<(GroupIndex, String)>,
+    pub num_groups: u32,
 }
 
 #[derive(Copy, Clone)]
@@ -186,9 +191,16 @@ impl KbvmContext {
             }
             has_indicators = true;
         }
+        let num_groups = map
+            .keys()
+            .map(|k| k.groups().count())
+            .max()
+            .unwrap_or_default()
+            .max(1) as u32;
         let builder = map.to_builder();
         let (_, xwayland_map) = create_keymap_memfd(&map, true).map_err(KbvmError::KeymapMemfd)?;
         let (map_text, map) = create_keymap_memfd(&map, false).map_err(KbvmError::KeymapMemfd)?;
+        let group_names = parse_group_names(&map_text);
         Ok(Rc::new(KbvmMap {
             id,
             state_machine: builder.build_state_machine(),
@@ -203,10 +215,77 @@ impl KbvmContext {
             compose,
             kana,
             shortcuts_group,
+            group_names,
+            num_groups,
         }))
     }
 }
 
+/// Returns the group names of a keymap formatted by kbvm.
+///
+/// kbvm does not expose the group names of a [`Keymap`]. Its formatter writes them as
+/// `name[GroupN] = "..."` statements at the start of the `xkb_symbols` section, which
+/// are read back here.
+fn parse_group_names(map_text: &str) -> Vec<(GroupIndex, String)> {
+    let mut res = vec![];
+    let mut lines = map_text.lines().map(|l| l.trim());
+    for line in lines.by_ref() {
+        if line.starts_with("xkb_symbols") {
+            break;
+        }
+    }
+    for line in lines {
+        if line.starts_with("key ") || line == "};" {
+            break;
+        }
+        let Some(line) = line.strip_prefix("name[Group") else {
+            continue;
+        };
+        let Some((idx, rest)) = line.split_once("] = \"") else {
+            continue;
+        };
+        let Ok(idx) = idx.parse::<u32>() else {
+            continue;
+        };
+        let Some(name) = rest.strip_suffix("\";").and_then(unescape_string) else {
+            continue;
+        };
+        res.push((GroupIndex(idx.saturating_sub(1)), name));
+    }
+    res
+}
+
+/// Reverses the escaping that kbvm applies when formatting strings.
+fn unescape_string(s: &str) -> Option<String> {
+    let mut res = Vec::new();
+    let mut bytes = s.bytes();
+    while let Some(b) = bytes.next() {
+        if b != b'\\' {
+            res.push(b);
+            continue;
+        }
+        let b = match bytes.next()? {
+            b'n' => b'\n',
+            b'r' => b'\r',
+            b't' => b'\t',
+            b @ b'0'..=b'7' => {
+                let mut n = (b - b'0') as u32;
+                for _ in 0..2 {
+                    let b = bytes.next()?;
+                    if !(b'0'..=b'7').contains(&b) {
+                        return None;
+                    }
+                    n = n * 8 + (b - b'0') as u32;
+                }
+                u8::try_from(n).ok()?
+            }
+            b => b,
+        };
+        res.push(b);
+    }
+    Some(String::from_utf8_lossy(&res).into_owned())
+}
+
 fn create_keymap_memfd(map: &Keymap, xwayland: bool) -> Result<(String, KeymapFd), OsError> {
     let mut format = map.format();
     if xwayland {
@@ -231,6 +310,13 @@ fn create_keymap_memfd(map: &Keymap, xwayland: bool) -> Result<(String, KeymapFd
 }
 
 impl KbvmMap {
+    pub fn group_name(&self, group: GroupIndex) -> Option<&str> {
+        self.group_names
+            .iter()
+            .find(|(idx, _)| *idx == group)
+            .map(|(_, name)| &**name)
+    }
+
     pub fn state(self: &Rc<Self>, id: KeyboardStateId) -> KbvmState {
         KbvmState {
             map: self.clone(),
@@ -242,6 +328,7 @@ impl KbvmMap {
                 mods: Default::default(),
                 leds: Default::default(),
                 leds_changed: Default::default(),
+                group_offset: 0,
             },
         }
     }
@@ -0,0 +1,76 @@
+use crate::kbvm::KbvmContext;
+use crate::kbvm::parse_group_names;
+use kbvm::GroupIndex;
+
+const KEYMAP: &str = r#"
+xkb_keymap {
+    xkb_keycodes {
+        <a> = 38;
+    };
+    xkb_types {
+        type "ONE_LEVEL" {
+            modifiers = None;
+            map[None] = Level1;
+        };
+    };
+    xkb_compat { };
+    xkb_symbols {
+        name[Group1] = "English (US)";
+        name[Group2] = "Tab\t\"quoted\" \\ \101";
+        name[Group4] = "";
+
+        key <a> { [ a ], [ b ], [ c ], [ d ] };
+    };
+};
+"#;
+
+#[test]
+fn group_names() {
+    let map = KbvmContext::default()
+        .parse_keymap(KEYMAP.as_bytes(), None)
+        .unwrap();
+    assert_eq!(map.num_groups, 4);
+    assert_eq!(map.group_name(GroupIndex(0)), Some("English (US)"));
+    assert_eq!(map.group_name(GroupIndex(1)), Some("Tab\t\"quoted\" \\ A"));
+    assert_eq!(map.group_name(GroupIndex(2)), None);
+    assert_eq!(map.group_name(GroupIndex(3)), Some(""));
+}
+
+#[test]
+fn group_names_outside_of_symbols() {
+    let text = r#"
+xkb_keymap {
+    xkb_types {
+        name[Group1] = "types";
+    };
+    xkb_symbols {
+        name[Group1] = "symbols";
+        key <a> { [ a ] };
+        name[Group2] = "key";
+    };
+};
+"#;
+    assert_eq!(
+        parse_group_names(text),
+        [(GroupIndex(0), "symbols".to_string())],
+    );
+}
+
+#[test]
+fn group_names_with_utf8_escapes() {
+    let text = r#"
+xkb_keymap {
+    xkb_symbols {
+        name[Group1] = "Fran\303\247ais";
+        name[Group2] = "\377";
+    };
+};
+"#;
+    assert_eq!(
+        parse_group_names(text),
+        [
+            (GroupIndex(0), "Français".to_string()),
+            (GroupIndex(1), "\u{fffd}".to_string()),
+        ],
+    );
+}
@@ -0,0 +1,331 @@
+#[cfg(test)]
+mod tests;
+
+use crate::async_engine::SpawnedFuture;
+use crate::backend::KeyState;
+use crate::state::DeviceHandlerData;
+use crate::state::State;
+use ahash::AHashMap;
+use jay_config::input::key_remap::HoldAction;
+use jay_config::input::key_remap::KeyRemap;
+use jay_config::input::key_remap::RemapAction;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::mem;
+use std::rc::Rc;
+use std::rc::Weak;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum KeyRemapError {
+    #[error("Layer {0} does not exist")]
+    UnknownLayer(u32),
+    #[error("Unknown remap action {0:?}")]
+    UnknownAction(RemapAction),
+    #[error("Unknown hold action {0:?}")]
+    UnknownHoldAction(HoldAction),
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Action {
+    Key(u32),
+    Layer(usize),
+    OneShot(u32),
+    TapHold {
+        tap: u32,
+        hold: Hold,
+        timeout_ms: u32,
+    },
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Hold {
+    Key(u32),
+    Layer(usize),
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Held {
+    Key(u32),
+    Layer(usize),
+    OneShot { key: u32, used: bool },
+}
+
+struct Pending {
+    key: u32,
+    tap: u32,
+    hold: Hold,
+    pressed: Vec<u32>,
+}
+
+#[derive(Default)]
+struct Inner {
+    held: AHashMap<u32, Held>,
+    layers: Vec<usize>,
+    pending: Option<Pending>,
+    buffer: VecDeque<(u64, u32, KeyState)>,
+    one_shots: Vec<u32>,
+    one_shot_consumer: Option<u32>,
+    timeout_ms: Option<u32>,
+}
+
+struct Remap {
+    layers: Vec<AHashMap<u32, Action>>,
+}
+
+pub struct KeyRemapper {
+    state: Rc<State>,
+    dev: Weak<DeviceHandlerData>,
+    remap: Remap,
+    inner: RefCell<Inner>,
+    timeout: Cell<Option<SpawnedFuture<()>>>,
+}
+
+type Out = Vec<(u64, u32, KeyState)>;
+
+impl KeyRemapper {
+    pub fn new(
+        state: &Rc<State>,
+        dev: &Rc<DeviceHandlerData>,
+        remap: &KeyRemap,
+    ) -> Result<Self, KeyRemapError> {
+        Ok(Self {
+            state: state.clone(),
+            dev: Rc::downgrade(dev),
+            remap: Remap::new(remap)?,
+            inner: Default::default(),
+            timeout: Default::default(),
+        })
+    }
+
+    pub fn handle_key(self: &Rc<Self>, time_usec: u64, key: u32, state: KeyState) {
+        let mut out = vec![];
+        {
+            let inner = &mut *self.inner.borrow_mut();
+            self.remap.process(inner, &mut out, time_usec, key, state);
+            self.update_timeout(inner);
+        }
+        self.emit(out);
+    }
+
+    pub fn reset(&self) {
+        self.timeout.take();
+        *self.inner.borrow_mut() = Default::default();
+    }
+
+    fn handle_timeout(self: &Rc<Self>) {
+        let mut out = vec![];
+        {
+            let inner = &mut *self.inner.borrow_mut();
+            if inner.pending.is_some() {
+                self.remap
+                    .resolve(inner, &mut out, self.state.now_usec(), false);
+            }
+            self.update_timeout(inner);
+        }
+        self.emit(out);
+    }
+
+    fn update_timeout(self: &Rc<Self>, inner: &mut Inner) {
+        if inner.pending.is_none() {
+            self.timeout.take();
+            return;
+        }
+        let Some(timeout_ms) = inner.timeout_ms.take() else {
+            return;
+        };
+        let slf = Rc::downgrade(self);
+        let state = self.state.clone();
+        let future = self.state.eng.spawn("key remap timeout", async move {
+            if state.wheel.timeout(timeout_ms as _).await.is_ok()
+                && let Some(slf) = slf.upgrade()
+            {
+                slf.handle_timeout();
+            }
+        });
+        self.timeout.set(Some(future));
+    }
+
+    fn emit(&self, out: Out) {
+        if out.is_empty() {
+            return;
+        }
+        let Some(dev) = self.dev.upgrade() else {
+            return;
+        };
+        let Some(seat) = dev.seat.get() else {
+            return;
+        };
+        for (time_usec, key, state) in out {
+            seat.physical_key_event(&dev, time_usec, key, state);
+        }
+    }
+}
+
+impl Remap {
+    fn new(remap: &KeyRemap) -> Result<Self, KeyRemapError> {
+        let num_layers = remap.layers.len() + 1;
+        let layer = |l: u32| {
+            let idx = l as usize + 1;
+            match idx < num_layers {
+                true => Ok(idx),
+                false => Err(KeyRemapError::UnknownLayer(l)),
+            }
+        };
+        let convert = |keys: &[(u32, RemapAction)]| {
+            let mut map = AHashMap::new();
+            for &(key, action) in keys {
+                let action = match action {
+                    RemapAction::Key(k) => Action::Key(k),
+                    RemapAction::Layer(l) => Action::Layer(layer(l)?),
+                    RemapAction::OneShot(k) => Action::OneShot(k),
+                    RemapAction::TapHold {
+                        tap,
+                        hold,
+                        timeout_ms,
+                    } => Action::TapHold {
+                        tap,
+                        hold: match hold {
+                            HoldAction::Key(k) => Hold::Key(k),
+                            HoldAction::Layer(l) => Hold::Layer(layer(l)?),
+                            _ => return Err(KeyRemapError::UnknownHoldAction(hold)),
+                        },
+                        timeout_ms,
+                    },
+                    _ => return Err(KeyRemapError::UnknownAction(action)),
+                };
+                map.insert(key, action);
+            }
+            Ok(map)
+        };
+        let mut layers = vec![convert(&remap.keys)?];
+        for keys in &remap.layers {
+            layers.push(convert(keys)?);
+        }
+        Ok(Self { layers })
+    }
+
+    fn lookup(&self, inner: &Inner, key: u32) -> Action {
+        for &layer in inner.layers.iter().rev() {
+            if let Some(action) = self.layers[layer].get(&key) {
+                return *action;
+            }
+        }
+        self.layers[0]
+            .get(&key)
+            .copied()
+            .unwrap_or(Action::Key(key))
+    }
+
+    fn process(&self, inner: &mut Inner, out: &mut Out, time_usec: u64, key: u32, state: KeyState) {
+        if let Some(pending) = &mut inner.pending {
+            if key == pending.key && state == KeyState::Released {
+                self.resolve(inner, out, time_usec, true);
+                return;
+            }
+            inner.buffer.push_back((time_usec, key, state));
+            match state {
+                KeyState::Pressed => pending.pressed.push(key),
+                KeyState::Released => {
+                    if pending.pressed.contains(&key) {
+                        self.resolve(inner, out, time_usec, false);
+                    }
+                }
+                KeyState::Repeated => {}
+            }
+            return;
+        }
+        match state {
+            KeyState::Pressed => match self.lookup(inner, key) {
+                Action::Key(k) => self.press(inner, out, time_usec, key, Held::Key(k)),
+                Action::Layer(l) => self.press(inner, out, time_usec, key, Held::Layer(l)),
+                Action::OneShot(k) => {
+                    let held = Held::OneShot {
+                        key: k,
+                        used: false,
+                    };
+                    self.press(inner, out, time_usec, key, held);
+                }
+                Action::TapHold {
+                    tap,
+                    hold,
+                    timeout_ms,
+                } => {
+                    inner.pending = Some(Pending {
+                        key,
+                        tap,
+                        hold,
+                        pressed: vec![],
+                    });
+                    inner.timeout_ms = Some(timeout_ms);
+                }
+            },
+            KeyState::Released => match inner.held.remove(&key) {
+                Some(Held::Key(k)) => {
+                    out.push((time_usec, k, KeyState::Released));
+                    if inner.one_shot_consumer == Some(key) {
+                        inner.one_shot_consumer = None;
+                        for k in inner.one_shots.drain(..) {
+                            out.push((time_usec, k, KeyState::Released));
+                        }
+                    }
+                }
+                Some(Held::Layer(l)) => {
+                    if let Some(pos) = inner.layers.iter().rposition(|&v| v == l) {
+                        inner.layers.remove(pos);
+                    }
+                }
+                Some(Held::OneShot { key: k, used }) => match used {
+                    true => out.push((time_usec, k, KeyState::Released)),
+                    false => inner.one_shots.push(k),
+                },
+                None => out.push((time_usec, key, KeyState::Released)),
+            },
+            KeyState::Repeated => {}
+        }
+    }
+
+    fn press(&self, inner: &mut Inner, out: &mut Out, time_usec: u64, key: u32, held: Held) {
+        match held {
+            Held::Key(k) => {
+                for held in inner.held.values_mut() {
+                    if let Held::OneShot { used, .. } = held {
+                        *used = true;
+                    }
+                }
+                if !inner.one_shots.is_empty() && inner.one_shot_consumer.is_none() {
+                    inner.one_shot_consumer = Some(key);
+                }
+                out.push((time_usec, k, KeyState::Pressed));
+            }
+            Held::Layer(l) => inner.layers.push(l),
+            Held::OneShot { key: k, .. } => {
+                if let Some(pos) = inner.one_shots.iter().position(|&v| v == k) {
+                    inner.one_shots.remove(pos);
+                } else {
+                    out.push((time_usec, k, KeyState::Pressed));
+                }
+            }
+        }
+        inner.held.insert(key, held);
+    }
+
+    fn resolve(&self, inner: &mut Inner, out: &mut Out, time_usec: u64, tap: bool) {
+        let Some(pending) = inner.pending.take() else {
+            return;
+        };
+        let held = match (tap, pending.hold) {
+            (true, _) => Held::Key(pending.tap),
+            (false, Hold::Key(k)) => Held::Key(k),
+            (false, Hold::Layer(l)) => Held::Layer(l),
+        };
+        self.press(inner, out, time_usec, pending.key, held);
+        for (time_usec, key, state) in mem::take(&mut inner.buffer) {
+            self.process(inner, out, time_usec, key, state);
+        }
+        if tap {
+            self.process(inner, out, time_usec, pending.key, KeyState::Released);
+        }
+    }
+}
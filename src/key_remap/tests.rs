@@ -0,0 +1,245 @@
+use crate::backend::KeyState;
+use crate::backend::KeyState::Pressed;
+use crate::backend::KeyState::Released;
+use crate::key_remap::Inner;
+use crate::key_remap::Remap;
+use jay_config::input::key_remap::HoldAction;
+use jay_config::input::key_remap::KeyRemap;
+use jay_config::input::key_remap::RemapAction;
+
+const A: u32 = 30;
+const S: u32 = 31;
+const D: u32 = 32;
+const F: u32 = 33;
+const J: u32 = 36;
+const K: u32 = 37;
+const LEFTCTRL: u32 = 29;
+const LEFTSHIFT: u32 = 42;
+const LEFTALT: u32 = 56;
+const CAPSLOCK: u32 = 58;
+const LEFT: u32 = 105;
+const DOWN: u32 = 108;
+
+struct Harness {
+    remap: Remap,
+    inner: Inner,
+    time_usec: u64,
+}
+
+impl Harness {
+    fn new(remap: KeyRemap) -> Self {
+        Self {
+            remap: Remap::new(&remap).unwrap(),
+            inner: Default::default(),
+            time_usec: 0,
+        }
+    }
+
+    fn key(&mut self, key: u32, state: KeyState) -> Vec<(u32, KeyState)> {
+        self.time_usec += 1000;
+        let mut out = vec![];
+        self.remap
+            .process(&mut self.inner, &mut out, self.time_usec, key, state);
+        out.into_iter().map(|(_, k, s)| (k, s)).collect()
+    }
+
+    fn timeout(&mut self) -> Vec<(u32, KeyState)> {
+        assert!(self.inner.pending.is_some());
+        self.time_usec += 1000;
+        let mut out = vec![];
+        self.remap
+            .resolve(&mut self.inner, &mut out, self.time_usec, false);
+        out.into_iter().map(|(_, k, s)| (k, s)).collect()
+    }
+}
+
+fn tap_hold(tap: u32, hold: HoldAction) -> RemapAction {
+    RemapAction::TapHold {
+        tap,
+        hold,
+        timeout_ms: 200,
+    }
+}
+
+fn home_row() -> Harness {
+    Harness::new(KeyRemap {
+        keys: vec![
+            (CAPSLOCK, RemapAction::Key(LEFTCTRL)),
+            (A, tap_hold(A, HoldAction::Key(LEFTCTRL))),
+            (S, tap_hold(S, HoldAction::Key(LEFTALT))),
+            (F, tap_hold(F, HoldAction::Layer(0))),
+            (LEFTSHIFT, RemapAction::OneShot(LEFTSHIFT)),
+            (D, RemapAction::Layer(0)),
+        ],
+        layers: vec![vec![
+            (J, RemapAction::Key(DOWN)),
+            (K, RemapAction::Key(LEFT)),
+        ]],
+    })
+}
+
+#[test]
+fn key() {
+    let mut h = home_row();
+    assert_eq!(h.key(CAPSLOCK, Pressed), [(LEFTCTRL, Pressed)]);
+    assert_eq!(h.key(CAPSLOCK, Released), [(LEFTCTRL, Released)]);
+    assert_eq!(h.key(J, Pressed), [(J, Pressed)]);
+    assert_eq!(h.key(J, Released), [(J, Released)]);
+}
+
+#[test]
+fn tap() {
+    let mut h = home_row();
+    assert_eq!(h.key(A, Pressed), []);
+    assert_eq!(h.key(A, Released), [(A, Pressed), (A, Released)]);
+    assert!(h.inner.pending.is_none());
+    assert!(h.inner.held.is_empty());
+}
+
+#[test]
+fn tap_with_rollover() {
+    let mut h = home_row();
+    assert_eq!(h.key(A, Pressed), []);
+    assert_eq!(h.key(J, Pressed), []);
+    assert_eq!(
+        h.key(A, Released),
+        [(A, Pressed), (J, Pressed), (A, Released)],
+    );
+    assert_eq!(h.key(J, Released), [(J, Released)]);
+}
+
+#[test]
+fn hold_by_timeout() {
+    let mut h = home_row();
+    assert_eq!(h.key(A, Pressed), []);
+    assert_eq!(h.inner.timeout_ms, Some(200));
+    assert_eq!(h.timeout(), [(LEFTCTRL, Pressed)]);
+    assert_eq!(h.key(J, Pressed), [(J, Pressed)]);
+    assert_eq!(h.key(J, Released), [(J, Released)]);
+    assert_eq!(h.key(A, Released), [(LEFTCTRL, Released)]);
+}
+
+#[test]
+fn hold_by_timeout_replays_buffered_keys() {
+    let mut h = home_row();
+    assert_eq!(h.key(A, Pressed), []);
+    assert_eq!(h.key(J, Pressed), []);
+    assert_eq!(h.timeout(), [(LEFTCTRL, Pressed), (J, Pressed)]);
+    assert_eq!(h.key(A, Released), [(LEFTCTRL, Released)]);
+    assert_eq!(h.key(J, Released), [(J, Released)]);
+}
+
+#[test]
+fn permissive_hold() {
+    let mut h = home_row();
+    assert_eq!(h.key(A, Pressed), []);
+    assert_eq!(h.key(J, Pressed), []);
+    assert_eq!(
+        h.key(J, Released),
+        [(LEFTCTRL, Pressed), (J, Pressed), (J, Released)],
+    );
+    assert_eq!(h.key(A, Released), [(LEFTCTRL, Released)]);
+}
+
+#[test]
+fn permissive_hold_layer() {
+    let mut h = home_row();
+    assert_eq!(h.key(F, Pressed), []);
+    assert_eq!(h.key(J, Pressed), []);
+    assert_eq!(h.key(J, Released), [(DOWN, Pressed), (DOWN, Released)]);
+    assert_eq!(h.key(F, Released), []);
+    assert_eq!(h.key(J, Pressed), [(J, Pressed)]);
+}
+
+#[test]
+fn nested_tap_hold() {
+    let mut h = home_row();
+    assert_eq!(h.key(A, Pressed), []);
+    assert_eq!(h.key(S, Pressed), []);
+    // S is tapped inside A, so A is held. S itself is resolved as a tap.
+    assert_eq!(
+        h.key(S, Released),
+        [(LEFTCTRL, Pressed), (S, Pressed), (S, Released)],
+    );
+    assert!(h.inner.pending.is_none());
+    assert_eq!(h.key(A, Released), [(LEFTCTRL, Released)]);
+}
+
+#[test]
+fn nested_tap_hold_both_held() {
+    let mut h = home_row();
+    assert_eq!(h.key(A, Pressed), []);
+    assert_eq!(h.key(S, Pressed), []);
+    assert_eq!(h.key(J, Pressed), []);
+    assert_eq!(h.timeout(), [(LEFTCTRL, Pressed)]);
+    // S is pending again after the replay and resolves when J is released.
+    assert_eq!(
+        h.key(J, Released),
+        [(LEFTALT, Pressed), (J, Pressed), (J, Released)],
+    );
+    assert_eq!(h.key(S, Released), [(LEFTALT, Released)]);
+    assert_eq!(h.key(A, Released), [(LEFTCTRL, Released)]);
+}
+
+#[test]
+fn one_shot_consumed_by_next_key() {
+    let mut h = home_row();
+    assert_eq!(h.key(LEFTSHIFT, Pressed), [(LEFTSHIFT, Pressed)]);
+    assert_eq!(h.key(LEFTSHIFT, Released), []);
+    assert_eq!(h.key(J, Pressed), [(J, Pressed)]);
+    assert_eq!(h.key(J, Released), [(J, Released), (LEFTSHIFT, Released)]);
+    assert!(h.inner.one_shots.is_empty());
+    assert_eq!(h.key(K, Pressed), [(K, Pressed)]);
+    assert_eq!(h.key(K, Released), [(K, Released)]);
+}
+
+#[test]
+fn one_shot_used_while_held() {
+    let mut h = home_row();
+    assert_eq!(h.key(LEFTSHIFT, Pressed), [(LEFTSHIFT, Pressed)]);
+    assert_eq!(h.key(J, Pressed), [(J, Pressed)]);
+    assert_eq!(h.key(J, Released), [(J, Released)]);
+    assert_eq!(h.key(LEFTSHIFT, Released), [(LEFTSHIFT, Released)]);
+    assert!(h.inner.one_shots.is_empty());
+}
+
+#[test]
+fn one_shot_pressed_twice() {
+    let mut h = home_row();
+    assert_eq!(h.key(LEFTSHIFT, Pressed), [(LEFTSHIFT, Pressed)]);
+    assert_eq!(h.key(LEFTSHIFT, Released), []);
+    assert_eq!(h.key(LEFTSHIFT, Pressed), []);
+    assert_eq!(h.key(LEFTSHIFT, Released), []);
+    assert_eq!(h.inner.one_shots, [LEFTSHIFT]);
+}
+
+#[test]
+fn layer() {
+    let mut h = home_row();
+    assert_eq!(h.key(D, Pressed), []);
+    assert_eq!(h.key(J, Pressed), [(DOWN, Pressed)]);
+    assert_eq!(h.key(J, Released), [(DOWN, Released)]);
+    assert_eq!(h.key(D, Released), []);
+    assert_eq!(h.key(J, Pressed), [(J, Pressed)]);
+}
+
+#[test]
+fn layer_released_while_key_held() {
+    let mut h = home_row();
+    assert_eq!(h.key(D, Pressed), []);
+    assert_eq!(h.key(J, Pressed), [(DOWN, Pressed)]);
+    assert_eq!(h.key(D, Released), []);
+    // The release must match the key that was pressed in the layer.
+    assert_eq!(h.key(J, Released), [(DOWN, Released)]);
+    assert!(h.inner.layers.is_empty());
+    assert!(h.inner.held.is_empty());
+}
+
+#[test]
+fn unknown_layer() {
+    let remap = KeyRemap {
+        keys: vec![(D, RemapAction::Layer(1))],
+        layers: vec![vec![]],
+    };
+    assert!(Remap::new(&remap).is_err());
+}
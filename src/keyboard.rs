@@ -11,6 +11,7 @@ use crate::utils::oserror::OsErrorExt;
 use crate::utils::oserror::OsErrorExt2;
 use crate::utils::vecset::VecSet;
 use kbvm::Components;
+use kbvm::GroupIndex;
 use kbvm::state_machine::Event;
 use std::cell::Ref;
 use std::cell::RefCell;
@@ -36,6 +37,9 @@ pub struct KeyboardState {
     pub mods: Components,
     pub leds: Leds,
     pub leds_changed: EventSource<dyn LedsListener>,
+    /// The difference between the locked group reported by the state machine and the
+    /// locked group in `mods`. This is non-zero after [`Self::set_locked_group`].
+    pub group_offset: u32,
 }
 
 pub trait LedsListener {
@@ -53,7 +57,12 @@ impl DynKeyboardState for RefCell<KeyboardState> {
 }
 
 impl KeyboardState {
-    pub fn apply_event(&mut self, event: Event) -> bool {
+    pub fn apply_event(&mut self, mut event: Event) -> bool {
+        if self.group_offset != 0
+            && let Event::GroupLocked(g) | Event::GroupEffective(g) = &mut event
+        {
+            g.0 = (g.0 + self.group_offset) % self.map.num_groups;
+        }
         let changed = self.mods.apply_event(event);
         if changed && self.map.has_indicators {
             self.update_leds();
@@ -61,6 +70,24 @@ impl KeyboardState {
         changed
     }
 
+    /// Sets the locked group without involving the state machine.
+    ///
+    /// Returns whether the group changed.
+    pub fn set_locked_group(&mut self, group: GroupIndex) -> bool {
+        let num_groups = self.map.num_groups;
+        let new = group.0 % num_groups;
+        let old = self.mods.group_locked.0 % num_groups;
+        if new == old {
+            return false;
+        }
+        let delta = num_groups + new - old;
+        self.group_offset = (self.group_offset + delta) % num_groups;
+        self.mods.group_locked.0 = new;
+        self.mods.group.0 = (self.mods.group.0 % num_groups + delta) % num_groups;
+        self.update_leds();
+        true
+    }
+
     pub fn update_leds(&mut self) {
         if !self.map.has_indicators {
             return;
@@ -168,6 +168,15 @@ cenum! {
     LIBINPUT_CONFIG_ACCEL_PROFILE_NONE = 0,
     LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT = 1 << 0,
     LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE = 1 << 1,
+    LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM = 1 << 2,
+}
+
+cenum! {
+    AccelType, LIBINPUT_ACCEL_TYPE;
+
+    LIBINPUT_ACCEL_TYPE_FALLBACK = 0,
+    LIBINPUT_ACCEL_TYPE_MOTION = 1,
+    LIBINPUT_ACCEL_TYPE_SCROLL = 2,
 }
 
 cenum! {
@@ -221,3 +230,32 @@ cenum! {
     LIBINPUT_CONFIG_SCROLL_BUTTON_LOCK_DISABLED = 0,
     LIBINPUT_CONFIG_SCROLL_BUTTON_LOCK_ENABLED = 1,
 }
+
+cenum! {
+    ConfigDwtState, LIBINPUT_CONFIG_DWT_STATE;
+
+    LIBINPUT_CONFIG_DWT_DISABLED = 0,
+    LIBINPUT_CONFIG_DWT_ENABLED = 1,
+}
+
+cenum! {
+    ConfigDwtpState, LIBINPUT_CONFIG_DWTP_STATE;
+
+    LIBINPUT_CONFIG_DWTP_DISABLED = 0,
+    LIBINPUT_CONFIG_DWTP_ENABLED = 1,
+}
+
+cenum! {
+    ConfigTapButtonMap, LIBINPUT_CONFIG_TAP_BUTTON_MAP;
+
+    LIBINPUT_CONFIG_TAP_MAP_LRM = 0,
+    LIBINPUT_CONFIG_TAP_MAP_LMR = 1,
+}
+
+cenum! {
+    ConfigSendEventsMode, LIBINPUT_CONFIG_SEND_EVENTS_MODE;
+
+    LIBINPUT_CONFIG_SEND_EVENTS_ENABLED = 0,
+    LIBINPUT_CONFIG_SEND_EVENTS_DISABLED = 1 << 0,
+    LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE = 1 << 1,
+}
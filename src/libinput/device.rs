@@ -4,23 +4,38 @@ use crate::libinput::consts::AccelProfile;
 use crate::libinput::consts::ConfigClickMethod;
 use crate::libinput::consts::ConfigDragLockState;
 use crate::libinput::consts::ConfigDragState;
+use crate::libinput::consts::ConfigDwtState;
+use crate::libinput::consts::ConfigDwtpState;
 use crate::libinput::consts::ConfigMiddleEmulationState;
 use crate::libinput::consts::ConfigScrollButtonLockState;
 use crate::libinput::consts::ConfigScrollMethod;
+use crate::libinput::consts::ConfigSendEventsMode;
+use crate::libinput::consts::ConfigTapButtonMap;
 use crate::libinput::consts::ConfigTapState;
 use crate::libinput::consts::DeviceCapability;
+use crate::libinput::consts::LIBINPUT_ACCEL_TYPE_FALLBACK;
+use crate::libinput::consts::LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM;
 use crate::libinput::consts::LIBINPUT_CONFIG_DRAG_DISABLED;
 use crate::libinput::consts::LIBINPUT_CONFIG_DRAG_ENABLED;
 use crate::libinput::consts::LIBINPUT_CONFIG_DRAG_LOCK_DISABLED;
 use crate::libinput::consts::LIBINPUT_CONFIG_DRAG_LOCK_ENABLED;
+use crate::libinput::consts::LIBINPUT_CONFIG_DWT_DISABLED;
+use crate::libinput::consts::LIBINPUT_CONFIG_DWT_ENABLED;
+use crate::libinput::consts::LIBINPUT_CONFIG_DWTP_DISABLED;
+use crate::libinput::consts::LIBINPUT_CONFIG_DWTP_ENABLED;
 use crate::libinput::consts::LIBINPUT_CONFIG_MIDDLE_EMULATION_DISABLED;
 use crate::libinput::consts::LIBINPUT_CONFIG_MIDDLE_EMULATION_ENABLED;
 use crate::libinput::consts::LIBINPUT_CONFIG_SCROLL_BUTTON_LOCK_DISABLED;
 use crate::libinput::consts::LIBINPUT_CONFIG_SCROLL_BUTTON_LOCK_ENABLED;
+use crate::libinput::consts::LIBINPUT_CONFIG_STATUS_SUCCESS;
 use crate::libinput::consts::LIBINPUT_CONFIG_TAP_DISABLED;
 use crate::libinput::consts::LIBINPUT_CONFIG_TAP_ENABLED;
 use crate::libinput::consts::Led;
+use crate::libinput::sys::libinput_config_accel_create;
+use crate::libinput::sys::libinput_config_accel_destroy;
+use crate::libinput::sys::libinput_config_accel_set_points;
 use crate::libinput::sys::libinput_device;
+use crate::libinput::sys::libinput_device_config_accel_apply;
 use crate::libinput::sys::libinput_device_config_accel_get_profile;
 use crate::libinput::sys::libinput_device_config_accel_get_speed;
 use crate::libinput::sys::libinput_device_config_accel_is_available;
@@ -32,12 +47,21 @@ use crate::libinput::sys::libinput_device_config_calibration_set_matrix;
 use crate::libinput::sys::libinput_device_config_click_get_method;
 use crate::libinput::sys::libinput_device_config_click_get_methods;
 use crate::libinput::sys::libinput_device_config_click_set_method;
+use crate::libinput::sys::libinput_device_config_dwt_get_enabled;
+use crate::libinput::sys::libinput_device_config_dwt_is_available;
+use crate::libinput::sys::libinput_device_config_dwt_set_enabled;
+use crate::libinput::sys::libinput_device_config_dwtp_get_enabled;
+use crate::libinput::sys::libinput_device_config_dwtp_is_available;
+use crate::libinput::sys::libinput_device_config_dwtp_set_enabled;
 use crate::libinput::sys::libinput_device_config_left_handed_get;
 use crate::libinput::sys::libinput_device_config_left_handed_is_available;
 use crate::libinput::sys::libinput_device_config_left_handed_set;
 use crate::libinput::sys::libinput_device_config_middle_emulation_get_enabled;
 use crate::libinput::sys::libinput_device_config_middle_emulation_is_available;
 use crate::libinput::sys::libinput_device_config_middle_emulation_set_enabled;
+use crate::libinput::sys::libinput_device_config_rotation_get_angle;
+use crate::libinput::sys::libinput_device_config_rotation_is_available;
+use crate::libinput::sys::libinput_device_config_rotation_set_angle;
 use crate::libinput::sys::libinput_device_config_scroll_get_button;
 use crate::libinput::sys::libinput_device_config_scroll_get_button_lock;
 use crate::libinput::sys::libinput_device_config_scroll_get_method;
@@ -48,10 +72,15 @@ use crate::libinput::sys::libinput_device_config_scroll_set_button;
 use crate::libinput::sys::libinput_device_config_scroll_set_button_lock;
 use crate::libinput::sys::libinput_device_config_scroll_set_method;
 use crate::libinput::sys::libinput_device_config_scroll_set_natural_scroll_enabled;
+use crate::libinput::sys::libinput_device_config_send_events_get_mode;
+use crate::libinput::sys::libinput_device_config_send_events_get_modes;
+use crate::libinput::sys::libinput_device_config_send_events_set_mode;
+use crate::libinput::sys::libinput_device_config_tap_get_button_map;
 use crate::libinput::sys::libinput_device_config_tap_get_drag_enabled;
 use crate::libinput::sys::libinput_device_config_tap_get_drag_lock_enabled;
 use crate::libinput::sys::libinput_device_config_tap_get_enabled;
 use crate::libinput::sys::libinput_device_config_tap_get_finger_count;
+use crate::libinput::sys::libinput_device_config_tap_set_button_map;
 use crate::libinput::sys::libinput_device_config_tap_set_drag_enabled;
 use crate::libinput::sys::libinput_device_config_tap_set_drag_lock_enabled;
 use crate::libinput::sys::libinput_device_config_tap_set_enabled;
@@ -60,6 +89,7 @@ use crate::libinput::sys::libinput_device_get_id_bustype;
 use crate::libinput::sys::libinput_device_get_id_product;
 use crate::libinput::sys::libinput_device_get_id_vendor;
 use crate::libinput::sys::libinput_device_get_name;
+use crate::libinput::sys::libinput_device_get_size;
 use crate::libinput::sys::libinput_device_get_user_data;
 use crate::libinput::sys::libinput_device_group;
 use crate::libinput::sys::libinput_device_group_get_user_data;
@@ -342,6 +372,126 @@ impl<'a> LibInputDevice<'a> {
         unsafe { libinput_device_config_middle_emulation_is_available(self.dev) != 0 }
     }
 
+    pub fn dwt_available(&self) -> bool {
+        unsafe { libinput_device_config_dwt_is_available(self.dev) != 0 }
+    }
+
+    pub fn dwt_enabled(&self) -> bool {
+        let enabled = unsafe { ConfigDwtState(libinput_device_config_dwt_get_enabled(self.dev)) };
+        enabled == LIBINPUT_CONFIG_DWT_ENABLED
+    }
+
+    pub fn set_dwt_enabled(&self, enabled: bool) {
+        let enabled = match enabled {
+            true => LIBINPUT_CONFIG_DWT_ENABLED,
+            false => LIBINPUT_CONFIG_DWT_DISABLED,
+        };
+        unsafe {
+            libinput_device_config_dwt_set_enabled(self.dev, enabled.raw() as _);
+        }
+    }
+
+    pub fn dwtp_available(&self) -> bool {
+        libinput_device_config_dwtp_is_available
+            .map(|f| unsafe { f(self.dev) != 0 })
+            .unwrap_or_default()
+    }
+
+    pub fn dwtp_enabled(&self) -> bool {
+        let Some(f) = *libinput_device_config_dwtp_get_enabled else {
+            return false;
+        };
+        let enabled = unsafe { ConfigDwtpState(f(self.dev) as _) };
+        enabled == LIBINPUT_CONFIG_DWTP_ENABLED
+    }
+
+    pub fn set_dwtp_enabled(&self, enabled: bool) {
+        let Some(f) = *libinput_device_config_dwtp_set_enabled else {
+            return;
+        };
+        let enabled = match enabled {
+            true => LIBINPUT_CONFIG_DWTP_ENABLED,
+            false => LIBINPUT_CONFIG_DWTP_DISABLED,
+        };
+        unsafe {
+            f(self.dev, enabled.raw() as _);
+        }
+    }
+
+    pub fn tap_button_map(&self) -> ConfigTapButtonMap {
+        unsafe { ConfigTapButtonMap(libinput_device_config_tap_get_button_map(self.dev) as _) }
+    }
+
+    pub fn set_tap_button_map(&self, map: ConfigTapButtonMap) {
+        unsafe {
+            libinput_device_config_tap_set_button_map(self.dev, map.raw() as _);
+        }
+    }
+
+    pub fn send_events_modes(&self) -> u32 {
+        unsafe { libinput_device_config_send_events_get_modes(self.dev) }
+    }
+
+    pub fn send_events_mode(&self) -> ConfigSendEventsMode {
+        unsafe { ConfigSendEventsMode(libinput_device_config_send_events_get_mode(self.dev) as _) }
+    }
+
+    pub fn set_send_events_mode(&self, mode: ConfigSendEventsMode) {
+        unsafe {
+            libinput_device_config_send_events_set_mode(self.dev, mode.raw() as _);
+        }
+    }
+
+    pub fn rotation_available(&self) -> bool {
+        unsafe { libinput_device_config_rotation_is_available(self.dev) != 0 }
+    }
+
+    pub fn rotation(&self) -> u32 {
+        unsafe { libinput_device_config_rotation_get_angle(self.dev) as u32 }
+    }
+
+    pub fn set_rotation(&self, degrees_cw: u32) {
+        unsafe {
+            libinput_device_config_rotation_set_angle(self.dev, degrees_cw as _);
+        }
+    }
+
+    pub fn custom_accel_available(&self) -> bool {
+        libinput_config_accel_create.is_some()
+            && libinput_config_accel_destroy.is_some()
+            && libinput_config_accel_set_points.is_some()
+            && libinput_device_config_accel_apply.is_some()
+    }
+
+    pub fn set_custom_accel(&self, step: f64, points: &[f64]) -> bool {
+        let (Some(create), Some(destroy), Some(set_points), Some(apply)) = (
+            *libinput_config_accel_create,
+            *libinput_config_accel_destroy,
+            *libinput_config_accel_set_points,
+            *libinput_device_config_accel_apply,
+        ) else {
+            return false;
+        };
+        unsafe {
+            let accel = create(LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM.raw() as _);
+            if accel.is_null() {
+                return false;
+            }
+            let mut res = set_points(
+                accel,
+                LIBINPUT_ACCEL_TYPE_FALLBACK.raw() as _,
+                step,
+                points.len(),
+                points.as_ptr(),
+            );
+            if res as i32 == LIBINPUT_CONFIG_STATUS_SUCCESS.raw() {
+                res = apply(self.dev, accel);
+            }
+            destroy(accel);
+            res as i32 == LIBINPUT_CONFIG_STATUS_SUCCESS.raw()
+        }
+    }
+
     pub fn device_group(&self) -> LibInputDeviceGroup<'_> {
         LibInputDeviceGroup {
             group: unsafe { libinput_device_get_device_group(self.dev) },
@@ -361,6 +511,13 @@ impl<'a> LibInputDevice<'a> {
         libinput_device_get_id_bustype.map(|f| unsafe { f(self.dev) as u32 })
     }
 
+    pub fn size(&self) -> Option<(f64, f64)> {
+        let mut width = 0.0;
+        let mut height = 0.0;
+        let res = unsafe { libinput_device_get_size(self.dev, &mut width, &mut height) };
+        (res == 0).then_some((width, height))
+    }
+
     pub fn pad_num_buttons(&self) -> u32 {
         match unsafe { libinput_device_tablet_pad_get_num_buttons(self.dev) } {
             -1 => 0,
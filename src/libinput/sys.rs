@@ -34,6 +34,8 @@ pub struct libinput_tablet_tool(u8);
 // pub struct libinput_tablet_pad(u8);
 #[repr(transparent)]
 pub struct libinput_event_touch(u8);
+#[repr(transparent)]
+pub struct libinput_config_accel(u8);
 
 #[link(name = "input")]
 unsafe extern "C" {
@@ -154,6 +156,33 @@ unsafe extern "C" {
     pub fn libinput_device_config_middle_emulation_is_available(
         device: *mut libinput_device,
     ) -> c::c_int;
+    pub fn libinput_device_config_dwt_is_available(device: *mut libinput_device) -> c::c_int;
+    pub fn libinput_device_config_dwt_set_enabled(
+        device: *mut libinput_device,
+        enable: libinput_config_dwt_state,
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_dwt_get_enabled(
+        device: *mut libinput_device,
+    ) -> libinput_config_dwt_state;
+    pub fn libinput_device_config_tap_set_button_map(
+        device: *mut libinput_device,
+        map: libinput_config_tap_button_map,
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_tap_get_button_map(
+        device: *mut libinput_device,
+    ) -> libinput_config_tap_button_map;
+    pub fn libinput_device_config_send_events_get_modes(device: *mut libinput_device) -> u32;
+    pub fn libinput_device_config_send_events_set_mode(
+        device: *mut libinput_device,
+        mode: u32,
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_send_events_get_mode(device: *mut libinput_device) -> u32;
+    pub fn libinput_device_config_rotation_is_available(device: *mut libinput_device) -> c::c_int;
+    pub fn libinput_device_config_rotation_set_angle(
+        device: *mut libinput_device,
+        degrees_cw: c::c_uint,
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_rotation_get_angle(device: *mut libinput_device) -> c::c_uint;
 
     pub fn libinput_event_destroy(event: *mut libinput_event);
     pub fn libinput_event_get_type(event: *mut libinput_event) -> libinput_event_type;
@@ -383,6 +412,11 @@ unsafe extern "C" {
     pub fn libinput_device_tablet_pad_get_num_buttons(device: *mut libinput_device) -> c::c_int;
     pub fn libinput_device_tablet_pad_get_num_rings(device: *mut libinput_device) -> c::c_int;
     pub fn libinput_device_tablet_pad_get_num_strips(device: *mut libinput_device) -> c::c_int;
+    pub fn libinput_device_get_size(
+        device: *mut libinput_device,
+        width: *mut f64,
+        height: *mut f64,
+    ) -> c::c_int;
     pub fn libinput_tablet_pad_mode_group_get_index(
         group: *mut libinput_tablet_pad_mode_group,
     ) -> c::c_uint;
@@ -478,4 +512,34 @@ dynload! {
         group: *mut libinput_tablet_pad_mode_group,
         dial: c::c_uint,
     ) -> c::c_int;
+
+    fn libinput_device_config_dwtp_is_available(device: *mut libinput_device) -> c::c_int;
+
+    fn libinput_device_config_dwtp_set_enabled(
+        device: *mut libinput_device,
+        enable: libinput_config_dwtp_state,
+    ) -> libinput_config_status;
+
+    fn libinput_device_config_dwtp_get_enabled(
+        device: *mut libinput_device,
+    ) -> libinput_config_dwtp_state;
+
+    fn libinput_config_accel_create(
+        profile: libinput_config_accel_profile,
+    ) -> *mut libinput_config_accel;
+
+    fn libinput_config_accel_destroy(accel_config: *mut libinput_config_accel) -> ();
+
+    fn libinput_config_accel_set_points(
+        accel_config: *mut libinput_config_accel,
+        accel_type: libinput_config_accel_type,
+        step: f64,
+        npoints: usize,
+        points: *const f64,
+    ) -> libinput_config_status;
+
+    fn libinput_device_config_accel_apply(
+        device: *mut libinput_device,
+        accel_config: *mut libinput_config_accel,
+    ) -> libinput_config_status;
 }
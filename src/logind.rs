@@ -10,6 +10,7 @@ use crate::wire_dbus::org;
 use crate::wire_dbus::org::freedesktop::login1::seat::SwitchToReply;
 use crate::wire_dbus::org::freedesktop::login1::session::PauseDevice;
 use crate::wire_dbus::org::freedesktop::login1::session::ResumeDevice;
+use crate::wire_dbus::org::freedesktop::login1::session::SetBrightnessReply;
 use crate::wire_dbus::org::freedesktop::login1::session::TakeDeviceReply;
 use std::rc::Rc;
 use thiserror::Error;
@@ -148,6 +149,22 @@ impl Session {
         );
     }
 
+    pub fn set_brightness<F>(&self, subsystem: &str, name: &str, brightness: u32, f: F)
+    where
+        F: FnOnce(Result<&SetBrightnessReply, DbusError>) + 'static,
+    {
+        self.socket.call(
+            LOGIND_NAME,
+            &self.session_path,
+            org::freedesktop::login1::session::SetBrightness {
+                subsystem: subsystem.into(),
+                name: name.into(),
+                brightness,
+            },
+            f,
+        );
+    }
+
     pub fn switch_to<F>(&self, vtnr: u32, f: F)
     where
         F: FnOnce(Result<&SwitchToReply, DbusError>) + 'static,
@@ -52,8 +52,11 @@ mod tracy;
 mod acceptor;
 mod allocator;
 mod async_engine;
+mod auto_rotation;
 mod backend;
 mod backends;
+mod backlight;
+mod bar_elements;
 mod buffer_id_device;
 mod bugs;
 mod cli;
@@ -91,11 +94,13 @@ mod io_uring;
 #[cfg(feature = "it")]
 mod it;
 mod kbvm;
+mod key_remap;
 mod keyboard;
 mod libinput;
 mod logger;
 mod logind;
 mod object;
+mod output_overlay;
 mod output_schedule;
 mod pango;
 mod pipewire;
@@ -127,6 +132,7 @@ mod utils;
 mod version;
 mod video;
 mod virtual_output;
+mod vnc;
 mod vulkan_core;
 mod wheel;
 mod wire;
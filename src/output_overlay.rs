@@ -0,0 +1,118 @@
+use crate::async_engine::SpawnedFuture;
+use crate::state::State;
+use crate::text::TextTexture;
+use crate::tree::OutputNode;
+use crate::tree::TreeTimeline::LiveTL;
+use crate::utils::clonecell::CloneCell;
+use crate::utils::errorfmt::ErrorFmt;
+use crate::utils::on_drop_event::OnDropEvent;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// The slots of an output that can display an overlay.
+///
+/// The overlays are stacked above the bottom of the workspace area in this order.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OverlayKind {
+    WhichKey,
+    BacklightIndicator,
+}
+
+impl OverlayKind {
+    fn name(self) -> &'static str {
+        match self {
+            OverlayKind::WhichKey => "which-key overlay",
+            OverlayKind::BacklightIndicator => "backlight indicator",
+        }
+    }
+}
+
+/// A box of text that is rendered by the compositor on top of an output.
+pub struct OutputOverlay {
+    kind: OverlayKind,
+    pending: Cell<Option<SpawnedFuture<()>>>,
+    output: CloneCell<Option<Rc<OutputNode>>>,
+    tex: RefCell<Option<TextTexture>>,
+}
+
+impl OutputOverlay {
+    pub fn new(kind: OverlayKind) -> Self {
+        Self {
+            kind,
+            pending: Default::default(),
+            output: Default::default(),
+            tex: Default::default(),
+        }
+    }
+
+    pub fn set_pending(&self, future: SpawnedFuture<()>) {
+        self.pending.set(Some(future));
+    }
+
+    /// Cancels the pending task and removes the overlay from its output.
+    pub fn hide(&self) {
+        self.pending.take();
+        if let Some(output) = self.output.take() {
+            output.set_overlay(self.kind, None);
+        }
+    }
+
+    /// Removes the overlay from its output without cancelling the pending task.
+    ///
+    /// This can be used by the pending task itself.
+    pub fn remove(&self) {
+        if let Some(output) = self.output.take() {
+            output.set_overlay(self.kind, None);
+        }
+        self.tex.take();
+    }
+
+    pub fn clear(&self) {
+        self.hide();
+        self.tex.take();
+    }
+
+    /// Renders the markup and displays it on the output.
+    ///
+    /// Returns whether the overlay is now being displayed.
+    pub async fn show(&self, state: &Rc<State>, output: &Rc<OutputNode>, markup: &str) -> bool {
+        let Some(ctx) = state.render_ctx.get() else {
+            return false;
+        };
+        let scale = output.node_state[LiveTL].scale.get();
+        let scale = if scale != 1 {
+            Some(scale.to_f64())
+        } else {
+            None
+        };
+        let tex = TextTexture::new(state, &ctx);
+        let on_completed = Rc::new(OnDropEvent::default());
+        let event = on_completed.event();
+        tex.schedule_render_fitting(
+            on_completed,
+            None,
+            &state.theme.font.get(),
+            markup,
+            state.theme.colors.bar_text.get(),
+            true,
+            scale,
+        );
+        event.triggered().await;
+        if let Err(e) = tex.flip() {
+            log::error!("Could not render the {}: {}", self.kind.name(), ErrorFmt(e));
+            return false;
+        }
+        let Some(texture) = tex.texture() else {
+            return false;
+        };
+        if let Some(old) = self.output.set(Some(output.clone()))
+            && old.id != output.id
+        {
+            old.set_overlay(self.kind, None);
+        }
+        output.set_overlay(self.kind, Some(texture));
+        *self.tex.borrow_mut() = Some(tex);
+        true
+    }
+}
@@ -257,6 +257,10 @@ impl CairoContext {
             cairo_move_to(self.c, x, y);
         }
     }
+
+    pub fn raw(&self) -> *mut c::c_void {
+        self.c.cast()
+    }
 }
 
 impl Drop for CairoContext {
@@ -1,5 +1,6 @@
 mod ptl_display;
 mod ptl_gui;
+mod ptl_input_capture;
 mod ptl_remote_desktop;
 mod ptl_render_ctx;
 mod ptl_screencast;
@@ -28,6 +29,7 @@ use crate::pipewire::pw_con::PwConOwner;
 use crate::portal::ptl_display::PortalDisplay;
 use crate::portal::ptl_display::PortalDisplayId;
 use crate::portal::ptl_display::watch_displays;
+use crate::portal::ptl_input_capture::add_input_capture_dbus_members;
 use crate::portal::ptl_remote_desktop::add_remote_desktop_dbus_members;
 use crate::portal::ptl_render_ctx::PortalRenderCtx;
 use crate::portal::ptl_screencast::add_screencast_dbus_members;
@@ -278,6 +280,7 @@ async fn run_async(
             add_screencast_dbus_members(&state, &pw_con.con, &obj);
         }
         add_remote_desktop_dbus_members(&state, &obj);
+        add_input_capture_dbus_members(&state, &obj);
         obj
     };
     watch_displays(state.clone()).await;
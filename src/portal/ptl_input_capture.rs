@@ -0,0 +1,589 @@
+mod input_capture_gui;
+
+use crate::dbus::DbusObject;
+use crate::dbus::DictEntry;
+use crate::dbus::DynamicType;
+use crate::dbus::PendingReply;
+use crate::dbus::prelude::ObjectPath;
+use crate::dbus::prelude::Variant;
+use crate::fixed::Fixed;
+use crate::ifs::jay_compositor::INPUT_CAPTURE_SINCE;
+use crate::portal::PORTAL_SUCCESS;
+use crate::portal::PortalState;
+use crate::portal::ptl_display::PortalDisplay;
+use crate::portal::ptl_display::PortalDisplayId;
+use crate::portal::ptl_input_capture::input_capture_gui::SelectionGui;
+use crate::portal::ptl_remote_desktop::RemoteDesktopPhase;
+use crate::portal::ptl_screencast::ScreencastPhase;
+use crate::portal::ptl_session::PortalSession;
+use crate::utils::clonecell::CloneCell;
+use crate::utils::copyhashmap::CopyHashMap;
+use crate::utils::numcell::NumCell;
+use crate::wire::jay_ei_session::CaptureActivated;
+use crate::wire::jay_ei_session::CaptureDeactivated;
+use crate::wire::jay_ei_session::Zone;
+use crate::wire_dbus::org;
+use crate::wire_dbus::org::freedesktop::impl_::portal::input_capture::Activated;
+use crate::wire_dbus::org::freedesktop::impl_::portal::input_capture::ConnectToEISReply;
+use crate::wire_dbus::org::freedesktop::impl_::portal::input_capture::CreateSession;
+use crate::wire_dbus::org::freedesktop::impl_::portal::input_capture::CreateSessionReply;
+use crate::wire_dbus::org::freedesktop::impl_::portal::input_capture::Deactivated;
+use crate::wire_dbus::org::freedesktop::impl_::portal::input_capture::DisableReply;
+use crate::wire_dbus::org::freedesktop::impl_::portal::input_capture::Disabled;
+use crate::wire_dbus::org::freedesktop::impl_::portal::input_capture::EnableReply;
+use crate::wire_dbus::org::freedesktop::impl_::portal::input_capture::GetZonesReply;
+use crate::wire_dbus::org::freedesktop::impl_::portal::input_capture::Release;
+use crate::wire_dbus::org::freedesktop::impl_::portal::input_capture::ReleaseReply;
+use crate::wire_dbus::org::freedesktop::impl_::portal::input_capture::SetPointerBarriers;
+use crate::wire_dbus::org::freedesktop::impl_::portal::input_capture::SetPointerBarriersReply;
+use crate::wire_dbus::org::freedesktop::impl_::portal::input_capture::ZonesChanged;
+use crate::wire_dbus::org::freedesktop::impl_::portal::session::CloseReply as SessionCloseReply;
+use crate::wl_usr::usr_ifs::usr_jay_ei_session::UsrJayEiSession;
+use crate::wl_usr::usr_ifs::usr_jay_ei_session::UsrJayEiSessionOwner;
+use jay_proc::jay_clone;
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::ops::Deref;
+use std::rc::Rc;
+use uapi::OwnedFd;
+
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+
+#[jay_clone]
+pub enum InputCapturePhase {
+    Init,
+    Selecting(Rc<SelectingDisplay>),
+    Starting(Rc<StartingInputCapture>),
+    Started(Rc<StartedInputCapture>),
+    Terminated,
+}
+
+pub struct SelectingDisplay {
+    pub session: Rc<PortalSession>,
+    pub request_obj: Rc<DbusObject>,
+    pub guis: CopyHashMap<PortalDisplayId, Rc<SelectionGui>>,
+    pub capabilities: u32,
+    pub reply: Cell<Option<PendingReply<CreateSessionReply<'static>>>>,
+}
+
+pub struct StartingInputCapture {
+    pub session: Rc<PortalSession>,
+    pub _request_obj: Rc<DbusObject>,
+    pub dpy: Rc<PortalDisplay>,
+    pub ei_session: Rc<UsrJayEiSession>,
+    pub capabilities: u32,
+    pub reply: Cell<Option<PendingReply<CreateSessionReply<'static>>>>,
+}
+
+pub struct StartedInputCapture {
+    pub session: Rc<PortalSession>,
+    pub dpy: Rc<PortalDisplay>,
+    pub ei_session: Rc<UsrJayEiSession>,
+    pub ei_fd: Cell<Option<Rc<OwnedFd>>>,
+    pub capabilities: u32,
+    pub reply: Cell<Option<PendingReply<CreateSessionReply<'static>>>>,
+    pub pending_zones: RefCell<Vec<CaptureZone>>,
+    pub zones: RefCell<Vec<CaptureZone>>,
+    pub zone_set: NumCell<u32>,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct CaptureZone {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+bitflags! {
+    Capabilities: u32;
+
+    KEYBOARD = 1,
+    POINTER = 2,
+    TOUCHSCREEN = 4,
+}
+
+impl SelectingDisplay {
+    pub fn starting(&self, dpy: &Rc<PortalDisplay>) {
+        let builder = dpy.jc.create_ei_session();
+        builder.set_app_id(&self.session.app);
+        builder.set_input_capture();
+        let ei_session = builder.commit();
+        let starting = Rc::new(StartingInputCapture {
+            session: self.session.clone(),
+            _request_obj: self.request_obj.clone(),
+            dpy: dpy.clone(),
+            ei_session,
+            capabilities: self.capabilities,
+            reply: Cell::new(self.reply.take()),
+        });
+        self.session
+            .ic_phase
+            .set(InputCapturePhase::Starting(starting.clone()));
+        starting.ei_session.owner.set(Some(starting.clone()));
+        dpy.sessions.set(
+            self.session.session_obj.path().to_owned(),
+            self.session.clone(),
+        );
+    }
+}
+
+impl UsrJayEiSessionOwner for StartingInputCapture {
+    fn created(&self, fd: &Rc<OwnedFd>) {
+        let started = Rc::new(StartedInputCapture {
+            session: self.session.clone(),
+            dpy: self.dpy.clone(),
+            ei_session: self.ei_session.clone(),
+            ei_fd: Cell::new(Some(fd.clone())),
+            capabilities: self.capabilities,
+            reply: Cell::new(self.reply.take()),
+            pending_zones: Default::default(),
+            zones: Default::default(),
+            zone_set: NumCell::new(1),
+        });
+        self.session
+            .ic_phase
+            .set(InputCapturePhase::Started(started.clone()));
+        started.ei_session.owner.set(Some(started.clone()));
+    }
+
+    fn failed(&self, reason: &str) {
+        log::error!("Could not create session: {}", reason);
+        if let Some(reply) = self.reply.take() {
+            reply.err(reason);
+        }
+        self.session.kill();
+    }
+}
+
+impl StartedInputCapture {
+    fn signal_options(
+        &self,
+        activation_id: u32,
+        x: Fixed,
+        y: Fixed,
+    ) -> Vec<DictEntry<Cow<'static, str>, Variant<'static>>> {
+        vec![
+            DictEntry {
+                key: "activation_id".into(),
+                value: Variant::U32(activation_id),
+            },
+            DictEntry {
+                key: "cursor_position".into(),
+                value: Variant::Struct(vec![Variant::F64(x.to_f64()), Variant::F64(y.to_f64())]),
+            },
+        ]
+    }
+
+    fn session_handle(&self) -> ObjectPath<'_> {
+        ObjectPath(self.session.session_obj.path().into())
+    }
+
+    fn barrier_is_valid(&self, x1: i32, y1: i32, x2: i32, y2: i32) -> bool {
+        let zones = &*self.zones.borrow();
+        let (x1, x2) = (x1.min(x2), x1.max(x2));
+        let (y1, y2) = (y1.min(y2), y1.max(y2));
+        let overlaps = |a1: i32, a2: i32, b1: i32, b2: i32| a1 < b2 && b1 <= a2;
+        let contains_column = |x: i32| {
+            zones
+                .iter()
+                .any(|z| x >= z.x && x < z.x + z.width && overlaps(y1, y2, z.y, z.y + z.height))
+        };
+        let contains_row = |y: i32| {
+            zones
+                .iter()
+                .any(|z| y >= z.y && y < z.y + z.height && overlaps(x1, x2, z.x, z.x + z.width))
+        };
+        if x1 == x2 {
+            zones.iter().any(|z| {
+                let within = y1 >= z.y && y2 <= z.y + z.height;
+                (within && x1 == z.x && !contains_column(x1 - 1))
+                    || (within && x1 == z.x + z.width && !contains_column(x1))
+            })
+        } else if y1 == y2 {
+            zones.iter().any(|z| {
+                let within = x1 >= z.x && x2 <= z.x + z.width;
+                (within && y1 == z.y && !contains_row(y1 - 1))
+                    || (within && y1 == z.y + z.height && !contains_row(y1))
+            })
+        } else {
+            false
+        }
+    }
+}
+
+impl UsrJayEiSessionOwner for StartedInputCapture {
+    fn destroyed(&self) {
+        self.session.state.dbus.emit_signal(
+            PORTAL_PATH,
+            &Disabled {
+                session_handle: self.session_handle(),
+                options: Default::default(),
+            },
+        );
+        self.session.kill();
+    }
+
+    fn zone(&self, ev: &Zone) {
+        self.pending_zones.borrow_mut().push(CaptureZone {
+            x: ev.x,
+            y: ev.y,
+            width: ev.width,
+            height: ev.height,
+        });
+    }
+
+    fn zones_done(&self) {
+        *self.zones.borrow_mut() = self.pending_zones.take();
+        if let Some(reply) = self.reply.take() {
+            let results = [DictEntry {
+                key: "capabilities".into(),
+                value: Variant::U32(self.capabilities),
+            }];
+            reply.ok(&CreateSessionReply {
+                response: PORTAL_SUCCESS,
+                results: Cow::Borrowed(&results),
+            });
+            return;
+        }
+        let zone_set = self.zone_set.fetch_add(1);
+        let options = [DictEntry {
+            key: "zone_set".into(),
+            value: Variant::U32(zone_set),
+        }];
+        self.session.state.dbus.emit_signal(
+            PORTAL_PATH,
+            &ZonesChanged {
+                session_handle: self.session_handle(),
+                options: Cow::Borrowed(&options),
+            },
+        );
+    }
+
+    fn capture_activated(&self, ev: &CaptureActivated) {
+        let mut options = self.signal_options(ev.activation_id, ev.x, ev.y);
+        options.push(DictEntry {
+            key: "barrier_id".into(),
+            value: Variant::U32(ev.barrier_id),
+        });
+        self.session.state.dbus.emit_signal(
+            PORTAL_PATH,
+            &Activated {
+                session_handle: self.session_handle(),
+                options: Cow::Borrowed(&options),
+            },
+        );
+    }
+
+    fn capture_deactivated(&self, ev: &CaptureDeactivated) {
+        let options = self.signal_options(ev.activation_id, ev.x, ev.y);
+        self.session.state.dbus.emit_signal(
+            PORTAL_PATH,
+            &Deactivated {
+                session_handle: self.session_handle(),
+                options: Cow::Borrowed(&options),
+            },
+        );
+    }
+}
+
+impl PortalSession {
+    fn started_input_capture<T>(&self, reply: &PendingReply<T>) -> Option<Rc<StartedInputCapture>> {
+        match self.ic_phase.get() {
+            InputCapturePhase::Started(s) => Some(s),
+            _ => {
+                reply.err("Session has not been started");
+                None
+            }
+        }
+    }
+
+    fn dbus_get_zones(&self, reply: PendingReply<GetZonesReply<'static>>) {
+        let Some(started) = self.started_input_capture(&reply) else {
+            return;
+        };
+        let zone_type = DynamicType::Struct(vec![
+            DynamicType::U32,
+            DynamicType::U32,
+            DynamicType::I32,
+            DynamicType::I32,
+        ]);
+        let zones = started
+            .zones
+            .borrow()
+            .iter()
+            .map(|z| {
+                Variant::Struct(vec![
+                    Variant::U32(z.width as u32),
+                    Variant::U32(z.height as u32),
+                    Variant::I32(z.x),
+                    Variant::I32(z.y),
+                ])
+            })
+            .collect();
+        let results = [
+            DictEntry {
+                key: "zones".into(),
+                value: Variant::Array(zone_type, zones),
+            },
+            DictEntry {
+                key: "zone_set".into(),
+                value: Variant::U32(started.zone_set.get()),
+            },
+        ];
+        reply.ok(&GetZonesReply {
+            response: PORTAL_SUCCESS,
+            results: Cow::Borrowed(&results),
+        });
+    }
+
+    fn dbus_set_pointer_barriers(
+        &self,
+        req: SetPointerBarriers,
+        reply: PendingReply<SetPointerBarriersReply<'static>>,
+    ) {
+        let Some(started) = self.started_input_capture(&reply) else {
+            return;
+        };
+        if req.zone_set != started.zone_set.get() {
+            reply.err("Zone set is outdated");
+            return;
+        }
+        let mut failed = vec![];
+        started.ei_session.clear_barriers();
+        for barrier in req.barriers.iter() {
+            let mut id = None;
+            let mut position = None;
+            for entry in barrier.iter() {
+                match (&*entry.key, &entry.value) {
+                    ("barrier_id", Variant::U32(v)) => id = Some(*v),
+                    ("position", Variant::Struct(s)) => {
+                        if let [
+                            Variant::I32(x1),
+                            Variant::I32(y1),
+                            Variant::I32(x2),
+                            Variant::I32(y2),
+                        ] = &s[..]
+                        {
+                            position = Some((*x1, *y1, *x2, *y2));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            let Some(id) = id.filter(|id| *id != 0) else {
+                continue;
+            };
+            match position {
+                Some((x1, y1, x2, y2)) if started.barrier_is_valid(x1, y1, x2, y2) => {
+                    started.ei_session.add_barrier(id, x1, y1, x2, y2);
+                }
+                _ => failed.push(Variant::U32(id)),
+            }
+        }
+        let results = [DictEntry {
+            key: "failed_barriers".into(),
+            value: Variant::Array(DynamicType::U32, failed),
+        }];
+        reply.ok(&SetPointerBarriersReply {
+            response: PORTAL_SUCCESS,
+            results: Cow::Borrowed(&results),
+        });
+    }
+
+    fn dbus_enable(&self, reply: PendingReply<EnableReply>) {
+        if let Some(started) = self.started_input_capture(&reply) {
+            started.ei_session.enable_capture();
+            reply.ok(&EnableReply);
+        }
+    }
+
+    fn dbus_disable(&self, reply: PendingReply<DisableReply>) {
+        if let Some(started) = self.started_input_capture(&reply) {
+            started.ei_session.disable_capture();
+            reply.ok(&DisableReply);
+        }
+    }
+
+    fn dbus_release(&self, req: Release, reply: PendingReply<ReleaseReply>) {
+        let Some(started) = self.started_input_capture(&reply) else {
+            return;
+        };
+        let mut activation_id = None;
+        let mut position = None;
+        for entry in req.options.iter() {
+            match (&*entry.key, &entry.value) {
+                ("activation_id", Variant::U32(v)) => activation_id = Some(*v),
+                ("cursor_position", Variant::Struct(s)) => {
+                    if let [Variant::F64(x), Variant::F64(y)] = &s[..] {
+                        position = Some((Fixed::from_f64(*x), Fixed::from_f64(*y)));
+                    }
+                }
+                _ => {}
+            }
+        }
+        let Some(activation_id) = activation_id else {
+            reply.err("Release called without an activation id");
+            return;
+        };
+        started.ei_session.release_capture(activation_id, position);
+        reply.ok(&ReleaseReply);
+    }
+
+    fn dbus_connect_to_eis_input_capture(&self, reply: PendingReply<ConnectToEISReply>) {
+        let Some(started) = self.started_input_capture(&reply) else {
+            return;
+        };
+        let Some(fd) = started.ei_fd.take() else {
+            reply.err("EI file descriptor has already been consumed");
+            return;
+        };
+        reply.ok(&ConnectToEISReply { fd });
+    }
+}
+
+pub(super) fn add_input_capture_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::input_capture::*;
+    let state = state_.clone();
+    object.add_method::<CreateSession, _>(move |req, pr| {
+        dbus_create_session(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<GetZones, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_get_zones(pr);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<SetPointerBarriers, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_set_pointer_barriers(req, pr);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<Enable, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_enable(pr);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<Disable, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_disable(pr);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<Release, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_release(req, pr);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<ConnectToEIS, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_connect_to_eis_input_capture(pr);
+        }
+    });
+    object.set_property::<SupportedCapabilities>(Variant::U32((KEYBOARD | POINTER).0));
+    object.set_property::<version>(Variant::U32(1));
+}
+
+fn dbus_create_session(
+    state: &Rc<PortalState>,
+    req: CreateSession,
+    reply: PendingReply<CreateSessionReply<'static>>,
+) {
+    log::info!("Create input capture session {:#?}", req);
+    if state.sessions.contains(req.session_handle.0.deref()) {
+        reply.err("Session already exists");
+        return;
+    }
+    let mut capabilities = KEYBOARD | POINTER;
+    for entry in req.options.iter() {
+        if let ("capabilities", Variant::U32(v)) = (&*entry.key, &entry.value) {
+            capabilities = Capabilities(*v) & (KEYBOARD | POINTER);
+        }
+    }
+    if capabilities.0 == 0 {
+        reply.err("None of the requested capabilities are supported");
+        return;
+    }
+    let obj = match state.dbus.add_object(req.session_handle.0.to_string()) {
+        Ok(obj) => obj,
+        Err(_) => {
+            reply.err("Session path is not unique");
+            return;
+        }
+    };
+    let request_obj = match state.dbus.add_object(req.handle.0.to_string()) {
+        Ok(obj) => Rc::new(obj),
+        Err(_) => {
+            reply.err("Request handle is not unique");
+            return;
+        }
+    };
+    let session = Rc::new(PortalSession {
+        _id: state.id(),
+        state: state.clone(),
+        pw_con: state.pw_con.clone(),
+        app: req.app_id.to_string(),
+        session_obj: obj,
+        sc_phase: CloneCell::new(ScreencastPhase::Init),
+        rd_phase: CloneCell::new(RemoteDesktopPhase::Init),
+        ic_phase: CloneCell::new(InputCapturePhase::Init),
+        start_reply: Default::default(),
+    });
+    {
+        use org::freedesktop::impl_::portal::session::*;
+        let ses = session.clone();
+        session.session_obj.add_method::<Close, _>(move |_, pr| {
+            ses.kill();
+            pr.ok(&SessionCloseReply);
+        });
+        session.session_obj.set_property::<version>(Variant::U32(2));
+    }
+    {
+        use org::freedesktop::impl_::portal::request::*;
+        let ses = session.clone();
+        request_obj.add_method::<Close, _>(move |_, pr| {
+            ses.kill();
+            pr.ok(&CloseReply);
+        });
+    }
+    let guis = CopyHashMap::new();
+    for dpy in state.displays.lock().values() {
+        if dpy.outputs.len() > 0 && dpy.jc.version >= INPUT_CAPTURE_SINCE {
+            guis.set(dpy.id, SelectionGui::new(&session, dpy));
+        }
+    }
+    if guis.is_empty() {
+        reply.err("There are no running displays");
+        return;
+    }
+    state
+        .sessions
+        .set(req.session_handle.0.to_string(), session.clone());
+    session
+        .ic_phase
+        .set(InputCapturePhase::Selecting(Rc::new(SelectingDisplay {
+            session: session.clone(),
+            request_obj,
+            guis,
+            capabilities: capabilities.0,
+            reply: Cell::new(Some(reply)),
+        })));
+}
+
+fn get_session<T>(
+    state: &Rc<PortalState>,
+    reply: &PendingReply<T>,
+    handle: &str,
+) -> Option<Rc<PortalSession>> {
+    let res = state.sessions.get(handle);
+    if res.is_none() {
+        let msg = format!("Input capture session `{}` does not exist", handle);
+        reply.err(&msg);
+    }
+    res
+}
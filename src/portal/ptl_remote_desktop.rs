@@ -8,6 +8,7 @@ use crate::portal::PORTAL_SUCCESS;
 use crate::portal::PortalState;
 use crate::portal::ptl_display::PortalDisplay;
 use crate::portal::ptl_display::PortalDisplayId;
+use crate::portal::ptl_input_capture::InputCapturePhase;
 use crate::portal::ptl_remote_desktop::remote_desktop_gui::SelectionGui;
 use crate::portal::ptl_screencast::ScreencastPhase;
 use crate::portal::ptl_session::PortalSession;
@@ -267,6 +268,7 @@ fn dbus_create_session(
         session_obj: obj,
         sc_phase: CloneCell::new(ScreencastPhase::Init),
         rd_phase: CloneCell::new(RemoteDesktopPhase::Init),
+        ic_phase: CloneCell::new(InputCapturePhase::Init),
         start_reply: Default::default(),
     });
     {
@@ -35,6 +35,7 @@ use crate::portal::PortalState;
 use crate::portal::ptl_display::PortalDisplay;
 use crate::portal::ptl_display::PortalDisplayId;
 use crate::portal::ptl_display::PortalOutput;
+use crate::portal::ptl_input_capture::InputCapturePhase;
 use crate::portal::ptl_remote_desktop::RemoteDesktopPhase;
 use crate::portal::ptl_screencast::screencast_gui::SelectionGui;
 use crate::portal::ptl_session::PortalSession;
@@ -807,6 +808,7 @@ fn dbus_create_session(
         session_obj: obj,
         sc_phase: CloneCell::new(ScreencastPhase::Init),
         rd_phase: CloneCell::new(RemoteDesktopPhase::Init),
+        ic_phase: CloneCell::new(InputCapturePhase::Init),
         start_reply: Default::default(),
     });
     {
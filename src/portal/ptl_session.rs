@@ -7,6 +7,7 @@ use crate::dbus::prelude::Variant;
 use crate::pipewire::pw_con::PwCon;
 use crate::portal::PORTAL_SUCCESS;
 use crate::portal::PortalState;
+use crate::portal::ptl_input_capture::InputCapturePhase;
 use crate::portal::ptl_remote_desktop::DeviceTypes;
 use crate::portal::ptl_remote_desktop::RemoteDesktopPhase;
 use crate::portal::ptl_screencast::ScreencastPhase;
@@ -30,6 +31,7 @@ pub struct PortalSession {
     pub session_obj: DbusObject,
     pub sc_phase: CloneCell<ScreencastPhase>,
     pub rd_phase: CloneCell<RemoteDesktopPhase>,
+    pub ic_phase: CloneCell<InputCapturePhase>,
     pub start_reply: Cell<Option<PortalSessionReply>>,
 }
 
@@ -61,6 +63,32 @@ impl PortalSession {
                 s.dpy.sessions.remove(self.session_obj.path());
             }
         }
+        match self.ic_phase.set(InputCapturePhase::Terminated) {
+            InputCapturePhase::Init => {}
+            InputCapturePhase::Terminated => {}
+            InputCapturePhase::Selecting(s) => {
+                for gui in s.guis.lock().drain_values() {
+                    gui.kill(false);
+                }
+                if let Some(reply) = s.reply.take() {
+                    reply.err("Session has been terminated");
+                }
+            }
+            InputCapturePhase::Starting(s) => {
+                s.ei_session.con.remove_obj(s.ei_session.deref());
+                s.dpy.sessions.remove(self.session_obj.path());
+                if let Some(reply) = s.reply.take() {
+                    reply.err("Session has been terminated");
+                }
+            }
+            InputCapturePhase::Started(s) => {
+                s.ei_session.con.remove_obj(s.ei_session.deref());
+                s.dpy.sessions.remove(self.session_obj.path());
+                if let Some(reply) = s.reply.take() {
+                    reply.err("Session has been terminated");
+                }
+            }
+        }
         match self.sc_phase.set(ScreencastPhase::Terminated) {
             ScreencastPhase::Init => {}
             ScreencastPhase::SourcesSelected(_) => {}
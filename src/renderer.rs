@@ -209,9 +209,10 @@ impl Renderer<'_> {
                 }
                 x += bar_rect.x1() - non_exclusive_rect_rel.x1();
                 y += bar_rect.y1() - non_exclusive_rect_rel.y1();
-                if let Some(status) = &rd.status
-                    && let Some(texture) = status.tex.texture()
-                {
+                for status in [&rd.bar_elements, &rd.status].into_iter().flatten() {
+                    let Some(texture) = status.tex.texture() else {
+                        continue;
+                    };
                     let (x, y) = self.base.scale_point(x + status.tex_x, y);
                     self.base.render_texture(
                         &texture,
@@ -280,6 +281,70 @@ impl Renderer<'_> {
                 self.base.fill_boxes(&[bounds], &color, srgb, perceptual);
             }
         }
+        let padding = self.state.theme.sizes.title_height.get(RenderTL) / 2;
+        let mut bottom = y + ns.rects.workspace_rel.get().y2() - padding;
+        for overlay in [&output.which_key, &output.backlight_indicator] {
+            if let Some(tex) = overlay.get() {
+                bottom = self.render_overlay(output, &tex, x, bottom) - padding;
+            }
+        }
+    }
+
+    /// Renders an overlay centered above `bottom` and returns the y coordinate of its
+    /// top edge.
+    fn render_overlay(
+        &mut self,
+        output: &OutputNode,
+        tex: &Rc<dyn GfxTexture>,
+        x: i32,
+        bottom: i32,
+    ) -> i32 {
+        let ns = &output.node_state[RenderTL];
+        let theme = &self.state.theme;
+        let scale = ns.scale.get();
+        let (mut width, mut height) = tex.size();
+        if scale != 1 {
+            let scale = scale.to_f64();
+            width = (width as f64 / scale).round() as _;
+            height = (height as f64 / scale).round() as _;
+        }
+        let border = theme.sizes.border_width.get(RenderTL);
+        let padding = theme.sizes.title_height.get(RenderTL) / 2;
+        let inset = border + padding;
+        let ws = ns.rects.workspace_rel.get();
+        let outer = Rect::new_sized_saturating(
+            x + ws.x1() + (ws.width() - width - 2 * inset) / 2,
+            bottom - height - 2 * inset,
+            width + 2 * inset,
+            height + 2 * inset,
+        );
+        let inner = Rect::new_sized_saturating(
+            outer.x1() + border,
+            outer.y1() + border,
+            outer.width() - 2 * border,
+            outer.height() - 2 * border,
+        );
+        let srgb = &self.state.color_manager.srgb_gamma22().linear;
+        let perceptual = RenderIntent::Perceptual;
+        self.base.sync();
+        let c = theme.colors.focused_border.get();
+        self.base.fill_boxes(&[outer], &c, srgb, perceptual);
+        let c = theme.colors.bar_background.get();
+        self.base.fill_boxes(&[inner], &c, srgb, perceptual);
+        self.base.sync();
+        let (tx, ty) = self
+            .base
+            .scale_point(outer.x1() + inset, outer.y1() + inset);
+        self.base.render_texture(
+            tex,
+            tx,
+            ty,
+            RenderTexture {
+                tscale: Some(scale),
+                ..Default::default()
+            },
+        );
+        outer.y1()
     }
 
     pub fn render_workspace(&mut self, workspace: &WorkspaceNode, x: i32, y: i32) {
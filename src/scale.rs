@@ -3,6 +3,9 @@ use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
 
+#[cfg(test)]
+mod tests;
+
 pub const SCALE_BASE: u32 = 120;
 const BASE64: i64 = SCALE_BASE as i64;
 pub const SCALE_BASEF: f64 = SCALE_BASE as f64;
@@ -57,6 +60,36 @@ impl Scale {
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AutoScale {
+    pub dpi: f64,
+    pub step: f64,
+}
+
+impl AutoScale {
+    pub fn compute(self, width: i32, height: i32, width_mm: i32, height_mm: i32) -> Option<Scale> {
+        if width <= 0 || height <= 0 {
+            return None;
+        }
+        if width_mm < 10 || height_mm < 10 {
+            return None;
+        }
+        // Some displays, in particular projectors, report their aspect ratio instead of
+        // their physical size.
+        const ASPECT_RATIOS: [(i32, i32); 5] = [(160, 90), (160, 100), (16, 9), (16, 10), (40, 30)];
+        for (w, h) in ASPECT_RATIOS {
+            if (width_mm, height_mm) == (w, h) || (width_mm, height_mm) == (h, w) {
+                return None;
+            }
+        }
+        let diagonal_px = (width as f64).hypot(height as f64);
+        let diagonal_in = (width_mm as f64).hypot(height_mm as f64) / 25.4;
+        let dpi = diagonal_px / diagonal_in;
+        let scale = (dpi / self.dpi / self.step).round() * self.step;
+        Some(Scale::from_f64(scale.max(1.0)))
+    }
+}
+
 impl PartialEq<u32> for Scale {
     fn eq(&self, other: &u32) -> bool {
         self.0 == other * SCALE_BASE
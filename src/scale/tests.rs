@@ -0,0 +1,43 @@
+use crate::scale::AutoScale;
+use crate::scale::Scale;
+
+const AUTO: AutoScale = AutoScale {
+    dpi: 96.0,
+    step: 0.25,
+};
+
+#[test]
+fn auto_scale() {
+    // 24" 1080p
+    assert_eq!(AUTO.compute(1920, 1080, 527, 296), Some(Scale::from_int(1)));
+    // 27" 4k
+    assert_eq!(
+        AUTO.compute(3840, 2160, 597, 336),
+        Some(Scale::from_f64(1.75))
+    );
+    // 13.3" 2560x1600
+    assert_eq!(
+        AUTO.compute(2560, 1600, 286, 179),
+        Some(Scale::from_f64(2.25))
+    );
+    // 14" 1080p
+    assert_eq!(
+        AUTO.compute(1920, 1080, 309, 174),
+        Some(Scale::from_f64(1.75))
+    );
+    // large TV
+    assert_eq!(
+        AUTO.compute(1920, 1080, 1600, 900),
+        Some(Scale::from_int(1))
+    );
+    let step = AutoScale { step: 1.0, ..AUTO };
+    assert_eq!(step.compute(3840, 2160, 597, 336), Some(Scale::from_int(2)));
+}
+
+#[test]
+fn auto_scale_unknown_size() {
+    assert_eq!(AUTO.compute(1920, 1080, 0, 0), None);
+    assert_eq!(AUTO.compute(1920, 1080, 160, 90), None);
+    assert_eq!(AUTO.compute(1920, 1200, 16, 10), None);
+    assert_eq!(AUTO.compute(0, 0, 527, 296), None);
+}
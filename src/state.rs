@@ -2,6 +2,7 @@ use crate::acceptor::Acceptor;
 use crate::allocator::BufferObject;
 use crate::async_engine::AsyncEngine;
 use crate::async_engine::SpawnedFuture;
+use crate::auto_rotation::AutoRotation;
 use crate::backend::Backend;
 use crate::backend::BackendConnectorState;
 use crate::backend::BackendConnectorStateSerials;
@@ -18,9 +19,18 @@ use crate::backend::InputDeviceGroupIds;
 use crate::backend::InputDeviceId;
 use crate::backend::InputDeviceIds;
 use crate::backend::InputDeviceScrollMethod;
+use crate::backend::InputDeviceSendEventsMode;
+use crate::backend::Mode;
 use crate::backend::MonitorInfo;
 use crate::backend::transaction::BackendConnectorTransactionError;
 use crate::backends::dummy::DummyBackend;
+use crate::backlight::Backlight;
+use crate::backlight::BacklightError;
+use crate::backlight::BacklightIndicator;
+use crate::backlight::hdr_percent_to_white;
+use crate::backlight::hdr_white_range;
+use crate::backlight::hdr_white_to_percent;
+use crate::bar_elements::BarElements;
 use crate::buffer_id_device::BufferIdDeviceDyn;
 use crate::buffer_id_device::BufferIdDeviceRegistry;
 use crate::cli::RunArgs;
@@ -64,6 +74,8 @@ use crate::egui_adapter::egui_platform::EggState;
 use crate::ei::ei_acceptor::EiAcceptor;
 use crate::ei::ei_client::EiClient;
 use crate::ei::ei_client::EiClients;
+use crate::env::XCURSOR_SIZE;
+use crate::env::XCURSOR_THEME;
 use crate::evdev::input_event_codes::InputEventCode;
 use crate::eventfd_cache::EventfdCache;
 use crate::fixed::Fixed;
@@ -107,6 +119,7 @@ use crate::ifs::jay_seat_events::JaySeatEvents;
 use crate::ifs::jay_workspace_watcher::JayWorkspaceWatcher;
 use crate::ifs::wl_buffer::WlBuffer;
 use crate::ifs::wl_output::BlendSpace;
+use crate::ifs::wl_output::DEFAULT_TOUCH_EDGE_WIDTH;
 use crate::ifs::wl_output::OutputGlobalOpt;
 use crate::ifs::wl_output::OutputId;
 use crate::ifs::wl_output::PersistentOutputState;
@@ -115,6 +128,8 @@ use crate::ifs::wl_seat::PhysicalKeyboardIds;
 use crate::ifs::wl_seat::PositionHintRequest;
 use crate::ifs::wl_seat::SeatIds;
 use crate::ifs::wl_seat::WlSeatGlobal;
+use crate::ifs::wl_seat::input_capture::InputCapture;
+use crate::ifs::wl_seat::tablet::TabletConfig;
 use crate::ifs::wl_seat::tablet::TabletIds;
 use crate::ifs::wl_seat::tablet::TabletInit;
 use crate::ifs::wl_seat::tablet::TabletPadIds;
@@ -134,6 +149,7 @@ use crate::ifs::wl_surface::zwp_idle_inhibitor_v1::IdleInhibitorId;
 use crate::ifs::wl_surface::zwp_idle_inhibitor_v1::IdleInhibitorIds;
 use crate::ifs::wl_surface::zwp_idle_inhibitor_v1::ZwpIdleInhibitorV1;
 use crate::ifs::wl_surface::zwp_input_popup_surface_v2::ZwpInputPopupSurfaceV2;
+use crate::ifs::wl_surface::zwp_keyboard_shortcuts_inhibitor_v1::ShortcutsInhibitPolicy;
 use crate::ifs::wlr_output_manager::WlrOutputManagerState;
 use crate::ifs::wlr_output_manager::zwlr_output_head_v1::ZwlrOutputHeadV1;
 use crate::ifs::wlr_output_manager::zwlr_output_manager_v1::WlrOutputManagerId;
@@ -142,10 +158,12 @@ use crate::ifs::wp_drm_lease_connector_v1::WpDrmLeaseConnectorV1;
 use crate::ifs::wp_drm_lease_device_v1::WpDrmLeaseDeviceV1Global;
 use crate::ifs::xdg_activation_token_v1::ActivationToken;
 use crate::ifs::zwlr_foreign_toplevel_manager_v1::ZwlrForeignToplevelManagerV1;
+use crate::ifs::zwlr_output_power_v1::ZwlrOutputPowerV1;
 use crate::ifs::zwlr_screencopy_frame_v1::ZwlrScreencopyFrameV1;
 use crate::io_uring::IoUring;
 use crate::kbvm::KbvmContext;
 use crate::kbvm::KbvmMap;
+use crate::key_remap::KeyRemapper;
 use crate::keyboard::KeyboardStateIds;
 use crate::keyboard::LedsListener;
 use crate::leaks::Tracker;
@@ -155,6 +173,7 @@ use crate::rect::Rect;
 use crate::rect::Region;
 use crate::renderer::Renderer;
 use crate::renderer::renderer_base::RenderTexture;
+use crate::scale::AutoScale;
 use crate::scale::Scale;
 use crate::security_context_acceptor::SecurityContextAcceptors;
 use crate::sm::SessionManager;
@@ -235,8 +254,12 @@ use crate::utils::sleeper::Sleeper;
 use crate::video::Modifier;
 use crate::video::dmabuf::DmaBufIds;
 use crate::video::drm::Drm;
+use crate::video::drm::DrmModeInfo;
 use crate::video::drm::NodeType;
 use crate::virtual_output::VirtualOutputs;
+use crate::vnc::VncAddress;
+use crate::vnc::VncError;
+use crate::vnc::VncServer;
 use crate::wheel::Wheel;
 use crate::wire::ExtForeignToplevelListV1Id;
 use crate::wire::ExtIdleNotificationV1Id;
@@ -244,8 +267,10 @@ use crate::wire::JayRenderCtxId;
 use crate::wire::JaySeatEventsId;
 use crate::wire::JayWorkspaceWatcherId;
 use crate::wire::ZwlrForeignToplevelManagerV1Id;
+use crate::wire::ZwlrOutputPowerV1Id;
 use crate::xwayland::XWaylandEvent;
 use crate::xwayland::{self};
+use ahash::AHashMap;
 use bstr::ByteSlice;
 use isnt::std_1::primitive::IsntSliceExt;
 use jay_config::PciId;
@@ -283,6 +308,8 @@ pub struct State {
     pub render_ctx_version: NumCell<u32>,
     pub render_ctx_ever_initialized: Cell<bool>,
     pub cursors: CloneCell<Option<Rc<ServerCursors>>>,
+    pub themed_cursors: RefCell<AHashMap<Rc<String>, Rc<ServerCursors>>>,
+    pub cursor_env: RefCell<Option<(Option<Rc<String>>, u32)>>,
     pub wheel: Rc<Wheel>,
     pub clients: Clients,
     pub globals: Globals,
@@ -324,6 +351,7 @@ pub struct State {
     pub drm_devs: CopyHashMap<DrmDeviceId, Rc<DrmDevData>>,
     pub drm_devs_by_dev_t: CopyHashMap<dev_t, Rc<DrmDevData>>,
     pub status: CloneCell<Rc<String>>,
+    pub bar_elements: BarElements,
     pub idle: IdleState,
     pub run_args: RunArgs,
     pub xwayland: XWaylandState,
@@ -338,6 +366,7 @@ pub struct State {
     pub lock: ScreenlockState,
     pub scales: RefCounted<Scale>,
     pub cursor_sizes: RefCounted<u32>,
+    pub cursor_themes: RefCounted<Rc<String>>,
     pub hardware_tick_cursor: AsyncQueue<Option<Rc<dyn Cursor>>>,
     pub testers: RefCell<BHashMap<(ClientId, JaySeatEventsId), Rc<JaySeatEvents>>>,
     pub render_ctx_watchers: CopyHashMap<(ClientId, JayRenderCtxId), Rc<JayRenderCtx>>,
@@ -373,11 +402,16 @@ pub struct State {
     pub default_vrr_mode: Cell<VrrMode>,
     pub default_vrr_cursor_hz: Cell<Option<f64>>,
     pub default_tearing_mode: Cell<TearingMode>,
+    pub auto_scale: Cell<Option<AutoScale>>,
+    pub vnc_servers: CopyHashMap<String, Rc<VncServer>>,
+    pub backlight_indicator: BacklightIndicator,
+    pub auto_rotation: AutoRotation,
     pub ei_acceptor: CloneCell<Option<Rc<EiAcceptor>>>,
     pub ei_acceptor_future: CloneCell<Option<SpawnedFuture<()>>>,
     pub enable_ei_acceptor: Cell<bool>,
     pub ei_clients: EiClients,
     pub slow_ei_clients: AsyncQueue<Rc<EiClient>>,
+    pub input_captures: CopyHashMap<ClientId, Rc<InputCapture>>,
     pub cpu_worker: Rc<CpuWorker>,
     pub ui_drag_enabled: Cell<bool>,
     pub ui_drag_threshold_squared: Cell<i32>,
@@ -567,10 +601,13 @@ pub struct DeviceHandlerData {
     pub syspath: Option<String>,
     pub devnode: Option<String>,
     pub keymap: CloneCell<Option<Rc<KbvmMap>>>,
+    pub key_remap: CloneCell<Option<Rc<KeyRemapper>>>,
     pub output: CloneCell<Option<Rc<OutputGlobalOpt>>>,
     pub tablet_init: Option<Box<TabletInit>>,
     pub tablet_pad_init: Option<Box<TabletPadInit>>,
+    pub tablet_config: TabletConfig,
     pub scroll_methods: StaticCopyMap<InputDeviceScrollMethod, bool>,
+    pub send_events_modes: StaticCopyMap<InputDeviceSendEventsMode, bool>,
     pub input_event_codes: Vec<InputEventCode>,
     pub is_touch: bool,
     pub is_kb: bool,
@@ -593,6 +630,9 @@ pub struct ConnectorData {
     pub state: RefCell<BackendConnectorState>,
     pub head_manager: HeadManager,
     pub wlr_output_heads: CopyHashMap<WlrOutputManagerId, Rc<ZwlrOutputHeadV1>>,
+    pub dpms_off: Cell<bool>,
+    pub wlr_output_powers: CopyHashMap<(ClientId, ZwlrOutputPowerV1Id), Rc<ZwlrOutputPowerV1>>,
+    pub backlight: Option<Rc<Backlight>>,
 }
 
 pub struct OutputData {
@@ -661,6 +701,40 @@ impl ConnectorData {
         Ok(())
     }
 
+    /// Returns whether the connector should be active. Turning DPMS on while the
+    /// backend is idle only takes effect once the backend wakes up.
+    pub fn should_be_active(&self, backend_idle: bool) -> bool {
+        !backend_idle && !self.dpms_off.get()
+    }
+
+    pub fn set_dpms(
+        &self,
+        state: &State,
+        on: bool,
+    ) -> Result<(), BackendConnectorTransactionError> {
+        let prev = self.dpms_off.replace(!on);
+        let active = self.should_be_active(state.idle.backend_idle.get());
+        let res = self.modify_state(state, |s| s.active = active);
+        if res.is_err() {
+            self.dpms_off.set(prev);
+        } else if prev == on {
+            state.trigger_cci(CCI_OUTPUTS);
+        }
+        res
+    }
+
+    pub fn set_custom_mode(
+        &self,
+        state: &State,
+        mode: DrmModeInfo,
+    ) -> Result<(), BackendConnectorTransactionError> {
+        let mode = Rc::new(mode);
+        self.modify_state(state, |s| {
+            s.mode = mode.to_backend();
+            s.custom_mode = Some(mode);
+        })
+    }
+
     pub fn set_state(&self, state: &State, s: BackendConnectorState) {
         let old = self.state.borrow().clone();
         if old.serial >= s.serial {
@@ -681,6 +755,9 @@ impl ConnectorData {
         }
         if b!(old.active != s.active) {
             self.head_manager.handle_active_change(s.active);
+            for power in self.wlr_output_powers.lock().values() {
+                power.send_mode(s.active);
+            }
         }
         if b!(old.non_desktop_override != s.non_desktop_override) {
             self.head_manager
@@ -818,6 +895,53 @@ impl State {
         }
     }
 
+    pub fn add_cursor_theme(&self, theme: &Rc<String>) {
+        if self.cursor_themes.add(theme.clone())
+            && let Some(ctx) = self.render_ctx.get()
+            && let Some(cursors) = self.load_cursors(&ctx, Some(theme))
+        {
+            self.themed_cursors
+                .borrow_mut()
+                .insert(theme.clone(), cursors);
+        }
+    }
+
+    pub fn remove_cursor_theme(&self, theme: &Rc<String>) {
+        if self.cursor_themes.remove(theme) {
+            self.themed_cursors.borrow_mut().remove(theme);
+        }
+    }
+
+    pub fn server_cursors(&self, theme: Option<&Rc<String>>) -> Option<Rc<ServerCursors>> {
+        match theme {
+            None => self.cursors.get(),
+            Some(theme) => self.themed_cursors.borrow().get(theme).cloned(),
+        }
+    }
+
+    pub fn update_cursor_env(&self) {
+        let Some(group) = self.cursor_user_group_hardware_cursor.get() else {
+            return;
+        };
+        let env = (group.cursor_theme(), group.cursor_size());
+        if self.cursor_env.borrow().as_ref() == Some(&env) {
+            return;
+        }
+        if let Some(forker) = self.forker.get() {
+            match env.0.as_deref().map(|t| t.as_str()).or(*XCURSOR_THEME) {
+                Some(theme) => forker.setenv(XCURSOR_THEME.name().as_bytes(), theme.as_bytes()),
+                None => forker.unsetenv(XCURSOR_THEME.name().as_bytes()),
+            }
+            forker.setenv(XCURSOR_SIZE.name().as_bytes(), env.1.to_string().as_bytes());
+        }
+        *self.cursor_env.borrow_mut() = Some(env);
+        if self.xwayland.display.is_some() {
+            self.xwayland
+                .queue
+                .push(XWaylandEvent::CursorSettingsChanged);
+        }
+    }
+
     fn output_scales_changed(&self) {
         self.visit_all_nodes(&mut UpdateTextTexturesVisitor);
         self.reload_cursors();
@@ -869,6 +993,7 @@ impl State {
         self.render_ctx.set(ctx.clone());
         self.render_ctx_version.fetch_add(1);
         self.cursors.set(None);
+        self.themed_cursors.borrow_mut().clear();
         self.icons.clear();
         self.wait_for_syncobj
             .set_ctx(ctx.as_ref().and_then(|c| c.syncobj_ctx().cloned()));
@@ -978,16 +1103,30 @@ impl State {
         self.trigger_cci(CCI_COLOR_MANAGEMENT | CCI_GPUS);
     }
 
+    fn load_cursors(
+        &self,
+        ctx: &Rc<dyn GfxContext>,
+        theme: Option<&Rc<String>>,
+    ) -> Option<Rc<ServerCursors>> {
+        match ServerCursors::load(ctx, self, theme.map(|t| t.as_str())) {
+            Ok(c) => c.map(Rc::new),
+            Err(e) => {
+                log::error!("Could not load the cursors: {}", ErrorFmt(e));
+                None
+            }
+        }
+    }
+
     fn reload_cursors(&self) {
         if let Some(ctx) = self.render_ctx.get() {
-            let cursors = match ServerCursors::load(&ctx, self) {
-                Ok(c) => c.map(Rc::new),
-                Err(e) => {
-                    log::error!("Could not load the cursors: {}", ErrorFmt(e));
-                    None
+            self.cursors.set(self.load_cursors(&ctx, None));
+            let mut themed_cursors = AHashMap::new();
+            for (theme, _) in &*self.cursor_themes.lock() {
+                if let Some(cursors) = self.load_cursors(&ctx, Some(theme)) {
+                    themed_cursors.insert(theme.clone(), cursors);
                 }
-            };
-            self.cursors.set(cursors);
+            }
+            *self.themed_cursors.borrow_mut() = themed_cursors;
             for cursor_user_group in self.cursor_user_groups.lock().values() {
                 cursor_user_group.reload_known_cursor();
             }
@@ -1479,6 +1618,9 @@ impl State {
         for (_, ws) in self.workspaces.clear() {
             ws.clear();
         }
+        for server in self.vnc_servers.clear().into_values() {
+            server.kill();
+        }
         {
             let seats = mem::take(self.globals.seats.lock().deref_mut());
             for seat in seats.values() {
@@ -1497,8 +1639,11 @@ impl State {
         self.eng.clear();
         self.ei_acceptor.take();
         self.ei_acceptor_future.take();
+        self.backlight_indicator.clear();
+        self.auto_rotation.clear();
         self.ei_clients.clear();
         self.slow_ei_clients.clear();
+        self.input_captures.clear();
         self.toplevels.clear();
         self.workspace_managers.clear();
         self.cl_matcher_manager.clear();
@@ -1845,6 +1990,9 @@ impl State {
         for seat in self.globals.seats.lock().values() {
             seat.output_extents_changed();
         }
+        for capture in self.input_captures.lock().values() {
+            capture.zones_changed();
+        }
     }
 
     pub fn update_ei_acceptor(self: &Rc<Self>) {
@@ -1972,6 +2120,10 @@ impl State {
         self.config.get()?.initial_tile_state(data)
     }
 
+    pub fn shortcuts_inhibit_policy(&self, data: &ToplevelData) -> Option<ShortcutsInhibitPolicy> {
+        self.config.get()?.shortcuts_inhibit_policy(data)
+    }
+
     pub fn update_capabilities(
         &self,
         data: &Rc<Client>,
@@ -2400,6 +2552,81 @@ impl State {
         }
     }
 
+    pub fn compute_auto_scale(&self, mode: &Mode, width_mm: i32, height_mm: i32) -> Option<Scale> {
+        let auto_scale = self.auto_scale.get()?;
+        auto_scale.compute(mode.width, mode.height, width_mm, height_mm)
+    }
+
+    pub fn set_vnc_server(
+        self: &Rc<Self>,
+        connector: &str,
+        address: Option<&str>,
+    ) -> Result<(), VncError> {
+        let address = address.map(VncAddress::parse).transpose()?;
+        if let Some(old) = self.vnc_servers.get(connector) {
+            if Some(old.address()) == address.as_ref() {
+                return Ok(());
+            }
+            self.vnc_servers.remove(connector);
+            old.kill();
+        }
+        if let Some(address) = address {
+            let server = VncServer::spawn(self, connector, address)?;
+            self.vnc_servers.set(connector.to_string(), server);
+        }
+        Ok(())
+    }
+
+    fn backlight_output(&self, connector: &ConnectorData) -> Option<Rc<OutputNode>> {
+        self.outputs.get(&connector.id)?.node.clone()
+    }
+
+    pub fn get_backlight(&self, connector: &ConnectorData) -> Result<f64, BacklightError> {
+        if let Some(output) = self.backlight_output(connector)
+            && let Some(range) = hdr_white_range(&output)
+        {
+            let white = output.global.persistent.brightness.get();
+            return Ok(hdr_white_to_percent(range, white));
+        }
+        let Some(backlight) = &connector.backlight else {
+            return Err(BacklightError::NotSupported(connector.name.to_string()));
+        };
+        Ok(backlight.to_percent(backlight.brightness()?))
+    }
+
+    pub fn set_backlight(
+        self: &Rc<Self>,
+        connector: &ConnectorData,
+        value: f64,
+        relative: bool,
+    ) -> Result<(), BacklightError> {
+        let output = self.backlight_output(connector);
+        let percent;
+        if let Some(output) = &output
+            && let Some(range) = hdr_white_range(output)
+        {
+            let current = hdr_white_to_percent(range, output.global.persistent.brightness.get());
+            let new = match relative {
+                true => current + value,
+                false => value,
+            };
+            let white = hdr_percent_to_white(range, new);
+            output.set_brightness(Some(white));
+            percent = hdr_white_to_percent(range, Some(white));
+        } else if let Some(backlight) = &connector.backlight {
+            let current = backlight.brightness()?;
+            let new = backlight.compute(current, value, relative);
+            self.backend.get().set_backlight(backlight, new);
+            percent = backlight.to_percent(new);
+        } else {
+            return Err(BacklightError::NotSupported(connector.name.to_string()));
+        }
+        if let Some(output) = &output {
+            self.backlight_indicator.show(self, output, percent);
+        }
+        Ok(())
+    }
+
     pub fn new_persistent_output_state(&self) -> Rc<PersistentOutputState> {
         let x1 = self
             .root
@@ -2420,6 +2647,9 @@ impl State {
             brightness: Cell::new(None),
             blend_space: Cell::new(BlendSpace::Srgb),
             use_native_gamut: Cell::new(false),
+            icc_profile: Default::default(),
+            icc_profile_serial: Default::default(),
+            touch_edge_width: Cell::new(DEFAULT_TOUCH_EDGE_WIDTH),
         })
     }
 
@@ -2,6 +2,8 @@ use crate::backend::Connector;
 use crate::backend::ConnectorEvent;
 use crate::backend::ConnectorId;
 use crate::backend::MonitorInfo;
+use crate::backlight::Backlight;
+use crate::bar_elements::BE_SCREEN_SHARE;
 use crate::control_center::CCI_OUTPUTS;
 use crate::globals::GlobalName;
 use crate::ifs::head_management::HeadManager;
@@ -36,6 +38,10 @@ pub fn handle(state: &Rc<State>, connector: &Rc<dyn Connector>) {
         };
     }
     let backend_state = connector.state();
+    let backlight = Backlight::find(
+        connector.kernel_id(),
+        drm_dev.as_ref().and_then(|d| d.syspath.as_deref()),
+    );
     let id = connector.id();
     let name = Rc::new(connector.name());
     let head_state = HeadState {
@@ -86,6 +92,9 @@ pub fn handle(state: &Rc<State>, connector: &Rc<dyn Connector>) {
         state: RefCell::new(backend_state),
         head_manager: HeadManager::new(state.head_names.next(), head_state),
         wlr_output_heads: Default::default(),
+        dpms_off: Cell::new(false),
+        wlr_output_powers: Default::default(),
+        backlight,
     });
     if let Some(dev) = drm_dev {
         dev.connectors.set(id, data.clone());
@@ -166,12 +175,23 @@ impl ConnectorHandler {
         for head in self.data.wlr_output_heads.lock().drain_values() {
             head.handle_disconnected();
         }
+        for power in self.data.wlr_output_powers.lock().drain_values() {
+            power.send_failed();
+        }
         log::info!("Connector {} disconnected", self.data.name);
     }
 
     async fn handle_desktop_connected(&self, info: MonitorInfo, name: GlobalName) {
         let output_id = info.output_id.clone();
+        let is_new = !self.state.persistent_output_states.contains(&output_id);
         let desired_state = self.state.ensure_persistent_output_state(&output_id);
+        if is_new
+            && let Some(scale) =
+                self.state
+                    .compute_auto_scale(&info.state.mode, info.width_mm, info.height_mm)
+        {
+            desired_state.scale.set(scale);
+        }
         let global = Rc::new(WlOutputGlobal::new(
             name,
             &self.state,
@@ -246,6 +266,7 @@ impl ConnectorHandler {
         if let Some(config) = self.state.config.get() {
             config.connector_connected(self.id);
         }
+        self.state.auto_rotation.output_connected(&on);
         self.state.add_global(&global);
         self.state.add_global(&tray);
         self.state.tree_changed();
@@ -297,6 +318,7 @@ impl ConnectorHandler {
         for sc in on.ext_copy_sessions.lock().drain_values() {
             sc.stop();
         }
+        self.state.bar_elements_changed(BE_SCREEN_SHARE);
         global.destroyed.set(true);
         if on.hardware_cursor.is_none() {
             self.state.outputs_without_hc.fetch_sub(1);
@@ -138,7 +138,7 @@ impl Idle {
         let mut tran = ConnectorTransaction::new(&self.state);
         for connector in self.state.connectors.lock().values() {
             let mut state = connector.state.borrow().clone();
-            state.active = !idle;
+            state.active = connector.should_be_active(idle);
             tran.add(&connector.connector, state)?;
         }
         tran.prepare()?.apply()?.commit();
@@ -27,10 +27,13 @@ pub fn handle(state: &Rc<State>, dev: Rc<dyn InputDevice>) {
         syspath: props.syspath,
         devnode: props.devnode,
         keymap: Default::default(),
+        key_remap: Default::default(),
         output: Default::default(),
         tablet_init: dev.tablet_info(),
         tablet_pad_init: dev.tablet_pad_info(),
+        tablet_config: Default::default(),
         scroll_methods: dev.scroll_methods(),
+        send_events_modes: dev.send_events_modes(),
         input_event_codes: dev.input_event_codes(),
         is_touch: dev.has_capability(InputDeviceCapability::Touch),
         is_kb: dev.has_capability(InputDeviceCapability::Keyboard),
@@ -224,6 +224,44 @@ impl Transform {
             _ => self,
         }
     }
+
+    fn split(self) -> (bool, u8) {
+        match self {
+            Self::None => (false, 0),
+            Self::Rotate90 => (false, 1),
+            Self::Rotate180 => (false, 2),
+            Self::Rotate270 => (false, 3),
+            Self::Flip => (true, 0),
+            Self::FlipRotate90 => (true, 1),
+            Self::FlipRotate180 => (true, 2),
+            Self::FlipRotate270 => (true, 3),
+        }
+    }
+
+    fn join(flip: bool, rotation: u8) -> Self {
+        match (flip, rotation % 4) {
+            (false, 0) => Self::None,
+            (false, 1) => Self::Rotate90,
+            (false, 2) => Self::Rotate180,
+            (false, _) => Self::Rotate270,
+            (true, 0) => Self::Flip,
+            (true, 1) => Self::FlipRotate90,
+            (true, 2) => Self::FlipRotate180,
+            (true, _) => Self::FlipRotate270,
+        }
+    }
+
+    /// Returns the transform that applies `self` followed by `next`.
+    pub fn then(self, next: Self) -> Self {
+        let (flip1, rot1) = self.split();
+        let (flip2, rot2) = next.split();
+        // Flipping after a rotation reverses the direction of the rotation.
+        let rot1 = match flip2 {
+            true => 4 - rot1,
+            false => rot1,
+        };
+        Self::join(flip1 ^ flip2, rot1 + rot2)
+    }
 }
 
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Linearize)]
@@ -7,9 +7,13 @@ use crate::backend::ButtonState;
 use crate::backend::HardwareCursor;
 use crate::backend::Mode;
 use crate::backend::transaction::BackendConnectorTransactionError;
+use crate::bar_elements::BE_SCREEN_SHARE;
 use crate::client::ClientId;
 use crate::cmm::cmm_description::ColorDescription;
 use crate::cmm::cmm_eotf::Eotf;
+use crate::cmm::cmm_icc::IccError;
+use crate::cmm::cmm_icc::IccGammaLut;
+use crate::cmm::cmm_icc::IccProfile;
 use crate::cmm::cmm_luminance::Luminance;
 use crate::cmm::cmm_primaries::NamedPrimaries;
 use crate::control_center::CCI_OUTPUTS;
@@ -59,6 +63,7 @@ use crate::ifs::zwlr_layer_shell_v1::BOTTOM;
 use crate::ifs::zwlr_layer_shell_v1::OVERLAY;
 use crate::ifs::zwlr_layer_shell_v1::TOP;
 use crate::ifs::zwlr_screencopy_frame_v1::ZwlrScreencopyFrameV1;
+use crate::output_overlay::OverlayKind;
 use crate::output_schedule::OutputSchedule;
 use crate::rect::Rect;
 use crate::renderer::Renderer;
@@ -166,12 +171,19 @@ pub struct OutputNode {
     pub pinned: LinkedList<Rc<dyn PinnedNode>>,
     pub tearing: Cell<bool>,
     pub active_zwlr_gamma_control: CloneCell<Option<Rc<ZwlrGammaControlV1>>>,
+    pub gamma_control_lut: CloneCell<Option<Rc<BackendGammaLut>>>,
+    pub icc_gamma_lut: CloneCell<Option<Rc<IccGammaLut>>>,
     pub cursor_users: CopyHashMap<CursorUserId, Rc<CursorUser>>,
     pub color_description_listeners:
         CopyHashMap<(ClientId, WpColorManagementOutputV1Id), Rc<WpColorManagementOutputV1>>,
     pub node_state: SplitView<OutputNodeState>,
     pub transaction_data: TransactionData<OutputTransactionOp>,
     pub damage_scheduled: Cell<bool>,
+    pub which_key: CloneCell<Option<Rc<dyn GfxTexture>>>,
+    pub backlight_indicator: CloneCell<Option<Rc<dyn GfxTexture>>>,
+    /// The rotation reported by the accelerometer. Applied after the configured
+    /// transform.
+    pub sensor_rotation: Cell<Transform>,
 }
 
 impl ObjWithId for OutputNode {
@@ -326,11 +338,16 @@ impl OutputNode {
             pinned: Default::default(),
             tearing: Default::default(),
             active_zwlr_gamma_control: Default::default(),
+            gamma_control_lut: Default::default(),
+            icc_gamma_lut: Default::default(),
             cursor_users: Default::default(),
             color_description_listeners: Default::default(),
             node_state: SplitView::from_fn(|_| OutputNodeState::new(state)),
             transaction_data: TransactionData::new(&state.tree),
             damage_scheduled: Default::default(),
+            which_key: Default::default(),
+            backlight_indicator: Default::default(),
+            sensor_rotation: Default::default(),
         });
         on.set_ns_pos(Rect::new_sized_saturating(x, y, width, height));
         on.set_ns_scale(scale);
@@ -440,6 +457,7 @@ impl OutputNode {
         for ws in self.workspaces.iter_valid(LiveTL) {
             ws.update_has_captures();
         }
+        self.state.bar_elements_changed(BE_SCREEN_SHARE);
     }
 
     pub fn perform_screencopies(
@@ -502,6 +520,20 @@ impl OutputNode {
                 size,
             );
         }
+        if let Some(vnc) = self.state.vnc_servers.get(&**self.global.connector.name) {
+            vnc.copy_texture(
+                self,
+                tex,
+                cd,
+                resv,
+                lazy,
+                acquire_sync,
+                release_sync,
+                x_off,
+                y_off,
+                size,
+            );
+        }
     }
 
     pub fn perform_wlr_screencopies(
@@ -698,6 +730,15 @@ impl OutputNode {
         self.damage_hardware_cursor(true);
     }
 
+    pub fn set_overlay(&self, kind: OverlayKind, tex: Option<Rc<dyn GfxTexture>>) {
+        let slot = match kind {
+            OverlayKind::WhichKey => &self.which_key,
+            OverlayKind::BacklightIndicator => &self.backlight_indicator,
+        };
+        slot.set(tex);
+        self.state.damage(self.node_state[RenderTL].pos.get());
+    }
+
     pub fn schedule_update_render_data(self: &Rc<Self>) {
         self.add_transaction_op(OutputTransactionOp::ScheduleUpdateRenderData);
     }
@@ -771,6 +812,23 @@ impl OutputNode {
             true,
             scale,
         );
+        if self.state.bar_elements.is_empty() {
+            rd.bar_elements = None;
+        } else {
+            let tex = rd.bar_elements.get_or_insert_with(|| OutputStatus {
+                tex_x: 0,
+                tex: TextTexture::new(&self.state, &ctx),
+            });
+            tex.tex.schedule_render_fitting(
+                on_completed.clone(),
+                Some(texture_height),
+                &font,
+                &self.state.format_bar_elements(),
+                tc,
+                true,
+                scale,
+            );
+        }
         on_completed.event()
     }
 
@@ -873,6 +931,12 @@ impl OutputNode {
         if let Some(ws) = ns.overlay.get() {
             handle_workspace(&ws, true);
         }
+        if let Some(elements) = &mut rd.bar_elements {
+            if let Err(e) = elements.tex.flip() {
+                log::error!("Could not render bar elements: {}", ErrorFmt(e));
+            }
+            elements.tex_x = pos + bar_rect_rel.height() / 2;
+        }
         if let Some(status) = &mut rd.status {
             if let Err(e) = status.tex.flip() {
                 log::error!("Could not render status: {}", ErrorFmt(e));
@@ -1217,14 +1281,22 @@ impl OutputNode {
     }
 
     pub fn update_mode(self: &Rc<Self>, mode: Mode) {
-        self.update_mode_and_transform(mode, self.node_state[LiveTL].transform.get());
+        self.update_mode_and_transform(mode, self.global.persistent.transform.get());
     }
 
     pub fn update_transform(self: &Rc<Self>, transform: Transform) {
         self.update_mode_and_transform(self.global.mode.get(), transform);
     }
 
+    pub fn set_sensor_rotation(self: &Rc<Self>, rotation: Transform) {
+        if self.sensor_rotation.replace(rotation) != rotation {
+            self.update_transform(self.global.persistent.transform.get());
+        }
+    }
+
     pub fn update_mode_and_transform(self: &Rc<Self>, mode: Mode, transform: Transform) {
+        self.global.persistent.transform.set(transform);
+        let transform = transform.then(self.sensor_rotation.get());
         let old_mode = self.global.mode.get();
         let old_transform = self.node_state[LiveTL].transform.get();
         if (old_mode, old_transform) == (mode, transform) {
@@ -1233,7 +1305,6 @@ impl OutputNode {
         let (old_width, old_height) = self.pixel_size();
         self.global.mode.set(mode);
         self.global.refresh_nsec.set(mode.refresh_nsec());
-        self.global.persistent.transform.set(transform);
         self.set_ns_transform(transform);
         let (new_width, new_height) = self.pixel_size();
         self.change_extents_(&self.calculate_extents());
@@ -1336,6 +1407,7 @@ impl OutputNode {
     }
 
     fn update_color_description(self: &Rc<Self>) {
+        self.update_icc_gamma_lut();
         if self.update_color_description_() {
             self.damage_hardware_cursor(true);
             for fb in self.color_description_listeners.lock().values() {
@@ -1390,6 +1462,14 @@ impl OutputNode {
         }
     }
 
+    pub fn set_icc_profile(self: &Rc<Self>, icc_profile: Option<Rc<IccProfile>>) {
+        let old = self.global.persistent.icc_profile.set(icc_profile.clone());
+        if old.is_some() || icc_profile.is_some() {
+            self.update_color_description();
+            self.state.trigger_cci(CCI_OUTPUTS);
+        }
+    }
+
     pub fn set_blend_space(self: &Rc<Self>, blend_space: BlendSpace) {
         let old = self.global.persistent.blend_space.replace(blend_space);
         if old != blend_space {
@@ -1984,6 +2064,68 @@ impl OutputNode {
         &self,
         gamma_lut: Option<Rc<BackendGammaLut>>,
     ) -> Result<(), BackendConnectorTransactionError> {
+        self.gamma_control_lut.set(gamma_lut);
+        self.apply_gamma_lut()
+    }
+
+    fn active_icc_profile(&self) -> Option<Rc<IccProfile>> {
+        let ns = &self.node_state[LiveTL];
+        match (ns.bcs.get(), ns.btf.get()) {
+            (BackendColorSpace::Default, BackendEotfs::Default) => {
+                self.global.persistent.icc_profile.get()
+            }
+            _ => None,
+        }
+    }
+
+    fn update_icc_gamma_lut(&self) {
+        let icc_profile = self.active_icc_profile();
+        let connector = &self.global.connector.connector;
+        let lut = match (&icc_profile, connector.gamma_lut_size()) {
+            (Some(icc), Some(size)) => icc.gamma_lut(size as usize).map(Rc::new),
+            (Some(icc), None) => {
+                if icc.gamma_lut(256).is_some() {
+                    log::warn!(
+                        "Output {} does not support gamma LUTs. The tone curves of {} will be approximated.",
+                        connector.kernel_id(),
+                        icc.path,
+                    );
+                }
+                None
+            }
+            (None, _) => None,
+        };
+        let old = self.icc_gamma_lut.set(lut.clone());
+        if old.is_some() || lut.is_some() {
+            let _ = self.apply_gamma_lut();
+        }
+    }
+
+    fn apply_gamma_lut(&self) -> Result<(), BackendConnectorTransactionError> {
+        let mut gamma_lut = self.gamma_control_lut.get();
+        if let Some(icc) = self.icc_gamma_lut.get() {
+            // The LUT of the gamma control is applied first since it operates on
+            // values encoded with the transfer function of the output.
+            let to_u16 = |v: f64| (v.clamp(0.0, 1.0) * 65535.0).round() as u16;
+            let map = |rgb: [f64; 3]| {
+                let [r, g, b] = [0, 1, 2].map(|c| to_u16(icc.apply(c, rgb[c])));
+                [r, g, b, 0]
+            };
+            let elements = match &gamma_lut {
+                Some(lut) => lut
+                    .gamma_lut
+                    .iter()
+                    .map(|e| map([0, 1, 2].map(|c| e[c] as f64 / 65535.0)))
+                    .collect(),
+                None => {
+                    let size = icc.size();
+                    (0..size)
+                        .map(|i| map([i as f64 / (size - 1) as f64; 3]))
+                        .collect()
+                }
+            };
+            gamma_lut = Some(Rc::new(BackendGammaLut::new(elements)));
+        }
         self.global
             .connector
             .modify_state(&self.state, |s| s.gamma_lut = gamma_lut)
@@ -2044,7 +2186,8 @@ impl OutputNode {
 
     fn update_color_description_(self: &Rc<Self>) -> bool {
         let ns = &self.node_state[LiveTL];
-        let (mut luminance, tf) = match ns.btf.get() {
+        let icc_profile = self.active_icc_profile();
+        let (mut luminance, mut tf) = match ns.btf.get() {
             BackendEotfs::Default => (Luminance::SRGB, Eotf::Gamma22),
             BackendEotfs::Pq => (Luminance::ST2084_PQ, Eotf::St2084Pq),
         };
@@ -2074,7 +2217,11 @@ impl OutputNode {
         let target_primaries;
         match ns.bcs.get() {
             BackendColorSpace::Default => {
-                if self.global.persistent.use_native_gamut.get()
+                if let Some(icc) = &icc_profile {
+                    named_primaries = None;
+                    primaries = icc.primaries;
+                    tf = icc.eotf;
+                } else if self.global.persistent.use_native_gamut.get()
                     && self.global.primaries != NamedPrimaries::Srgb.primaries()
                 {
                     named_primaries = None;
@@ -2224,12 +2371,14 @@ pub struct OutputRenderData {
     pub captured_inactive_workspaces: Vec<Rect>,
     pub titles: Vec<OutputTitle>,
     pub status: Option<OutputStatus>,
+    pub bar_elements: Option<OutputStatus>,
 }
 
 impl OutputRenderData {
     fn clear(&mut self) {
         self.titles.clear();
         self.status.take();
+        self.bar_elements.take();
     }
 }
 
@@ -2799,6 +2948,47 @@ impl OutputNodeOrPersistent {
         }
     }
 
+    fn persistent(&self) -> &Rc<PersistentOutputState> {
+        match self {
+            OutputNodeOrPersistent::Node(n) => &n.global.persistent,
+            OutputNodeOrPersistent::Persistent(p) => p,
+        }
+    }
+
+    pub fn set_icc_profile(&self, icc_profile: Option<Rc<IccProfile>>) {
+        self.persistent().icc_profile_serial.fetch_add(1);
+        self.apply_icc_profile(icc_profile);
+    }
+
+    fn apply_icc_profile(&self, icc_profile: Option<Rc<IccProfile>>) {
+        match self {
+            OutputNodeOrPersistent::Node(n) => n.set_icc_profile(icc_profile),
+            OutputNodeOrPersistent::Persistent(p) => {
+                p.icc_profile.set(icc_profile);
+            }
+        }
+    }
+
+    /// Loads the profile on the CPU worker and applies it unless the profile has been
+    /// changed again in the meantime.
+    pub fn load_icc_profile(
+        self,
+        state: &State,
+        path: &str,
+        on_error: impl FnOnce(IccError) + 'static,
+    ) {
+        let serial = self.persistent().icc_profile_serial.add_fetch(1);
+        IccProfile::load_async(&state.cpu_worker, path, move |res| {
+            if self.persistent().icc_profile_serial.get() != serial {
+                return;
+            }
+            match res {
+                Ok(icc) => self.apply_icc_profile(Some(Rc::new(icc))),
+                Err(e) => on_error(e),
+            }
+        });
+    }
+
     pub fn set_use_native_gamut(&self, use_native_gamut: bool) {
         match self {
             OutputNodeOrPersistent::Node(n) => n.set_use_native_gamut(use_native_gamut),
@@ -2806,6 +2996,13 @@ impl OutputNodeOrPersistent {
         }
     }
 
+    pub fn set_touch_edge_width(&self, width: i32) {
+        match self {
+            OutputNodeOrPersistent::Node(n) => n.global.persistent.touch_edge_width.set(width),
+            OutputNodeOrPersistent::Persistent(p) => p.touch_edge_width.set(width),
+        }
+    }
+
     pub fn set_cursor_hz(&self, state: &State, hz: Option<f64>) {
         match self {
             OutputNodeOrPersistent::Node(n) => {
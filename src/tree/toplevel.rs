@@ -1,3 +1,5 @@
+use crate::bar_elements::BE_FOCUS;
+use crate::bar_elements::BE_SCREEN_SHARE;
 use crate::client::Client;
 use crate::client::ClientId;
 use crate::criteria::CritDestroyListener;
@@ -499,6 +501,7 @@ pub struct ToplevelData {
     pub changed_properties: Cell<TlMatcherChange>,
     pub just_mapped_scheduled: Cell<bool>,
     pub seat_foci: CopyHashMap<SeatId, ()>,
+    pub kb_groups: CopyHashMap<SeatId, u32>,
     pub content_type: Cell<Option<ContentType>>,
     pub property_changed_source: OnceCell<Rc<LazyEventSource>>,
     pub session: CloneCell<Option<Rc<ToplevelSession>>>,
@@ -561,6 +564,7 @@ impl ToplevelData {
             changed_properties: Default::default(),
             just_mapped_scheduled: Cell::new(false),
             seat_foci: Default::default(),
+            kb_groups: Default::default(),
             content_type: Default::default(),
             property_changed_source: Default::default(),
             session: Default::default(),
@@ -670,6 +674,7 @@ impl ToplevelData {
         for screencast in self.ext_copy_sessions.lock().drain_values() {
             screencast.stop();
         }
+        self.state.bar_elements_changed(BE_SCREEN_SHARE);
         {
             let id = toplevel_identifier();
             let prev = self.identifier.replace(id);
@@ -838,6 +843,9 @@ impl ToplevelData {
             handle.send_title(title);
             handle.send_done();
         }
+        if self.seat_foci.is_not_empty() {
+            self.state.bar_elements_changed(BE_FOCUS);
+        }
     }
 
     pub fn set_app_id(&self, app_id: &str) {
@@ -854,6 +862,9 @@ impl ToplevelData {
             handle.send_app_id(app_id);
             handle.send_done();
         }
+        if self.seat_foci.is_not_empty() {
+            self.state.bar_elements_changed(BE_FOCUS);
+        }
         self.property_changed(TL_CHANGED_APP_ID)
     }
 
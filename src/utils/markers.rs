@@ -26,6 +26,9 @@ mod impls {
     use jay_config::_private::PollableId;
     use jay_config::_private::WindowCriterionIpc;
     use jay_config::client::ClientMatcher;
+    use jay_config::input::ModifiedPointerTrigger;
+    use jay_config::input::gesture::Gesture;
+    use jay_config::input::gesture::TouchGesture;
     use jay_config::keyboard::Keymap;
     use jay_config::keyboard::ModifiedKeySym;
     use jay_config::keyboard::mods::Modifiers;
@@ -44,11 +47,13 @@ mod impls {
     unsafe impl<T: ?Sized> JayClone for Arc<T> {}
 
     unsafe impl JayClone for () {}
+    unsafe impl JayClone for bool {}
     unsafe impl JayClone for u64 {}
     unsafe impl JayClone for i32 {}
     unsafe impl JayClone for u32 {}
     unsafe impl JayClone for usize {}
     unsafe impl JayClone for f32 {}
+    unsafe impl JayClone for f64 {}
 
     unsafe impl<A: JayClone, B: JayClone> JayClone for (A, B) {}
     unsafe impl<T: JayClone, const N: usize> JayClone for [T; N] {}
@@ -82,6 +87,9 @@ mod impls {
     unsafe impl JayHash for WindowMatcher {}
     unsafe impl JayHash for Keycode {}
     unsafe impl JayHash for ModifiedKeySym {}
+    unsafe impl JayHash for ModifiedPointerTrigger {}
+    unsafe impl JayHash for Gesture {}
+    unsafe impl JayHash for TouchGesture {}
     unsafe impl<T, U> JayHash for (T, U)
     where
         T: JayHash,
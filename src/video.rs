@@ -1,3 +1,4 @@
+pub mod cvt;
 pub mod dmabuf;
 pub mod drm;
 pub mod gbm;
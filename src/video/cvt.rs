@@ -0,0 +1,389 @@
+#[cfg(test)]
+mod tests;
+
+use crate::edid::DetailedTimingDescriptor;
+use crate::edid::SyncSignal;
+use crate::video::drm::DrmModeInfo;
+use thiserror::Error;
+
+pub const DRM_MODE_FLAG_PHSYNC: u32 = 1 << 0;
+pub const DRM_MODE_FLAG_NHSYNC: u32 = 1 << 1;
+pub const DRM_MODE_FLAG_PVSYNC: u32 = 1 << 2;
+pub const DRM_MODE_FLAG_NVSYNC: u32 = 1 << 3;
+pub const DRM_MODE_FLAG_INTERLACE: u32 = 1 << 4;
+pub const DRM_MODE_FLAG_DBLSCAN: u32 = 1 << 5;
+
+pub const DRM_MODE_TYPE_USERDEF: u32 = 1 << 5;
+
+const H_GRANULARITY: u32 = 8;
+const MIN_V_PORCH: u32 = 3;
+const MIN_V_BPORCH: u32 = 6;
+const CLOCK_STEP_KHZ: u32 = 250;
+
+const MIN_VSYNC_BP_US: f64 = 550.0;
+const HSYNC_PERCENTAGE: u32 = 8;
+const M_PRIME: f64 = 600.0 * 128.0 / 256.0;
+const C_PRIME: f64 = (40.0 - 20.0) * 128.0 / 256.0 + 20.0;
+
+const RB_MIN_VBLANK_US: f64 = 460.0;
+const RB_H_SYNC: u32 = 32;
+const RB_H_BLANK: u32 = 160;
+const RB_V_FPORCH: u32 = 3;
+
+#[derive(Debug, Error)]
+pub enum CvtError {
+    #[error("Width and height must be at least 1")]
+    EmptySize,
+    #[error("The refresh rate must be positive")]
+    InvalidRefreshRate,
+    #[error("The timings of the mode do not fit into 16 bits")]
+    TooLarge,
+}
+
+#[derive(Debug, Error)]
+pub enum ModelineError {
+    #[error("The modeline contains no pixel clock")]
+    MissingClock,
+    #[error("Could not parse the pixel clock `{0}`")]
+    InvalidClock(String),
+    #[error("The modeline contains fewer than 8 timings")]
+    MissingTimings,
+    #[error("Could not parse the timing `{0}`")]
+    InvalidTiming(String),
+    #[error("Unknown modeline flag `{0}`")]
+    UnknownFlag(String),
+    #[error("The horizontal timings are not monotonic")]
+    HorizontalTimings,
+    #[error("The vertical timings are not monotonic")]
+    VerticalTimings,
+}
+
+/// Generates VESA Coordinated Video Timings for the given size and refresh rate.
+///
+/// If `reduced_blanking` is set, the CVT-RB (v1) timings are generated. These are
+/// intended for digital displays and require a lower pixel clock.
+pub fn generate(
+    width: u32,
+    height: u32,
+    refresh_rate_millihz: u32,
+    reduced_blanking: bool,
+) -> Result<DrmModeInfo, CvtError> {
+    if width == 0 || height == 0 {
+        return Err(CvtError::EmptySize);
+    }
+    if refresh_rate_millihz == 0 {
+        return Err(CvtError::InvalidRefreshRate);
+    }
+    let refresh = refresh_rate_millihz as f64 / 1000.0;
+    let hdisplay = width - width % H_GRANULARITY;
+    if hdisplay == 0 {
+        return Err(CvtError::EmptySize);
+    }
+    let vdisplay = height;
+    let vsync = vsync_width(width, height);
+    let hperiod;
+    let htotal;
+    let hsync_start;
+    let hsync_end;
+    let vtotal;
+    let vsync_start;
+    let flags;
+    if reduced_blanking {
+        hperiod = (1_000_000.0 / refresh - RB_MIN_VBLANK_US) / vdisplay as f64;
+        if hperiod <= 0.0 {
+            return Err(CvtError::InvalidRefreshRate);
+        }
+        let vbilines =
+            ((RB_MIN_VBLANK_US / hperiod) as u32 + 1).max(RB_V_FPORCH + vsync + MIN_V_BPORCH);
+        vtotal = vdisplay + vbilines;
+        htotal = hdisplay + RB_H_BLANK;
+        hsync_end = hdisplay + RB_H_BLANK / 2;
+        hsync_start = hsync_end - RB_H_SYNC;
+        vsync_start = vdisplay + RB_V_FPORCH;
+        flags = DRM_MODE_FLAG_PHSYNC | DRM_MODE_FLAG_NVSYNC;
+    } else {
+        hperiod = (1_000_000.0 / refresh - MIN_VSYNC_BP_US) / (vdisplay + MIN_V_PORCH) as f64;
+        if hperiod <= 0.0 {
+            return Err(CvtError::InvalidRefreshRate);
+        }
+        let vsync_and_back_porch =
+            ((MIN_VSYNC_BP_US / hperiod) as u32 + 1).max(vsync + MIN_V_PORCH);
+        vtotal = vdisplay + vsync_and_back_porch + MIN_V_PORCH;
+        let hblank_percentage = (C_PRIME - M_PRIME * hperiod / 1000.0).max(20.0);
+        let mut hblank = (hdisplay as f64 * hblank_percentage / (100.0 - hblank_percentage)) as u32;
+        hblank -= hblank % (2 * H_GRANULARITY);
+        htotal = hdisplay + hblank;
+        hsync_end = hdisplay + hblank / 2;
+        let start = hsync_end - htotal * HSYNC_PERCENTAGE / 100;
+        hsync_start = start + H_GRANULARITY - start % H_GRANULARITY;
+        vsync_start = vdisplay + MIN_V_PORCH;
+        flags = DRM_MODE_FLAG_NHSYNC | DRM_MODE_FLAG_PVSYNC;
+    }
+    let mut clock = (htotal as f64 * 1000.0 / hperiod) as u32;
+    clock -= clock % CLOCK_STEP_KHZ;
+    let vsync_end = vsync_start + vsync;
+    let narrow = |v: u32| u16::try_from(v).map_err(|_| CvtError::TooLarge);
+    let mut mode = DrmModeInfo {
+        clock,
+        hdisplay: narrow(hdisplay)?,
+        hsync_start: narrow(hsync_start)?,
+        hsync_end: narrow(hsync_end)?,
+        htotal: narrow(htotal)?,
+        hskew: 0,
+        vdisplay: narrow(vdisplay)?,
+        vsync_start: narrow(vsync_start)?,
+        vsync_end: narrow(vsync_end)?,
+        vtotal: narrow(vtotal)?,
+        vscan: 0,
+        vrefresh: 0,
+        flags,
+        ty: DRM_MODE_TYPE_USERDEF,
+        name: Default::default(),
+    };
+    finish(&mut mode);
+    Ok(mode)
+}
+
+fn vsync_width(width: u32, height: u32) -> u32 {
+    let is_aspect = |div: u32, w: u32, h: u32| height % div == 0 && height * w / h == width;
+    if is_aspect(3, 4, 3) {
+        4
+    } else if is_aspect(9, 16, 9) {
+        5
+    } else if is_aspect(10, 16, 10) {
+        6
+    } else if is_aspect(4, 5, 4) || is_aspect(9, 15, 9) {
+        7
+    } else {
+        10
+    }
+}
+
+/// Parses an X11-style modeline.
+///
+/// The accepted format is
+///
+/// ```text
+/// [Modeline] ["name"] clock hdisp hsyncstart hsyncend htotal vdisp vsyncstart vsyncend vtotal [flags...]
+/// ```
+///
+/// where the clock is specified in MHz. This is the output format of tools such as
+/// `cvt` and `gtf`.
+pub fn parse_modeline(modeline: &str) -> Result<DrmModeInfo, ModelineError> {
+    let mut tokens = modeline.split_ascii_whitespace().peekable();
+    if let Some(t) = tokens.peek()
+        && t.eq_ignore_ascii_case("modeline")
+    {
+        tokens.next();
+    }
+    if let Some(t) = tokens.peek()
+        && t.starts_with('"')
+    {
+        let t = tokens.next().unwrap();
+        if t.len() == 1 || !t.ends_with('"') {
+            for t in tokens.by_ref() {
+                if t.ends_with('"') {
+                    break;
+                }
+            }
+        }
+    }
+    let clock = tokens.next().ok_or(ModelineError::MissingClock)?;
+    let clock = match clock.parse::<f64>() {
+        Ok(c) if c.is_finite() && c > 0.0 && c * 1000.0 <= u32::MAX as f64 => {
+            (c * 1000.0).round() as u32
+        }
+        _ => return Err(ModelineError::InvalidClock(clock.to_string())),
+    };
+    let mut timings = [0u16; 8];
+    for timing in &mut timings {
+        let t = tokens.next().ok_or(ModelineError::MissingTimings)?;
+        *timing = t
+            .parse()
+            .map_err(|_| ModelineError::InvalidTiming(t.to_string()))?;
+    }
+    let mut flags = 0;
+    for t in tokens {
+        flags |= match &*t.to_ascii_lowercase() {
+            "+hsync" => DRM_MODE_FLAG_PHSYNC,
+            "-hsync" => DRM_MODE_FLAG_NHSYNC,
+            "+vsync" => DRM_MODE_FLAG_PVSYNC,
+            "-vsync" => DRM_MODE_FLAG_NVSYNC,
+            "interlace" => DRM_MODE_FLAG_INTERLACE,
+            "doublescan" => DRM_MODE_FLAG_DBLSCAN,
+            _ => return Err(ModelineError::UnknownFlag(t.to_string())),
+        };
+    }
+    let [
+        hdisplay,
+        hsync_start,
+        hsync_end,
+        htotal,
+        vdisplay,
+        vsync_start,
+        vsync_end,
+        vtotal,
+    ] = timings;
+    if hdisplay == 0 || hdisplay > hsync_start || hsync_start > hsync_end || hsync_end > htotal {
+        return Err(ModelineError::HorizontalTimings);
+    }
+    if vdisplay == 0 || vdisplay > vsync_start || vsync_start > vsync_end || vsync_end > vtotal {
+        return Err(ModelineError::VerticalTimings);
+    }
+    let mut mode = DrmModeInfo {
+        clock,
+        hdisplay,
+        hsync_start,
+        hsync_end,
+        htotal,
+        hskew: 0,
+        vdisplay,
+        vsync_start,
+        vsync_end,
+        vtotal,
+        vscan: 0,
+        vrefresh: 0,
+        flags,
+        ty: DRM_MODE_TYPE_USERDEF,
+        name: Default::default(),
+    };
+    finish(&mut mode);
+    Ok(mode)
+}
+
+/// Progressive CTA-861 video formats as `(vic, clock_khz, timings, positive_sync)`.
+#[rustfmt::skip]
+const CTA_VIDEO_FORMATS: &[(u8, u32, [u16; 8], bool)] = &[
+    (1, 25175, [640, 656, 752, 800, 480, 490, 492, 525], false),
+    (2, 27000, [720, 736, 798, 858, 480, 489, 495, 525], false),
+    (3, 27000, [720, 736, 798, 858, 480, 489, 495, 525], false),
+    (4, 74250, [1280, 1390, 1430, 1650, 720, 725, 730, 750], true),
+    (16, 148500, [1920, 2008, 2052, 2200, 1080, 1084, 1089, 1125], true),
+    (17, 27000, [720, 732, 796, 864, 576, 581, 586, 625], false),
+    (18, 27000, [720, 732, 796, 864, 576, 581, 586, 625], false),
+    (19, 74250, [1280, 1720, 1760, 1980, 720, 725, 730, 750], true),
+    (31, 148500, [1920, 2448, 2492, 2640, 1080, 1084, 1089, 1125], true),
+    (32, 74250, [1920, 2558, 2602, 2750, 1080, 1084, 1089, 1125], true),
+    (33, 74250, [1920, 2448, 2492, 2640, 1080, 1084, 1089, 1125], true),
+    (34, 74250, [1920, 2008, 2052, 2200, 1080, 1084, 1089, 1125], true),
+    (60, 59400, [1280, 3040, 3080, 3300, 720, 725, 730, 750], true),
+    (61, 74250, [1280, 3700, 3740, 3960, 720, 725, 730, 750], true),
+    (62, 74250, [1280, 3040, 3080, 3300, 720, 725, 730, 750], true),
+    (63, 297000, [1920, 2008, 2052, 2200, 1080, 1084, 1089, 1125], true),
+    (64, 297000, [1920, 2448, 2492, 2640, 1080, 1084, 1089, 1125], true),
+    (93, 297000, [3840, 5116, 5204, 5500, 2160, 2168, 2178, 2250], true),
+    (94, 297000, [3840, 4896, 4984, 5280, 2160, 2168, 2178, 2250], true),
+    (95, 297000, [3840, 4016, 4104, 4400, 2160, 2168, 2178, 2250], true),
+    (96, 594000, [3840, 4896, 4984, 5280, 2160, 2168, 2178, 2250], true),
+    (97, 594000, [3840, 4016, 4104, 4400, 2160, 2168, 2178, 2250], true),
+    (98, 297000, [4096, 5116, 5204, 5500, 2160, 2168, 2178, 2250], true),
+    (99, 297000, [4096, 5064, 5152, 5280, 2160, 2168, 2178, 2250], true),
+    (100, 297000, [4096, 4184, 4272, 4400, 2160, 2168, 2178, 2250], true),
+    (101, 594000, [4096, 5064, 5152, 5280, 2160, 2168, 2178, 2250], true),
+    (102, 594000, [4096, 4184, 4272, 4400, 2160, 2168, 2178, 2250], true),
+];
+
+/// Converts a CTA-861 video identification code to a mode.
+///
+/// Returns `None` for interlaced formats and formats that are not known.
+pub fn from_vic(vic: u8) -> Option<DrmModeInfo> {
+    let &(_, clock, timings, positive_sync) = CTA_VIDEO_FORMATS.iter().find(|(v, ..)| *v == vic)?;
+    let [
+        hdisplay,
+        hsync_start,
+        hsync_end,
+        htotal,
+        vdisplay,
+        vsync_start,
+        vsync_end,
+        vtotal,
+    ] = timings;
+    let flags = match positive_sync {
+        true => DRM_MODE_FLAG_PHSYNC | DRM_MODE_FLAG_PVSYNC,
+        false => DRM_MODE_FLAG_NHSYNC | DRM_MODE_FLAG_NVSYNC,
+    };
+    let mut mode = DrmModeInfo {
+        clock,
+        hdisplay,
+        hsync_start,
+        hsync_end,
+        htotal,
+        hskew: 0,
+        vdisplay,
+        vsync_start,
+        vsync_end,
+        vtotal,
+        vscan: 0,
+        vrefresh: 0,
+        flags,
+        ty: DRM_MODE_TYPE_USERDEF,
+        name: Default::default(),
+    };
+    finish(&mut mode);
+    Some(mode)
+}
+
+/// Converts a detailed timing descriptor of an EDID to a mode.
+///
+/// Returns `None` for interlaced timings and timings that do not fit into a mode.
+pub fn from_detailed_timing(dtd: &DetailedTimingDescriptor) -> Option<DrmModeInfo> {
+    if dtd.interlaced || dtd.pixel_clock_khz == 0 {
+        return None;
+    }
+    let hdisplay = dtd.horizontal_addressable_pixels;
+    let hsync_start = hdisplay.checked_add(dtd.horizontal_front_porch_pixels)?;
+    let hsync_end = hsync_start.checked_add(dtd.horizontal_sync_pulse_pixels)?;
+    let htotal = hdisplay.checked_add(dtd.horizontal_blanking_pixels)?;
+    let vdisplay = dtd.vertical_addressable_lines;
+    let vsync_start = vdisplay.checked_add(dtd.vertical_front_porch_lines as u16)?;
+    let vsync_end = vsync_start.checked_add(dtd.vertical_sync_pulse_lines as u16)?;
+    let vtotal = vdisplay.checked_add(dtd.vertical_blanking_lines)?;
+    if hdisplay == 0 || vdisplay == 0 || hsync_end > htotal || vsync_end > vtotal {
+        return None;
+    }
+    let flags = match dtd.sync {
+        SyncSignal::DigitalSeparate {
+            vertical_sync_is_positive,
+            horizontal_sync_is_positive,
+        } => {
+            let h = match horizontal_sync_is_positive {
+                true => DRM_MODE_FLAG_PHSYNC,
+                false => DRM_MODE_FLAG_NHSYNC,
+            };
+            let v = match vertical_sync_is_positive {
+                true => DRM_MODE_FLAG_PVSYNC,
+                false => DRM_MODE_FLAG_NVSYNC,
+            };
+            h | v
+        }
+        _ => 0,
+    };
+    let mut mode = DrmModeInfo {
+        clock: dtd.pixel_clock_khz,
+        hdisplay,
+        hsync_start,
+        hsync_end,
+        htotal,
+        hskew: 0,
+        vdisplay,
+        vsync_start,
+        vsync_end,
+        vtotal,
+        vscan: 0,
+        vrefresh: 0,
+        flags,
+        ty: DRM_MODE_TYPE_USERDEF,
+        name: Default::default(),
+    };
+    finish(&mut mode);
+    Some(mode)
+}
+
+fn finish(mode: &mut DrmModeInfo) {
+    mode.vrefresh = (mode.refresh_rate_millihz() + 500) / 1000;
+    let mut name = format!("{}x{}", mode.hdisplay, mode.vdisplay);
+    if mode.flags & DRM_MODE_FLAG_INTERLACE != 0 {
+        name.push('i');
+    }
+    mode.name = name.into();
+}
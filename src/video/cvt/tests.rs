@@ -0,0 +1,140 @@
+use crate::edid::DetailedTimingDescriptor;
+use crate::edid::StereoViewingSupport;
+use crate::edid::SyncSignal;
+use crate::video::cvt::DRM_MODE_FLAG_NHSYNC;
+use crate::video::cvt::DRM_MODE_FLAG_NVSYNC;
+use crate::video::cvt::DRM_MODE_FLAG_PHSYNC;
+use crate::video::cvt::DRM_MODE_FLAG_PVSYNC;
+use crate::video::cvt::from_detailed_timing;
+use crate::video::cvt::from_vic;
+use crate::video::cvt::generate;
+use crate::video::cvt::parse_modeline;
+use crate::video::drm::DrmModeInfo;
+
+fn timings(mode: &DrmModeInfo) -> [u32; 9] {
+    [
+        mode.clock,
+        mode.hdisplay as u32,
+        mode.hsync_start as u32,
+        mode.hsync_end as u32,
+        mode.htotal as u32,
+        mode.vdisplay as u32,
+        mode.vsync_start as u32,
+        mode.vsync_end as u32,
+        mode.vtotal as u32,
+    ]
+}
+
+#[test]
+fn cvt_1920x1080_60() {
+    let mode = generate(1920, 1080, 60_000, false).unwrap();
+    assert_eq!(
+        timings(&mode),
+        [173000, 1920, 2048, 2248, 2576, 1080, 1083, 1088, 1120],
+    );
+    assert_eq!(mode.flags, DRM_MODE_FLAG_NHSYNC | DRM_MODE_FLAG_PVSYNC);
+    assert_eq!(mode.vrefresh, 60);
+    assert_eq!(mode.name, "1920x1080");
+}
+
+#[test]
+fn cvt_rb_1920x1080_60() {
+    let mode = generate(1920, 1080, 60_000, true).unwrap();
+    assert_eq!(
+        timings(&mode),
+        [138500, 1920, 1968, 2000, 2080, 1080, 1083, 1088, 1111],
+    );
+    assert_eq!(mode.flags, DRM_MODE_FLAG_PHSYNC | DRM_MODE_FLAG_NVSYNC);
+}
+
+#[test]
+fn cvt_2560x1440_75() {
+    let mode = generate(2560, 1440, 75_000, false).unwrap();
+    assert_eq!(
+        timings(&mode),
+        [397250, 2560, 2760, 3040, 3520, 1440, 1443, 1448, 1506],
+    );
+}
+
+#[test]
+fn cvt_invalid() {
+    assert!(generate(0, 1080, 60_000, false).is_err());
+    assert!(generate(1920, 1080, 0, false).is_err());
+    assert!(generate(1920, 1080, 10_000_000, true).is_err());
+}
+
+#[test]
+fn modeline() {
+    let mode = parse_modeline(
+        r#"Modeline "1920x1080_60.00"  173.00  1920 2048 2248 2576  1080 1083 1088 1120 -hsync +vsync"#,
+    )
+    .unwrap();
+    assert_eq!(mode, generate(1920, 1080, 60_000, false).unwrap());
+    let mode =
+        parse_modeline("138.5 1920 1968 2000 2080 1080 1083 1088 1111 +HSync -VSync").unwrap();
+    assert_eq!(mode, generate(1920, 1080, 60_000, true).unwrap());
+}
+
+#[test]
+fn modeline_invalid() {
+    assert!(parse_modeline("").is_err());
+    assert!(parse_modeline("173.00 1920 2048 2248 2576 1080 1083 1088").is_err());
+    assert!(parse_modeline("173.00 1920 2048 2248 2576 1080 1083 1088 1120 +foo").is_err());
+    assert!(parse_modeline("173.00 1920 2048 2000 2576 1080 1083 1088 1120").is_err());
+}
+
+#[test]
+fn detailed_timing() {
+    let dtd = DetailedTimingDescriptor {
+        pixel_clock_khz: 148500,
+        horizontal_addressable_pixels: 1920,
+        horizontal_blanking_pixels: 280,
+        vertical_addressable_lines: 1080,
+        vertical_blanking_lines: 45,
+        horizontal_front_porch_pixels: 88,
+        horizontal_sync_pulse_pixels: 44,
+        vertical_front_porch_lines: 4,
+        vertical_sync_pulse_lines: 5,
+        horizontal_addressable_mm: 527,
+        vertical_addressable_mm: 296,
+        horizontal_left_border_pixels: 0,
+        vertical_top_border_pixels: 0,
+        interlaced: false,
+        stereo_viewing_support: StereoViewingSupport::None,
+        sync: SyncSignal::DigitalSeparate {
+            vertical_sync_is_positive: true,
+            horizontal_sync_is_positive: true,
+        },
+    };
+    let mode = from_detailed_timing(&dtd).unwrap();
+    assert_eq!(
+        timings(&mode),
+        [148500, 1920, 2008, 2052, 2200, 1080, 1084, 1089, 1125],
+    );
+    assert_eq!(mode.flags, DRM_MODE_FLAG_PHSYNC | DRM_MODE_FLAG_PVSYNC);
+    assert_eq!(mode.refresh_rate_millihz(), 60_000);
+    assert!(
+        from_detailed_timing(&DetailedTimingDescriptor {
+            interlaced: true,
+            ..dtd
+        })
+        .is_none()
+    );
+}
+
+#[test]
+fn vic() {
+    let mode = from_vic(16).unwrap();
+    assert_eq!(
+        timings(&mode),
+        [148500, 1920, 2008, 2052, 2200, 1080, 1084, 1089, 1125],
+    );
+    assert_eq!(mode.flags, DRM_MODE_FLAG_PHSYNC | DRM_MODE_FLAG_PVSYNC);
+    assert_eq!(mode.vrefresh, 60);
+    assert_eq!(from_vic(1).unwrap().vrefresh, 60);
+    assert_eq!(from_vic(31).unwrap().vrefresh, 50);
+    assert_eq!(from_vic(97).unwrap().name, "3840x2160");
+    // 1080i
+    assert!(from_vic(5).is_none());
+    assert!(from_vic(0).is_none());
+}
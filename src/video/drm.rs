@@ -1270,6 +1270,11 @@ impl ConnectorType {
         }
     }
 
+    /// Returns whether this is the connector of a built-in panel.
+    pub fn is_internal(self) -> bool {
+        matches!(self, Self::eDP | Self::LVDS | Self::DSI)
+    }
+
     #[expect(unused)]
     pub fn to_drm(self) -> u32 {
         match self {
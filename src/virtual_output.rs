@@ -263,6 +263,7 @@ fn default_state(state: &State) -> BackendConnectorState {
             height: 600,
             refresh_rate_millihz: 60_000,
         },
+        custom_mode: None,
         non_desktop_override: Default::default(),
         vrr: Default::default(),
         tearing: Default::default(),
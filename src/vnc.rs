@@ -0,0 +1,554 @@
+mod client;
+mod rfb;
+#[cfg(test)]
+mod tests;
+
+use crate::async_engine::SpawnedFuture;
+use crate::cmm::cmm_description::ColorDescription;
+use crate::env::XDG_RUNTIME_DIR;
+use crate::format::XRGB8888;
+use crate::gfx_api::AcquireSync;
+use crate::gfx_api::AsyncShmGfxTextureCallback;
+use crate::gfx_api::BufferResv;
+use crate::gfx_api::GfxError;
+use crate::gfx_api::GfxFramebuffer;
+use crate::gfx_api::GfxInternalFramebuffer;
+use crate::gfx_api::GfxStagingBuffer;
+use crate::gfx_api::GfxTexture;
+use crate::gfx_api::LazyTexture;
+use crate::gfx_api::PendingShmTransfer;
+use crate::gfx_api::ReleaseSync;
+use crate::gfx_api::STAGING_DOWNLOAD;
+use crate::ifs::wl_seat::WlSeatGlobal;
+use crate::rect::Rect;
+use crate::rect::Region;
+use crate::rect::RegionBuilder;
+use crate::state::State;
+use crate::tree::OutputNode;
+use crate::tree::Transform;
+use crate::tree::TreeTimeline::RenderTL;
+use crate::utils::copyhashmap::CopyHashMap;
+use crate::utils::errorfmt::ErrorFmt;
+use crate::utils::numcell::NumCell;
+use crate::utils::oserror::OsError;
+use crate::utils::oserror::OsErrorExt;
+use crate::utils::oserror::OsErrorExt2;
+use crate::vnc::client::VncClient;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+use std::rc::Rc;
+use thiserror::Error;
+use uapi::OwnedFd;
+use uapi::Ustring;
+use uapi::c;
+
+const TILE_SIZE: i32 = 64;
+
+#[derive(Debug, Error)]
+pub enum VncError {
+    #[error("`{0}` is not a valid socket name")]
+    InvalidName(String),
+    #[error("{0} is not a loopback address")]
+    NotLoopback(SocketAddr),
+    #[error("XDG_RUNTIME_DIR is not set")]
+    XrdNotSet,
+    #[error("The path {0} is too long to form a unix socket address")]
+    PathTooLong(String),
+    #[error("Could not create a socket")]
+    CreateSocket(#[source] OsError),
+    #[error("Could not stat the existing file")]
+    Stat(#[source] OsError),
+    #[error("{0} exists and is not a socket")]
+    NotASocket(String),
+    #[error("Could not bind the socket to an address")]
+    Bind(#[source] OsError),
+    #[error("Could not change the permissions of the socket")]
+    Chmod(#[source] OsError),
+    #[error("Could not start listening for incoming connections")]
+    Listen(#[source] OsError),
+    #[error("There is no render context")]
+    NoRenderContext,
+    #[error("Could not create the capture framebuffer")]
+    CreateFramebuffer(#[source] GfxError),
+    #[error("Could not copy the output to the capture framebuffer")]
+    Copy(#[source] GfxError),
+    #[error("Could not download the capture framebuffer")]
+    Download(#[source] GfxError),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VncAddress {
+    Unix(String),
+    Tcp(SocketAddr),
+}
+
+impl VncAddress {
+    /// Parses a port such as `5900`, a TCP socket address such as `[::1]:5900`, or
+    /// the name of a unix socket in `XDG_RUNTIME_DIR`.
+    ///
+    /// The server does not support authentication. A bare port is therefore bound to
+    /// `127.0.0.1` and other TCP addresses must be loopback addresses. Unix sockets
+    /// are created in the runtime directory with mode 0600 so that only the current
+    /// user can connect to them.
+    pub fn parse(s: &str) -> Result<Self, VncError> {
+        if let Ok(port) = s.parse::<u16>() {
+            return Ok(Self::Tcp(SocketAddr::new(Ipv4Addr::LOCALHOST.into(), port)));
+        }
+        if let Ok(addr) = s.parse::<SocketAddr>() {
+            if !addr.ip().is_loopback() {
+                return Err(VncError::NotLoopback(addr));
+            }
+            return Ok(Self::Tcp(addr));
+        }
+        if s.is_empty() || s == "." || s == ".." || s.contains('/') {
+            return Err(VncError::InvalidName(s.to_string()));
+        }
+        Ok(Self::Unix(s.to_string()))
+    }
+}
+
+impl Display for VncAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VncAddress::Unix(name) => f.write_str(name),
+            VncAddress::Tcp(addr) => Display::fmt(addr, f),
+        }
+    }
+}
+
+struct VncSocket {
+    fd: Rc<OwnedFd>,
+    unix_path: Option<Ustring>,
+}
+
+impl Drop for VncSocket {
+    fn drop(&mut self) {
+        if let Some(path) = &self.unix_path {
+            let _ = uapi::unlink(path);
+        }
+    }
+}
+
+fn bind(address: &VncAddress) -> Result<VncSocket, VncError> {
+    match address {
+        VncAddress::Unix(name) => {
+            let Some(xrd) = *XDG_RUNTIME_DIR else {
+                return Err(VncError::XrdNotSet);
+            };
+            bind_unix(&format!("{xrd}/{name}"))
+        }
+        VncAddress::Tcp(addr) => bind_tcp(*addr),
+    }
+}
+
+fn bind_unix(path: &str) -> Result<VncSocket, VncError> {
+    let mut addr: c::sockaddr_un = uapi::pod_zeroed();
+    addr.sun_family = c::AF_UNIX as _;
+    if path.len() + 1 > addr.sun_path.len() {
+        return Err(VncError::PathTooLong(path.to_string()));
+    }
+    let fd = uapi::socket(c::AF_UNIX, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0)
+        .map(Rc::new)
+        .map_os_err(VncError::CreateSocket)?;
+    let upath = Ustring::from_vec(path.as_bytes().to_vec());
+    match uapi::lstat(&upath).to_os_error() {
+        Ok(stat) if stat.st_mode & c::S_IFMT == c::S_IFSOCK => {
+            log::info!("Unlinking {}", path);
+            let _ = uapi::unlink(&upath);
+        }
+        Ok(_) => return Err(VncError::NotASocket(path.to_string())),
+        Err(OsError(c::ENOENT)) => {}
+        Err(e) => return Err(VncError::Stat(e)),
+    }
+    let sun_path = uapi::as_bytes_mut(&mut addr.sun_path[..]);
+    sun_path[..path.len()].copy_from_slice(path.as_bytes());
+    sun_path[path.len()] = 0;
+    uapi::bind(fd.raw(), &addr).map_os_err(VncError::Bind)?;
+    uapi::chmod(&upath, 0o600)
+        .map_os_err(VncError::Chmod)
+        .inspect_err(|_| {
+            let _ = uapi::unlink(&upath);
+        })?;
+    Ok(VncSocket {
+        fd,
+        unix_path: Some(upath),
+    })
+}
+
+fn bind_tcp(addr: SocketAddr) -> Result<VncSocket, VncError> {
+    let domain = match addr.ip() {
+        IpAddr::V4(_) => c::AF_INET,
+        IpAddr::V6(_) => c::AF_INET6,
+    };
+    let fd = uapi::socket(domain, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0)
+        .map(Rc::new)
+        .map_os_err(VncError::CreateSocket)?;
+    let _ = uapi::setsockopt(fd.raw(), c::SOL_SOCKET, c::SO_REUSEADDR, &1 as &c::c_int);
+    let res = match addr {
+        SocketAddr::V4(addr) => {
+            let mut sa: c::sockaddr_in = uapi::pod_zeroed();
+            sa.sin_family = c::AF_INET as _;
+            sa.sin_port = addr.port().to_be();
+            sa.sin_addr.s_addr = u32::from_ne_bytes(addr.ip().octets());
+            uapi::bind(fd.raw(), &sa)
+        }
+        SocketAddr::V6(addr) => {
+            let mut sa: c::sockaddr_in6 = uapi::pod_zeroed();
+            sa.sin6_family = c::AF_INET6 as _;
+            sa.sin6_port = addr.port().to_be();
+            sa.sin6_addr.s6_addr = addr.ip().octets();
+            sa.sin6_scope_id = addr.scope_id();
+            uapi::bind(fd.raw(), &sa)
+        }
+    };
+    res.map_os_err(VncError::Bind)?;
+    Ok(VncSocket {
+        fd,
+        unix_path: None,
+    })
+}
+
+#[derive(Default)]
+struct Framebuffer {
+    width: i32,
+    height: i32,
+    data: Vec<u8>,
+}
+
+struct Capture {
+    ctx_version: u32,
+    width: i32,
+    height: i32,
+    fb: Rc<dyn GfxInternalFramebuffer>,
+    staging: Rc<dyn GfxStagingBuffer>,
+    mem: Rc<Vec<Cell<u8>>>,
+}
+
+pub struct VncServer {
+    state: Rc<State>,
+    connector: String,
+    address: VncAddress,
+    seat: Rc<WlSeatGlobal>,
+    /// Whether the seat was created for this server and must be destroyed with it.
+    owns_seat: bool,
+    socket: VncSocket,
+    accept: Cell<Option<SpawnedFuture<()>>>,
+    next_client_id: NumCell<u64>,
+    clients: CopyHashMap<u64, Rc<VncClient>>,
+    framebuffer: RefCell<Framebuffer>,
+    capture: RefCell<Option<Capture>>,
+    pending_download: Cell<Option<PendingShmTransfer>>,
+    downloading: Cell<bool>,
+    force_capture: Cell<bool>,
+}
+
+impl VncServer {
+    pub fn spawn(
+        state: &Rc<State>,
+        connector: &str,
+        address: VncAddress,
+    ) -> Result<Rc<Self>, VncError> {
+        let socket = bind(&address)?;
+        uapi::listen(socket.fd.raw(), 16).map_os_err(VncError::Listen)?;
+        let seat_name = format!("vnc-{}", connector);
+        let seat = state
+            .globals
+            .seats
+            .lock()
+            .values()
+            .find(|s| s.seat_name() == seat_name)
+            .cloned();
+        let owns_seat = seat.is_none();
+        let seat = seat.unwrap_or_else(|| state.create_seat(&seat_name));
+        let slf = Rc::new(Self {
+            state: state.clone(),
+            connector: connector.to_string(),
+            address,
+            seat,
+            owns_seat,
+            socket,
+            accept: Default::default(),
+            next_client_id: Default::default(),
+            clients: Default::default(),
+            framebuffer: Default::default(),
+            capture: Default::default(),
+            pending_download: Default::default(),
+            downloading: Default::default(),
+            force_capture: Cell::new(true),
+        });
+        log::info!(
+            "Serving output {} via VNC on {}",
+            slf.connector,
+            slf.address
+        );
+        slf.accept
+            .set(Some(state.eng.spawn("vnc accept", slf.clone().accept())));
+        Ok(slf)
+    }
+
+    pub fn address(&self) -> &VncAddress {
+        &self.address
+    }
+
+    pub fn has_clients(&self) -> bool {
+        self.clients.is_not_empty()
+    }
+
+    pub fn kill(&self) {
+        self.accept.take();
+        self.pending_download.take();
+        self.capture.take();
+        for client in self.clients.clear().into_values() {
+            client.disconnect();
+        }
+        if self.owns_seat {
+            self.seat.destroy();
+        }
+    }
+
+    async fn accept(self: Rc<Self>) {
+        loop {
+            let fd = match self
+                .state
+                .ring
+                .accept(&self.socket.fd, c::SOCK_CLOEXEC)
+                .await
+            {
+                Ok(fd) => fd,
+                Err(e) => {
+                    log::error!(
+                        "Could not accept a VNC client on {}: {}",
+                        self.address,
+                        ErrorFmt(e)
+                    );
+                    return;
+                }
+            };
+            let id = self.next_client_id.fetch_add(1);
+            log::info!("VNC client {} connected to output {}", id, self.connector);
+            let client = VncClient::spawn(&self, id, &fd);
+            self.clients.set(id, client);
+        }
+    }
+
+    fn output(&self) -> Option<Rc<OutputNode>> {
+        self.state
+            .root
+            .outputs
+            .lock()
+            .values()
+            .find(|o| *o.global.connector.name == self.connector)
+            .cloned()
+    }
+
+    /// Causes the output to be rendered again if a frame was skipped.
+    fn request_frame(&self) {
+        if self.force_capture.replace(false) {
+            if let Some(output) = self.output() {
+                output.global.connector.damage();
+            }
+        }
+    }
+
+    pub fn copy_texture(
+        self: &Rc<Self>,
+        on: &OutputNode,
+        tex: &Rc<dyn GfxTexture>,
+        cd: &Rc<ColorDescription>,
+        resv: Option<&Rc<dyn BufferResv>>,
+        lazy: Option<&Rc<dyn LazyTexture>>,
+        acquire_sync: &AcquireSync,
+        release_sync: ReleaseSync,
+        x_off: i32,
+        y_off: i32,
+        size: Option<(i32, i32)>,
+    ) {
+        let wants_update = self.clients.lock().values().any(|c| c.wants_update());
+        if !wants_update || self.downloading.get() {
+            self.force_capture.set(true);
+            return;
+        }
+        let res = self.capture(
+            on,
+            tex,
+            cd,
+            resv,
+            lazy,
+            acquire_sync,
+            release_sync,
+            x_off,
+            y_off,
+            size,
+        );
+        match res {
+            Ok(Some(pending)) => {
+                self.downloading.set(true);
+                self.pending_download.set(Some(pending));
+            }
+            Ok(None) => self.update_framebuffer(),
+            Err(e) => {
+                log::error!(
+                    "Could not capture output {}: {}",
+                    self.connector,
+                    ErrorFmt(e)
+                );
+            }
+        }
+    }
+
+    fn capture(
+        self: &Rc<Self>,
+        on: &OutputNode,
+        tex: &Rc<dyn GfxTexture>,
+        cd: &Rc<ColorDescription>,
+        resv: Option<&Rc<dyn BufferResv>>,
+        lazy: Option<&Rc<dyn LazyTexture>>,
+        acquire_sync: &AcquireSync,
+        release_sync: ReleaseSync,
+        x_off: i32,
+        y_off: i32,
+        size: Option<(i32, i32)>,
+    ) -> Result<Option<PendingShmTransfer>, VncError> {
+        let Some(ctx) = self.state.render_ctx.get() else {
+            return Err(VncError::NoRenderContext);
+        };
+        let ctx_version = self.state.render_ctx_version.get();
+        let (width, height) = on.pixel_size();
+        let capture = &mut *self.capture.borrow_mut();
+        let capture = match capture {
+            Some(c) if (c.ctx_version, c.width, c.height) == (ctx_version, width, height) => c,
+            _ => {
+                let stride = width * 4;
+                let fb = ctx
+                    .clone()
+                    .create_internal_fb(&self.state.cpu_worker, width, height, stride, XRGB8888)
+                    .map_err(VncError::CreateFramebuffer)?;
+                let staging = ctx.create_staging_buffer(fb.staging_size(), STAGING_DOWNLOAD);
+                let mem = (0..stride as usize * height as usize)
+                    .map(|_| Cell::new(0))
+                    .collect();
+                capture.insert(Capture {
+                    ctx_version,
+                    width,
+                    height,
+                    fb,
+                    staging,
+                    mem: Rc::new(mem),
+                })
+            }
+        };
+        let ns = &on.node_state[RenderTL];
+        self.state
+            .perform_screencopy(
+                tex,
+                resv,
+                lazy,
+                acquire_sync,
+                release_sync,
+                cd,
+                &(capture.fb.clone() as Rc<dyn GfxFramebuffer>),
+                AcquireSync::Unnecessary,
+                ReleaseSync::None,
+                Transform::None,
+                self.state.color_manager.srgb_gamma22(),
+                ns.pos.get(),
+                true,
+                x_off,
+                y_off,
+                size,
+                ns.transform.get(),
+                ns.scale.get(),
+                on.global.persistent.scaling_filter.get(),
+            )
+            .map_err(VncError::Copy)?;
+        capture
+            .fb
+            .clone()
+            .download(
+                &capture.staging,
+                self.clone(),
+                capture.mem.clone(),
+                Region::new(Rect::new_sized_saturating(0, 0, width, height)),
+            )
+            .map_err(VncError::Download)
+    }
+
+    fn update_framebuffer(&self) {
+        let damage = {
+            let capture = self.capture.borrow();
+            let Some(capture) = &*capture else {
+                return;
+            };
+            self.diff_framebuffer(capture)
+        };
+        let clients: Vec<_> = self.clients.lock().values().cloned().collect();
+        for client in clients {
+            client.framebuffer_changed(&damage);
+        }
+    }
+
+    /// Copies the captured frame into the framebuffer and returns the tiles that have
+    /// changed.
+    fn diff_framebuffer(&self, capture: &Capture) -> Rc<Region> {
+        let (width, height) = (capture.width, capture.height);
+        let mem = &capture.mem[..];
+        let fb = &mut *self.framebuffer.borrow_mut();
+        let resized = (fb.width, fb.height) != (width, height);
+        if resized {
+            fb.width = width;
+            fb.height = height;
+            fb.data = vec![0; mem.len()];
+        }
+        let stride = width as usize * 4;
+        let mut damage = RegionBuilder::default();
+        for y1 in (0..height).step_by(TILE_SIZE as usize) {
+            let tile_height = TILE_SIZE.min(height - y1);
+            for x1 in (0..width).step_by(TILE_SIZE as usize) {
+                let tile_width = TILE_SIZE.min(width - x1);
+                let rows = (y1..y1 + tile_height).map(|y| {
+                    let start = y as usize * stride + x1 as usize * 4;
+                    start..start + tile_width as usize * 4
+                });
+                let changed = resized
+                    || rows.clone().any(|row| {
+                        mem[row.clone()]
+                            .iter()
+                            .zip(&fb.data[row])
+                            .any(|(new, old)| new.get() != *old)
+                    });
+                if !changed {
+                    continue;
+                }
+                for row in rows {
+                    for (new, old) in mem[row.clone()].iter().zip(&mut fb.data[row]) {
+                        *old = new.get();
+                    }
+                }
+                damage.add(Rect::new_sized_saturating(x1, y1, tile_width, tile_height));
+            }
+        }
+        damage.get()
+    }
+}
+
+impl AsyncShmGfxTextureCallback for VncServer {
+    fn completed(self: Rc<Self>, res: Result<(), GfxError>) {
+        self.downloading.set(false);
+        self.pending_download.take();
+        match res {
+            Ok(()) => self.update_framebuffer(),
+            Err(e) => {
+                log::error!(
+                    "Could not download the contents of output {}: {}",
+                    self.connector,
+                    ErrorFmt(e)
+                );
+            }
+        }
+    }
+}
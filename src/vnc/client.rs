@@ -0,0 +1,428 @@
+use crate::async_engine::SpawnedFuture;
+use crate::backend::AXIS_120;
+use crate::backend::AxisSource;
+use crate::backend::ButtonState;
+use crate::backend::KeyState;
+use crate::backend::ScrollAxis;
+use crate::bar_elements::BE_SCREEN_SHARE;
+use crate::ifs::wl_seat::BTN_LEFT;
+use crate::ifs::wl_seat::BTN_MIDDLE;
+use crate::ifs::wl_seat::BTN_RIGHT;
+use crate::ifs::wl_seat::PX_PER_SCROLL;
+use crate::ifs::wl_seat::PhysicalKeyboardId;
+use crate::kbvm::KbvmMap;
+use crate::rect::Rect;
+use crate::rect::Region;
+use crate::tree::TreeTimeline::LiveTL;
+use crate::utils::buf::Buf;
+use crate::utils::bufio::BufIo;
+use crate::utils::bufio::BufIoError;
+use crate::utils::bufio::BufIoIncoming;
+use crate::utils::bufio::BufIoMessage;
+use crate::utils::clonecell::CloneCell;
+use crate::utils::errorfmt::ErrorFmt;
+use crate::vnc::VncServer;
+use crate::vnc::rfb::CLIENT_CUT_TEXT;
+use crate::vnc::rfb::CLIENT_FRAMEBUFFER_UPDATE_REQUEST;
+use crate::vnc::rfb::CLIENT_KEY_EVENT;
+use crate::vnc::rfb::CLIENT_POINTER_EVENT;
+use crate::vnc::rfb::CLIENT_SET_ENCODINGS;
+use crate::vnc::rfb::CLIENT_SET_PIXEL_FORMAT;
+use crate::vnc::rfb::ENCODING_DESKTOP_SIZE;
+use crate::vnc::rfb::ENCODING_RAW;
+use crate::vnc::rfb::PROTOCOL_VERSION;
+use crate::vnc::rfb::PixelFormat;
+use crate::vnc::rfb::RfbError;
+use crate::vnc::rfb::SECURITY_NONE;
+use crate::vnc::rfb::parse_protocol_version;
+use crate::vnc::rfb::write_framebuffer_update_header;
+use crate::vnc::rfb::write_rect_header;
+use crate::vnc::rfb::write_server_init;
+use ahash::AHashMap;
+use kbvm::GroupIndex;
+use kbvm::Keycode;
+use kbvm::ModifierMask;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::rc::Rc;
+use thiserror::Error;
+use uapi::OwnedFd;
+
+const MAX_CUT_TEXT: u32 = 1 << 20;
+
+const BUTTONS: [(u8, u32); 3] = [(0, BTN_LEFT), (1, BTN_MIDDLE), (2, BTN_RIGHT)];
+
+const WHEEL: [(u8, ScrollAxis, i32); 4] = [
+    (3, ScrollAxis::Vertical, -AXIS_120),
+    (4, ScrollAxis::Vertical, AXIS_120),
+    (5, ScrollAxis::Horizontal, -AXIS_120),
+    (6, ScrollAxis::Horizontal, AXIS_120),
+];
+
+#[derive(Debug, Error)]
+enum VncClientError {
+    #[error(transparent)]
+    Io(#[from] BufIoError),
+    #[error(transparent)]
+    Rfb(#[from] RfbError),
+}
+
+pub struct VncClient {
+    id: u64,
+    server: Rc<VncServer>,
+    bufio: Rc<BufIo>,
+    keyboard_id: PhysicalKeyboardId,
+    pixel_format: Cell<PixelFormat>,
+    desktop_size: Cell<bool>,
+    size: Cell<(i32, i32)>,
+    request: Cell<Option<Rect>>,
+    damage: CloneCell<Rc<Region>>,
+    buttons: Cell<u8>,
+    pressed_keys: RefCell<AHashMap<u32, u32>>,
+    incoming: Cell<Option<SpawnedFuture<()>>>,
+    outgoing: Cell<Option<SpawnedFuture<()>>>,
+}
+
+impl VncClient {
+    pub fn spawn(server: &Rc<VncServer>, id: u64, fd: &Rc<OwnedFd>) -> Rc<Self> {
+        let state = &server.state;
+        let slf = Rc::new(Self {
+            id,
+            server: server.clone(),
+            bufio: Rc::new(BufIo::new(fd, &state.ring)),
+            keyboard_id: state.physical_keyboard_ids.next(),
+            pixel_format: Cell::new(PixelFormat::SERVER),
+            desktop_size: Default::default(),
+            size: Default::default(),
+            request: Default::default(),
+            damage: CloneCell::new(Region::empty()),
+            buttons: Default::default(),
+            pressed_keys: Default::default(),
+            incoming: Default::default(),
+            outgoing: Default::default(),
+        });
+        slf.incoming
+            .set(Some(state.eng.spawn("vnc incoming", slf.clone().run())));
+        slf.outgoing
+            .set(Some(state.eng.spawn("vnc outgoing", slf.clone().flush())));
+        state.bar_elements_changed(BE_SCREEN_SHARE);
+        slf
+    }
+
+    pub fn wants_update(&self) -> bool {
+        self.request.get().is_some()
+    }
+
+    pub fn framebuffer_changed(&self, damage: &Rc<Region>) {
+        self.damage.set(self.damage.get().union(damage));
+        self.maybe_send_update();
+    }
+
+    fn kill(&self) {
+        self.server.clients.remove(&self.id);
+        self.disconnect();
+    }
+
+    pub fn disconnect(&self) {
+        log::info!("VNC client {} disconnected", self.id);
+        self.bufio.shutdown();
+        let seat = &self.server.seat;
+        let time = self.server.state.now_usec();
+        let buttons = self.buttons.take();
+        for (bit, button) in BUTTONS {
+            if buttons & (1 << bit) != 0 {
+                seat.button_event(time, button, ButtonState::Released);
+            }
+        }
+        seat.destroy_physical_keyboard(self.keyboard_id);
+        self.pressed_keys.borrow_mut().clear();
+        self.incoming.take();
+        self.outgoing.take();
+        self.server.state.bar_elements_changed(BE_SCREEN_SHARE);
+    }
+
+    fn send(&self, msg: &[u8]) {
+        self.bufio.send(BufIoMessage {
+            fds: vec![],
+            buf: Buf::from_slice(msg),
+        });
+    }
+
+    async fn flush(self: Rc<Self>) {
+        if let Err(e) = self.bufio.clone().outgoing().await {
+            log::info!("VNC client {}: {}", self.id, ErrorFmt(e));
+        }
+        self.kill();
+    }
+
+    async fn run(self: Rc<Self>) {
+        let mut incoming = self.bufio.incoming();
+        let mut buf = vec![];
+        if let Err(e) = self.handle_incoming(&mut incoming, &mut buf).await {
+            log::info!("VNC client {}: {}", self.id, ErrorFmt(e));
+        }
+        self.kill();
+    }
+
+    async fn handle_incoming(
+        &self,
+        incoming: &mut BufIoIncoming,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), VncClientError> {
+        macro_rules! read {
+            ($n:expr) => {{
+                buf.clear();
+                incoming.fill_msg_buf($n as usize, buf).await?;
+            }};
+        }
+        let u16 = |buf: &[u8], i: usize| u16::from_be_bytes([buf[i], buf[i + 1]]);
+        let u32 = |buf: &[u8], i: usize| u32::from_be_bytes(buf[i..i + 4].try_into().unwrap());
+        self.send(PROTOCOL_VERSION);
+        read!(PROTOCOL_VERSION.len());
+        let minor = parse_protocol_version(buf[..].try_into().unwrap())?;
+        if minor == 3 {
+            self.send(&(SECURITY_NONE as u32).to_be_bytes());
+        } else {
+            self.send(&[1, SECURITY_NONE]);
+            read!(1);
+            if buf[0] != SECURITY_NONE {
+                return Err(RfbError::UnsupportedSecurityType(buf[0]).into());
+            }
+            if minor == 8 {
+                self.send(&0u32.to_be_bytes());
+            }
+        }
+        // shared flag
+        read!(1);
+        self.send_server_init();
+        loop {
+            read!(1);
+            match buf[0] {
+                CLIENT_SET_PIXEL_FORMAT => {
+                    read!(19);
+                    let format = PixelFormat::parse(buf[3..].try_into().unwrap())?;
+                    self.pixel_format.set(format);
+                }
+                CLIENT_SET_ENCODINGS => {
+                    read!(3);
+                    let n = u16(buf, 1);
+                    read!(n as usize * 4);
+                    let desktop_size = buf
+                        .chunks_exact(4)
+                        .any(|e| u32(e, 0) as i32 == ENCODING_DESKTOP_SIZE);
+                    self.desktop_size.set(desktop_size);
+                }
+                CLIENT_FRAMEBUFFER_UPDATE_REQUEST => {
+                    read!(9);
+                    let incremental = buf[0] != 0;
+                    let rect = Rect::new_sized_saturating(
+                        u16(buf, 1) as i32,
+                        u16(buf, 3) as i32,
+                        u16(buf, 5) as i32,
+                        u16(buf, 7) as i32,
+                    );
+                    if !incremental {
+                        let damage = Region::from_rects(&[rect]);
+                        self.damage.set(self.damage.get().union(&damage));
+                    }
+                    self.request.set(Some(rect));
+                    self.maybe_send_update();
+                }
+                CLIENT_KEY_EVENT => {
+                    read!(7);
+                    self.handle_key(buf[0] != 0, u32(buf, 3));
+                }
+                CLIENT_POINTER_EVENT => {
+                    read!(5);
+                    self.handle_pointer(buf[0], u16(buf, 1), u16(buf, 3));
+                }
+                CLIENT_CUT_TEXT => {
+                    read!(7);
+                    // Negative lengths are used by the extended clipboard pseudo-encoding.
+                    let len = (u32(buf, 3) as i32).unsigned_abs();
+                    if len > MAX_CUT_TEXT {
+                        return Err(RfbError::CutTextTooLarge(len).into());
+                    }
+                    read!(len);
+                }
+                ty => return Err(RfbError::UnknownMessage(ty).into()),
+            }
+        }
+    }
+
+    fn send_server_init(&self) {
+        let size = {
+            let fb = self.server.framebuffer.borrow();
+            (fb.width, fb.height)
+        };
+        let (width, height) = match size {
+            (0, _) | (_, 0) => match self.server.output() {
+                Some(o) => o.pixel_size(),
+                _ => (640, 480),
+            },
+            _ => size,
+        };
+        self.size.set((width, height));
+        let mut msg = vec![];
+        let name = format!("Jay - {}", self.server.connector);
+        write_server_init(&mut msg, width as u16, height as u16, &name);
+        self.send(&msg);
+    }
+
+    fn maybe_send_update(&self) {
+        let Some(request) = self.request.get() else {
+            return;
+        };
+        self.server.request_frame();
+        let fb = self.server.framebuffer.borrow();
+        if fb.width == 0 || fb.height == 0 {
+            return;
+        }
+        let mut msg = vec![];
+        if (fb.width, fb.height) != self.size.get() {
+            if !self.desktop_size.get() {
+                log::warn!(
+                    "VNC client {} does not support changing the framebuffer size",
+                    self.id
+                );
+                drop(fb);
+                self.kill();
+                return;
+            }
+            self.size.set((fb.width, fb.height));
+            self.request.set(None);
+            let full = Rect::new_sized_saturating(0, 0, fb.width, fb.height);
+            self.damage.set(Region::from_rects(&[full]));
+            write_framebuffer_update_header(&mut msg, 1);
+            write_rect_header(
+                &mut msg,
+                0,
+                0,
+                fb.width as u16,
+                fb.height as u16,
+                ENCODING_DESKTOP_SIZE,
+            );
+            self.send(&msg);
+            return;
+        }
+        let bounds = Rect::new_sized_saturating(0, 0, fb.width, fb.height);
+        let damage = self
+            .damage
+            .get()
+            .intersect(&Region::new(request.intersect(bounds)));
+        if damage.rects().is_empty() {
+            return;
+        }
+        self.request.set(None);
+        let request = Region::from_rects(&[request]);
+        self.damage.set(self.damage.get().subtract(&request));
+        let extents;
+        let mut rects = damage.rects();
+        if rects.len() > u16::MAX as usize {
+            extents = [damage.extents()];
+            rects = &extents;
+        }
+        let format = self.pixel_format.get();
+        write_framebuffer_update_header(&mut msg, rects.len() as u16);
+        for rect in rects {
+            write_rect_header(
+                &mut msg,
+                rect.x1() as u16,
+                rect.y1() as u16,
+                rect.width() as u16,
+                rect.height() as u16,
+                ENCODING_RAW,
+            );
+            for y in rect.y1()..rect.y2() {
+                let start = (y * fb.width + rect.x1()) as usize * 4;
+                let end = start + rect.width() as usize * 4;
+                format.encode(&fb.data[start..end], &mut msg);
+            }
+        }
+        self.send(&msg);
+    }
+
+    fn handle_key(&self, down: bool, keysym: u32) {
+        let seat = &self.server.seat;
+        let key = {
+            let pressed_keys = &mut *self.pressed_keys.borrow_mut();
+            if down {
+                if pressed_keys.contains_key(&keysym) {
+                    // Clients send key repeats as additional key-down events.
+                    return;
+                }
+                let Some(key) = find_keycode(&seat.keymap(), keysym) else {
+                    log::debug!("Keysym {:#x} is not part of the keymap", keysym);
+                    return;
+                };
+                pressed_keys.insert(keysym, key);
+                key
+            } else {
+                let Some(key) = pressed_keys.remove(&keysym) else {
+                    return;
+                };
+                key
+            }
+        };
+        let state = match down {
+            true => KeyState::Pressed,
+            false => KeyState::Released,
+        };
+        let time = self.server.state.now_usec();
+        let kb = seat.get_physical_keyboard(self.keyboard_id, None);
+        kb.phy_state.update(time, seat, key, state);
+    }
+
+    fn handle_pointer(&self, mask: u8, x: u16, y: u16) {
+        let Some(output) = self.server.output() else {
+            return;
+        };
+        let seat = &self.server.seat;
+        let time = self.server.state.now_usec();
+        let (width, height) = self.size.get();
+        if width > 0 && height > 0 {
+            let x = (x as f32 + 0.5) / width as f32;
+            let y = (y as f32 + 0.5) / height as f32;
+            let rect = output.node_state[LiveTL].pos.get();
+            seat.motion_absolute_event(time, rect, x, y);
+        }
+        let changed = self.buttons.replace(mask) ^ mask;
+        for (bit, button) in BUTTONS {
+            if changed & (1 << bit) != 0 {
+                let state = match mask & (1 << bit) != 0 {
+                    true => ButtonState::Pressed,
+                    false => ButtonState::Released,
+                };
+                seat.button_event(time, button, state);
+            }
+        }
+        let pressed = changed & mask;
+        for (bit, axis, delta) in WHEEL {
+            if pressed & (1 << bit) != 0 {
+                seat.axis_source(AxisSource::Wheel);
+                seat.axis_120(delta, axis, false);
+                seat.axis_frame(PX_PER_SCROLL, time);
+            }
+        }
+    }
+}
+
+fn find_keycode(map: &KbvmMap, keysym: u32) -> Option<u32> {
+    let masks = [
+        ModifierMask::default(),
+        ModifierMask::SHIFT,
+        ModifierMask::MOD5,
+        ModifierMask::SHIFT | ModifierMask::MOD5,
+    ];
+    for mods in masks {
+        for key in 1..256 {
+            let lookup = map
+                .lookup_table
+                .lookup(GroupIndex(0), mods, Keycode::from_evdev(key));
+            if lookup.into_iter().any(|p| p.keysym().0 == keysym) {
+                return Some(key);
+            }
+        }
+    }
+    None
+}
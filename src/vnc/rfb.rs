@@ -0,0 +1,188 @@
+#[cfg(test)]
+mod tests;
+
+use thiserror::Error;
+
+pub const PROTOCOL_VERSION: &[u8; 12] = b"RFB 003.008\n";
+
+pub const SECURITY_NONE: u8 = 1;
+
+pub const CLIENT_SET_PIXEL_FORMAT: u8 = 0;
+pub const CLIENT_SET_ENCODINGS: u8 = 2;
+pub const CLIENT_FRAMEBUFFER_UPDATE_REQUEST: u8 = 3;
+pub const CLIENT_KEY_EVENT: u8 = 4;
+pub const CLIENT_POINTER_EVENT: u8 = 5;
+pub const CLIENT_CUT_TEXT: u8 = 6;
+
+pub const SERVER_FRAMEBUFFER_UPDATE: u8 = 0;
+
+pub const ENCODING_RAW: i32 = 0;
+pub const ENCODING_DESKTOP_SIZE: i32 = -223;
+
+#[derive(Debug, Error)]
+pub enum RfbError {
+    #[error("Client sent an invalid protocol version {0:?}")]
+    InvalidProtocolVersion(String),
+    #[error("Client selected unsupported security type {0}")]
+    UnsupportedSecurityType(u8),
+    #[error("Client sent unknown message type {0}")]
+    UnknownMessage(u8),
+    #[error("Pixel formats with {0} bits per pixel are not supported")]
+    UnsupportedBitsPerPixel(u8),
+    #[error("Color-mapped pixel formats are not supported")]
+    ColorMap,
+    #[error("Cut text of {0} bytes is too large")]
+    CutTextTooLarge(u32),
+}
+
+/// Parses the protocol version sent by the client and returns the minor version that
+/// will be used for the connection.
+pub fn parse_protocol_version(version: &[u8; 12]) -> Result<u32, RfbError> {
+    let invalid =
+        || RfbError::InvalidProtocolVersion(String::from_utf8_lossy(version).into_owned());
+    let Some(rest) = version.strip_prefix(b"RFB ") else {
+        return Err(invalid());
+    };
+    if rest[3] != b'.' || rest[7] != b'\n' {
+        return Err(invalid());
+    }
+    let parse = |s: &[u8]| {
+        str::from_utf8(s)
+            .ok()
+            .and_then(|s| s.parse::<u32>().ok())
+            .ok_or_else(invalid)
+    };
+    let major = parse(&rest[..3])?;
+    let minor = parse(&rest[4..7])?;
+    let minor = match major {
+        0..3 => return Err(invalid()),
+        3 => match minor {
+            0..3 => return Err(invalid()),
+            3..7 => 3,
+            7 => 7,
+            _ => 8,
+        },
+        _ => 8,
+    };
+    Ok(minor)
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct PixelFormat {
+    pub bits_per_pixel: u8,
+    pub depth: u8,
+    pub big_endian: bool,
+    pub true_color: bool,
+    pub red_max: u16,
+    pub green_max: u16,
+    pub blue_max: u16,
+    pub red_shift: u8,
+    pub green_shift: u8,
+    pub blue_shift: u8,
+}
+
+impl PixelFormat {
+    /// The memory layout of XRGB8888.
+    pub const SERVER: Self = Self {
+        bits_per_pixel: 32,
+        depth: 24,
+        big_endian: false,
+        true_color: true,
+        red_max: 255,
+        green_max: 255,
+        blue_max: 255,
+        red_shift: 16,
+        green_shift: 8,
+        blue_shift: 0,
+    };
+
+    pub fn parse(buf: &[u8; 16]) -> Result<Self, RfbError> {
+        let u16 = |i: usize| u16::from_be_bytes([buf[i], buf[i + 1]]);
+        let format = Self {
+            bits_per_pixel: buf[0],
+            depth: buf[1],
+            big_endian: buf[2] != 0,
+            true_color: buf[3] != 0,
+            red_max: u16(4),
+            green_max: u16(6),
+            blue_max: u16(8),
+            red_shift: buf[10],
+            green_shift: buf[11],
+            blue_shift: buf[12],
+        };
+        if !matches!(format.bits_per_pixel, 8 | 16 | 32) {
+            return Err(RfbError::UnsupportedBitsPerPixel(format.bits_per_pixel));
+        }
+        if !format.true_color {
+            return Err(RfbError::ColorMap);
+        }
+        Ok(format)
+    }
+
+    pub fn write(&self, dst: &mut Vec<u8>) {
+        dst.extend_from_slice(&[
+            self.bits_per_pixel,
+            self.depth,
+            self.big_endian as u8,
+            self.true_color as u8,
+        ]);
+        dst.extend_from_slice(&self.red_max.to_be_bytes());
+        dst.extend_from_slice(&self.green_max.to_be_bytes());
+        dst.extend_from_slice(&self.blue_max.to_be_bytes());
+        dst.extend_from_slice(&[self.red_shift, self.green_shift, self.blue_shift, 0, 0, 0]);
+    }
+
+    /// Converts XRGB8888 pixels to this format.
+    pub fn encode(&self, src: &[u8], dst: &mut Vec<u8>) {
+        if *self == Self::SERVER {
+            dst.extend_from_slice(src);
+            return;
+        }
+        let channel = |v: u32, max: u16, shift: u8| {
+            let max = max as u32;
+            ((v * max + 127) / 255)
+                .checked_shl(shift as u32)
+                .unwrap_or(0)
+        };
+        for px in src.chunks_exact(4) {
+            let v = channel(px[2] as u32, self.red_max, self.red_shift)
+                | channel(px[1] as u32, self.green_max, self.green_shift)
+                | channel(px[0] as u32, self.blue_max, self.blue_shift);
+            match (self.bits_per_pixel, self.big_endian) {
+                (8, _) => dst.push(v as u8),
+                (16, false) => dst.extend_from_slice(&(v as u16).to_le_bytes()),
+                (16, true) => dst.extend_from_slice(&(v as u16).to_be_bytes()),
+                (_, false) => dst.extend_from_slice(&v.to_le_bytes()),
+                (_, true) => dst.extend_from_slice(&v.to_be_bytes()),
+            }
+        }
+    }
+}
+
+pub fn write_server_init(dst: &mut Vec<u8>, width: u16, height: u16, name: &str) {
+    dst.extend_from_slice(&width.to_be_bytes());
+    dst.extend_from_slice(&height.to_be_bytes());
+    PixelFormat::SERVER.write(dst);
+    dst.extend_from_slice(&(name.len() as u32).to_be_bytes());
+    dst.extend_from_slice(name.as_bytes());
+}
+
+pub fn write_framebuffer_update_header(dst: &mut Vec<u8>, num_rects: u16) {
+    dst.extend_from_slice(&[SERVER_FRAMEBUFFER_UPDATE, 0]);
+    dst.extend_from_slice(&num_rects.to_be_bytes());
+}
+
+pub fn write_rect_header(
+    dst: &mut Vec<u8>,
+    x: u16,
+    y: u16,
+    width: u16,
+    height: u16,
+    encoding: i32,
+) {
+    dst.extend_from_slice(&x.to_be_bytes());
+    dst.extend_from_slice(&y.to_be_bytes());
+    dst.extend_from_slice(&width.to_be_bytes());
+    dst.extend_from_slice(&height.to_be_bytes());
+    dst.extend_from_slice(&encoding.to_be_bytes());
+}
@@ -0,0 +1,95 @@
+use crate::vnc::rfb::PixelFormat;
+use crate::vnc::rfb::RfbError;
+use crate::vnc::rfb::parse_protocol_version;
+use crate::vnc::rfb::write_server_init;
+
+#[test]
+fn protocol_version() {
+    assert_eq!(parse_protocol_version(b"RFB 003.003\n").unwrap(), 3);
+    assert_eq!(parse_protocol_version(b"RFB 003.005\n").unwrap(), 3);
+    assert_eq!(parse_protocol_version(b"RFB 003.007\n").unwrap(), 7);
+    assert_eq!(parse_protocol_version(b"RFB 003.008\n").unwrap(), 8);
+    assert_eq!(parse_protocol_version(b"RFB 003.889\n").unwrap(), 8);
+    assert_eq!(parse_protocol_version(b"RFB 004.001\n").unwrap(), 8);
+    assert!(parse_protocol_version(b"RFB 002.008\n").is_err());
+    assert!(parse_protocol_version(b"RFB 003.002\n").is_err());
+    assert!(parse_protocol_version(b"RFB 003,008\n").is_err());
+    assert!(parse_protocol_version(b"HTTP/1.1 200").is_err());
+}
+
+#[test]
+fn pixel_format_roundtrip() {
+    let mut buf = vec![];
+    PixelFormat::SERVER.write(&mut buf);
+    assert_eq!(buf.len(), 16);
+    let parsed = PixelFormat::parse(buf.as_slice().try_into().unwrap()).unwrap();
+    assert_eq!(parsed, PixelFormat::SERVER);
+}
+
+#[test]
+fn pixel_format_unsupported() {
+    let mut buf = [0; 16];
+    buf[0] = 24;
+    buf[3] = 1;
+    assert!(matches!(
+        PixelFormat::parse(&buf),
+        Err(RfbError::UnsupportedBitsPerPixel(24))
+    ));
+    buf[0] = 8;
+    buf[3] = 0;
+    assert!(matches!(PixelFormat::parse(&buf), Err(RfbError::ColorMap)));
+}
+
+#[test]
+fn encode() {
+    // XRGB8888 in memory: b, g, r, x
+    let src = [0x10, 0x80, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00];
+    let mut dst = vec![];
+    PixelFormat::SERVER.encode(&src, &mut dst);
+    assert_eq!(dst, src);
+
+    let rgb565 = PixelFormat {
+        bits_per_pixel: 16,
+        depth: 16,
+        big_endian: false,
+        true_color: true,
+        red_max: 31,
+        green_max: 63,
+        blue_max: 31,
+        red_shift: 11,
+        green_shift: 5,
+        blue_shift: 0,
+    };
+    let expected = (31u16 << 11) | (32 << 5) | 2;
+    let mut dst = vec![];
+    rgb565.encode(&src, &mut dst);
+    assert_eq!(dst[..2], expected.to_le_bytes());
+    assert_eq!(dst[2..], [0, 0]);
+    let mut dst = vec![];
+    PixelFormat {
+        big_endian: true,
+        ..rgb565
+    }
+    .encode(&src[..4], &mut dst);
+    assert_eq!(dst, expected.to_be_bytes());
+
+    let rgbx_be = PixelFormat {
+        big_endian: true,
+        red_shift: 24,
+        green_shift: 16,
+        blue_shift: 8,
+        ..PixelFormat::SERVER
+    };
+    let mut dst = vec![];
+    rgbx_be.encode(&src[..4], &mut dst);
+    assert_eq!(dst, [0xff, 0x80, 0x10, 0x00]);
+}
+
+#[test]
+fn server_init() {
+    let mut dst = vec![];
+    write_server_init(&mut dst, 1920, 1080, "jay");
+    assert_eq!(dst[..4], [0x07, 0x80, 0x04, 0x38]);
+    assert_eq!(dst[20..24], [0, 0, 0, 3]);
+    assert_eq!(&dst[24..], b"jay");
+}
@@ -0,0 +1,36 @@
+use crate::vnc::VncAddress;
+use crate::vnc::VncError;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+
+#[test]
+fn address() {
+    assert_eq!(
+        VncAddress::parse("vnc-0").unwrap(),
+        VncAddress::Unix("vnc-0".to_string()),
+    );
+    for invalid in ["", ".", "..", "/run/user/1000/vnc", "../vnc"] {
+        assert!(
+            matches!(VncAddress::parse(invalid), Err(VncError::InvalidName(_))),
+            "{invalid}",
+        );
+    }
+}
+
+#[test]
+fn tcp_address() {
+    assert_eq!(
+        VncAddress::parse("5900").unwrap(),
+        VncAddress::Tcp(SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 5900)),
+    );
+    assert_eq!(
+        VncAddress::parse("[::1]:5901").unwrap().to_string(),
+        "[::1]:5901",
+    );
+    for remote in ["0.0.0.0:5900", "192.168.0.1:5900", "[::]:5900"] {
+        assert!(
+            matches!(VncAddress::parse(remote), Err(VncError::NotLoopback(_))),
+            "{remote}",
+        );
+    }
+}
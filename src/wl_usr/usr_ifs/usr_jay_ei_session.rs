@@ -1,11 +1,22 @@
+use crate::fixed::Fixed;
 use crate::object::Version;
 use crate::utils::clonecell::CloneCell;
 use crate::wire::JayEiSessionId;
+use crate::wire::jay_ei_session::AddBarrier;
+use crate::wire::jay_ei_session::CaptureActivated;
+use crate::wire::jay_ei_session::CaptureDeactivated;
+use crate::wire::jay_ei_session::ClearBarriers;
 use crate::wire::jay_ei_session::Created;
 use crate::wire::jay_ei_session::Destroyed;
+use crate::wire::jay_ei_session::DisableCapture;
+use crate::wire::jay_ei_session::EnableCapture;
 use crate::wire::jay_ei_session::Failed;
 use crate::wire::jay_ei_session::JayEiSessionEventHandler;
 use crate::wire::jay_ei_session::Release;
+use crate::wire::jay_ei_session::ReleaseCapture;
+use crate::wire::jay_ei_session::ReleaseCaptureAt;
+use crate::wire::jay_ei_session::Zone;
+use crate::wire::jay_ei_session::ZonesDone;
 use crate::wl_usr::UsrCon;
 use crate::wl_usr::usr_object::UsrObject;
 use std::convert::Infallible;
@@ -29,6 +40,60 @@ pub trait UsrJayEiSessionOwner {
     fn failed(&self, reason: &str) {
         let _ = reason;
     }
+
+    fn zone(&self, ev: &Zone) {
+        let _ = ev;
+    }
+
+    fn zones_done(&self) {}
+
+    fn capture_activated(&self, ev: &CaptureActivated) {
+        let _ = ev;
+    }
+
+    fn capture_deactivated(&self, ev: &CaptureDeactivated) {
+        let _ = ev;
+    }
+}
+
+impl UsrJayEiSession {
+    pub fn clear_barriers(&self) {
+        self.con.request(ClearBarriers { self_id: self.id });
+    }
+
+    pub fn add_barrier(&self, id: u32, x1: i32, y1: i32, x2: i32, y2: i32) {
+        self.con.request(AddBarrier {
+            self_id: self.id,
+            id,
+            x1,
+            y1,
+            x2,
+            y2,
+        });
+    }
+
+    pub fn enable_capture(&self) {
+        self.con.request(EnableCapture { self_id: self.id });
+    }
+
+    pub fn disable_capture(&self) {
+        self.con.request(DisableCapture { self_id: self.id });
+    }
+
+    pub fn release_capture(&self, activation_id: u32, position: Option<(Fixed, Fixed)>) {
+        match position {
+            Some((x, y)) => self.con.request(ReleaseCaptureAt {
+                self_id: self.id,
+                activation_id,
+                x,
+                y,
+            }),
+            None => self.con.request(ReleaseCapture {
+                self_id: self.id,
+                activation_id,
+            }),
+        }
+    }
 }
 
 impl JayEiSessionEventHandler for UsrJayEiSession {
@@ -54,6 +119,38 @@ impl JayEiSessionEventHandler for UsrJayEiSession {
         }
         Ok(())
     }
+
+    fn zone(&self, ev: Zone, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.zone(&ev);
+        }
+        Ok(())
+    }
+
+    fn zones_done(&self, _ev: ZonesDone, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.zones_done();
+        }
+        Ok(())
+    }
+
+    fn capture_activated(&self, ev: CaptureActivated, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.capture_activated(&ev);
+        }
+        Ok(())
+    }
+
+    fn capture_deactivated(
+        &self,
+        ev: CaptureDeactivated,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.capture_deactivated(&ev);
+        }
+        Ok(())
+    }
 }
 
 usr_object_base! {
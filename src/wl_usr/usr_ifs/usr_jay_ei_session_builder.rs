@@ -3,6 +3,7 @@ use crate::wire::JayEiSessionBuilderId;
 use crate::wire::jay_ei_session_builder::Commit;
 use crate::wire::jay_ei_session_builder::JayEiSessionBuilderEventHandler;
 use crate::wire::jay_ei_session_builder::SetAppId;
+use crate::wire::jay_ei_session_builder::SetInputCapture;
 use crate::wl_usr::UsrCon;
 use crate::wl_usr::usr_ifs::usr_jay_ei_session::UsrJayEiSession;
 use crate::wl_usr::usr_object::UsrObject;
@@ -23,6 +24,10 @@ impl UsrJayEiSessionBuilder {
         });
     }
 
+    pub fn set_input_capture(&self) {
+        self.con.request(SetInputCapture { self_id: self.id });
+    }
+
     pub fn commit(&self) -> Rc<UsrJayEiSession> {
         let obj = Rc::new(UsrJayEiSession {
             id: self.con.id(),
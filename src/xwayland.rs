@@ -307,6 +307,7 @@ pub enum XWaylandEvent {
     Close(Rc<XwindowData>),
     #[expect(unused)]
     SeatChanged,
+    CursorSettingsChanged,
 
     IpcCancelSource {
         location: IpcLocation,
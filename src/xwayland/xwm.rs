@@ -4,6 +4,8 @@ use crate::async_engine::SpawnedFuture;
 use crate::client::Client;
 use crate::criteria::tlm::TL_CHANGED_CLASS_INST;
 use crate::criteria::tlm::TL_CHANGED_ROLE;
+use crate::cursor::DEFAULT_CURSOR_SIZE;
+use crate::env::XCURSOR_THEME;
 use crate::ifs::ipc::DataOfferId;
 use crate::ifs::ipc::DataSourceId;
 use crate::ifs::ipc::DynDataOffer;
@@ -93,6 +95,7 @@ use crate::xcon::consts::_NET_WM_STATE_REMOVE;
 use crate::xcon::consts::_NET_WM_STATE_TOGGLE;
 use crate::xcon::consts::ATOM_ATOM;
 use crate::xcon::consts::ATOM_NONE;
+use crate::xcon::consts::ATOM_RESOURCE_MANAGER;
 use crate::xcon::consts::ATOM_STRING;
 use crate::xcon::consts::ATOM_WINDOW;
 use crate::xcon::consts::ATOM_WM_CLASS;
@@ -137,6 +140,7 @@ use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::cell::Cell;
 use std::cell::RefCell;
+use std::fmt::Write;
 use std::marker::PhantomData;
 use std::mem::{self};
 use std::ops::Deref;
@@ -488,46 +492,7 @@ impl Wm {
                 data: uapi::as_bytes(&0u32),
             });
         }
-        'set_root_cursor: {
-            let cursors = match state.cursors.get() {
-                Some(g) => g,
-                _ => break 'set_root_cursor,
-            };
-            let first = match cursors.default.xcursor.first() {
-                Some(f) => f,
-                _ => break 'set_root_cursor,
-            };
-            let first = match first.iter().find(|i| i.0.0 == 1) {
-                Some(f) => f.1,
-                _ => break 'set_root_cursor,
-            };
-            let cursor = match c
-                .create_cursor(
-                    &first.pixels,
-                    first.width,
-                    first.height,
-                    first.xhot,
-                    first.yhot,
-                )
-                .await
-            {
-                Ok(c) => c,
-                Err(e) => {
-                    log::warn!("Could not create a root cursor: {}", ErrorFmt(e));
-                    break 'set_root_cursor;
-                }
-            };
-            let cwa = ChangeWindowAttributes {
-                window: root,
-                values: CreateWindowValues {
-                    cursor: Some(cursor),
-                    ..Default::default()
-                },
-            };
-            if let Err(e) = c.call(&cwa).await {
-                return Err(XWaylandError::SetCursor(e));
-            }
-        }
+        set_root_cursor(state, &c, root).await?;
         {
             let qv = XfixesQueryVersion {
                 client_major_version: 1,
@@ -644,6 +609,7 @@ impl Wm {
 
     pub async fn run(mut self) {
         self.seats_changed();
+        self.update_resource_manager().await;
         loop {
             select! {
                 e = self.state.xwayland.queue.pop().fuse() => self.handle_xwayland_event(e).await,
@@ -652,6 +618,46 @@ impl Wm {
         }
     }
 
+    async fn cursor_settings_changed(&self) {
+        if let Err(e) = set_root_cursor(&self.state, &self.c, self.root).await {
+            log::warn!("Could not update the root cursor: {}", ErrorFmt(e));
+        }
+        self.update_resource_manager().await;
+    }
+
+    async fn update_resource_manager(&self) {
+        let Some((theme, size)) = self.state.cursor_env.borrow().clone() else {
+            return;
+        };
+        let mut buf = vec![];
+        let mut resources = String::new();
+        if self
+            .c
+            .get_property::<u8>(self.root, ATOM_RESOURCE_MANAGER, ATOM_STRING, &mut buf)
+            .await
+            .is_ok()
+        {
+            for line in String::from_utf8_lossy(&buf).lines() {
+                if line.starts_with("Xcursor.theme:") || line.starts_with("Xcursor.size:") {
+                    continue;
+                }
+                let _ = writeln!(resources, "{line}");
+            }
+        }
+        if let Some(theme) = theme.as_deref().map(|t| t.as_str()).or(*XCURSOR_THEME) {
+            let _ = writeln!(resources, "Xcursor.theme:\t{theme}");
+        }
+        let _ = writeln!(resources, "Xcursor.size:\t{size}");
+        self.c.call(&ChangeProperty {
+            mode: PROP_MODE_REPLACE,
+            window: self.root,
+            property: ATOM_RESOURCE_MANAGER,
+            ty: ATOM_STRING,
+            format: 8,
+            data: resources.as_bytes(),
+        });
+    }
+
     async fn handle_xwayland_event(&mut self, e: XWaylandEvent) {
         match e {
             XWaylandEvent::SurfaceCreated(event) => {
@@ -671,6 +677,7 @@ impl Wm {
             XWaylandEvent::ActivateRoot => self.activate_window(None, Initiator::Wayland).await,
             XWaylandEvent::Close(window) => self.close_window(&window).await,
             XWaylandEvent::SeatChanged => self.seats_changed(),
+            XWaylandEvent::CursorSettingsChanged => self.cursor_settings_changed().await,
             XWaylandEvent::IpcCancelSource {
                 location,
                 seat,
@@ -2723,3 +2730,51 @@ impl WaylandToXTransfer {
         self.shared.transfers.remove(&self.id);
     }
 }
+
+async fn set_root_cursor(state: &State, c: &Rc<Xcon>, root: u32) -> Result<(), XWaylandError> {
+    let (theme, size) = state
+        .cursor_env
+        .borrow()
+        .clone()
+        .unwrap_or((None, *DEFAULT_CURSOR_SIZE));
+    let Some(cursors) = state.server_cursors(theme.as_ref()) else {
+        return Ok(());
+    };
+    let Some(first) = cursors.default.xcursor.first() else {
+        return Ok(());
+    };
+    let Some((_, first)) = first
+        .iter()
+        .filter(|i| i.0.0 == 1)
+        .min_by_key(|i| i.0.1.abs_diff(size))
+    else {
+        return Ok(());
+    };
+    let cursor = match c
+        .create_cursor(
+            &first.pixels,
+            first.width,
+            first.height,
+            first.xhot,
+            first.yhot,
+        )
+        .await
+    {
+        Ok(c) => c,
+        Err(e) => {
+            log::warn!("Could not create a root cursor: {}", ErrorFmt(e));
+            return Ok(());
+        }
+    };
+    let cwa = ChangeWindowAttributes {
+        window: root,
+        values: CreateWindowValues {
+            cursor: Some(cursor),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = c.call(&cwa).await {
+        return Err(XWaylandError::SetCursor(e));
+    }
+    Ok(())
+}
@@ -10,12 +10,14 @@ mod spanned;
 mod value;
 
 use crate::config::context::Context;
+use crate::config::parsers::bar::Bar;
 pub use crate::config::parsers::client_match::parse_client_match;
 use crate::config::parsers::color_management::ColorManagement;
 use crate::config::parsers::config::ConfigParser;
 use crate::config::parsers::config::ConfigParserError;
 use crate::config::parsers::float::Float;
 use crate::config::parsers::focus_history::FocusHistory;
+use crate::config::parsers::gestures::Gestures;
 pub use crate::config::parsers::input_mode::InputMode;
 use crate::config::parsers::session_management::SessionManagement;
 use crate::config::parsers::transactions::Transactions;
@@ -33,14 +35,25 @@ use jay_config::Workspace;
 use jay_config::client::ClientCapabilities;
 use jay_config::get_overlay;
 use jay_config::get_workspace;
+use jay_config::input::AccessibilityFeedback;
 use jay_config::input::FallbackOutputMode;
 use jay_config::input::InputEventCode;
 use jay_config::input::LayerDirection;
+use jay_config::input::LayoutMemoryMode;
+use jay_config::input::ModifiedPointerTrigger;
 use jay_config::input::SwitchEvent;
 use jay_config::input::Timeline;
 use jay_config::input::acceleration::AccelProfile;
 use jay_config::input::clickmethod::ClickMethod;
+use jay_config::input::key_remap::KeyRemap;
+use jay_config::input::mouse_keys::MouseKeys as MouseKeysSettings;
 use jay_config::input::scrollmethod::ScrollMethod;
+use jay_config::input::sendevents::SendEventsMode;
+use jay_config::input::tablet::EraserMode;
+use jay_config::input::tablet::PressureCurve;
+use jay_config::input::tablet::TabletPadEvent;
+use jay_config::input::tablet::TabletToolType;
+use jay_config::input::tapbuttonmap::TapButtonMap;
 use jay_config::keyboard::Keymap;
 use jay_config::keyboard::ModifiedKeySym;
 use jay_config::keyboard::mods::Modifiers;
@@ -50,6 +63,7 @@ use jay_config::status::MessageFormat;
 use jay_config::theme::BarPosition;
 use jay_config::theme::Color;
 use jay_config::theme::ContainerBorders;
+use jay_config::video::AutoScale;
 use jay_config::video::BlendSpace;
 use jay_config::video::ColorSpace;
 use jay_config::video::Connector;
@@ -61,6 +75,7 @@ use jay_config::video::TearingMode;
 use jay_config::video::Transform;
 use jay_config::video::VrrMode;
 use jay_config::window::ContentType;
+use jay_config::window::ShortcutsInhibit;
 use jay_config::window::TileState;
 use jay_config::window::WindowType;
 use jay_config::workspace::WorkspaceDisplayOrder;
@@ -75,6 +90,9 @@ use std::time::Duration;
 use thiserror::Error;
 use toml::toml_parser;
 
+pub const DEFAULT_MACRO_NAME: &str = "default";
+pub const DEFAULT_BACKLIGHT_STEP: f64 = 5.0;
+
 #[derive(Debug, Copy, Clone)]
 pub enum SimpleCommand {
     Close,
@@ -115,6 +133,19 @@ pub enum SimpleCommand {
     EnableSimpleIm(bool),
     ToggleSimpleImEnabled,
     ReloadSimpleIm,
+    EnableStickyKeys(bool),
+    ToggleStickyKeys,
+    EnableSlowKeys(bool),
+    ToggleSlowKeys,
+    EnableBounceKeys(bool),
+    ToggleBounceKeys,
+    EnableMouseKeys(bool),
+    ToggleMouseKeys,
+    StartMacroRecording,
+    StopMacroRecording,
+    ReplayMacro,
+    MapTabletsToWindow,
+    UnmapTabletsFromWindow,
     EnableUnicodeInput,
     OpenControlCenter,
     WarpMouseToFocus,
@@ -123,6 +154,11 @@ pub enum SimpleCommand {
     ToggleVisualizeCompositing,
     SetSplitReusesContainer(bool),
     ToggleSplitReusesContainer,
+    SetDpms(bool),
+    ToggleDpms,
+    AdjustBacklight(f64),
+    SetRotationLocked(bool),
+    ToggleRotationLock,
 }
 
 #[derive(Debug, Clone)]
@@ -242,6 +278,20 @@ pub enum Action {
         counter: Rc<CounterSlot>,
         value: i64,
     },
+    StartMacroRecording {
+        name: String,
+        pointer: bool,
+    },
+    ReplayMacro {
+        name: String,
+    },
+    ApplyOutputProfile {
+        name: String,
+    },
+    SetBacklight {
+        value: f64,
+        relative: bool,
+    },
 }
 
 #[derive(Debug)]
@@ -368,6 +418,7 @@ pub struct WindowRule {
     pub latch: Option<Action>,
     pub auto_focus: Option<bool>,
     pub initial_tile_state: Option<TileState>,
+    pub shortcuts_inhibit: Option<ShortcutsInhibit>,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -418,10 +469,20 @@ pub struct Mode {
     pub width: i32,
     pub height: i32,
     pub refresh_rate: Option<f64>,
+    pub timings: Option<ModeTimings>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ModeTimings {
+    Cvt { reduced_blanking: bool },
+    Modeline(String),
 }
 
 impl Display for Mode {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(ModeTimings::Modeline(modeline)) = &self.timings {
+            return write!(f, "{modeline}");
+        }
         write!(f, "{} x {}", self.width, self.height)?;
         if let Some(rr) = self.refresh_rate {
             write!(f, " @ {rr}")?;
@@ -449,6 +510,8 @@ pub struct Output {
     pub blend_space: Option<BlendSpace>,
     pub use_native_gamut: Option<bool>,
     pub enabled: Option<bool>,
+    pub touch_edge_width: Option<i32>,
+    pub icc_profile: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -499,6 +562,38 @@ pub struct Input {
     pub scroll_button: Option<InputEventCode>,
     pub scroll_button_lock: Option<bool>,
     pub detached: Option<bool>,
+    pub disable_while_typing: Option<bool>,
+    pub disable_while_trackpointing: Option<bool>,
+    pub tap_button_map: Option<TapButtonMap>,
+    pub send_events: Option<SendEventsMode>,
+    pub rotation: Option<u32>,
+    pub custom_accel: Option<CustomAccel>,
+    pub key_remap: Option<KeyRemap>,
+    pub tablet: Option<Tablet>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TabletRegion {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Tablet {
+    pub area: Option<[f64; 4]>,
+    pub keep_aspect_ratio: Option<bool>,
+    pub region: Option<TabletRegion>,
+    pub eraser_mode: Option<EraserMode>,
+    pub pressure_curves: Vec<(TabletToolType, PressureCurve)>,
+    pub pad_actions: AHashMap<TabletPadEvent, Action>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CustomAccel {
+    pub step: f64,
+    pub points: Vec<f64>,
 }
 
 #[derive(Debug, Clone)]
@@ -514,6 +609,8 @@ pub struct Exec {
 pub struct ConfigConnector {
     pub match_: ConnectorMatch,
     pub enabled: bool,
+    pub edid_override: Option<String>,
+    pub vnc: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -545,6 +642,40 @@ pub struct Vrr {
     pub cursor_hz: Option<f64>,
 }
 
+#[derive(Debug, Clone)]
+pub struct KeyboardAccessibility {
+    pub sticky_keys: Option<bool>,
+    pub slow_keys: Option<bool>,
+    pub slow_keys_delay: Option<u32>,
+    pub bounce_keys: Option<bool>,
+    pub bounce_keys_delay: Option<u32>,
+    pub feedback_actions: AHashMap<AccessibilityFeedback, Action>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MouseKeys {
+    pub enabled: Option<bool>,
+    pub settings: MouseKeysSettings,
+}
+
+#[derive(Debug, Clone)]
+pub struct WhichKey {
+    pub enabled: Option<bool>,
+    pub delay: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OutputProfile {
+    pub name: String,
+    pub outputs: Vec<Output>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Cursor {
+    pub hide_while_typing: Option<bool>,
+    pub hide_after_ms: Option<u64>,
+}
+
 #[derive(Debug, Clone)]
 pub struct SimpleIm {
     pub enabled: Option<bool>,
@@ -566,14 +697,22 @@ pub struct Libei {
     pub enable_socket: Option<bool>,
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ShortcutTrigger {
+    Key(ModifiedKeySym),
+    Pointer(ModifiedPointerTrigger),
+}
+
 #[derive(Debug, Clone)]
 pub struct Shortcut {
     pub mask: Modifiers,
-    pub keysym: ModifiedKeySym,
+    pub trigger: ShortcutTrigger,
     pub repeat: bool,
     pub allow_locked: bool,
     pub action: Action,
     pub latch: Option<Action>,
+    pub name: String,
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -592,6 +731,10 @@ pub struct Config {
     pub status: Option<Status>,
     pub connectors: Vec<ConfigConnector>,
     pub outputs: Vec<Output>,
+    pub output_profiles: Vec<OutputProfile>,
+    pub auto_scale: Option<AutoScale>,
+    pub show_backlight_indicator: bool,
+    pub auto_rotation: bool,
     pub workspace_capture: bool,
     pub env: Vec<(String, String)>,
     pub on_startup: Option<Action>,
@@ -633,13 +776,23 @@ pub struct Config {
     pub input_modes: AHashMap<String, InputMode>,
     pub workspace_display_order: Option<WorkspaceDisplayOrder>,
     pub simple_im: Option<SimpleIm>,
+    pub keyboard_accessibility: Option<KeyboardAccessibility>,
+    pub mouse_keys: Option<MouseKeys>,
+    pub which_key: Option<WhichKey>,
+    pub cursor: Option<Cursor>,
     pub fallback_output_mode: Option<FallbackOutputMode>,
+    pub layout_memory: Option<LayoutMemoryMode>,
+    pub shortcuts_inhibit_escape: Option<ModifiedKeySym>,
+    pub input_capture_release: Option<ModifiedKeySym>,
     pub mouse_follows_focus: Option<bool>,
     pub transactions: Option<Transactions>,
     pub cursor_size: Option<i32>,
+    pub cursor_theme: Option<String>,
     pub configure_all_devices: bool,
     pub triggers: Vec<Rc<Trigger>>,
     pub max_trigger_depth: u64,
+    pub bar: Option<Bar>,
+    pub gestures: Option<Gestures>,
 }
 
 #[derive(Debug, Error)]
@@ -650,6 +803,18 @@ pub enum ConfigError {
     Parser(#[from] ConfigParserError),
 }
 
+#[cfg(test)]
+pub fn parse_test_config(input: &str) -> Config {
+    let mut workspaces = Default::default();
+    parse_config(
+        input.as_bytes(),
+        &Default::default(),
+        &mut workspaces,
+        |e| panic!("Could not parse the config: {e}"),
+    )
+    .unwrap()
+}
+
 pub fn parse_config<F>(
     input: &[u8],
     mark_names: &RefCell<AHashMap<String, u32>>,
@@ -15,6 +15,8 @@ use thiserror::Error;
 
 pub mod action;
 mod actions;
+mod auto_scale;
+pub mod bar;
 mod capabilities;
 mod clean_logs_older_than;
 pub mod client_match;
@@ -25,6 +27,7 @@ pub mod config;
 mod connector;
 mod connector_match;
 mod content_type;
+mod cursor;
 mod drm_device;
 mod drm_device_match;
 mod egui;
@@ -34,24 +37,32 @@ mod fallback_output_mode;
 pub mod float;
 pub mod focus_history;
 mod format;
+pub mod gestures;
 mod gfx_api;
 mod idle;
 mod input;
 mod input_match;
 pub mod input_mode;
+mod key_remap;
+mod keyboard_accessibility;
 pub mod keymap;
+mod layout_memory;
 mod libei;
 mod log_level;
 pub mod mark_id;
 mod mode;
 pub mod modified_keysym;
+mod mouse_keys;
 mod output;
 mod output_match;
+mod output_profile;
 mod repeat_rate;
 pub mod session_management;
 pub mod shortcuts;
+mod shortcuts_inhibit;
 mod simple_im;
 mod status;
+pub mod tablet;
 mod tearing;
 mod theme;
 mod tile_state;
@@ -59,6 +70,7 @@ pub mod transactions;
 pub mod trigger;
 mod ui_drag;
 mod vrr;
+mod which_key;
 pub mod window_match;
 mod window_rule;
 mod window_type;
@@ -1,13 +1,17 @@
 use crate::config::Action;
+use crate::config::DEFAULT_BACKLIGHT_STEP;
+use crate::config::DEFAULT_MACRO_NAME;
 use crate::config::SimpleCommand;
 use crate::config::context::Context;
 use crate::config::extractor::Extractor;
 use crate::config::extractor::ExtractorError;
 use crate::config::extractor::arr;
 use crate::config::extractor::bol;
+use crate::config::extractor::fltorint;
 use crate::config::extractor::int;
 use crate::config::extractor::n32;
 use crate::config::extractor::opt;
+use crate::config::extractor::recover;
 use crate::config::extractor::s32;
 use crate::config::extractor::str;
 use crate::config::extractor::val;
@@ -206,6 +210,23 @@ impl ActionParser<'_, '_, '_> {
             "disable-simple-im" => EnableSimpleIm(false),
             "toggle-simple-im-enabled" => ToggleSimpleImEnabled,
             "reload-simple-im" => ReloadSimpleIm,
+            "enable-sticky-keys" => EnableStickyKeys(true),
+            "disable-sticky-keys" => EnableStickyKeys(false),
+            "toggle-sticky-keys" => ToggleStickyKeys,
+            "enable-slow-keys" => EnableSlowKeys(true),
+            "disable-slow-keys" => EnableSlowKeys(false),
+            "toggle-slow-keys" => ToggleSlowKeys,
+            "enable-bounce-keys" => EnableBounceKeys(true),
+            "disable-bounce-keys" => EnableBounceKeys(false),
+            "toggle-bounce-keys" => ToggleBounceKeys,
+            "enable-mouse-keys" => EnableMouseKeys(true),
+            "disable-mouse-keys" => EnableMouseKeys(false),
+            "toggle-mouse-keys" => ToggleMouseKeys,
+            "start-macro-recording" => StartMacroRecording,
+            "stop-macro-recording" => StopMacroRecording,
+            "replay-macro" => ReplayMacro,
+            "map-tablets-to-window" => MapTabletsToWindow,
+            "unmap-tablets-from-window" => UnmapTabletsFromWindow,
             "enable-unicode-input" => EnableUnicodeInput,
             "open-control-center" => OpenControlCenter,
             "warp-mouse-to-focus" => WarpMouseToFocus,
@@ -216,6 +237,14 @@ impl ActionParser<'_, '_, '_> {
             "enable-split-reuses-container" => SetSplitReusesContainer(true),
             "disable-split-reuses-container" => SetSplitReusesContainer(false),
             "toggle-split-reuses-container" => ToggleSplitReusesContainer,
+            "dpms-on" => SetDpms(true),
+            "dpms-off" => SetDpms(false),
+            "toggle-dpms" => ToggleDpms,
+            "inc-backlight" => AdjustBacklight(DEFAULT_BACKLIGHT_STEP),
+            "dec-backlight" => AdjustBacklight(-DEFAULT_BACKLIGHT_STEP),
+            "lock-rotation" => SetRotationLocked(true),
+            "unlock-rotation" => SetRotationLocked(false),
+            "toggle-rotation-lock" => ToggleRotationLock,
             _ => {
                 return Err(
                     ActionParserError::UnknownSimpleAction(string.to_string()).spanned(span)
@@ -690,6 +719,61 @@ impl ActionParser<'_, '_, '_> {
             value: value.value,
         })
     }
+
+    fn parse_start_macro_recording(
+        &mut self,
+        ext: &mut Extractor<'_, '_, '_>,
+    ) -> ParseResult<Self> {
+        let (
+            name, //
+            pointer,
+        ) = ext.extract((
+            opt(str("name")), //
+            recover(opt(bol("pointer"))),
+        ))?;
+        Ok(Action::StartMacroRecording {
+            name: name.despan().unwrap_or(DEFAULT_MACRO_NAME).to_string(),
+            pointer: pointer.despan().unwrap_or(false),
+        })
+    }
+
+    fn parse_replay_macro(&mut self, ext: &mut Extractor<'_, '_, '_>) -> ParseResult<Self> {
+        let (name,) = ext.extract((opt(str("name")),))?;
+        Ok(Action::ReplayMacro {
+            name: name.despan().unwrap_or(DEFAULT_MACRO_NAME).to_string(),
+        })
+    }
+
+    fn parse_apply_output_profile(&mut self, ext: &mut Extractor<'_, '_, '_>) -> ParseResult<Self> {
+        let (name,) = ext.extract((str("name"),))?;
+        Ok(Action::ApplyOutputProfile {
+            name: name.value.to_string(),
+        })
+    }
+
+    fn parse_set_backlight(&mut self, ext: &mut Extractor<'_, '_, '_>) -> ParseResult<Self> {
+        let (value,) = ext.extract((fltorint("value"),))?;
+        Ok(Action::SetBacklight {
+            value: value.value,
+            relative: false,
+        })
+    }
+
+    fn parse_adj_backlight(
+        &mut self,
+        ext: &mut Extractor<'_, '_, '_>,
+        dec: bool,
+    ) -> ParseResult<Self> {
+        let (step,) = ext.extract((opt(fltorint("step")),))?;
+        let mut value = step.despan().unwrap_or(DEFAULT_BACKLIGHT_STEP);
+        if dec {
+            value = -value;
+        }
+        Ok(Action::SetBacklight {
+            value,
+            relative: true,
+        })
+    }
 }
 
 struct ShowWorkspaceDefaults {
@@ -764,6 +848,12 @@ impl Parser for ActionParser<'_, '_, '_> {
             "inc-counter" => self.parse_adj_counter(&mut ext, false),
             "dec-counter" => self.parse_adj_counter(&mut ext, true),
             "set-counter" => self.parse_set_counter(&mut ext),
+            "start-macro-recording" => self.parse_start_macro_recording(&mut ext),
+            "replay-macro" => self.parse_replay_macro(&mut ext),
+            "apply-output-profile" => self.parse_apply_output_profile(&mut ext),
+            "set-backlight" => self.parse_set_backlight(&mut ext),
+            "inc-backlight" => self.parse_adj_backlight(&mut ext, false),
+            "dec-backlight" => self.parse_adj_backlight(&mut ext, true),
             v => {
                 ext.ignore_unused();
                 return Err(ActionParserError::UnknownType(v.to_string()).spanned(ty.span));
@@ -0,0 +1,63 @@
+use crate::config::context::Context;
+use crate::config::extractor::Extractor;
+use crate::config::extractor::ExtractorError;
+use crate::config::extractor::fltorint;
+use crate::config::extractor::opt;
+use crate::config::parser::DataType;
+use crate::config::parser::ParseResult;
+use crate::config::parser::Parser;
+use crate::config::parser::UnexpectedDataType;
+use crate::toml::toml_span::Span;
+use crate::toml::toml_span::Spanned;
+use crate::toml::toml_span::SpannedExt;
+use crate::toml::toml_value::Value;
+use indexmap::IndexMap;
+use jay_config::video::AutoScale;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AutoScaleParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("`dpi` must be positive")]
+    NonPositiveDpi,
+    #[error("`step` must be positive")]
+    NonPositiveStep,
+}
+
+pub struct AutoScaleParser<'a, 'b, 'c>(pub &'a Context<'b, 'c>);
+
+impl Parser for AutoScaleParser<'_, '_, '_> {
+    type Value = Option<AutoScale>;
+    type Error = AutoScaleParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Boolean, DataType::Table];
+
+    fn parse_bool(&mut self, _span: Span, bool: bool) -> ParseResult<Self> {
+        Ok(bool.then(AutoScale::default))
+    }
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (dpi, step) = ext.extract((opt(fltorint("dpi")), opt(fltorint("step"))))?;
+        let mut auto_scale = AutoScale::default();
+        if let Some(dpi) = dpi {
+            if dpi.value <= 0.0 {
+                return Err(AutoScaleParserError::NonPositiveDpi.spanned(dpi.span));
+            }
+            auto_scale.dpi = dpi.value;
+        }
+        if let Some(step) = step {
+            if step.value <= 0.0 {
+                return Err(AutoScaleParserError::NonPositiveStep.spanned(step.span));
+            }
+            auto_scale.step = step.value;
+        }
+        Ok(Some(auto_scale))
+    }
+}
@@ -0,0 +1,124 @@
+use crate::config::context::Context;
+use crate::config::extractor::Extractor;
+use crate::config::extractor::ExtractorError;
+use crate::config::extractor::arr;
+use crate::config::extractor::opt;
+use crate::config::extractor::recover;
+use crate::config::extractor::str;
+use crate::config::extractor::val;
+use crate::config::parser::DataType;
+use crate::config::parser::ParseResult;
+use crate::config::parser::Parser;
+use crate::config::parser::UnexpectedDataType;
+use crate::config::parsers::color::ColorParser;
+use crate::toml::toml_span::DespanExt;
+use crate::toml::toml_span::Span;
+use crate::toml::toml_span::Spanned;
+use crate::toml::toml_span::SpannedExt;
+use crate::toml::toml_value::Value;
+use indexmap::IndexMap;
+use jay_config::theme::BarElement;
+use jay_config::theme::BarElementKind;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum BarParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("Unknown bar element {0}")]
+    UnknownElement(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Bar {
+    pub elements: Option<Vec<BarElement>>,
+    pub separator: Option<String>,
+}
+
+pub struct BarParser<'a, 'b, 'c>(pub &'a Context<'b, 'c>);
+
+impl Parser for BarParser<'_, '_, '_> {
+    type Value = Bar;
+    type Error = BarParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (
+            elements_val, //
+            separator,
+        ) = ext.extract((
+            recover(opt(arr("elements"))),
+            recover(opt(str("separator"))),
+        ))?;
+        let elements = elements_val.map(|array| {
+            let mut res = vec![];
+            for el in array.value {
+                match el.parse(&mut BarElementParser(self.0)) {
+                    Ok(e) => res.push(e),
+                    Err(e) => {
+                        log::warn!("Could not parse bar element: {}", self.0.error(e));
+                    }
+                }
+            }
+            res
+        });
+        Ok(Bar {
+            elements,
+            separator: separator.despan_into(),
+        })
+    }
+}
+
+struct BarElementParser<'a, 'b, 'c>(&'a Context<'b, 'c>);
+
+impl Parser for BarElementParser<'_, '_, '_> {
+    type Value = BarElement;
+    type Error = BarParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String, DataType::Table];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        let kind = match string {
+            "focused-window-title" => BarElementKind::FocusedWindowTitle,
+            "focused-window-app-id" => BarElementKind::FocusedWindowAppId,
+            "input-modes" => BarElementKind::InputModes,
+            "keyboard-layout" => BarElementKind::KeyboardLayout,
+            "lock-indicators" => BarElementKind::LockIndicators,
+            "screen-share" => BarElementKind::ScreenShare,
+            "sticky-keys" => BarElementKind::StickyKeys,
+            _ => return Err(BarParserError::UnknownElement(string.to_string()).spanned(span)),
+        };
+        Ok(BarElement::new(kind))
+    }
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (
+            ty, //
+            color_val,
+        ) = ext.extract((
+            str("type"), //
+            opt(val("color")),
+        ))?;
+        let mut element = self.parse_string(ty.span, ty.value)?;
+        if let Some(color) = color_val {
+            match color.parse(&mut ColorParser) {
+                Ok(color) => element = element.with_color(color),
+                Err(e) => {
+                    log::warn!("Could not parse the color: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(element)
+    }
+}
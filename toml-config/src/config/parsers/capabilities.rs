@@ -15,6 +15,7 @@ use jay_config::client::CC_HEAD_MANAGER;
 use jay_config::client::CC_IDLE_NOTIFIER;
 use jay_config::client::CC_INPUT_METHOD;
 use jay_config::client::CC_LAYER_SHELL;
+use jay_config::client::CC_OUTPUT_POWER_MANAGER;
 use jay_config::client::CC_SCREENCOPY;
 use jay_config::client::CC_SEAT_MANAGER;
 use jay_config::client::CC_SESSION_LOCK;
@@ -58,6 +59,7 @@ impl Parser for CapabilitiesParser {
             "head-manager" => CC_HEAD_MANAGER,
             "gamma-control-manager" => CC_GAMMA_CONTROL_MANAGER,
             "virtual-pointer" => CC_VIRTUAL_POINTER,
+            "output-power-manager" => CC_OUTPUT_POWER_MANAGER,
             _ => {
                 return Err(
                     CapabilitiesParserError::UnknownCapability(string.to_owned()).spanned(span),
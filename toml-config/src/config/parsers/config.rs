@@ -21,10 +21,13 @@ use crate::config::parser::Parser;
 use crate::config::parser::UnexpectedDataType;
 use crate::config::parsers::action::ActionParser;
 use crate::config::parsers::actions::ActionsParser;
+use crate::config::parsers::auto_scale::AutoScaleParser;
+use crate::config::parsers::bar::BarParser;
 use crate::config::parsers::clean_logs_older_than::CleanLogsOlderThanParser;
 use crate::config::parsers::client_rule::ClientRulesParser;
 use crate::config::parsers::color_management::ColorManagementParser;
 use crate::config::parsers::connector::ConnectorsParser;
+use crate::config::parsers::cursor::CursorParser;
 use crate::config::parsers::drm_device::DrmDevicesParser;
 use crate::config::parsers::drm_device_match::DrmDeviceMatchParser;
 use crate::config::parsers::egui::EguiParser;
@@ -32,14 +35,20 @@ use crate::config::parsers::env::EnvParser;
 use crate::config::parsers::fallback_output_mode::FallbackOutputModeParser;
 use crate::config::parsers::float::FloatParser;
 use crate::config::parsers::focus_history::FocusHistoryParser;
+use crate::config::parsers::gestures::GesturesParser;
 use crate::config::parsers::gfx_api::GfxApiParser;
 use crate::config::parsers::idle::IdleParser;
 use crate::config::parsers::input::InputsParser;
 use crate::config::parsers::input_mode::InputModesParser;
+use crate::config::parsers::keyboard_accessibility::KeyboardAccessibilityParser;
 use crate::config::parsers::keymap::KeymapParser;
+use crate::config::parsers::layout_memory::LayoutMemoryParser;
 use crate::config::parsers::libei::LibeiParser;
 use crate::config::parsers::log_level::LogLevelParser;
+use crate::config::parsers::modified_keysym::ModifiedKeysymParser;
+use crate::config::parsers::mouse_keys::MouseKeysParser;
 use crate::config::parsers::output::OutputsParser;
+use crate::config::parsers::output_profile::OutputProfilesParser;
 use crate::config::parsers::repeat_rate::RepeatRateParser;
 use crate::config::parsers::session_management::SessionManagementParser;
 use crate::config::parsers::shortcuts::ComplexShortcutsParser;
@@ -54,6 +63,7 @@ use crate::config::parsers::transactions::TransactionsParser;
 use crate::config::parsers::trigger::TriggersParser;
 use crate::config::parsers::ui_drag::UiDragParser;
 use crate::config::parsers::vrr::VrrParser;
+use crate::config::parsers::which_key::WhichKeyParser;
 use crate::config::parsers::window_rule::WindowRulesParser;
 use crate::config::parsers::workspace::WorkspacesParser;
 use crate::config::parsers::workspace_display_order::WorkspaceDisplayOrderParser;
@@ -177,7 +187,22 @@ impl Parser for ConfigParser<'_, '_, '_> {
                 split_reuses_container,
                 triggers_val,
                 max_trigger_depth_val,
+                bar_val,
+                gestures_val,
             ),
+            (
+                layout_memory_val,
+                shortcuts_inhibit_escape_val,
+                keyboard_accessibility_val,
+                mouse_keys_val,
+                which_key_val,
+                cursor_val,
+                cursor_theme,
+                output_profiles_val,
+                auto_scale_val,
+                show_backlight_indicator,
+            ),
+            (auto_rotation, input_capture_release_val),
         ) = ext.extract((
             (
                 opt(val("keymap")),
@@ -247,6 +272,24 @@ impl Parser for ConfigParser<'_, '_, '_> {
                 recover(opt(bol("split-reuses-container"))),
                 opt(val("triggers")),
                 opt(int("max-trigger-depth")),
+                opt(val("bar")),
+                opt(val("gestures")),
+            ),
+            (
+                opt(val("layout-memory")),
+                opt(val("shortcuts-inhibit-escape")),
+                opt(val("keyboard-accessibility")),
+                opt(val("mouse-keys")),
+                opt(val("which-key")),
+                opt(val("cursor")),
+                recover(opt(str("cursor-theme"))),
+                opt(val("output-profiles")),
+                opt(val("auto-scale")),
+                recover(opt(bol("show-backlight-indicator"))),
+            ),
+            (
+                recover(opt(bol("auto-rotation"))),
+                opt(val("input-capture-release")),
             ),
         ))?;
         let mut keymap = None;
@@ -295,6 +338,20 @@ impl Parser for ConfigParser<'_, '_, '_> {
                 Err(e) => log::warn!("Could not parse the status config: {}", self.0.error(e)),
             }
         }
+        let mut bar = None;
+        if let Some(value) = bar_val {
+            match value.parse(&mut BarParser(self.0)) {
+                Ok(v) => bar = Some(v),
+                Err(e) => log::warn!("Could not parse the bar config: {}", self.0.error(e)),
+            }
+        }
+        let mut gestures = None;
+        if let Some(value) = gestures_val {
+            match value.parse(&mut GesturesParser(self.0)) {
+                Ok(v) => gestures = Some(v),
+                Err(e) => log::warn!("Could not parse the gestures: {}", self.0.error(e)),
+            }
+        }
         let mut outputs = vec![];
         if let Some(value) = outputs_val {
             match value.parse(&mut OutputsParser(self.0)) {
@@ -302,6 +359,23 @@ impl Parser for ConfigParser<'_, '_, '_> {
                 Err(e) => log::warn!("Could not parse the outputs: {}", self.0.error(e)),
             }
         }
+        let mut output_profiles = vec![];
+        if let Some(value) = output_profiles_val {
+            match value.parse(&mut OutputProfilesParser(self.0)) {
+                Ok(v) => output_profiles = v,
+                Err(e) => log::warn!("Could not parse the output profiles: {}", self.0.error(e)),
+            }
+        }
+        let mut auto_scale = None;
+        if let Some(value) = auto_scale_val {
+            match value.parse(&mut AutoScaleParser(self.0)) {
+                Ok(v) => auto_scale = v,
+                Err(e) => log::warn!(
+                    "Could not parse the auto-scale setting: {}",
+                    self.0.error(e)
+                ),
+            }
+        }
         let mut connectors = vec![];
         if let Some(value) = connectors_val {
             match value.parse(&mut ConnectorsParser(self.0)) {
@@ -599,6 +673,45 @@ impl Parser for ConfigParser<'_, '_, '_> {
                 }
             }
         }
+        let mut keyboard_accessibility = None;
+        if let Some(value) = keyboard_accessibility_val {
+            match value.parse(&mut KeyboardAccessibilityParser(self.0)) {
+                Ok(v) => keyboard_accessibility = Some(v),
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse keyboard accessibility settings: {}",
+                        self.0.error(e)
+                    );
+                }
+            }
+        }
+        let mut mouse_keys = None;
+        if let Some(value) = mouse_keys_val {
+            match value.parse(&mut MouseKeysParser(self.0)) {
+                Ok(v) => mouse_keys = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse mouse keys settings: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut which_key = None;
+        if let Some(value) = which_key_val {
+            match value.parse(&mut WhichKeyParser(self.0)) {
+                Ok(v) => which_key = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse which-key settings: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut cursor = None;
+        if let Some(value) = cursor_val {
+            match value.parse(&mut CursorParser(self.0)) {
+                Ok(v) => cursor = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse cursor settings: {}", self.0.error(e));
+                }
+            }
+        }
         let mut fallback_output_mode = None;
         if let Some(value) = fallback_output_mode_val {
             match value.parse(&mut FallbackOutputModeParser) {
@@ -611,6 +724,39 @@ impl Parser for ConfigParser<'_, '_, '_> {
                 }
             }
         }
+        let mut layout_memory = None;
+        if let Some(value) = layout_memory_val {
+            match value.parse(&mut LayoutMemoryParser) {
+                Ok(v) => layout_memory = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse the layout memory: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut shortcuts_inhibit_escape = None;
+        if let Some(value) = shortcuts_inhibit_escape_val {
+            match value.parse(&mut ModifiedKeysymParser) {
+                Ok(v) => shortcuts_inhibit_escape = Some(v),
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse the shortcuts inhibit escape key: {}",
+                        self.0.error(e)
+                    );
+                }
+            }
+        }
+        let mut input_capture_release = None;
+        if let Some(value) = input_capture_release_val {
+            match value.parse(&mut ModifiedKeysymParser) {
+                Ok(v) => input_capture_release = Some(v),
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse the input capture release key: {}",
+                        self.0.error(e)
+                    );
+                }
+            }
+        }
         if let Some(value) = workspaces_val {
             if let Err(e) = value.parse(&mut WorkspacesParser(self.0)) {
                 log::warn!("Could not parse the workspaces: {}", self.0.error(e),);
@@ -669,6 +815,10 @@ impl Parser for ConfigParser<'_, '_, '_> {
             on_idle,
             status,
             outputs,
+            output_profiles,
+            auto_scale,
+            show_backlight_indicator: show_backlight_indicator.despan().unwrap_or(true),
+            auto_rotation: auto_rotation.despan().unwrap_or(false),
             connectors,
             workspace_capture: workspace_capture.despan().unwrap_or(true),
             env,
@@ -711,13 +861,23 @@ impl Parser for ConfigParser<'_, '_, '_> {
             input_modes,
             workspace_display_order,
             simple_im,
+            keyboard_accessibility,
+            mouse_keys,
+            which_key,
+            cursor,
             fallback_output_mode,
+            layout_memory,
+            shortcuts_inhibit_escape,
+            input_capture_release,
             mouse_follows_focus: mouse_follows_focus.despan(),
             transactions,
             cursor_size: cursor_size.despan(),
+            cursor_theme: cursor_theme.despan_into(),
             configure_all_devices,
             triggers,
             max_trigger_depth,
+            bar,
+            gestures,
         })
     }
 }
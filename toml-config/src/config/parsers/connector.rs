@@ -4,6 +4,8 @@ use crate::config::extractor::Extractor;
 use crate::config::extractor::ExtractorError;
 use crate::config::extractor::bol;
 use crate::config::extractor::opt;
+use crate::config::extractor::recover;
+use crate::config::extractor::str;
 use crate::config::extractor::val;
 use crate::config::parser::DataType;
 use crate::config::parser::ParseResult;
@@ -16,6 +18,8 @@ use crate::toml::toml_span::Span;
 use crate::toml::toml_span::Spanned;
 use crate::toml::toml_value::Value;
 use indexmap::IndexMap;
+use jay_config::config_dir;
+use std::path::PathBuf;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -44,13 +48,26 @@ impl Parser for ConnectorParser<'_, '_, '_> {
         let (
             match_val, //
             enabled,
+            edid_override,
+            vnc,
         ) = ext.extract((
             val("match"), //
             opt(bol("enabled")),
+            recover(opt(str("edid-override"))),
+            recover(opt(str("vnc"))),
         ))?;
         Ok(ConfigConnector {
             match_: match_val.parse_map(&mut ConnectorMatchParser(self.0))?,
             enabled: enabled.despan().unwrap_or(true),
+            edid_override: edid_override.despan().map(|path| match path {
+                "" => String::new(),
+                _ => {
+                    let mut root = PathBuf::from(config_dir());
+                    root.push(path);
+                    root.display().to_string()
+                }
+            }),
+            vnc: vnc.despan_into(),
         })
     }
 }
@@ -0,0 +1,50 @@
+use crate::config::Cursor;
+use crate::config::context::Context;
+use crate::config::extractor::Extractor;
+use crate::config::extractor::ExtractorError;
+use crate::config::extractor::bol;
+use crate::config::extractor::n64;
+use crate::config::extractor::opt;
+use crate::config::extractor::recover;
+use crate::config::parser::DataType;
+use crate::config::parser::ParseResult;
+use crate::config::parser::Parser;
+use crate::config::parser::UnexpectedDataType;
+use crate::toml::toml_span::DespanExt;
+use crate::toml::toml_span::Span;
+use crate::toml::toml_span::Spanned;
+use crate::toml::toml_value::Value;
+use indexmap::IndexMap;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CursorParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct CursorParser<'a, 'b, 'c>(pub &'a Context<'b, 'c>);
+
+impl Parser for CursorParser<'_, '_, '_> {
+    type Value = Cursor;
+    type Error = CursorParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (hide_while_typing, hide_after_ms) = ext.extract((
+            recover(opt(bol("hide-while-typing"))),
+            recover(opt(n64("hide-after-ms"))),
+        ))?;
+        Ok(Cursor {
+            hide_while_typing: hide_while_typing.despan(),
+            hide_after_ms: hide_after_ms.despan(),
+        })
+    }
+}
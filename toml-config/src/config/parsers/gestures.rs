@@ -0,0 +1,262 @@
+use crate::config::Action;
+use crate::config::context::Context;
+use crate::config::extractor::Extractor;
+use crate::config::extractor::ExtractorError;
+use crate::config::extractor::bol;
+use crate::config::extractor::fltorint;
+use crate::config::extractor::opt;
+use crate::config::extractor::recover;
+use crate::config::extractor::val;
+use crate::config::parser::DataType;
+use crate::config::parser::ParseResult;
+use crate::config::parser::Parser;
+use crate::config::parser::UnexpectedDataType;
+use crate::config::parsers::action::ActionParser;
+use crate::toml::toml_span::DespanExt;
+use crate::toml::toml_span::Span;
+use crate::toml::toml_span::Spanned;
+use crate::toml::toml_span::SpannedExt;
+use crate::toml::toml_value::Value;
+use indexmap::IndexMap;
+use jay_config::input::gesture::Gesture;
+use jay_config::input::gesture::GestureKind;
+use jay_config::input::gesture::TouchGesture;
+use jay_config::input::gesture::TouchGestureKind;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum GesturesParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("Unknown gesture {0}")]
+    UnknownGesture(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Gestures {
+    pub swipe_distance: Option<f64>,
+    pub bindings: Vec<GestureBinding>,
+    pub touch_bindings: Vec<TouchGestureBinding>,
+    pub touch_long_press_drag: Option<bool>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GestureBinding {
+    pub gesture: Gesture,
+    pub continuous: bool,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone)]
+pub struct TouchGestureBinding {
+    pub gesture: TouchGesture,
+    pub action: Action,
+}
+
+pub struct GesturesParser<'a, 'b, 'c>(pub &'a Context<'b, 'c>);
+
+impl Parser for GesturesParser<'_, '_, '_> {
+    type Value = Gestures;
+    type Error = GesturesParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (
+            swipe_distance, //
+            bindings_val,
+            continuous_bindings_val,
+            touch_bindings_val,
+            touch_long_press_drag,
+        ) = ext.extract((
+            recover(opt(fltorint("swipe-distance"))),
+            opt(val("bindings")),
+            opt(val("continuous-bindings")),
+            opt(val("touch-bindings")),
+            recover(opt(bol("touch-long-press-drag"))),
+        ))?;
+        let mut bindings = vec![];
+        for (value, continuous) in [(bindings_val, false), (continuous_bindings_val, true)] {
+            let Some(value) = value else {
+                continue;
+            };
+            let res = value.parse(&mut GestureBindingsParser {
+                cx: self.0,
+                continuous,
+                bindings: &mut bindings,
+            });
+            if let Err(e) = res {
+                log::warn!("Could not parse gesture bindings: {}", self.0.error(e));
+            }
+        }
+        let mut touch_bindings = vec![];
+        if let Some(value) = touch_bindings_val {
+            let res = value.parse(&mut TouchGestureBindingsParser {
+                cx: self.0,
+                bindings: &mut touch_bindings,
+            });
+            if let Err(e) = res {
+                log::warn!(
+                    "Could not parse touch gesture bindings: {}",
+                    self.0.error(e)
+                );
+            }
+        }
+        let mut swipe_distance = swipe_distance.despan();
+        if let Some(d) = swipe_distance
+            && d <= 0.0
+        {
+            log::warn!("Swipe distance must be positive");
+            swipe_distance = None;
+        }
+        Ok(Gestures {
+            swipe_distance,
+            bindings,
+            touch_bindings,
+            touch_long_press_drag: touch_long_press_drag.despan(),
+        })
+    }
+}
+
+struct GestureBindingsParser<'a, 'b, 'c, 'd> {
+    cx: &'a Context<'b, 'c>,
+    continuous: bool,
+    bindings: &'d mut Vec<GestureBinding>,
+}
+
+impl Parser for GestureBindingsParser<'_, '_, '_, '_> {
+    type Value = ();
+    type Error = GesturesParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        _span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        for (key, value) in table.iter() {
+            let gesture = match parse_gesture(key.span, &key.value) {
+                Ok(g) => g,
+                Err(e) => {
+                    log::warn!("Could not parse gesture: {}", self.cx.error(e));
+                    continue;
+                }
+            };
+            let action = match value.parse(&mut ActionParser(self.cx)) {
+                Ok(a) => a,
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse action for gesture {}: {}",
+                        key.value,
+                        self.cx.error(e)
+                    );
+                    continue;
+                }
+            };
+            self.bindings.push(GestureBinding {
+                gesture,
+                continuous: self.continuous,
+                action,
+            });
+        }
+        Ok(())
+    }
+}
+
+struct TouchGestureBindingsParser<'a, 'b, 'c, 'd> {
+    cx: &'a Context<'b, 'c>,
+    bindings: &'d mut Vec<TouchGestureBinding>,
+}
+
+impl Parser for TouchGestureBindingsParser<'_, '_, '_, '_> {
+    type Value = ();
+    type Error = GesturesParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        _span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        for (key, value) in table.iter() {
+            let gesture = match parse_touch_gesture(key.span, &key.value) {
+                Ok(g) => g,
+                Err(e) => {
+                    log::warn!("Could not parse touch gesture: {}", self.cx.error(e));
+                    continue;
+                }
+            };
+            let action = match value.parse(&mut ActionParser(self.cx)) {
+                Ok(a) => a,
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse action for touch gesture {}: {}",
+                        key.value,
+                        self.cx.error(e)
+                    );
+                    continue;
+                }
+            };
+            self.bindings.push(TouchGestureBinding { gesture, action });
+        }
+        Ok(())
+    }
+}
+
+fn parse_touch_gesture(
+    span: Span,
+    name: &str,
+) -> Result<TouchGesture, Spanned<GesturesParserError>> {
+    let unknown = || GesturesParserError::UnknownGesture(name.to_string()).spanned(span);
+    let mut parts = name.split("-");
+    let ty = parts.next().unwrap_or_default();
+    let Some(Ok(fingers)) = parts.next().map(|f| f.parse::<u32>()) else {
+        return Err(unknown());
+    };
+    let kind = match (ty, parts.next()) {
+        ("edge", Some("left")) => TouchGestureKind::FromLeftEdge,
+        ("edge", Some("right")) => TouchGestureKind::FromRightEdge,
+        ("edge", Some("top")) => TouchGestureKind::FromTopEdge,
+        ("edge", Some("bottom")) => TouchGestureKind::FromBottomEdge,
+        ("swipe", Some("left")) => TouchGestureKind::SwipeLeft,
+        ("swipe", Some("right")) => TouchGestureKind::SwipeRight,
+        ("swipe", Some("up")) => TouchGestureKind::SwipeUp,
+        ("swipe", Some("down")) => TouchGestureKind::SwipeDown,
+        ("pinch", Some("in")) => TouchGestureKind::PinchIn,
+        ("pinch", Some("out")) => TouchGestureKind::PinchOut,
+        _ => return Err(unknown()),
+    };
+    if parts.next().is_some() {
+        return Err(unknown());
+    }
+    Ok(TouchGesture::new(kind, fingers))
+}
+
+fn parse_gesture(span: Span, name: &str) -> Result<Gesture, Spanned<GesturesParserError>> {
+    let unknown = || GesturesParserError::UnknownGesture(name.to_string()).spanned(span);
+    let mut parts = name.split("-");
+    let ty = parts.next().unwrap_or_default();
+    let Some(Ok(fingers)) = parts.next().map(|f| f.parse::<u32>()) else {
+        return Err(unknown());
+    };
+    let kind = match (ty, parts.next()) {
+        ("swipe", Some("left")) => GestureKind::SwipeLeft,
+        ("swipe", Some("right")) => GestureKind::SwipeRight,
+        ("swipe", Some("up")) => GestureKind::SwipeUp,
+        ("swipe", Some("down")) => GestureKind::SwipeDown,
+        ("pinch", Some("in")) => GestureKind::PinchIn,
+        ("pinch", Some("out")) => GestureKind::PinchOut,
+        ("hold", None) => GestureKind::Hold,
+        _ => return Err(unknown()),
+    };
+    if parts.next().is_some() {
+        return Err(unknown());
+    }
+    Ok(Gesture::new(kind, fingers))
+}
@@ -1,9 +1,12 @@
+use crate::config::CustomAccel;
 use crate::config::Input;
 use crate::config::context::Context;
 use crate::config::extractor::Extractor;
 use crate::config::extractor::ExtractorError;
+use crate::config::extractor::arr;
 use crate::config::extractor::bol;
 use crate::config::extractor::fltorint;
+use crate::config::extractor::n32;
 use crate::config::extractor::opt;
 use crate::config::extractor::recover;
 use crate::config::extractor::str;
@@ -15,8 +18,10 @@ use crate::config::parser::UnexpectedDataType;
 use crate::config::parsers::action::ActionParser;
 use crate::config::parsers::input_match::InputMatchParser;
 use crate::config::parsers::input_match::InputMatchParserError;
+use crate::config::parsers::key_remap::KeyRemapParser;
 use crate::config::parsers::keymap::KeymapParser;
 use crate::config::parsers::output_match::OutputMatchParser;
+use crate::config::parsers::tablet::TabletParser;
 use crate::input_event_code_from_name;
 use crate::toml::toml_span::DespanExt;
 use crate::toml::toml_span::Span;
@@ -28,6 +33,7 @@ use indexmap::IndexMap;
 use jay_config::input::InputEventCode;
 use jay_config::input::SwitchEvent;
 use jay_config::input::acceleration::ACCEL_PROFILE_ADAPTIVE;
+use jay_config::input::acceleration::ACCEL_PROFILE_CUSTOM;
 use jay_config::input::acceleration::ACCEL_PROFILE_FLAT;
 use jay_config::input::clickmethod::CLICK_METHOD_BUTTON_AREAS;
 use jay_config::input::clickmethod::CLICK_METHOD_CLICKFINGER;
@@ -36,6 +42,11 @@ use jay_config::input::scrollmethod::SCROLL_METHOD_EDGE;
 use jay_config::input::scrollmethod::SCROLL_METHOD_NO_SCROLL;
 use jay_config::input::scrollmethod::SCROLL_METHOD_ON_BUTTON_DOWN;
 use jay_config::input::scrollmethod::SCROLL_METHOD_TWO_FINGERS;
+use jay_config::input::sendevents::SEND_EVENTS_DISABLED;
+use jay_config::input::sendevents::SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE;
+use jay_config::input::sendevents::SEND_EVENTS_ENABLED;
+use jay_config::input::tapbuttonmap::TAP_BUTTON_MAP_LEFT_MIDDLE_RIGHT;
+use jay_config::input::tapbuttonmap::TAP_BUTTON_MAP_LEFT_RIGHT_MIDDLE;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -58,6 +69,10 @@ pub enum InputParserError {
     CaliThreeColumns,
     #[error("Calibration matrix entries must be floats")]
     CaliFloat,
+    #[error("Custom acceleration points must be floats")]
+    AccelFloat,
+    #[error("Custom acceleration curves must have between 2 and 64 points")]
+    AccelPointCount,
 }
 
 pub struct InputParser<'a, 'b, 'c> {
@@ -108,7 +123,12 @@ impl Parser for InputParser<'_, '_, '_> {
                 scroll_button,
                 scroll_button_lock,
                 detached,
+                disable_while_typing,
+                disable_while_trackpointing,
+                tap_button_map,
+                send_events,
             ),
+            (rotation, custom_accel, key_remap, tablet_val),
         ) = ext.extract((
             (
                 opt(str("tag")),
@@ -141,6 +161,16 @@ impl Parser for InputParser<'_, '_, '_> {
                 recover(opt(str("scroll-button"))),
                 recover(opt(bol("scroll-button-lock"))),
                 recover(opt(bol("detached"))),
+                recover(opt(bol("disable-while-typing"))),
+                recover(opt(bol("disable-while-trackpointing"))),
+                recover(opt(str("tap-button-map"))),
+                recover(opt(str("send-events"))),
+            ),
+            (
+                recover(opt(n32("rotation"))),
+                opt(val("custom-accel")),
+                opt(val("remap")),
+                opt(val("tablet")),
             ),
         ))?;
         let accel_profile = match accel_profile {
@@ -148,6 +178,7 @@ impl Parser for InputParser<'_, '_, '_> {
             Some(p) => match p.value.to_ascii_lowercase().as_str() {
                 "flat" => Some(ACCEL_PROFILE_FLAT),
                 "adaptive" => Some(ACCEL_PROFILE_ADAPTIVE),
+                "custom" => Some(ACCEL_PROFILE_CUSTOM),
                 v => {
                     log::warn!("Unknown accel-profile {v}: {}", self.cx.error3(p.span));
                     None
@@ -302,6 +333,75 @@ impl Parser for InputParser<'_, '_, '_> {
                 Some(v) => Some(InputEventCode(v)),
             },
         };
+        let tap_button_map = match tap_button_map {
+            None => None,
+            Some(p) => match p.value.to_ascii_lowercase().as_str() {
+                "left-right-middle" => Some(TAP_BUTTON_MAP_LEFT_RIGHT_MIDDLE),
+                "left-middle-right" => Some(TAP_BUTTON_MAP_LEFT_MIDDLE_RIGHT),
+                v => {
+                    log::warn!("Unknown tap-button-map {v}: {}", self.cx.error3(p.span));
+                    None
+                }
+            },
+        };
+        let send_events = match send_events {
+            None => None,
+            Some(p) => match p.value.to_ascii_lowercase().as_str() {
+                "enabled" => Some(SEND_EVENTS_ENABLED),
+                "disabled" => Some(SEND_EVENTS_DISABLED),
+                "disabled-on-external-mouse" => Some(SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE),
+                v => {
+                    log::warn!("Unknown send-events mode {v}: {}", self.cx.error3(p.span));
+                    None
+                }
+            },
+        };
+        let rotation = match rotation {
+            Some(r) if r.value >= 360 => {
+                log::warn!(
+                    "Rotation must be less than 360 degrees: {}",
+                    self.cx.error3(r.span)
+                );
+                None
+            }
+            r => r.despan(),
+        };
+        let custom_accel = match custom_accel {
+            None => None,
+            Some(v) => match v.parse(&mut CustomAccelParser(self.cx)) {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse custom acceleration curve: {}",
+                        self.cx.error(e)
+                    );
+                    None
+                }
+            },
+        };
+        let key_remap = match key_remap {
+            None => None,
+            Some(v) => match v.parse(&mut KeyRemapParser(self.cx)) {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse key remapping: {}", self.cx.error(e));
+                    None
+                }
+            },
+        };
+        let tablet = match tablet_val {
+            None => None,
+            Some(v) => match v.parse(&mut TabletParser {
+                cx: self.cx,
+                is_inputs_array: self.is_inputs_array,
+            }) {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse tablet settings: {}", self.cx.error(e));
+                    None
+                }
+            },
+        };
         Ok(Input {
             tag: tag.despan_into(),
             match_: match_val.parse_map(&mut InputMatchParser(self.cx))?,
@@ -325,6 +425,14 @@ impl Parser for InputParser<'_, '_, '_> {
             scroll_button,
             scroll_button_lock: scroll_button_lock.despan(),
             detached: detached.despan(),
+            disable_while_typing: disable_while_typing.despan(),
+            disable_while_trackpointing: disable_while_trackpointing.despan(),
+            tap_button_map,
+            send_events,
+            rotation,
+            custom_accel,
+            key_remap,
+            tablet,
         })
     }
 }
@@ -449,3 +557,36 @@ impl Parser for CalibrationMatrixRowParser {
         ])
     }
 }
+
+struct CustomAccelParser<'a, 'b, 'c>(&'a Context<'b, 'c>);
+
+impl Parser for CustomAccelParser<'_, '_, '_> {
+    type Value = CustomAccel;
+    type Error = InputParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (step, points_val) = ext.extract((fltorint("step"), arr("points")))?;
+        if !(2..=64).contains(&points_val.value.len()) {
+            return Err(InputParserError::AccelPointCount.spanned(points_val.span));
+        }
+        let mut points = vec![];
+        for v in points_val.value {
+            let p = match v.value {
+                Value::Float(f) => f,
+                Value::Integer(f) => f as _,
+                _ => return Err(InputParserError::AccelFloat.spanned(v.span)),
+            };
+            points.push(p);
+        }
+        Ok(CustomAccel {
+            step: step.value,
+            points,
+        })
+    }
+}
@@ -2,6 +2,7 @@ use crate::config::Shortcut;
 use crate::config::context::Context;
 use crate::config::extractor::Extractor;
 use crate::config::extractor::ExtractorError;
+use crate::config::extractor::bol;
 use crate::config::extractor::opt;
 use crate::config::extractor::recover;
 use crate::config::extractor::str;
@@ -38,6 +39,7 @@ pub enum InputModeParserError {
 pub struct InputMode {
     pub parent: Option<String>,
     pub shortcuts: Vec<Shortcut>,
+    pub mouse_keys: Option<bool>,
 }
 
 pub struct InputModesParser<'a, 'b, 'c>(pub &'a Context<'b, 'c>);
@@ -90,10 +92,12 @@ impl Parser for InputModeParser<'_, '_, '_> {
             parent, //
             shortcuts_val,
             complex_shortcuts_val,
+            mouse_keys,
         ) = ext.extract((
             recover(opt(str("parent"))),
             opt(val("shortcuts")),
             opt(val("complex-shortcuts")),
+            recover(opt(bol("mouse-keys"))),
         ))?;
         let mut used_keys = HashSet::new();
         let mut shortcuts = vec![];
@@ -118,6 +122,7 @@ impl Parser for InputModeParser<'_, '_, '_> {
         Ok(InputMode {
             parent: parent.despan_into(),
             shortcuts,
+            mouse_keys: mouse_keys.despan(),
         })
     }
 }
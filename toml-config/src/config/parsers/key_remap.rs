@@ -0,0 +1,201 @@
+use crate::config::context::Context;
+use crate::config::extractor::Extractor;
+use crate::config::extractor::ExtractorError;
+use crate::config::extractor::n32;
+use crate::config::extractor::opt;
+use crate::config::extractor::str;
+use crate::config::extractor::tbl;
+use crate::config::keycodes::keycode_from_name;
+use crate::config::parser::DataType;
+use crate::config::parser::ParseResult;
+use crate::config::parser::Parser;
+use crate::config::parser::UnexpectedDataType;
+use crate::toml::toml_span::Span;
+use crate::toml::toml_span::Spanned;
+use crate::toml::toml_span::SpannedExt;
+use crate::toml::toml_value::Value;
+use ahash::AHashMap;
+use indexmap::IndexMap;
+use jay_config::input::key_remap::DEFAULT_TAP_HOLD_TIMEOUT_MS;
+use jay_config::input::key_remap::HoldAction;
+use jay_config::input::key_remap::KeyRemap;
+use jay_config::input::key_remap::RemapAction;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum KeyRemapParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("Unknown key {0}")]
+    UnknownKey(String),
+    #[error("Unknown layer {0}")]
+    UnknownLayer(String),
+    #[error("Exactly one of `tap`, `one-shot`, and `layer` must be set")]
+    ExactlyOneAction,
+    #[error("`tap` requires exactly one of `hold` and `hold-layer`")]
+    ExactlyOneHold,
+    #[error("`{0}` can only be used together with `tap`")]
+    RequiresTap(&'static str),
+}
+
+pub struct KeyRemapParser<'a, 'b, 'c>(pub &'a Context<'b, 'c>);
+
+impl Parser for KeyRemapParser<'_, '_, '_> {
+    type Value = KeyRemap;
+    type Error = KeyRemapParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (keys, layers_val) = ext.extract((opt(tbl("keys")), opt(tbl("layers"))))?;
+        let mut names = AHashMap::new();
+        if let Some(layers) = layers_val {
+            for (idx, name) in layers.value.keys().enumerate() {
+                names.insert(name.value.as_str(), idx as u32);
+            }
+        }
+        let mut parser = RemapKeysParser {
+            cx: self.0,
+            layers: &names,
+        };
+        let mut remap = KeyRemap::default();
+        if let Some(keys) = keys {
+            remap.keys = parser.parse_table(keys.span, keys.value)?;
+        }
+        if let Some(layers) = layers_val {
+            for layer in layers.value.values() {
+                remap.layers.push(layer.parse(&mut parser)?);
+            }
+        }
+        Ok(remap)
+    }
+}
+
+struct RemapKeysParser<'a, 'b, 'c, 'd> {
+    cx: &'a Context<'b, 'c>,
+    layers: &'a AHashMap<&'d str, u32>,
+}
+
+impl Parser for RemapKeysParser<'_, '_, '_, '_> {
+    type Value = Vec<(u32, RemapAction)>;
+    type Error = KeyRemapParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        _span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut res = vec![];
+        for (key, value) in table {
+            let Some(code) = keycode_from_name(&key.value) else {
+                log::warn!(
+                    "Ignoring unknown key {}: {}",
+                    key.value,
+                    self.cx.error3(key.span)
+                );
+                continue;
+            };
+            let mut parser = RemapActionParser {
+                cx: self.cx,
+                layers: self.layers,
+            };
+            match value.parse(&mut parser) {
+                Ok(action) => res.push((code, action)),
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse remap action of key {}: {}",
+                        key.value,
+                        self.cx.error(e)
+                    );
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+struct RemapActionParser<'a, 'b, 'c, 'd> {
+    cx: &'a Context<'b, 'c>,
+    layers: &'a AHashMap<&'d str, u32>,
+}
+
+impl RemapActionParser<'_, '_, '_, '_> {
+    fn key(&self, name: Spanned<&str>) -> Result<u32, Spanned<KeyRemapParserError>> {
+        match keycode_from_name(name.value) {
+            Some(c) => Ok(c),
+            _ => Err(name.map(|s| KeyRemapParserError::UnknownKey(s.to_string()))),
+        }
+    }
+
+    fn layer(&self, name: Spanned<&str>) -> Result<u32, Spanned<KeyRemapParserError>> {
+        match self.layers.get(name.value) {
+            Some(l) => Ok(*l),
+            _ => Err(name.map(|s| KeyRemapParserError::UnknownLayer(s.to_string()))),
+        }
+    }
+}
+
+impl Parser for RemapActionParser<'_, '_, '_, '_> {
+    type Value = RemapAction;
+    type Error = KeyRemapParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String, DataType::Table];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        Ok(RemapAction::Key(self.key(string.spanned(span))?))
+    }
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.cx, span, table);
+        let (tap, hold, hold_layer, timeout, one_shot, layer) = ext.extract((
+            opt(str("tap")),
+            opt(str("hold")),
+            opt(str("hold-layer")),
+            opt(n32("timeout")),
+            opt(str("one-shot")),
+            opt(str("layer")),
+        ))?;
+        if tap.is_none() {
+            let extra = [
+                ("hold", hold.map(|v| v.span)),
+                ("hold-layer", hold_layer.map(|v| v.span)),
+                ("timeout", timeout.map(|v| v.span)),
+            ];
+            for (name, span) in extra {
+                if let Some(span) = span {
+                    return Err(KeyRemapParserError::RequiresTap(name).spanned(span));
+                }
+            }
+        }
+        let action = match (tap, one_shot, layer) {
+            (Some(tap), None, None) => {
+                let hold = match (hold, hold_layer) {
+                    (Some(hold), None) => HoldAction::Key(self.key(hold)?),
+                    (None, Some(layer)) => HoldAction::Layer(self.layer(layer)?),
+                    _ => return Err(KeyRemapParserError::ExactlyOneHold.spanned(span)),
+                };
+                RemapAction::TapHold {
+                    tap: self.key(tap)?,
+                    hold,
+                    timeout_ms: timeout
+                        .map(|t| t.value)
+                        .unwrap_or(DEFAULT_TAP_HOLD_TIMEOUT_MS),
+                }
+            }
+            (None, Some(key), None) => RemapAction::OneShot(self.key(key)?),
+            (None, None, Some(layer)) => RemapAction::Layer(self.layer(layer)?),
+            _ => return Err(KeyRemapParserError::ExactlyOneAction.spanned(span)),
+        };
+        Ok(action)
+    }
+}
@@ -0,0 +1,93 @@
+use crate::config::KeyboardAccessibility;
+use crate::config::context::Context;
+use crate::config::extractor::Extractor;
+use crate::config::extractor::ExtractorError;
+use crate::config::extractor::bol;
+use crate::config::extractor::n32;
+use crate::config::extractor::opt;
+use crate::config::extractor::recover;
+use crate::config::extractor::tbl;
+use crate::config::parser::DataType;
+use crate::config::parser::ParseResult;
+use crate::config::parser::Parser;
+use crate::config::parser::UnexpectedDataType;
+use crate::config::parsers::action::ActionParser;
+use crate::toml::toml_span::DespanExt;
+use crate::toml::toml_span::Span;
+use crate::toml::toml_span::Spanned;
+use crate::toml::toml_value::Value;
+use ahash::AHashMap;
+use indexmap::IndexMap;
+use jay_config::input::AccessibilityFeedback;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum KeyboardAccessibilityParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct KeyboardAccessibilityParser<'a, 'b, 'c>(pub &'a Context<'b, 'c>);
+
+impl Parser for KeyboardAccessibilityParser<'_, '_, '_> {
+    type Value = KeyboardAccessibility;
+    type Error = KeyboardAccessibilityParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (sticky_keys, slow_keys, slow_keys_delay, bounce_keys, bounce_keys_delay, on_feedback) =
+            ext.extract((
+                recover(opt(bol("sticky-keys"))),
+                recover(opt(bol("slow-keys"))),
+                recover(opt(n32("slow-keys-delay"))),
+                recover(opt(bol("bounce-keys"))),
+                recover(opt(n32("bounce-keys-delay"))),
+                recover(opt(tbl("on-feedback"))),
+            ))?;
+        let mut feedback_actions = AHashMap::new();
+        if let Some(on_feedback) = on_feedback {
+            for (key, value) in on_feedback.value {
+                let feedback = match key.value.as_str() {
+                    "modifier-latched" => AccessibilityFeedback::ModifierLatched,
+                    "modifier-locked" => AccessibilityFeedback::ModifierLocked,
+                    "modifier-released" => AccessibilityFeedback::ModifierReleased,
+                    "slow-key-pressed" => AccessibilityFeedback::SlowKeyPressed,
+                    "slow-key-accepted" => AccessibilityFeedback::SlowKeyAccepted,
+                    "slow-key-rejected" => AccessibilityFeedback::SlowKeyRejected,
+                    "bounce-key-rejected" => AccessibilityFeedback::BounceKeyRejected,
+                    _ => {
+                        log::warn!(
+                            "Ignoring unknown feedback {}: {}",
+                            key.value,
+                            self.0.error3(key.span)
+                        );
+                        continue;
+                    }
+                };
+                match value.parse(&mut ActionParser(self.0)) {
+                    Ok(a) => {
+                        feedback_actions.insert(feedback, a);
+                    }
+                    Err(e) => {
+                        log::warn!("Could not parse {} action: {}", key.value, self.0.error(e));
+                    }
+                }
+            }
+        }
+        Ok(KeyboardAccessibility {
+            sticky_keys: sticky_keys.despan(),
+            slow_keys: slow_keys.despan(),
+            slow_keys_delay: slow_keys_delay.despan(),
+            bounce_keys: bounce_keys.despan(),
+            bounce_keys_delay: bounce_keys_delay.despan(),
+            feedback_actions,
+        })
+    }
+}
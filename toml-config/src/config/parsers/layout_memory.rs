@@ -0,0 +1,35 @@
+use crate::config::parser::DataType;
+use crate::config::parser::ParseResult;
+use crate::config::parser::Parser;
+use crate::config::parser::UnexpectedDataType;
+use crate::toml::toml_span::Span;
+use crate::toml::toml_span::SpannedExt;
+use jay_config::input::LayoutMemoryMode;
+use thiserror::Error;
+
+pub struct LayoutMemoryParser;
+
+#[derive(Debug, Error)]
+pub enum LayoutMemoryParserError {
+    #[error(transparent)]
+    DataType(#[from] UnexpectedDataType),
+    #[error("Unknown mode {0}")]
+    Unknown(String),
+}
+
+impl Parser for LayoutMemoryParser {
+    type Value = LayoutMemoryMode;
+    type Error = LayoutMemoryParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        use LayoutMemoryMode::*;
+        let mode = match string.to_ascii_lowercase().as_str() {
+            "global" => Global,
+            "window" => Window,
+            "app-id" => AppId,
+            _ => return Err(LayoutMemoryParserError::Unknown(string.to_string()).spanned(span)),
+        };
+        Ok(mode)
+    }
+}
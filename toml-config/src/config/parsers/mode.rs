@@ -1,10 +1,12 @@
 use crate::config::Mode;
+use crate::config::ModeTimings;
 use crate::config::context::Context;
 use crate::config::extractor::Extractor;
 use crate::config::extractor::ExtractorError;
 use crate::config::extractor::fltorint;
 use crate::config::extractor::opt;
 use crate::config::extractor::s32;
+use crate::config::extractor::str;
 use crate::config::parser::DataType;
 use crate::config::parser::ParseResult;
 use crate::config::parser::Parser;
@@ -12,6 +14,7 @@ use crate::config::parser::UnexpectedDataType;
 use crate::toml::toml_span::DespanExt;
 use crate::toml::toml_span::Span;
 use crate::toml::toml_span::Spanned;
+use crate::toml::toml_span::SpannedExt;
 use crate::toml::toml_value::Value;
 use indexmap::IndexMap;
 use thiserror::Error;
@@ -22,6 +25,12 @@ pub enum ModeParserError {
     Expected(#[from] UnexpectedDataType),
     #[error(transparent)]
     Extract(#[from] ExtractorError),
+    #[error("Unknown timings `{0}`")]
+    UnknownTimings(String),
+    #[error("CVT timings require a refresh rate")]
+    MissingRefreshRate,
+    #[error("A modeline cannot be combined with other fields")]
+    ModelineWithOtherFields,
 }
 
 pub struct ModeParser<'a, 'b, 'c>(pub &'a Context<'b, 'c>);
@@ -41,15 +50,58 @@ impl Parser for ModeParser<'_, '_, '_> {
             width, //
             height,
             refresh_rate,
+            timings,
+            modeline,
         ) = ext.extract((
-            s32("width"), //
-            s32("height"),
+            opt(s32("width")), //
+            opt(s32("height")),
             opt(fltorint("refresh-rate")),
+            opt(str("timings")),
+            opt(str("modeline")),
         ))?;
+        if let Some(modeline) = modeline {
+            if width.is_some() || height.is_some() || refresh_rate.is_some() || timings.is_some() {
+                return Err(ModeParserError::ModelineWithOtherFields.spanned(span));
+            }
+            return Ok(Mode {
+                width: 0,
+                height: 0,
+                refresh_rate: None,
+                timings: Some(ModeTimings::Modeline(modeline.value.to_string())),
+            });
+        }
+        let Some(width) = width else {
+            return Err(
+                ModeParserError::Extract(ExtractorError::MissingField("width")).spanned(span),
+            );
+        };
+        let Some(height) = height else {
+            return Err(
+                ModeParserError::Extract(ExtractorError::MissingField("height")).spanned(span),
+            );
+        };
+        let timings = match timings {
+            None => None,
+            Some(timings) => {
+                let reduced_blanking = match timings.value {
+                    "cvt" => false,
+                    "cvt-rb" => true,
+                    _ => {
+                        return Err(ModeParserError::UnknownTimings(timings.value.to_string())
+                            .spanned(timings.span));
+                    }
+                };
+                if refresh_rate.is_none() {
+                    return Err(ModeParserError::MissingRefreshRate.spanned(span));
+                }
+                Some(ModeTimings::Cvt { reduced_blanking })
+            }
+        };
         Ok(Mode {
             width: width.value,
             height: height.value,
             refresh_rate: refresh_rate.despan(),
+            timings,
         })
     }
 }
@@ -1,9 +1,14 @@
+use crate::config::ShortcutTrigger;
 use crate::config::parser::DataType;
 use crate::config::parser::ParseResult;
 use crate::config::parser::Parser;
 use crate::config::parser::UnexpectedDataType;
+use crate::input_event_code_from_name;
 use crate::toml::toml_span::Span;
+use crate::toml::toml_span::Spanned;
 use crate::toml::toml_span::SpannedExt;
+use jay_config::input::InputEventCode;
+use jay_config::input::PointerTrigger;
 use jay_config::keyboard::ModifiedKeySym;
 use jay_config::keyboard::mods::ALT;
 use jay_config::keyboard::mods::CAPS;
@@ -45,29 +50,86 @@ impl Parser for ModifiedKeysymParser {
     const EXPECTED: &'static [DataType] = &[DataType::String];
 
     fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
-        let mut modifiers = Modifiers(0);
-        let mut sym = None;
-        for part in string.split("-") {
-            let modifier = match parse_mod(part) {
-                Some(m) => m,
-                _ => match Keysym::from_str(part) {
-                    Some(new) if sym.is_none() => {
-                        sym = Some(KeySym(new.0));
-                        continue;
-                    }
-                    Some(_) => return Err(ModifiedKeysymParserError::MoreThanOneSym.spanned(span)),
-                    _ => {
-                        return Err(ModifiedKeysymParserError::UnknownKeysym(part.to_string())
-                            .spanned(span));
-                    }
-                },
-            };
-            modifiers |= modifier;
-        }
-        match sym {
-            Some(s) => Ok(modifiers | s),
-            None => Err(ModifiedKeysymParserError::MissingSym.spanned(span)),
+        let (modifiers, sym) = parse_modified(span, string, |part| {
+            Keysym::from_str(part).map(|sym| KeySym(sym.0))
+        })?;
+        Ok(modifiers | sym)
+    }
+}
+
+pub struct ShortcutTriggerParser;
+
+impl Parser for ShortcutTriggerParser {
+    type Value = ShortcutTrigger;
+    type Error = ModifiedKeysymParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        let scroll = [
+            ("scroll-up", PointerTrigger::ScrollUp),
+            ("scroll-down", PointerTrigger::ScrollDown),
+            ("scroll-left", PointerTrigger::ScrollLeft),
+            ("scroll-right", PointerTrigger::ScrollRight),
+        ];
+        for (name, trigger) in scroll {
+            if let Some(mods) = string.strip_suffix(name) {
+                let mods = match mods.strip_suffix("-") {
+                    Some(mods) => mods,
+                    None if mods.is_empty() => mods,
+                    _ => continue,
+                };
+                let mods = ModifiersParser.parse_string(span, mods)?;
+                return Ok(ShortcutTrigger::Pointer(mods | trigger));
+            }
         }
+        let (modifiers, trigger) = parse_modified(span, string, |part| {
+            if let Some(sym) = Keysym::from_str(part) {
+                return Some(ShortcutTrigger::Key(KeySym(sym.0).into()));
+            }
+            if part.to_ascii_uppercase().starts_with("BTN_")
+                && let Some(code) = input_event_code_from_name(part)
+            {
+                let trigger = PointerTrigger::Button(InputEventCode(code));
+                return Some(ShortcutTrigger::Pointer(trigger.into()));
+            }
+            None
+        })?;
+        let trigger = match trigger {
+            ShortcutTrigger::Key(k) => ShortcutTrigger::Key(k | modifiers),
+            ShortcutTrigger::Pointer(p) => ShortcutTrigger::Pointer(p | modifiers),
+        };
+        Ok(trigger)
+    }
+}
+
+fn parse_modified<T>(
+    span: Span,
+    string: &str,
+    mut parse_sym: impl FnMut(&str) -> Option<T>,
+) -> Result<(Modifiers, T), Spanned<ModifiedKeysymParserError>> {
+    let mut modifiers = Modifiers(0);
+    let mut sym = None;
+    for part in string.split("-") {
+        let modifier = match parse_mod(part) {
+            Some(m) => m,
+            _ => match parse_sym(part) {
+                Some(new) if sym.is_none() => {
+                    sym = Some(new);
+                    continue;
+                }
+                Some(_) => return Err(ModifiedKeysymParserError::MoreThanOneSym.spanned(span)),
+                _ => {
+                    return Err(
+                        ModifiedKeysymParserError::UnknownKeysym(part.to_string()).spanned(span)
+                    );
+                }
+            },
+        };
+        modifiers |= modifier;
+    }
+    match sym {
+        Some(s) => Ok((modifiers, s)),
+        None => Err(ModifiedKeysymParserError::MissingSym.spanned(span)),
     }
 }
 
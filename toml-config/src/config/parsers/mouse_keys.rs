@@ -0,0 +1,136 @@
+use crate::config::MouseKeys;
+use crate::config::context::Context;
+use crate::config::extractor::Extractor;
+use crate::config::extractor::ExtractorError;
+use crate::config::extractor::bol;
+use crate::config::extractor::fltorint;
+use crate::config::extractor::n32;
+use crate::config::extractor::opt;
+use crate::config::extractor::recover;
+use crate::config::extractor::tbl;
+use crate::config::keycodes::keycode_from_name;
+use crate::config::parser::DataType;
+use crate::config::parser::ParseResult;
+use crate::config::parser::Parser;
+use crate::config::parser::UnexpectedDataType;
+use crate::toml::toml_span::DespanExt;
+use crate::toml::toml_span::Span;
+use crate::toml::toml_span::Spanned;
+use crate::toml::toml_span::SpannedExt;
+use crate::toml::toml_value::Value;
+use indexmap::IndexMap;
+use jay_config::input::mouse_keys::MouseKeys as MouseKeysSettings;
+use jay_config::input::mouse_keys::MouseKeysAction;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum MouseKeysParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("Unknown mouse-keys action {0}")]
+    UnknownAction(String),
+}
+
+pub struct MouseKeysParser<'a, 'b, 'c>(pub &'a Context<'b, 'c>);
+
+impl Parser for MouseKeysParser<'_, '_, '_> {
+    type Value = MouseKeys;
+    type Error = MouseKeysParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (enabled, speed, max_speed, acceleration_time, scroll_interval, keys) =
+            ext.extract((
+                recover(opt(bol("enabled"))),
+                recover(opt(fltorint("speed"))),
+                recover(opt(fltorint("max-speed"))),
+                recover(opt(n32("acceleration-time"))),
+                recover(opt(n32("scroll-interval"))),
+                recover(opt(tbl("keys"))),
+            ))?;
+        let mut settings = MouseKeysSettings::default();
+        if let Some(speed) = speed {
+            settings.speed = speed.value;
+        }
+        if let Some(max_speed) = max_speed {
+            settings.max_speed = max_speed.value;
+        }
+        if let Some(time) = acceleration_time {
+            settings.acceleration_time_ms = time.value;
+        }
+        if let Some(interval) = scroll_interval {
+            settings.scroll_interval_ms = interval.value;
+        }
+        if let Some(keys) = keys {
+            settings.keys.clear();
+            for (key, value) in keys.value {
+                let Some(code) = keycode_from_name(&key.value) else {
+                    log::warn!(
+                        "Ignoring unknown key {}: {}",
+                        key.value,
+                        self.0.error3(key.span)
+                    );
+                    continue;
+                };
+                match value.parse(&mut MouseKeysActionParser) {
+                    Ok(action) => settings.keys.push((code, action)),
+                    Err(e) => {
+                        log::warn!(
+                            "Could not parse mouse-keys action of key {}: {}",
+                            key.value,
+                            self.0.error(e)
+                        );
+                    }
+                }
+            }
+        }
+        Ok(MouseKeys {
+            enabled: enabled.despan(),
+            settings,
+        })
+    }
+}
+
+struct MouseKeysActionParser;
+
+impl Parser for MouseKeysActionParser {
+    type Value = MouseKeysAction;
+    type Error = MouseKeysParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        use MouseKeysAction::*;
+        let action = match string {
+            "move-left" => MoveLeft,
+            "move-right" => MoveRight,
+            "move-up" => MoveUp,
+            "move-down" => MoveDown,
+            "move-up-left" => MoveUpLeft,
+            "move-up-right" => MoveUpRight,
+            "move-down-left" => MoveDownLeft,
+            "move-down-right" => MoveDownRight,
+            "click" => Click,
+            "double-click" => DoubleClick,
+            "press" => Press,
+            "release" => Release,
+            "select-left-button" => SelectLeftButton,
+            "select-middle-button" => SelectMiddleButton,
+            "select-right-button" => SelectRightButton,
+            "scroll-up" => ScrollUp,
+            "scroll-down" => ScrollDown,
+            "scroll-left" => ScrollLeft,
+            "scroll-right" => ScrollRight,
+            _ => {
+                return Err(MouseKeysParserError::UnknownAction(string.to_string()).spanned(span));
+            }
+        };
+        Ok(action)
+    }
+}
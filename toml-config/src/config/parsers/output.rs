@@ -25,11 +25,13 @@ use crate::toml::toml_span::Spanned;
 use crate::toml::toml_span::SpannedExt;
 use crate::toml::toml_value::Value;
 use indexmap::IndexMap;
+use jay_config::config_dir;
 use jay_config::video::BlendSpace;
 use jay_config::video::ColorSpace;
 use jay_config::video::Eotf;
 use jay_config::video::ScalingFilter;
 use jay_config::video::Transform;
+use std::path::PathBuf;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -79,6 +81,8 @@ impl Parser for OutputParser<'_, '_, '_> {
                 use_native_gamut,
                 enabled,
                 scaling_filter,
+                touch_edge_width,
+                icc_profile,
             ),
         ) = ext.extract((
             (
@@ -101,6 +105,8 @@ impl Parser for OutputParser<'_, '_, '_> {
                 recover(opt(bol("use-native-gamut"))),
                 recover(opt(bol("enabled"))),
                 recover(opt(str("scaling-filter"))),
+                recover(opt(s32("touch-edge-width"))),
+                recover(opt(str("icc-profile"))),
             ),
         ))?;
         let transform = match transform {
@@ -257,6 +263,15 @@ impl Parser for OutputParser<'_, '_, '_> {
             blend_space,
             use_native_gamut: use_native_gamut.despan(),
             enabled: enabled.despan(),
+            touch_edge_width: touch_edge_width.despan(),
+            icc_profile: icc_profile.despan().map(|path| match path {
+                "" => String::new(),
+                _ => {
+                    let mut root = PathBuf::from(config_dir());
+                    root.push(path);
+                    root.display().to_string()
+                }
+            }),
         })
     }
 }
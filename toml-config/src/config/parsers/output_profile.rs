@@ -0,0 +1,90 @@
+use crate::config::OutputProfile;
+use crate::config::context::Context;
+use crate::config::extractor::Extractor;
+use crate::config::extractor::ExtractorError;
+use crate::config::extractor::arr;
+use crate::config::extractor::str;
+use crate::config::parser::DataType;
+use crate::config::parser::ParseResult;
+use crate::config::parser::Parser;
+use crate::config::parser::UnexpectedDataType;
+use crate::config::parsers::output::OutputParser;
+use crate::toml::toml_span::Span;
+use crate::toml::toml_span::Spanned;
+use crate::toml::toml_value::Value;
+use ahash::AHashSet;
+use indexmap::IndexMap;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum OutputProfileParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct OutputProfileParser<'a, 'b, 'c>(pub &'a Context<'b, 'c>);
+
+impl Parser for OutputProfileParser<'_, '_, '_> {
+    type Value = OutputProfile;
+    type Error = OutputProfileParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (name, outputs_val) = ext.extract((str("name"), arr("outputs")))?;
+        let mut outputs = vec![];
+        for el in outputs_val.value {
+            match el.parse(&mut OutputParser {
+                cx: self.0,
+                name_ok: false,
+            }) {
+                Ok(o) => outputs.push(o),
+                Err(e) => {
+                    log::warn!("Could not parse output: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(OutputProfile {
+            name: name.value.to_string(),
+            outputs,
+        })
+    }
+}
+
+pub struct OutputProfilesParser<'a, 'b, 'c>(pub &'a Context<'b, 'c>);
+
+impl Parser for OutputProfilesParser<'_, '_, '_> {
+    type Value = Vec<OutputProfile>;
+    type Error = OutputProfileParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Array];
+
+    fn parse_array(&mut self, _span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let mut res = vec![];
+        let mut names = AHashSet::new();
+        for el in array {
+            match el.parse(&mut OutputProfileParser(self.0)) {
+                Ok(p) => {
+                    if !names.insert(p.name.clone()) {
+                        log::warn!(
+                            "Duplicate output profile {}: {}",
+                            p.name,
+                            self.0.error3(el.span)
+                        );
+                        continue;
+                    }
+                    res.push(p)
+                }
+                Err(e) => {
+                    log::warn!("Could not parse output profile: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(res)
+    }
+}
@@ -1,5 +1,6 @@
 use crate::config::Action;
 use crate::config::Shortcut;
+use crate::config::ShortcutTrigger;
 use crate::config::SimpleCommand;
 use crate::config::context::Context;
 use crate::config::extractor::Extractor;
@@ -18,6 +19,7 @@ use crate::config::parsers::action::ActionParserError;
 use crate::config::parsers::modified_keysym::ModifiedKeysymParser;
 use crate::config::parsers::modified_keysym::ModifiedKeysymParserError;
 use crate::config::parsers::modified_keysym::ModifiersParser;
+use crate::config::parsers::modified_keysym::ShortcutTriggerParser;
 use crate::config::spanned::SpannedErrorExt;
 use crate::toml::toml_span::DespanExt;
 use crate::toml::toml_span::Span;
@@ -46,7 +48,7 @@ pub enum ShortcutsParserError {
 
 pub struct ShortcutsParser<'a, 'b, 'c, 'd> {
     pub cx: &'a Context<'c, 'd>,
-    pub used_keys: &'b mut HashSet<Spanned<ModifiedKeySym>>,
+    pub used_keys: &'b mut HashSet<Spanned<ShortcutTrigger>>,
     pub shortcuts: &'b mut Vec<Shortcut>,
 }
 
@@ -61,21 +63,23 @@ impl Parser for ShortcutsParser<'_, '_, '_, '_> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         for (key, value) in table.iter() {
-            let Some(keysym) = parse_modified_keysym(self.cx, key) else {
+            let Some(trigger) = parse_shortcut_trigger(self.cx, key) else {
                 continue;
             };
             let Some(action) = parse_action(self.cx, &key.value, value) else {
                 continue;
             };
-            let spanned = keysym.spanned(key.span);
+            let spanned = trigger.spanned(key.span);
             log_used(self.cx, self.used_keys, spanned);
             self.shortcuts.push(Shortcut {
                 mask: Modifiers(!0),
-                keysym,
+                trigger,
                 repeat: false,
                 allow_locked: false,
                 action,
                 latch: None,
+                name: key.value.clone(),
+                description: action_label(&value.value),
             });
         }
         Ok(())
@@ -84,7 +88,7 @@ impl Parser for ShortcutsParser<'_, '_, '_, '_> {
 
 pub struct ComplexShortcutsParser<'a, 'b, 'c, 'd> {
     pub cx: &'a Context<'c, 'd>,
-    pub used_keys: &'b mut HashSet<Spanned<ModifiedKeySym>>,
+    pub used_keys: &'b mut HashSet<Spanned<ShortcutTrigger>>,
     pub shortcuts: &'b mut Vec<Shortcut>,
 }
 
@@ -99,11 +103,12 @@ impl Parser for ComplexShortcutsParser<'_, '_, '_, '_> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         for (key, value) in table.iter() {
-            let Some(keysym) = parse_modified_keysym(self.cx, key) else {
+            let Some(trigger) = parse_shortcut_trigger(self.cx, key) else {
                 continue;
             };
             let shortcut = match value.parse(&mut ComplexShortcutParser {
-                keysym,
+                trigger,
+                name: &key.value,
                 cx: self.cx,
             }) {
                 Ok(v) => v,
@@ -116,7 +121,7 @@ impl Parser for ComplexShortcutsParser<'_, '_, '_, '_> {
                     continue;
                 }
             };
-            let spanned = keysym.spanned(key.span);
+            let spanned = trigger.spanned(key.span);
             log_used(self.cx, self.used_keys, spanned);
             self.shortcuts.push(shortcut);
         }
@@ -125,7 +130,8 @@ impl Parser for ComplexShortcutsParser<'_, '_, '_, '_> {
 }
 
 struct ComplexShortcutParser<'a, 'b, 'c> {
-    pub keysym: ModifiedKeySym,
+    pub trigger: ShortcutTrigger,
+    pub name: &'a str,
     pub cx: &'a Context<'b, 'c>,
 }
 
@@ -146,12 +152,14 @@ impl Parser for ComplexShortcutParser<'_, '_, '_> {
             latch_val,
             repeat,
             allow_locked,
+            description,
         ) = ext.extract((
             opt(str("mod-mask")),
             opt(val("action")),
             opt(val("latch")),
             recover(opt(bol("repeat"))),
             recover(opt(bol("allow-locked"))),
+            recover(opt(str("description"))),
         ))?;
         let mod_mask = match mod_mask_val {
             None => Modifiers(!0),
@@ -159,6 +167,10 @@ impl Parser for ComplexShortcutParser<'_, '_, '_> {
                 .parse_string(v.span, v.value)
                 .map_spanned_err(ShortcutsParserError::ModMask)?,
         };
+        let description = match description {
+            Some(d) => Some(d.value.to_string()),
+            None => action_val.and_then(|v| action_label(v.value)),
+        };
         let action = match action_val {
             None => Action::SimpleCommand {
                 cmd: SimpleCommand::None,
@@ -169,22 +181,46 @@ impl Parser for ComplexShortcutParser<'_, '_, '_> {
         };
         let mut latch = None;
         if let Some(v) = latch_val {
-            latch = Some(
-                v.parse(&mut ActionParser(self.cx))
-                    .map_spanned_err(ShortcutsParserError::LatchError)?,
-            );
+            if let ShortcutTrigger::Pointer(_) = self.trigger {
+                log::warn!(
+                    "Latch actions are not supported for pointer shortcuts: {}",
+                    self.cx.error3(v.span)
+                );
+            } else {
+                latch = Some(
+                    v.parse(&mut ActionParser(self.cx))
+                        .map_spanned_err(ShortcutsParserError::LatchError)?,
+                );
+            }
         }
         Ok(Shortcut {
             mask: mod_mask,
-            keysym: self.keysym,
+            trigger: self.trigger,
             repeat: repeat.despan().unwrap_or(false),
             allow_locked: allow_locked.despan().unwrap_or(false),
             action,
             latch,
+            name: self.name.to_string(),
+            description,
         })
     }
 }
 
+fn action_label(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Table(t) => t.iter().find_map(|(k, v)| match (&*k.value, &v.value) {
+            ("type", Value::String(s)) => Some(s.clone()),
+            _ => None,
+        }),
+        Value::Array(a) => {
+            let labels: Vec<_> = a.iter().filter_map(|v| action_label(&v.value)).collect();
+            Some(labels.join(", "))
+        }
+        _ => None,
+    }
+}
+
 fn parse_action(cx: &Context<'_, '_>, key: &str, value: &Spanned<Value>) -> Option<Action> {
     match value.parse(&mut ActionParser(cx)) {
         Ok(a) => Some(a),
@@ -195,8 +231,14 @@ fn parse_action(cx: &Context<'_, '_>, key: &str, value: &Spanned<Value>) -> Opti
     }
 }
 
-fn parse_modified_keysym(cx: &Context<'_, '_>, key: &Spanned<String>) -> Option<ModifiedKeySym> {
-    parse_modified_keysym_str(cx, key.span, &key.value)
+fn parse_shortcut_trigger(cx: &Context<'_, '_>, key: &Spanned<String>) -> Option<ShortcutTrigger> {
+    match ShortcutTriggerParser.parse_string(key.span, &key.value) {
+        Ok(k) => Some(k),
+        Err(e) => {
+            log::warn!("Could not parse keysym {}: {}", key.value, cx.error(e));
+            None
+        }
+    }
 }
 
 pub fn parse_modified_keysym_str(
@@ -215,8 +257,8 @@ pub fn parse_modified_keysym_str(
 
 fn log_used(
     cx: &Context<'_, '_>,
-    used: &mut HashSet<Spanned<ModifiedKeySym>>,
-    key: Spanned<ModifiedKeySym>,
+    used: &mut HashSet<Spanned<ShortcutTrigger>>,
+    key: Spanned<ShortcutTrigger>,
 ) {
     if let Some(prev) = used.get(&key) {
         log::warn!(
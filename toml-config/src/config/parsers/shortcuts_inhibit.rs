@@ -0,0 +1,38 @@
+use crate::config::parser::DataType;
+use crate::config::parser::ParseResult;
+use crate::config::parser::Parser;
+use crate::config::parser::UnexpectedDataType;
+use crate::toml::toml_span::Span;
+use crate::toml::toml_span::SpannedExt;
+use jay_config::window::ShortcutsInhibit;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ShortcutsInhibitParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error("Unknown shortcuts inhibit policy `{}`", .0)]
+    UnknownPolicy(String),
+}
+
+pub struct ShortcutsInhibitParser;
+
+impl Parser for ShortcutsInhibitParser {
+    type Value = ShortcutsInhibit;
+    type Error = ShortcutsInhibitParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        let ty = match string {
+            "allow" => ShortcutsInhibit::Allow,
+            "deny" => ShortcutsInhibit::Deny,
+            "ask" => ShortcutsInhibit::Ask,
+            _ => {
+                return Err(
+                    ShortcutsInhibitParserError::UnknownPolicy(string.to_owned()).spanned(span),
+                );
+            }
+        };
+        Ok(ty)
+    }
+}
@@ -0,0 +1,266 @@
+use crate::config::Tablet;
+use crate::config::TabletRegion;
+use crate::config::context::Context;
+use crate::config::extractor::Extractor;
+use crate::config::extractor::ExtractorError;
+use crate::config::extractor::arr;
+use crate::config::extractor::bol;
+use crate::config::extractor::opt;
+use crate::config::extractor::recover;
+use crate::config::extractor::s32;
+use crate::config::extractor::str;
+use crate::config::extractor::tbl;
+use crate::config::extractor::val;
+use crate::config::parser::DataType;
+use crate::config::parser::ParseResult;
+use crate::config::parser::Parser;
+use crate::config::parser::UnexpectedDataType;
+use crate::config::parsers::action::ActionParser;
+use crate::toml::toml_span::DespanExt;
+use crate::toml::toml_span::Span;
+use crate::toml::toml_span::Spanned;
+use crate::toml::toml_span::SpannedExt;
+use crate::toml::toml_value::Value;
+use indexmap::IndexMap;
+use jay_config::input::tablet::ERASER_MODE_ERASER;
+use jay_config::input::tablet::ERASER_MODE_PEN;
+use jay_config::input::tablet::PressureCurve;
+use jay_config::input::tablet::TABLET_TOOL_TYPE_AIRBRUSH;
+use jay_config::input::tablet::TABLET_TOOL_TYPE_BRUSH;
+use jay_config::input::tablet::TABLET_TOOL_TYPE_ERASER;
+use jay_config::input::tablet::TABLET_TOOL_TYPE_LENS;
+use jay_config::input::tablet::TABLET_TOOL_TYPE_MOUSE;
+use jay_config::input::tablet::TABLET_TOOL_TYPE_PEN;
+use jay_config::input::tablet::TABLET_TOOL_TYPE_PENCIL;
+use jay_config::input::tablet::TabletPadEvent;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TabletParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("Expected exactly four numbers")]
+    FourNumbers,
+    #[error("All numbers must be in the range [0, 1]")]
+    Range,
+}
+
+pub struct TabletParser<'a, 'b, 'c> {
+    pub cx: &'a Context<'b, 'c>,
+    pub is_inputs_array: bool,
+}
+
+impl Parser for TabletParser<'_, '_, '_> {
+    type Value = Tablet;
+    type Error = TabletParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.cx, span, table);
+        let (
+            (area_val, keep_aspect_ratio, region_val, eraser_mode_val, pressure_curves_val),
+            (pad_buttons_val, pad_rings_val, pad_strips_val, pad_dials_val),
+        ) = ext.extract((
+            (
+                recover(opt(val("area"))),
+                recover(opt(bol("keep-aspect-ratio"))),
+                recover(opt(val("region"))),
+                recover(opt(str("eraser-mode"))),
+                recover(opt(tbl("pressure-curves"))),
+            ),
+            (
+                recover(opt(tbl("pad-buttons"))),
+                recover(opt(arr("pad-rings"))),
+                recover(opt(arr("pad-strips"))),
+                recover(opt(arr("pad-dials"))),
+            ),
+        ))?;
+        let mut tablet = Tablet {
+            keep_aspect_ratio: keep_aspect_ratio.despan(),
+            ..Default::default()
+        };
+        if let Some(area) = area_val {
+            match area.parse(&mut UnitQuadParser) {
+                Ok(v) => tablet.area = Some(v),
+                Err(e) => log::warn!("Could not parse the tablet area: {}", self.cx.error(e)),
+            }
+        }
+        if let Some(region) = region_val {
+            match region.parse(&mut TabletRegionParser(self.cx)) {
+                Ok(v) => tablet.region = Some(v),
+                Err(e) => log::warn!("Could not parse the tablet region: {}", self.cx.error(e)),
+            }
+        }
+        if let Some(mode) = eraser_mode_val {
+            match mode.value {
+                "eraser" => tablet.eraser_mode = Some(ERASER_MODE_ERASER),
+                "pen" => tablet.eraser_mode = Some(ERASER_MODE_PEN),
+                v => log::warn!("Unknown eraser-mode {v}: {}", self.cx.error3(mode.span)),
+            }
+        }
+        if let Some(curves) = pressure_curves_val {
+            for (tool, value) in curves.value {
+                let ty = match tool.value.as_str() {
+                    "pen" => TABLET_TOOL_TYPE_PEN,
+                    "eraser" => TABLET_TOOL_TYPE_ERASER,
+                    "brush" => TABLET_TOOL_TYPE_BRUSH,
+                    "pencil" => TABLET_TOOL_TYPE_PENCIL,
+                    "airbrush" => TABLET_TOOL_TYPE_AIRBRUSH,
+                    "mouse" => TABLET_TOOL_TYPE_MOUSE,
+                    "lens" => TABLET_TOOL_TYPE_LENS,
+                    v => {
+                        log::warn!("Unknown tool type {v}: {}", self.cx.error3(tool.span));
+                        continue;
+                    }
+                };
+                match value.parse(&mut UnitQuadParser) {
+                    Ok([x1, y1, x2, y2]) => {
+                        let curve = PressureCurve { x1, y1, x2, y2 };
+                        tablet.pressure_curves.push((ty, curve));
+                    }
+                    Err(e) => {
+                        log::warn!(
+                            "Could not parse the pressure curve of {}: {}",
+                            tool.value,
+                            self.cx.error(e)
+                        );
+                    }
+                }
+            }
+        }
+        let mut parse_action = |val: Spanned<&Value>, event: TabletPadEvent| {
+            if !self.is_inputs_array {
+                log::warn!(
+                    "Pad actions have no effect in this position: {}",
+                    self.cx.error3(val.span)
+                );
+                return;
+            }
+            match val.parse(&mut ActionParser(self.cx)) {
+                Ok(a) => {
+                    tablet.pad_actions.insert(event, a);
+                }
+                Err(e) => {
+                    log::warn!("Could not parse pad action: {}", self.cx.error(e));
+                }
+            }
+        };
+        if let Some(buttons) = pad_buttons_val {
+            for (button, action) in buttons.value {
+                let Ok(button) = button.value.parse::<u32>() else {
+                    log::warn!(
+                        "Pad buttons must be identified by numbers: {}",
+                        self.cx.error3(button.span)
+                    );
+                    continue;
+                };
+                let pressed = true;
+                parse_action(action.as_ref(), TabletPadEvent::Button { button, pressed });
+            }
+        }
+        let mut parse_pair =
+            |vals: Option<Spanned<&[Spanned<Value>]>>,
+             names: [&'static str; 2],
+             event: &dyn Fn(u32, bool) -> TabletPadEvent| {
+                let Some(vals) = vals else {
+                    return;
+                };
+                for (idx, entry) in vals.value.iter().enumerate() {
+                    let Value::Table(table) = &entry.value else {
+                        log::warn!(
+                            "Expected a table but found {}: {}",
+                            entry.value.name(),
+                            self.cx.error3(entry.span)
+                        );
+                        continue;
+                    };
+                    let mut ext = Extractor::new(self.cx, entry.span, table);
+                    let res: Result<_, Spanned<ExtractorError>> =
+                        ext.extract((opt(val(names[0])), opt(val(names[1]))));
+                    let (first, second) = match res {
+                        Ok(v) => v,
+                        Err(e) => {
+                            log::warn!("Could not parse pad actions: {}", self.cx.error(e));
+                            continue;
+                        }
+                    };
+                    if let Some(first) = first {
+                        parse_action(first, event(idx as u32, true));
+                    }
+                    if let Some(second) = second {
+                        parse_action(second, event(idx as u32, false));
+                    }
+                }
+            };
+        parse_pair(
+            pad_rings_val,
+            ["clockwise", "counter-clockwise"],
+            &|ring, clockwise| TabletPadEvent::Ring { ring, clockwise },
+        );
+        parse_pair(pad_strips_val, ["up", "down"], &|strip, up| {
+            TabletPadEvent::Strip { strip, up }
+        });
+        parse_pair(
+            pad_dials_val,
+            ["clockwise", "counter-clockwise"],
+            &|dial, clockwise| TabletPadEvent::Dial { dial, clockwise },
+        );
+        Ok(tablet)
+    }
+}
+
+struct UnitQuadParser;
+
+impl Parser for UnitQuadParser {
+    type Value = [f64; 4];
+    type Error = TabletParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Array];
+
+    fn parse_array(&mut self, span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        if array.len() != 4 {
+            return Err(TabletParserError::FourNumbers.spanned(span));
+        }
+        let mut res = [0.0; 4];
+        for (res, v) in res.iter_mut().zip(array) {
+            *res = match v.value {
+                Value::Float(f) => f,
+                Value::Integer(i) => i as f64,
+                _ => return Err(TabletParserError::FourNumbers.spanned(v.span)),
+            };
+            if !(0.0..=1.0).contains(res) {
+                return Err(TabletParserError::Range.spanned(v.span));
+            }
+        }
+        Ok(res)
+    }
+}
+
+struct TabletRegionParser<'a, 'b, 'c>(&'a Context<'b, 'c>);
+
+impl Parser for TabletRegionParser<'_, '_, '_> {
+    type Value = TabletRegion;
+    type Error = TabletParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (x, y, width, height) =
+            ext.extract((s32("x"), s32("y"), s32("width"), s32("height")))?;
+        Ok(TabletRegion {
+            x: x.value,
+            y: y.value,
+            width: width.value,
+            height: height.value,
+        })
+    }
+}
@@ -0,0 +1,48 @@
+use crate::config::WhichKey;
+use crate::config::context::Context;
+use crate::config::extractor::Extractor;
+use crate::config::extractor::ExtractorError;
+use crate::config::extractor::bol;
+use crate::config::extractor::n32;
+use crate::config::extractor::opt;
+use crate::config::extractor::recover;
+use crate::config::parser::DataType;
+use crate::config::parser::ParseResult;
+use crate::config::parser::Parser;
+use crate::config::parser::UnexpectedDataType;
+use crate::toml::toml_span::DespanExt;
+use crate::toml::toml_span::Span;
+use crate::toml::toml_span::Spanned;
+use crate::toml::toml_value::Value;
+use indexmap::IndexMap;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum WhichKeyParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct WhichKeyParser<'a, 'b, 'c>(pub &'a Context<'b, 'c>);
+
+impl Parser for WhichKeyParser<'_, '_, '_> {
+    type Value = WhichKey;
+    type Error = WhichKeyParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (enabled, delay) =
+            ext.extract((recover(opt(bol("enabled"))), recover(opt(n32("delay")))))?;
+        Ok(WhichKey {
+            enabled: enabled.despan(),
+            delay: delay.despan(),
+        })
+    }
+}
@@ -14,6 +14,7 @@ use crate::config::parser::Parser;
 use crate::config::parser::UnexpectedDataType;
 use crate::config::parsers::action::ActionParser;
 use crate::config::parsers::action::ActionParserError;
+use crate::config::parsers::shortcuts_inhibit::ShortcutsInhibitParser;
 use crate::config::parsers::tile_state::TileStateParser;
 use crate::config::parsers::window_match::WindowMatchParser;
 use crate::config::parsers::window_match::WindowMatchParserError;
@@ -59,6 +60,7 @@ impl Parser for WindowRuleParser<'_, '_, '_> {
             latch_val,
             auto_focus,
             initial_tile_state_val,
+            shortcuts_inhibit_val,
         ) = ext.extract((
             opt(str("name")),
             opt(val("match")),
@@ -66,6 +68,7 @@ impl Parser for WindowRuleParser<'_, '_, '_> {
             opt(val("latch")),
             recover(opt(bol("auto-focus"))),
             opt(val("initial-tile-state")),
+            opt(val("shortcuts-inhibit")),
         ))?;
         let mut action = None;
         if let Some(value) = action_val {
@@ -95,6 +98,18 @@ impl Parser for WindowRuleParser<'_, '_, '_> {
                 }
             }
         }
+        let mut shortcuts_inhibit = None;
+        if let Some(value) = shortcuts_inhibit_val {
+            match value.parse(&mut ShortcutsInhibitParser) {
+                Ok(v) => shortcuts_inhibit = Some(v),
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse the shortcuts inhibit policy: {}",
+                        self.0.error(e)
+                    );
+                }
+            }
+        }
         let match_ = match match_val {
             None => WindowMatch::default(),
             Some(m) => m.parse_map(&mut WindowMatchParser {
@@ -109,6 +124,7 @@ impl Parser for WindowRuleParser<'_, '_, '_> {
             latch,
             auto_focus: auto_focus.despan(),
             initial_tile_state,
+            shortcuts_inhibit,
         })
     }
 }
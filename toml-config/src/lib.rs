@@ -5,6 +5,9 @@
     clippy::collapsible_else_if
 )]
 
+#[cfg(test)]
+mod tests;
+
 mod config;
 mod phf;
 pub mod phf_map;
@@ -20,14 +23,17 @@ use crate::config::ConfigConnector;
 use crate::config::ConfigDrmDevice;
 use crate::config::ConfigKeymap;
 use crate::config::ConnectorMatch;
+use crate::config::DEFAULT_MACRO_NAME;
 use crate::config::DrmDeviceMatch;
 use crate::config::Exec;
 pub use crate::config::GenericMatch;
 use crate::config::Input;
 use crate::config::InputMatch;
 pub use crate::config::MatchExactly;
+use crate::config::ModeTimings;
 use crate::config::Output;
 use crate::config::OutputMatch;
+use crate::config::OutputProfile;
 use crate::config::SimpleCommand;
 use crate::config::Status;
 use crate::config::Theme;
@@ -59,11 +65,16 @@ use jay_config::input::InputDevice;
 use jay_config::input::Seat;
 use jay_config::input::SwitchEvent;
 use jay_config::input::capability::CAP_SWITCH;
+use jay_config::input::capability::CAP_TABLET_PAD;
+use jay_config::input::capability::CAP_TABLET_TOOL;
+use jay_config::input::gesture::Gesture;
+use jay_config::input::gesture::TouchGesture;
 use jay_config::input::get_seat;
 use jay_config::input::input_devices;
 use jay_config::input::on_input_device_removed;
 use jay_config::input::on_new_input_device;
 use jay_config::input::set_libei_socket_enabled;
+use jay_config::input::tablet::TabletPadEvent;
 use jay_config::io::Async;
 use jay_config::is_reload;
 use jay_config::keyboard::Keymap;
@@ -102,6 +113,8 @@ use jay_config::tasks::{self};
 use jay_config::theme::reset_colors;
 use jay_config::theme::reset_font;
 use jay_config::theme::reset_sizes;
+use jay_config::theme::set_bar_element_separator;
+use jay_config::theme::set_bar_elements;
 use jay_config::theme::set_bar_font;
 use jay_config::theme::set_bar_position;
 use jay_config::theme::set_container_borders;
@@ -118,19 +131,29 @@ use jay_config::toggle_split_reuses_container;
 use jay_config::toggle_visualize_compositing;
 use jay_config::video::ColorSpace;
 use jay_config::video::Connector;
+use jay_config::video::ConnectorConfiguration;
 use jay_config::video::DrmDevice;
 use jay_config::video::Eotf;
+use jay_config::video::Mode;
+use jay_config::video::configure_connectors;
 use jay_config::video::connectors;
 use jay_config::video::create_virtual_output;
 use jay_config::video::drm_devices;
+use jay_config::video::on_apply_output_profile;
 use jay_config::video::on_connector_connected;
 use jay_config::video::on_connector_disconnected;
 use jay_config::video::on_graphics_initialized;
 use jay_config::video::on_new_connector;
 use jay_config::video::on_new_drm_device;
 use jay_config::video::remove_virtual_output;
+use jay_config::video::rotation_locked;
+use jay_config::video::set_auto_rotation_enabled;
+use jay_config::video::set_auto_scale;
+use jay_config::video::set_backlight_indicator_enabled;
 use jay_config::video::set_direct_scanout_enabled;
 use jay_config::video::set_gfx_api;
+use jay_config::video::set_output_profiles;
+use jay_config::video::set_rotation_locked;
 use jay_config::video::set_tearing_mode;
 use jay_config::video::set_vrr_cursor_hz;
 use jay_config::video::set_vrr_mode;
@@ -168,6 +191,18 @@ use uapi::c::TFD_NONBLOCK;
 use uapi::c::timespec;
 use uapi::c::{self};
 
+fn set_backlight(value: f64, relative: bool) {
+    for connector in connectors() {
+        if !connector.connected() || connector.backlight().is_none() {
+            continue;
+        }
+        match relative {
+            true => connector.adjust_backlight(value),
+            false => connector.set_backlight(value),
+        }
+    }
+}
+
 fn default_seat() -> Seat {
     get_seat("default")
 }
@@ -331,6 +366,75 @@ impl Action {
                     let persistent = state.persistent.clone();
                     b.new(move || persistent.seat.reload_simple_im())
                 }
+                SimpleCommand::EnableStickyKeys(v) => {
+                    let persistent = state.persistent.clone();
+                    b.new(move || persistent.seat.set_sticky_keys_enabled(v))
+                }
+                SimpleCommand::ToggleStickyKeys => {
+                    let persistent = state.persistent.clone();
+                    b.new(move || persistent.seat.toggle_sticky_keys_enabled())
+                }
+                SimpleCommand::EnableSlowKeys(v) => {
+                    let persistent = state.persistent.clone();
+                    b.new(move || persistent.seat.set_slow_keys_enabled(v))
+                }
+                SimpleCommand::ToggleSlowKeys => {
+                    let persistent = state.persistent.clone();
+                    b.new(move || persistent.seat.toggle_slow_keys_enabled())
+                }
+                SimpleCommand::EnableBounceKeys(v) => {
+                    let persistent = state.persistent.clone();
+                    b.new(move || persistent.seat.set_bounce_keys_enabled(v))
+                }
+                SimpleCommand::ToggleBounceKeys => {
+                    let persistent = state.persistent.clone();
+                    b.new(move || persistent.seat.toggle_bounce_keys_enabled())
+                }
+                SimpleCommand::EnableMouseKeys(v) => {
+                    let persistent = state.persistent.clone();
+                    b.new(move || persistent.seat.set_mouse_keys_enabled(v))
+                }
+                SimpleCommand::ToggleMouseKeys => {
+                    let persistent = state.persistent.clone();
+                    b.new(move || persistent.seat.toggle_mouse_keys_enabled())
+                }
+                SimpleCommand::StartMacroRecording => {
+                    let persistent = state.persistent.clone();
+                    b.new(move || {
+                        persistent
+                            .seat
+                            .start_macro_recording(DEFAULT_MACRO_NAME, false)
+                    })
+                }
+                SimpleCommand::StopMacroRecording => {
+                    let persistent = state.persistent.clone();
+                    b.new(move || persistent.seat.stop_macro_recording())
+                }
+                SimpleCommand::ReplayMacro => {
+                    let persistent = state.persistent.clone();
+                    b.new(move || persistent.seat.replay_macro(DEFAULT_MACRO_NAME))
+                }
+                SimpleCommand::MapTabletsToWindow => {
+                    let persistent = state.persistent.clone();
+                    b.new(move || {
+                        let window = persistent.seat.window();
+                        if !window.exists() {
+                            return;
+                        }
+                        for c in input_devices() {
+                            if c.has_capability(CAP_TABLET_TOOL) {
+                                c.map_to_window(window);
+                            }
+                        }
+                    })
+                }
+                SimpleCommand::UnmapTabletsFromWindow => b.new(|| {
+                    for c in input_devices() {
+                        if c.has_capability(CAP_TABLET_TOOL) {
+                            c.unmap_from_window();
+                        }
+                    }
+                }),
                 SimpleCommand::EnableUnicodeInput => {
                     let persistent = state.persistent.clone();
                     b.new(move || persistent.seat.enable_unicode_input())
@@ -349,6 +453,28 @@ impl Action {
                     b.new(move || set_split_reuses_container(v))
                 }
                 SimpleCommand::ToggleSplitReusesContainer => b.new(toggle_split_reuses_container),
+                SimpleCommand::SetDpms(on) => b.new(move || {
+                    for connector in connectors() {
+                        if connector.connected() {
+                            connector.set_dpms(on);
+                        }
+                    }
+                }),
+                SimpleCommand::ToggleDpms => b.new(|| {
+                    let connectors: Vec<_> =
+                        connectors().into_iter().filter(|c| c.connected()).collect();
+                    let on = toggled_dpms(connectors.iter().map(|c| c.dpms()));
+                    for connector in connectors {
+                        connector.set_dpms(on);
+                    }
+                }),
+                SimpleCommand::AdjustBacklight(delta) => b.new(move || set_backlight(delta, true)),
+                SimpleCommand::SetRotationLocked(locked) => {
+                    b.new(move || set_rotation_locked(locked))
+                }
+                SimpleCommand::ToggleRotationLock => {
+                    b.new(|| set_rotation_locked(!rotation_locked()))
+                }
             },
             Action::Multi { actions } => {
                 let actions: Vec<_> = actions.into_iter().map(|a| a.into_fn(state)).collect();
@@ -596,7 +722,7 @@ impl Action {
                         log::warn!("Input mode {name} does not exist");
                         return;
                     };
-                    state.set_mode(new, latch);
+                    state.set_mode(&name, new, latch);
                 })
             }
             Action::CreateVirtualOutput { name } => b.new(move || create_virtual_output(&name)),
@@ -624,6 +750,21 @@ impl Action {
                     }
                 })
             }
+            Action::StartMacroRecording { name, pointer } => {
+                let persistent = state.persistent.clone();
+                b.new(move || persistent.seat.start_macro_recording(&name, pointer))
+            }
+            Action::ReplayMacro { name } => {
+                let persistent = state.persistent.clone();
+                b.new(move || persistent.seat.replay_macro(&name))
+            }
+            Action::ApplyOutputProfile { name } => {
+                let state = state.clone();
+                b.new(move || state.apply_output_profile_by_name(&name))
+            }
+            Action::SetBacklight { value, relative } => {
+                b.new(move || set_backlight(value, relative))
+            }
         }
     }
 }
@@ -881,6 +1022,44 @@ impl Input {
         if let Some(v) = self.scroll_button_lock {
             c.set_scroll_button_lock(v);
         }
+        if let Some(v) = self.disable_while_typing {
+            c.set_disable_while_typing(v);
+        }
+        if let Some(v) = self.disable_while_trackpointing {
+            c.set_disable_while_trackpointing(v);
+        }
+        if let Some(v) = self.tap_button_map {
+            c.set_tap_button_map(v);
+        }
+        if let Some(v) = self.send_events {
+            c.set_send_events_mode(v);
+        }
+        if let Some(v) = self.rotation {
+            c.set_rotation(v);
+        }
+        if let Some(v) = &self.custom_accel {
+            c.set_custom_accel(v.step, &v.points);
+        }
+        if let Some(v) = &self.key_remap {
+            c.set_key_remap(v);
+        }
+        if let Some(t) = &self.tablet {
+            if let Some([x1, y1, x2, y2]) = t.area {
+                c.set_tablet_area(x1, y1, x2, y2);
+            }
+            if let Some(v) = t.keep_aspect_ratio {
+                c.set_tablet_keep_aspect_ratio(v);
+            }
+            if let Some(r) = &t.region {
+                c.set_tablet_region(r.x, r.y, r.width, r.height);
+            }
+            if let Some(v) = t.eraser_mode {
+                c.set_tablet_eraser_mode(v);
+            }
+            for &(tool, curve) in &t.pressure_curves {
+                c.set_tablet_pressure_curve(tool, curve);
+            }
+        }
         if let Some(v) = self.detached {
             let seat = match v {
                 true => Seat::INVALID,
@@ -973,6 +1152,12 @@ impl ConnectorMatch {
 
 impl ConfigConnector {
     fn apply(&self, c: Connector) {
+        if let Some(path) = &self.edid_override {
+            c.set_edid_override(Some(path.as_str()).filter(|p| !p.is_empty()));
+        }
+        if let Some(address) = &self.vnc {
+            c.set_vnc_server(Some(address.as_str()).filter(|a| !a.is_empty()));
+        }
         c.set_enabled(self.enabled);
     }
 }
@@ -989,38 +1174,83 @@ impl Output {
         if let Some(scale) = self.scale {
             c.set_scale(scale);
         }
-        if let Some(scaling_filter) = self.scaling_filter {
-            c.set_scaling_filter(scaling_filter);
-        }
         if let Some(transform) = self.transform {
             c.set_transform(transform);
         }
-        if let Some(mode) = &self.mode {
-            let modes = c.modes();
-            let m = modes.iter().find(|m| {
-                if m.width() != mode.width || m.height() != mode.height {
-                    return false;
-                }
-                match mode.refresh_rate {
-                    None => true,
-                    Some(rr) => m.refresh_rate() as f64 / 1000.0 == rr,
-                }
-            });
-            'set_mode: {
-                let (w, h, mhz) = 'mode: {
-                    if let Some(m) = m {
-                        break 'mode (m.width(), m.height(), m.refresh_rate());
-                    }
-                    if c.supports_arbitrary_modes()
-                        && let Some(refresh) = mode.refresh_rate
-                    {
-                        break 'mode (mode.width, mode.height, (refresh * 1_000.0).round() as u32);
-                    }
-                    log::warn!("Output {} does not support mode {mode}", c.name());
-                    break 'set_mode;
-                };
-                c.set_mode(w, h, Some(mhz));
+        if let Some(mode) = self.find_mode(c) {
+            c.set_mode(mode.width(), mode.height(), Some(mode.refresh_rate()));
+        }
+        self.apply_other(c);
+    }
+
+    fn configuration(&self, c: Connector) -> ConnectorConfiguration {
+        let position = (self.x.is_some() || self.y.is_some()).then(|| {
+            let (old_x, old_y) = c.position();
+            (self.x.unwrap_or(old_x), self.y.unwrap_or(old_y))
+        });
+        ConnectorConfiguration {
+            enabled: self.enabled,
+            mode: self.find_mode(c),
+            position,
+            scale: self.scale,
+            transform: self.transform,
+        }
+    }
+
+    fn find_mode(&self, c: Connector) -> Option<Mode> {
+        let mode = self.mode.as_ref()?;
+        if mode.timings.is_some() {
+            return None;
+        }
+        let modes = c.modes();
+        let m = modes.iter().find(|m| {
+            if m.width() != mode.width || m.height() != mode.height {
+                return false;
+            }
+            match mode.refresh_rate {
+                None => true,
+                Some(rr) => m.refresh_rate() as f64 / 1000.0 == rr,
             }
+        });
+        if let Some(m) = m {
+            return Some(*m);
+        }
+        if c.supports_arbitrary_modes()
+            && let Some(refresh) = mode.refresh_rate
+        {
+            return Some(Mode::new(
+                mode.width,
+                mode.height,
+                (refresh * 1_000.0).round() as u32,
+            ));
+        }
+        log::warn!("Output {} does not support mode {mode}", c.name());
+        None
+    }
+
+    fn apply_custom_mode(&self, c: Connector) {
+        let Some(mode) = &self.mode else {
+            return;
+        };
+        match &mode.timings {
+            None => {}
+            Some(ModeTimings::Cvt { reduced_blanking }) => {
+                let refresh = mode.refresh_rate.unwrap_or_default();
+                c.set_cvt_mode(
+                    mode.width,
+                    mode.height,
+                    (refresh * 1_000.0).round() as u32,
+                    *reduced_blanking,
+                );
+            }
+            Some(ModeTimings::Modeline(modeline)) => c.set_modeline(modeline),
+        }
+    }
+
+    fn apply_other(&self, c: Connector) {
+        self.apply_custom_mode(c);
+        if let Some(scaling_filter) = self.scaling_filter {
+            c.set_scaling_filter(scaling_filter);
         }
         if let Some(vrr) = &self.vrr {
             if let Some(mode) = vrr.mode {
@@ -1052,6 +1282,12 @@ impl Output {
         if let Some(use_native_gamut) = self.use_native_gamut {
             c.set_use_native_gamut(use_native_gamut);
         }
+        if let Some(width) = self.touch_edge_width {
+            c.set_touch_edge_width(width);
+        }
+        if let Some(path) = &self.icc_profile {
+            c.set_icc_profile(Some(path.as_str()).filter(|p| !p.is_empty()));
+        }
     }
 }
 
@@ -1077,6 +1313,9 @@ struct State {
     workspaces: Vec<TomlWorkspace>,
 
     max_trigger_depth: u64,
+
+    output_profiles: Vec<OutputProfile>,
+    active_output_profile: RefCell<Option<String>>,
 }
 
 impl Drop for State {
@@ -1088,6 +1327,7 @@ impl Drop for State {
 }
 
 type SwitchActions = Vec<(InputMatch, AHashMap<SwitchEvent, Box<dyn Fn()>>)>;
+type PadActions = Vec<(InputMatch, AHashMap<TabletPadEvent, Box<dyn Fn()>>)>;
 
 impl State {
     fn get_keymap(&self, map: &ConfigKeymap) -> Option<Keymap> {
@@ -1212,6 +1452,104 @@ impl State {
         });
     }
 
+    fn handle_tablet_pad_device(self: &Rc<Self>, dev: InputDevice, actions: &Rc<PadActions>) {
+        if !dev.has_capability(CAP_TABLET_PAD) {
+            return;
+        }
+        let state = self.clone();
+        let actions = actions.clone();
+        dev.on_tablet_pad_event(move |ev| {
+            for (match_, actions) in &*actions {
+                if match_.matches(dev, &state)
+                    && let Some(action) = actions.get(&ev)
+                {
+                    action();
+                }
+            }
+        });
+    }
+
+    fn connected_connectors(&self, exclude: Option<Connector>) -> Vec<Connector> {
+        connectors()
+            .into_iter()
+            .filter(|&c| Some(c) != exclude && c.connected())
+            .collect()
+    }
+
+    fn match_output_profile<'a>(
+        &self,
+        outputs: &'a [Output],
+        connectors: &mut Vec<Connector>,
+        assigned: &mut Vec<(&'a Output, Connector)>,
+        exact: bool,
+    ) -> bool {
+        assign_profile_outputs(outputs, connectors, assigned, exact, &|output, c| {
+            output.match_.matches(c, self)
+        })
+    }
+
+    fn apply_matching_output_profile(&self, exclude: Option<Connector>) {
+        if self.output_profiles.is_empty() {
+            return;
+        }
+        let connected = self.connected_connectors(exclude);
+        let active = self.active_output_profile.borrow().clone();
+        let profiles = self
+            .output_profiles
+            .iter()
+            .filter(|p| Some(&p.name) == active.as_ref())
+            .chain(
+                self.output_profiles
+                    .iter()
+                    .filter(|p| Some(&p.name) != active.as_ref()),
+            );
+        for profile in profiles {
+            let mut connectors = connected.clone();
+            let mut assigned = vec![];
+            if self.match_output_profile(&profile.outputs, &mut connectors, &mut assigned, true) {
+                self.apply_output_profile(profile, &assigned);
+                return;
+            }
+        }
+        if self.active_output_profile.take().is_some() {
+            self.publish_output_profiles();
+        }
+    }
+
+    fn apply_output_profile_by_name(&self, name: &str) {
+        let Some(profile) = self.output_profiles.iter().find(|p| p.name == name) else {
+            log::warn!("Output profile {name} does not exist");
+            return;
+        };
+        let mut connectors = self.connected_connectors(None);
+        let mut assigned = vec![];
+        self.match_output_profile(&profile.outputs, &mut connectors, &mut assigned, false);
+        if assigned.len() < profile.outputs.len() {
+            log::warn!("Some outputs of output profile {name} are not connected");
+        }
+        self.apply_output_profile(profile, &assigned);
+    }
+
+    fn apply_output_profile(&self, profile: &OutputProfile, assigned: &[(&Output, Connector)]) {
+        log::info!("Applying output profile {}", profile.name);
+        let configurations: Vec<_> = assigned
+            .iter()
+            .map(|&(output, c)| (c, output.configuration(c)))
+            .collect();
+        configure_connectors(&configurations);
+        for &(output, c) in assigned {
+            output.apply_other(c);
+        }
+        *self.active_output_profile.borrow_mut() = Some(profile.name.clone());
+        self.publish_output_profiles();
+    }
+
+    fn publish_output_profiles(&self) {
+        let names: Vec<_> = self.output_profiles.iter().map(|p| &*p.name).collect();
+        let active = self.active_output_profile.borrow();
+        set_output_profiles(&names, active.as_deref());
+    }
+
     fn add_io_output(&self, c: Connector) {
         let mappings: Vec<_> = self
             .io_maps
@@ -1309,6 +1647,8 @@ struct PersistentState {
     workspaces_with_initial_outputs: RefCell<AHashSet<Workspace>>,
     triggers: RefCell<Vec<Rc<TomlTrigger>>>,
     counters: RefCell<Vec<Rc<Counter>>>,
+    gestures: RefCell<Vec<Gesture>>,
+    touch_gestures: RefCell<Vec<TouchGesture>>,
 }
 
 async fn watch_config(persistent: Rc<PersistentState>) {
@@ -1615,6 +1955,8 @@ fn load_config(initial_load: bool, auto_reload: bool, persistent: &Rc<Persistent
         window: Default::default(),
         workspaces: workspaces.values().map(|v| v.to_toml()).collect(),
         max_trigger_depth: config.max_trigger_depth,
+        output_profiles: config.output_profiles,
+        active_output_profile: Default::default(),
     });
     persistent.triggers.borrow_mut().clear();
     persistent.counters.borrow_mut().clear();
@@ -1631,6 +1973,7 @@ fn load_config(initial_load: bool, auto_reload: bool, persistent: &Rc<Persistent
         persistent.actions.borrow_mut().insert(a.name, action);
     }
     let mut switch_actions = vec![];
+    let mut pad_actions = vec![];
     for input in &mut config.inputs {
         let mut actions = AHashMap::new();
         for (event, action) in input.switch_actions.drain() {
@@ -1639,8 +1982,18 @@ fn load_config(initial_load: bool, auto_reload: bool, persistent: &Rc<Persistent
         if actions.len() > 0 {
             switch_actions.push((input.match_.clone(), actions));
         }
+        if let Some(tablet) = &mut input.tablet {
+            let mut actions = AHashMap::new();
+            for (event, action) in tablet.pad_actions.drain() {
+                actions.insert(event, action.into_fn(&state));
+            }
+            if actions.len() > 0 {
+                pad_actions.push((input.match_.clone(), actions));
+            }
+        }
     }
     let switch_actions = Rc::new(switch_actions);
+    let pad_actions = Rc::new(pad_actions);
     match config.on_graphics_initialized {
         None => on_graphics_initialized(|| ()),
         Some(a) => on_graphics_initialized(a.into_fn(&state)),
@@ -1650,6 +2003,37 @@ fn load_config(initial_load: bool, auto_reload: bool, persistent: &Rc<Persistent
         Some(a) => on_idle(a.into_fn(&state)),
     }
     state.init_modes(&config.shortcuts, &config.input_modes);
+    for gesture in persistent.gestures.borrow_mut().drain(..) {
+        persistent.seat.unbind_gesture(gesture);
+    }
+    for gesture in persistent.touch_gestures.borrow_mut().drain(..) {
+        persistent.seat.unbind_touch_gesture(gesture);
+    }
+    if let Some(gestures) = config.gestures.take() {
+        if let Some(distance) = gestures.swipe_distance {
+            persistent.seat.set_gesture_swipe_distance(distance);
+        }
+        for binding in gestures.bindings {
+            persistent
+                .seat
+                .bind_gesture(binding.gesture, binding.action.into_fn(&state));
+            if binding.continuous {
+                persistent
+                    .seat
+                    .set_gesture_continuous(binding.gesture, true);
+            }
+            persistent.gestures.borrow_mut().push(binding.gesture);
+        }
+        for binding in gestures.touch_bindings {
+            persistent
+                .seat
+                .bind_touch_gesture(binding.gesture, binding.action.into_fn(&state));
+            persistent.touch_gestures.borrow_mut().push(binding.gesture);
+        }
+        if let Some(enabled) = gestures.touch_long_press_drag {
+            persistent.seat.set_touch_long_press_drag(enabled);
+        }
+    }
     if let Some(keymap) = config.keymap {
         state.set_keymap(&keymap);
     }
@@ -1701,6 +2085,7 @@ fn load_config(initial_load: bool, auto_reload: bool, persistent: &Rc<Persistent
             for ws in &state.workspaces {
                 ws.handle_connector_connected(&state, c);
             }
+            state.apply_matching_output_profile(None);
         }
     });
     on_connector_disconnected({
@@ -1710,8 +2095,15 @@ fn load_config(initial_load: bool, auto_reload: bool, persistent: &Rc<Persistent
             for ws in &state.workspaces {
                 ws.handle_connector_disconnected(&state, c);
             }
+            state.apply_matching_output_profile(Some(c));
         }
     });
+    on_apply_output_profile({
+        let state = state.clone();
+        move |name| state.apply_output_profile_by_name(&name)
+    });
+    state.publish_output_profiles();
+    state.apply_matching_output_profile(None);
     set_default_workspace_capture(config.workspace_capture);
     for (k, v) in config.env {
         set_env(&k, &v);
@@ -1795,10 +2187,12 @@ fn load_config(initial_load: bool, auto_reload: bool, persistent: &Rc<Persistent
     on_new_input_device({
         let state = state.clone();
         let switch_actions = switch_actions.clone();
+        let pad_actions = pad_actions.clone();
         move |c| {
             state.add_io_input(c);
             apply_inputs(c);
             state.handle_switch_device(c, &switch_actions);
+            state.handle_tablet_pad_device(c, &pad_actions);
         }
     });
     on_input_device_removed({
@@ -1818,6 +2212,7 @@ fn load_config(initial_load: bool, auto_reload: bool, persistent: &Rc<Persistent
         state.add_io_input(c);
         state.map_input_to_output(c);
         state.handle_switch_device(c, &switch_actions);
+        state.handle_tablet_pad_device(c, &pad_actions);
     }
     persistent
         .seat
@@ -1844,6 +2239,9 @@ fn load_config(initial_load: bool, auto_reload: bool, persistent: &Rc<Persistent
         set_tearing_mode(mode);
     }
     set_libei_socket_enabled(config.libei.enable_socket.unwrap_or(false));
+    set_auto_scale(config.auto_scale);
+    set_backlight_indicator_enabled(config.show_backlight_indicator);
+    set_auto_rotation_enabled(config.auto_rotation);
     if let Some(enabled) = config.ui_drag.enabled {
         set_ui_drag_enabled(enabled);
     }
@@ -1891,6 +2289,14 @@ fn load_config(initial_load: bool, auto_reload: bool, persistent: &Rc<Persistent
     if let Some(v) = config.theme.bar_position {
         set_bar_position(v);
     }
+    if let Some(bar) = &config.bar {
+        if let Some(elements) = &bar.elements {
+            set_bar_elements(elements);
+        }
+        if let Some(separator) = &bar.separator {
+            set_bar_element_separator(separator);
+        }
+    }
     if let Some(v) = config.focus_history {
         if let Some(v) = v.only_visible {
             persistent.seat.focus_history_set_only_visible(v);
@@ -1910,9 +2316,70 @@ fn load_config(initial_load: bool, auto_reload: bool, persistent: &Rc<Persistent
             persistent.seat.set_simple_im_enabled(enabled);
         }
     }
+    let mut feedback_actions = AHashMap::new();
+    if let Some(ka) = config.keyboard_accessibility {
+        if let Some(v) = ka.sticky_keys {
+            persistent.seat.set_sticky_keys_enabled(v);
+        }
+        if let Some(v) = ka.slow_keys_delay {
+            persistent.seat.set_slow_keys_delay(v);
+        }
+        if let Some(v) = ka.slow_keys {
+            persistent.seat.set_slow_keys_enabled(v);
+        }
+        if let Some(v) = ka.bounce_keys_delay {
+            persistent.seat.set_bounce_keys_delay(v);
+        }
+        if let Some(v) = ka.bounce_keys {
+            persistent.seat.set_bounce_keys_enabled(v);
+        }
+        for (feedback, action) in ka.feedback_actions {
+            feedback_actions.insert(feedback, action.into_fn(&state));
+        }
+    }
+    persistent
+        .seat
+        .on_keyboard_accessibility_feedback(move |feedback| {
+            if let Some(action) = feedback_actions.get(&feedback) {
+                action();
+            }
+        });
+    if let Some(mk) = config.mouse_keys {
+        persistent.seat.set_mouse_keys(&mk.settings);
+        if let Some(v) = mk.enabled {
+            persistent.seat.set_mouse_keys_enabled(v);
+        }
+    }
+    if let Some(wk) = config.which_key {
+        if let Some(v) = wk.delay {
+            persistent.seat.set_which_key_delay(v);
+        }
+        if let Some(v) = wk.enabled {
+            persistent.seat.set_which_key_enabled(v);
+        }
+    }
+    if let Some(cursor) = config.cursor {
+        if let Some(v) = cursor.hide_while_typing {
+            persistent.seat.set_hide_cursor_while_typing(v);
+        }
+        if let Some(v) = cursor.hide_after_ms {
+            persistent
+                .seat
+                .set_cursor_hide_timeout(Duration::from_millis(v));
+        }
+    }
     if let Some(v) = config.fallback_output_mode {
         persistent.seat.set_fallback_output_mode(v);
     }
+    if let Some(v) = config.layout_memory {
+        persistent.seat.set_layout_memory_mode(v);
+    }
+    if let Some(v) = config.shortcuts_inhibit_escape {
+        persistent.seat.set_shortcuts_inhibit_escape(v);
+    }
+    if let Some(v) = config.input_capture_release {
+        persistent.seat.set_input_capture_release(v);
+    }
     if let Some(f) = &config.egui.proportional_fonts {
         set_egui_proportional_fonts(f.iter().map(|s| &**s));
     }
@@ -1936,6 +2403,9 @@ fn load_config(initial_load: bool, auto_reload: bool, persistent: &Rc<Persistent
     if let Some(v) = config.cursor_size {
         persistent.seat.set_cursor_size(v);
     }
+    if let Some(v) = &config.cursor_theme {
+        persistent.seat.set_cursor_theme(v);
+    }
     for trigger in &config.triggers {
         if let Some(trigger) = trigger.build(&state).upgrade() {
             trigger.check_active();
@@ -1977,6 +2447,8 @@ pub fn configure() {
         workspaces_with_initial_outputs: Default::default(),
         triggers: Default::default(),
         counters: Default::default(),
+        gestures: Default::default(),
+        touch_gestures: Default::default(),
     });
     {
         let p = persistent.clone();
@@ -1991,4 +2463,46 @@ pub fn configure() {
     load_config(true, false, &persistent);
 }
 
+/// Returns whether `toggle-dpms` turns the displays on.
+///
+/// If any display is on, all displays are turned off. Otherwise they are turned on.
+fn toggled_dpms(on: impl IntoIterator<Item = bool>) -> bool {
+    !on.into_iter().any(|on| on)
+}
+
+/// Assigns the connectors to the outputs of a profile.
+///
+/// If `exact` is true, every connector must be assigned and only outputs that are
+/// disabled by the profile may remain unassigned.
+fn assign_profile_outputs<'a, C: Copy>(
+    outputs: &'a [Output],
+    connectors: &mut Vec<C>,
+    assigned: &mut Vec<(&'a Output, C)>,
+    exact: bool,
+    matches: &impl Fn(&Output, C) -> bool,
+) -> bool {
+    let Some((output, rest)) = outputs.split_first() else {
+        return !exact || connectors.is_empty();
+    };
+    for idx in 0..connectors.len() {
+        let c = connectors[idx];
+        if !matches(output, c) {
+            continue;
+        }
+        connectors.swap_remove(idx);
+        assigned.push((output, c));
+        if assign_profile_outputs(rest, connectors, assigned, exact, matches) {
+            return true;
+        }
+        assigned.pop();
+        connectors.push(c);
+        let last = connectors.len() - 1;
+        connectors.swap(idx, last);
+    }
+    if !exact || output.enabled == Some(false) {
+        return assign_profile_outputs(rest, connectors, assigned, exact, matches);
+    }
+    false
+}
+
 config!(configure);
@@ -353,6 +353,9 @@ impl Rule for WindowRule {
         if let Some(tile_state) = self.initial_tile_state {
             matcher.set_initial_tile_state(tile_state);
         }
+        if let Some(inhibit) = self.shortcuts_inhibit {
+            matcher.set_shortcuts_inhibit(inhibit);
+        }
     }
 
     fn gen_matcher(m: Self::Matcher) -> Self::Criterion<'static> {
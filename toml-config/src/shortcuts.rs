@@ -1,12 +1,15 @@
+#[cfg(test)]
+mod tests;
+
 use crate::State;
 use crate::config::Action;
 use crate::config::InputMode;
 use crate::config::Shortcut;
+use crate::config::ShortcutTrigger;
 use crate::config::SimpleCommand;
 use ahash::AHashMap;
 use ahash::AHashSet;
 use derivative::Derivative;
-use jay_config::keyboard::ModifiedKeySym;
 use jay_config::keyboard::mods::Modifiers;
 use std::cell::Cell;
 use std::cell::RefCell;
@@ -16,10 +19,19 @@ use std::rc::Rc;
 #[derive(Default)]
 pub struct ModeState {
     latched: Cell<bool>,
-    stack: RefCell<Vec<Rc<ConvertedShortcuts>>>,
+    stack: RefCell<Vec<(String, Rc<ConvertedShortcuts>)>>,
     slots: RefCell<AHashMap<String, Rc<ModeSlot>>>,
     diffs: RefCell<AHashMap<[*const ConvertedShortcuts; 2], Rc<Vec<ModeDiff>>>>,
     current: RefCell<Rc<ConvertedShortcuts>>,
+    mouse_keys: RefCell<AHashMap<String, bool>>,
+    mouse_keys_active: Cell<bool>,
+    which_key: RefCell<AHashMap<String, Rc<Vec<WhichKeyEntry>>>>,
+}
+
+struct WhichKeyEntry {
+    trigger: ShortcutTrigger,
+    name: String,
+    description: String,
 }
 
 impl ModeState {
@@ -27,11 +39,13 @@ impl ModeState {
         self.slots.borrow_mut().clear();
         self.stack.borrow_mut().clear();
         self.diffs.borrow_mut().clear();
+        self.mouse_keys.borrow_mut().clear();
+        self.which_key.borrow_mut().clear();
         *self.current.borrow_mut() = Default::default();
     }
 }
 
-pub type ConvertedShortcuts = AHashMap<ModifiedKeySym, ConvertedShortcut>;
+pub type ConvertedShortcuts = AHashMap<ShortcutTrigger, ConvertedShortcut>;
 
 #[derive(Clone, Derivative)]
 #[derivative(PartialEq)]
@@ -50,13 +64,13 @@ pub struct ModeSlot {
 
 enum ModeDiff {
     Bind {
-        key: ModifiedKeySym,
+        key: ShortcutTrigger,
         mask: Modifiers,
         repeat: bool,
         allow_locked: bool,
         f: Rc<dyn Fn()>,
     },
-    Unbind(ModifiedKeySym),
+    Unbind(ShortcutTrigger),
 }
 
 impl State {
@@ -74,6 +88,8 @@ impl State {
         let state = &self.persistent.mode_state;
         state.slots.borrow_mut().clear();
         state.diffs.borrow_mut().clear();
+        state.mouse_keys.borrow_mut().clear();
+        state.which_key.borrow_mut().clear();
     }
 
     pub fn init_modes(
@@ -83,40 +99,47 @@ impl State {
     ) {
         let state = &self.persistent.mode_state;
         let base = self.convert_shortcuts(shortcuts);
-        let stack = &mut *state.stack.borrow_mut();
-        stack.clear();
-        stack.push(base.clone());
+        {
+            let stack = &mut *state.stack.borrow_mut();
+            stack.clear();
+            stack.push((String::new(), base.clone()));
+        }
         self.convert_modes(&base, modes);
         self.apply_shortcuts(&base);
         state.latched.set(false);
+        self.send_mode_stack();
     }
 
-    pub fn set_mode(&self, new: &Rc<ConvertedShortcuts>, latch: bool) {
+    pub fn set_mode(&self, name: &str, new: &Rc<ConvertedShortcuts>, latch: bool) {
         let state = &self.persistent.mode_state;
         self.cancel_mode_latch();
         self.apply_shortcuts(new);
-        let stack = &mut *state.stack.borrow_mut();
-        stack.push(new.clone());
+        state
+            .stack
+            .borrow_mut()
+            .push((name.to_string(), new.clone()));
         if latch {
             state.latched.set(true);
         }
+        self.send_mode_stack();
     }
 
     pub fn pop_mode(&self, pop: bool) {
         let state = &self.persistent.mode_state;
-        let stack = &mut *state.stack.borrow_mut();
-        if stack.len() < 1 + pop as usize {
+        if state.stack.borrow().len() < 1 + pop as usize {
             log::error!("Mode stack is empty");
             return;
         }
         self.cancel_mode_latch();
+        let stack = &mut *state.stack.borrow_mut();
         if pop {
             stack.pop();
         } else {
             stack.truncate(1);
         }
-        let new = stack.last().unwrap();
+        let (_, new) = stack.last().unwrap();
         self.apply_shortcuts(new);
+        self.send_mode_stack_(stack);
     }
 
     pub fn cancel_mode_latch(&self) {
@@ -130,8 +153,38 @@ impl State {
             return;
         }
         let _ = stack.pop();
-        let new = stack.last().unwrap();
+        let (_, new) = stack.last().unwrap();
         self.apply_shortcuts(new);
+        self.send_mode_stack_(stack);
+    }
+
+    fn send_mode_stack(&self) {
+        self.send_mode_stack_(&self.persistent.mode_state.stack.borrow());
+    }
+
+    fn send_mode_stack_(&self, stack: &[(String, Rc<ConvertedShortcuts>)]) {
+        let names: Vec<_> = stack.iter().skip(1).map(|(name, _)| &**name).collect();
+        self.persistent.seat.set_input_mode_stack(&names);
+        let state = &self.persistent.mode_state;
+        let mouse_keys = {
+            let modes = state.mouse_keys.borrow();
+            names
+                .iter()
+                .rev()
+                .find_map(|name| modes.get(*name).copied())
+        };
+        let mouse_keys = mouse_keys.unwrap_or_default();
+        if state.mouse_keys_active.replace(mouse_keys) != mouse_keys {
+            self.persistent.seat.set_mouse_keys_enabled(mouse_keys);
+        }
+        let title = names.last().copied().unwrap_or_default();
+        let which_key = state.which_key.borrow().get(title).cloned();
+        let entries: Vec<_> = which_key
+            .iter()
+            .flat_map(|e| e.iter())
+            .map(|e| (&*e.name, &*e.description))
+            .collect();
+        self.persistent.seat.set_which_key(title, &entries);
     }
 
     pub fn convert_modes(
@@ -178,6 +231,22 @@ impl State {
             }
         }
         let mut shortcuts = shortcuts.unwrap_or_else(|| base.clone());
+        {
+            let mouse_keys = &mut *self.persistent.mode_state.mouse_keys.borrow_mut();
+            let inherited = mode
+                .parent
+                .as_ref()
+                .and_then(|p| mouse_keys.get(p).copied());
+            if let Some(v) = mode.mouse_keys.or(inherited) {
+                mouse_keys.insert(mode_name.clone(), v);
+            }
+        }
+        {
+            let which_key = &mut *self.persistent.mode_state.which_key.borrow_mut();
+            let parent = mode.parent.as_ref().and_then(|p| which_key.get(p));
+            let entries = which_key_entries(parent.map(|p| &***p), &mode.shortcuts);
+            which_key.insert(mode_name.clone(), Rc::new(entries));
+        }
         self.convert_shortcuts_(&mode.shortcuts, &mut shortcuts);
         let shortcuts = Rc::new(shortcuts);
         *self.get_mode_slot(mode_name).mode.borrow_mut() = Some(shortcuts.clone());
@@ -201,18 +270,14 @@ impl State {
     ) {
         for sc in shortcuts {
             match self.convert_shortcut(sc.clone()) {
-                None => dst.remove(&sc.keysym),
-                Some(cs) => dst.insert(sc.keysym, cs),
+                None => dst.remove(&sc.trigger),
+                Some(cs) => dst.insert(sc.trigger, cs),
             };
         }
     }
 
     fn convert_shortcut(self: &Rc<Self>, shortcut: Shortcut) -> Option<ConvertedShortcut> {
-        if let Action::SimpleCommand {
-            cmd: SimpleCommand::None,
-        } = shortcut.action
-            && shortcut.latch.is_none()
-        {
+        if is_unbind(&shortcut) {
             return None;
         }
         let mut f = shortcut.action.into_shortcut_fn(self);
@@ -248,17 +313,25 @@ impl State {
                     f,
                 } => {
                     let f = f.clone();
-                    seat.bind_masked(*mask, *key, move || f());
-                    if *repeat {
-                        seat.set_repeat_bind(*key, true);
-                    }
-                    if *allow_locked {
-                        seat.set_bind_allow_locked(*key, true);
+                    match *key {
+                        ShortcutTrigger::Key(key) => {
+                            seat.bind_masked(*mask, key, move || f());
+                            if *repeat {
+                                seat.set_repeat_bind(key, true);
+                            }
+                            if *allow_locked {
+                                seat.set_bind_allow_locked(key, true);
+                            }
+                        }
+                        ShortcutTrigger::Pointer(trigger) => {
+                            seat.bind_pointer(trigger, move || f());
+                        }
                     }
                 }
-                ModeDiff::Unbind(key) => {
-                    seat.unbind(*key);
-                }
+                ModeDiff::Unbind(key) => match *key {
+                    ShortcutTrigger::Key(key) => seat.unbind(key),
+                    ShortcutTrigger::Pointer(trigger) => seat.unbind_pointer(trigger),
+                },
             }
         }
         *current = new.clone();
@@ -296,3 +369,39 @@ impl State {
         }
     }
 }
+
+fn which_key_entries(
+    parent: Option<&[WhichKeyEntry]>,
+    shortcuts: &[Shortcut],
+) -> Vec<WhichKeyEntry> {
+    let mut entries = vec![];
+    if let Some(parent) = parent {
+        entries.extend(parent.iter().map(|e| WhichKeyEntry {
+            trigger: e.trigger,
+            name: e.name.clone(),
+            description: e.description.clone(),
+        }));
+    }
+    for sc in shortcuts {
+        entries.retain(|e| e.trigger != sc.trigger);
+        if is_unbind(sc) {
+            continue;
+        }
+        entries.push(WhichKeyEntry {
+            trigger: sc.trigger,
+            name: sc.name.clone(),
+            description: sc.description.clone().unwrap_or_default(),
+        });
+    }
+    entries
+}
+
+fn is_unbind(shortcut: &Shortcut) -> bool {
+    let is_none = matches!(
+        shortcut.action,
+        Action::SimpleCommand {
+            cmd: SimpleCommand::None,
+        }
+    );
+    is_none && shortcut.latch.is_none()
+}
@@ -0,0 +1,77 @@
+use crate::config::parse_test_config;
+use crate::shortcuts::WhichKeyEntry;
+use crate::shortcuts::which_key_entries;
+
+const CONFIG: &str = r#"
+[modes."windows".shortcuts]
+h = "focus-left"
+l = { type = "exec", exec = "foot" }
+x = "quit"
+
+[modes."windows".complex-shortcuts]
+q = { action = "close", description = "Close the window" }
+
+[modes."resize"]
+parent = "windows"
+
+[modes."resize".shortcuts]
+h = "focus-right"
+
+[modes."resize".complex-shortcuts]
+x = {}
+"#;
+
+fn entries(mode: &str, parent: Option<&[WhichKeyEntry]>) -> Vec<WhichKeyEntry> {
+    let config = parse_test_config(CONFIG);
+    which_key_entries(parent, &config.input_modes[mode].shortcuts)
+}
+
+fn names(entries: &[WhichKeyEntry]) -> Vec<(&str, &str)> {
+    let mut names: Vec<_> = entries
+        .iter()
+        .map(|e| (&*e.name, &*e.description))
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn descriptions() {
+    let windows = entries("windows", None);
+    assert_eq!(
+        names(&windows),
+        [
+            ("h", "focus-left"),
+            ("l", "exec"),
+            ("q", "Close the window"),
+            ("x", "quit"),
+        ],
+    );
+}
+
+#[test]
+fn inherited() {
+    let windows = entries("windows", None);
+    let resize = entries("resize", Some(&windows));
+    // `h` is overridden by the mode and `x` is unbound.
+    assert_eq!(
+        names(&resize),
+        [
+            ("h", "focus-right"),
+            ("l", "exec"),
+            ("q", "Close the window")
+        ],
+    );
+}
+
+#[test]
+fn settings() {
+    let config = parse_test_config("which-key = { enabled = true, delay = 250 }\n");
+    let which_key = config.which_key.unwrap();
+    assert_eq!(which_key.enabled, Some(true));
+    assert_eq!(which_key.delay, Some(250));
+    let config = parse_test_config("which-key = { delay = -1 }\n");
+    let which_key = config.which_key.unwrap();
+    assert_eq!(which_key.enabled, None);
+    assert_eq!(which_key.delay, None);
+}
@@ -0,0 +1,146 @@
+use crate::assign_profile_outputs;
+use crate::config::Action;
+use crate::config::Output;
+use crate::config::OutputMatch;
+use crate::config::SimpleCommand;
+use crate::config::parse_test_config;
+use crate::toggled_dpms;
+
+fn profile(outputs: &str) -> Vec<Output> {
+    let config = parse_test_config(&format!(
+        "[[output-profiles]]\nname = \"test\"\noutputs = [{outputs}]\n"
+    ));
+    config.output_profiles.into_iter().next().unwrap().outputs
+}
+
+fn matches(output: &Output, connector: &str) -> bool {
+    match &output.match_ {
+        OutputMatch::All {
+            connector: Some(c), ..
+        } => c == connector,
+        OutputMatch::All {
+            connector: None, ..
+        } => true,
+        OutputMatch::Any(_) => false,
+    }
+}
+
+fn assign(
+    outputs: &[Output],
+    connectors: &[&'static str],
+    exact: bool,
+) -> Option<Vec<(Option<i32>, &'static str)>> {
+    let mut connectors = connectors.to_vec();
+    let mut assigned = vec![];
+    if !assign_profile_outputs(outputs, &mut connectors, &mut assigned, exact, &matches) {
+        return None;
+    }
+    let mut res: Vec<_> = assigned.iter().map(|(o, c)| (o.x, *c)).collect();
+    res.sort();
+    Some(res)
+}
+
+#[test]
+fn exact() {
+    let outputs = profile(
+        r#"
+        { match.connector = "eDP-1", x = 0 },
+        { match.connector = "DP-1", x = 1 },
+        "#,
+    );
+    assert_eq!(
+        assign(&outputs, &["DP-1", "eDP-1"], true),
+        Some(vec![(Some(0), "eDP-1"), (Some(1), "DP-1")]),
+    );
+    // All connected connectors must be part of the profile.
+    assert_eq!(assign(&outputs, &["DP-1", "eDP-1", "HDMI-A-1"], true), None);
+    // All enabled outputs of the profile must be connected.
+    assert_eq!(assign(&outputs, &["eDP-1"], true), None);
+}
+
+#[test]
+fn disabled_outputs_may_be_missing() {
+    let outputs = profile(
+        r#"
+        { match.connector = "eDP-1", x = 0, enabled = false },
+        { match.connector = "DP-1", x = 1 },
+        "#,
+    );
+    assert_eq!(
+        assign(&outputs, &["DP-1"], true),
+        Some(vec![(Some(1), "DP-1")]),
+    );
+    assert_eq!(
+        assign(&outputs, &["DP-1", "eDP-1"], true),
+        Some(vec![(Some(0), "eDP-1"), (Some(1), "DP-1")]),
+    );
+}
+
+#[test]
+fn backtracking() {
+    // The first output matches any connector. The matcher must not assign DP-1 to it
+    // since DP-1 is the only connector that matches the second output.
+    let outputs = profile(
+        r#"
+        { match = {}, x = 0 },
+        { match.connector = "DP-1", x = 1 },
+        "#,
+    );
+    assert_eq!(
+        assign(&outputs, &["DP-1", "eDP-1"], true),
+        Some(vec![(Some(0), "eDP-1"), (Some(1), "DP-1")]),
+    );
+}
+
+#[test]
+fn partial() {
+    let outputs = profile(
+        r#"
+        { match.connector = "eDP-1", x = 0 },
+        { match.connector = "DP-1", x = 1 },
+        "#,
+    );
+    // When a profile is applied manually, missing outputs and extra connectors are
+    // ignored.
+    assert_eq!(
+        assign(&outputs, &["DP-1", "HDMI-A-1"], false),
+        Some(vec![(Some(1), "DP-1")]),
+    );
+    assert_eq!(assign(&outputs, &[], false), Some(vec![]));
+}
+
+#[test]
+fn toggle_dpms() {
+    assert!(toggled_dpms([false, false]));
+    assert!(!toggled_dpms([true, false]));
+    assert!(!toggled_dpms([true, true]));
+    assert!(toggled_dpms([]));
+}
+
+#[test]
+fn dpms_actions() {
+    let config = parse_test_config(
+        r#"
+        [shortcuts]
+        alt-a = "dpms-on"
+        alt-b = "dpms-off"
+        alt-c = "toggle-dpms"
+        "#,
+    );
+    let cmds: Vec<_> = config
+        .shortcuts
+        .iter()
+        .map(|s| match &s.action {
+            Action::SimpleCommand { cmd } => *cmd,
+            a => panic!("unexpected action {a:?}"),
+        })
+        .collect();
+    assert!(matches!(
+        &*cmds,
+        [
+            SimpleCommand::SetDpms(true),
+            SimpleCommand::SetDpms(false),
+            SimpleCommand::ToggleDpms,
+        ],
+    ));
+}